@@ -0,0 +1,32 @@
+//! Background driver for the arbitrage monitor
+//!
+//! Polls at roughly block cadence; the service evaluates each new block once
+//! and only while the monitor is enabled. Opportunities are emitted as
+//! `arb-opportunity` events, the best circular one with its chain pre-built.
+
+use citadel_api::services::arb_monitor as arb_monitor_svc;
+use citadel_api::AppState;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often the monitor checks for a new block (seconds).
+const POLL_INTERVAL_SECS: u64 = 30;
+
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(POLL_INTERVAL_SECS)).await;
+
+            let state = app_handle.state::<AppState>();
+            match arb_monitor_svc::process_arb_monitor(&state).await {
+                Ok(alerts) => {
+                    for alert in alerts {
+                        if let Err(e) = app_handle.emit("arb-opportunity", alert) {
+                            tracing::warn!("Failed to emit arb opportunity: {}", e);
+                        }
+                    }
+                }
+                Err(e) => tracing::debug!("Arb monitor pass skipped: {}", e),
+            }
+        }
+    });
+}
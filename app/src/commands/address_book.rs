@@ -0,0 +1,53 @@
+use citadel_api::dto::{AddressBookEntry, AddressInfo, OutputRecipientInfo};
+use citadel_api::services::address_book as address_book_svc;
+use citadel_api::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_address_book(
+    state: State<'_, AppState>,
+) -> Result<Vec<AddressBookEntry>, String> {
+    address_book_svc::list_address_book(&state).await
+}
+
+#[tauri::command]
+pub async fn save_address_book_entry(
+    state: State<'_, AppState>,
+    entry: AddressBookEntry,
+) -> Result<Vec<AddressBookEntry>, String> {
+    address_book_svc::save_address_book_entry(&state, entry).await
+}
+
+#[tauri::command]
+pub async fn remove_address_book_entry(
+    state: State<'_, AppState>,
+    address: String,
+) -> Result<Vec<AddressBookEntry>, String> {
+    address_book_svc::remove_address_book_entry(&state, &address).await
+}
+
+#[tauri::command]
+pub async fn describe_address(
+    state: State<'_, AppState>,
+    address: String,
+) -> Result<AddressInfo, String> {
+    address_book_svc::describe_address(&state, &address).await
+}
+
+#[tauri::command]
+pub async fn describe_addresses(
+    state: State<'_, AppState>,
+    addresses: Vec<String>,
+) -> Result<Vec<AddressInfo>, String> {
+    address_book_svc::describe_addresses(&state, addresses).await
+}
+
+#[tauri::command]
+pub async fn describe_tx_outputs(
+    state: State<'_, AppState>,
+    unsigned_tx: serde_json::Value,
+) -> Result<Vec<OutputRecipientInfo>, String> {
+    let tx: ergo_tx::Eip12UnsignedTx = serde_json::from_value(unsigned_tx)
+        .map_err(|e| format!("Invalid unsigned transaction: {}", e))?;
+    address_book_svc::describe_tx_outputs(&state, &tx).await
+}
@@ -3,10 +3,12 @@
 use citadel_api::dto::{AmmPoolsResponse, SwapQuoteResponse};
 use citadel_api::services::amm::{
     self as amm_svc, AmmLpBuildResponse, AmmLpDepositPreviewResponse, AmmLpRedeemPreviewResponse,
-    ArbChainBuildResponse, ArbChainSubmitResponse, ArbLegSignResponse, CircularArbSnapshot,
-    DepthTiers, DirectSwapBuildResponse, DirectSwapPreviewResponse, MempoolSwapDto,
-    OracleArbSnapshot, PendingOrderDto, PoolCreatePreviewResponse, SplitAllocationInput,
-    SplitChainBuildResponse, SwapBuildResponse, SwapChainBuildResponse, SwapPreviewResponse,
+    ArbChainBuildResponse, ArbChainSubmitResponse, ArbLegSignResponse, BulkRefundBuildResponse,
+    CircularArbSnapshot, DepthTiers, DirectSwapBuildResponse, DirectSwapPreviewResponse,
+    MempoolSwapDto, OpenOrdersResponse, OracleArbSnapshot, PendingOrderDto, PoolAnalytics,
+    PoolCreatePreviewResponse, PoolLaunchBuildResponse, PoolLaunchPreviewResponse,
+    PoolLaunchRequest, SplitAllocationInput, SplitChainBuildResponse, SwapBuildResponse,
+    SwapChainBuildResponse, SwapPreviewResponse,
 };
 use citadel_api::AppState;
 use tauri::State;
@@ -23,8 +25,17 @@ pub async fn get_amm_quote(
     input_type: String,
     amount: u64,
     token_id: Option<String>,
+    include_mempool: Option<bool>,
 ) -> Result<SwapQuoteResponse, String> {
-    amm_svc::get_amm_quote(&state, &pool_id, &input_type, amount, token_id).await
+    amm_svc::get_amm_quote(
+        &state,
+        &pool_id,
+        &input_type,
+        amount,
+        token_id,
+        include_mempool,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -91,8 +102,18 @@ pub async fn preview_direct_swap(
     amount: u64,
     token_id: Option<String>,
     slippage: Option<f64>,
+    include_mempool: Option<bool>,
 ) -> Result<DirectSwapPreviewResponse, String> {
-    amm_svc::preview_direct_swap(&state, &pool_id, &input_type, amount, token_id, slippage).await
+    amm_svc::preview_direct_swap(
+        &state,
+        &pool_id,
+        &input_type,
+        amount,
+        token_id,
+        slippage,
+        include_mempool,
+    )
+    .await
 }
 
 #[tauri::command]
@@ -109,6 +130,7 @@ pub async fn build_direct_swap_tx(
     recipient_address: Option<String>,
     // Optional custom miner fee in nanoERG. None = network default.
     miner_fee_nano: Option<u64>,
+    include_mempool: Option<bool>,
 ) -> Result<DirectSwapBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
     amm_svc::build_direct_swap_tx(
@@ -122,6 +144,7 @@ pub async fn build_direct_swap_tx(
         current_height,
         recipient_address,
         miner_fee_nano,
+        include_mempool,
     )
     .await
 }
@@ -147,6 +170,23 @@ pub async fn build_swap_refund_tx(
     amm_svc::build_swap_refund_tx(&state, box_id, user_ergo_tree).await
 }
 
+#[tauri::command]
+pub async fn scan_open_orders(
+    state: State<'_, AppState>,
+    max_age_blocks: Option<u32>,
+) -> Result<OpenOrdersResponse, String> {
+    amm_svc::scan_open_orders(&state, max_age_blocks).await
+}
+
+#[tauri::command]
+pub async fn build_bulk_refund_tx(
+    state: State<'_, AppState>,
+    box_ids: Vec<String>,
+    user_ergo_tree: String,
+) -> Result<BulkRefundBuildResponse, String> {
+    amm_svc::build_bulk_refund_tx(&state, box_ids, user_ergo_tree).await
+}
+
 #[tauri::command]
 pub async fn preview_amm_lp_deposit(
     state: State<'_, AppState>,
@@ -321,6 +361,28 @@ pub async fn build_pool_create_tx(
     )
 }
 
+/// Plan a pool launch anchored at a price, listing pools already trading the pair.
+#[tauri::command]
+pub async fn preview_pool_launch(
+    state: State<'_, AppState>,
+    request: PoolLaunchRequest,
+) -> Result<PoolLaunchPreviewResponse, String> {
+    amm_svc::preview_pool_launch(&state, request).await
+}
+
+/// Bootstrap + create as a 0-conf chain; legs are signed with
+/// `start_arb_leg_sign` and broadcast with `submit_arb_chain`.
+#[tauri::command]
+pub async fn build_pool_launch_tx(
+    state: State<'_, AppState>,
+    request: PoolLaunchRequest,
+    user_utxos: Vec<serde_json::Value>,
+    current_height: i32,
+) -> Result<PoolLaunchBuildResponse, String> {
+    let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
+    amm_svc::build_pool_launch_tx(&state, request, parsed_utxos, current_height).await
+}
+
 #[tauri::command]
 pub async fn find_swap_routes(
     state: State<'_, AppState>,
@@ -331,6 +393,7 @@ pub async fn find_swap_routes(
     max_routes: Option<usize>,
     slippage: Option<f64>,
     min_rate: Option<f64>,
+    include_mempool: Option<bool>,
 ) -> Result<serde_json::Value, String> {
     amm_svc::find_swap_routes(
         &state,
@@ -341,6 +404,7 @@ pub async fn find_swap_routes(
         max_routes,
         slippage,
         min_rate,
+        include_mempool,
     )
     .await
 }
@@ -381,6 +445,15 @@ pub async fn get_liquidity_depth(
     amm_svc::get_liquidity_depth(&state, &source_token).await
 }
 
+#[tauri::command]
+pub async fn get_pool_analytics(
+    state: State<'_, AppState>,
+    pool_id: String,
+    max_steps: Option<usize>,
+) -> Result<PoolAnalytics, String> {
+    amm_svc::get_pool_analytics(&state, &pool_id, max_steps).await
+}
+
 #[tauri::command]
 pub async fn get_sigusd_arb_snapshot(
     state: State<'_, AppState>,
@@ -398,6 +471,7 @@ pub async fn find_swap_routes_by_output(
     max_hops: Option<usize>,
     max_routes: Option<usize>,
     slippage: Option<f64>,
+    include_mempool: Option<bool>,
 ) -> Result<serde_json::Value, String> {
     amm_svc::find_swap_routes_by_output(
         &state,
@@ -407,6 +481,7 @@ pub async fn find_swap_routes_by_output(
         max_hops,
         max_routes,
         slippage,
+        include_mempool,
     )
     .await
 }
@@ -0,0 +1,28 @@
+use citadel_api::dto::{ArbMonitorConfig, ArbMonitorResponse};
+use citadel_api::services::arb_monitor::{self as arb_monitor_svc, ArbAlert};
+use citadel_api::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_arb_monitor(state: State<'_, AppState>) -> Result<ArbMonitorResponse, String> {
+    arb_monitor_svc::get_arb_monitor(&state).await
+}
+
+#[tauri::command]
+pub async fn set_arb_monitor_config(
+    state: State<'_, AppState>,
+    config: ArbMonitorConfig,
+) -> Result<ArbMonitorConfig, String> {
+    arb_monitor_svc::set_arb_monitor_config(&state, config).await
+}
+
+#[tauri::command]
+pub async fn clear_arb_monitor_history(state: State<'_, AppState>) -> Result<(), String> {
+    arb_monitor_svc::clear_arb_monitor_history(&state).await
+}
+
+/// Run a monitor pass now instead of waiting for the background poll.
+#[tauri::command]
+pub async fn process_arb_monitor(state: State<'_, AppState>) -> Result<Vec<ArbAlert>, String> {
+    arb_monitor_svc::process_arb_monitor(&state).await
+}
@@ -0,0 +1,21 @@
+use citadel_api::dto::{ContentionStatusResponse, TxInputCheckResponse};
+use citadel_api::services::contention as contention_svc;
+use citadel_api::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_contention_status(
+    state: State<'_, AppState>,
+    protocol: String,
+    key: Option<String>,
+) -> Result<ContentionStatusResponse, String> {
+    contention_svc::get_contention_status(&state, &protocol, key.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn check_tx_inputs(
+    state: State<'_, AppState>,
+    unsigned_tx: serde_json::Value,
+) -> Result<TxInputCheckResponse, String> {
+    contention_svc::check_tx_inputs(&state, &unsigned_tx).await
+}
@@ -1,7 +1,7 @@
 use citadel_api::dto::{
-    DexyBuildRequest, DexyBuildResponse, DexyLpBuildResponse, DexyLpPreviewResponse,
-    DexyPreviewRequest, DexyPreviewResponse, DexyStateResponse, DexySwapBuildResponse,
-    DexySwapPreviewResponse,
+    DexyBuildRequest, DexyBuildResponse, DexyKeeperBuildResponse, DexyKeeperStatusResponse,
+    DexyLpBuildResponse, DexyLpPreviewResponse, DexyPreviewRequest, DexyPreviewResponse,
+    DexyStateResponse, DexySwapBuildResponse, DexySwapPreviewResponse,
 };
 use citadel_api::services::dexy as dexy_svc;
 use citadel_api::AppState;
@@ -46,6 +46,8 @@ pub async fn build_mint_dexy(
         user_utxos,
         request.current_height,
         request.recipient_address,
+        request.mint_path.as_deref(),
+        request.build_on_pending,
     )
     .await
 }
@@ -72,6 +74,7 @@ pub async fn build_dexy_swap_tx(
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
     recipient_address: Option<String>,
+    build_on_pending: Option<bool>,
 ) -> Result<DexySwapBuildResponse, String> {
     let user_utxos = super::parse_eip12_utxos(user_utxos)?;
     dexy_svc::build_swap(
@@ -84,6 +87,7 @@ pub async fn build_dexy_swap_tx(
         user_utxos,
         current_height,
         recipient_address,
+        build_on_pending.unwrap_or(false),
     )
     .await
 }
@@ -108,6 +112,7 @@ pub async fn build_lp_deposit_tx(
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
     recipient_address: Option<String>,
+    build_on_pending: Option<bool>,
 ) -> Result<DexyLpBuildResponse, String> {
     let user_utxos = super::parse_eip12_utxos(user_utxos)?;
     dexy_svc::build_lp_deposit(
@@ -119,6 +124,7 @@ pub async fn build_lp_deposit_tx(
         user_utxos,
         current_height,
         recipient_address,
+        build_on_pending.unwrap_or(false),
     )
     .await
 }
@@ -141,6 +147,7 @@ pub async fn build_lp_redeem_tx(
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
     recipient_address: Option<String>,
+    build_on_pending: Option<bool>,
 ) -> Result<DexyLpBuildResponse, String> {
     let user_utxos = super::parse_eip12_utxos(user_utxos)?;
     dexy_svc::build_lp_redeem(
@@ -151,6 +158,27 @@ pub async fn build_lp_redeem_tx(
         user_utxos,
         current_height,
         recipient_address,
+        build_on_pending.unwrap_or(false),
     )
     .await
 }
+
+#[tauri::command]
+pub async fn get_dexy_keeper_status(
+    state: State<'_, AppState>,
+    variant: String,
+) -> Result<DexyKeeperStatusResponse, String> {
+    dexy_svc::get_keeper_status(&state, &variant).await
+}
+
+#[tauri::command]
+pub async fn build_dexy_keeper_tx(
+    state: State<'_, AppState>,
+    variant: String,
+    action: String,
+    user_utxos: Vec<serde_json::Value>,
+    current_height: i32,
+) -> Result<DexyKeeperBuildResponse, String> {
+    let user_utxos = super::parse_eip12_utxos(user_utxos)?;
+    dexy_svc::build_keeper_tx(&state, &variant, &action, user_utxos, current_height).await
+}
@@ -1,4 +1,5 @@
 use citadel_api::services::hodlcoin as hodl_svc;
+use citadel_api::services::hodlcoin_history;
 use citadel_api::AppState;
 use tauri::State;
 
@@ -9,13 +10,28 @@ pub async fn get_hodlcoin_banks(
     hodl_svc::get_banks(&state).await
 }
 
+#[tauri::command]
+pub async fn get_hodlcoin_discovery_config(
+    state: State<'_, AppState>,
+) -> Result<hodlcoin::DiscoveryOptions, String> {
+    hodl_svc::get_discovery_config(&state).await
+}
+
+#[tauri::command]
+pub async fn set_hodlcoin_discovery_config(
+    state: State<'_, AppState>,
+    options: hodlcoin::DiscoveryOptions,
+) -> Result<hodlcoin::DiscoveryOptions, String> {
+    hodl_svc::set_discovery_config(&state, options).await
+}
+
 #[tauri::command]
 pub async fn preview_hodlcoin_mint(
     state: State<'_, AppState>,
     singleton_token_id: String,
-    erg_amount: i64,
+    deposit_amount: i64,
 ) -> Result<hodlcoin::HodlMintPreview, String> {
-    hodl_svc::preview_mint(&state, &singleton_token_id, erg_amount).await
+    hodl_svc::preview_mint(&state, &singleton_token_id, deposit_amount).await
 }
 
 #[tauri::command]
@@ -31,20 +47,21 @@ pub async fn preview_hodlcoin_burn(
 pub async fn build_hodlcoin_mint_tx(
     state: State<'_, AppState>,
     singleton_token_id: String,
-    erg_amount: i64,
+    deposit_amount: i64,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+    build_on_pending: Option<bool>,
+) -> Result<hodl_svc::HodlBuildResponse, String> {
     let parsed = super::parse_eip12_utxos(user_utxos)?;
-    let tx = hodl_svc::build_mint_tx(
+    hodl_svc::build_mint_tx(
         &state,
         &singleton_token_id,
-        erg_amount,
+        deposit_amount,
         parsed,
         current_height,
+        build_on_pending.unwrap_or(false),
     )
-    .await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))
+    .await
 }
 
 #[tauri::command]
@@ -54,15 +71,60 @@ pub async fn build_hodlcoin_burn_tx(
     hodl_amount: i64,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+    build_on_pending: Option<bool>,
+) -> Result<hodl_svc::HodlBuildResponse, String> {
     let parsed = super::parse_eip12_utxos(user_utxos)?;
-    let tx = hodl_svc::build_burn_tx(
+    hodl_svc::build_burn_tx(
         &state,
         &singleton_token_id,
         hodl_amount,
         parsed,
         current_height,
+        build_on_pending.unwrap_or(false),
     )
-    .await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))
+    .await
+}
+
+#[tauri::command]
+pub async fn preview_hodlcoin_deploy(
+    params: hodlcoin::BankDeployParams,
+) -> Result<hodlcoin::BankDeployPlan, String> {
+    hodl_svc::preview_deploy(params)
+}
+
+/// Bootstrap + bank creation as a 0-conf chain; legs are signed with
+/// `start_arb_leg_sign` and broadcast with `submit_arb_chain`.
+#[tauri::command]
+pub async fn build_hodlcoin_deploy_tx(
+    params: hodlcoin::BankDeployParams,
+    user_utxos: Vec<serde_json::Value>,
+    current_height: i32,
+) -> Result<hodl_svc::HodlBankDeployResponse, String> {
+    let parsed = super::parse_eip12_utxos(user_utxos)?;
+    hodl_svc::build_deploy_tx(params, parsed, current_height)
+}
+
+#[tauri::command]
+pub async fn get_hodlcoin_history(
+    state: State<'_, AppState>,
+    singleton_token_id: String,
+    max_steps: Option<usize>,
+    sample_blocks: Option<u64>,
+    since_height: Option<u64>,
+) -> Result<hodlcoin::BankPriceHistory, String> {
+    hodlcoin_history::get_hodlcoin_history(
+        &state,
+        &singleton_token_id,
+        max_steps,
+        sample_blocks,
+        since_height,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_hodlcoin_positions(
+    state: State<'_, AppState>,
+) -> Result<hodlcoin_history::HodlPositionsResponse, String> {
+    hodlcoin_history::get_hodlcoin_positions(&state).await
 }
@@ -26,7 +26,7 @@ pub async fn mewlock_build_lock(
     lock_description: Option<String>,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<mewlock_svc::MewLockBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
 
     let lock_erg: u64 = lock_erg
@@ -43,7 +43,7 @@ pub async fn mewlock_build_lock(
 
     let timestamp: Option<i64> = timestamp.and_then(|ts| ts.parse().ok());
 
-    let tx = mewlock_svc::build_lock(
+    mewlock_svc::build_lock(
         user_ergo_tree,
         lock_erg,
         lock_tokens,
@@ -53,8 +53,7 @@ pub async fn mewlock_build_lock(
         lock_description,
         parsed_utxos,
         current_height,
-    )?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize tx: {}", e))
+    )
 }
 
 #[tauri::command]
@@ -64,16 +63,15 @@ pub async fn mewlock_build_unlock(
     user_ergo_tree: String,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<mewlock_svc::MewLockBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
 
-    let tx = mewlock_svc::build_unlock(
+    mewlock_svc::build_unlock(
         &state,
         &box_id,
         user_ergo_tree,
         parsed_utxos,
         current_height,
     )
-    .await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize tx: {}", e))
+    .await
 }
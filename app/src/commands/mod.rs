@@ -5,36 +5,50 @@
 #![allow(clippy::too_many_arguments)]
 
 mod activity;
+mod address_book;
 mod amm;
+mod arb_monitor;
 mod burn;
+mod contention;
 mod dexy;
 mod explorer;
 mod hodlcoin;
 mod lending;
 mod mewlock;
 mod node;
+mod oracle;
+mod orders;
 mod sigmafi;
 mod sigmausd;
 mod signing;
+mod simulator;
 mod stake_recovery;
+mod tokens;
 mod utxo;
 mod wallet;
 
 // Wildcard re-exports needed so tauri::generate_handler! can see
 // the hidden __cmd__* symbols generated by #[tauri::command].
 pub use activity::*;
+pub use address_book::*;
 pub use amm::*;
+pub use arb_monitor::*;
 pub use burn::*;
+pub use contention::*;
 pub use dexy::*;
 pub use explorer::*;
 pub use hodlcoin::*;
 pub use lending::*;
 pub use mewlock::*;
 pub use node::*;
+pub use oracle::*;
+pub use orders::*;
 pub use sigmafi::*;
 pub use sigmausd::*;
 pub use signing::*;
+pub use simulator::*;
 pub use stake_recovery::*;
+pub use tokens::*;
 pub use utxo::*;
 pub use wallet::*;
 
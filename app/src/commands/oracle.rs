@@ -0,0 +1,27 @@
+use citadel_api::dto::{OracleCheck, OracleFeed, OracleGuardConfig};
+use citadel_api::services::oracle as oracle_svc;
+use citadel_api::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_oracle_guard_config(
+    state: State<'_, AppState>,
+) -> Result<OracleGuardConfig, String> {
+    oracle_svc::get_oracle_guard_config(&state).await
+}
+
+#[tauri::command]
+pub async fn set_oracle_guard_config(
+    state: State<'_, AppState>,
+    config: OracleGuardConfig,
+) -> Result<OracleGuardConfig, String> {
+    oracle_svc::set_oracle_guard_config(&state, config).await
+}
+
+#[tauri::command]
+pub async fn check_oracle(
+    state: State<'_, AppState>,
+    feed: OracleFeed,
+) -> Result<OracleCheck, String> {
+    oracle_svc::check_oracle(&state, feed).await
+}
@@ -0,0 +1,48 @@
+use citadel_api::dto::{CreateSwapOrderRequest, SwapOrder};
+use citadel_api::services::orders as orders_svc;
+use citadel_api::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub async fn list_swap_orders(state: State<'_, AppState>) -> Result<Vec<SwapOrder>, String> {
+    orders_svc::list_swap_orders(&state).await
+}
+
+#[tauri::command]
+pub async fn create_swap_order(
+    state: State<'_, AppState>,
+    request: CreateSwapOrderRequest,
+) -> Result<SwapOrder, String> {
+    orders_svc::create_swap_order(&state, request).await
+}
+
+#[tauri::command]
+pub async fn cancel_swap_order(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<SwapOrder>, String> {
+    orders_svc::cancel_swap_order(&state, &id).await
+}
+
+#[tauri::command]
+pub async fn skip_swap_order_execution(
+    state: State<'_, AppState>,
+    id: String,
+) -> Result<Vec<SwapOrder>, String> {
+    orders_svc::skip_swap_order_execution(&state, &id).await
+}
+
+#[tauri::command]
+pub async fn mark_swap_order_submitted(
+    state: State<'_, AppState>,
+    id: String,
+    tx_id: String,
+) -> Result<SwapOrder, String> {
+    orders_svc::mark_swap_order_submitted(&state, &id, &tx_id).await
+}
+
+/// Run one engine pass now instead of waiting for the background poll.
+#[tauri::command]
+pub async fn process_swap_orders(state: State<'_, AppState>) -> Result<Vec<SwapOrder>, String> {
+    orders_svc::process_swap_orders(&state).await
+}
@@ -29,7 +29,7 @@ pub async fn sigmafi_build_open_order(
     collateral_tokens_json: String,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<sigmafi_svc::SigmaFiBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
 
     let principal: u64 = principal
@@ -50,7 +50,7 @@ pub async fn sigmafi_build_open_order(
                 .map_err(|e| format!("Invalid collateral tokens JSON: {}", e))?
         };
 
-    let tx = sigmafi_svc::build_open_order(
+    sigmafi_svc::build_open_order(
         borrower_ergo_tree,
         loan_token_id,
         principal,
@@ -60,8 +60,7 @@ pub async fn sigmafi_build_open_order(
         collateral_tokens,
         parsed_utxos,
         current_height,
-    )?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize tx: {}", e))
+    )
 }
 
 /// Build a cancel order transaction (borrower withdraws unfilled order)
@@ -72,18 +71,17 @@ pub async fn sigmafi_build_cancel_order(
     borrower_ergo_tree: String,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<sigmafi_svc::SigmaFiBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
 
-    let tx = sigmafi_svc::build_cancel_order(
+    sigmafi_svc::build_cancel_order(
         &state,
         &box_id,
         borrower_ergo_tree,
         parsed_utxos,
         current_height,
     )
-    .await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize tx: {}", e))
+    .await
 }
 
 /// Build a close order transaction (lender fills an order, creating a bond)
@@ -96,10 +94,10 @@ pub async fn sigmafi_build_close_order(
     loan_token_id: String,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<sigmafi_svc::SigmaFiBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
 
-    let tx = sigmafi_svc::build_close_order(
+    sigmafi_svc::build_close_order(
         &state,
         &box_id,
         lender_ergo_tree,
@@ -108,8 +106,7 @@ pub async fn sigmafi_build_close_order(
         parsed_utxos,
         current_height,
     )
-    .await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize tx: {}", e))
+    .await
 }
 
 /// Build a repay transaction (borrower repays loan before maturity)
@@ -121,10 +118,10 @@ pub async fn sigmafi_build_repay(
     borrower_ergo_tree: String,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<sigmafi_svc::SigmaFiBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
 
-    let tx = sigmafi_svc::build_repay(
+    sigmafi_svc::build_repay(
         &state,
         &box_id,
         loan_token_id,
@@ -132,8 +129,7 @@ pub async fn sigmafi_build_repay(
         parsed_utxos,
         current_height,
     )
-    .await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize tx: {}", e))
+    .await
 }
 
 /// Build a liquidate transaction (lender claims collateral after maturity)
@@ -144,16 +140,15 @@ pub async fn sigmafi_build_liquidate(
     lender_ergo_tree: String,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<sigmafi_svc::SigmaFiBuildResponse, String> {
     let parsed_utxos = super::parse_eip12_utxos(user_utxos)?;
 
-    let tx = sigmafi_svc::build_liquidate(
+    sigmafi_svc::build_liquidate(
         &state,
         &box_id,
         lender_ergo_tree,
         parsed_utxos,
         current_height,
     )
-    .await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize tx: {}", e))
+    .await
 }
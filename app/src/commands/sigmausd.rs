@@ -1,11 +1,13 @@
 use citadel_api::dto::{
     MintBuildRequest, MintBuildResponse, MintPreviewRequest, MintPreviewResponse,
-    OraclePriceResponse, SigmaUsdBuildRequest, SigmaUsdBuildResponse, SigmaUsdPreviewRequest,
-    SigmaUsdPreviewResponse,
+    OraclePriceResponse, SigmaUsdAdviceResponse, SigmaUsdBuildRequest, SigmaUsdBuildResponse,
+    SigmaUsdPreviewRequest, SigmaUsdPreviewResponse,
 };
 use citadel_api::services::sigmausd as sigmausd_svc;
+use citadel_api::services::sigmausd_advisor;
+use citadel_api::services::sigmausd_history;
 use citadel_api::AppState;
-use sigmausd::SigmaUsdState;
+use sigmausd::{BankHistory, SigmaUsdState};
 use tauri::State;
 
 #[tauri::command]
@@ -64,6 +66,26 @@ pub async fn build_sigmausd_tx(
         user_utxos,
         request.current_height,
         request.recipient_address,
+        request.build_on_pending,
     )
     .await
 }
+
+#[tauri::command]
+pub async fn get_sigmausd_history(
+    state: State<'_, AppState>,
+    max_steps: Option<usize>,
+    sample_blocks: Option<u64>,
+    since_height: Option<u64>,
+) -> Result<BankHistory, String> {
+    sigmausd_history::get_sigmausd_history(&state, max_steps, sample_blocks, since_height).await
+}
+
+#[tauri::command]
+pub async fn get_sigmausd_advice(
+    state: State<'_, AppState>,
+    action: Option<String>,
+    amount: Option<i64>,
+) -> Result<SigmaUsdAdviceResponse, String> {
+    sigmausd_advisor::get_sigmausd_advice(&state, action, amount).await
+}
@@ -0,0 +1,22 @@
+use citadel_api::dto::{DexySimRequest, SigmaUsdSimRequest};
+use citadel_api::services::simulator as simulator_svc;
+use citadel_api::AppState;
+use dexy::DexySimulationResult;
+use sigmausd::SimulationResult;
+use tauri::State;
+
+#[tauri::command]
+pub async fn simulate_sigmausd(
+    state: State<'_, AppState>,
+    request: SigmaUsdSimRequest,
+) -> Result<Vec<SimulationResult>, String> {
+    simulator_svc::simulate_sigmausd(&state, request).await
+}
+
+#[tauri::command]
+pub async fn simulate_dexy(
+    state: State<'_, AppState>,
+    request: DexySimRequest,
+) -> Result<Vec<DexySimulationResult>, String> {
+    simulator_svc::simulate_dexy(&state, request).await
+}
@@ -23,10 +23,9 @@ pub async fn build_recovery_tx(
     stake_key_id: String,
     user_utxos: Vec<serde_json::Value>,
     current_height: i32,
-) -> Result<serde_json::Value, String> {
+) -> Result<stake_svc::RecoveryBuildResponse, String> {
     let parsed = super::parse_eip12_utxos(user_utxos)?;
-    let tx = stake_svc::build_recovery_tx(&state, &stake_key_id, parsed, current_height).await?;
-    serde_json::to_value(&tx).map_err(|e| format!("Failed to serialize transaction: {}", e))
+    stake_svc::build_recovery_tx(&state, &stake_key_id, parsed, current_height).await
 }
 
 /// Expose the parsed stake for a given key (used by the UI to render confirm
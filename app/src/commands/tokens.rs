@@ -0,0 +1,45 @@
+use citadel_api::dto::TokenRegistryResponse;
+use citadel_api::services::tokens as tokens_svc;
+use citadel_api::AppState;
+use citadel_core::{TokenList, VerifiedToken};
+use tauri::State;
+
+#[tauri::command]
+pub async fn get_token_registry(
+    state: State<'_, AppState>,
+) -> Result<TokenRegistryResponse, String> {
+    tokens_svc::get_token_registry(&state).await
+}
+
+#[tauri::command]
+pub async fn import_token_list(
+    state: State<'_, AppState>,
+    list: TokenList,
+) -> Result<TokenRegistryResponse, String> {
+    tokens_svc::import_token_list(&state, list).await
+}
+
+#[tauri::command]
+pub async fn add_verified_token(
+    state: State<'_, AppState>,
+    token: VerifiedToken,
+) -> Result<TokenRegistryResponse, String> {
+    tokens_svc::add_verified_token(&state, token).await
+}
+
+#[tauri::command]
+pub async fn remove_verified_token(
+    state: State<'_, AppState>,
+    token_id: String,
+) -> Result<TokenRegistryResponse, String> {
+    tokens_svc::remove_verified_token(&state, &token_id).await
+}
+
+#[tauri::command]
+pub async fn set_token_blocked(
+    state: State<'_, AppState>,
+    token_id: String,
+    blocked: bool,
+) -> Result<TokenRegistryResponse, String> {
+    tokens_svc::set_token_blocked(&state, &token_id, blocked).await
+}
@@ -2,6 +2,7 @@ use citadel_api::dto::{MintSignRequest, MintSignResponse, MintTxStatusResponse};
 use citadel_api::services::signing as sign_svc;
 use citadel_api::services::utxo as utxo_svc;
 use citadel_api::AppState;
+use citadel_core::TxIntent;
 use tauri::State;
 
 pub use utxo_svc::{
@@ -62,12 +63,14 @@ pub async fn start_utxo_mgmt_sign(
     state: State<'_, AppState>,
     unsigned_tx: serde_json::Value,
     message: String,
+    intent: Option<TxIntent>,
 ) -> Result<MintSignResponse, String> {
     sign_svc::start_mint_sign(
         &state,
         MintSignRequest {
             unsigned_tx,
             message,
+            intent,
         },
     )
     .await
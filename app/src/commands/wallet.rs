@@ -1,7 +1,11 @@
 use citadel_api::dto::{
-    ConnectionStatusResponse, RecentTxsResponse, WalletBalanceResponse, WalletConnectResponse,
-    WalletStatusResponse,
+    AddWatchWalletRequest, ConnectionStatusResponse, LpPositionsResponse, PortfolioResponse,
+    RecentTxsResponse, WalletBalanceResponse, WalletConnectResponse, WalletListResponse,
+    WalletStatusResponse, WalletValuationResponse,
 };
+use citadel_api::services::portfolio as portfolio_svc;
+use citadel_api::services::positions as positions_svc;
+use citadel_api::services::valuation as valuation_svc;
 use citadel_api::services::wallet as wallet_svc;
 use citadel_api::AppState;
 use tauri::State;
@@ -31,6 +35,52 @@ pub async fn disconnect_wallet(state: State<'_, AppState>) -> Result<(), String>
     wallet_svc::disconnect_wallet(&state).await
 }
 
+#[tauri::command]
+pub async fn list_wallets(state: State<'_, AppState>) -> Result<WalletListResponse, String> {
+    wallet_svc::list_wallets(&state).await
+}
+
+#[tauri::command]
+pub async fn add_watch_wallet(
+    state: State<'_, AppState>,
+    request: AddWatchWalletRequest,
+) -> Result<WalletListResponse, String> {
+    wallet_svc::add_watch_wallet(&state, request).await
+}
+
+#[tauri::command]
+pub async fn remove_watch_wallet(
+    state: State<'_, AppState>,
+    name: String,
+) -> Result<WalletListResponse, String> {
+    wallet_svc::remove_watch_wallet(&state, &name).await
+}
+
+#[tauri::command]
+pub async fn set_active_wallet(
+    state: State<'_, AppState>,
+    name: Option<String>,
+) -> Result<WalletListResponse, String> {
+    wallet_svc::set_active_wallet(&state, name).await
+}
+
+#[tauri::command]
+pub async fn get_portfolio(state: State<'_, AppState>) -> Result<PortfolioResponse, String> {
+    portfolio_svc::get_portfolio(&state).await
+}
+
+#[tauri::command]
+pub async fn get_wallet_valuation(
+    state: State<'_, AppState>,
+) -> Result<WalletValuationResponse, String> {
+    valuation_svc::get_wallet_valuation(&state).await
+}
+
+#[tauri::command]
+pub async fn get_lp_positions(state: State<'_, AppState>) -> Result<LpPositionsResponse, String> {
+    positions_svc::get_lp_positions(&state).await
+}
+
 #[tauri::command]
 pub async fn get_wallet_balance(
     state: State<'_, AppState>,
@@ -48,7 +98,7 @@ pub async fn get_recent_transactions(
 
 #[tauri::command]
 pub async fn build_send_tx(
-    _state: State<'_, AppState>,
+    state: State<'_, AppState>,
     recipient_address: String,
     change_address: String,
     erg_nano: String,
@@ -59,6 +109,7 @@ pub async fn build_send_tx(
 ) -> Result<serde_json::Value, String> {
     let parsed = super::parse_eip12_utxos(user_utxos)?;
     let response = wallet_svc::build_send_tx(
+        &state,
         &recipient_address,
         &change_address,
         &erg_nano,
@@ -66,7 +117,8 @@ pub async fn build_send_tx(
         token_amount.as_deref(),
         parsed,
         current_height,
-    )?;
+    )
+    .await?;
     serde_json::to_value(&response).map_err(|e| format!("Failed to serialize response: {}", e))
 }
 
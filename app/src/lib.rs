@@ -1,9 +1,12 @@
 //! Citadel Tauri application library
 
+pub mod arb_monitor;
 pub mod commands;
+pub mod order_engine;
 pub mod tx_watcher;
 
 use citadel_api::AppState;
+use tauri::Manager;
 
 use tx_watcher::TxWatcherState;
 
@@ -29,6 +32,23 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_notification::init())
+        .setup({
+            let state = state.clone();
+            move |app| {
+                let data_dir = app.path().app_data_dir()?;
+                let result = tauri::async_runtime::block_on(state.set_data_dir(data_dir.clone()));
+                if let Err(e) = result {
+                    tracing::error!(
+                        "Failed to open data directory {}: {}",
+                        data_dir.display(),
+                        e
+                    );
+                }
+                order_engine::start(app.handle().clone());
+                arb_monitor::start(app.handle().clone());
+                Ok(())
+            }
+        })
         .manage(state)
         .manage(tx_watcher_state)
         .invoke_handler(tauri::generate_handler![
@@ -37,10 +57,19 @@ pub fn run() {
             commands::configure_node,
             commands::get_sigmausd_state,
             commands::get_oracle_price,
+            commands::get_sigmausd_history,
+            commands::get_sigmausd_advice,
             commands::start_wallet_connect,
             commands::get_wallet_status,
             commands::get_connection_status,
             commands::disconnect_wallet,
+            commands::list_wallets,
+            commands::add_watch_wallet,
+            commands::remove_watch_wallet,
+            commands::set_active_wallet,
+            commands::get_portfolio,
+            commands::get_wallet_valuation,
+            commands::get_lp_positions,
             commands::get_wallet_balance,
             commands::get_recent_transactions,
             commands::build_send_tx,
@@ -52,6 +81,16 @@ pub fn run() {
             commands::preview_sigmausd_tx,
             commands::build_sigmausd_tx,
             commands::open_nautilus,
+            // Singleton Contention (SigmaUSD / Dexy / HodlCoin)
+            commands::get_contention_status,
+            commands::check_tx_inputs,
+            // Oracle Guard
+            commands::get_oracle_guard_config,
+            commands::set_oracle_guard_config,
+            commands::check_oracle,
+            // What-if Simulator (SigmaUSD / Dexy)
+            commands::simulate_sigmausd,
+            commands::simulate_dexy,
             // Dexy Protocol
             commands::get_dexy_state,
             commands::get_dexy_rates,
@@ -63,6 +102,8 @@ pub fn run() {
             commands::build_lp_deposit_tx,
             commands::preview_lp_redeem,
             commands::build_lp_redeem_tx,
+            commands::get_dexy_keeper_status,
+            commands::build_dexy_keeper_tx,
             // Duckpools Lending Protocol
             commands::get_lending_markets,
             commands::get_lending_positions,
@@ -86,6 +127,8 @@ pub fn run() {
             commands::get_pending_orders,
             commands::get_mempool_swaps,
             commands::build_swap_refund_tx,
+            commands::scan_open_orders,
+            commands::build_bulk_refund_tx,
             // AMM LP Operations
             commands::preview_amm_lp_deposit,
             commands::build_amm_lp_deposit_tx,
@@ -97,12 +140,15 @@ pub fn run() {
             commands::preview_pool_create,
             commands::build_pool_bootstrap_tx,
             commands::build_pool_create_tx,
+            commands::preview_pool_launch,
+            commands::build_pool_launch_tx,
             // AMM Smart Router
             commands::find_swap_routes,
             commands::find_swap_routes_by_output,
             commands::find_split_route,
             commands::compare_sigusd_options,
             commands::get_liquidity_depth,
+            commands::get_pool_analytics,
             commands::get_sigusd_arb_snapshot,
             commands::scan_circular_arbs,
             commands::build_arb_chain_tx,
@@ -137,10 +183,16 @@ pub fn run() {
             commands::get_sigmausd_activity,
             // HodlCoin Protocol
             commands::get_hodlcoin_banks,
+            commands::get_hodlcoin_discovery_config,
+            commands::set_hodlcoin_discovery_config,
             commands::preview_hodlcoin_mint,
             commands::preview_hodlcoin_burn,
             commands::build_hodlcoin_mint_tx,
             commands::build_hodlcoin_burn_tx,
+            commands::preview_hodlcoin_deploy,
+            commands::build_hodlcoin_deploy_tx,
+            commands::get_hodlcoin_history,
+            commands::get_hodlcoin_positions,
             // SigmaFi Bonds
             commands::sigmafi_fetch_market,
             commands::sigmafi_get_tokens,
@@ -164,6 +216,29 @@ pub fn run() {
             // Node Discovery
             commands::discover_nodes,
             commands::probe_single_node,
+            // Address Book
+            commands::list_address_book,
+            commands::save_address_book_entry,
+            commands::remove_address_book_entry,
+            commands::describe_address,
+            commands::describe_addresses,
+            commands::describe_tx_outputs,
+            // Limit / DCA Swap Orders
+            commands::list_swap_orders,
+            commands::create_swap_order,
+            commands::cancel_swap_order,
+            commands::skip_swap_order_execution,
+            commands::mark_swap_order_submitted,
+            commands::process_swap_orders,
+            commands::get_arb_monitor,
+            commands::set_arb_monitor_config,
+            commands::clear_arb_monitor_history,
+            commands::process_arb_monitor,
+            commands::get_token_registry,
+            commands::import_token_list,
+            commands::add_verified_token,
+            commands::remove_verified_token,
+            commands::set_token_blocked,
             // Transaction Watcher
             tx_watcher::watch_tx,
             tx_watcher::watch_order,
@@ -0,0 +1,33 @@
+//! Background driver for client-side limit / DCA swap orders
+//!
+//! Polls at roughly block cadence and runs one engine pass per new block
+//! (the service skips heights it has already checked). Orders whose status
+//! changes are emitted as `swap-order-update` events so the frontend can
+//! prompt for signing.
+
+use citadel_api::services::orders as orders_svc;
+use citadel_api::AppState;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// How often the engine checks for a new block (seconds).
+const POLL_INTERVAL_SECS: u64 = 30;
+
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(POLL_INTERVAL_SECS)).await;
+
+            let state = app_handle.state::<AppState>();
+            match orders_svc::process_swap_orders(&state).await {
+                Ok(changed) => {
+                    for order in changed {
+                        if let Err(e) = app_handle.emit("swap-order-update", order) {
+                            tracing::warn!("Failed to emit swap order update: {}", e);
+                        }
+                    }
+                }
+                Err(e) => tracing::debug!("Swap order pass skipped: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,427 @@
+//! Built-in registry of known protocol contracts.
+//!
+//! Contracts are recognised three ways:
+//! - exact ErgoTree (fixed proxies, order contracts, fee addresses),
+//! - ErgoTree template (per-pool / per-order contracts whose constants vary),
+//! - singleton NFT held at the address (banks and pools whose script is not
+//!   tracked in the protocol crates).
+//!
+//! Only mainnet deployments are registered.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use ergo_lib::ergotree_ir::ergo_tree::ErgoTree;
+use ergo_lib::ergotree_ir::serialization::SigmaSerializable;
+
+use crate::dto::{ContractInfo, ContractMatch};
+
+const P2PK_TREE_PREFIX: &str = "0008cd";
+const P2PK_TREE_HEX_LEN: usize = 72;
+
+enum Pattern {
+    Tree(String),
+    Template(Vec<u8>),
+    /// Fixed hex segments with variable-length hex in between, in order
+    /// (SigmaFi token contracts splice token IDs and hashes into the tree).
+    Segments(&'static [&'static str]),
+}
+
+struct Entry {
+    protocol: &'static str,
+    name: String,
+    pattern: Pattern,
+}
+
+impl Entry {
+    fn tree(protocol: &'static str, name: impl Into<String>, tree: &str) -> Self {
+        Self {
+            protocol,
+            name: name.into(),
+            pattern: Pattern::Tree(tree.to_lowercase()),
+        }
+    }
+
+    fn address(protocol: &'static str, name: impl Into<String>, address: &str) -> Option<Self> {
+        let tree = ergo_tx::address_to_ergo_tree(address).ok()?;
+        Some(Self::tree(protocol, name, &tree))
+    }
+
+    fn template(protocol: &'static str, name: impl Into<String>, tree: &str) -> Option<Self> {
+        Some(Self {
+            protocol,
+            name: name.into(),
+            pattern: Pattern::Template(template_bytes(tree)?),
+        })
+    }
+
+    fn segments(
+        protocol: &'static str,
+        name: impl Into<String>,
+        segments: &'static [&'static str],
+    ) -> Self {
+        Self {
+            protocol,
+            name: name.into(),
+            pattern: Pattern::Segments(segments),
+        }
+    }
+}
+
+fn template_bytes(tree_hex: &str) -> Option<Vec<u8>> {
+    let bytes = hex::decode(tree_hex).ok()?;
+    ErgoTree::sigma_parse_bytes(&bytes)
+        .ok()?
+        .template_bytes()
+        .ok()
+}
+
+fn matches_segments(tree: &str, segments: &[&str]) -> bool {
+    let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+        return false;
+    };
+    if segments.len() < 2 || !tree.starts_with(first) || !tree.ends_with(last) {
+        return false;
+    }
+    let mut rest = &tree[first.len()..tree.len() - last.len()];
+    for segment in &segments[1..segments.len() - 1] {
+        match rest.find(segment) {
+            Some(pos) => rest = &rest[pos + segment.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+static TREE_REGISTRY: LazyLock<Vec<Entry>> = LazyLock::new(|| {
+    use sigmafi::constants as sf;
+
+    let mut entries = vec![
+        Entry::tree(
+            "Citadel",
+            "Citadel dev fee",
+            ergo_tx::DEFAULT_DEV_FEE_ERGO_TREE,
+        ),
+        Entry::tree(
+            "Ergo",
+            "Miner fee",
+            citadel_core::constants::MINER_FEE_ERGO_TREE,
+        ),
+        Entry::tree(
+            "MewLock",
+            "MewLock timelock",
+            mewlock::constants::MEWLOCK_ERGO_TREE,
+        ),
+        Entry::tree("SigmaFi", "SigmaFi ERG bond", sf::ERG_BOND_CONTRACT),
+        Entry::tree(
+            "SigmaFi",
+            "SigmaFi ERG loan order (on close)",
+            sf::ORDER_ON_CLOSE_ERG_CONTRACT,
+        ),
+        Entry::tree(
+            "SigmaFi",
+            "SigmaFi ERG loan order (fixed height)",
+            sf::ORDER_FIXED_HEIGHT_ERG_CONTRACT,
+        ),
+        Entry::tree("SigmaFi", "SigmaFi dev fee", sf::DEV_FEE_ERGO_TREE),
+        Entry::segments(
+            "SigmaFi",
+            "SigmaFi token bond",
+            &sf::TOKEN_BOND_CONTRACT_TEMPLATE,
+        ),
+        Entry::segments(
+            "SigmaFi",
+            "SigmaFi token loan order (on close)",
+            &sf::ORDER_ON_CLOSE_TOKEN_TEMPLATE,
+        ),
+        Entry::segments(
+            "SigmaFi",
+            "SigmaFi token loan order (fixed height)",
+            &sf::ORDER_FIXED_HEIGHT_TOKEN_TEMPLATE,
+        ),
+    ];
+
+    entries.extend(Entry::address(
+        "MewLock",
+        "MewLock dev fee",
+        mewlock::DEV_ADDRESS,
+    ));
+
+    for proxy in lending::constants::unique_proxy_addresses() {
+        entries.extend(Entry::address(
+            "Duckpools",
+            format!("Duckpools {} proxy", proxy.operation.label()),
+            proxy.address,
+        ));
+    }
+    for pool in lending::constants::get_pools() {
+        if !pool.collateral_address.is_empty() {
+            entries.extend(Entry::address(
+                "Duckpools",
+                format!("Duckpools {} collateral", pool.name),
+                pool.collateral_address,
+            ));
+        }
+        if !pool.repayment_address.is_empty() {
+            entries.extend(Entry::address(
+                "Duckpools",
+                format!("Duckpools {} repayment", pool.name),
+                pool.repayment_address,
+            ));
+        }
+    }
+
+    let templates = [
+        (
+            "Spectrum",
+            "Spectrum N2T pool",
+            amm::pool_templates::N2T_POOL_TEMPLATE,
+        ),
+        (
+            "Spectrum",
+            "Spectrum T2T pool",
+            amm::pool_templates::T2T_POOL_TEMPLATE,
+        ),
+        (
+            "Spectrum",
+            "Spectrum N2T swap order (sell ERG)",
+            amm::constants::swap_templates::N2T_SWAP_SELL_TEMPLATE,
+        ),
+        (
+            "Spectrum",
+            "Spectrum N2T swap order (buy ERG)",
+            amm::constants::swap_templates::N2T_SWAP_BUY_TEMPLATE,
+        ),
+        (
+            "Spectrum",
+            "Spectrum T2T swap order",
+            amm::constants::swap_templates::T2T_SWAP_TEMPLATE,
+        ),
+        (
+            "HodlCoin",
+            "HodlCoin bank",
+            hodlcoin::HODLERG_BANK_ERGO_TREE,
+        ),
+    ];
+    for (protocol, name, tree) in templates {
+        match Entry::template(protocol, name, tree) {
+            Some(entry) => entries.push(entry),
+            None => tracing::warn!("Contract registry: cannot parse template for {}", name),
+        }
+    }
+
+    entries
+});
+
+static NFT_REGISTRY: LazyLock<HashMap<&'static str, (&'static str, String)>> =
+    LazyLock::new(|| {
+        let mut nfts: HashMap<&'static str, (&'static str, String)> = HashMap::new();
+        let mut add = |id: &'static str, protocol: &'static str, name: &str| {
+            nfts.insert(id, (protocol, name.to_string()));
+        };
+
+        add(sigmausd::mainnet::BANK_NFT_ID, "SigmaUSD", "SigmaUSD bank");
+        add(
+            sigmausd::mainnet::ORACLE_POOL_NFT_ID,
+            "Oracle",
+            "ERG/USD oracle pool",
+        );
+
+        for (variant, ids) in [("DexyGold", DexyIds::GOLD), ("DexyUSD", DexyIds::USD)] {
+            add(ids.bank, "Dexy", &format!("{} bank", variant));
+            add(ids.lp, "Dexy", &format!("{} LP pool", variant));
+            add(ids.free_mint, "Dexy", &format!("{} FreeMint", variant));
+            add(ids.arb_mint, "Dexy", &format!("{} ArbitrageMint", variant));
+            add(ids.buyback, "Dexy", &format!("{} buyback", variant));
+            add(ids.lp_swap, "Dexy", &format!("{} LP swap", variant));
+            add(ids.lp_mint, "Dexy", &format!("{} LP mint", variant));
+            add(ids.lp_redeem, "Dexy", &format!("{} LP redeem", variant));
+            add(ids.oracle, "Oracle", &format!("{} oracle pool", variant));
+        }
+
+        for pool in lending::constants::get_pools() {
+            add(
+                pool.pool_nft,
+                "Duckpools",
+                &format!("Duckpools {}", pool.name),
+            );
+            add(
+                pool.child_nft,
+                "Duckpools",
+                &format!("Duckpools {} interest (child)", pool.name),
+            );
+            add(
+                pool.parent_nft,
+                "Duckpools",
+                &format!("Duckpools {} interest (parent)", pool.name),
+            );
+            add(
+                pool.parameter_nft,
+                "Duckpools",
+                &format!("Duckpools {} parameters", pool.name),
+            );
+        }
+
+        nfts
+    });
+
+struct DexyIds {
+    bank: &'static str,
+    lp: &'static str,
+    free_mint: &'static str,
+    arb_mint: &'static str,
+    buyback: &'static str,
+    lp_swap: &'static str,
+    lp_mint: &'static str,
+    lp_redeem: &'static str,
+    oracle: &'static str,
+}
+
+impl DexyIds {
+    const GOLD: Self = {
+        use dexy::gold_mainnet as g;
+        Self {
+            bank: g::BANK_NFT_ID,
+            lp: g::LP_NFT_ID,
+            free_mint: g::FREE_MINT_NFT_ID,
+            arb_mint: g::ARBITRAGE_MINT_NFT_ID,
+            buyback: g::BUYBACK_NFT_ID,
+            lp_swap: g::LP_SWAP_NFT_ID,
+            lp_mint: g::LP_MINT_NFT_ID,
+            lp_redeem: g::LP_REDEEM_NFT_ID,
+            oracle: g::ORACLE_POOL_NFT_ID,
+        }
+    };
+
+    const USD: Self = {
+        use dexy::usd_mainnet as u;
+        Self {
+            bank: u::BANK_NFT_ID,
+            lp: u::LP_NFT_ID,
+            free_mint: u::FREE_MINT_NFT_ID,
+            arb_mint: u::ARBITRAGE_MINT_NFT_ID,
+            buyback: u::BUYBACK_NFT_ID,
+            lp_swap: u::LP_SWAP_NFT_ID,
+            lp_mint: u::LP_MINT_NFT_ID,
+            lp_redeem: u::LP_REDEEM_NFT_ID,
+            oracle: u::ORACLE_POOL_NFT_ID,
+        }
+    };
+}
+
+/// `true` for P2S / P2SH trees, `false` for plain P2PK wallets.
+pub fn is_script_tree(ergo_tree_hex: &str) -> bool {
+    !(ergo_tree_hex.len() == P2PK_TREE_HEX_LEN
+        && ergo_tree_hex.to_lowercase().starts_with(P2PK_TREE_PREFIX))
+}
+
+/// Recognise a contract from its ErgoTree alone (no node access).
+pub fn recognize_tree(ergo_tree_hex: &str) -> Option<ContractInfo> {
+    let tree = ergo_tree_hex.to_lowercase();
+    let mut template: Option<Option<Vec<u8>>> = None;
+
+    for entry in TREE_REGISTRY.iter() {
+        let matched_by = match &entry.pattern {
+            Pattern::Tree(t) if *t == tree => ContractMatch::Tree,
+            Pattern::Segments(segments) if matches_segments(&tree, segments) => {
+                ContractMatch::Template
+            }
+            Pattern::Template(expected) => {
+                let actual = template.get_or_insert_with(|| template_bytes(&tree));
+                if actual.as_ref() != Some(expected) {
+                    continue;
+                }
+                ContractMatch::Template
+            }
+            _ => continue,
+        };
+        return Some(ContractInfo {
+            protocol: entry.protocol.to_string(),
+            name: entry.name.clone(),
+            matched_by,
+            is_script: is_script_tree(&tree),
+        });
+    }
+    None
+}
+
+/// Recognise a contract from an address (no node access).
+pub fn recognize_address(address: &str) -> Option<ContractInfo> {
+    let tree = ergo_tx::address_to_ergo_tree(address).ok()?;
+    recognize_tree(&tree)
+}
+
+/// Recognise a protocol box from the tokens it holds (singleton NFTs).
+pub fn recognize_tokens<'a>(token_ids: impl IntoIterator<Item = &'a str>) -> Option<ContractInfo> {
+    token_ids.into_iter().find_map(|id| {
+        NFT_REGISTRY.get(id).map(|(protocol, name)| ContractInfo {
+            protocol: protocol.to_string(),
+            name: name.clone(),
+            matched_by: ContractMatch::Nft,
+            is_script: true,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USER_TREE: &str =
+        "0008cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    #[test]
+    fn test_p2pk_is_not_recognised() {
+        assert!(recognize_tree(USER_TREE).is_none());
+        assert!(!is_script_tree(USER_TREE));
+    }
+
+    #[test]
+    fn test_exact_tree_match() {
+        let info = recognize_tree(mewlock::constants::MEWLOCK_ERGO_TREE).unwrap();
+        assert_eq!(info.protocol, "MewLock");
+        assert_eq!(info.matched_by, ContractMatch::Tree);
+        assert!(info.is_script);
+    }
+
+    #[test]
+    fn test_dev_fee_is_known_wallet() {
+        let info = recognize_tree(ergo_tx::DEFAULT_DEV_FEE_ERGO_TREE).unwrap();
+        assert_eq!(info.name, "Citadel dev fee");
+        assert!(!info.is_script);
+    }
+
+    #[test]
+    fn test_lending_proxy_address() {
+        let proxy = &lending::constants::unique_proxy_addresses()[0];
+        let info = recognize_address(proxy.address).unwrap();
+        assert_eq!(info.protocol, "Duckpools");
+        assert!(info.name.ends_with("proxy"));
+    }
+
+    #[test]
+    fn test_pool_template_match() {
+        let info = recognize_tree(amm::pool_templates::N2T_POOL_TEMPLATE).unwrap();
+        assert_eq!(info.name, "Spectrum N2T pool");
+        assert_eq!(info.matched_by, ContractMatch::Template);
+    }
+
+    #[test]
+    fn test_sigmafi_token_order_segments() {
+        let order = sigmafi::constants::build_order_contract(
+            "03faf2cb329f2e90d6d23b58d91bbb6c046aa143261cc21f52fbe2824bfcbf04",
+            sigmafi::constants::OrderType::OnClose,
+        );
+        let info = recognize_tree(&order).unwrap();
+        assert_eq!(info.name, "SigmaFi token loan order (on close)");
+        assert_eq!(info.matched_by, ContractMatch::Template);
+    }
+
+    #[test]
+    fn test_nft_match() {
+        let info = recognize_tokens(["deadbeef", sigmausd::mainnet::BANK_NFT_ID]).unwrap();
+        assert_eq!(info.name, "SigmaUSD bank");
+        assert_eq!(info.matched_by, ContractMatch::Nft);
+        assert!(recognize_tokens(["deadbeef"]).is_none());
+    }
+}
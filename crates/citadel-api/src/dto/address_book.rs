@@ -0,0 +1,81 @@
+//! Address book and contract recognition DTOs.
+
+use serde::{Deserialize, Serialize};
+
+/// A user-labelled address, persisted in the app data directory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressBookEntry {
+    pub address: String,
+    pub label: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// How a known contract was recognised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractMatch {
+    /// ErgoTree is byte-identical to the registered contract.
+    Tree,
+    /// ErgoTree shares the registered template; only constants differ
+    /// (per-pool / per-order contracts).
+    Template,
+    /// Address holds the protocol's singleton NFT.
+    Nft,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractInfo {
+    pub protocol: String,
+    pub name: String,
+    pub matched_by: ContractMatch,
+    /// `false` for known wallet addresses (dev fee recipients); `true` for
+    /// script contracts whose spending rules ignore the sender.
+    pub is_script: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressInfo {
+    pub address: String,
+    /// Address book label, if the user saved one.
+    pub label: Option<String>,
+    pub notes: Option<String>,
+    pub tags: Vec<String>,
+    pub contract: Option<ContractInfo>,
+    /// P2S / P2SH address (not a wallet), whether or not it is recognised.
+    pub is_script: bool,
+    /// Why the address could not be parsed; nothing else is annotated then.
+    pub invalid: Option<String>,
+}
+
+impl AddressInfo {
+    /// Result for input that is not a valid Ergo address.
+    pub fn invalid(address: &str, reason: impl std::fmt::Display) -> Self {
+        Self {
+            address: address.to_string(),
+            label: None,
+            notes: None,
+            tags: Vec::new(),
+            contract: None,
+            is_script: false,
+            invalid: Some(reason.to_string()),
+        }
+    }
+
+    /// Human name: the user's label, else the recognised contract name.
+    pub fn display_name(&self) -> Option<String> {
+        self.label
+            .clone()
+            .or_else(|| self.contract.as_ref().map(|c| c.name.clone()))
+    }
+}
+
+/// Recipient annotation for one output of a transaction preview.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputRecipientInfo {
+    pub index: usize,
+    #[serde(flatten)]
+    pub info: AddressInfo,
+}
@@ -0,0 +1,100 @@
+//! Background arbitrage monitor DTOs: settings and per-block profitability
+//! history, persisted in the app data directory.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArbMonitorConfig {
+    pub enabled: bool,
+    /// Opportunities below this profit (after miner fees) are ignored.
+    pub min_net_profit_nano: i64,
+    /// Longest ERG -> ... -> ERG cycle searched.
+    pub max_hops: usize,
+    /// Build the circular arb chain for the connected wallet on each alert.
+    pub build_chains: bool,
+}
+
+impl Default for ArbMonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_net_profit_nano: 10_000_000,
+            max_hops: 4,
+            build_chains: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArbKind {
+    /// ERG -> ... -> ERG through Spectrum pools.
+    Circular,
+    /// ERG -> SigUSD on the DEX below the oracle rate, redeemed at the bank.
+    Oracle,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbOpportunityRecord {
+    pub kind: ArbKind,
+    pub path_label: String,
+    pub pool_ids: Vec<String>,
+    pub input_nano: u64,
+    /// Profit after miner fees (and the bank fee for oracle arbs).
+    pub net_profit_nano: i64,
+}
+
+/// One monitored block. Blocks without opportunities are kept too, so the
+/// history shows how often opportunities appear.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbObservation {
+    pub height: u64,
+    /// Wall-clock time of the check, ms since epoch.
+    pub timestamp: u64,
+    pub opportunities: Vec<ArbOpportunityRecord>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArbMonitorState {
+    #[serde(default)]
+    pub config: ArbMonitorConfig,
+    #[serde(default)]
+    pub last_checked_height: u64,
+}
+
+impl ArbMonitorState {
+    /// Append `observation` to `history` (oldest first), keeping the newest
+    /// `max_len` blocks.
+    pub fn push_observation(
+        &mut self,
+        history: &mut Vec<ArbObservation>,
+        observation: ArbObservation,
+        max_len: usize,
+    ) {
+        self.last_checked_height = observation.height;
+        history.push(observation);
+        if history.len() > max_len {
+            let excess = history.len() - max_len;
+            history.drain(..excess);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArbMonitorStats {
+    pub blocks_scanned: usize,
+    pub blocks_with_opportunity: usize,
+    pub circular_opportunities: usize,
+    pub oracle_opportunities: usize,
+    pub best_net_profit_nano: i64,
+    /// Sum of the best opportunity per block.
+    pub total_best_net_profit_nano: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbMonitorResponse {
+    pub config: ArbMonitorConfig,
+    pub last_checked_height: u64,
+    pub stats: ArbMonitorStats,
+    pub history: Vec<ArbObservation>,
+}
@@ -0,0 +1,40 @@
+//! Singleton contention DTOs: pending bank/LP successors and invalidated builds.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SingletonStatus {
+    /// What the singleton is, e.g. "bank" or "lp".
+    pub label: String,
+    pub token_id: String,
+    pub confirmed_box_id: String,
+    /// Latest unconfirmed successor, or the confirmed box when nothing is pending.
+    pub latest_box_id: String,
+    /// Unconfirmed spends between the two, oldest first.
+    pub pending_tx_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentionStatusResponse {
+    pub protocol: String,
+    pub singletons: Vec<SingletonStatus>,
+    /// Pending spends across all singletons; non-zero means a build on the
+    /// confirmed state will be rejected unless it chains on the pending boxes.
+    pub pending_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpentInputDto {
+    pub box_id: String,
+    /// Competing mempool tx, or `None` if the spend is already confirmed.
+    pub spent_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxInputCheckResponse {
+    /// Another transaction spent one of the inputs; the tx must be rebuilt.
+    pub invalidated: bool,
+    /// Our own signed tx, if it is already in the mempool.
+    pub submitted_tx_id: Option<String>,
+    pub spent_inputs: Vec<SpentInputDto>,
+}
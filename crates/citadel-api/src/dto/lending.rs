@@ -1,5 +1,6 @@
 //! Duckpools lending IPC / façade DTOs.
 
+use citadel_core::TxIntent;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -121,6 +122,7 @@ pub struct RefundBuildRequest {
 pub struct LendingBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: LendingTxSummary,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,12 +1,52 @@
+use citadel_core::{TokenWarning, TxIntent};
 use serde::{Deserialize, Serialize};
 
+pub mod address_book;
+pub mod arb_monitor;
+pub mod contention;
 pub mod lending;
-
+pub mod oracle;
+pub mod orders;
+pub mod portfolio;
+pub mod positions;
+pub mod sigmausd_advisor;
+pub mod simulator;
+pub mod tokens;
+pub mod valuation;
+
+pub use address_book::{
+    AddressBookEntry, AddressInfo, ContractInfo, ContractMatch, OutputRecipientInfo,
+};
+pub use arb_monitor::{
+    ArbKind, ArbMonitorConfig, ArbMonitorResponse, ArbMonitorState, ArbMonitorStats,
+    ArbObservation, ArbOpportunityRecord,
+};
+pub use contention::{
+    ContentionStatusResponse, SingletonStatus, SpentInputDto, TxInputCheckResponse,
+};
 pub use lending::{
     BorrowBuildRequest, BorrowPositionInfo, CollateralOptionInfo, LendBuildRequest,
     LendPositionInfo, LendingBuildResponse, LendingTxSummary, MarketsResponse, PoolInfo,
     PositionsResponse, RefundBuildRequest, RepayBuildRequest, WithdrawBuildRequest,
 };
+pub use oracle::{OracleCheck, OracleFeed, OracleGuardConfig, OracleReference, OracleVerdict};
+pub use orders::{
+    CreateSwapOrderRequest, OrderEvent, OrderEventKind, PreparedOrderSwap, SwapOrder,
+    SwapOrderKind, SwapOrderStatus,
+};
+pub use portfolio::{
+    AddWatchWalletRequest, HodlPositionInfo, LpPositionInfo, PortfolioResponse, PortfolioToken,
+    PortfolioWallet, WalletListResponse, WalletPosition, WatchWalletInfo,
+};
+pub use positions::{LpEntry, LpPositionDetail, LpPositionsResponse};
+pub use sigmausd_advisor::{
+    DexPoolQuote, RouteAdvice, RouteQuote, SigmaUsdAdviceResponse, VenueComparison,
+};
+pub use simulator::{DexySimRequest, SigmaUsdSimRequest, SimFlowDto};
+pub use tokens::TokenRegistryResponse;
+pub use valuation::{
+    AssetValuation, PriceConfidence, PriceSource, ValuationBreakdown, WalletValuationResponse,
+};
 
 /// Serialize a u64 as a JSON string (e.g. `"9223372036854774807"`) and accept
 /// either string or number on the way back. Needed for fields that can exceed
@@ -132,6 +172,10 @@ pub struct MintBuildRequest {
 pub struct MintBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: TxSummaryDto,
+    pub intent: TxIntent,
+    /// Oracle freshness/deviation warnings that did not block the build.
+    #[serde(default)]
+    pub oracle_warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -159,6 +203,9 @@ pub struct TxSubmitResponse {
 pub struct MintSignRequest {
     pub unsigned_tx: serde_json::Value,
     pub message: String,
+    /// When present, rendered as the ErgoPay message instead of `message`.
+    #[serde(default)]
+    pub intent: Option<TxIntent>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -166,6 +213,11 @@ pub struct MintSignResponse {
     pub request_id: String,
     pub ergopay_url: String,
     pub nautilus_url: String,
+    /// "standard" or "fallback" (scripts not evaluated before signing)
+    #[serde(default)]
+    pub reduction_path: String,
+    #[serde(default)]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -202,12 +254,19 @@ pub struct SigmaUsdBuildRequest {
     pub user_utxos: Vec<serde_json::Value>,
     pub current_height: i32,
     pub recipient_address: Option<String>,
+    /// Chain on an unconfirmed bank successor instead of the confirmed box.
+    #[serde(default)]
+    pub build_on_pending: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SigmaUsdBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: TxSummaryDto,
+    pub intent: TxIntent,
+    /// Oracle freshness/deviation warnings that did not block the build.
+    #[serde(default)]
+    pub oracle_warnings: Vec<String>,
 }
 
 pub mod wallet_status {
@@ -230,6 +289,9 @@ pub struct WalletStatusResponse {
     pub address: Option<String>,
     #[serde(default)]
     pub addresses: Vec<String>,
+    /// Name of the active watch-only wallet (cannot sign), if one is selected.
+    #[serde(default)]
+    pub watch_only: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -257,6 +319,11 @@ pub struct TokenBalance {
     /// tokens not yet in a block; negative = confirmed tokens spent in mempool.
     #[serde(default)]
     pub pending_amount: i64,
+    /// On the verified token list.
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub warning: Option<TokenWarning>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -325,12 +392,22 @@ pub struct DexyBuildRequest {
     pub user_utxos: Vec<serde_json::Value>,
     pub current_height: i32,
     pub recipient_address: Option<String>,
+    /// "free_mint" (default) or "arb_mint"
+    #[serde(default)]
+    pub mint_path: Option<String>,
+    /// Chain on unconfirmed bank/LP successors instead of the confirmed boxes.
+    #[serde(default)]
+    pub build_on_pending: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DexyBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: TxSummaryDto,
+    pub intent: TxIntent,
+    /// Oracle freshness/deviation warnings that did not block the build.
+    #[serde(default)]
+    pub oracle_warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -354,6 +431,7 @@ pub struct DexySwapPreviewResponse {
 pub struct DexySwapBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: dexy::SwapTxSummary,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -373,6 +451,24 @@ pub struct DexyLpPreviewResponse {
 pub struct DexyLpBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: dexy::LpTxSummary,
+    pub intent: TxIntent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexyKeeperStatusResponse {
+    pub variant: String,
+    pub current_height: i32,
+    pub lp_rate_nano: i64,
+    /// Oracle rate in nanoERG per token (divisor applied)
+    pub oracle_rate_nano: i64,
+    pub actions: Vec<dexy::KeeperActionStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexyKeeperBuildResponse {
+    pub unsigned_tx: serde_json::Value,
+    pub summary: dexy::KeeperTxSummary,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -383,6 +479,10 @@ pub struct AmmTokenDto {
     pub decimals: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<TokenWarning>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -428,6 +528,9 @@ pub struct SwapQuoteResponse {
     pub fee_amount: u64,
     pub effective_rate: f64,
     pub min_output_suggested: u64,
+    /// Unconfirmed swaps the quoted pool state builds on, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_tx_ids: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -446,6 +549,10 @@ pub struct TokenChangeDto {
     pub amount: i64,
     pub name: Option<String>,
     pub decimals: u8,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub warning: Option<TokenWarning>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -453,6 +560,20 @@ pub struct RecentTxsResponse {
     pub transactions: Vec<RecentTxDto>,
 }
 
+/// Verification is filled in by the service; see `services::tokens`.
+impl From<amm::TokenAmount> for AmmTokenDto {
+    fn from(token: amm::TokenAmount) -> Self {
+        Self {
+            token_id: token.token_id,
+            amount: token.amount,
+            decimals: token.decimals,
+            name: token.name,
+            verified: false,
+            warning: None,
+        }
+    }
+}
+
 impl From<amm::AmmPool> for AmmPoolDto {
     fn from(pool: amm::AmmPool) -> Self {
         Self {
@@ -460,18 +581,8 @@ impl From<amm::AmmPool> for AmmPoolDto {
             pool_type: format!("{:?}", pool.pool_type),
             box_id: pool.box_id,
             erg_reserves: pool.erg_reserves,
-            token_x: pool.token_x.map(|t| AmmTokenDto {
-                token_id: t.token_id,
-                amount: t.amount,
-                decimals: t.decimals,
-                name: t.name,
-            }),
-            token_y: AmmTokenDto {
-                token_id: pool.token_y.token_id,
-                amount: pool.token_y.amount,
-                decimals: pool.token_y.decimals,
-                name: pool.token_y.name,
-            },
+            token_x: pool.token_x.map(Into::into),
+            token_y: pool.token_y.into(),
             lp_token_id: pool.lp_token_id,
             lp_circulating: pool.lp_circulating,
             fee_percent: ((1.0 - pool.fee_num as f64 / pool.fee_denom as f64) * 10_000.0).round()
@@ -489,16 +600,12 @@ impl From<amm::SwapQuote> for SwapQuoteResponse {
                     SwapInputDto::Token { token_id, amount }
                 }
             },
-            output: AmmTokenDto {
-                token_id: quote.output.token_id,
-                amount: quote.output.amount,
-                decimals: quote.output.decimals,
-                name: quote.output.name,
-            },
+            output: quote.output.into(),
             price_impact: quote.price_impact,
             fee_amount: quote.fee_amount,
             effective_rate: quote.effective_rate,
             min_output_suggested: quote.min_output_suggested,
+            pending_tx_ids: Vec::new(),
         }
     }
 }
@@ -0,0 +1,88 @@
+//! Oracle guard DTOs: freshness/deviation settings and per-feed checks,
+//! with the settings persisted in the app data directory.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OracleGuardConfig {
+    /// An oracle pool box not refreshed for this many blocks is stale.
+    pub max_age_blocks: i32,
+    /// Median deviation from the on-chain references that triggers a warning.
+    pub warn_deviation_pct: f64,
+    /// Median deviation that blocks builds.
+    pub block_deviation_pct: f64,
+    /// Block builds on a stale oracle instead of only warning.
+    pub block_when_stale: bool,
+}
+
+impl Default for OracleGuardConfig {
+    fn default() -> Self {
+        Self {
+            // Pool v2 epochs are 30 blocks; three missed refreshes is stale
+            max_age_blocks: 90,
+            warn_deviation_pct: 3.0,
+            block_deviation_pct: 10.0,
+            block_when_stale: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleFeed {
+    /// ERG/USD pool read by SigmaUSD and lending valuations.
+    ErgUsd,
+    /// XAU pool behind DexyGold.
+    DexyGold,
+    /// ERG/USD pool behind USE.
+    DexyUsd,
+}
+
+impl OracleFeed {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OracleFeed::ErgUsd => "erg_usd",
+            OracleFeed::DexyGold => "dexy_gold",
+            OracleFeed::DexyUsd => "dexy_usd",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OracleVerdict {
+    Ok,
+    Warn,
+    Block,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleReference {
+    /// e.g. "USE LP" or "Spectrum ERG/SigUSD".
+    pub source: String,
+    /// Same unit as the oracle datapoint (nanoERG per USD or per kg).
+    pub rate_nano: f64,
+    /// Signed: positive when the reference prices the asset above the oracle.
+    pub deviation_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OracleCheck {
+    pub feed: OracleFeed,
+    pub oracle_box_id: String,
+    /// R4 datapoint: nanoERG per USD (USD feeds) or per kg (gold).
+    pub rate_nano: i64,
+    /// R5 epoch counter, when the pool box carries one.
+    pub epoch: Option<i32>,
+    /// Creation height of the pool box, i.e. its last refresh.
+    pub box_height: i32,
+    pub current_height: i32,
+    pub age_blocks: i32,
+    pub stale: bool,
+    pub references: Vec<OracleReference>,
+    /// Median absolute deviation across references; `None` without references.
+    pub median_deviation_pct: Option<f64>,
+    pub verdict: OracleVerdict,
+    pub issues: Vec<String>,
+}
@@ -0,0 +1,106 @@
+//! Client-side limit and DCA swap order DTOs.
+//!
+//! Orders live in the app data directory and are evaluated locally on each
+//! new block; nothing is placed on chain until a triggered swap is signed.
+
+use citadel_core::TxIntent;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SwapOrderKind {
+    /// Swap once the best route returns at least `min_output` raw target
+    /// units for the order's input (input amount × limit rate).
+    Limit { min_output: u64 },
+    /// Swap the order's input every `interval_blocks`, `executions` times.
+    Dca {
+        interval_blocks: u32,
+        executions: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapOrderStatus {
+    /// Re-quoted on every new block.
+    Active,
+    /// Conditions met; `prepared` holds a swap waiting to be signed.
+    AwaitingSignature,
+    Completed,
+    Cancelled,
+}
+
+/// A direct swap built for a triggered order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreparedOrderSwap {
+    /// Height the swap was quoted and built at.
+    pub height: u64,
+    pub pool_id: String,
+    pub quoted_output: u64,
+    pub min_output: u64,
+    pub unsigned_tx: serde_json::Value,
+    pub intent: TxIntent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderEventKind {
+    Created,
+    Triggered,
+    /// A check or a prepared swap was abandoned (no route, build failure,
+    /// prepared swap went stale).
+    Skipped,
+    Submitted,
+    Cancelled,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderEvent {
+    pub height: u64,
+    pub kind: OrderEventKind,
+    pub message: String,
+    #[serde(default)]
+    pub tx_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapOrder {
+    pub id: String,
+    /// Primary address of the wallet that created the order; only that
+    /// wallet's UTXOs fund its swaps.
+    pub owner: String,
+    /// `"ERG"` or a token ID, as accepted by the router.
+    pub source_token: String,
+    pub target_token: String,
+    /// Raw source units swapped per execution.
+    pub input_amount: u64,
+    pub kind: SwapOrderKind,
+    pub slippage_percent: f64,
+    pub status: SwapOrderStatus,
+    pub created_height: u64,
+    /// First height the order may trigger at (advanced after each DCA buy).
+    pub next_height: u64,
+    /// Last height the order was quoted at, so each block is checked once.
+    #[serde(default)]
+    pub last_checked_height: u64,
+    /// Best executable output at the last check.
+    #[serde(default)]
+    pub last_quoted_output: Option<u64>,
+    #[serde(default)]
+    pub executions_done: u32,
+    #[serde(default)]
+    pub prepared: Option<PreparedOrderSwap>,
+    #[serde(default)]
+    pub history: Vec<OrderEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSwapOrderRequest {
+    pub source_token: String,
+    pub target_token: String,
+    pub input_amount: u64,
+    pub kind: SwapOrderKind,
+    #[serde(default)]
+    pub slippage_percent: Option<f64>,
+}
@@ -0,0 +1,119 @@
+//! Watch-only wallets and multi-wallet portfolio DTOs.
+
+use citadel_core::TokenWarning;
+use serde::{Deserialize, Serialize};
+
+use super::{u64_as_string, BorrowPositionInfo, LendPositionInfo};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddWatchWalletRequest {
+    pub name: String,
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    /// Extended public key at the EIP-3 change level (`m/44'/429'/0'/0`).
+    #[serde(default)]
+    pub xpub: Option<String>,
+    /// Number of addresses to derive from `xpub` (default 20).
+    #[serde(default)]
+    pub xpub_count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchWalletInfo {
+    pub name: String,
+    pub addresses: Vec<String>,
+    pub xpub: Option<String>,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletListResponse {
+    /// Primary address of the connected wallet, if any.
+    pub connected_address: Option<String>,
+    /// `None` when the connected wallet is active.
+    pub active_watch_wallet: Option<String>,
+    pub watch_wallets: Vec<WatchWalletInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioToken {
+    pub token_id: String,
+    #[serde(with = "u64_as_string")]
+    pub amount: u64,
+    pub name: Option<String>,
+    pub decimals: u8,
+    #[serde(default)]
+    pub verified: bool,
+    #[serde(default)]
+    pub warning: Option<TokenWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioWallet {
+    /// Watch-only wallet name, or "connected" for the Nautilus / ErgoPay wallet.
+    pub name: String,
+    pub watch_only: bool,
+    pub active: bool,
+    pub addresses: Vec<String>,
+    pub erg_nano: u64,
+    pub tokens: Vec<PortfolioToken>,
+}
+
+/// A protocol position tagged with the wallet that holds it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletPosition<T> {
+    pub wallet: String,
+    #[serde(flatten)]
+    pub position: T,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LpPositionInfo {
+    pub pool_id: String,
+    pub lp_token_id: String,
+    #[serde(with = "u64_as_string")]
+    pub lp_amount: u64,
+    pub pool_share_pct: f64,
+    /// ERG (N2T, Dexy) or token X (T2T) underlying the LP tokens.
+    pub x_token_id: String,
+    #[serde(with = "u64_as_string")]
+    pub x_amount: u64,
+    pub y_token_id: String,
+    pub y_name: Option<String>,
+    #[serde(with = "u64_as_string")]
+    pub y_amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HodlPositionInfo {
+    pub singleton_token_id: String,
+    pub hodl_token_id: String,
+    pub hodl_token_name: Option<String>,
+    #[serde(with = "u64_as_string")]
+    pub amount: u64,
+    pub price_nano_per_hodl: f64,
+    /// Reserve token of a token-backed bank; `None` for ERG banks.
+    pub reserve_token_id: Option<String>,
+    pub reserve_token_name: Option<String>,
+    /// Reserve received if burned now, after bank and dev fees: nanoERG, or raw
+    /// reserve token units for token-backed banks.
+    pub burn_value: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioResponse {
+    pub wallets: Vec<PortfolioWallet>,
+    pub total_erg_nano: u64,
+    /// Token balances summed across all wallets.
+    pub tokens: Vec<PortfolioToken>,
+    pub lend_positions: Vec<WalletPosition<LendPositionInfo>>,
+    pub borrow_positions: Vec<WalletPosition<BorrowPositionInfo>>,
+    pub lp_positions: Vec<WalletPosition<LpPositionInfo>>,
+    pub sigmafi_bonds: Vec<WalletPosition<sigmafi::ActiveBond>>,
+    pub sigmafi_orders: Vec<WalletPosition<sigmafi::OpenOrder>>,
+    pub mewlocks: Vec<WalletPosition<mewlock::MewLockBox>>,
+    pub hodl_positions: Vec<WalletPosition<HodlPositionInfo>>,
+    /// Protocols that could not be queried; positions from them are missing.
+    pub errors: Vec<String>,
+    pub block_height: u32,
+}
@@ -0,0 +1,72 @@
+//! LP position tracker DTOs: current value, entry, impermanent loss and fees.
+//!
+//! Values are in raw units of the pool's X side (nanoERG for N2T and Dexy
+//! pools) priced at the current pool rate.
+
+use serde::{Deserialize, Serialize};
+
+use super::u64_as_string;
+
+/// A wallet transaction that minted LP tokens against the pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LpEntry {
+    pub tx_id: String,
+    pub height: u64,
+    pub timestamp: u64,
+    #[serde(with = "u64_as_string")]
+    pub lp_received: u64,
+    pub x_deposited: u64,
+    pub y_deposited: u64,
+    /// Pool price (X per Y, raw units) right after the deposit.
+    pub price_x_per_y: f64,
+    /// Pool reserves and circulating LP right after the deposit.
+    pub pool_x: u64,
+    pub pool_y: u64,
+    #[serde(with = "u64_as_string")]
+    pub pool_lp_supply: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LpPositionDetail {
+    /// "Spectrum", "DexyGold" or "DexyUSD".
+    pub protocol: String,
+    pub pool_id: String,
+    pub lp_token_id: String,
+    #[serde(with = "u64_as_string")]
+    pub lp_amount: u64,
+    pub pool_share_pct: f64,
+    /// ERG (N2T, Dexy) or token X (T2T) underlying the LP tokens.
+    pub x_token_id: String,
+    pub x_amount: u64,
+    pub y_token_id: String,
+    pub y_name: Option<String>,
+    pub y_amount: u64,
+    pub price_x_per_y: f64,
+    /// Redeemable value of the position.
+    pub value_x: u64,
+    /// Deposits matched to the current holding, newest first.
+    pub entries: Vec<LpEntry>,
+    /// LP tokens not matched to a deposit (transfers, history beyond the
+    /// scanned range). Entry-based figures cover the tracked part only.
+    #[serde(with = "u64_as_string")]
+    pub untracked_lp: u64,
+    /// Average entry price over the matched deposits.
+    pub entry_price_x_per_y: Option<f64>,
+    /// Value today of the deposited amounts, had they been held.
+    pub hodl_value_x: Option<u64>,
+    /// Loss from the price move alone, excluding fees (≤ 0).
+    pub impermanent_loss_x: Option<i64>,
+    pub impermanent_loss_pct: Option<f64>,
+    /// Swap fees accrued to the tracked LP tokens since entry.
+    pub fees_earned_x: Option<u64>,
+    /// Position value minus HODL value: fees plus impermanent loss.
+    pub net_vs_hodl_x: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LpPositionsResponse {
+    pub positions: Vec<LpPositionDetail>,
+    /// Pools or history that could not be queried.
+    pub errors: Vec<String>,
+    pub block_height: u64,
+}
@@ -0,0 +1,67 @@
+//! SigmaUSD action advisor DTOs: exact bank limits next to the matching
+//! Spectrum pools, and the best bank/DEX split for one action.
+
+use serde::{Deserialize, Serialize};
+use sigmausd::BankAdvice;
+
+/// Deepest ERG pool for SigUSD or SigRSV.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexPoolQuote {
+    pub pool_id: String,
+    pub erg_reserves: u64,
+    pub token_reserves: u64,
+    pub fee_pct: f64,
+    /// nanoERG per raw unit for a marginal buy, pool fee included.
+    pub buy_price_nano: f64,
+    /// nanoERG per raw unit for a marginal sell, pool fee included.
+    pub sell_price_nano: f64,
+}
+
+/// Marginal bank vs DEX price for one action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueComparison {
+    pub action: String,
+    /// `None` when the reserve ratio blocks the action.
+    pub bank_price_nano: Option<f64>,
+    /// `None` without a pool for the token.
+    pub dex_price_nano: Option<f64>,
+    /// "bank", "dex" or "none".
+    pub better_venue: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteQuote {
+    /// Raw units through the bank.
+    pub bank_amount: i64,
+    /// Raw units through the pool.
+    pub dex_amount: i64,
+    /// ERG paid (mints) or received (redeems), bank and pool fees included.
+    pub erg_nano: i64,
+    pub effective_price_nano: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteAdvice {
+    pub action: String,
+    pub amount: i64,
+    /// `None` when the bank cannot take the whole amount.
+    pub bank_only: Option<RouteQuote>,
+    /// `None` when the pool cannot take the whole amount.
+    pub dex_only: Option<RouteQuote>,
+    pub best: Option<RouteQuote>,
+    /// "bank", "dex", "split" or "unavailable".
+    pub recommendation: String,
+    /// ERG saved (buys) or gained (sells) by `best` over the better
+    /// single-venue route; 0 when neither venue can take the action alone.
+    pub improvement_nano: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigmaUsdAdviceResponse {
+    pub bank: BankAdvice,
+    pub sigusd_pool: Option<DexPoolQuote>,
+    pub sigrsv_pool: Option<DexPoolQuote>,
+    pub comparisons: Vec<VenueComparison>,
+    /// Present when an action and amount were given.
+    pub route: Option<RouteAdvice>,
+}
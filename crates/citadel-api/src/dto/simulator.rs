@@ -0,0 +1,39 @@
+//! What-if simulator DTOs: price shocks, hypothetical flows and user holdings.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimFlowDto {
+    /// SigmaUSD: "mint_sigusd" | "redeem_sigusd" | "mint_sigrsv" | "redeem_sigrsv".
+    /// Dexy: "mint" | "sell_to_lp" | "buy_from_lp" (amount in nanoERG).
+    pub action: String,
+    /// Raw token units (nanoERG for "buy_from_lp").
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigmaUsdSimRequest {
+    /// ERG price changes in percent, one scenario each. Empty = no shock.
+    #[serde(default)]
+    pub price_shocks_pct: Vec<f64>,
+    /// Applied in order after each shock.
+    #[serde(default)]
+    pub flows: Vec<SimFlowDto>,
+    #[serde(default)]
+    pub sigusd_held: i64,
+    #[serde(default)]
+    pub sigrsv_held: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexySimRequest {
+    pub variant: String,
+    #[serde(default)]
+    pub price_shocks_pct: Vec<f64>,
+    #[serde(default)]
+    pub flows: Vec<SimFlowDto>,
+    #[serde(default)]
+    pub dexy_held: i64,
+    #[serde(default)]
+    pub lp_tokens_held: i64,
+}
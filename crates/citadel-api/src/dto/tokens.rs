@@ -0,0 +1,15 @@
+//! Token registry DTOs: the verified list in effect and local overrides.
+
+use citadel_core::VerifiedToken;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRegistryResponse {
+    /// Version of the list in effect (bundled or imported).
+    pub list_version: u32,
+    /// Every verified token, including local additions, sorted by name.
+    pub tokens: Vec<VerifiedToken>,
+    /// IDs of tokens verified locally rather than by the list.
+    pub local_verified: Vec<String>,
+    pub blocked: Vec<String>,
+}
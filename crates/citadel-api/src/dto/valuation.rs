@@ -0,0 +1,87 @@
+//! Wallet valuation DTOs (ERG / USD with per-asset price confidence).
+
+use serde::{Deserialize, Serialize};
+
+use super::u64_as_string;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    /// ERG itself.
+    Native,
+    /// Protocol oracle (SigUSD at the ERG/USD oracle, Dexy at its oracle rate).
+    Oracle,
+    /// Protocol bank redemption value (SigRSV equity, HodlCoin burn).
+    ProtocolBank,
+    /// Best Spectrum route to ERG for the held amount.
+    AmmRoute,
+    /// Spectrum LP token valued through its pool share.
+    SpectrumLp,
+    /// Dexy LP token valued through its share of the Dexy LP pool.
+    DexyLp,
+    /// Duckpools lend token valued through its share of pool assets.
+    LendingLp,
+    Unpriced,
+}
+
+/// Ordered from least to most trustworthy so the weakest component of a
+/// composite valuation can be taken with `min`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceConfidence {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetValuation {
+    /// `"ERG"` for the native balance.
+    pub token_id: String,
+    pub name: Option<String>,
+    pub decimals: u8,
+    #[serde(with = "u64_as_string")]
+    pub amount: u64,
+    pub source: PriceSource,
+    pub confidence: PriceConfidence,
+    /// Realisable value: after price impact for AMM routes, after fees for
+    /// bank redemptions.
+    pub value_nano_erg: u64,
+    /// Value at the marginal (spot / oracle) price.
+    pub spot_value_nano_erg: u64,
+    /// Discount of `value_nano_erg` against `spot_value_nano_erg`, in percent.
+    pub discount_pct: f64,
+    pub value_usd: Option<f64>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValuationBreakdown {
+    pub erg_nano: u64,
+    /// Plain tokens (oracle, bank and AMM-priced).
+    pub tokens_nano: u64,
+    /// Spectrum and Dexy LP tokens.
+    pub amm_lp_nano: u64,
+    pub lending_lp_nano: u64,
+    /// Portion of the total whose confidence is below `high`.
+    pub uncertain_nano: u64,
+    pub unpriced_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletValuationResponse {
+    /// Watch-only wallet name, or "connected".
+    pub wallet: String,
+    pub addresses: Vec<String>,
+    pub erg_usd: Option<f64>,
+    pub total_value_nano_erg: u64,
+    pub total_value_usd: Option<f64>,
+    pub breakdown: ValuationBreakdown,
+    /// Sorted by value, largest first.
+    pub assets: Vec<AssetValuation>,
+    /// Price sources that could not be loaded; affected assets fall back to
+    /// AMM routes or are left unpriced.
+    pub errors: Vec<String>,
+    pub block_height: u32,
+}
@@ -2,8 +2,11 @@
 //!
 //! Tauri IPC is the sole app door. ErgoPay local HTTP lives in `ergopay-server`.
 
+pub mod contracts;
 pub mod dto;
 pub mod services;
 pub mod state;
+pub mod store;
 
-pub use state::{ApiError, AppState, WalletState};
+pub use state::{ApiError, AppState, WalletState, WatchWallet};
+pub use store::{JsonStore, StoreError};
@@ -0,0 +1,146 @@
+//! Address book CRUD and address / contract annotation for sends, tx previews
+//! and the explorer.
+
+use ergo_tx::Eip12UnsignedTx;
+
+use super::error::{IntoServiceError, ServiceResult};
+use crate::contracts;
+use crate::dto::{AddressBookEntry, AddressInfo, OutputRecipientInfo};
+use crate::AppState;
+
+pub async fn list_address_book(state: &AppState) -> ServiceResult<Vec<AddressBookEntry>> {
+    Ok(state.address_book().await)
+}
+
+pub async fn save_address_book_entry(
+    state: &AppState,
+    entry: AddressBookEntry,
+) -> ServiceResult<Vec<AddressBookEntry>> {
+    let address = entry.address.trim().to_string();
+    ergo_tx::address_to_ergo_tree(&address)
+        .map_err(|e| format!("Invalid address '{}': {}", address, e))?;
+
+    let label = entry.label.trim().to_string();
+    if label.is_empty() {
+        return Err("Label must not be empty".to_string());
+    }
+    let notes = entry
+        .notes
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    let mut tags: Vec<String> = Vec::new();
+    for tag in entry.tags {
+        let tag = tag.trim().to_string();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    state
+        .upsert_address_book_entry(AddressBookEntry {
+            address,
+            label,
+            notes,
+            tags,
+        })
+        .await
+        .into_service()?;
+    Ok(state.address_book().await)
+}
+
+pub async fn remove_address_book_entry(
+    state: &AppState,
+    address: &str,
+) -> ServiceResult<Vec<AddressBookEntry>> {
+    state
+        .remove_address_book_entry(address)
+        .await
+        .into_service()?;
+    Ok(state.address_book().await)
+}
+
+/// Annotate `address` from the address book and the contract registry only.
+pub(crate) fn address_info(book: &[AddressBookEntry], address: &str, tree: &str) -> AddressInfo {
+    let entry = book.iter().find(|e| e.address == address);
+    AddressInfo {
+        address: address.to_string(),
+        label: entry.map(|e| e.label.clone()),
+        notes: entry.and_then(|e| e.notes.clone()),
+        tags: entry.map(|e| e.tags.clone()).unwrap_or_default(),
+        contract: contracts::recognize_tree(tree),
+        is_script: contracts::is_script_tree(tree),
+        invalid: None,
+    }
+}
+
+/// Warnings to show before sending funds to `recipient`.
+pub(crate) fn send_warnings(recipient: &AddressInfo) -> Vec<String> {
+    match &recipient.contract {
+        Some(c) if c.is_script => vec![format!(
+            "Recipient is the {} contract ({}). Funds sent directly to a protocol \
+             contract are usually unrecoverable.",
+            c.name, c.protocol
+        )],
+        Some(_) => Vec::new(),
+        None if recipient.is_script => vec![
+            "Recipient is a script address, not a wallet. Make sure its spending \
+             conditions let the intended owner claim these funds."
+                .to_string(),
+        ],
+        None => Vec::new(),
+    }
+}
+
+/// Annotate one address. Unrecognised script addresses are also checked for a
+/// protocol singleton NFT via the node (requires extraIndex; skipped on error).
+pub async fn describe_address(state: &AppState, address: &str) -> ServiceResult<AddressInfo> {
+    let tree = ergo_tx::address_to_ergo_tree(address)
+        .map_err(|e| format!("Invalid address '{}': {}", address, e))?;
+    let mut info = address_info(&state.address_book().await, address, &tree);
+
+    if info.contract.is_none() && info.is_script {
+        if let Some(client) = state.node_client().await {
+            if let Ok((_, tokens)) = client.get_address_balances(address).await {
+                info.contract =
+                    contracts::recognize_tokens(tokens.iter().map(|(id, _)| id.as_str()));
+            }
+        }
+    }
+    Ok(info)
+}
+
+/// Annotate many addresses at once (address book + registry, no node queries).
+/// Invalid addresses are flagged in their result rather than failing the batch.
+pub async fn describe_addresses(
+    state: &AppState,
+    addresses: Vec<String>,
+) -> ServiceResult<Vec<AddressInfo>> {
+    let book = state.address_book().await;
+    Ok(addresses
+        .into_iter()
+        .map(|address| match ergo_tx::address_to_ergo_tree(&address) {
+            Ok(tree) => address_info(&book, &address, &tree),
+            Err(e) => AddressInfo::invalid(&address, e),
+        })
+        .collect())
+}
+
+/// Annotate every output recipient of an unsigned transaction (tx previews).
+pub async fn describe_tx_outputs(
+    state: &AppState,
+    unsigned_tx: &Eip12UnsignedTx,
+) -> ServiceResult<Vec<OutputRecipientInfo>> {
+    let book = state.address_book().await;
+    Ok(unsigned_tx
+        .outputs
+        .iter()
+        .enumerate()
+        .map(|(index, output)| {
+            let address = ergo_tx::ergo_tree_to_address(&output.ergo_tree).unwrap_or_default();
+            OutputRecipientInfo {
+                index,
+                info: address_info(&book, &address, &output.ergo_tree),
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,62 @@
+//! Pool analytics over the traced pool box chain, cached in the data dir.
+//!
+//! Each query only walks the boxes created since the last cached one, so
+//! repeated lookups cost a handful of node requests.
+
+use amm::PoolSnapshot;
+
+use crate::services::chain_history::refresh_history;
+use crate::AppState;
+
+const POOL_HISTORY_FILE: &str = "pool_history";
+/// Snapshots kept per pool (newest first).
+const MAX_CACHED_SNAPSHOTS: usize = 2_000;
+/// Boxes walked per query when the caller does not say.
+const DEFAULT_TRACE_STEPS: usize = 300;
+/// History older than the longest analytics window is not traced.
+const HISTORY_WINDOW_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Volume, fees, TVL history and realized LP fee APR for one pool.
+pub async fn get_pool_analytics(
+    state: &AppState,
+    pool_id: &str,
+    max_steps: Option<usize>,
+) -> Result<amm::PoolAnalytics, String> {
+    let client = state.require_node_client().await?;
+    let pool = super::find_pool(&client, pool_id).await?;
+
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let max_steps = max_steps.unwrap_or(DEFAULT_TRACE_STEPS).max(1);
+
+    let (client, pool) = (&client, &pool);
+    let min_timestamp = now_ms.saturating_sub(HISTORY_WINDOW_MS);
+    let snapshots = refresh_history(
+        &state.store().await,
+        POOL_HISTORY_FILE,
+        pool_id,
+        &pool.box_id,
+        max_steps,
+        None,
+        MAX_CACHED_SNAPSHOTS,
+        |start, known, steps| async move {
+            amm::trace_pool_history(
+                client,
+                pool.pool_type,
+                &pool.pool_id,
+                &start,
+                &known,
+                steps,
+                min_timestamp,
+            )
+            .await
+            .map_err(|e| e.to_string())
+        },
+    )
+    .await?;
+
+    let oldest_first: Vec<PoolSnapshot> = snapshots.into_iter().rev().collect();
+    Ok(amm::pool_analytics(pool, &oldest_first, now_ms))
+}
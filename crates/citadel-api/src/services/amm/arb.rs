@@ -4,7 +4,7 @@ use crate::services::error::IntoServiceError;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArbChainLegDto {
     pub pool_id: String,
@@ -13,7 +13,7 @@ pub struct ArbChainLegDto {
     pub summary: amm::DirectSwapSummary,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ArbChainBuildResponse {
     pub legs: Vec<ArbChainLegDto>,
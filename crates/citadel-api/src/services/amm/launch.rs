@@ -0,0 +1,244 @@
+//! Guided pool launch: price-anchored initial ratio, duplicate-pair check and
+//! the bootstrap + create txs built as one 0-conf chain.
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::error::IntoServiceError;
+use crate::AppState;
+use citadel_core::TxIntent;
+use ergo_node_client::NodeClient;
+
+/// Where the launch price comes from.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PriceAnchor {
+    /// Y per X in display units.
+    Price { price: f64 },
+    /// Spot price of an existing pool trading the same pair.
+    Pool { pool_id: String },
+    /// ERG/USD from the SigmaUSD oracle, with Y taken as a 1 USD token.
+    /// N2T only.
+    OracleUsd,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolLaunchRequest {
+    pub pool_type: String,
+    /// None for N2T (X is ERG).
+    pub x_token_id: Option<String>,
+    pub y_token_id: String,
+    /// X held by the pool box (nanoERG for N2T).
+    pub x_amount: u64,
+    pub anchor: PriceAnchor,
+    pub fee_percent: f64,
+    /// Launch even if a pool for the same pair already exists.
+    #[serde(default)]
+    pub allow_duplicate: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExistingPoolDto {
+    pub pool_id: String,
+    pub pool_type: String,
+    pub fee_num: i32,
+    pub fee_denom: i32,
+    /// Y per X in display units, oriented like the requested pair.
+    pub price: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolLaunchPreviewResponse {
+    pub plan: amm::PoolLaunchPlan,
+    pub x_decimals: u8,
+    pub y_decimals: u8,
+    /// Pools already trading this pair (either orientation).
+    pub existing_pools: Vec<ExistingPoolDto>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolLaunchLegDto {
+    pub tx_id: String,
+    pub unsigned_tx: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolLaunchBuildResponse {
+    /// Bootstrap then create; sign each and submit in order.
+    pub legs: Vec<PoolLaunchLegDto>,
+    pub plan: amm::PoolLaunchPlan,
+    pub pool_nft_id: String,
+    pub lp_token_id: String,
+    pub intent: TxIntent,
+}
+
+pub async fn preview_pool_launch(
+    state: &AppState,
+    request: PoolLaunchRequest,
+) -> Result<PoolLaunchPreviewResponse, String> {
+    let client = state.require_node_client().await?;
+    resolve_launch(state, &client, &request).await
+}
+
+/// Build both launch txs. Refuses a pair that already has a pool unless
+/// `allow_duplicate` is set, since a second pool splits its liquidity.
+pub async fn build_pool_launch_tx(
+    state: &AppState,
+    request: PoolLaunchRequest,
+    user_utxos: Vec<ergo_tx::Eip12InputBox>,
+    current_height: i32,
+) -> Result<PoolLaunchBuildResponse, String> {
+    let client = state.require_node_client().await?;
+    let preview = resolve_launch(state, &client, &request).await?;
+    if !preview.existing_pools.is_empty() && !request.allow_duplicate {
+        let ids: Vec<&str> = preview
+            .existing_pools
+            .iter()
+            .map(|p| p.pool_id.as_str())
+            .collect();
+        return Err(format!(
+            "A pool for this pair already exists: {}",
+            ids.join(", ")
+        ));
+    }
+
+    let user_ergo_tree = user_utxos
+        .first()
+        .map(|u| u.ergo_tree.clone())
+        .ok_or("No wallet UTXOs")?;
+    let params = preview.plan.setup_params().into_service()?;
+    let build = amm::build_pool_launch_chain(&params, &user_utxos, &user_ergo_tree, current_height)
+        .into_service()?;
+
+    let legs = [&build.bootstrap, &build.create]
+        .into_iter()
+        .map(|leg| {
+            Ok(PoolLaunchLegDto {
+                tx_id: leg.tx_id.clone(),
+                unsigned_tx: serde_json::to_value(&leg.unsigned_tx)
+                    .map_err(|e| format!("Failed to serialize leg tx: {}", e))?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(PoolLaunchBuildResponse {
+        legs,
+        intent: build.intent(&params),
+        pool_nft_id: build.create_summary.pool_nft_id.clone(),
+        lp_token_id: build.bootstrap_summary.lp_token_id.clone(),
+        plan: preview.plan,
+    })
+}
+
+async fn resolve_launch(
+    state: &AppState,
+    client: &NodeClient,
+    request: &PoolLaunchRequest,
+) -> Result<PoolLaunchPreviewResponse, String> {
+    let pool_type = match request.pool_type.as_str() {
+        "N2T" => amm::state::PoolType::N2T,
+        "T2T" => amm::state::PoolType::T2T,
+        _ => return Err(format!("Invalid pool type: {}", request.pool_type)),
+    };
+    let fee_num = ((1.0 - request.fee_percent / 100.0)
+        * amm::constants::fees::DEFAULT_FEE_DENOM as f64)
+        .round() as i32;
+    if fee_num <= 0 || fee_num >= amm::constants::fees::DEFAULT_FEE_DENOM {
+        return Err("Fee must be between 0% and 100% (exclusive)".to_string());
+    }
+
+    let x_decimals = match &request.x_token_id {
+        Some(id) => token_decimals(client, id).await?,
+        None => 9,
+    };
+    let y_decimals = token_decimals(client, &request.y_token_id).await?;
+    let decimal_shift = 10f64.powi(y_decimals as i32 - x_decimals as i32);
+
+    let pools = amm::discover_pools(client).await.into_service()?;
+    let x_id = request.x_token_id.as_deref();
+    let y_id = request.y_token_id.as_str();
+    let existing_pools: Vec<ExistingPoolDto> = pools
+        .iter()
+        .filter_map(|p| {
+            let raw_price = pair_raw_price(p, x_id, y_id)?;
+            Some(ExistingPoolDto {
+                pool_id: p.pool_id.clone(),
+                pool_type: format!("{:?}", p.pool_type),
+                fee_num: p.fee_num,
+                fee_denom: p.fee_denom,
+                price: raw_price / decimal_shift,
+            })
+        })
+        .collect();
+
+    let price = match &request.anchor {
+        PriceAnchor::Price { price } => *price,
+        PriceAnchor::Pool { pool_id } => existing_pools
+            .iter()
+            .find(|p| &p.pool_id == pool_id)
+            .map(|p| p.price)
+            .ok_or_else(|| format!("Pool {} does not trade this pair", pool_id))?,
+        PriceAnchor::OracleUsd => {
+            if request.x_token_id.is_some() {
+                return Err("Oracle anchoring is only available for N2T pools".to_string());
+            }
+            crate::services::sigmausd::get_oracle_price(state)
+                .await?
+                .erg_usd
+        }
+    };
+
+    let plan = amm::plan_pool_launch(
+        pool_type,
+        request.x_token_id.clone(),
+        request.y_token_id.clone(),
+        request.x_amount,
+        price,
+        x_decimals,
+        y_decimals,
+        fee_num,
+    )
+    .into_service()?;
+
+    Ok(PoolLaunchPreviewResponse {
+        plan,
+        x_decimals,
+        y_decimals,
+        existing_pools,
+    })
+}
+
+async fn token_decimals(client: &NodeClient, token_id: &str) -> Result<u8, String> {
+    let info = client
+        .get_token_info(token_id)
+        .await
+        .map_err(|e| format!("Cannot fetch token {}: {}", token_id, e))?;
+    Ok(info.decimals.unwrap_or(0) as u8)
+}
+
+/// Raw Y per raw X of `pool` if it trades the (x, y) pair in either
+/// orientation. `x = None` is ERG.
+fn pair_raw_price(pool: &amm::AmmPool, x: Option<&str>, y: &str) -> Option<f64> {
+    let (pool_x, pool_x_reserve) = match pool.pool_type {
+        amm::state::PoolType::N2T => (None, pool.erg_reserves?),
+        amm::state::PoolType::T2T => {
+            let tx = pool.token_x.as_ref()?;
+            (Some(tx.token_id.as_str()), tx.amount)
+        }
+    };
+    let (pool_y, pool_y_reserve) = (pool.token_y.token_id.as_str(), pool.token_y.amount);
+    if pool_x_reserve == 0 || pool_y_reserve == 0 {
+        return None;
+    }
+    if pool_x == x && pool_y == y {
+        Some(pool_y_reserve as f64 / pool_x_reserve as f64)
+    } else if x.is_some() && pool_x == Some(y) && Some(pool_y) == x {
+        Some(pool_x_reserve as f64 / pool_y_reserve as f64)
+    } else {
+        None
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::services::error::IntoServiceError;
 use crate::AppState;
+use citadel_core::TxIntent;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -34,6 +35,7 @@ pub struct AmmLpRedeemPreviewResponse {
 pub struct AmmLpBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: serde_json::Value,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Serialize)]
@@ -152,6 +154,7 @@ pub async fn build_amm_lp_deposit_tx(
     Ok(AmmLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
         summary: summary_json,
+        intent: result.summary.intent(&pool),
     })
 }
 
@@ -191,6 +194,7 @@ pub async fn build_amm_lp_deposit_order(
     Ok(AmmLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
         summary: summary_json,
+        intent: result.summary.intent(&pool),
     })
 }
 
@@ -269,6 +273,7 @@ pub async fn build_amm_lp_redeem_tx(
     Ok(AmmLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
         summary: summary_json,
+        intent: result.summary.intent(&pool),
     })
 }
 
@@ -306,6 +311,7 @@ pub async fn build_amm_lp_redeem_order(
     Ok(AmmLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
         summary: summary_json,
+        intent: result.summary.intent(&pool),
     })
 }
 
@@ -402,6 +408,7 @@ pub fn build_pool_bootstrap_tx(
     Ok(AmmLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
         summary: summary_json,
+        intent: result.summary.intent(&params),
     })
 }
 
@@ -456,5 +463,10 @@ pub fn build_pool_create_tx(
     Ok(AmmLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
         summary: summary_json,
+        intent: ergo_tx::derive_intent(
+            &result.unsigned_tx,
+            "Spectrum",
+            format!("Create {} pool", pool_type),
+        ),
     })
 }
@@ -4,21 +4,29 @@
 //! Split into private submodules for readability; the public surface is the
 //! flat `services::amm` API consumed by the Tauri command wrappers.
 
+pub mod analytics;
 pub mod arb;
+pub mod launch;
 pub mod lp;
+pub mod orders;
 pub mod quote;
 pub mod router;
+pub mod sources;
 pub mod swap;
 
+pub use analytics::*;
 pub use arb::*;
+pub use launch::*;
 pub use lp::*;
+pub use orders::*;
 pub use quote::*;
 pub use router::*;
+pub use sources::*;
 pub use swap::*;
 
 // amm-crate response types surfaced through the façade so Tauri command
 // wrappers stay free of direct `amm::` references.
-pub use amm::{CircularArbSnapshot, DepthTiers, OracleArbSnapshot};
+pub use amm::{CircularArbSnapshot, DepthTiers, OracleArbSnapshot, PoolAnalytics};
 
 use crate::services::error::IntoServiceError;
 use ergo_node_client::NodeClient;
@@ -32,6 +40,36 @@ pub(crate) async fn find_pool(client: &NodeClient, pool_id: &str) -> Result<amm:
         .ok_or_else(|| format!("Pool not found: {}", pool_id))
 }
 
+/// [`find_pool`] advanced past its pending mempool swaps when
+/// `include_mempool` is set. The pending state carries the box to spend.
+pub(crate) async fn find_pool_with_mempool(
+    client: &NodeClient,
+    pool_id: &str,
+    include_mempool: bool,
+) -> Result<(amm::AmmPool, Option<amm::PendingPoolState>), String> {
+    let mut pool = find_pool(client, pool_id).await?;
+    if !include_mempool {
+        return Ok((pool, None));
+    }
+    let txs = amm::fetch_pool_mempool(client).await;
+    let mut projection = amm::project_pools(std::slice::from_mut(&mut pool), &txs);
+    let pending = projection.pending.remove(pool_id);
+    Ok((pool, pending))
+}
+
+/// All pools, projected past pending mempool swaps when `include_mempool`.
+pub(crate) async fn discover_pools_with_mempool(
+    client: &NodeClient,
+    include_mempool: bool,
+) -> Result<(Vec<amm::AmmPool>, amm::MempoolProjection), String> {
+    if include_mempool {
+        amm::discover_pools_projected(client).await.into_service()
+    } else {
+        let pools = amm::discover_pools(client).await.into_service()?;
+        Ok((pools, amm::MempoolProjection::default()))
+    }
+}
+
 pub(crate) fn parse_swap_input(
     input_type: &str,
     amount: u64,
@@ -0,0 +1,168 @@
+//! Open order scan across all wallet addresses and bulk refund of stale orders.
+
+use serde::Serialize;
+
+use crate::services::error::IntoServiceError;
+use crate::AppState;
+use citadel_core::TxIntent;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrderDto {
+    pub box_id: String,
+    pub tx_id: String,
+    pub kind: amm::OrderKind,
+    pub pool_id: String,
+    pub input: Option<serde_json::Value>,
+    pub min_output: Option<u64>,
+    pub value_nano_erg: u64,
+    /// (token_id, amount) locked in the order box
+    pub tokens: Vec<(String, u64)>,
+    pub created_height: i32,
+    pub age_blocks: u32,
+    pub owner_ergo_tree: String,
+    pub stale: Vec<amm::StaleReason>,
+}
+
+impl OpenOrderDto {
+    fn from_order(o: &amm::UserOrder, current_height: u32) -> Self {
+        let b = &o.order_box;
+        Self {
+            box_id: b.box_id.clone(),
+            tx_id: b.transaction_id.clone(),
+            kind: o.kind,
+            pool_id: o.pool_id.clone(),
+            input: o.input.as_ref().and_then(|i| serde_json::to_value(i).ok()),
+            min_output: o.min_output,
+            value_nano_erg: b.value.parse().unwrap_or(0),
+            tokens: b
+                .assets
+                .iter()
+                .map(|a| (a.token_id.clone(), a.amount.parse().unwrap_or(0)))
+                .collect(),
+            created_height: b.creation_height,
+            age_blocks: current_height.saturating_sub(b.creation_height.max(0) as u32),
+            owner_ergo_tree: o.owner_ergo_tree.clone(),
+            stale: o.stale.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrdersResponse {
+    pub orders: Vec<OpenOrderDto>,
+    pub stale_count: usize,
+    pub current_height: u32,
+    pub max_age_blocks: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkRefundTx {
+    pub unsigned_tx: serde_json::Value,
+    pub summary: amm::BatchRefundSummary,
+    pub intent: TxIntent,
+}
+
+/// Independent refund txs, each spending at most
+/// [`amm::MAX_BATCH_REFUND_INPUTS`] order boxes.
+#[derive(Debug, Serialize)]
+pub struct BulkRefundBuildResponse {
+    pub txs: Vec<BulkRefundTx>,
+}
+
+/// Every open swap/deposit/redeem order of the connected wallet, with stale
+/// ones flagged. Orders older than `max_age_blocks` (default ~1 day) or whose
+/// min output the pool can no longer meet count as stale.
+pub async fn scan_open_orders(
+    state: &AppState,
+    max_age_blocks: Option<u32>,
+) -> Result<OpenOrdersResponse, String> {
+    let client = state.require_node_client().await?;
+    let wallet = state
+        .connected_wallet()
+        .await
+        .ok_or("Wallet not connected")?;
+    let trees: Vec<String> = wallet
+        .addresses
+        .iter()
+        .filter_map(|a| ergo_node_client::address_to_ergo_tree(a))
+        .collect();
+    let max_age_blocks = max_age_blocks.unwrap_or(amm::DEFAULT_STALE_ORDER_BLOCKS);
+
+    let current_height = client.current_height().await.into_service()? as u32;
+    let pools = amm::discover_pools(&client).await.into_service()?;
+    let orders = amm::scan_user_orders(&client, &trees, &pools, current_height, max_age_blocks)
+        .await
+        .into_service()?;
+
+    Ok(OpenOrdersResponse {
+        stale_count: orders.iter().filter(|o| o.is_stale()).count(),
+        orders: orders
+            .iter()
+            .map(|o| OpenOrderDto::from_order(o, current_height))
+            .collect(),
+        current_height,
+        max_age_blocks,
+    })
+}
+
+/// Refund txs for the given order boxes, paid out to `user_ergo_tree`.
+/// Each box is re-fetched and must still be an order owned by the wallet.
+pub async fn build_bulk_refund_tx(
+    state: &AppState,
+    box_ids: Vec<String>,
+    user_ergo_tree: String,
+) -> Result<BulkRefundBuildResponse, String> {
+    let client = state.require_node_client().await?;
+    let wallet = state
+        .connected_wallet()
+        .await
+        .ok_or("Wallet not connected")?;
+    let trees: Vec<String> = wallet
+        .addresses
+        .iter()
+        .filter_map(|a| ergo_node_client::address_to_ergo_tree(a))
+        .collect();
+
+    let mut proxy_boxes = Vec::with_capacity(box_ids.len());
+    for box_id in &box_ids {
+        let order_box = client.get_eip12_box_by_id(box_id).await.map_err(|e| {
+            format!(
+                "Cannot fetch order box {}: {}. It may have been spent.",
+                box_id, e
+            )
+        })?;
+        if amm::parse_user_order(&order_box, &trees).is_none() {
+            return Err(format!(
+                "Box {} is not an order owned by this wallet",
+                box_id
+            ));
+        }
+        proxy_boxes.push(order_box);
+    }
+
+    let current_height = client.current_height().await.into_service()? as i32;
+    let results = amm::build_batch_refund_txs_eip12(&proxy_boxes, &user_ergo_tree, current_height)
+        .into_service()?;
+
+    let count = results.len();
+    let txs = results
+        .into_iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let mut intent = result.summary.intent();
+            if count > 1 {
+                intent = intent.note(format!("Refund transaction {} of {}", i + 1, count));
+            }
+            Ok(BulkRefundTx {
+                unsigned_tx: serde_json::to_value(&result.unsigned_tx)
+                    .map_err(|e| format!("Failed to serialize tx: {}", e))?,
+                intent,
+                summary: result.summary,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(BulkRefundBuildResponse { txs })
+}
@@ -1,7 +1,12 @@
 //! Pool discovery and single-pool swap quotes.
 
-use crate::dto::{AmmPoolDto, AmmPoolsResponse, SwapQuoteResponse};
+use std::collections::HashMap;
+
+use citadel_core::TokenStatus;
+
+use crate::dto::{AmmPoolDto, AmmPoolsResponse, AmmTokenDto, SwapQuoteResponse};
 use crate::services::error::IntoServiceError;
+use crate::services::tokens::screen_tokens;
 use crate::AppState;
 
 pub async fn get_amm_pools(state: &AppState) -> Result<AmmPoolsResponse, String> {
@@ -9,7 +14,20 @@ pub async fn get_amm_pools(state: &AppState) -> Result<AmmPoolsResponse, String>
 
     let pools = amm::discover_pools(&client).await.into_service()?;
 
-    let pool_dtos: Vec<AmmPoolDto> = pools.into_iter().map(Into::into).collect();
+    let tokens: Vec<(String, Option<String>)> = pools
+        .iter()
+        .flat_map(|p| p.token_x.iter().chain(std::iter::once(&p.token_y)))
+        .map(|t| (t.token_id.clone(), t.name.clone()))
+        .collect();
+    let statuses = screen_tokens(state, &client, Some(&pools), &tokens).await;
+
+    let mut pool_dtos: Vec<AmmPoolDto> = pools.into_iter().map(Into::into).collect();
+    for pool in &mut pool_dtos {
+        if let Some(token_x) = &mut pool.token_x {
+            apply_status(token_x, &statuses);
+        }
+        apply_status(&mut pool.token_y, &statuses);
+    }
     let count = pool_dtos.len();
 
     Ok(AmmPoolsResponse {
@@ -18,12 +36,20 @@ pub async fn get_amm_pools(state: &AppState) -> Result<AmmPoolsResponse, String>
     })
 }
 
+fn apply_status(token: &mut AmmTokenDto, statuses: &HashMap<String, TokenStatus>) {
+    if let Some(status) = statuses.get(&token.token_id) {
+        token.verified = status.verified;
+        token.warning = status.warning.clone();
+    }
+}
+
 pub async fn get_amm_quote(
     state: &AppState,
     pool_id: &str,
     input_type: &str,
     amount: u64,
     token_id: Option<String>,
+    include_mempool: Option<bool>,
 ) -> Result<SwapQuoteResponse, String> {
     if amount == 0 {
         return Err("Amount must be greater than 0".to_string());
@@ -31,12 +57,24 @@ pub async fn get_amm_quote(
 
     let client = state.require_node_client().await?;
 
-    let pool = super::find_pool(&client, pool_id).await?;
+    let (pool, pending) =
+        super::find_pool_with_mempool(&client, pool_id, include_mempool.unwrap_or(false)).await?;
 
     let input = super::parse_swap_input(input_type, amount, token_id.clone())?;
 
     match amm::quote_swap(&pool, &input) {
-        Some(quote) => Ok(quote.into()),
+        Some(quote) => {
+            let mut response: SwapQuoteResponse = quote.into();
+            response.pending_tx_ids = pending.map(|p| p.pending_tx_ids).unwrap_or_default();
+            let output = [(
+                response.output.token_id.clone(),
+                response.output.name.clone(),
+            )];
+            let statuses =
+                screen_tokens(state, &client, Some(std::slice::from_ref(&pool)), &output).await;
+            apply_status(&mut response.output, &statuses);
+            Ok(response)
+        }
         None => {
             // Token → ERG that would drain below min box: surface the max size.
             if matches!(pool.pool_type, amm::PoolType::N2T) && input_type == "token" {
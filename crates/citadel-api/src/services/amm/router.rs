@@ -1,8 +1,14 @@
 //! Multi-hop routing, depth, SigUSD acquisition compare, and circular arb scan.
 
+use std::collections::HashMap;
+
+use citadel_core::TokenStatus;
+
 use crate::services::error::IntoServiceError;
+use crate::services::tokens::screen_tokens;
 use crate::AppState;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn find_swap_routes(
     state: &AppState,
     source_token: &str,
@@ -12,18 +18,21 @@ pub async fn find_swap_routes(
     max_routes: Option<usize>,
     slippage: Option<f64>,
     min_rate: Option<f64>,
+    include_mempool: Option<bool>,
 ) -> Result<serde_json::Value, String> {
     if input_amount == 0 {
         return Err("Amount must be greater than 0".to_string());
     }
 
     let client = state.require_node_client().await?;
-    let pools = amm::discover_pools(&client).await.into_service()?;
+    let (pools, projection) =
+        super::discover_pools_with_mempool(&client, include_mempool.unwrap_or(false)).await?;
 
     let mut graph = amm::build_pool_graph(&pools, amm::DEFAULT_MIN_LIQUIDITY_NANO);
     // The requested pair is always routable even if its pools sit below the
     // liquidity floor -- price impact on the quote conveys thinness.
     amm::ensure_direct_pair_edges(&mut graph, &pools, source_token, target_token);
+    add_protocol_sources(state, &client, &mut graph).await?;
     let max_hops = max_hops.unwrap_or(3);
     let max_routes = max_routes.unwrap_or(5);
     let slippage_pct = slippage.unwrap_or(0.5);
@@ -39,7 +48,11 @@ pub async fn find_swap_routes(
 
     let route_quotes: Vec<amm::RouteQuote> = routes
         .into_iter()
-        .map(|r| amm::make_route_quote(r, slippage_pct))
+        .map(|r| {
+            let mut quote = amm::make_route_quote(r, slippage_pct);
+            projection.flag_route(&mut quote);
+            quote
+        })
         .collect();
 
     let depth_tiers = amm::calculate_all_depth_tiers(&graph, source_token);
@@ -58,12 +71,14 @@ pub async fn find_swap_routes(
 
     let max_swap =
         amm::max_swap_hint_if_needed(&graph, source_token, target_token, input_amount, max_hops);
+    let tokens = route_token_statuses(state, &client, &pools, &route_quotes).await;
 
     let response = serde_json::json!({
         "routes": route_quotes,
         "depth_tiers": depth_tiers,
         "split": split,
         "max_swap": max_swap,
+        "tokens": tokens,
     });
 
     Ok(response)
@@ -86,22 +101,21 @@ pub async fn find_split_route(
 
     let mut graph = amm::build_pool_graph(&pools, amm::DEFAULT_MIN_LIQUIDITY_NANO);
     amm::ensure_direct_pair_edges(&mut graph, &pools, source_token, target_token);
+    add_protocol_sources(state, &client, &mut graph).await?;
     let max_hops = 3;
     let max_splits = max_splits.unwrap_or(2);
     let _slippage_pct = slippage.unwrap_or(0.5);
 
-    let paths = amm::find_paths(&graph, source_token, target_token, max_hops);
-
-    let mut quoted: Vec<(usize, u64)> = paths
-        .iter()
-        .enumerate()
-        .filter_map(|(i, p)| amm::quote_route(p, input_amount).map(|r| (i, r.total_output)))
-        .collect();
-    quoted.sort_by_key(|b| std::cmp::Reverse(b.1));
-    quoted.truncate(max_splits);
-
-    let top_paths: Vec<Vec<amm::PoolEdge>> =
-        quoted.iter().map(|(i, _)| paths[*i].clone()).collect();
+    // Best first at this trade size. The beam also bounds labels kept per
+    // token during the search, so use the same width as the detailed split.
+    let top_paths = amm::find_k_best_paths(
+        &graph,
+        source_token,
+        target_token,
+        input_amount,
+        max_hops,
+        (max_splits * 2).max(amm::DEFAULT_PATH_BEAM),
+    );
 
     let split = amm::optimize_split(&top_paths, input_amount, max_splits);
 
@@ -172,6 +186,7 @@ pub async fn get_sigusd_arb_snapshot(
     ))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn find_swap_routes_by_output(
     state: &AppState,
     source_token: &str,
@@ -180,16 +195,19 @@ pub async fn find_swap_routes_by_output(
     max_hops: Option<usize>,
     max_routes: Option<usize>,
     slippage: Option<f64>,
+    include_mempool: Option<bool>,
 ) -> Result<serde_json::Value, String> {
     if desired_output == 0 {
         return Err("Amount must be greater than 0".to_string());
     }
 
     let client = state.require_node_client().await?;
-    let pools = amm::discover_pools(&client).await.into_service()?;
+    let (pools, projection) =
+        super::discover_pools_with_mempool(&client, include_mempool.unwrap_or(false)).await?;
 
     let mut graph = amm::build_pool_graph(&pools, amm::DEFAULT_MIN_LIQUIDITY_NANO);
     amm::ensure_direct_pair_edges(&mut graph, &pools, source_token, target_token);
+    add_protocol_sources(state, &client, &mut graph).await?;
     let max_hops = max_hops.unwrap_or(3);
     let max_routes = max_routes.unwrap_or(5);
     let slippage_pct = slippage.unwrap_or(0.5);
@@ -205,19 +223,60 @@ pub async fn find_swap_routes_by_output(
 
     let route_quotes: Vec<amm::RouteQuote> = routes
         .into_iter()
-        .map(|r| amm::make_route_quote(r, slippage_pct))
+        .map(|r| {
+            let mut quote = amm::make_route_quote(r, slippage_pct);
+            projection.flag_route(&mut quote);
+            quote
+        })
         .collect();
 
     let depth_tiers = amm::calculate_all_depth_tiers(&graph, source_token);
+    let tokens = route_token_statuses(state, &client, &pools, &route_quotes).await;
 
     let response = serde_json::json!({
         "routes": route_quotes,
         "depth_tiers": depth_tiers,
+        "tokens": tokens,
     });
 
     Ok(response)
 }
 
+/// Verification status of every token on the quoted routes, keyed by ID.
+async fn route_token_statuses(
+    state: &AppState,
+    client: &ergo_node_client::NodeClient,
+    pools: &[amm::AmmPool],
+    quotes: &[amm::RouteQuote],
+) -> HashMap<String, TokenStatus> {
+    let tokens: Vec<(String, Option<String>)> = quotes
+        .iter()
+        .flat_map(|q| &q.route.hops)
+        .flat_map(|hop| {
+            [
+                (hop.token_in.clone(), hop.token_in_name.clone()),
+                (hop.token_out.clone(), hop.token_out_name.clone()),
+            ]
+        })
+        .collect();
+    screen_tokens(state, client, Some(pools), &tokens).await
+}
+
+/// Adds protocol banks and the Dexy LPs so routes can mix them with Spectrum
+/// pools. Routes through these hops are quote-only: execution goes through
+/// the protocol's own builder.
+async fn add_protocol_sources(
+    state: &AppState,
+    client: &ergo_node_client::NodeClient,
+    graph: &mut amm::PoolGraph,
+) -> Result<(), String> {
+    let capabilities = client.require_capabilities().await?;
+    let network = state.config().await.network;
+    let sources = super::sources::load_liquidity_sources(client, &capabilities, network).await;
+    amm::add_liquidity_sources(graph, &sources);
+    Ok(())
+}
+
 async fn fetch_sigmausd_params(state: &AppState) -> Result<amm::SigmaUsdParams, String> {
     let client = state.require_node_client().await?;
     let capabilities = client.require_capabilities().await?;
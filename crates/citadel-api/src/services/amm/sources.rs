@@ -0,0 +1,172 @@
+//! Protocol banks and foreign pools as router edges (`amm::LiquiditySource`).
+
+use std::sync::Arc;
+
+use amm::{BankSource, CfmmSource, LiquiditySource, SourceToken};
+use citadel_core::Network;
+use ergo_node_client::{NodeCapabilities, NodeClient};
+
+/// HodlCoin tokens are minted with ERG's precision.
+const HODL_DECIMALS: u8 = 9;
+
+/// SigmaUSD bank legs that are open at the current reserve ratio.
+pub fn sigmausd_sources(
+    ids: &sigmausd::NftIds,
+    state: &sigmausd::SigmaUsdState,
+) -> Vec<Arc<dyn LiquiditySource>> {
+    const PROTOCOL: &str = "SigmaUSD";
+    let fee = sigmausd::params::FEE_BPS as u64;
+    let sigusd = SourceToken::new(&ids.sigusd_token, "SigUSD", 2);
+    let sigrsv = SourceToken::new(&ids.sigrsv_token, "SigRSV", 0);
+    // Oracle rate is per USD = 100 SigUSD units; SigRSV is priced per unit
+    let usd_nano = state.oracle_erg_per_usd_nano.max(0) as u64;
+    let rsv_nano = state.sigrsv_price_nano.max(0) as u64;
+
+    let mut sources: Vec<Arc<dyn LiquiditySource>> = Vec::new();
+    if state.can_mint_sigusd {
+        sources.push(Arc::new(
+            BankSource::mint(&ids.bank_nft, PROTOCOL, sigusd.clone(), usd_nano, 100)
+                .with_fee(fee, 10_000)
+                .with_max_tokens(state.max_sigusd_mintable.max(0) as u64),
+        ));
+    }
+    if state.can_redeem_sigusd {
+        sources.push(Arc::new(
+            BankSource::redeem(&ids.bank_nft, PROTOCOL, sigusd, usd_nano, 100)
+                .with_fee(fee, 10_000)
+                .with_max_tokens(state.sigusd_circulating.max(0) as u64),
+        ));
+    }
+    if state.can_mint_sigrsv {
+        sources.push(Arc::new(
+            BankSource::mint(&ids.bank_nft, PROTOCOL, sigrsv.clone(), rsv_nano, 1)
+                .with_fee(fee, 10_000)
+                .with_max_tokens(state.max_sigrsv_mintable.max(0) as u64),
+        ));
+    }
+    if state.can_redeem_sigrsv {
+        sources.push(Arc::new(
+            BankSource::redeem(&ids.bank_nft, PROTOCOL, sigrsv, rsv_nano, 1)
+                .with_fee(fee, 10_000)
+                .with_max_tokens(state.max_sigrsv_redeemable.max(0) as u64),
+        ));
+    }
+    sources
+}
+
+/// Dexy free mint (bank + buyback fees, capped by the period allowance) and
+/// both directions of the Dexy LP.
+pub fn dexy_sources(ids: &dexy::DexyIds, state: &dexy::DexyState) -> Vec<Arc<dyn LiquiditySource>> {
+    const PROTOCOL: &str = "Dexy";
+    let variant = ids.variant;
+    let token = SourceToken::new(&ids.dexy_token, variant.token_name(), variant.decimals());
+
+    let mut sources: Vec<Arc<dyn LiquiditySource>> = Vec::new();
+    if state.can_mint && state.free_mint_available > 0 && state.oracle_rate_nano > 0 {
+        sources.push(Arc::new(
+            BankSource::mint(
+                &ids.bank_nft,
+                PROTOCOL,
+                token.clone(),
+                state.oracle_rate_nano as u64,
+                1,
+            )
+            .with_fee(
+                (dexy::BANK_FEE_NUM + dexy::BUYBACK_FEE_NUM) as u64,
+                dexy::FEE_DENOM as u64,
+            )
+            .with_max_tokens(state.free_mint_available.min(state.dexy_in_bank) as u64),
+        ));
+    }
+    if state.lp_erg_reserves > 0 && state.lp_dexy_reserves > 0 {
+        let fee_denom = dexy::LP_SWAP_FEE_DENOM as i32;
+        for leg in CfmmSource::pair(
+            &ids.lp_nft,
+            PROTOCOL,
+            (SourceToken::erg(), state.lp_erg_reserves as u64),
+            (token, state.lp_dexy_reserves as u64),
+            fee_denom - dexy::LP_SWAP_FEE_NUM as i32,
+            fee_denom,
+        ) {
+            sources.push(Arc::new(leg));
+        }
+    }
+    sources
+}
+
+/// HodlCoin banks: mint at the bank price, burn minus bank and dev fees.
+pub fn hodlcoin_sources(bank: &hodlcoin::HodlBankState) -> Vec<Arc<dyn LiquiditySource>> {
+    const PROTOCOL: &str = "HodlCoin";
+    // Token-backed banks trade hodlTokens against their reserve token, not ERG
+    if bank.is_token_backed() {
+        return Vec::new();
+    }
+    let price = hodlcoin::hodl_price(
+        bank.reserve_nano_erg,
+        bank.circulating_supply,
+        bank.precision_factor,
+    );
+    if price <= 0 {
+        return Vec::new();
+    }
+    let name = bank
+        .hodl_token_name
+        .clone()
+        .unwrap_or_else(|| "hodlToken".to_string());
+    let token = SourceToken::new(&bank.hodl_token_id, name, HODL_DECIMALS);
+    let (price, per) = (price as u64, bank.precision_factor.max(1) as u64);
+
+    let mint = BankSource::mint(
+        &bank.singleton_token_id,
+        PROTOCOL,
+        token.clone(),
+        price,
+        per,
+    )
+    .with_max_tokens(bank.hodl_tokens_in_bank.max(0) as u64);
+    let burn = BankSource::redeem(&bank.singleton_token_id, PROTOCOL, token, price, per)
+        .with_fee(
+            (bank.bank_fee_num + bank.dev_fee_num) as u64,
+            hodlcoin::constants::FEE_DENOM as u64,
+        )
+        .with_max_tokens(bank.circulating_supply.max(0) as u64);
+    vec![Arc::new(mint), Arc::new(burn)]
+}
+
+/// Every source available on `network`. Protocols that fail to load are
+/// left out, so routing falls back to Spectrum pools for their tokens.
+pub(crate) async fn load_liquidity_sources(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    network: Network,
+) -> Vec<Arc<dyn LiquiditySource>> {
+    let mut sources = Vec::new();
+
+    if let Some(ids) = sigmausd::NftIds::for_network(network) {
+        match sigmausd::fetch_sigmausd_state(client, capabilities, &ids).await {
+            Ok(state) => sources.extend(sigmausd_sources(&ids, &state)),
+            Err(e) => tracing::warn!("SigmaUSD liquidity source unavailable: {}", e),
+        }
+    }
+
+    for variant in [dexy::DexyVariant::Gold, dexy::DexyVariant::Usd] {
+        let Some(ids) = dexy::DexyIds::for_variant(variant, network) else {
+            continue;
+        };
+        match dexy::fetch_dexy_state(client, capabilities, &ids).await {
+            Ok(state) => sources.extend(dexy_sources(&ids, &state)),
+            Err(e) => tracing::warn!(
+                "{} liquidity source unavailable: {}",
+                variant.token_name(),
+                e
+            ),
+        }
+    }
+
+    match hodlcoin::discover_banks(client).await {
+        Ok(banks) => sources.extend(banks.iter().flat_map(hodlcoin_sources)),
+        Err(e) => tracing::warn!("HodlCoin liquidity sources unavailable: {}", e),
+    }
+
+    sources
+}
@@ -2,6 +2,7 @@
 
 use crate::services::error::IntoServiceError;
 use crate::AppState;
+use citadel_core::TxIntent;
 use serde::Serialize;
 
 #[derive(Debug, Serialize)]
@@ -24,6 +25,7 @@ pub struct SwapPreviewResponse {
 pub struct SwapBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: SwapTxSummaryDto,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,12 +53,20 @@ pub struct DirectSwapPreviewResponse {
     pub miner_fee_nano: u64,
     pub citadel_fee_nano: u64,
     pub total_erg_cost_nano: u64,
+    /// Unconfirmed swaps the quoted pool state builds on, oldest first.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pending_tx_ids: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
 pub struct DirectSwapBuildResponse {
     pub unsigned_tx: serde_json::Value,
     pub summary: DirectSwapSummaryDto,
+    pub intent: TxIntent,
+    /// Unconfirmed swaps the spent pool box depends on; the tx is rejected
+    /// if any of them is dropped.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub pending_tx_ids: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -181,6 +191,7 @@ pub async fn build_swap_tx(
 
     let unsigned_tx_json = serde_json::to_value(&result.unsigned_tx)
         .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    let intent = result.summary.intent(&pool, &request.input);
 
     Ok(SwapBuildResponse {
         unsigned_tx: unsigned_tx_json,
@@ -194,10 +205,12 @@ pub async fn build_swap_tx(
             citadel_fee_nano: result.summary.citadel_fee_nano,
             total_erg_cost: result.summary.total_erg_cost,
         },
+        intent,
     })
 }
 
 /// No execution fee -- direct swaps have no bot involved.
+#[allow(clippy::too_many_arguments)]
 pub async fn preview_direct_swap(
     state: &AppState,
     pool_id: &str,
@@ -205,6 +218,7 @@ pub async fn preview_direct_swap(
     amount: u64,
     token_id: Option<String>,
     slippage: Option<f64>,
+    include_mempool: Option<bool>,
 ) -> Result<DirectSwapPreviewResponse, String> {
     if amount == 0 {
         return Err("Amount must be greater than 0".to_string());
@@ -212,7 +226,8 @@ pub async fn preview_direct_swap(
 
     let client = state.require_node_client().await?;
 
-    let pool = super::find_pool(&client, pool_id).await?;
+    let (pool, pending) =
+        super::find_pool_with_mempool(&client, pool_id, include_mempool.unwrap_or(false)).await?;
 
     let input = super::parse_swap_input(input_type, amount, token_id)?;
 
@@ -244,6 +259,7 @@ pub async fn preview_direct_swap(
         miner_fee_nano,
         citadel_fee_nano,
         total_erg_cost_nano,
+        pending_tx_ids: pending.map(|p| p.pending_tx_ids).unwrap_or_default(),
     })
 }
 
@@ -260,6 +276,8 @@ pub async fn build_direct_swap_tx(
     recipient_address: Option<String>,
     // Optional custom miner fee in nanoERG. None = network default.
     miner_fee_nano: Option<u64>,
+    // Spend the latest unconfirmed pool box instead of the confirmed one.
+    include_mempool: Option<bool>,
 ) -> Result<DirectSwapBuildResponse, String> {
     if amount == 0 {
         return Err("Amount must be greater than 0".to_string());
@@ -267,12 +285,19 @@ pub async fn build_direct_swap_tx(
 
     let client = state.require_node_client().await?;
 
-    let pool = super::find_pool(&client, pool_id).await?;
-
-    let pool_box = client
-        .get_eip12_box_by_id(&pool.box_id)
-        .await
-        .map_err(|e| format!("Failed to fetch pool box: {}", e))?;
+    let (pool, pending) =
+        super::find_pool_with_mempool(&client, pool_id, include_mempool.unwrap_or(false)).await?;
+
+    let (pool_box, pending_tx_ids) = match pending {
+        Some(p) => (p.pool_box, p.pending_tx_ids),
+        None => {
+            let pool_box = client
+                .get_eip12_box_by_id(&pool.box_id)
+                .await
+                .map_err(|e| format!("Failed to fetch pool box: {}", e))?;
+            (pool_box, Vec::new())
+        }
+    };
 
     let user_ergo_tree = user_utxos[0].ergo_tree.clone();
 
@@ -298,6 +323,7 @@ pub async fn build_direct_swap_tx(
 
     let unsigned_tx_json = serde_json::to_value(&result.unsigned_tx)
         .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    let intent = result.summary.intent(&pool, &input);
 
     Ok(DirectSwapBuildResponse {
         unsigned_tx: unsigned_tx_json,
@@ -311,6 +337,8 @@ pub async fn build_direct_swap_tx(
             citadel_fee_nano: result.summary.citadel_fee_nano,
             total_erg_cost: result.summary.total_erg_cost,
         },
+        intent,
+        pending_tx_ids,
     })
 }
 
@@ -336,6 +364,7 @@ pub async fn build_swap_refund_tx(
 
     Ok(SwapBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(),
         summary: SwapTxSummaryDto {
             input_amount: result.summary.refunded_erg,
             input_token: "Refund".to_string(),
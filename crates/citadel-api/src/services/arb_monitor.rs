@@ -0,0 +1,389 @@
+//! Background arbitrage monitor: circular and oracle-vs-DEX arbs re-evaluated
+//! once per block.
+//!
+//! Each pass records the opportunities above the configured profit threshold
+//! (including empty blocks) so the history shows how often they appear. The
+//! most profitable circular arb is pre-built for the connected wallet, ready
+//! to sign leg by leg with `start_arb_leg_sign` / `submit_arb_chain`.
+
+use serde::Serialize;
+
+use super::amm::ArbChainBuildResponse;
+use super::error::{IntoServiceError, ServiceResult};
+use crate::dto::{
+    ArbKind, ArbMonitorConfig, ArbMonitorResponse, ArbMonitorStats, ArbObservation,
+    ArbOpportunityRecord,
+};
+use crate::AppState;
+
+/// Monitored blocks kept in the history (about two weeks).
+const MAX_HISTORY_BLOCKS: usize = 10_000;
+/// Pool filters matching `get_sigusd_arb_snapshot`, which needs small pools.
+const ORACLE_MIN_LIQUIDITY_NANO: u64 = 1_000_000_000;
+const ORACLE_MAX_POOLS_PER_PAIR: usize = 10;
+const ORACLE_MAX_HOPS: usize = 3;
+/// Smallest input probed for an oracle arb (0.01 ERG).
+const ORACLE_MIN_INPUT_NANO: u64 = 10_000_000;
+
+/// An opportunity found by [`process_arb_monitor`], emitted to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArbAlert {
+    pub height: u64,
+    #[serde(flatten)]
+    pub opportunity: ArbOpportunityRecord,
+    /// Unsigned legs for the connected wallet (best circular arb only).
+    pub chain: Option<ArbChainBuildResponse>,
+    /// Why `chain` could not be built, when it was attempted.
+    pub chain_error: Option<String>,
+}
+
+pub async fn get_arb_monitor(state: &AppState) -> ServiceResult<ArbMonitorResponse> {
+    let monitor = state.arb_monitor().await;
+    let history = state.arb_monitor_history().await;
+    Ok(ArbMonitorResponse {
+        stats: monitor_stats(&history),
+        config: monitor.config,
+        last_checked_height: monitor.last_checked_height,
+        history,
+    })
+}
+
+pub async fn set_arb_monitor_config(
+    state: &AppState,
+    config: ArbMonitorConfig,
+) -> ServiceResult<ArbMonitorConfig> {
+    if !(2..=6).contains(&config.max_hops) {
+        return Err("Max hops must be between 2 and 6".to_string());
+    }
+    if config.min_net_profit_nano < 0 {
+        return Err("Minimum profit must not be negative".to_string());
+    }
+    state
+        .set_arb_monitor_config(config.clone())
+        .await
+        .into_service()?;
+    Ok(config)
+}
+
+pub async fn clear_arb_monitor_history(state: &AppState) -> ServiceResult<()> {
+    state.clear_arb_monitor_history().await.into_service()
+}
+
+/// Evaluate arbs for the current block if the monitor is enabled and the
+/// block has not been checked yet. Returns the opportunities found, best
+/// first.
+pub async fn process_arb_monitor(state: &AppState) -> ServiceResult<Vec<ArbAlert>> {
+    let monitor = state.arb_monitor().await;
+    if !monitor.config.enabled {
+        return Ok(Vec::new());
+    }
+    let client = state.require_node_client().await?;
+    let height = client.current_height().await.into_service()?;
+    if height <= monitor.last_checked_height {
+        return Ok(Vec::new());
+    }
+    let config = monitor.config;
+
+    let pools = amm::discover_pools(&client).await.into_service()?;
+    let graph = amm::build_pool_graph(&pools, amm::DEFAULT_MIN_LIQUIDITY_NANO);
+    let circular = amm::find_circular_arbs(&graph, config.max_hops, config.min_net_profit_nano);
+
+    let mut opportunities: Vec<ArbOpportunityRecord> = circular
+        .windows
+        .iter()
+        .map(|arb| ArbOpportunityRecord {
+            kind: ArbKind::Circular,
+            path_label: arb.path_label.clone(),
+            pool_ids: arb.pool_ids.clone(),
+            input_nano: arb.optimal_input_nano,
+            net_profit_nano: arb.net_profit_nano,
+        })
+        .collect();
+    match oracle_opportunities(state, &client, &pools).await {
+        Ok(found) => opportunities.extend(
+            found
+                .into_iter()
+                .filter(|o| o.net_profit_nano >= config.min_net_profit_nano),
+        ),
+        Err(e) => tracing::debug!("Oracle arb check skipped: {}", e),
+    }
+    opportunities.sort_by_key(|o| std::cmp::Reverse(o.net_profit_nano));
+
+    let observation = ArbObservation {
+        height,
+        timestamp: now_ms(),
+        opportunities: opportunities.clone(),
+    };
+    state
+        .record_arb_observation(observation, MAX_HISTORY_BLOCKS)
+        .await
+        .into_service()?;
+
+    let best_circular = opportunities
+        .iter()
+        .position(|o| o.kind == ArbKind::Circular);
+    let mut alerts = Vec::with_capacity(opportunities.len());
+    for (i, opportunity) in opportunities.into_iter().enumerate() {
+        let (chain, chain_error) = if config.build_chains && Some(i) == best_circular {
+            match build_chain(state, &client, &opportunity, height, &config).await {
+                Ok(chain) => (Some(chain), None),
+                Err(e) => (None, Some(e)),
+            }
+        } else {
+            (None, None)
+        };
+        alerts.push(ArbAlert {
+            height,
+            opportunity,
+            chain,
+            chain_error,
+        });
+    }
+    Ok(alerts)
+}
+
+/// ERG -> SigUSD routes cheaper than the oracle, redeemed at the bank, at the
+/// input that maximizes profit. Empty when SigUSD redemption is closed.
+async fn oracle_opportunities(
+    state: &AppState,
+    client: &ergo_node_client::NodeClient,
+    pools: &[amm::AmmPool],
+) -> Result<Vec<ArbOpportunityRecord>, String> {
+    let ids = sigmausd::NftIds::for_network(state.network().await)
+        .ok_or("SigmaUSD is not deployed on this network")?;
+    let capabilities = client.require_capabilities().await?;
+    let bank = sigmausd::fetch_sigmausd_state(client, &capabilities, &ids)
+        .await
+        .into_service()?;
+    if !bank.can_redeem_sigusd || bank.oracle_erg_per_usd_nano <= 0 {
+        return Ok(Vec::new());
+    }
+    let oracle_rate_usd_per_erg = 1e9 / bank.oracle_erg_per_usd_nano as f64;
+
+    let graph = amm::build_pool_graph_with_limit(
+        pools,
+        ORACLE_MIN_LIQUIDITY_NANO,
+        ORACLE_MAX_POOLS_PER_PAIR,
+    );
+    let snapshot =
+        amm::calculate_oracle_arb_snapshot(&graph, &ids.sigusd_token, oracle_rate_usd_per_erg, 2);
+    if snapshot.windows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let paths = amm::find_paths(
+        &graph,
+        amm::ERG_TOKEN_ID,
+        &ids.sigusd_token,
+        ORACLE_MAX_HOPS,
+    );
+    let mut found = Vec::new();
+    for window in &snapshot.windows {
+        let Some(path) = paths.iter().find(|p| {
+            p.len() == window.pool_ids.len()
+                && p.iter()
+                    .zip(&window.pool_ids)
+                    .all(|(e, id)| &e.pool.pool_id == id)
+        }) else {
+            continue;
+        };
+        let profit = |input: u64| {
+            amm::quote_route(path, input)
+                .map(|r| {
+                    redeem_profit(
+                        r.total_output,
+                        input,
+                        path.len(),
+                        bank.oracle_erg_per_usd_nano,
+                    )
+                })
+                .unwrap_or(i64::MIN)
+        };
+        if let Some((input_nano, net_profit_nano)) =
+            maximize(profit, ORACLE_MIN_INPUT_NANO, window.max_erg_input_nano)
+        {
+            found.push(ArbOpportunityRecord {
+                kind: ArbKind::Oracle,
+                path_label: format!("{} \u{2192} ERG (bank)", window.path_label),
+                pool_ids: window.pool_ids.clone(),
+                input_nano,
+                net_profit_nano,
+            });
+        }
+    }
+    Ok(found)
+}
+
+/// Profit of redeeming `sigusd_cents` bought with `input_nano` over `hops`
+/// pools: bank proceeds after its fee, less the input and all miner fees.
+pub(crate) fn redeem_profit(
+    sigusd_cents: u64,
+    input_nano: u64,
+    hops: usize,
+    nanoerg_per_usd: i64,
+) -> i64 {
+    let redeemed = sigmausd::erg_from_redeem_sigusd(sigusd_cents as i64, nanoerg_per_usd);
+    let fees = amm::miner_fees_for_hops(hops + 1);
+    redeemed.net_amount - input_nano as i64 - fees as i64
+}
+
+/// Ternary search for the best input in `[lo, hi]` of a unimodal profit
+/// curve. `None` when nothing in range is profitable.
+pub(crate) fn maximize(profit: impl Fn(u64) -> i64, lo: u64, hi: u64) -> Option<(u64, i64)> {
+    if hi <= lo {
+        return None;
+    }
+    let (mut a, mut b) = (lo, hi);
+    while b - a > 1_000_000 {
+        let m1 = a + (b - a) / 3;
+        let m2 = b - (b - a) / 3;
+        if profit(m1) < profit(m2) {
+            a = m1;
+        } else {
+            b = m2;
+        }
+    }
+    let input = (a + b) / 2;
+    let best = profit(input);
+    (best > 0).then_some((input, best))
+}
+
+async fn build_chain(
+    state: &AppState,
+    client: &ergo_node_client::NodeClient,
+    opportunity: &ArbOpportunityRecord,
+    height: u64,
+    config: &ArbMonitorConfig,
+) -> Result<ArbChainBuildResponse, String> {
+    let wallet = state
+        .connected_wallet()
+        .await
+        .ok_or_else(|| "Connect a wallet to pre-build arb chains".to_string())?;
+    let utxos = client
+        .get_effective_utxos_multi(&wallet.addresses)
+        .await
+        .into_service()?;
+    if utxos.is_empty() {
+        return Err("Wallet has no spendable UTXOs".to_string());
+    }
+    super::amm::build_arb_chain_tx(
+        state,
+        opportunity.pool_ids.clone(),
+        opportunity.input_nano,
+        utxos,
+        height as i32,
+        Some(config.min_net_profit_nano),
+    )
+    .await
+}
+
+pub(crate) fn monitor_stats(history: &[ArbObservation]) -> ArbMonitorStats {
+    let mut stats = ArbMonitorStats {
+        blocks_scanned: history.len(),
+        ..Default::default()
+    };
+    for observation in history {
+        let Some(best) = observation
+            .opportunities
+            .iter()
+            .map(|o| o.net_profit_nano)
+            .max()
+        else {
+            continue;
+        };
+        stats.blocks_with_opportunity += 1;
+        stats.best_net_profit_nano = stats.best_net_profit_nano.max(best);
+        stats.total_best_net_profit_nano += best;
+        for o in &observation.opportunities {
+            match o.kind {
+                ArbKind::Circular => stats.circular_opportunities += 1,
+                ArbKind::Oracle => stats.oracle_opportunities += 1,
+            }
+        }
+    }
+    stats
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::ArbMonitorState;
+
+    fn record(kind: ArbKind, profit: i64) -> ArbOpportunityRecord {
+        ArbOpportunityRecord {
+            kind,
+            path_label: "ERG → X → ERG".to_string(),
+            pool_ids: vec!["p1".to_string()],
+            input_nano: 1_000_000_000,
+            net_profit_nano: profit,
+        }
+    }
+
+    fn observation(height: u64, opportunities: Vec<ArbOpportunityRecord>) -> ArbObservation {
+        ArbObservation {
+            height,
+            timestamp: 0,
+            opportunities,
+        }
+    }
+
+    #[test]
+    fn test_stats_count_blocks_and_kinds() {
+        let history = vec![
+            observation(1, vec![]),
+            observation(
+                2,
+                vec![record(ArbKind::Circular, 50), record(ArbKind::Oracle, 20)],
+            ),
+            observation(3, vec![record(ArbKind::Oracle, 70)]),
+        ];
+        let stats = monitor_stats(&history);
+        assert_eq!(stats.blocks_scanned, 3);
+        assert_eq!(stats.blocks_with_opportunity, 2);
+        assert_eq!(stats.circular_opportunities, 1);
+        assert_eq!(stats.oracle_opportunities, 2);
+        assert_eq!(stats.best_net_profit_nano, 70);
+        assert_eq!(stats.total_best_net_profit_nano, 120);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut monitor = ArbMonitorState::default();
+        let mut history = Vec::new();
+        for h in 0..(MAX_HISTORY_BLOCKS as u64 + 5) {
+            monitor.push_observation(&mut history, observation(h, vec![]), MAX_HISTORY_BLOCKS);
+        }
+        assert_eq!(history.len(), MAX_HISTORY_BLOCKS);
+        assert_eq!(history[0].height, 5);
+        assert_eq!(monitor.last_checked_height, MAX_HISTORY_BLOCKS as u64 + 4);
+    }
+
+    #[test]
+    fn test_redeem_profit_nets_bank_and_miner_fees() {
+        // $10 at 0.5 ERG/USD = 5 ERG, less 2% bank fee, bought for 4.5 ERG
+        let profit = redeem_profit(1_000, 4_500_000_000, 1, 500_000_000);
+        let fees = amm::miner_fees_for_hops(2) as i64;
+        assert_eq!(profit, 4_900_000_000 - 4_500_000_000 - fees);
+    }
+
+    #[test]
+    fn test_maximize_finds_peak() {
+        let peak = 3_000_000_000i64;
+        let (input, best) = maximize(
+            |x| 1_000_000_000 - (x as i64 - peak).abs() / 2,
+            10_000_000,
+            10_000_000_000,
+        )
+        .unwrap();
+        assert!((input as i64 - peak).abs() < 2_000_000);
+        assert!(best > 999_000_000);
+        assert!(maximize(|_| -1, 10_000_000, 10_000_000_000).is_none());
+    }
+}
@@ -1,6 +1,7 @@
 //! Token burn transaction building.
 
 use citadel_core::constants::{MIN_BOX_VALUE_NANO, TX_FEE_NANO};
+use citadel_core::TxIntent;
 use serde::{Deserialize, Serialize};
 
 use super::error::{IntoServiceError, ServiceResult};
@@ -15,6 +16,7 @@ pub struct BurnBuildResponse {
     pub miner_fee: i64,
     pub citadel_fee_nano: i64,
     pub change_erg: i64,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Serialize)]
@@ -25,6 +27,7 @@ pub struct MultiBurnBuildResponse {
     pub miner_fee: i64,
     pub citadel_fee_nano: i64,
     pub change_erg: i64,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -73,6 +76,7 @@ pub fn build_burn_tx(
 
     Ok(BurnBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(),
         burned_token_id: result.summary.burned_token_id,
         burned_amount: result.summary.burned_amount,
         miner_fee: result.summary.miner_fee,
@@ -123,6 +127,7 @@ pub fn build_multi_burn_tx(
 
     Ok(MultiBurnBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(),
         burned_tokens: result
             .summary
             .burned_tokens
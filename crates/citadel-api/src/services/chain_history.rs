@@ -0,0 +1,227 @@
+//! Shared caching for protocol histories traced along a box chain (a bank or
+//! pool NFT's successive boxes), newest first.
+//!
+//! The cache holds disjoint segments of the chain, newest segment first. A
+//! walk that stops on its step limit or a height bound before joining the
+//! cached range starts a new segment instead of replacing the old one, and a
+//! later backfill that reaches an older segment joins the two.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::services::error::ServiceResult;
+use crate::store::JsonStore;
+
+/// One traced box of a chain.
+pub(crate) trait ChainEntry {
+    fn box_id(&self) -> &str;
+    fn height(&self) -> u64;
+}
+
+impl ChainEntry for amm::PoolSnapshot {
+    fn box_id(&self) -> &str {
+        &self.box_id
+    }
+    fn height(&self) -> u64 {
+        self.height
+    }
+}
+
+impl ChainEntry for sigmausd::BankSnapshot {
+    fn box_id(&self) -> &str {
+        &self.box_id
+    }
+    fn height(&self) -> u64 {
+        self.height
+    }
+}
+
+impl ChainEntry for hodlcoin::BankPricePoint {
+    fn box_id(&self) -> &str {
+        &self.box_id
+    }
+    fn height(&self) -> u64 {
+        self.height
+    }
+}
+
+/// Bring the cached history under `key` in store file `file` up to
+/// `tip_box_id` and, when `since_height` is below the cached range, back to
+/// it, spending at most `max_steps` trace steps. Returns the history joined
+/// to the tip, newest first.
+///
+/// `trace(start_box_id, known_box_ids, max_steps)` walks back from the start
+/// box (returned first) until a known box, and returns the entries newest
+/// first and whether the walk ended on a known box.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn refresh_history<T, F, Fut>(
+    store: &JsonStore,
+    file: &str,
+    key: &str,
+    tip_box_id: &str,
+    max_steps: usize,
+    since_height: Option<u64>,
+    max_len: usize,
+    trace: F,
+) -> ServiceResult<Vec<T>>
+where
+    T: ChainEntry + Clone + Serialize + DeserializeOwned,
+    F: Fn(String, HashSet<String>, usize) -> Fut,
+    Fut: Future<Output = ServiceResult<(Vec<T>, bool)>>,
+{
+    let mut cache: HashMap<String, Vec<Vec<T>>> = store.load(file);
+    let mut segments = cache.remove(key).unwrap_or_default();
+
+    let (traced, reached_known) =
+        trace(tip_box_id.to_string(), known_ids(&segments, 0), max_steps).await?;
+    if traced.is_empty() && !reached_known {
+        // The tip box did not parse; leave the cache as it is
+        return Ok(Vec::new());
+    }
+    let mut steps_left = max_steps.saturating_sub(traced.len());
+    add_tip(&mut segments, traced, reached_known);
+
+    // Backfill below the newest segment when older history is asked for. The
+    // walk restarts at its oldest box, which comes back first.
+    while let (Some(min_height), Some(oldest)) = (since_height, segments[0].last()) {
+        if oldest.height() <= min_height || steps_left == 0 {
+            break;
+        }
+        let (older, reached_known) = trace(
+            oldest.box_id().to_string(),
+            known_ids(&segments, 1),
+            steps_left + 1,
+        )
+        .await?;
+        steps_left = steps_left.saturating_sub(older.len().saturating_sub(1));
+        if !add_backfill(&mut segments, older, reached_known) {
+            break;
+        }
+    }
+
+    cap_segments(&mut segments, max_len);
+    let history = segments.first().cloned().unwrap_or_default();
+    cache.insert(key.to_string(), segments);
+    if let Err(e) = store.save(file, &cache) {
+        tracing::warn!("Failed to cache {}: {}", file, e);
+    }
+    Ok(history)
+}
+
+/// Box IDs of every segment from `from` on.
+fn known_ids<T: ChainEntry>(segments: &[Vec<T>], from: usize) -> HashSet<String> {
+    segments
+        .iter()
+        .skip(from)
+        .flatten()
+        .map(|e| e.box_id().to_string())
+        .collect()
+}
+
+/// Put entries traced from the tip in front. Walking back from the tip, the
+/// first cached box met is the head of the newest segment, so a walk that
+/// reached the cache joins that segment; otherwise it starts a new one.
+fn add_tip<T>(segments: &mut Vec<Vec<T>>, traced: Vec<T>, reached_known: bool) {
+    match segments.first_mut() {
+        Some(newest) if reached_known => {
+            let older = std::mem::replace(newest, traced);
+            newest.extend(older);
+        }
+        _ => segments.insert(0, traced),
+    }
+}
+
+/// Append entries traced back from the oldest box of the newest segment
+/// (that box first). A walk that reached the cache met the head of the next
+/// segment, which is joined on. Returns whether the walk reached it, i.e.
+/// whether backfilling can continue past it.
+fn add_backfill<T>(segments: &mut Vec<Vec<T>>, older: Vec<T>, reached_known: bool) -> bool {
+    segments[0].extend(older.into_iter().skip(1));
+    if reached_known && segments.len() > 1 {
+        let next = segments.remove(1);
+        segments[0].extend(next);
+        return true;
+    }
+    false
+}
+
+/// Keep at most `max_len` entries, dropping the oldest.
+fn cap_segments<T>(segments: &mut Vec<Vec<T>>, mut max_len: usize) {
+    segments.retain_mut(|segment| {
+        segment.truncate(max_len);
+        max_len -= segment.len();
+        !segment.is_empty()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Entry(String);
+
+    impl ChainEntry for Entry {
+        fn box_id(&self) -> &str {
+            &self.0
+        }
+        fn height(&self) -> u64 {
+            0
+        }
+    }
+
+    fn entries(ids: &[&str]) -> Vec<Entry> {
+        ids.iter().map(|id| Entry(id.to_string())).collect()
+    }
+
+    fn ids(segments: &[Vec<Entry>]) -> Vec<Vec<&str>> {
+        segments
+            .iter()
+            .map(|s| s.iter().map(|e| e.0.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_tip_joins_newest_segment() {
+        let mut segments = vec![entries(&["b", "a"])];
+        add_tip(&mut segments, entries(&["d", "c"]), true);
+        assert_eq!(ids(&segments), vec![vec!["d", "c", "b", "a"]]);
+
+        // The tip box itself was already cached
+        add_tip(&mut segments, Vec::new(), true);
+        assert_eq!(ids(&segments), vec![vec!["d", "c", "b", "a"]]);
+    }
+
+    #[test]
+    fn test_tip_across_gap_keeps_cache() {
+        let mut segments = vec![entries(&["b", "a"])];
+        add_tip(&mut segments, entries(&["z", "y"]), false);
+        assert_eq!(ids(&segments), vec![vec!["z", "y"], vec!["b", "a"]]);
+    }
+
+    #[test]
+    fn test_backfill_joins_next_segment() {
+        let mut segments = vec![entries(&["z", "y"]), entries(&["b", "a"])];
+        assert!(add_backfill(&mut segments, entries(&["y", "x", "c"]), true));
+        assert_eq!(ids(&segments), vec![vec!["z", "y", "x", "c", "b", "a"]]);
+    }
+
+    #[test]
+    fn test_backfill_short_of_next_segment() {
+        let mut segments = vec![entries(&["z", "y"]), entries(&["b", "a"])];
+        assert!(!add_backfill(&mut segments, entries(&["y", "x"]), false));
+        assert_eq!(ids(&segments), vec![vec!["z", "y", "x"], vec!["b", "a"]]);
+    }
+
+    #[test]
+    fn test_cap_drops_oldest() {
+        let mut segments = vec![entries(&["z", "y"]), entries(&["c", "b", "a"])];
+        cap_segments(&mut segments, 3);
+        assert_eq!(ids(&segments), vec![vec!["z", "y"], vec!["c"]]);
+        cap_segments(&mut segments, 2);
+        assert_eq!(ids(&segments), vec![vec!["z", "y"]]);
+    }
+}
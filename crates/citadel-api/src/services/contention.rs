@@ -0,0 +1,137 @@
+//! Singleton contention: pending bank/LP successors and invalidated builds.
+//!
+//! SigmaUSD, Dexy and HodlCoin txs all spend one singleton protocol box, so two
+//! users building on the same confirmed box race and one is rejected. Builds
+//! can opt into chaining on the latest unconfirmed successor, and a built tx
+//! can be checked for inputs a competing tx already spent so the frontend
+//! rebuilds it with the same parameters.
+
+use dexy::{DexyIds, DexyVariant};
+use ergo_node_client::{mempool, NodeClient};
+use sigmausd::NftIds;
+
+use crate::dto::{ContentionStatusResponse, SingletonStatus, SpentInputDto, TxInputCheckResponse};
+use crate::services::error::{IntoServiceError, ServiceResult};
+use crate::AppState;
+
+/// Node client for a protocol build: follows singleton boxes through the
+/// mempool when `build_on_pending` is set.
+pub async fn build_client(state: &AppState, build_on_pending: bool) -> ServiceResult<NodeClient> {
+    let client = state.require_node_client().await?;
+    Ok(if build_on_pending {
+        client.following_mempool()
+    } else {
+        client
+    })
+}
+
+/// Pending spends of a protocol's singleton boxes.
+///
+/// `protocol` is "sigmausd", "dexy" (with `key` the variant) or "hodlcoin"
+/// (with `key` the bank singleton token ID).
+pub async fn get_contention_status(
+    state: &AppState,
+    protocol: &str,
+    key: Option<&str>,
+) -> ServiceResult<ContentionStatusResponse> {
+    let client = state.require_node_client().await?;
+    let network = state.config().await.network;
+
+    // (label, token ID, confirmed box ID)
+    let mut confirmed: Vec<(&str, String, String)> = Vec::new();
+    match protocol {
+        "sigmausd" => {
+            let capabilities = client.require_capabilities().await?;
+            let ids = NftIds::for_network(network)
+                .ok_or_else(|| format!("SigmaUSD not available on {:?}", network))?;
+            let bank = client
+                .get_box_by_token_id(&capabilities, &citadel_core::TokenId::new(&ids.bank_nft))
+                .await
+                .into_service()?;
+            confirmed.push(("bank", ids.bank_nft, bank.box_id().to_string()));
+        }
+        "dexy" => {
+            let capabilities = client.require_capabilities().await?;
+            let variant = key
+                .unwrap_or_default()
+                .parse::<DexyVariant>()
+                .map_err(|_| "Dexy contention status needs variant 'gold' or 'usd'".to_string())?;
+            let ids = DexyIds::for_variant(variant, network)
+                .ok_or_else(|| "Dexy not available on this network".to_string())?;
+            for (label, nft) in [("bank", ids.bank_nft), ("lp", ids.lp_nft)] {
+                let ergo_box = client
+                    .get_box_by_token_id(&capabilities, &citadel_core::TokenId::new(&nft))
+                    .await
+                    .into_service()?;
+                confirmed.push((label, nft, ergo_box.box_id().to_string()));
+            }
+        }
+        "hodlcoin" => {
+            let singleton = key.ok_or_else(|| "HodlCoin bank singleton required".to_string())?;
+            let bank = super::hodlcoin::find_bank(state, &client, singleton).await?;
+            confirmed.push(("bank", bank.singleton_token_id, bank.bank_box_id));
+        }
+        other => return Err(format!("Unknown protocol: {}", other)),
+    }
+
+    let mempool_txs = client.get_mempool_transactions().await.into_service()?;
+    let singletons: Vec<SingletonStatus> = confirmed
+        .into_iter()
+        .map(|(label, token_id, box_id)| {
+            let successor = mempool::follow_singleton(&box_id, &token_id, &mempool_txs);
+            SingletonStatus {
+                label: label.to_string(),
+                latest_box_id: successor
+                    .as_ref()
+                    .map_or_else(|| box_id.clone(), |s| s.box_id.clone()),
+                pending_tx_ids: successor.map(|s| s.pending_tx_ids).unwrap_or_default(),
+                token_id,
+                confirmed_box_id: box_id,
+            }
+        })
+        .collect();
+
+    Ok(ContentionStatusResponse {
+        protocol: protocol.to_string(),
+        pending_count: singletons.iter().map(|s| s.pending_tx_ids.len()).sum(),
+        singletons,
+    })
+}
+
+/// Checks whether a built, unsubmitted tx can still be accepted.
+///
+/// `invalidated` is set once a competing tx spends any of its inputs; the
+/// caller should rebuild (typically with `build_on_pending`) and re-sign.
+pub async fn check_tx_inputs(
+    state: &AppState,
+    unsigned_tx: &serde_json::Value,
+) -> ServiceResult<TxInputCheckResponse> {
+    let input_ids: Vec<String> = unsigned_tx["inputs"]
+        .as_array()
+        .ok_or_else(|| "Transaction has no inputs".to_string())?
+        .iter()
+        .filter_map(|input| input["boxId"].as_str().map(str::to_string))
+        .collect();
+    if input_ids.is_empty() {
+        return Err("Transaction has no inputs".to_string());
+    }
+
+    let client = state.require_node_client().await?;
+    let spends = client
+        .check_inputs_unspent(&input_ids)
+        .await
+        .into_service()?;
+
+    Ok(TxInputCheckResponse {
+        invalidated: !spends.competing.is_empty(),
+        submitted_tx_id: spends.own_tx_id,
+        spent_inputs: spends
+            .competing
+            .into_iter()
+            .map(|s| SpentInputDto {
+                box_id: s.box_id,
+                spent_by: s.spent_by,
+            })
+            .collect(),
+    })
+}
@@ -1,22 +1,28 @@
-//! Dexy use-case orchestration: bank/LP state, mint preview/build, swap and LP deposit/redeem.
+//! Dexy use-case orchestration: bank/LP state, mint preview/build, swap, LP deposit/redeem
+//! and keeper (maintenance) actions.
 
 use crate::dto::{
-    DexyBuildResponse, DexyLpBuildResponse, DexyLpPreviewResponse, DexyPreviewResponse,
-    DexyStateResponse, DexySwapBuildResponse, DexySwapPreviewResponse, TxSummaryDto,
+    DexyBuildResponse, DexyKeeperBuildResponse, DexyKeeperStatusResponse, DexyLpBuildResponse,
+    DexyLpPreviewResponse, DexyPreviewResponse, DexyStateResponse, DexySwapBuildResponse,
+    DexySwapPreviewResponse, TxSummaryDto,
 };
+use crate::services::contention;
 use crate::services::error::{IntoServiceError, ServiceResult};
+use crate::services::oracle;
 use crate::AppState;
 use citadel_core::constants::{MIN_BOX_VALUE_NANO, TX_FEE_NANO};
 use dexy::{
     calculator::{calculate_lp_deposit, calculate_lp_redeem, can_redeem_lp, cost_to_mint_dexy},
     constants::{DexyIds, DexyVariant},
     fetch::{
-        fetch_dexy_state, fetch_lp_tx_context, fetch_tx_context as fetch_dexy_tx_context,
-        parse_lp_box, LpAction,
+        fetch_arb_mint_tx_context, fetch_dexy_state, fetch_keeper_tx_context, fetch_lp_tx_context,
+        fetch_tx_context as fetch_dexy_tx_context, parse_lp_box, LpAction,
     },
     rates::DexyRates,
     tx_builder::{
-        build_mint_dexy_tx, validate_mint_dexy, LpDepositRequest, LpRedeemRequest, MintDexyRequest,
+        build_arb_mint_dexy_tx, build_keeper_tx as build_dexy_keeper_tx, build_mint_dexy_tx,
+        evaluate_keeper_actions, validate_mint_dexy, KeeperAction, KeeperRequest, LpDepositRequest,
+        LpRedeemRequest, MintDexyRequest,
     },
 };
 
@@ -164,6 +170,7 @@ pub async fn preview_mint(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn build_mint(
     state: &AppState,
     variant: &str,
@@ -172,10 +179,18 @@ pub async fn build_mint(
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
     recipient_address: Option<String>,
+    mint_path: Option<&str>,
+    build_on_pending: bool,
 ) -> ServiceResult<DexyBuildResponse> {
     let dexy_variant = parse_variant(variant)?;
+    let arb_mint = match mint_path.unwrap_or("free_mint") {
+        "free_mint" => false,
+        "arb_mint" => true,
+        other => return Err(format!("Invalid mint path: {}", other)),
+    };
+    let oracle_warnings = oracle::guard_build(state, oracle::dexy_feed(dexy_variant)).await?;
 
-    let client = state.require_node_client().await?;
+    let client = contention::build_client(state, build_on_pending).await?;
     let capabilities = client.require_capabilities().await?;
 
     let config = state.config().await;
@@ -188,10 +203,6 @@ pub async fn build_mint(
 
     validate_mint_dexy(amount, &dexy_state).into_service()?;
 
-    let tx_ctx = fetch_dexy_tx_context(&client, &capabilities, &ids)
-        .await
-        .into_service()?;
-
     let user_ergo_tree = user_utxos[0].ergo_tree.clone();
     let recipient_ergo_tree = recipient_ergo_tree(&recipient_address)?;
 
@@ -205,11 +216,23 @@ pub async fn build_mint(
         recipient_ergo_tree,
     };
 
-    let result = build_mint_dexy_tx(&mint_request, &tx_ctx, &dexy_state).into_service()?;
+    let result = if arb_mint {
+        let tx_ctx = fetch_arb_mint_tx_context(&client, &capabilities, &ids)
+            .await
+            .into_service()?;
+        build_arb_mint_dexy_tx(&mint_request, &tx_ctx, &dexy_state).into_service()?
+    } else {
+        let tx_ctx = fetch_dexy_tx_context(&client, &capabilities, &ids)
+            .await
+            .into_service()?;
+        build_mint_dexy_tx(&mint_request, &tx_ctx, &dexy_state).into_service()?
+    };
 
     let unsigned_tx_json = serde_json::to_value(&result.unsigned_tx)
         .map_err(|e| format!("Failed to serialize tx: {}", e))?;
 
+    let intent = result.summary.intent(&ids);
+
     Ok(DexyBuildResponse {
         unsigned_tx: unsigned_tx_json,
         summary: TxSummaryDto {
@@ -220,6 +243,8 @@ pub async fn build_mint(
             protocol_fee_nano: "0".to_string(), // Dexy has no protocol fee
             tx_fee_nano: result.summary.tx_fee_nano.to_string(),
         },
+        intent,
+        oracle_warnings,
     })
 }
 
@@ -324,6 +349,7 @@ pub async fn build_swap(
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
     recipient_address: Option<String>,
+    build_on_pending: bool,
 ) -> ServiceResult<DexySwapBuildResponse> {
     let dexy_variant = parse_variant(variant)?;
 
@@ -333,7 +359,7 @@ pub async fn build_swap(
         _ => return Err(format!("Invalid direction: {}", direction)),
     };
 
-    let client = state.require_node_client().await?;
+    let client = contention::build_client(state, build_on_pending).await?;
     let capabilities = client.require_capabilities().await?;
     let config = state.config().await;
     let ids = DexyIds::for_variant(dexy_variant, config.network)
@@ -370,6 +396,7 @@ pub async fn build_swap(
 
     Ok(DexySwapBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(&ids),
         summary: result.summary,
     })
 }
@@ -455,10 +482,11 @@ pub async fn build_lp_deposit(
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
     recipient_address: Option<String>,
+    build_on_pending: bool,
 ) -> ServiceResult<DexyLpBuildResponse> {
     let dexy_variant = parse_variant(variant)?;
 
-    let client = state.require_node_client().await?;
+    let client = contention::build_client(state, build_on_pending).await?;
     let capabilities = client.require_capabilities().await?;
     let config = state.config().await;
     let ids = DexyIds::for_variant(dexy_variant, config.network)
@@ -496,6 +524,7 @@ pub async fn build_lp_deposit(
 
     Ok(DexyLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(&ids),
         summary: result.summary,
     })
 }
@@ -603,10 +632,11 @@ pub async fn build_lp_redeem(
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
     recipient_address: Option<String>,
+    build_on_pending: bool,
 ) -> ServiceResult<DexyLpBuildResponse> {
     let dexy_variant = parse_variant(variant)?;
 
-    let client = state.require_node_client().await?;
+    let client = contention::build_client(state, build_on_pending).await?;
     let capabilities = client.require_capabilities().await?;
     let config = state.config().await;
     let ids = DexyIds::for_variant(dexy_variant, config.network)
@@ -643,6 +673,81 @@ pub async fn build_lp_redeem(
 
     Ok(DexyLpBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(&ids),
+        summary: result.summary,
+    })
+}
+
+/// Which keeper (maintenance) actions the current chain state admits.
+pub async fn get_keeper_status(
+    state: &AppState,
+    variant: &str,
+) -> ServiceResult<DexyKeeperStatusResponse> {
+    let dexy_variant = parse_variant(variant)?;
+
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+
+    let config = state.config().await;
+    let ids = DexyIds::for_variant(dexy_variant, config.network)
+        .ok_or_else(|| format!("Dexy {} not available on {:?}", variant, config.network))?;
+
+    let ctx = fetch_keeper_tx_context(&client, &capabilities, &ids)
+        .await
+        .into_service()?;
+    let current_height = client.current_height().await.into_service()? as i32;
+
+    Ok(DexyKeeperStatusResponse {
+        variant: variant.to_string(),
+        current_height,
+        lp_rate_nano: ctx.lp_erg_reserves / ctx.lp_dexy_reserves.max(1),
+        oracle_rate_nano: ctx.oracle_rate_nano / dexy_variant.oracle_divisor(),
+        actions: evaluate_keeper_actions(&ctx, current_height, dexy_variant),
+    })
+}
+
+pub async fn build_keeper_tx(
+    state: &AppState,
+    variant: &str,
+    action: &str,
+    user_utxos: Vec<ergo_tx::Eip12InputBox>,
+    current_height: i32,
+) -> ServiceResult<DexyKeeperBuildResponse> {
+    let dexy_variant = parse_variant(variant)?;
+    let action =
+        KeeperAction::parse(action).ok_or_else(|| format!("Invalid keeper action: {}", action))?;
+
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+
+    let config = state.config().await;
+    let ids = DexyIds::for_variant(dexy_variant, config.network)
+        .ok_or_else(|| "Dexy not available on this network".to_string())?;
+
+    let ctx = fetch_keeper_tx_context(&client, &capabilities, &ids)
+        .await
+        .into_service()?;
+
+    let user_ergo_tree = user_utxos
+        .first()
+        .map(|u| u.ergo_tree.clone())
+        .ok_or("No wallet UTXOs")?;
+    let request = KeeperRequest {
+        variant: dexy_variant,
+        action,
+        user_ergo_tree,
+        user_inputs: user_utxos,
+        current_height,
+    };
+
+    let result = build_dexy_keeper_tx(&request, &ctx, &ids).into_service()?;
+
+    let unsigned_tx_json = serde_json::to_value(&result.unsigned_tx)
+        .map_err(|e| format!("Failed to serialize tx: {}", e))?;
+
+    Ok(DexyKeeperBuildResponse {
+        unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(&ids),
         summary: result.summary,
     })
 }
@@ -4,7 +4,10 @@ use ergo_lib::ergotree_ir::chain::address::{Address, AddressEncoder, NetworkPref
 use ergo_lib::ergotree_ir::ergo_tree::ErgoTree;
 use ergo_lib::ergotree_ir::serialization::SigmaSerializable;
 
+use super::address_book::address_info;
 use super::error::{IntoServiceError, ServiceResult};
+use crate::contracts;
+use crate::dto::AddressBookEntry;
 use crate::AppState;
 
 fn ergo_tree_to_address(ergo_tree_hex: &str) -> Option<String> {
@@ -35,6 +38,41 @@ fn enrich_addresses_from_ergo_tree(boxes: &mut [serde_json::Value]) {
     }
 }
 
+/// Attach `addressLabel` (address book label or known contract name) and
+/// `contract` to every input and output that can be identified.
+fn label_tx_boxes(book: &[AddressBookEntry], tx: &mut serde_json::Value) {
+    for key in ["inputs", "outputs"] {
+        let Some(boxes) = tx.get_mut(key).and_then(|v| v.as_array_mut()) else {
+            continue;
+        };
+        for b in boxes.iter_mut() {
+            let Some(obj) = b.as_object_mut() else {
+                continue;
+            };
+            let address = obj.get("address").and_then(|v| v.as_str()).unwrap_or("");
+            let tree = obj.get("ergoTree").and_then(|v| v.as_str()).unwrap_or("");
+            let mut info = address_info(book, address, tree);
+            if info.contract.is_none() {
+                if let Some(assets) = obj.get("assets").and_then(|v| v.as_array()) {
+                    info.contract = contracts::recognize_tokens(
+                        assets
+                            .iter()
+                            .filter_map(|a| a.get("tokenId").and_then(|v| v.as_str())),
+                    );
+                }
+            }
+            if let Some(name) = info.display_name() {
+                obj.insert("addressLabel".to_string(), serde_json::Value::String(name));
+            }
+            if let Some(contract) = info.contract {
+                if let Ok(v) = serde_json::to_value(contract) {
+                    obj.insert("contract".to_string(), v);
+                }
+            }
+        }
+    }
+}
+
 pub async fn explorer_node_info(state: &AppState) -> ServiceResult<serde_json::Value> {
     let client = state.require_node_client().await?;
     client.get_full_node_info().await.into_service()
@@ -58,6 +96,7 @@ pub async fn explorer_get_transaction(
             if let Some(outputs) = tx.get_mut("outputs").and_then(|v| v.as_array_mut()) {
                 enrich_addresses_from_ergo_tree(outputs);
             }
+            label_tx_boxes(&state.address_book().await, &mut tx);
             return Ok(tx);
         }
     }
@@ -105,6 +144,7 @@ pub async fn explorer_get_transaction(
     if let Some(outputs) = utx.get_mut("outputs").and_then(|v| v.as_array_mut()) {
         enrich_addresses_from_ergo_tree(outputs);
     }
+    label_tx_boxes(&state.address_book().await, &mut utx);
 
     Ok(utx)
 }
@@ -209,6 +249,11 @@ pub async fn explorer_get_address(
     );
 
     let (erg_balance, tokens) = balance_result.into_service()?;
+    let tree = ergo_tx::address_to_ergo_tree(&address).unwrap_or_default();
+    let mut info = address_info(&state.address_book().await, &address, &tree);
+    if info.contract.is_none() && info.is_script {
+        info.contract = contracts::recognize_tokens(tokens.iter().map(|(id, _)| id.as_str()));
+    }
     let (transactions, total_txs) = txs_result.into_service()?;
     let unconfirmed_txs = unconfirmed_result.unwrap_or_default();
 
@@ -232,6 +277,11 @@ pub async fn explorer_get_address(
 
     Ok(serde_json::json!({
         "address": address,
+        "label": info.label,
+        "notes": info.notes,
+        "tags": info.tags,
+        "contract": info.contract,
+        "isScript": info.is_script,
         "balance": {
             "nanoErgs": erg_balance,
             "tokens": tokens.iter().map(|(id, amt)| {
@@ -1,54 +1,192 @@
 //! HodlCoin use-case orchestration: bank discovery, preview math, tx building.
 
+use citadel_core::{IntentAsset, TxIntent};
 use ergo_node_client::NodeClient;
+use serde::Serialize;
 
 use super::error::{IntoServiceError, ServiceResult};
 use crate::AppState;
 
-async fn find_bank(
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HodlBuildResponse {
+    pub unsigned_tx: serde_json::Value,
+    pub intent: TxIntent,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HodlBankDeployResponse {
+    /// Bootstrap then create; sign each and submit in order.
+    pub legs: Vec<hodlcoin::BankDeployLeg>,
+    pub plan: hodlcoin::BankDeployPlan,
+    pub singleton_token_id: String,
+    pub hodl_token_id: String,
+    pub intent: TxIntent,
+}
+
+/// Look a bank up by singleton across every configured contract template.
+/// Low-TVL banks are included: a bank the user already picked must stay
+/// reachable regardless of the display toggle.
+pub(crate) async fn find_bank(
+    state: &AppState,
     client: &NodeClient,
     singleton_token_id: &str,
 ) -> ServiceResult<hodlcoin::HodlBankState> {
-    let banks = hodlcoin::discover_banks(client).await.into_service()?;
+    let options = hodlcoin::DiscoveryOptions {
+        include_low_tvl: true,
+        ..state.hodl_discovery().await
+    };
+    let banks = hodlcoin::discover_banks_with(client, &options)
+        .await
+        .into_service()?;
     banks
         .into_iter()
         .find(|b| b.singleton_token_id == singleton_token_id)
         .ok_or_else(|| format!("Bank not found: {}", singleton_token_id))
 }
 
+/// The bank's reserve asset: ERG, or its reserve token in raw units.
+fn reserve_asset(bank: &hodlcoin::HodlBankState, amount: i64) -> IntentAsset {
+    match &bank.reserve_token_id {
+        Some(token_id) => IntentAsset::token(token_id, amount.max(0) as u64),
+        None => IntentAsset::erg(amount.max(0) as u64),
+    }
+}
+
+fn hodl_intent(bank: &hodlcoin::HodlBankState, action: &str) -> TxIntent {
+    let token = bank.hodl_token_name.as_deref().unwrap_or("hodlToken");
+    TxIntent::new("HodlCoin", format!("{} {}", action, token))
+        .miner_fee(citadel_core::constants::TX_FEE_NANO as u64)
+        .counterparty("bank", format!("{} bank", token))
+}
+
+fn describe_bank_tokens(intent: &mut TxIntent, bank: &hodlcoin::HodlBankState) {
+    intent.describe_token(&bank.hodl_token_id, bank.hodl_token_name.as_deref(), None);
+    if let Some(token_id) = &bank.reserve_token_id {
+        intent.describe_token(token_id, bank.reserve_token_name.as_deref(), None);
+    }
+}
+
+fn build_response(
+    tx: ergo_tx::Eip12UnsignedTx,
+    intent: TxIntent,
+) -> ServiceResult<HodlBuildResponse> {
+    Ok(HodlBuildResponse {
+        unsigned_tx: serde_json::to_value(&tx)
+            .map_err(|e| format!("Failed to serialize transaction: {}", e))?,
+        intent,
+    })
+}
+
+/// Bank state and input box for a build. With `build_on_pending` the bank is
+/// re-read from its latest unconfirmed successor so the tx chains on it.
+async fn bank_for_build(
+    state: &AppState,
+    client: &NodeClient,
+    singleton_token_id: &str,
+    build_on_pending: bool,
+) -> ServiceResult<(hodlcoin::HodlBankState, ergo_tx::Eip12InputBox)> {
+    let bank = find_bank(state, client, singleton_token_id).await?;
+
+    if build_on_pending {
+        let pending = client
+            .pending_successor(&bank.bank_box_id, singleton_token_id)
+            .await
+            .into_service()?;
+        if let Some(successor) = pending {
+            let mut pending_bank = hodlcoin::parse_bank_box(&successor.ergo_box).into_service()?;
+            pending_bank.hodl_token_name = bank.hodl_token_name;
+            pending_bank.reserve_token_name = bank.reserve_token_name;
+            let bank_box = ergo_tx::Eip12InputBox::from_ergo_box(
+                &successor.ergo_box,
+                successor.tx_id,
+                successor.index,
+            );
+            return Ok((pending_bank, bank_box));
+        }
+    }
+
+    let bank_box = client
+        .get_eip12_box_by_id(&bank.bank_box_id)
+        .await
+        .map_err(|e| format!("Failed to fetch bank box: {}", e))?;
+    Ok((bank, bank_box))
+}
+
 pub async fn get_banks(state: &AppState) -> ServiceResult<Vec<hodlcoin::HodlBankState>> {
     let client = state.require_node_client().await?;
-    hodlcoin::discover_banks(&client).await.into_service()
+    let options = state.hodl_discovery().await;
+    hodlcoin::discover_banks_with(&client, &options)
+        .await
+        .into_service()
+}
+
+pub async fn get_discovery_config(state: &AppState) -> ServiceResult<hodlcoin::DiscoveryOptions> {
+    Ok(state.hodl_discovery().await)
+}
+
+pub async fn set_discovery_config(
+    state: &AppState,
+    mut options: hodlcoin::DiscoveryOptions,
+) -> ServiceResult<hodlcoin::DiscoveryOptions> {
+    for trees in [
+        &mut options.custom_bank_trees,
+        &mut options.custom_fee_trees,
+    ] {
+        for tree in trees.iter_mut() {
+            *tree = tree.trim().to_lowercase();
+            if hodlcoin::template_hash(tree).is_none() {
+                return Err(format!("Not a valid ErgoTree: {}", tree));
+            }
+        }
+        trees.sort();
+        trees.dedup();
+    }
+    state
+        .set_hodl_discovery(options.clone())
+        .await
+        .into_service()?;
+    Ok(options)
 }
 
+/// `deposit_amount` is in the bank's reserve unit: nanoERG, or raw reserve
+/// token units for token-backed banks.
 pub async fn preview_mint(
     state: &AppState,
     singleton_token_id: &str,
-    erg_amount: i64,
+    deposit_amount: i64,
 ) -> ServiceResult<hodlcoin::HodlMintPreview> {
-    if erg_amount <= 0 {
+    if deposit_amount <= 0 {
         return Err("Amount must be greater than 0".to_string());
     }
 
     let client = state.require_node_client().await?;
-    let bank = find_bank(&client, singleton_token_id).await?;
+    let bank = find_bank(state, &client, singleton_token_id).await?;
 
     let tokens_received = hodlcoin::mint_amount(
-        bank.reserve_nano_erg,
+        bank.reserve_amount,
         bank.circulating_supply,
         bank.precision_factor,
-        erg_amount,
+        deposit_amount,
     );
 
     let miner_fee = citadel_core::constants::TX_FEE_NANO;
     let min_box = citadel_core::constants::MIN_BOX_VALUE_NANO;
+    // Token-backed deposits are paid in the reserve token, not ERG
+    let erg_deposit = if bank.is_token_backed() {
+        0
+    } else {
+        deposit_amount
+    };
 
     Ok(hodlcoin::HodlMintPreview {
-        erg_deposited: erg_amount,
+        erg_deposited: deposit_amount,
         hodl_tokens_received: tokens_received,
         price_per_token: bank.price_nano_per_hodl,
         miner_fee,
-        total_erg_cost: erg_amount + miner_fee + min_box,
+        total_erg_cost: erg_deposit + miner_fee + min_box,
+        reserve_token_id: bank.reserve_token_id,
     })
 }
 
@@ -62,10 +200,10 @@ pub async fn preview_burn(
     }
 
     let client = state.require_node_client().await?;
-    let bank = find_bank(&client, singleton_token_id).await?;
+    let bank = find_bank(state, &client, singleton_token_id).await?;
 
     let burn_result = hodlcoin::burn_amount(
-        bank.reserve_nano_erg,
+        bank.reserve_amount,
         bank.circulating_supply,
         bank.precision_factor,
         hodl_amount,
@@ -83,39 +221,65 @@ pub async fn preview_burn(
         erg_before_fees: burn_result.before_fees,
         price_per_token: bank.price_nano_per_hodl,
         miner_fee,
+        reserve_token_id: bank.reserve_token_id,
     })
 }
 
+/// `deposit_amount` is in the bank's reserve unit (see [`preview_mint`]).
 pub async fn build_mint_tx(
     state: &AppState,
     singleton_token_id: &str,
-    erg_amount: i64,
+    deposit_amount: i64,
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
-    if erg_amount <= 0 {
+    build_on_pending: bool,
+) -> ServiceResult<HodlBuildResponse> {
+    if deposit_amount <= 0 {
         return Err("Amount must be greater than 0".to_string());
     }
 
     let client = state.require_node_client().await?;
-    let bank = find_bank(&client, singleton_token_id).await?;
-
-    let bank_box = client
-        .get_eip12_box_by_id(&bank.bank_box_id)
-        .await
-        .map_err(|e| format!("Failed to fetch bank box: {}", e))?;
+    let (bank, bank_box) =
+        bank_for_build(state, &client, singleton_token_id, build_on_pending).await?;
 
     let user_ergo_tree = user_utxos[0].ergo_tree.clone();
 
-    hodlcoin::build_mint_tx_eip12(
-        &bank_box,
-        &bank,
-        erg_amount,
-        &user_utxos,
-        &user_ergo_tree,
-        current_height,
-    )
-    .into_service()
+    let tx = if bank.is_token_backed() {
+        hodlcoin::build_token_mint_tx_eip12(
+            &bank_box,
+            &bank,
+            deposit_amount,
+            &user_utxos,
+            &user_ergo_tree,
+            current_height,
+        )
+        .into_service()?
+    } else {
+        hodlcoin::build_mint_tx_eip12(
+            &bank_box,
+            &bank,
+            deposit_amount,
+            &user_utxos,
+            &user_ergo_tree,
+            current_height,
+        )
+        .into_service()?
+    };
+
+    let hodl_out = hodlcoin::mint_amount(
+        bank.reserve_amount,
+        bank.circulating_supply,
+        bank.precision_factor,
+        deposit_amount,
+    );
+    let mut intent = hodl_intent(&bank, "Mint")
+        .pays(reserve_asset(&bank, deposit_amount))
+        .receives(IntentAsset::token(
+            &bank.hodl_token_id,
+            hodl_out.max(0) as u64,
+        ));
+    describe_bank_tokens(&mut intent, &bank);
+    build_response(tx, intent)
 }
 
 pub async fn build_burn_tx(
@@ -124,28 +288,112 @@ pub async fn build_burn_tx(
     hodl_amount: i64,
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+    build_on_pending: bool,
+) -> ServiceResult<HodlBuildResponse> {
     if hodl_amount <= 0 {
         return Err("Amount must be greater than 0".to_string());
     }
 
     let client = state.require_node_client().await?;
-    let bank = find_bank(&client, singleton_token_id).await?;
-
-    let bank_box = client
-        .get_eip12_box_by_id(&bank.bank_box_id)
-        .await
-        .map_err(|e| format!("Failed to fetch bank box: {}", e))?;
+    let (bank, bank_box) =
+        bank_for_build(state, &client, singleton_token_id, build_on_pending).await?;
 
     let user_ergo_tree = user_utxos[0].ergo_tree.clone();
 
-    hodlcoin::build_burn_tx_eip12(
-        &bank_box,
-        &bank,
+    let tx = if bank.is_token_backed() {
+        let custom_fee_trees = state.hodl_discovery().await.custom_fee_trees;
+        let dev_fee_tree = hodlcoin::resolve_dev_fee_tree(&bank_box.ergo_tree, &custom_fee_trees)
+            .ok_or_else(|| {
+            "Unknown dev fee contract for this bank; add it to the HodlCoin discovery settings"
+                .to_string()
+        })?;
+        hodlcoin::build_token_burn_tx_eip12(
+            &bank_box,
+            &bank,
+            hodl_amount,
+            &dev_fee_tree,
+            &user_utxos,
+            &user_ergo_tree,
+            current_height,
+        )
+        .into_service()?
+    } else {
+        hodlcoin::build_burn_tx_eip12(
+            &bank_box,
+            &bank,
+            hodl_amount,
+            &user_utxos,
+            &user_ergo_tree,
+            current_height,
+        )
+        .into_service()?
+    };
+
+    let burn = hodlcoin::burn_amount(
+        bank.reserve_amount,
+        bank.circulating_supply,
+        bank.precision_factor,
         hodl_amount,
-        &user_utxos,
-        &user_ergo_tree,
-        current_height,
+        bank.bank_fee_num,
+        bank.dev_fee_num,
+    );
+    let mut intent = hodl_intent(&bank, "Burn")
+        .pays(IntentAsset::token(&bank.hodl_token_id, hodl_amount as u64))
+        .receives(reserve_asset(&bank, burn.erg_to_user));
+    // Fees are taken from the reserve, so they are only ERG on ERG-backed banks
+    if bank.is_token_backed() {
+        intent = intent.note(format!(
+            "Fees in raw reserve units: bank {}, dev {}",
+            burn.bank_fee, burn.dev_fee
+        ));
+    } else {
+        intent = intent
+            .protocol_fee((burn.bank_fee + burn.dev_fee).max(0) as u64)
+            .note("The bank fee stays in the reserve for remaining holders");
+    }
+    describe_bank_tokens(&mut intent, &bank);
+    build_response(tx, intent)
+}
+
+/// Validate deployment parameters and show the new bank's opening state.
+pub fn preview_deploy(
+    params: hodlcoin::BankDeployParams,
+) -> ServiceResult<hodlcoin::BankDeployPlan> {
+    hodlcoin::plan_bank_deploy(&params).into_service()
+}
+
+/// Build the bootstrap + bank creation txs as a 0-conf chain.
+pub fn build_deploy_tx(
+    params: hodlcoin::BankDeployParams,
+    user_utxos: Vec<ergo_tx::Eip12InputBox>,
+    current_height: i32,
+) -> ServiceResult<HodlBankDeployResponse> {
+    let user_ergo_tree = user_utxos
+        .first()
+        .map(|u| u.ergo_tree.clone())
+        .ok_or("No wallet UTXOs")?;
+    let build =
+        hodlcoin::build_bank_deploy_chain(&params, &user_utxos, &user_ergo_tree, current_height)
+            .into_service()?;
+
+    // The creator's hodlToken box ERG comes back; the reserve stays in the bank
+    let intent = TxIntent::new(
+        "HodlCoin",
+        format!("Deploy {} bank", params.hodl_token_name),
     )
-    .into_service()
+    .pays(IntentAsset::erg(params.initial_reserve as u64))
+    .receives(IntentAsset::token(
+        &build.hodl_token_id,
+        params.initial_circulating as u64,
+    ))
+    .miner_fee(build.plan.miner_fee as u64)
+    .note(format!("Bank singleton {}", build.singleton_token_id));
+
+    Ok(HodlBankDeployResponse {
+        legs: vec![build.bootstrap, build.create],
+        plan: build.plan,
+        singleton_token_id: build.singleton_token_id,
+        hodl_token_id: build.hodl_token_id,
+        intent,
+    })
 }
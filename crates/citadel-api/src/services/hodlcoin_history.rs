@@ -0,0 +1,152 @@
+//! HodlCoin bank price history, cached in the data dir, and the active
+//! wallet's positions (average mint price, burn value, gains).
+//!
+//! Each history query only walks the bank boxes created since the last cached
+//! one, so charts can re-query cheaply.
+
+use std::collections::HashSet;
+
+use hodlcoin::{BankPriceHistory, BankPricePoint, HodlPosition};
+use serde::Serialize;
+
+use crate::services::chain_history::refresh_history;
+use crate::services::error::{IntoServiceError, ServiceResult};
+use crate::services::hodlcoin::find_bank;
+use crate::AppState;
+
+const HODLCOIN_HISTORY_FILE: &str = "hodlcoin_history";
+/// Points kept per bank (newest first).
+const MAX_CACHED_POINTS: usize = 10_000;
+/// Bank boxes walked per query when the caller does not say.
+const DEFAULT_TRACE_STEPS: usize = 300;
+/// Wallet transactions scanned per address for mints and burns.
+const HISTORY_SCAN_LIMIT: u64 = 500;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HodlPositionsResponse {
+    pub positions: Vec<HodlPosition>,
+    /// Banks or history that could not be queried.
+    pub errors: Vec<String>,
+    pub block_height: u64,
+}
+
+/// Price, reserve and supply of the bank at each transition since
+/// `since_height`, sampled to one point per `sample_blocks` blocks (0 = every
+/// transition). Without `since_height` only the transitions since the cached
+/// range are traced.
+pub async fn get_hodlcoin_history(
+    state: &AppState,
+    singleton_token_id: &str,
+    max_steps: Option<usize>,
+    sample_blocks: Option<u64>,
+    since_height: Option<u64>,
+) -> ServiceResult<BankPriceHistory> {
+    let client = state.require_node_client().await?;
+    let bank = find_bank(state, &client, singleton_token_id).await?;
+    let max_steps = max_steps.unwrap_or(DEFAULT_TRACE_STEPS).max(1);
+
+    let client = &client;
+    let points = refresh_history(
+        &state.store().await,
+        HODLCOIN_HISTORY_FILE,
+        singleton_token_id,
+        &bank.bank_box_id,
+        max_steps,
+        since_height,
+        MAX_CACHED_POINTS,
+        |start, known, steps| async move {
+            hodlcoin::trace_bank_history(
+                client,
+                singleton_token_id,
+                &start,
+                &known,
+                steps,
+                since_height.unwrap_or(0),
+            )
+            .await
+            .into_service()
+        },
+    )
+    .await?;
+
+    let oldest_first: Vec<BankPricePoint> = points
+        .into_iter()
+        .rev()
+        .filter(|p| p.height >= since_height.unwrap_or(0))
+        .collect();
+    Ok(hodlcoin::price_history(
+        singleton_token_id,
+        &oldest_first,
+        sample_blocks.unwrap_or(0),
+    ))
+}
+
+/// Positions of the connected wallet in every bank it holds or has traded.
+pub async fn get_hodlcoin_positions(state: &AppState) -> ServiceResult<HodlPositionsResponse> {
+    let wallet = state
+        .connected_wallet()
+        .await
+        .ok_or("No wallet connected")?;
+    let client = state.require_node_client().await?;
+    let height = client.current_height().await.into_service()?;
+
+    // Positions in banks hidden by the low-TVL toggle still count
+    let options = hodlcoin::DiscoveryOptions {
+        include_low_tvl: true,
+        ..state.hodl_discovery().await
+    };
+    let banks = hodlcoin::discover_banks_with(&client, &options)
+        .await
+        .into_service()?;
+    let (_, balances) = client
+        .get_addresses_balances(&wallet.addresses)
+        .await
+        .into_service()?;
+
+    let mut errors = Vec::new();
+    let mut history: Vec<serde_json::Value> = Vec::new();
+    let mut seen_txs: HashSet<String> = HashSet::new();
+    for address in &wallet.addresses {
+        match client
+            .get_transactions_by_address(address, 0, HISTORY_SCAN_LIMIT)
+            .await
+        {
+            Ok((txs, _)) => {
+                for tx in txs {
+                    let id = tx["id"].as_str().unwrap_or_default().to_string();
+                    if seen_txs.insert(id) {
+                        history.push(tx);
+                    }
+                }
+            }
+            Err(e) => errors.push(format!("History ({}): {}", address, e)),
+        }
+    }
+    history.sort_by_key(|tx| tx["inclusionHeight"].as_u64().unwrap_or(0));
+
+    let addresses: HashSet<&str> = wallet.addresses.iter().map(String::as_str).collect();
+    let positions = banks
+        .iter()
+        .filter_map(|bank| {
+            let held = balances
+                .iter()
+                .find(|(token_id, _)| token_id == &bank.hodl_token_id)
+                .map_or(0, |(_, amount)| *amount as i64);
+            let trades: Vec<hodlcoin::HodlTrade> = history
+                .iter()
+                .filter_map(|tx| hodlcoin::trade_from_tx(tx, bank, &addresses))
+                .collect();
+            if held == 0 && trades.is_empty() {
+                return None;
+            }
+            Some(hodlcoin::holder_position(bank, held, trades))
+        })
+        .collect();
+
+    Ok(HodlPositionsResponse {
+        positions,
+        errors,
+        block_height: height,
+    })
+}
@@ -7,6 +7,7 @@ use crate::dto::lending::{
 };
 use crate::services::error::{IntoServiceError, ServiceResult};
 use crate::AppState;
+use citadel_core::TxIntent;
 use lending::{
     constants as lending_constants, fetch_all_markets, tx_builder as lending_tx_builder, PoolState,
 };
@@ -241,6 +242,7 @@ fn lending_build_response_to_dto(
             service_fee_nano: response.summary.service_fee_raw.to_string(),
             total_to_send: response.summary.total_to_send_display,
         },
+        intent: response.intent,
     })
 }
 
@@ -500,6 +502,10 @@ pub async fn build_refund(
             service_fee_nano: "0".to_string(),
             total_to_send: String::new(),
         },
+        intent: TxIntent::new("Duckpools", "Refund proxy")
+            .miner_fee(result.fee_nano.max(0) as u64)
+            .counterparty("proxy", "Duckpools proxy box")
+            .note("Returns the proxy box contents to your wallet"),
     })
 }
 
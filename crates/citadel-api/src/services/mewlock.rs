@@ -1,8 +1,29 @@
 //! MewLock use-case orchestration: state fetch, duration presets, lock/unlock tx building.
 
+use citadel_core::TxIntent;
+use serde::Serialize;
+
 use super::error::{IntoServiceError, ServiceResult};
 use crate::AppState;
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MewLockBuildResponse {
+    pub unsigned_tx: serde_json::Value,
+    pub intent: TxIntent,
+}
+
+fn build_response(
+    tx: ergo_tx::Eip12UnsignedTx,
+    intent: TxIntent,
+) -> ServiceResult<MewLockBuildResponse> {
+    Ok(MewLockBuildResponse {
+        unsigned_tx: serde_json::to_value(&tx)
+            .map_err(|e| format!("Failed to serialize tx: {}", e))?,
+        intent: intent.counterparty("contract", "MewLock timelock"),
+    })
+}
+
 pub async fn fetch_state(
     state: &AppState,
     user_address: Option<&str>,
@@ -38,7 +59,7 @@ pub fn build_lock(
     lock_description: Option<String>,
     user_inputs: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<MewLockBuildResponse> {
     let req = mewlock::tx_builder::LockRequest {
         user_ergo_tree,
         lock_erg,
@@ -51,7 +72,10 @@ pub fn build_lock(
         current_height,
     };
 
-    mewlock::build_lock_tx(&req).into_service()
+    let tx = mewlock::build_lock_tx(&req).into_service()?;
+    let intent = ergo_tx::derive_intent(&tx, "MewLock", "Lock")
+        .note(format!("Unlockable at height {}", unlock_height));
+    build_response(tx, intent)
 }
 
 pub async fn build_unlock(
@@ -60,7 +84,7 @@ pub async fn build_unlock(
     user_ergo_tree: String,
     user_inputs: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<MewLockBuildResponse> {
     let client = state.require_node_client().await?;
     let lock_box = client
         .get_eip12_box_by_id(box_id)
@@ -74,5 +98,7 @@ pub async fn build_unlock(
         current_height,
     };
 
-    mewlock::build_unlock_tx(&req).into_service()
+    let tx = mewlock::build_unlock_tx(&req).into_service()?;
+    let intent = ergo_tx::derive_intent(&tx, "MewLock", "Unlock");
+    build_response(tx, intent)
 }
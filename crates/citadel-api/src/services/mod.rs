@@ -1,18 +1,32 @@
 pub mod activity;
+pub mod address_book;
 pub mod amm;
+pub mod arb_monitor;
 pub mod burn;
+pub mod chain_history;
+pub mod contention;
 pub mod dexy;
 pub mod error;
 pub mod explorer;
 pub mod hodlcoin;
+pub mod hodlcoin_history;
 pub mod lending;
 pub mod mewlock;
 pub mod node;
+pub mod oracle;
+pub mod orders;
+pub mod portfolio;
+pub mod positions;
 pub mod sigmafi;
 pub mod sigmausd;
+pub mod sigmausd_advisor;
+pub mod sigmausd_history;
 pub mod signing;
+pub mod simulator;
 pub mod stake_recovery;
+pub mod tokens;
 pub mod utxo;
+pub mod valuation;
 pub mod wallet;
 
 pub use error::{to_string_err, IntoServiceError, ServiceResult};
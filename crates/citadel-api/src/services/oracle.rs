@@ -0,0 +1,395 @@
+//! Oracle guard: freshness and sanity checks on oracle pool datapoints.
+//!
+//! SigmaUSD and Dexy builds take the oracle pool's R4 at face value. Before
+//! building, the pool box's age (blocks since its last epoch refresh) and the
+//! datapoint's deviation from independent on-chain prices (the other ERG/USD
+//! pool, the Dexy LP, the main ERG/SigUSD Spectrum pool) are compared
+//! against [`OracleGuardConfig`]; the build is blocked or carries warnings.
+
+use citadel_core::{Network, TokenId};
+use dexy::{DexyIds, DexyVariant};
+use ergo_lib::ergotree_ir::chain::ergo_box::NonMandatoryRegisterId;
+use ergo_node_client::{NodeCapabilities, NodeClient};
+use ergo_tx::ergo_box_utils::{read_register_int, read_register_long};
+use sigmausd::NftIds;
+
+use super::error::{IntoServiceError, ServiceResult};
+use crate::dto::{OracleCheck, OracleFeed, OracleGuardConfig, OracleReference, OracleVerdict};
+use crate::AppState;
+
+/// SigUSD has 2 decimals: pool reserves count cents.
+const SIGUSD_UNITS_PER_USD: f64 = 100.0;
+/// Spectrum ERG/SigUSD pool, the deepest SigUSD market (mainnet).
+const SIGUSD_ERG_POOL_NFT: &str =
+    "9916d75132593c8b07fe18bd8d583bda1652eed7565cf41a4738ddd90fc992ec";
+
+pub async fn get_oracle_guard_config(state: &AppState) -> ServiceResult<OracleGuardConfig> {
+    Ok(state.oracle_guard().await)
+}
+
+pub async fn set_oracle_guard_config(
+    state: &AppState,
+    config: OracleGuardConfig,
+) -> ServiceResult<OracleGuardConfig> {
+    if config.max_age_blocks <= 0 {
+        return Err("Maximum oracle age must be positive".to_string());
+    }
+    if config.warn_deviation_pct <= 0.0 || config.block_deviation_pct < config.warn_deviation_pct {
+        return Err("Block deviation must be at least the (positive) warn deviation".to_string());
+    }
+    state
+        .set_oracle_guard(config.clone())
+        .await
+        .into_service()?;
+    Ok(config)
+}
+
+/// Age and cross-reference check for one oracle feed.
+pub async fn check_oracle(state: &AppState, feed: OracleFeed) -> ServiceResult<OracleCheck> {
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+    let network = state.config().await.network;
+    let config = state.oracle_guard().await;
+    check_oracle_with(&client, &capabilities, network, &config, feed).await
+}
+
+/// [`check_oracle`] for callers already holding a client and the guard config.
+pub(crate) async fn check_oracle_with(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    network: Network,
+    config: &OracleGuardConfig,
+    feed: OracleFeed,
+) -> ServiceResult<OracleCheck> {
+    let nft = feed_nft(feed, network)
+        .ok_or_else(|| format!("Oracle {} not available on {:?}", feed.as_str(), network))?;
+    let oracle_box = client
+        .get_box_by_token_id(capabilities, &TokenId::new(&nft))
+        .await
+        .into_service()?;
+    let rate_nano = read_register_long(&oracle_box, NonMandatoryRegisterId::R4)
+        .ok_or_else(|| format!("Oracle {} box has no R4 datapoint", feed.as_str()))?;
+    let epoch = read_register_int(&oracle_box, NonMandatoryRegisterId::R5);
+    let box_height = oracle_box.creation_height as i32;
+    let current_height = client.current_height().await.into_service()? as i32;
+    let age_blocks = (current_height - box_height).max(0);
+
+    let references = fetch_references(client, capabilities, feed, network)
+        .await
+        .into_iter()
+        .map(|(source, rate)| reference(source, rate, rate_nano))
+        .collect::<Vec<_>>();
+
+    let assessment = assess(rate_nano, age_blocks, &references, config);
+
+    Ok(OracleCheck {
+        feed,
+        oracle_box_id: oracle_box.box_id().to_string(),
+        rate_nano,
+        epoch,
+        box_height,
+        current_height,
+        age_blocks,
+        stale: assessment.stale,
+        references,
+        median_deviation_pct: assessment.median_deviation_pct,
+        verdict: assessment.verdict,
+        issues: assessment.issues,
+    })
+}
+
+/// Pre-build gate: `Err` when the feed is blocked, otherwise the warnings to
+/// surface with the built tx.
+pub async fn guard_build(state: &AppState, feed: OracleFeed) -> ServiceResult<Vec<String>> {
+    let check = check_oracle(state, feed).await?;
+    match check.verdict {
+        OracleVerdict::Block => Err(format!("Oracle check failed: {}", check.issues.join("; "))),
+        OracleVerdict::Warn | OracleVerdict::Ok => Ok(check.issues),
+    }
+}
+
+pub fn dexy_feed(variant: DexyVariant) -> OracleFeed {
+    match variant {
+        DexyVariant::Gold => OracleFeed::DexyGold,
+        DexyVariant::Usd => OracleFeed::DexyUsd,
+    }
+}
+
+fn feed_nft(feed: OracleFeed, network: Network) -> Option<String> {
+    match feed {
+        OracleFeed::ErgUsd => NftIds::for_network(network).map(|ids| ids.oracle_pool_nft),
+        OracleFeed::DexyGold => {
+            DexyIds::for_variant(DexyVariant::Gold, network).map(|ids| ids.oracle_pool_nft)
+        }
+        OracleFeed::DexyUsd => {
+            DexyIds::for_variant(DexyVariant::Usd, network).map(|ids| ids.oracle_pool_nft)
+        }
+    }
+}
+
+/// Independent prices in the feed's unit. Sources that are unavailable or
+/// fail to parse are skipped: fewer references only weakens the check.
+async fn fetch_references(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    feed: OracleFeed,
+    network: Network,
+) -> Vec<(&'static str, f64)> {
+    let mut refs = Vec::new();
+    match feed {
+        OracleFeed::DexyGold => {
+            if let Some(rate) = dexy_lp_rate(client, capabilities, DexyVariant::Gold, network).await
+            {
+                refs.push(("DexyGold LP", rate));
+            }
+        }
+        OracleFeed::ErgUsd | OracleFeed::DexyUsd => {
+            let other = match feed {
+                OracleFeed::ErgUsd => (OracleFeed::DexyUsd, "USE oracle"),
+                _ => (OracleFeed::ErgUsd, "SigmaUSD oracle"),
+            };
+            if let Some(rate) = oracle_rate(client, capabilities, other.0, network).await {
+                refs.push((other.1, rate));
+            }
+            if let Some(rate) = dexy_lp_rate(client, capabilities, DexyVariant::Usd, network).await
+            {
+                refs.push(("USE LP", rate));
+            }
+            if let Some(rate) = sigusd_pool_rate(client, capabilities, network).await {
+                refs.push(("Spectrum ERG/SigUSD", rate));
+            }
+        }
+    }
+    refs
+}
+
+async fn oracle_rate(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    feed: OracleFeed,
+    network: Network,
+) -> Option<f64> {
+    let nft = feed_nft(feed, network)?;
+    let oracle_box = client
+        .get_box_by_token_id(capabilities, &TokenId::new(&nft))
+        .await
+        .ok()?;
+    read_register_long(&oracle_box, NonMandatoryRegisterId::R4)
+        .filter(|r| *r > 0)
+        .map(|r| r as f64)
+}
+
+/// LP price scaled from per-token to the oracle unit.
+async fn dexy_lp_rate(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    variant: DexyVariant,
+    network: Network,
+) -> Option<f64> {
+    let ids = DexyIds::for_variant(variant, network)?;
+    let lp_box = client
+        .get_box_by_token_id(capabilities, &TokenId::new(&ids.lp_nft))
+        .await
+        .ok()?;
+    let lp = dexy::parse_lp_box(&lp_box, &ids).ok()?;
+    if lp.dexy_reserves <= 0 {
+        return None;
+    }
+    Some(lp.erg_reserves as f64 / lp.dexy_reserves as f64 * variant.oracle_divisor() as f64)
+}
+
+/// Price in the main ERG/SigUSD Spectrum pool, fetched by its NFT.
+async fn sigusd_pool_rate(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    network: Network,
+) -> Option<f64> {
+    if network != Network::Mainnet {
+        return None;
+    }
+    let sigusd = NftIds::for_network(network)?.sigusd_token;
+    let pool_box = client
+        .get_box_by_token_id(capabilities, &TokenId::new(SIGUSD_ERG_POOL_NFT))
+        .await
+        .ok()?;
+    let pool = amm::parse_n2t_pool(&pool_box).ok()?;
+    if pool.token_y.token_id != sigusd || pool.token_y.amount == 0 {
+        return None;
+    }
+    let erg = pool.erg_reserves? as f64;
+    Some(erg / pool.token_y.amount as f64 * SIGUSD_UNITS_PER_USD)
+}
+
+fn reference(source: &str, rate_nano: f64, oracle_rate_nano: i64) -> OracleReference {
+    let deviation_pct = if oracle_rate_nano > 0 {
+        (rate_nano - oracle_rate_nano as f64) / oracle_rate_nano as f64 * 100.0
+    } else {
+        0.0
+    };
+    OracleReference {
+        source: source.to_string(),
+        rate_nano,
+        deviation_pct,
+    }
+}
+
+struct Assessment {
+    stale: bool,
+    median_deviation_pct: Option<f64>,
+    verdict: OracleVerdict,
+    issues: Vec<String>,
+}
+
+/// The median keeps one thin or manipulated reference from deciding alone.
+fn median_abs_deviation(references: &[OracleReference]) -> Option<f64> {
+    let mut devs: Vec<f64> = references.iter().map(|r| r.deviation_pct.abs()).collect();
+    if devs.is_empty() {
+        return None;
+    }
+    devs.sort_by(|a, b| a.total_cmp(b));
+    let mid = devs.len() / 2;
+    Some(if devs.len() % 2 == 0 {
+        (devs[mid - 1] + devs[mid]) / 2.0
+    } else {
+        devs[mid]
+    })
+}
+
+fn assess(
+    rate_nano: i64,
+    age_blocks: i32,
+    references: &[OracleReference],
+    config: &OracleGuardConfig,
+) -> Assessment {
+    let mut verdict = OracleVerdict::Ok;
+    let mut issues = Vec::new();
+    let mut raise = |level: OracleVerdict, issue: String| {
+        if level == OracleVerdict::Block || verdict == OracleVerdict::Ok {
+            verdict = level;
+        }
+        issues.push(issue);
+    };
+
+    if rate_nano <= 0 {
+        raise(
+            OracleVerdict::Block,
+            "Oracle datapoint is not positive".to_string(),
+        );
+    }
+
+    let stale = age_blocks > config.max_age_blocks;
+    if stale {
+        let level = if config.block_when_stale {
+            OracleVerdict::Block
+        } else {
+            OracleVerdict::Warn
+        };
+        raise(
+            level,
+            format!(
+                "Oracle last refreshed {} blocks ago (limit {})",
+                age_blocks, config.max_age_blocks
+            ),
+        );
+    }
+
+    let median_deviation_pct = median_abs_deviation(references);
+    if let Some(median) = median_deviation_pct {
+        let level = if median > config.block_deviation_pct {
+            Some(OracleVerdict::Block)
+        } else if median > config.warn_deviation_pct {
+            Some(OracleVerdict::Warn)
+        } else {
+            None
+        };
+        if let Some(level) = level {
+            let sources = references
+                .iter()
+                .map(|r| format!("{} {:+.1}%", r.source, r.deviation_pct))
+                .collect::<Vec<_>>()
+                .join(", ");
+            raise(
+                level,
+                format!(
+                    "Oracle deviates {:.1}% from on-chain prices ({})",
+                    median, sources
+                ),
+            );
+        }
+    }
+
+    Assessment {
+        stale,
+        median_deviation_pct,
+        verdict,
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: i64 = 2_000_000_000;
+
+    fn refs(rates: &[f64]) -> Vec<OracleReference> {
+        rates.iter().map(|r| reference("ref", *r, RATE)).collect()
+    }
+
+    #[test]
+    fn test_fresh_oracle_in_line_with_references_is_ok() {
+        let a = assess(
+            RATE,
+            10,
+            &refs(&[2_020_000_000.0, 1_990_000_000.0]),
+            &OracleGuardConfig::default(),
+        );
+        assert_eq!(a.verdict, OracleVerdict::Ok);
+        assert!(!a.stale);
+        assert!(a.issues.is_empty());
+        // |+1%| and |-0.5%| -> 0.75%
+        assert!((a.median_deviation_pct.unwrap() - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stale_oracle_blocks_or_warns_per_config() {
+        let mut config = OracleGuardConfig::default();
+        let a = assess(RATE, 91, &[], &config);
+        assert!(a.stale);
+        assert_eq!(a.verdict, OracleVerdict::Block);
+        assert_eq!(a.median_deviation_pct, None);
+
+        config.block_when_stale = false;
+        assert_eq!(assess(RATE, 91, &[], &config).verdict, OracleVerdict::Warn);
+        assert_eq!(assess(RATE, 90, &[], &config).verdict, OracleVerdict::Ok);
+    }
+
+    #[test]
+    fn test_deviation_uses_median_reference() {
+        let config = OracleGuardConfig::default();
+
+        // One outlier pool (+50%) is outvoted by two references within 1%
+        let a = assess(
+            RATE,
+            0,
+            &refs(&[3_000_000_000.0, 2_010_000_000.0, 2_020_000_000.0]),
+            &config,
+        );
+        assert_eq!(a.verdict, OracleVerdict::Ok);
+
+        let a = assess(RATE, 0, &refs(&[2_100_000_000.0]), &config);
+        assert_eq!(a.verdict, OracleVerdict::Warn);
+        assert!(a.issues[0].contains("+5.0%"), "{}", a.issues[0]);
+
+        let a = assess(RATE, 0, &refs(&[1_700_000_000.0]), &config);
+        assert_eq!(a.verdict, OracleVerdict::Block);
+    }
+
+    #[test]
+    fn test_warning_does_not_downgrade_block() {
+        let config = OracleGuardConfig::default();
+        let a = assess(RATE, 500, &refs(&[2_100_000_000.0]), &config);
+        assert_eq!(a.verdict, OracleVerdict::Block);
+        assert_eq!(a.issues.len(), 2);
+    }
+}
@@ -0,0 +1,519 @@
+//! Client-side limit orders and DCA schedules for AMM swaps.
+//!
+//! Spectrum swap orders execute immediately against a minimum output, so
+//! price conditions are evaluated here instead: on each new block every active
+//! order is re-quoted with `amm::find_best_routes` and, when it triggers, a
+//! direct swap is built for the owner's wallet. The prepared swap waits in the
+//! order until it is signed; [`mark_swap_order_submitted`] then records the
+//! execution and reschedules DCA orders.
+//!
+//! Triggered swaps are never signed automatically: Citadel holds no keys and
+//! signs only through ErgoPay or Nautilus, so every execution needs the user.
+
+use super::error::{IntoServiceError, ServiceResult};
+use crate::dto::{
+    CreateSwapOrderRequest, OrderEvent, OrderEventKind, PreparedOrderSwap, SwapOrder,
+    SwapOrderKind, SwapOrderStatus,
+};
+use crate::AppState;
+
+/// A prepared swap older than this is dropped and re-quoted: its pool box has
+/// most likely been spent by someone else.
+const PREPARED_SWAP_TTL_BLOCKS: u64 = 5;
+
+const MAX_ROUTE_HOPS: usize = 3;
+
+pub async fn list_swap_orders(state: &AppState) -> ServiceResult<Vec<SwapOrder>> {
+    Ok(state.swap_orders().await)
+}
+
+pub async fn create_swap_order(
+    state: &AppState,
+    request: CreateSwapOrderRequest,
+) -> ServiceResult<SwapOrder> {
+    let wallet = state
+        .connected_wallet()
+        .await
+        .ok_or_else(|| "Connect a wallet to create orders".to_string())?;
+
+    if request.input_amount == 0 {
+        return Err("Amount must be greater than 0".to_string());
+    }
+    if request.source_token == request.target_token {
+        return Err("Source and target token must differ".to_string());
+    }
+    match request.kind {
+        SwapOrderKind::Limit { min_output } if min_output == 0 => {
+            return Err("Limit output must be greater than 0".to_string());
+        }
+        SwapOrderKind::Dca {
+            interval_blocks,
+            executions,
+        } if interval_blocks == 0 || executions == 0 => {
+            return Err("DCA interval and execution count must be greater than 0".to_string());
+        }
+        _ => {}
+    }
+    let slippage_percent = request.slippage_percent.unwrap_or(0.5);
+    if !(0.0..=50.0).contains(&slippage_percent) {
+        return Err("Slippage must be between 0 and 50%".to_string());
+    }
+
+    let client = state.require_node_client().await?;
+    let height = client.current_height().await.into_service()?;
+
+    let order = state
+        .update_swap_orders(|orders| {
+            let order = SwapOrder {
+                id: next_order_id(orders),
+                owner: wallet.address.clone(),
+                source_token: request.source_token,
+                target_token: request.target_token,
+                input_amount: request.input_amount,
+                kind: request.kind,
+                slippage_percent,
+                status: SwapOrderStatus::Active,
+                created_height: height,
+                next_height: height,
+                last_checked_height: 0,
+                last_quoted_output: None,
+                executions_done: 0,
+                prepared: None,
+                history: vec![event(height, OrderEventKind::Created, "Order created")],
+            };
+            orders.push(order.clone());
+            order
+        })
+        .await
+        .into_service()?;
+    Ok(order)
+}
+
+pub async fn cancel_swap_order(state: &AppState, id: &str) -> ServiceResult<Vec<SwapOrder>> {
+    let client = state.require_node_client().await?;
+    let height = client.current_height().await.into_service()?;
+
+    state
+        .update_swap_orders(|orders| {
+            let order = find_order(orders, id)?;
+            if is_finished(order.status) {
+                return Err(format!("Order {} is already finished", id));
+            }
+            order.status = SwapOrderStatus::Cancelled;
+            order.prepared = None;
+            order.history.push(event(
+                height,
+                OrderEventKind::Cancelled,
+                "Cancelled by user",
+            ));
+            Ok(())
+        })
+        .await
+        .into_service()??;
+    Ok(state.swap_orders().await)
+}
+
+/// Drop a prepared swap without signing it. Limit orders go back to watching
+/// the price; DCA orders skip this execution and wait for the next interval.
+pub async fn skip_swap_order_execution(
+    state: &AppState,
+    id: &str,
+) -> ServiceResult<Vec<SwapOrder>> {
+    let client = state.require_node_client().await?;
+    let height = client.current_height().await.into_service()?;
+
+    state
+        .update_swap_orders(|orders| {
+            let order = find_order(orders, id)?;
+            if order.status != SwapOrderStatus::AwaitingSignature {
+                return Err(format!("Order {} has no prepared swap", id));
+            }
+            order.prepared = None;
+            order.status = SwapOrderStatus::Active;
+            if let SwapOrderKind::Dca {
+                interval_blocks, ..
+            } = order.kind
+            {
+                order.next_height = height + interval_blocks as u64;
+            }
+            order
+                .history
+                .push(event(height, OrderEventKind::Skipped, "Skipped by user"));
+            Ok(())
+        })
+        .await
+        .into_service()??;
+    Ok(state.swap_orders().await)
+}
+
+/// Record that the prepared swap of order `id` was signed and submitted.
+pub async fn mark_swap_order_submitted(
+    state: &AppState,
+    id: &str,
+    tx_id: &str,
+) -> ServiceResult<SwapOrder> {
+    let order = state
+        .update_swap_orders(|orders| {
+            let order = find_order(orders, id)?;
+            let prepared = order
+                .prepared
+                .take()
+                .ok_or_else(|| format!("Order {} has no prepared swap", id))?;
+            record_execution(order, prepared.height, tx_id);
+            Ok(order.clone())
+        })
+        .await
+        .into_service()??;
+    Ok(order)
+}
+
+/// Re-quote every active order once per block and prepare swaps for those that
+/// trigger. Returns the orders whose status changed during this pass.
+pub async fn process_swap_orders(state: &AppState) -> ServiceResult<Vec<SwapOrder>> {
+    let pending: Vec<SwapOrder> = state
+        .swap_orders()
+        .await
+        .into_iter()
+        .filter(|o| !is_finished(o.status))
+        .collect();
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let client = state.require_node_client().await?;
+    let height = client.current_height().await.into_service()?;
+    if pending.iter().all(|o| o.last_checked_height >= height) {
+        return Ok(Vec::new());
+    }
+
+    let pools = amm::discover_pools(&client).await.into_service()?;
+    let wallet = state.connected_wallet().await;
+
+    let mut changed = Vec::new();
+    for order in pending {
+        if order.last_checked_height >= height {
+            continue;
+        }
+
+        if order.status == SwapOrderStatus::AwaitingSignature {
+            let stale = match &order.prepared {
+                Some(p) => height >= p.height + PREPARED_SWAP_TTL_BLOCKS,
+                None => true,
+            };
+            if stale {
+                let updated = update_order(state, &order.id, |o| {
+                    o.prepared = None;
+                    o.status = SwapOrderStatus::Active;
+                    o.last_checked_height = height;
+                    o.history.push(event(
+                        height,
+                        OrderEventKind::Skipped,
+                        "Prepared swap expired unsigned; re-quoting",
+                    ));
+                })
+                .await?;
+                changed.extend(updated);
+            }
+            continue;
+        }
+
+        if height < order.next_height {
+            continue;
+        }
+
+        let quote = best_direct_route(&pools, &order);
+        let output = quote.as_ref().map(|r| r.total_output);
+        if !should_trigger(&order, output) {
+            update_order(state, &order.id, |o| {
+                o.last_checked_height = height;
+                o.last_quoted_output = output;
+            })
+            .await?;
+            continue;
+        }
+        let Some(route) = quote else {
+            continue;
+        };
+
+        let owner_connected = wallet.as_ref().is_some_and(|w| w.address == order.owner);
+        let prepared = if owner_connected {
+            prepare_swap(state, &client, &order, &route, height).await
+        } else {
+            Err("Owner wallet is not connected".to_string())
+        };
+
+        let updated = update_order(state, &order.id, |o| {
+            o.last_checked_height = height;
+            o.last_quoted_output = Some(route.total_output);
+            match prepared {
+                Ok(prepared) => {
+                    o.history.push(event(
+                        height,
+                        OrderEventKind::Triggered,
+                        &format!(
+                            "Quoted {} (min {}) via pool {}",
+                            prepared.quoted_output,
+                            prepared.min_output,
+                            &prepared.pool_id[..prepared.pool_id.len().min(8)]
+                        ),
+                    ));
+                    o.prepared = Some(prepared);
+                    o.status = SwapOrderStatus::AwaitingSignature;
+                }
+                Err(e) => {
+                    o.history.push(event(height, OrderEventKind::Skipped, &e));
+                }
+            }
+        })
+        .await?;
+        changed.extend(updated);
+    }
+
+    Ok(changed)
+}
+
+/// Whether `order` should execute given the best executable route output.
+pub(crate) fn should_trigger(order: &SwapOrder, best_output: Option<u64>) -> bool {
+    let Some(output) = best_output else {
+        return false;
+    };
+    match order.kind {
+        SwapOrderKind::Limit { min_output } => output >= min_output,
+        SwapOrderKind::Dca { .. } => output > 0,
+    }
+}
+
+/// Minimum output for the prepared swap: the quote less slippage, but never
+/// below a limit order's price.
+pub(crate) fn order_min_output(order: &SwapOrder, quoted_output: u64) -> u64 {
+    let with_slippage = amm::calculator::apply_slippage(quoted_output, order.slippage_percent);
+    match order.kind {
+        SwapOrderKind::Limit { min_output } => with_slippage.max(min_output),
+        SwapOrderKind::Dca { .. } => with_slippage,
+    }
+}
+
+/// Count one execution: limit orders complete, DCA orders reschedule until
+/// their execution count is reached.
+pub(crate) fn record_execution(order: &mut SwapOrder, height: u64, tx_id: &str) {
+    order.executions_done += 1;
+    order.history.push(OrderEvent {
+        height,
+        kind: OrderEventKind::Submitted,
+        message: format!("Execution {} submitted", order.executions_done),
+        tx_id: Some(tx_id.to_string()),
+    });
+    let done = match order.kind {
+        SwapOrderKind::Limit { .. } => true,
+        SwapOrderKind::Dca {
+            interval_blocks,
+            executions,
+        } => {
+            order.next_height = height + interval_blocks as u64;
+            order.executions_done >= executions
+        }
+    };
+    if done {
+        order.status = SwapOrderStatus::Completed;
+        order
+            .history
+            .push(event(height, OrderEventKind::Completed, "Order completed"));
+    } else {
+        order.status = SwapOrderStatus::Active;
+    }
+}
+
+/// Best route that a single direct swap can execute. Multi-hop and protocol
+/// bank routes are quote-only, so the engine waits for a direct pool to meet
+/// the condition.
+fn best_direct_route(pools: &[amm::AmmPool], order: &SwapOrder) -> Option<amm::Route> {
+    let mut graph = amm::build_pool_graph(pools, amm::DEFAULT_MIN_LIQUIDITY_NANO);
+    amm::ensure_direct_pair_edges(&mut graph, pools, &order.source_token, &order.target_token);
+    amm::find_best_routes(
+        &graph,
+        &order.source_token,
+        &order.target_token,
+        order.input_amount,
+        MAX_ROUTE_HOPS,
+        10,
+    )
+    .into_iter()
+    .find(|r| r.hops.len() == 1)
+}
+
+async fn prepare_swap(
+    state: &AppState,
+    client: &ergo_node_client::NodeClient,
+    order: &SwapOrder,
+    route: &amm::Route,
+    height: u64,
+) -> Result<PreparedOrderSwap, String> {
+    let wallet = state
+        .connected_wallet()
+        .await
+        .ok_or_else(|| "Owner wallet is not connected".to_string())?;
+    let utxos = client
+        .get_effective_utxos_multi(&wallet.addresses)
+        .await
+        .into_service()?;
+    if utxos.is_empty() {
+        return Err("Owner wallet has no spendable UTXOs".to_string());
+    }
+
+    let pool_id = route.hops[0].pool_id.clone();
+    let (input_type, token_id) = if order.source_token == amm::ERG_TOKEN_ID {
+        ("erg", None)
+    } else {
+        ("token", Some(order.source_token.clone()))
+    };
+    let min_output = order_min_output(order, route.total_output);
+
+    let built = super::amm::build_direct_swap_tx(
+        state,
+        &pool_id,
+        input_type,
+        order.input_amount,
+        token_id,
+        min_output,
+        utxos,
+        height as i32,
+        None,
+        None,
+        Some(true),
+    )
+    .await?;
+
+    Ok(PreparedOrderSwap {
+        height,
+        pool_id,
+        quoted_output: route.total_output,
+        min_output,
+        unsigned_tx: built.unsigned_tx,
+        intent: built.intent,
+    })
+}
+
+async fn update_order(
+    state: &AppState,
+    id: &str,
+    f: impl FnOnce(&mut SwapOrder),
+) -> ServiceResult<Option<SwapOrder>> {
+    state
+        .update_swap_orders(|orders| {
+            // The order may have been cancelled while quoting
+            let order = orders
+                .iter_mut()
+                .find(|o| o.id == id && !is_finished(o.status))?;
+            let before = order.status;
+            f(order);
+            (order.status != before).then(|| order.clone())
+        })
+        .await
+        .into_service()
+}
+
+fn find_order<'a>(orders: &'a mut [SwapOrder], id: &str) -> Result<&'a mut SwapOrder, String> {
+    orders
+        .iter_mut()
+        .find(|o| o.id == id)
+        .ok_or_else(|| format!("Order not found: {}", id))
+}
+
+fn is_finished(status: SwapOrderStatus) -> bool {
+    matches!(
+        status,
+        SwapOrderStatus::Completed | SwapOrderStatus::Cancelled
+    )
+}
+
+fn next_order_id(orders: &[SwapOrder]) -> String {
+    let next = orders
+        .iter()
+        .filter_map(|o| o.id.strip_prefix("order-")?.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    format!("order-{}", next)
+}
+
+fn event(height: u64, kind: OrderEventKind, message: &str) -> OrderEvent {
+    OrderEvent {
+        height,
+        kind,
+        message: message.to_string(),
+        tx_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(kind: SwapOrderKind) -> SwapOrder {
+        SwapOrder {
+            id: "order-1".to_string(),
+            owner: "9f".to_string(),
+            source_token: "ERG".to_string(),
+            target_token: "token".to_string(),
+            input_amount: 1_000_000_000,
+            kind,
+            slippage_percent: 1.0,
+            status: SwapOrderStatus::Active,
+            created_height: 100,
+            next_height: 100,
+            last_checked_height: 0,
+            last_quoted_output: None,
+            executions_done: 0,
+            prepared: None,
+            history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_limit_triggers_at_price() {
+        let limit = order(SwapOrderKind::Limit { min_output: 500 });
+        assert!(!should_trigger(&limit, None));
+        assert!(!should_trigger(&limit, Some(499)));
+        assert!(should_trigger(&limit, Some(500)));
+    }
+
+    #[test]
+    fn test_limit_min_output_never_below_limit() {
+        let limit = order(SwapOrderKind::Limit { min_output: 500 });
+        // 1% slippage on 502 would allow 496
+        assert_eq!(order_min_output(&limit, 502), 500);
+        assert_eq!(order_min_output(&limit, 1000), 990);
+    }
+
+    #[test]
+    fn test_dca_reschedules_until_done() {
+        let mut dca = order(SwapOrderKind::Dca {
+            interval_blocks: 720,
+            executions: 2,
+        });
+        record_execution(&mut dca, 100, "tx1");
+        assert_eq!(dca.status, SwapOrderStatus::Active);
+        assert_eq!(dca.next_height, 820);
+
+        record_execution(&mut dca, 820, "tx2");
+        assert_eq!(dca.status, SwapOrderStatus::Completed);
+        assert_eq!(dca.executions_done, 2);
+        assert_eq!(dca.history.last().unwrap().kind, OrderEventKind::Completed);
+    }
+
+    #[test]
+    fn test_limit_completes_after_one_execution() {
+        let mut limit = order(SwapOrderKind::Limit { min_output: 500 });
+        record_execution(&mut limit, 150, "tx1");
+        assert_eq!(limit.status, SwapOrderStatus::Completed);
+        assert_eq!(limit.history[0].tx_id.as_deref(), Some("tx1"));
+    }
+
+    #[test]
+    fn test_next_order_id() {
+        let mut orders = vec![order(SwapOrderKind::Limit { min_output: 1 })];
+        orders[0].id = "order-7".to_string();
+        assert_eq!(next_order_id(&orders), "order-8");
+        assert_eq!(next_order_id(&[]), "order-1");
+    }
+}
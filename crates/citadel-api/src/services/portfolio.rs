@@ -0,0 +1,467 @@
+//! Combined portfolio across the connected wallet and every watch-only wallet:
+//! balances plus lending, LP, SigmaFi, MewLock and HodlCoin positions.
+//!
+//! Market-wide protocol state (pools, bonds, locks, banks) is fetched once and
+//! matched against each wallet's addresses and balances; only lending positions
+//! need a per-address query.
+//!
+//! An address listed by several wallets is counted once, under the first wallet
+//! listing it (the connected wallet comes first).
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use ergo_node_client::NodeClient;
+
+use super::error::{IntoServiceError, ServiceResult};
+use crate::dto::{
+    HodlPositionInfo, LpPositionInfo, PortfolioResponse, PortfolioToken, PortfolioWallet,
+    WalletPosition,
+};
+use crate::{AppState, WalletState};
+
+/// Display name of the Nautilus / ErgoPay connected wallet in portfolio output.
+const CONNECTED_WALLET_NAME: &str = "connected";
+
+fn wallet_name(wallet: &WalletState) -> String {
+    wallet
+        .watch_only
+        .clone()
+        .unwrap_or_else(|| CONNECTED_WALLET_NAME.to_string())
+}
+
+/// Give each address to the first wallet listing it. Wallets left without
+/// addresses are fully covered by earlier ones and dropped.
+fn dedup_wallet_addresses(wallets: Vec<WalletState>) -> Vec<WalletState> {
+    let mut seen: HashSet<String> = HashSet::new();
+    wallets
+        .into_iter()
+        .filter_map(|mut wallet| {
+            wallet.addresses.retain(|a| seen.insert(a.clone()));
+            if wallet.addresses.is_empty() {
+                return None;
+            }
+            if !wallet.addresses.contains(&wallet.address) {
+                wallet.address = wallet.addresses[0].clone();
+            }
+            Some(wallet)
+        })
+        .collect()
+}
+
+pub async fn get_portfolio(state: &AppState) -> ServiceResult<PortfolioResponse> {
+    let wallets = dedup_wallet_addresses(state.all_wallets().await);
+    if wallets.is_empty() {
+        return Err("No wallet connected and no watch-only wallets added".to_string());
+    }
+
+    let client = state.require_node_client().await?;
+    let caps = client.require_capabilities().await?;
+    if caps.capability_tier == ergo_node_client::CapabilityTier::Basic {
+        return Err("Portfolio queries require extraIndex enabled on the node".to_string());
+    }
+    let height = client.current_height().await.into_service()? as u32;
+    let active = state.wallet().await.map(|w| wallet_name(&w));
+
+    let mut token_info: HashMap<String, (Option<String>, u8)> = HashMap::new();
+    let mut totals: BTreeMap<String, u64> = BTreeMap::new();
+    let mut total_erg_nano: u64 = 0;
+    let mut portfolio_wallets = Vec::with_capacity(wallets.len());
+
+    for wallet in &wallets {
+        let (erg_nano, tokens) = client
+            .get_addresses_balances(&wallet.addresses)
+            .await
+            .into_service()?;
+        total_erg_nano += erg_nano;
+
+        let mut wallet_tokens = Vec::with_capacity(tokens.len());
+        for (token_id, amount) in tokens {
+            *totals.entry(token_id.clone()).or_insert(0) += amount;
+            wallet_tokens.push(portfolio_token(&client, &mut token_info, token_id, amount).await);
+        }
+
+        let name = wallet_name(wallet);
+        portfolio_wallets.push(PortfolioWallet {
+            active: active.as_deref() == Some(name.as_str()),
+            name,
+            watch_only: wallet.is_watch_only(),
+            addresses: wallet.addresses.clone(),
+            erg_nano,
+            tokens: wallet_tokens,
+        });
+    }
+
+    let mut tokens = Vec::with_capacity(totals.len());
+    for (token_id, amount) in totals {
+        tokens.push(portfolio_token(&client, &mut token_info, token_id, amount).await);
+    }
+
+    let mut response = PortfolioResponse {
+        wallets: portfolio_wallets,
+        total_erg_nano,
+        tokens,
+        lend_positions: Vec::new(),
+        borrow_positions: Vec::new(),
+        lp_positions: Vec::new(),
+        sigmafi_bonds: Vec::new(),
+        sigmafi_orders: Vec::new(),
+        mewlocks: Vec::new(),
+        hodl_positions: Vec::new(),
+        errors: Vec::new(),
+        block_height: height,
+    };
+
+    let pools = match amm::discover_pools(&client).await {
+        Ok(pools) => Some(pools),
+        Err(e) => {
+            response.errors.push(format!("AMM pools: {}", e));
+            None
+        }
+    };
+
+    collect_lending(state, &wallets, &mut response).await;
+    if let Some(pools) = &pools {
+        collect_lp(pools, &mut response);
+    }
+    collect_dexy_lp(state, &client, &caps, &mut response).await;
+    screen_portfolio_tokens(state, &client, pools.as_deref(), &mut response).await;
+    collect_sigmafi(&client, &wallets, height, &mut response).await;
+    collect_mewlock(&client, &wallets, height, &mut response).await;
+    collect_hodl(&client, &mut response).await;
+
+    Ok(response)
+}
+
+async fn portfolio_token(
+    client: &NodeClient,
+    cache: &mut HashMap<String, (Option<String>, u8)>,
+    token_id: String,
+    amount: u64,
+) -> PortfolioToken {
+    if !cache.contains_key(&token_id) {
+        let info = match client.get_token_info(&token_id).await {
+            Ok(ti) => (ti.name, ti.decimals.unwrap_or(0) as u8),
+            Err(_) => (None, 0),
+        };
+        cache.insert(token_id.clone(), info);
+    }
+    let (name, decimals) = cache.get(&token_id).cloned().unwrap_or((None, 0));
+    PortfolioToken {
+        token_id,
+        amount,
+        name,
+        decimals,
+        verified: false,
+        warning: None,
+    }
+}
+
+/// Fill in `verified` / `warning` on every wallet and total token.
+async fn screen_portfolio_tokens(
+    state: &AppState,
+    client: &NodeClient,
+    pools: Option<&[amm::AmmPool]>,
+    out: &mut PortfolioResponse,
+) {
+    let screen: Vec<(String, Option<String>)> = out
+        .tokens
+        .iter()
+        .map(|t| (t.token_id.clone(), t.name.clone()))
+        .collect();
+    let statuses = super::tokens::screen_tokens(state, client, pools, &screen).await;
+    let tokens = out
+        .tokens
+        .iter_mut()
+        .chain(out.wallets.iter_mut().flat_map(|w| w.tokens.iter_mut()));
+    for token in tokens {
+        if let Some(status) = statuses.get(&token.token_id) {
+            token.verified = status.verified;
+            token.warning = status.warning.clone();
+        }
+    }
+}
+
+async fn collect_lending(state: &AppState, wallets: &[WalletState], out: &mut PortfolioResponse) {
+    for wallet in wallets {
+        let name = wallet_name(wallet);
+        for address in &wallet.addresses {
+            match super::lending::get_positions(state, address.clone()).await {
+                Ok(positions) => {
+                    out.lend_positions
+                        .extend(
+                            positions
+                                .lend_positions
+                                .into_iter()
+                                .map(|p| WalletPosition {
+                                    wallet: name.clone(),
+                                    position: p,
+                                }),
+                        );
+                    out.borrow_positions
+                        .extend(
+                            positions
+                                .borrow_positions
+                                .into_iter()
+                                .map(|p| WalletPosition {
+                                    wallet: name.clone(),
+                                    position: p,
+                                }),
+                        );
+                }
+                Err(e) => {
+                    out.errors.push(format!("Lending ({}): {}", address, e));
+                }
+            }
+        }
+    }
+}
+
+fn collect_lp(pools: &[amm::AmmPool], out: &mut PortfolioResponse) {
+    let by_lp_token: HashMap<&str, &amm::AmmPool> =
+        pools.iter().map(|p| (p.lp_token_id.as_str(), p)).collect();
+
+    for wallet in &out.wallets {
+        for token in &wallet.tokens {
+            let Some(pool) = by_lp_token.get(token.token_id.as_str()) else {
+                continue;
+            };
+            out.lp_positions.push(WalletPosition {
+                wallet: wallet.name.clone(),
+                position: lp_position(pool, token.amount),
+            });
+        }
+    }
+}
+
+/// Dexy LP tokens, valued by their share of the Dexy LP pool reserves.
+async fn collect_dexy_lp(
+    state: &AppState,
+    client: &NodeClient,
+    caps: &ergo_node_client::NodeCapabilities,
+    out: &mut PortfolioResponse,
+) {
+    let network = state.network().await;
+    for variant in [dexy::DexyVariant::Gold, dexy::DexyVariant::Usd] {
+        let Some(ids) = dexy::DexyIds::for_variant(variant, network) else {
+            continue;
+        };
+        let holders: Vec<(String, u64)> = out
+            .wallets
+            .iter()
+            .flat_map(|w| {
+                w.tokens
+                    .iter()
+                    .filter(|t| t.token_id == ids.lp_token_id)
+                    .map(|t| (w.name.clone(), t.amount))
+            })
+            .collect();
+        if holders.is_empty() {
+            continue;
+        }
+        let dexy_state = match dexy::fetch_dexy_state(client, caps, &ids).await {
+            Ok(s) => s,
+            Err(e) => {
+                out.errors
+                    .push(format!("{} LP: {}", variant.token_name(), e));
+                continue;
+            }
+        };
+        let erg_reserves = dexy_state.lp_erg_reserves.max(0) as u64;
+        let dexy_reserves = dexy_state.lp_dexy_reserves.max(0) as u64;
+        let lp_circulating = dexy_state.lp_circulating.max(0) as u64;
+
+        for (wallet, lp_amount) in holders {
+            let (x_amount, y_amount) = amm::calculator::calculate_redeem_shares(
+                erg_reserves,
+                dexy_reserves,
+                lp_circulating,
+                lp_amount,
+            );
+            out.lp_positions.push(WalletPosition {
+                wallet,
+                position: LpPositionInfo {
+                    pool_id: ids.lp_nft.clone(),
+                    lp_token_id: ids.lp_token_id.clone(),
+                    lp_amount,
+                    pool_share_pct: if lp_circulating > 0 {
+                        lp_amount as f64 / lp_circulating as f64 * 100.0
+                    } else {
+                        0.0
+                    },
+                    x_token_id: amm::ERG_TOKEN_ID.to_string(),
+                    x_amount,
+                    y_token_id: ids.dexy_token.clone(),
+                    y_name: Some(variant.token_name().to_string()),
+                    y_amount,
+                },
+            });
+        }
+    }
+}
+
+fn lp_position(pool: &amm::AmmPool, lp_amount: u64) -> LpPositionInfo {
+    let (x_token_id, x_reserves) = match (&pool.pool_type, &pool.token_x) {
+        (amm::PoolType::T2T, Some(x)) => (x.token_id.clone(), x.amount),
+        _ => (
+            amm::ERG_TOKEN_ID.to_string(),
+            pool.erg_reserves.unwrap_or(0),
+        ),
+    };
+    let (x_amount, y_amount) = amm::calculator::calculate_redeem_shares(
+        x_reserves,
+        pool.token_y.amount,
+        pool.lp_circulating,
+        lp_amount,
+    );
+    let pool_share_pct = if pool.lp_circulating > 0 {
+        lp_amount as f64 / pool.lp_circulating as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    LpPositionInfo {
+        pool_id: pool.pool_id.clone(),
+        lp_token_id: pool.lp_token_id.clone(),
+        lp_amount,
+        pool_share_pct,
+        x_token_id,
+        x_amount,
+        y_token_id: pool.token_y.token_id.clone(),
+        y_name: pool.token_y.name.clone(),
+        y_amount,
+    }
+}
+
+/// Wallet owning `address`, if any.
+fn owner<'a>(wallets: &'a [WalletState], address: &str) -> Option<&'a WalletState> {
+    wallets
+        .iter()
+        .find(|w| w.addresses.iter().any(|a| a == address))
+}
+
+async fn collect_sigmafi(
+    client: &NodeClient,
+    wallets: &[WalletState],
+    height: u32,
+    out: &mut PortfolioResponse,
+) {
+    let market = match sigmafi::fetch_bond_market(client, None, height, None).await {
+        Ok(market) => market,
+        Err(e) => {
+            out.errors.push(format!("SigmaFi: {}", e));
+            return;
+        }
+    };
+
+    for bond in market.bonds {
+        let holder =
+            owner(wallets, &bond.lender_address).or_else(|| owner(wallets, &bond.borrower_address));
+        if let Some(wallet) = holder {
+            out.sigmafi_bonds.push(WalletPosition {
+                wallet: wallet_name(wallet),
+                position: bond,
+            });
+        }
+    }
+    for order in market.orders {
+        if let Some(wallet) = owner(wallets, &order.borrower_address) {
+            out.sigmafi_orders.push(WalletPosition {
+                wallet: wallet_name(wallet),
+                position: order,
+            });
+        }
+    }
+}
+
+async fn collect_mewlock(
+    client: &NodeClient,
+    wallets: &[WalletState],
+    height: u32,
+    out: &mut PortfolioResponse,
+) {
+    let locks = match mewlock::fetch_mewlock_state(client, None, height).await {
+        Ok(state) => state.locks,
+        Err(e) => {
+            out.errors.push(format!("MewLock: {}", e));
+            return;
+        }
+    };
+
+    for lock in locks {
+        if let Some(wallet) = owner(wallets, &lock.depositor_address) {
+            out.mewlocks.push(WalletPosition {
+                wallet: wallet_name(wallet),
+                position: lock,
+            });
+        }
+    }
+}
+
+async fn collect_hodl(client: &NodeClient, out: &mut PortfolioResponse) {
+    let banks = match hodlcoin::discover_banks(client).await {
+        Ok(banks) => banks,
+        Err(e) => {
+            out.errors.push(format!("HodlCoin: {}", e));
+            return;
+        }
+    };
+
+    for wallet in &out.wallets {
+        for token in &wallet.tokens {
+            let Some(bank) = banks.iter().find(|b| b.hodl_token_id == token.token_id) else {
+                continue;
+            };
+            // Token-backed banks pay out in their reserve token
+            let burn = hodlcoin::burn_amount(
+                bank.reserve_amount,
+                bank.circulating_supply,
+                bank.precision_factor,
+                token.amount as i64,
+                bank.bank_fee_num,
+                bank.dev_fee_num,
+            );
+            out.hodl_positions.push(WalletPosition {
+                wallet: wallet.name.clone(),
+                position: HodlPositionInfo {
+                    singleton_token_id: bank.singleton_token_id.clone(),
+                    hodl_token_id: bank.hodl_token_id.clone(),
+                    hodl_token_name: bank.hodl_token_name.clone(),
+                    amount: token.amount,
+                    price_nano_per_hodl: bank.price_nano_per_hodl,
+                    reserve_token_id: bank.reserve_token_id.clone(),
+                    reserve_token_name: bank.reserve_token_name.clone(),
+                    burn_value: burn.erg_to_user,
+                },
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet(name: Option<&str>, addresses: &[&str]) -> WalletState {
+        WalletState {
+            watch_only: name.map(str::to_string),
+            ..WalletState::with_addresses(
+                addresses[0].to_string(),
+                addresses.iter().map(|a| a.to_string()).collect(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_shared_addresses_counted_once() {
+        let wallets = dedup_wallet_addresses(vec![
+            wallet(None, &["9a", "9b"]),
+            wallet(Some("cold"), &["9b", "9c"]),
+            wallet(Some("copy"), &["9a"]),
+        ]);
+
+        assert_eq!(wallets.len(), 2);
+        assert_eq!(wallets[0].addresses, vec!["9a", "9b"]);
+        assert_eq!(wallets[1].addresses, vec!["9c"]);
+        assert_eq!(wallets[1].address, "9c");
+    }
+}
@@ -0,0 +1,487 @@
+//! LP position tracker for Spectrum and Dexy LP tokens in the active wallet.
+//!
+//! Entry data comes from the wallet's own history: the transaction that
+//! delivered LP tokens also spends and recreates the pool box, so the pool
+//! delta is the deposit and the recreated box is the pool state at entry.
+//!
+//! Fees are separated from impermanent loss with the CFMM invariant: without
+//! fees `sqrt(x * y) / lp_supply` is constant, so its growth since entry is
+//! the fee income per LP token.
+
+use std::collections::HashSet;
+
+use ergo_node_client::NodeClient;
+
+use super::error::{IntoServiceError, ServiceResult};
+use crate::dto::{LpEntry, LpPositionDetail, LpPositionsResponse};
+use crate::AppState;
+
+/// Wallet transactions scanned per address for deposit entries.
+const HISTORY_SCAN_LIMIT: u64 = 500;
+
+/// A pool whose LP token the wallet may hold.
+#[derive(Debug, Clone)]
+struct TrackedPool {
+    protocol: String,
+    pool_id: String,
+    lp_token_id: String,
+    /// `None` when X is ERG (box value).
+    x_token_id: Option<String>,
+    y_token_id: String,
+    y_name: Option<String>,
+    x_reserves: u64,
+    y_reserves: u64,
+    lp_supply: u64,
+    /// Total LP emission; circulating supply is this minus the pool's LP.
+    lp_emission: u64,
+}
+
+impl TrackedPool {
+    fn from_amm(pool: &amm::AmmPool) -> Self {
+        let (x_token_id, x_reserves) = match (&pool.pool_type, &pool.token_x) {
+            (amm::PoolType::T2T, Some(x)) => (Some(x.token_id.clone()), x.amount),
+            _ => (None, pool.erg_reserves.unwrap_or(0)),
+        };
+        Self {
+            protocol: "Spectrum".to_string(),
+            pool_id: pool.pool_id.clone(),
+            lp_token_id: pool.lp_token_id.clone(),
+            x_token_id,
+            y_token_id: pool.token_y.token_id.clone(),
+            y_name: pool.token_y.name.clone(),
+            x_reserves,
+            y_reserves: pool.token_y.amount,
+            lp_supply: pool.lp_circulating,
+            lp_emission: amm::lp::TOTAL_EMISSION as u64,
+        }
+    }
+
+    fn from_dexy(ids: &dexy::DexyIds, state: &dexy::DexyState) -> Self {
+        let variant = ids.variant;
+        Self {
+            protocol: variant.token_name().to_string(),
+            pool_id: ids.lp_nft.clone(),
+            lp_token_id: ids.lp_token_id.clone(),
+            x_token_id: None,
+            y_token_id: ids.dexy_token.clone(),
+            y_name: Some(variant.token_name().to_string()),
+            x_reserves: state.lp_erg_reserves.max(0) as u64,
+            y_reserves: state.lp_dexy_reserves.max(0) as u64,
+            lp_supply: state.lp_circulating.max(0) as u64,
+            lp_emission: variant.initial_lp().max(0) as u64,
+        }
+    }
+
+    fn price_x_per_y(&self) -> f64 {
+        if self.y_reserves == 0 {
+            return 0.0;
+        }
+        self.x_reserves as f64 / self.y_reserves as f64
+    }
+
+    /// (x, y, circulating LP) of a box holding the pool NFT.
+    fn box_reserves(&self, box_json: &serde_json::Value) -> Option<(u64, u64, u64)> {
+        let assets = box_json["assets"].as_array()?;
+        if assets.first()?["tokenId"].as_str()? != self.pool_id {
+            return None;
+        }
+        let amount = |token_id: &str| {
+            assets
+                .iter()
+                .find(|a| a["tokenId"].as_str() == Some(token_id))
+                .and_then(|a| a["amount"].as_u64())
+        };
+        let x = match &self.x_token_id {
+            Some(id) => amount(id)?,
+            None => box_json["value"].as_u64()?,
+        };
+        let y = amount(&self.y_token_id)?;
+        let locked = amount(&self.lp_token_id).unwrap_or(0);
+        Some((x, y, self.lp_emission.saturating_sub(locked)))
+    }
+}
+
+/// Net LP tokens `tx` moved into `addresses`.
+fn net_lp_received(tx: &serde_json::Value, lp_token_id: &str, addresses: &HashSet<&str>) -> i128 {
+    let sum = |boxes: &serde_json::Value| -> i128 {
+        boxes
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+            .iter()
+            .filter(|b| addresses.contains(b["address"].as_str().unwrap_or("")))
+            .flat_map(|b| b["assets"].as_array().cloned().unwrap_or_default())
+            .filter(|a| a["tokenId"].as_str() == Some(lp_token_id))
+            .map(|a| a["amount"].as_u64().unwrap_or(0) as i128)
+            .sum()
+    };
+    sum(&tx["outputs"]) - sum(&tx["inputs"])
+}
+
+/// The deposit `tx` made into `pool` for `addresses`, if it is one.
+fn deposit_entry(
+    tx: &serde_json::Value,
+    pool: &TrackedPool,
+    addresses: &HashSet<&str>,
+) -> Option<LpEntry> {
+    let received = net_lp_received(tx, &pool.lp_token_id, addresses);
+    if received <= 0 {
+        return None;
+    }
+    let find_pool_box =
+        |boxes: &serde_json::Value| boxes.as_array()?.iter().find_map(|b| pool.box_reserves(b));
+    let (x_before, y_before, _) = find_pool_box(&tx["inputs"])?;
+    let (x_after, y_after, supply_after) = find_pool_box(&tx["outputs"])?;
+    if x_after < x_before || y_after < y_before {
+        return None;
+    }
+
+    Some(LpEntry {
+        tx_id: tx["id"].as_str().unwrap_or_default().to_string(),
+        height: tx["inclusionHeight"].as_u64().unwrap_or(0),
+        timestamp: tx["timestamp"].as_u64().unwrap_or(0),
+        lp_received: received as u64,
+        x_deposited: x_after - x_before,
+        y_deposited: y_after - y_before,
+        price_x_per_y: if y_after > 0 {
+            x_after as f64 / y_after as f64
+        } else {
+            0.0
+        },
+        pool_x: x_after,
+        pool_y: y_after,
+        pool_lp_supply: supply_after,
+    })
+}
+
+/// `sqrt(x * y) / supply`: the per-LP invariant that only fees grow.
+fn invariant_per_lp(x: u64, y: u64, supply: u64) -> f64 {
+    if supply == 0 {
+        return 0.0;
+    }
+    ((x as f64) * (y as f64)).sqrt() / supply as f64
+}
+
+/// Current value, entry matching and IL / fee split for `lp_amount` tokens.
+/// `deposits` are newest first; the newest deposits are matched to the
+/// holding first, the oldest one partially if needed.
+fn position_detail(pool: &TrackedPool, lp_amount: u64, deposits: &[LpEntry]) -> LpPositionDetail {
+    let (x_amount, y_amount) = amm::calculator::calculate_redeem_shares(
+        pool.x_reserves,
+        pool.y_reserves,
+        pool.lp_supply,
+        lp_amount,
+    );
+    let price = pool.price_x_per_y();
+    let value_x = x_amount + (y_amount as f64 * price) as u64;
+    let pool_share_pct = if pool.lp_supply > 0 {
+        lp_amount as f64 / pool.lp_supply as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    let mut entries = Vec::new();
+    let mut remaining = lp_amount;
+    for deposit in deposits {
+        if remaining == 0 {
+            break;
+        }
+        let mut entry = deposit.clone();
+        if entry.lp_received > remaining {
+            let scale = |v: u64| (v as u128 * remaining as u128 / entry.lp_received as u128) as u64;
+            entry.x_deposited = scale(entry.x_deposited);
+            entry.y_deposited = scale(entry.y_deposited);
+            entry.lp_received = remaining;
+        }
+        remaining -= entry.lp_received;
+        entries.push(entry);
+    }
+    let tracked = lp_amount - remaining;
+
+    let mut detail = LpPositionDetail {
+        protocol: pool.protocol.clone(),
+        pool_id: pool.pool_id.clone(),
+        lp_token_id: pool.lp_token_id.clone(),
+        lp_amount,
+        pool_share_pct,
+        x_token_id: pool
+            .x_token_id
+            .clone()
+            .unwrap_or_else(|| amm::ERG_TOKEN_ID.to_string()),
+        x_amount,
+        y_token_id: pool.y_token_id.clone(),
+        y_name: pool.y_name.clone(),
+        y_amount,
+        price_x_per_y: price,
+        value_x,
+        entries,
+        untracked_lp: remaining,
+        entry_price_x_per_y: None,
+        hodl_value_x: None,
+        impermanent_loss_x: None,
+        impermanent_loss_pct: None,
+        fees_earned_x: None,
+        net_vs_hodl_x: None,
+    };
+    if tracked == 0 || price <= 0.0 {
+        return detail;
+    }
+
+    let x_deposited: u64 = detail.entries.iter().map(|e| e.x_deposited).sum();
+    let y_deposited: u64 = detail.entries.iter().map(|e| e.y_deposited).sum();
+    let hodl = x_deposited as f64 + y_deposited as f64 * price;
+
+    // At price p a fee-less CFMM share with invariant r holds r*sqrt(p) X and
+    // r/sqrt(p) Y, worth 2*r*sqrt(p) in X
+    let value_factor = 2.0 * price.sqrt();
+    let current = tracked as f64
+        * invariant_per_lp(pool.x_reserves, pool.y_reserves, pool.lp_supply)
+        * value_factor;
+    let without_fees: f64 = detail
+        .entries
+        .iter()
+        .map(|e| {
+            e.lp_received as f64
+                * invariant_per_lp(e.pool_x, e.pool_y, e.pool_lp_supply)
+                * value_factor
+        })
+        .sum();
+
+    if y_deposited > 0 {
+        detail.entry_price_x_per_y = Some(x_deposited as f64 / y_deposited as f64);
+    }
+    detail.hodl_value_x = Some(hodl as u64);
+    detail.impermanent_loss_x = Some((without_fees - hodl).min(0.0) as i64);
+    detail.impermanent_loss_pct =
+        (hodl > 0.0).then(|| (without_fees / hodl - 1.0).min(0.0) * 100.0);
+    detail.fees_earned_x = Some((current - without_fees).max(0.0) as u64);
+    detail.net_vs_hodl_x = Some((current - hodl) as i64);
+    detail
+}
+
+async fn tracked_pools(
+    client: &NodeClient,
+    state: &AppState,
+    errors: &mut Vec<String>,
+) -> Vec<TrackedPool> {
+    let mut pools = match amm::discover_pools(client).await {
+        Ok(pools) => pools.iter().map(TrackedPool::from_amm).collect(),
+        Err(e) => {
+            errors.push(format!("AMM pools: {}", e));
+            Vec::new()
+        }
+    };
+
+    let network = state.network().await;
+    let capabilities = match client.require_capabilities().await {
+        Ok(caps) => caps,
+        Err(e) => {
+            errors.push(format!("Dexy LP: {}", e));
+            return pools;
+        }
+    };
+    for variant in [dexy::DexyVariant::Gold, dexy::DexyVariant::Usd] {
+        let Some(ids) = dexy::DexyIds::for_variant(variant, network) else {
+            continue;
+        };
+        match dexy::fetch_dexy_state(client, &capabilities, &ids).await {
+            Ok(dexy_state) => pools.push(TrackedPool::from_dexy(&ids, &dexy_state)),
+            Err(e) => errors.push(format!("{} LP: {}", variant.token_name(), e)),
+        }
+    }
+    pools
+}
+
+/// LP positions of the connected wallet with entry, IL and fee breakdown.
+pub async fn get_lp_positions(state: &AppState) -> ServiceResult<LpPositionsResponse> {
+    let wallet = state
+        .connected_wallet()
+        .await
+        .ok_or("No wallet connected")?;
+    let client = state.require_node_client().await?;
+    let height = client.current_height().await.into_service()?;
+
+    let mut errors = Vec::new();
+    let pools = tracked_pools(&client, state, &mut errors).await;
+    let (_, balances) = client
+        .get_addresses_balances(&wallet.addresses)
+        .await
+        .into_service()?;
+
+    let held: Vec<(&TrackedPool, u64)> = balances
+        .iter()
+        .filter_map(|(token_id, amount)| {
+            let pool = pools.iter().find(|p| &p.lp_token_id == token_id)?;
+            Some((pool, *amount))
+        })
+        .collect();
+    if held.is_empty() {
+        return Ok(LpPositionsResponse {
+            positions: Vec::new(),
+            errors,
+            block_height: height,
+        });
+    }
+
+    let mut history: Vec<serde_json::Value> = Vec::new();
+    for address in &wallet.addresses {
+        match client
+            .get_transactions_by_address(address, 0, HISTORY_SCAN_LIMIT)
+            .await
+        {
+            Ok((txs, _)) => {
+                for tx in txs {
+                    if !history.iter().any(|t| t["id"] == tx["id"]) {
+                        history.push(tx);
+                    }
+                }
+            }
+            Err(e) => errors.push(format!("History ({}): {}", address, e)),
+        }
+    }
+    history.sort_by_key(|tx| std::cmp::Reverse(tx["inclusionHeight"].as_u64().unwrap_or(0)));
+
+    let addresses: HashSet<&str> = wallet.addresses.iter().map(String::as_str).collect();
+    let positions = held
+        .into_iter()
+        .map(|(pool, amount)| {
+            let deposits: Vec<LpEntry> = history
+                .iter()
+                .filter_map(|tx| deposit_entry(tx, pool, &addresses))
+                .collect();
+            position_detail(pool, amount, &deposits)
+        })
+        .collect();
+
+    Ok(LpPositionsResponse {
+        positions,
+        errors,
+        block_height: height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NFT: &str = "nft";
+    const LP: &str = "lp";
+    const Y: &str = "y";
+    const USER: &str = "9user";
+    const EMISSION: u64 = 1_000_000;
+
+    fn pool(x: u64, y: u64, supply: u64) -> TrackedPool {
+        TrackedPool {
+            protocol: "Spectrum".to_string(),
+            pool_id: NFT.to_string(),
+            lp_token_id: LP.to_string(),
+            x_token_id: None,
+            y_token_id: Y.to_string(),
+            y_name: None,
+            x_reserves: x,
+            y_reserves: y,
+            lp_supply: supply,
+            lp_emission: EMISSION,
+        }
+    }
+
+    fn pool_box(x: u64, y: u64, supply: u64) -> serde_json::Value {
+        serde_json::json!({
+            "address": "pool",
+            "value": x,
+            "assets": [
+                { "tokenId": NFT, "amount": 1 },
+                { "tokenId": LP, "amount": EMISSION - supply },
+                { "tokenId": Y, "amount": y },
+            ],
+        })
+    }
+
+    fn entry(lp: u64, x: u64, y: u64, pool_x: u64, pool_y: u64, supply: u64) -> LpEntry {
+        LpEntry {
+            tx_id: "tx".to_string(),
+            height: 1,
+            timestamp: 0,
+            lp_received: lp,
+            x_deposited: x,
+            y_deposited: y,
+            price_x_per_y: pool_x as f64 / pool_y as f64,
+            pool_x,
+            pool_y,
+            pool_lp_supply: supply,
+        }
+    }
+
+    #[test]
+    fn test_deposit_entry_from_pool_delta() {
+        let tx = serde_json::json!({
+            "id": "dep",
+            "inclusionHeight": 100,
+            "inputs": [pool_box(1_000, 4_000, 2_000), { "address": USER, "value": 600 }],
+            "outputs": [
+                pool_box(1_500, 6_000, 3_000),
+                { "address": USER, "value": 60, "assets": [{ "tokenId": LP, "amount": 1_000 }] },
+            ],
+        });
+        let addresses = HashSet::from([USER]);
+        let e = deposit_entry(&tx, &pool(0, 0, 0), &addresses).unwrap();
+        assert_eq!(e.lp_received, 1_000);
+        assert_eq!((e.x_deposited, e.y_deposited), (500, 2_000));
+        assert_eq!(
+            (e.pool_x, e.pool_y, e.pool_lp_supply),
+            (1_500, 6_000, 3_000)
+        );
+        assert_eq!(e.height, 100);
+    }
+
+    #[test]
+    fn test_transfer_is_not_a_deposit() {
+        let tx = serde_json::json!({
+            "id": "transfer",
+            "inputs": [{ "address": "9other", "assets": [{ "tokenId": LP, "amount": 5 }] }],
+            "outputs": [{ "address": USER, "assets": [{ "tokenId": LP, "amount": 5 }] }],
+        });
+        let addresses = HashSet::from([USER]);
+        assert!(deposit_entry(&tx, &pool(0, 0, 0), &addresses).is_none());
+    }
+
+    #[test]
+    fn test_price_doubling_without_fees_is_pure_il() {
+        // Entered 10% of a 1M/1M pool; price of Y doubled with k unchanged
+        let x = (2.0f64.sqrt() * 1_000_000.0) as u64;
+        let y = (1_000_000.0 / 2.0f64.sqrt()) as u64;
+        let deposits = vec![entry(100, 100_000, 100_000, 1_000_000, 1_000_000, 1_000)];
+        let detail = position_detail(&pool(x, y, 1_000), 100, &deposits);
+
+        assert_eq!(detail.untracked_lp, 0);
+        assert_eq!(detail.hodl_value_x, Some(300_000));
+        let il = detail.impermanent_loss_pct.unwrap();
+        assert!((il + 5.72).abs() < 0.01, "il {}", il);
+        assert!(detail.fees_earned_x.unwrap() < 10);
+    }
+
+    #[test]
+    fn test_fee_growth_at_constant_price() {
+        // Reserves grew 1% with no price move and no new LP
+        let deposits = vec![entry(100, 100_000, 100_000, 1_000_000, 1_000_000, 1_000)];
+        let detail = position_detail(&pool(1_010_000, 1_010_000, 1_000), 100, &deposits);
+
+        assert_eq!(detail.impermanent_loss_x, Some(0));
+        let fees = detail.fees_earned_x.unwrap();
+        assert!((1_990..=2_010).contains(&fees), "fees {}", fees);
+    }
+
+    #[test]
+    fn test_partial_match_scales_oldest_deposit() {
+        let deposits = vec![
+            entry(60, 60_000, 60_000, 1_000_000, 1_000_000, 1_000),
+            entry(100, 100_000, 100_000, 1_000_000, 1_000_000, 1_000),
+        ];
+        let detail = position_detail(&pool(1_000_000, 1_000_000, 1_000), 80, &deposits);
+
+        assert_eq!(detail.entries.len(), 2);
+        assert_eq!(detail.entries[1].lp_received, 20);
+        assert_eq!(detail.entries[1].x_deposited, 20_000);
+        assert_eq!(detail.untracked_lp, 0);
+    }
+}
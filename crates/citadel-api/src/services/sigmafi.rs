@@ -1,8 +1,36 @@
 //! SigmaFi use-case orchestration: bond market fetch, loan token list, order/bond tx building.
 
+use citadel_core::TxIntent;
+use serde::Serialize;
+
 use super::error::{IntoServiceError, ServiceResult};
 use crate::AppState;
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigmaFiBuildResponse {
+    pub unsigned_tx: serde_json::Value,
+    pub intent: TxIntent,
+}
+
+/// Flows come from the tx; loan tokens are named from the supported list.
+fn build_response(
+    tx: ergo_tx::Eip12UnsignedTx,
+    action: &str,
+    contract: &str,
+) -> ServiceResult<SigmaFiBuildResponse> {
+    let mut intent =
+        ergo_tx::derive_intent(&tx, "SigmaFi", action).counterparty("contract", contract);
+    for token in sigmafi::SUPPORTED_TOKENS {
+        intent.describe_token(token.token_id, Some(token.name), Some(token.decimals));
+    }
+    Ok(SigmaFiBuildResponse {
+        unsigned_tx: serde_json::to_value(&tx)
+            .map_err(|e| format!("Failed to serialize tx: {}", e))?,
+        intent,
+    })
+}
+
 async fn oracle_erg_usd(state: &AppState) -> ServiceResult<f64> {
     let client = state.require_node_client().await?;
     let capabilities = client.require_capabilities().await?;
@@ -54,7 +82,7 @@ pub fn build_open_order(
     collateral_tokens: Vec<(String, u64)>,
     user_inputs: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<SigmaFiBuildResponse> {
     let req = sigmafi::tx_builder::OpenOrderRequest {
         borrower_ergo_tree,
         loan_token_id,
@@ -67,7 +95,8 @@ pub fn build_open_order(
         current_height,
     };
 
-    sigmafi::tx_builder::build_open_order(&req).into_service()
+    let tx = sigmafi::tx_builder::build_open_order(&req).into_service()?;
+    build_response(tx, "Open loan request", "SigmaFi order")
 }
 
 pub async fn build_cancel_order(
@@ -76,7 +105,7 @@ pub async fn build_cancel_order(
     borrower_ergo_tree: String,
     user_inputs: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<SigmaFiBuildResponse> {
     let client = state.require_node_client().await?;
     let order_box = client
         .get_eip12_box_by_id(box_id)
@@ -90,7 +119,8 @@ pub async fn build_cancel_order(
         current_height,
     };
 
-    sigmafi::tx_builder::build_cancel_order(&req).into_service()
+    let tx = sigmafi::tx_builder::build_cancel_order(&req).into_service()?;
+    build_response(tx, "Cancel loan request", "SigmaFi order")
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -102,7 +132,7 @@ pub async fn build_close_order(
     loan_token_id: String,
     user_inputs: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<SigmaFiBuildResponse> {
     let client = state.require_node_client().await?;
     let order_box = client
         .get_eip12_box_by_id(box_id)
@@ -118,7 +148,8 @@ pub async fn build_close_order(
         current_height,
     };
 
-    sigmafi::tx_builder::build_close_order(&req).into_service()
+    let tx = sigmafi::tx_builder::build_close_order(&req).into_service()?;
+    build_response(tx, "Lend", "SigmaFi order")
 }
 
 pub async fn build_repay(
@@ -128,7 +159,7 @@ pub async fn build_repay(
     borrower_ergo_tree: String,
     user_inputs: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<SigmaFiBuildResponse> {
     let client = state.require_node_client().await?;
     let bond_box = client
         .get_eip12_box_by_id(box_id)
@@ -143,7 +174,8 @@ pub async fn build_repay(
         current_height,
     };
 
-    sigmafi::tx_builder::build_repay(&req).into_service()
+    let tx = sigmafi::tx_builder::build_repay(&req).into_service()?;
+    build_response(tx, "Repay loan", "SigmaFi bond")
 }
 
 pub async fn build_liquidate(
@@ -152,7 +184,7 @@ pub async fn build_liquidate(
     lender_ergo_tree: String,
     user_inputs: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<SigmaFiBuildResponse> {
     let client = state.require_node_client().await?;
     let bond_box = client
         .get_eip12_box_by_id(box_id)
@@ -166,5 +198,6 @@ pub async fn build_liquidate(
         current_height,
     };
 
-    sigmafi::tx_builder::build_liquidate(&req).into_service()
+    let tx = sigmafi::tx_builder::build_liquidate(&req).into_service()?;
+    build_response(tx, "Liquidate bond", "SigmaFi bond")
 }
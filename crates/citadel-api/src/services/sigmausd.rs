@@ -1,10 +1,12 @@
 //! SigmaUSD use-case orchestration: bank state, oracle price, mint/redeem preview and build.
 
 use crate::dto::{
-    MintBuildResponse, MintPreviewResponse, OraclePriceResponse, SigmaUsdBuildResponse,
+    MintBuildResponse, MintPreviewResponse, OracleFeed, OraclePriceResponse, SigmaUsdBuildResponse,
     SigmaUsdPreviewResponse, TxSummaryDto,
 };
+use crate::services::contention;
 use crate::services::error::{IntoServiceError, ServiceResult};
+use crate::services::oracle;
 use crate::AppState;
 use citadel_core::constants::{MIN_BOX_VALUE_NANO, TX_FEE_NANO};
 use sigmausd::{
@@ -109,6 +111,7 @@ pub async fn build_mint_sigusd(
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
 ) -> ServiceResult<MintBuildResponse> {
+    let oracle_warnings = oracle::guard_build(state, OracleFeed::ErgUsd).await?;
     let client = state.require_node_client().await?;
     let capabilities = client.require_capabilities().await?;
 
@@ -153,6 +156,7 @@ pub async fn build_mint_sigusd(
 
     let unsigned_tx_json = serde_json::to_value(&result.unsigned_tx)
         .map_err(|e| format!("Failed to serialize transaction: {}", e))?;
+    let intent = result.summary.intent(&nft_ids);
 
     Ok(MintBuildResponse {
         unsigned_tx: unsigned_tx_json,
@@ -164,6 +168,8 @@ pub async fn build_mint_sigusd(
             protocol_fee_nano: result.summary.protocol_fee_nano.to_string(),
             tx_fee_nano: result.summary.tx_fee_nano.to_string(),
         },
+        intent,
+        oracle_warnings,
     })
 }
 
@@ -360,6 +366,22 @@ fn preview_redeem_sigrsv_internal(
         });
     }
 
+    if amount > sigmausd_state.max_sigrsv_redeemable {
+        return Ok(SigmaUsdPreviewResponse {
+            erg_amount_nano: "0".to_string(),
+            protocol_fee_nano: "0".to_string(),
+            tx_fee_nano: TX_FEE_NANO.to_string(),
+            total_erg_nano: "0".to_string(),
+            token_amount: amount.to_string(),
+            token_name: "SigRSV".to_string(),
+            can_execute: false,
+            error: Some(format!(
+                "Amount would take the reserve ratio below 400%: max {} SigRSV",
+                sigmausd_state.max_sigrsv_redeemable
+            )),
+        });
+    }
+
     let calc = erg_from_redeem_sigrsv(amount, sigmausd_state.sigrsv_price_nano);
     let citadel_fee = ergo_tx::resolved_dev_fee_config().budget();
     let tx_fee = TX_FEE_NANO;
@@ -387,8 +409,10 @@ pub async fn build_sigmausd_tx(
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
     recipient_address: Option<String>,
+    build_on_pending: bool,
 ) -> ServiceResult<SigmaUsdBuildResponse> {
-    let client = state.require_node_client().await?;
+    let oracle_warnings = oracle::guard_build(state, OracleFeed::ErgUsd).await?;
+    let client = contention::build_client(state, build_on_pending).await?;
     let capabilities = client.require_capabilities().await?;
     let config = state.config().await;
     let nft_ids = NftIds::for_network(config.network)
@@ -475,6 +499,7 @@ pub async fn build_sigmausd_tx(
 
     let unsigned_tx_json = serde_json::to_value(&result.unsigned_tx)
         .map_err(|e| format!("Failed to serialize tx: {}", e))?;
+    let intent = result.summary.intent(&nft_ids);
 
     Ok(SigmaUsdBuildResponse {
         unsigned_tx: unsigned_tx_json,
@@ -486,5 +511,7 @@ pub async fn build_sigmausd_tx(
             protocol_fee_nano: result.summary.protocol_fee_nano.to_string(),
             tx_fee_nano: result.summary.tx_fee_nano.to_string(),
         },
+        intent,
+        oracle_warnings,
     })
 }
@@ -0,0 +1,330 @@
+//! SigmaUSD action advisor: exact bank limits and fee-inclusive prices next
+//! to the deepest Spectrum ERG pools, plus the best bank/DEX split when one
+//! action is too large for either venue alone.
+
+use amm::{AmmPool, PoolType};
+use sigmausd::tx_builder::SigmaUsdAction;
+use sigmausd::{fetch_sigmausd_state, BankAdvice, NftIds, SigmaUsdState};
+
+use crate::dto::{DexPoolQuote, RouteAdvice, RouteQuote, SigmaUsdAdviceResponse, VenueComparison};
+use crate::services::error::{IntoServiceError, ServiceResult};
+use crate::AppState;
+
+/// Grid points per pass of the split search (coarse pass, then one refine
+/// pass around the best point).
+const SPLIT_STEPS: i64 = 50;
+
+fn deepest_pool<'a>(pools: &'a [AmmPool], token_id: &str) -> Option<&'a AmmPool> {
+    pools
+        .iter()
+        .filter(|p| p.pool_type == PoolType::N2T && p.token_y.token_id == token_id)
+        .max_by_key(|p| p.erg_reserves.unwrap_or(0))
+}
+
+fn pool_quote(pool: &AmmPool) -> Option<DexPoolQuote> {
+    let erg = pool.erg_reserves?;
+    let tokens = pool.token_y.amount;
+    if erg == 0 || tokens == 0 || pool.fee_num <= 0 || pool.fee_denom <= 0 {
+        return None;
+    }
+    let spot = erg as f64 / tokens as f64;
+    let pass_through = pool.fee_num as f64 / pool.fee_denom as f64;
+    Some(DexPoolQuote {
+        pool_id: pool.pool_id.clone(),
+        erg_reserves: erg,
+        token_reserves: tokens,
+        fee_pct: ((1.0 - pass_through) * 10_000.0).round() / 100.0,
+        buy_price_nano: spot / pass_through,
+        sell_price_nano: spot * pass_through,
+    })
+}
+
+/// ERG paid for (`buying`) or received from `amount` raw units on the pool.
+fn dex_quote(pool: &AmmPool, buying: bool, amount: i64) -> Option<i64> {
+    if amount == 0 {
+        return Some(0);
+    }
+    let erg = pool.erg_reserves?;
+    let tokens = pool.token_y.amount;
+    let amount = u64::try_from(amount).ok()?;
+    let quote = if buying {
+        amm::calculate_input(erg, tokens, amount, pool.fee_num, pool.fee_denom)?
+    } else {
+        match amm::calculate_token_to_erg_output(tokens, erg, amount, pool.fee_num, pool.fee_denom)
+        {
+            0 => return None,
+            out => out,
+        }
+    };
+    i64::try_from(quote).ok()
+}
+
+fn bank_quote(state: &SigmaUsdState, action: SigmaUsdAction, amount: i64) -> Option<i64> {
+    if amount == 0 {
+        return Some(0);
+    }
+    sigmausd::bank_quote(state, action, amount).ok()
+}
+
+fn is_better(buying: bool, erg: i64, than: i64) -> bool {
+    if buying {
+        erg < than
+    } else {
+        erg > than
+    }
+}
+
+/// Split of `total` between bank and pool with the lowest ERG cost
+/// (`buying`) or the highest proceeds. Both quotes are convex in the amount
+/// on their own venue, so a grid search plus one refine pass lands within a
+/// few units of the optimum; `bank_max` is always tried as the bank leg
+/// since the optimum often sits right at the ratio limit.
+fn best_split(
+    total: i64,
+    bank_max: i64,
+    buying: bool,
+    bank: impl Fn(i64) -> Option<i64>,
+    dex: impl Fn(i64) -> Option<i64>,
+) -> Option<(i64, i64)> {
+    let quote = |bank_part: i64| bank(bank_part)?.checked_add(dex(total - bank_part)?);
+    let consider = |best: &mut Option<(i64, i64)>, bank_part: i64| {
+        if let Some(erg) = quote(bank_part) {
+            let improves = match *best {
+                Some((_, b)) => is_better(buying, erg, b),
+                None => true,
+            };
+            if improves {
+                *best = Some((bank_part, erg));
+            }
+        }
+    };
+
+    let mut best = None;
+    let step = (total / SPLIT_STEPS).max(1);
+    for k in 0..=SPLIT_STEPS {
+        consider(
+            &mut best,
+            (total as i128 * k as i128 / SPLIT_STEPS as i128) as i64,
+        );
+    }
+    consider(&mut best, bank_max.clamp(0, total));
+
+    let (center, _) = best?;
+    let lo = (center - step).max(0);
+    let hi = (center + step).min(total);
+    let fine = ((hi - lo) / SPLIT_STEPS).max(1);
+    let mut bank_part = lo;
+    while bank_part <= hi {
+        consider(&mut best, bank_part);
+        bank_part += fine;
+    }
+    best
+}
+
+fn route_quote(bank_amount: i64, dex_amount: i64, erg_nano: i64) -> RouteQuote {
+    let total = bank_amount + dex_amount;
+    RouteQuote {
+        bank_amount,
+        dex_amount,
+        erg_nano,
+        effective_price_nano: if total > 0 {
+            erg_nano as f64 / total as f64
+        } else {
+            0.0
+        },
+    }
+}
+
+fn route_advice(
+    state: &SigmaUsdState,
+    bank_advice: &BankAdvice,
+    action: SigmaUsdAction,
+    amount: i64,
+    pool: Option<&AmmPool>,
+) -> RouteAdvice {
+    let buying = matches!(
+        action,
+        SigmaUsdAction::MintSigUsd | SigmaUsdAction::MintSigRsv
+    );
+    let bank_max = bank_advice
+        .actions
+        .iter()
+        .find(|a| a.action == action.as_str())
+        .map_or(0, |a| a.max_amount);
+    let bank = |n: i64| bank_quote(state, action, n);
+    let dex = |n: i64| pool.and_then(|p| dex_quote(p, buying, n));
+
+    let bank_only = bank(amount).map(|erg| route_quote(amount, 0, erg));
+    let dex_only = dex(amount).map(|erg| route_quote(0, amount, erg));
+    let best = best_split(amount, bank_max, buying, bank, dex)
+        .map(|(bank_part, erg)| route_quote(bank_part, amount - bank_part, erg));
+
+    let single = [&bank_only, &dex_only]
+        .into_iter()
+        .flatten()
+        .map(|q| q.erg_nano)
+        .reduce(|a, b| if is_better(buying, a, b) { a } else { b });
+    let (recommendation, improvement_nano) = match &best {
+        None => ("unavailable", 0),
+        Some(q) => {
+            let improvement = single.map_or(0, |s| (q.erg_nano - s).abs());
+            if q.dex_amount == 0 {
+                ("bank", improvement)
+            } else if q.bank_amount == 0 {
+                ("dex", improvement)
+            } else {
+                ("split", improvement)
+            }
+        }
+    };
+
+    RouteAdvice {
+        action: action.as_str().to_string(),
+        amount,
+        bank_only,
+        dex_only,
+        best,
+        recommendation: recommendation.to_string(),
+        improvement_nano,
+    }
+}
+
+fn compare_venues(
+    bank_advice: &BankAdvice,
+    action: &str,
+    pool: Option<&DexPoolQuote>,
+) -> VenueComparison {
+    let buying = action.starts_with("mint_");
+    let bank_price_nano = bank_advice
+        .actions
+        .iter()
+        .find(|a| a.action == action && a.allowed)
+        .map(|a| a.effective_price_nano);
+    let dex_price_nano = pool.map(|p| {
+        if buying {
+            p.buy_price_nano
+        } else {
+            p.sell_price_nano
+        }
+    });
+    let better_venue = match (bank_price_nano, dex_price_nano) {
+        (Some(b), Some(d)) if (buying && d < b) || (!buying && d > b) => "dex",
+        (Some(_), _) => "bank",
+        (None, Some(_)) => "dex",
+        (None, None) => "none",
+    };
+    VenueComparison {
+        action: action.to_string(),
+        bank_price_nano,
+        dex_price_nano,
+        better_venue: better_venue.to_string(),
+    }
+}
+
+/// Exact limits, break-even moves and bank vs DEX prices for all four
+/// SigmaUSD actions. With `action` and `amount` also the cheapest bank/DEX
+/// split for that trade.
+pub async fn get_sigmausd_advice(
+    state: &AppState,
+    action: Option<String>,
+    amount: Option<i64>,
+) -> ServiceResult<SigmaUsdAdviceResponse> {
+    let trade = match (action, amount) {
+        (Some(action), Some(amount)) => {
+            let action = action
+                .parse::<SigmaUsdAction>()
+                .map_err(|e| e.to_string())?;
+            if amount <= 0 {
+                return Err("Amount must be positive".to_string());
+            }
+            Some((action, amount))
+        }
+        _ => None,
+    };
+
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+    let config = state.config().await;
+    let nft_ids = NftIds::for_network(config.network)
+        .ok_or_else(|| format!("SigmaUSD not available on {:?}", config.network))?;
+    let sigmausd_state = fetch_sigmausd_state(&client, &capabilities, &nft_ids)
+        .await
+        .into_service()?;
+
+    // Bank advice stands on its own; the DEX side is best effort
+    let pools = amm::discover_pools(&client).await.unwrap_or_else(|e| {
+        tracing::warn!("SigmaUSD advisor: pool discovery failed: {}", e);
+        Vec::new()
+    });
+    let sigusd_pool = deepest_pool(&pools, &nft_ids.sigusd_token);
+    let sigrsv_pool = deepest_pool(&pools, &nft_ids.sigrsv_token);
+    let sigusd_quote = sigusd_pool.and_then(pool_quote);
+    let sigrsv_quote = sigrsv_pool.and_then(pool_quote);
+
+    let bank = sigmausd::advise(&sigmausd_state);
+    let comparisons = [
+        (SigmaUsdAction::MintSigUsd, sigusd_quote.as_ref()),
+        (SigmaUsdAction::RedeemSigUsd, sigusd_quote.as_ref()),
+        (SigmaUsdAction::MintSigRsv, sigrsv_quote.as_ref()),
+        (SigmaUsdAction::RedeemSigRsv, sigrsv_quote.as_ref()),
+    ]
+    .into_iter()
+    .map(|(action, pool)| compare_venues(&bank, action.as_str(), pool))
+    .collect();
+
+    let route = trade.map(|(action, amount)| {
+        let pool = match action {
+            SigmaUsdAction::MintSigUsd | SigmaUsdAction::RedeemSigUsd => sigusd_pool,
+            SigmaUsdAction::MintSigRsv | SigmaUsdAction::RedeemSigRsv => sigrsv_pool,
+        };
+        route_advice(&sigmausd_state, &bank, action, amount, pool)
+    });
+
+    Ok(SigmaUsdAdviceResponse {
+        bank,
+        sigusd_pool: sigusd_quote,
+        sigrsv_pool: sigrsv_quote,
+        comparisons,
+        route,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat bank price, capped at `max`.
+    fn bank(price: i64, max: i64) -> impl Fn(i64) -> Option<i64> {
+        move |n| (n <= max).then_some(n * price)
+    }
+
+    /// Constant-product pool without fee: 1_000_000 ERG units vs 1_000 tokens.
+    fn pool_buy(n: i64) -> Option<i64> {
+        amm::calculate_input(1_000_000, 1_000, n as u64, 1, 1).map(|x| x as i64)
+    }
+
+    #[test]
+    fn test_split_fills_capped_bank_first() {
+        // Bank at 1020/unit beats the pool (marginal 1000 → rising) past ~10
+        // units, but only takes 300
+        let (bank_part, erg) = best_split(400, 300, true, bank(1020, 300), pool_buy).unwrap();
+        assert_eq!(bank_part, 300);
+        assert_eq!(erg, 300 * 1020 + pool_buy(100).unwrap());
+    }
+
+    #[test]
+    fn test_split_uses_pool_below_bank_price() {
+        // Small buys are cheaper on the pool until its price passes the bank's
+        let (bank_part, erg) = best_split(100, 1_000, true, bank(1020, 1_000), pool_buy).unwrap();
+        assert!((1..100).contains(&bank_part));
+        let dex_part = 100 - bank_part;
+        assert!((8..=12).contains(&dex_part));
+        assert!(erg < 100 * 1020);
+    }
+
+    #[test]
+    fn test_split_unavailable_when_neither_venue_fits() {
+        let none = |_: i64| None;
+        assert!(best_split(100, 0, true, bank(1020, 0), none).is_none());
+    }
+}
@@ -0,0 +1,72 @@
+//! SigmaUSD reserve ratio, supply and SigRSV price history, cached in the
+//! data dir.
+//!
+//! Each query only walks the bank boxes created since the last cached one, so
+//! charts and backtests can re-query cheaply.
+
+use sigmausd::{fetch_sigmausd_state, BankHistory, BankSnapshot, NftIds};
+
+use crate::services::chain_history::refresh_history;
+use crate::services::error::{IntoServiceError, ServiceResult};
+use crate::AppState;
+
+const SIGMAUSD_HISTORY_FILE: &str = "sigmausd_history";
+/// Snapshots kept per bank (newest first).
+const MAX_CACHED_SNAPSHOTS: usize = 10_000;
+/// Bank boxes walked per query when the caller does not say.
+const DEFAULT_TRACE_STEPS: usize = 300;
+
+/// Bank state at each transition since `since_height`, sampled to one point
+/// per `sample_blocks` blocks (0 = every transition). Without `since_height`
+/// only the transitions since the cached range are traced.
+pub async fn get_sigmausd_history(
+    state: &AppState,
+    max_steps: Option<usize>,
+    sample_blocks: Option<u64>,
+    since_height: Option<u64>,
+) -> ServiceResult<BankHistory> {
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+    let config = state.config().await;
+    let nft_ids = NftIds::for_network(config.network)
+        .ok_or_else(|| format!("SigmaUSD not available on {:?}", config.network))?;
+
+    let bank = fetch_sigmausd_state(&client, &capabilities, &nft_ids)
+        .await
+        .into_service()?;
+    let max_steps = max_steps.unwrap_or(DEFAULT_TRACE_STEPS).max(1);
+
+    let (client, nft_ids) = (&client, &nft_ids);
+    let snapshots = refresh_history(
+        &state.store().await,
+        SIGMAUSD_HISTORY_FILE,
+        &nft_ids.bank_nft,
+        &bank.bank_box_id,
+        max_steps,
+        since_height,
+        MAX_CACHED_SNAPSHOTS,
+        |start, known, steps| async move {
+            sigmausd::trace_bank_history(
+                client,
+                nft_ids,
+                &start,
+                &known,
+                steps,
+                since_height.unwrap_or(0),
+            )
+            .await
+            .into_service()
+        },
+    )
+    .await?;
+
+    let oldest_first: Vec<BankSnapshot> = snapshots
+        .into_iter()
+        .rev()
+        .filter(|s| s.height >= since_height.unwrap_or(0))
+        .collect();
+    Ok(sigmausd::bank_history(
+        &oldest_first,
+        sample_blocks.unwrap_or(0),
+    ))
+}
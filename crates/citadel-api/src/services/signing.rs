@@ -1,14 +1,15 @@
 //! ErgoPay signing flow: transaction reduction and request lifecycle.
 
-use citadel_core::BoxId;
+use citadel_core::{BoxId, TxIntent};
 use ergo_lib::ergotree_ir::chain::ergo_box::ErgoBox;
 use ergo_node_client::NodeClient;
 use ergo_tx::Eip12UnsignedTx;
-use ergopay_core::{reduce_transaction, reduce_transaction_fallback};
+use ergopay_core::reduce_transaction_checked;
 use ergopay_server::RequestStatus;
 
 use super::error::{IntoServiceError, ServiceResult};
-use crate::dto::{MintSignRequest, MintSignResponse, MintTxStatusResponse};
+use crate::contracts::recognize_tree;
+use crate::dto::{ContractInfo, MintSignRequest, MintSignResponse, MintTxStatusResponse};
 use crate::AppState;
 
 async fn fetch_boxes_by_ids(
@@ -26,6 +27,28 @@ async fn fetch_boxes_by_ids(
     Ok(boxes)
 }
 
+/// Intent for callers that only send a free-text message: flows come from
+/// the transaction, protocol and counterparties from recognised input contracts.
+fn fallback_intent(tx: &Eip12UnsignedTx, action: String) -> TxIntent {
+    let mut contracts: Vec<ContractInfo> = Vec::new();
+    for input in &tx.inputs {
+        if let Some(info) = recognize_tree(&input.ergo_tree).filter(|c| c.is_script) {
+            if !contracts.iter().any(|c| c.name == info.name) {
+                contracts.push(info);
+            }
+        }
+    }
+    let protocol = contracts
+        .first()
+        .map(|c| c.protocol.clone())
+        .unwrap_or_else(|| "Citadel".to_string());
+    contracts
+        .into_iter()
+        .fold(ergo_tx::derive_intent(tx, protocol, action), |intent, c| {
+            intent.counterparty("contract", c.name)
+        })
+}
+
 pub async fn start_mint_sign(
     state: &AppState,
     request: MintSignRequest,
@@ -55,28 +78,45 @@ pub async fn start_mint_sign(
     )
     .await?;
 
-    let reduced_bytes =
-        match reduce_transaction(&eip12_tx, input_boxes, data_input_boxes, &client).await {
-            Ok(bytes) => bytes,
-            Err(e) => {
-                tracing::warn!("Standard reduction failed ({}), using fallback", e);
-                reduce_transaction_fallback(&eip12_tx)
-                    .map_err(|e| format!("Fallback reduction also failed: {}", e))?
-            }
-        };
+    let outcome = reduce_transaction_checked(&eip12_tx, input_boxes, data_input_boxes, &client)
+        .await
+        .map_err(|e| format!("Fallback reduction also failed: {}", e))?;
+
+    let intent = request
+        .intent
+        .unwrap_or_else(|| fallback_intent(&eip12_tx, request.message));
+
+    // Mobile wallets only see the ErgoPay message, so fallback warnings go there too
+    let message = if outcome.warnings.is_empty() {
+        intent.message()
+    } else {
+        format!("{}\n\n{}", intent.message(), outcome.warnings.join("\n"))
+    };
 
     let server = state.ergopay_server().await.into_service()?;
 
     let (request_id, ergopay_url) = server
-        .create_tx_request(reduced_bytes, request.unsigned_tx.clone(), request.message)
+        .create_tx_request(outcome.reduced_bytes, request.unsigned_tx.clone(), message)
         .await;
 
     let nautilus_url = server.get_nautilus_url(&request_id);
 
+    // The standard bytes are signed either way, but a diverging fallback is a bug the
+    // user should see rather than only a log line
+    let mut warnings = outcome.warnings;
+    warnings.extend(
+        outcome
+            .mismatches
+            .iter()
+            .map(|m| format!("Fallback reducer disagrees with sigma-rust: {}", m)),
+    );
+
     Ok(MintSignResponse {
         request_id,
         ergopay_url,
         nautilus_url,
+        reduction_path: outcome.path.to_string(),
+        warnings,
     })
 }
 
@@ -0,0 +1,119 @@
+//! What-if simulation against live SigmaUSD and Dexy state.
+//!
+//! Fetches the current protocol state once and runs the pure simulators in
+//! the protocol crates for each requested price shock.
+
+use dexy::{DexyFlow, DexyHoldings, DexyIds, DexyScenario, DexySimulationResult, DexyVariant};
+use sigmausd::tx_builder::SigmaUsdAction;
+use sigmausd::{fetch_sigmausd_state, Holdings, NftIds, Scenario, SimulationResult};
+
+use crate::dto::{DexySimRequest, SigmaUsdSimRequest, SimFlowDto};
+use crate::services::error::{IntoServiceError, ServiceResult};
+use crate::AppState;
+
+/// Shocks to run; no shock at all when the caller gave none.
+fn shocks(price_shocks_pct: &[f64]) -> Vec<f64> {
+    if price_shocks_pct.is_empty() {
+        vec![0.0]
+    } else {
+        price_shocks_pct.to_vec()
+    }
+}
+
+fn sigmausd_flows(flows: &[SimFlowDto]) -> ServiceResult<Vec<(SigmaUsdAction, i64)>> {
+    flows
+        .iter()
+        .map(|f| {
+            let action = f
+                .action
+                .parse::<SigmaUsdAction>()
+                .map_err(|e| e.to_string())?;
+            Ok((action, f.amount))
+        })
+        .collect()
+}
+
+fn dexy_flows(flows: &[SimFlowDto]) -> ServiceResult<Vec<DexyFlow>> {
+    flows
+        .iter()
+        .map(|f| match f.action.as_str() {
+            "mint" => Ok(DexyFlow::Mint(f.amount)),
+            "sell_to_lp" => Ok(DexyFlow::SellToLp(f.amount)),
+            "buy_from_lp" => Ok(DexyFlow::BuyFromLp(f.amount)),
+            other => Err(format!(
+                "Invalid Dexy flow: {}. Use 'mint', 'sell_to_lp' or 'buy_from_lp'",
+                other
+            )),
+        })
+        .collect()
+}
+
+/// SigmaUSD reserve ratio, SigRSV price, blocked actions and holdings value
+/// under each price shock followed by the requested flows.
+pub async fn simulate_sigmausd(
+    state: &AppState,
+    request: SigmaUsdSimRequest,
+) -> ServiceResult<Vec<SimulationResult>> {
+    let flows = sigmausd_flows(&request.flows)?;
+    let holdings = Holdings {
+        sigusd: request.sigusd_held.max(0),
+        sigrsv: request.sigrsv_held.max(0),
+    };
+
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+    let config = state.config().await;
+    let nft_ids = NftIds::for_network(config.network)
+        .ok_or_else(|| format!("SigmaUSD not available on {:?}", config.network))?;
+    let sigmausd_state = fetch_sigmausd_state(&client, &capabilities, &nft_ids)
+        .await
+        .into_service()?;
+
+    shocks(&request.price_shocks_pct)
+        .into_iter()
+        .map(|pct| {
+            let scenario = Scenario {
+                erg_price_change_pct: pct,
+                flows: flows.clone(),
+            };
+            sigmausd::simulate(&sigmausd_state, &scenario, &holdings).into_service()
+        })
+        .collect()
+}
+
+/// Dexy mint availability, LP rate and holdings value under each price shock
+/// followed by the requested flows.
+pub async fn simulate_dexy(
+    state: &AppState,
+    request: DexySimRequest,
+) -> ServiceResult<Vec<DexySimulationResult>> {
+    let variant = request
+        .variant
+        .parse::<DexyVariant>()
+        .map_err(|_| format!("Invalid variant: {}. Use 'gold' or 'usd'", request.variant))?;
+    let flows = dexy_flows(&request.flows)?;
+    let holdings = DexyHoldings {
+        dexy: request.dexy_held.max(0),
+        lp_tokens: request.lp_tokens_held.max(0),
+    };
+
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+    let config = state.config().await;
+    let ids = DexyIds::for_variant(variant, config.network)
+        .ok_or_else(|| "Dexy not available on this network".to_string())?;
+    let dexy_state = dexy::fetch_dexy_state(&client, &capabilities, &ids)
+        .await
+        .into_service()?;
+
+    shocks(&request.price_shocks_pct)
+        .into_iter()
+        .map(|pct| {
+            let scenario = DexyScenario {
+                erg_price_change_pct: pct,
+                flows: flows.clone(),
+            };
+            dexy::simulate_dexy(&dexy_state, &scenario, &holdings).into_service()
+        })
+        .collect()
+}
@@ -1,6 +1,7 @@
 //! Stake recovery use-case orchestration: multi-protocol stake scan, recovery tx
 //! building, and the Paideia permissionless proxy check/submit flow.
 
+use citadel_core::TxIntent;
 use ergo_lib::ergotree_ir::chain::ergo_box::ErgoBox;
 use serde::Serialize;
 use stake_recovery::{
@@ -13,6 +14,13 @@ use stake_recovery::{
 use super::error::{IntoServiceError, ServiceResult};
 use crate::AppState;
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryBuildResponse {
+    pub unsigned_tx: serde_json::Value,
+    pub intent: TxIntent,
+}
+
 /// Scan every registered v1 staking protocol (Ergopad, EGIO, …) for StakeBoxes whose
 /// R5 matches any of the provided candidate token IDs (typically the wallet's
 /// unique-qty-1 tokens). Auto-detects which protocol each recovered stake belongs to.
@@ -47,7 +55,7 @@ pub async fn build_recovery_tx(
     stake_key_id: &str,
     user_utxos: Vec<ergo_tx::Eip12InputBox>,
     current_height: i32,
-) -> ServiceResult<ergo_tx::Eip12UnsignedTx> {
+) -> ServiceResult<RecoveryBuildResponse> {
     let client = state.require_node_client().await?;
 
     // Detect the protocol by locating the live StakeBox for this key.
@@ -59,7 +67,7 @@ pub async fn build_recovery_tx(
     // Dispatch on the pool's recovery mechanism. Paideia is redeemed via a proxy box
     // (step 1 of 2) rather than the Ergopad/EGIO direct unstake; the two tx shapes are
     // not interchangeable.
-    match cfg.mechanism {
+    let (tx, action) = match cfg.mechanism {
         RecoveryMechanism::Direct => {
             let (state_ergo_box, state_snapshot) =
                 fetch_stake_state(&client, cfg).await.into_service()?;
@@ -67,7 +75,7 @@ pub async fn build_recovery_tx(
             let stake_box = ergo_box_to_eip12(&client, &stake_ergo_box).await?;
             let user_ergo_tree = recipient_ergo_tree_for_key(&user_utxos, &stake.stake_key_id)?;
 
-            build_recovery_tx_eip12(
+            let tx = build_recovery_tx_eip12(
                 &state_box,
                 &state_snapshot,
                 &stake_box,
@@ -76,7 +84,8 @@ pub async fn build_recovery_tx(
                 &user_ergo_tree,
                 current_height,
             )
-            .into_service()
+            .into_service()?;
+            (tx, "Recover stake")
         }
         RecoveryMechanism::PaideiaProxy => {
             // Payout recipient = the wallet address that holds the stake key (a P2PK).
@@ -85,16 +94,36 @@ pub async fn build_recovery_tx(
             let (_state_ergo_box, state_snapshot) =
                 fetch_stake_state(&client, cfg).await.into_service()?;
 
-            build_paideia_proxy_tx(
+            let tx = build_paideia_proxy_tx(
                 &stake,
                 &state_snapshot,
                 &user_utxos,
                 &recipient_ergo_tree,
                 current_height,
             )
-            .into_service()
+            .into_service()?;
+            (tx, "Unstake via proxy (step 1 of 2)")
         }
+    };
+
+    let mut intent = ergo_tx::derive_intent(&tx, cfg.name, action)
+        .counterparty("contract", format!("{} stake", cfg.name));
+    if cfg.mechanism == RecoveryMechanism::PaideiaProxy {
+        intent = intent.note(format!(
+            "{} {} is paid out by the proxy in step 2",
+            stake.reward_amount_display, cfg.reward_token_name
+        ));
     }
+    intent.describe_token(
+        cfg.reward_token,
+        Some(cfg.reward_token_name),
+        Some(cfg.reward_decimals as u8),
+    );
+    Ok(RecoveryBuildResponse {
+        unsigned_tx: serde_json::to_value(&tx)
+            .map_err(|e| format!("Failed to serialize transaction: {}", e))?,
+        intent,
+    })
 }
 
 /// Select the ErgoTree of the parsed UTXO that actually carries `stake_key_id` — the
@@ -0,0 +1,246 @@
+//! Token registry management and verification of the tokens the router and
+//! wallet return.
+//!
+//! The freshness heuristic needs each token's mint height: two node lookups
+//! per token, so heights are cached in the data dir and only a bounded number
+//! of new tokens is looked up per call.
+
+use std::collections::HashMap;
+
+use citadel_core::{TokenList, TokenMarket, TokenStatus, VerifiedToken, LOW_LIQUIDITY_NANO};
+use ergo_node_client::NodeClient;
+
+use super::error::{IntoServiceError, ServiceResult};
+use crate::dto::TokenRegistryResponse;
+use crate::AppState;
+
+const TOKEN_MINT_HEIGHTS_FILE: &str = "token_mint_heights";
+/// Mint heights fetched from the node per call; the rest wait for the next.
+const MAX_MINT_LOOKUPS: usize = 20;
+
+pub async fn get_token_registry(state: &AppState) -> ServiceResult<TokenRegistryResponse> {
+    let registry = state.token_registry().await;
+    let overrides = state.token_overrides().await;
+    Ok(TokenRegistryResponse {
+        list_version: registry.list_version(),
+        tokens: registry.tokens().into_iter().cloned().collect(),
+        local_verified: overrides
+            .verified
+            .iter()
+            .map(|t| t.token_id.clone())
+            .collect(),
+        blocked: overrides.blocked,
+    })
+}
+
+/// Replace the bundled list with a newer one (e.g. a downloaded update).
+pub async fn import_token_list(
+    state: &AppState,
+    list: TokenList,
+) -> ServiceResult<TokenRegistryResponse> {
+    let current = state.token_registry().await.list_version();
+    if list.version <= current {
+        return Err(format!(
+            "Token list version {} is not newer than the current version {}",
+            list.version, current
+        ));
+    }
+    for token in &list.tokens {
+        validate_token_id(&token.token_id)?;
+    }
+    state
+        .update_token_overrides(|o| o.list = Some(list))
+        .await
+        .into_service()?;
+    get_token_registry(state).await
+}
+
+pub async fn add_verified_token(
+    state: &AppState,
+    token: VerifiedToken,
+) -> ServiceResult<TokenRegistryResponse> {
+    validate_token_id(&token.token_id)?;
+    if token.name.trim().is_empty() {
+        return Err("Token name must not be empty".to_string());
+    }
+    state
+        .update_token_overrides(|o| {
+            o.verified.retain(|t| t.token_id != token.token_id);
+            o.blocked.retain(|id| *id != token.token_id);
+            o.verified.push(token);
+        })
+        .await
+        .into_service()?;
+    get_token_registry(state).await
+}
+
+pub async fn remove_verified_token(
+    state: &AppState,
+    token_id: &str,
+) -> ServiceResult<TokenRegistryResponse> {
+    state
+        .update_token_overrides(|o| o.verified.retain(|t| t.token_id != token_id))
+        .await
+        .into_service()?;
+    get_token_registry(state).await
+}
+
+pub async fn set_token_blocked(
+    state: &AppState,
+    token_id: &str,
+    blocked: bool,
+) -> ServiceResult<TokenRegistryResponse> {
+    validate_token_id(token_id)?;
+    state
+        .update_token_overrides(|o| {
+            o.blocked.retain(|id| id != token_id);
+            if blocked {
+                o.blocked.push(token_id.to_string());
+            }
+        })
+        .await
+        .into_service()?;
+    get_token_registry(state).await
+}
+
+fn validate_token_id(token_id: &str) -> ServiceResult<()> {
+    if token_id.len() == 64 && token_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(format!("Invalid token ID: {}", token_id))
+    }
+}
+
+/// ERG locked in N2T pools per token. T2T pools hold no ERG and add nothing.
+fn pool_liquidity(pools: &[amm::AmmPool]) -> HashMap<String, u64> {
+    let mut liquidity: HashMap<String, u64> = HashMap::new();
+    for pool in pools {
+        if let (amm::PoolType::N2T, Some(erg)) = (&pool.pool_type, pool.erg_reserves) {
+            let entry = liquidity.entry(pool.token_y.token_id.clone()).or_insert(0);
+            *entry = entry.saturating_add(erg);
+        }
+    }
+    liquidity
+}
+
+async fn fetch_mint_height(client: &NodeClient, token_id: &str) -> Option<u64> {
+    let box_id = client.get_token_info(token_id).await.ok()?.box_id?;
+    let mint_box = client.get_blockchain_box_by_id(&box_id).await.ok()?;
+    mint_box["inclusionHeight"]
+        .as_u64()
+        .or_else(|| mint_box["creationHeight"].as_u64())
+}
+
+/// Verification status of `tokens` (ID and on-chain name).
+///
+/// With `pools` the freshness heuristic runs for unverified tokens below the
+/// liquidity threshold; without them only the list and name checks apply.
+pub(crate) async fn screen_tokens(
+    state: &AppState,
+    client: &NodeClient,
+    pools: Option<&[amm::AmmPool]>,
+    tokens: &[(String, Option<String>)],
+) -> HashMap<String, TokenStatus> {
+    let registry = state.token_registry().await;
+    let mut statuses: HashMap<String, TokenStatus> = HashMap::new();
+    let mut thin: Vec<(String, Option<String>, u64)> = Vec::new();
+    let liquidity = pools.map(pool_liquidity);
+
+    for (token_id, name) in tokens {
+        if statuses.contains_key(token_id) {
+            continue;
+        }
+        let status = registry.assess(token_id, name.as_deref(), None);
+        if let (false, None, Some(pool_erg)) = (status.verified, &status.warning, &liquidity) {
+            let erg = pool_erg.get(token_id).copied().unwrap_or(0);
+            if erg < LOW_LIQUIDITY_NANO {
+                thin.push((token_id.clone(), name.clone(), erg));
+            }
+        }
+        statuses.insert(token_id.clone(), status);
+    }
+    if thin.is_empty() {
+        return statuses;
+    }
+
+    let Ok(height) = client.current_height().await else {
+        return statuses;
+    };
+    let store = state.store().await;
+    let mut mint_heights: HashMap<String, u64> = store.load(TOKEN_MINT_HEIGHTS_FILE);
+    let mut lookups = 0;
+    let mut fetched = false;
+
+    for (token_id, name, erg) in thin {
+        let mint_height = match mint_heights.get(&token_id) {
+            Some(h) => Some(*h),
+            None if lookups < MAX_MINT_LOOKUPS => {
+                lookups += 1;
+                let h = fetch_mint_height(client, &token_id).await;
+                if let Some(h) = h {
+                    mint_heights.insert(token_id.clone(), h);
+                    fetched = true;
+                }
+                h
+            }
+            None => None,
+        };
+        let market = TokenMarket {
+            liquidity_nano: erg,
+            age_blocks: mint_height.map(|h| height.saturating_sub(h)),
+        };
+        let status = registry.assess(&token_id, name.as_deref(), Some(&market));
+        statuses.insert(token_id, status);
+    }
+
+    if fetched {
+        if let Err(e) = store.save(TOKEN_MINT_HEIGHTS_FILE, &mint_heights) {
+            tracing::warn!("Failed to cache token mint heights: {}", e);
+        }
+    }
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(pool_type: amm::PoolType, token_id: &str, erg: Option<u64>) -> amm::AmmPool {
+        amm::AmmPool {
+            pool_id: format!("pool-{}", token_id),
+            pool_type,
+            box_id: String::new(),
+            erg_reserves: erg,
+            token_x: None,
+            token_y: amm::TokenAmount {
+                token_id: token_id.to_string(),
+                amount: 1,
+                decimals: None,
+                name: None,
+            },
+            lp_token_id: String::new(),
+            lp_circulating: 0,
+            fee_num: 997,
+            fee_denom: 1000,
+        }
+    }
+
+    #[test]
+    fn test_pool_liquidity_sums_n2t_pools() {
+        let pools = vec![
+            pool(amm::PoolType::N2T, "a", Some(10)),
+            pool(amm::PoolType::N2T, "a", Some(5)),
+            pool(amm::PoolType::T2T, "b", Some(600_000)),
+        ];
+        let liquidity = pool_liquidity(&pools);
+        assert_eq!(liquidity.get("a"), Some(&15));
+        assert_eq!(liquidity.get("b"), None);
+    }
+
+    #[test]
+    fn test_validate_token_id() {
+        assert!(validate_token_id(&"ab".repeat(32)).is_ok());
+        assert!(validate_token_id("ERG").is_err());
+        assert!(validate_token_id(&"zz".repeat(32)).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! UTXO consolidation, split, and restructure transaction building.
 
 use citadel_core::constants::{MIN_BOX_VALUE_NANO, TX_FEE_NANO};
+use citadel_core::TxIntent;
 use serde::{Deserialize, Serialize};
 
 use super::error::{IntoServiceError, ServiceResult};
@@ -15,6 +16,7 @@ pub struct ConsolidateBuildResponse {
     pub token_count: usize,
     pub miner_fee: i64,
     pub citadel_fee_nano: i64,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Serialize)]
@@ -27,6 +29,7 @@ pub struct SplitBuildResponse {
     pub change_erg: i64,
     pub miner_fee: i64,
     pub citadel_fee_nano: i64,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Deserialize)]
@@ -55,6 +58,7 @@ pub struct RestructureBuildResponse {
     pub has_change: bool,
     pub miner_fee: i64,
     pub citadel_fee_nano: i64,
+    pub intent: TxIntent,
 }
 
 pub fn build_consolidate_tx(
@@ -70,6 +74,7 @@ pub fn build_consolidate_tx(
 
     Ok(ConsolidateBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(),
         input_count: result.summary.input_count,
         total_erg_in: result.summary.total_erg_in,
         change_erg: result.summary.change_erg,
@@ -149,6 +154,7 @@ pub fn build_split_tx(
 
     Ok(SplitBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(),
         split_count: result.summary.split_count,
         amount_per_box: result.summary.amount_per_box,
         total_split: result.summary.total_split,
@@ -195,6 +201,7 @@ pub fn build_restructure_tx(
 
     Ok(RestructureBuildResponse {
         unsigned_tx: unsigned_tx_json,
+        intent: result.summary.intent(),
         input_count: result.summary.input_count,
         output_count: result.summary.output_count,
         total_erg_in: result.summary.total_erg_in,
@@ -0,0 +1,705 @@
+//! Wallet valuation in ERG and USD.
+//!
+//! Each token is priced by the most authoritative source available: protocol
+//! oracles and banks first (SigUSD, SigRSV, Dexy, HodlCoin), then the best
+//! Spectrum route to ERG for the *held amount*, so positions in thin pools are
+//! marked down by their price impact. LP tokens are valued through their
+//! underlying reserves (Spectrum and Dexy pool share, Duckpools lend-token
+//! share).
+
+use std::collections::HashMap;
+
+use citadel_core::Network;
+use ergo_node_client::{NodeCapabilities, NodeClient};
+
+use super::error::{IntoServiceError, ServiceResult};
+use super::oracle;
+use crate::dto::{
+    AssetValuation, OracleFeed, OracleGuardConfig, OracleVerdict, PriceConfidence, PriceSource,
+    ValuationBreakdown, WalletValuationResponse,
+};
+use crate::AppState;
+
+/// Pools below this ERG depth are still routed through; the route's price
+/// impact carries the thinness into the discount.
+const VALUATION_MIN_LIQUIDITY_NANO: u64 = 1_000_000_000;
+const VALUATION_MAX_HOPS: usize = 3;
+/// Route discount (price impact + pool fees) up to which a route price is `High`.
+const HIGH_CONFIDENCE_DISCOUNT_PCT: f64 = 2.0;
+/// Route discount up to which a route price is `Medium`; above is `Low`.
+const MEDIUM_CONFIDENCE_DISCOUNT_PCT: f64 = 10.0;
+/// Dexy oracle vs LP deviation above which the oracle price is only `Medium`.
+const DEXY_LP_DEVIATION_PCT: f64 = 5.0;
+
+/// Linear protocol price (oracle peg or bank equity).
+#[derive(Debug, Clone)]
+pub(crate) struct FixedPrice {
+    pub nano_per_unit: f64,
+    pub source: PriceSource,
+    pub confidence: PriceConfidence,
+    pub note: Option<String>,
+}
+
+/// Duckpools lend token and the pool state needed to redeem it.
+#[derive(Debug, Clone)]
+pub(crate) struct LendingLpPrice {
+    pub lend_token_id: String,
+    /// `None` for the ERG pool.
+    pub currency_id: Option<String>,
+    pub total_supplied: u64,
+    pub lp_circulating: u64,
+}
+
+/// Dexy LP token and the LP pool reserves it redeems against.
+#[derive(Debug, Clone)]
+pub(crate) struct DexyLpPrice {
+    pub lp_token_id: String,
+    pub dexy_token_id: String,
+    pub erg_reserves: u64,
+    pub dexy_reserves: u64,
+    pub lp_circulating: u64,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Valuation {
+    pub source: PriceSource,
+    pub confidence: PriceConfidence,
+    pub value_nano: u64,
+    pub spot_value_nano: u64,
+    pub note: Option<String>,
+}
+
+impl Valuation {
+    fn unpriced(note: &str) -> Self {
+        Self {
+            source: PriceSource::Unpriced,
+            confidence: PriceConfidence::None,
+            value_nano: 0,
+            spot_value_nano: 0,
+            note: Some(note.to_string()),
+        }
+    }
+
+    fn discount_pct(&self) -> f64 {
+        if self.spot_value_nano == 0 || self.value_nano >= self.spot_value_nano {
+            return 0.0;
+        }
+        (1.0 - self.value_nano as f64 / self.spot_value_nano as f64) * 100.0
+    }
+}
+
+/// All price sources needed to value arbitrary holdings, loaded once per request.
+pub(crate) struct PriceBook {
+    pub erg_usd: Option<f64>,
+    fixed: HashMap<String, FixedPrice>,
+    hodl_banks: Vec<hodlcoin::HodlBankState>,
+    pools: Vec<amm::AmmPool>,
+    graph: amm::PoolGraph,
+    lending: Vec<LendingLpPrice>,
+    dexy_lp: Vec<DexyLpPrice>,
+}
+
+impl PriceBook {
+    pub fn new(erg_usd: Option<f64>, pools: Vec<amm::AmmPool>) -> Self {
+        let graph = amm::build_pool_graph_with_limit(
+            &pools,
+            VALUATION_MIN_LIQUIDITY_NANO,
+            amm::DEFAULT_MAX_POOLS_PER_PAIR,
+        );
+        Self {
+            erg_usd,
+            fixed: HashMap::new(),
+            hodl_banks: Vec::new(),
+            pools,
+            graph,
+            lending: Vec::new(),
+            dexy_lp: Vec::new(),
+        }
+    }
+
+    pub fn set_fixed(&mut self, token_id: &str, price: FixedPrice) {
+        self.fixed.insert(token_id.to_string(), price);
+    }
+
+    /// Only ERG-backed banks are kept: their burn value is in ERG.
+    pub fn set_hodl_banks(&mut self, banks: Vec<hodlcoin::HodlBankState>) {
+        self.hodl_banks = banks.into_iter().filter(|b| !b.is_token_backed()).collect();
+    }
+
+    pub fn set_lending(&mut self, lending: Vec<LendingLpPrice>) {
+        self.lending = lending;
+    }
+
+    pub fn add_dexy_lp(&mut self, lp: DexyLpPrice) {
+        self.dexy_lp.push(lp);
+    }
+
+    pub fn usd(&self, nano_erg: u64) -> Option<f64> {
+        self.erg_usd.map(|p| nano_erg as f64 / 1e9 * p)
+    }
+
+    /// Value `amount` raw units of `token_id` (`"ERG"` for nanoERG).
+    pub fn value(&self, token_id: &str, amount: u64) -> Valuation {
+        if let Some(pool) = self.pools.iter().find(|p| p.lp_token_id == token_id) {
+            return self.value_spectrum_lp(pool, amount);
+        }
+        if let Some(lp) = self.dexy_lp.iter().find(|l| l.lp_token_id == token_id) {
+            return self.value_dexy_lp(lp, amount);
+        }
+        if let Some(lp) = self.lending.iter().find(|l| l.lend_token_id == token_id) {
+            return self.value_lending_lp(lp, amount);
+        }
+        self.value_underlying(token_id, amount)
+    }
+
+    /// Value a plain (non-LP) token.
+    fn value_underlying(&self, token_id: &str, amount: u64) -> Valuation {
+        if token_id == amm::ERG_TOKEN_ID {
+            return Valuation {
+                source: PriceSource::Native,
+                confidence: PriceConfidence::High,
+                value_nano: amount,
+                spot_value_nano: amount,
+                note: None,
+            };
+        }
+        if amount == 0 {
+            return Valuation {
+                source: PriceSource::Unpriced,
+                confidence: PriceConfidence::High,
+                value_nano: 0,
+                spot_value_nano: 0,
+                note: None,
+            };
+        }
+        if let Some(fixed) = self.fixed.get(token_id) {
+            let value = (amount as f64 * fixed.nano_per_unit) as u64;
+            return Valuation {
+                source: fixed.source,
+                confidence: fixed.confidence,
+                value_nano: value,
+                spot_value_nano: value,
+                note: fixed.note.clone(),
+            };
+        }
+        if let Some(bank) = self.hodl_banks.iter().find(|b| b.hodl_token_id == token_id) {
+            let burn = hodlcoin::burn_amount(
+                bank.reserve_nano_erg,
+                bank.circulating_supply,
+                bank.precision_factor,
+                amount.min(i64::MAX as u64) as i64,
+                bank.bank_fee_num,
+                bank.dev_fee_num,
+            );
+            return Valuation {
+                source: PriceSource::ProtocolBank,
+                confidence: PriceConfidence::High,
+                value_nano: burn.erg_to_user.max(0) as u64,
+                spot_value_nano: (amount as f64 * bank.price_nano_per_hodl) as u64,
+                note: Some("Burn value after bank and dev fees".to_string()),
+            };
+        }
+        self.value_by_route(token_id, amount)
+    }
+
+    fn value_by_route(&self, token_id: &str, amount: u64) -> Valuation {
+        let routes = amm::find_best_routes(
+            &self.graph,
+            token_id,
+            amm::ERG_TOKEN_ID,
+            amount,
+            VALUATION_MAX_HOPS,
+            1,
+        );
+        let Some(route) = routes.into_iter().next() else {
+            return Valuation::unpriced("No AMM route to ERG");
+        };
+
+        let spot_rate: f64 = route
+            .hops
+            .iter()
+            .map(|h| {
+                if h.reserves_in == 0 {
+                    0.0
+                } else {
+                    h.reserves_out as f64 / h.reserves_in as f64
+                }
+            })
+            .product();
+        let mut valuation = Valuation {
+            source: PriceSource::AmmRoute,
+            confidence: PriceConfidence::High,
+            value_nano: route.total_output,
+            spot_value_nano: (amount as f64 * spot_rate) as u64,
+            note: None,
+        };
+        let discount = valuation.discount_pct();
+        valuation.confidence = if discount <= HIGH_CONFIDENCE_DISCOUNT_PCT {
+            PriceConfidence::High
+        } else if discount <= MEDIUM_CONFIDENCE_DISCOUNT_PCT {
+            PriceConfidence::Medium
+        } else {
+            PriceConfidence::Low
+        };
+        if route.hops.len() > 1 {
+            valuation.note = Some(format!("{}-hop route", route.hops.len()));
+        }
+        valuation
+    }
+
+    fn value_spectrum_lp(&self, pool: &amm::AmmPool, lp_amount: u64) -> Valuation {
+        let (x_token, x_reserves) = match (&pool.pool_type, &pool.token_x) {
+            (amm::PoolType::T2T, Some(x)) => (x.token_id.as_str(), x.amount),
+            _ => (amm::ERG_TOKEN_ID, pool.erg_reserves.unwrap_or(0)),
+        };
+        let (x_amount, y_amount) = amm::calculator::calculate_redeem_shares(
+            x_reserves,
+            pool.token_y.amount,
+            pool.lp_circulating,
+            lp_amount,
+        );
+        let x = self.value_underlying(x_token, x_amount);
+        let y = self.value_underlying(&pool.token_y.token_id, y_amount);
+        combine(PriceSource::SpectrumLp, &[x, y])
+    }
+
+    fn value_dexy_lp(&self, lp: &DexyLpPrice, lp_amount: u64) -> Valuation {
+        let (erg_amount, dexy_amount) = amm::calculator::calculate_redeem_shares(
+            lp.erg_reserves,
+            lp.dexy_reserves,
+            lp.lp_circulating,
+            lp_amount,
+        );
+        let erg = self.value_underlying(amm::ERG_TOKEN_ID, erg_amount);
+        let dexy = self.value_underlying(&lp.dexy_token_id, dexy_amount);
+        combine(PriceSource::DexyLp, &[erg, dexy])
+    }
+
+    fn value_lending_lp(&self, lp: &LendingLpPrice, lp_amount: u64) -> Valuation {
+        let underlying =
+            lending::calculate_underlying_for_lp(lp_amount, lp.total_supplied, lp.lp_circulating);
+        let currency = lp.currency_id.as_deref().unwrap_or(amm::ERG_TOKEN_ID);
+        combine(
+            PriceSource::LendingLp,
+            &[self.value_underlying(currency, underlying)],
+        )
+    }
+}
+
+/// Sum underlying valuations, keeping the weakest confidence.
+fn combine(source: PriceSource, parts: &[Valuation]) -> Valuation {
+    let confidence = parts
+        .iter()
+        .map(|p| p.confidence)
+        .min()
+        .unwrap_or(PriceConfidence::None);
+    let unpriced = parts
+        .iter()
+        .any(|p| p.source == PriceSource::Unpriced && p.note.is_some());
+    Valuation {
+        source,
+        confidence: if unpriced {
+            confidence.min(PriceConfidence::Low)
+        } else {
+            confidence
+        },
+        value_nano: parts.iter().map(|p| p.value_nano).sum(),
+        spot_value_nano: parts.iter().map(|p| p.spot_value_nano).sum(),
+        note: unpriced.then(|| "Part of the underlying could not be priced".to_string()),
+    }
+}
+
+/// Load every price source. Failures are reported in the returned error list and
+/// the affected tokens fall back to AMM routes.
+pub(crate) async fn load_price_book(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    network: Network,
+    oracle_guard: &OracleGuardConfig,
+) -> (PriceBook, Vec<String>) {
+    let mut errors = Vec::new();
+
+    let pools = amm::discover_pools(client).await.unwrap_or_else(|e| {
+        errors.push(format!("AMM pools: {}", e));
+        Vec::new()
+    });
+
+    let sigmausd_ids = sigmausd::NftIds::for_network(network);
+    let sigmausd_state = match &sigmausd_ids {
+        Some(ids) => match sigmausd::fetch_sigmausd_state(client, capabilities, ids).await {
+            Ok(s) => Some(s),
+            Err(e) => {
+                errors.push(format!("SigmaUSD: {}", e));
+                None
+            }
+        },
+        None => None,
+    };
+    // The oracle's ERG/USD only when it passes the same guard as builds
+    let erg_usd = match oracle::check_oracle_with(
+        client,
+        capabilities,
+        network,
+        oracle_guard,
+        OracleFeed::ErgUsd,
+    )
+    .await
+    {
+        Ok(check) if check.verdict == OracleVerdict::Block => {
+            errors.push(format!("ERG/USD oracle: {}", check.issues.join("; ")));
+            None
+        }
+        Ok(check) => Some(1_000_000_000.0 / check.rate_nano as f64),
+        Err(e) => {
+            errors.push(format!("ERG/USD oracle: {}", e));
+            None
+        }
+    };
+
+    let mut book = PriceBook::new(erg_usd, pools);
+
+    // Bank prices derive from the same datapoint; a rejected oracle leaves
+    // SigUSD and SigRSV to AMM routes
+    if let (Some(s), Some(ids), Some(_)) = (&sigmausd_state, &sigmausd_ids, erg_usd) {
+        // Oracle R4 is nanoERG per USD; SigUSD has 2 decimals
+        book.set_fixed(
+            &ids.sigusd_token,
+            FixedPrice {
+                nano_per_unit: s.sigusd_price_nano as f64 / 100.0,
+                source: PriceSource::Oracle,
+                confidence: PriceConfidence::High,
+                note: None,
+            },
+        );
+        book.set_fixed(
+            &ids.sigrsv_token,
+            FixedPrice {
+                nano_per_unit: s.sigrsv_price_nano as f64,
+                source: PriceSource::ProtocolBank,
+                confidence: if s.can_redeem_sigrsv {
+                    PriceConfidence::High
+                } else {
+                    PriceConfidence::Low
+                },
+                note: (!s.can_redeem_sigrsv).then(|| {
+                    format!(
+                        "SigRSV redemption is closed at {:.0}% reserve ratio",
+                        s.reserve_ratio_pct
+                    )
+                }),
+            },
+        );
+    }
+
+    for variant in [dexy::DexyVariant::Gold, dexy::DexyVariant::Usd] {
+        let Some(ids) = dexy::DexyIds::for_variant(variant, network) else {
+            continue;
+        };
+        match dexy::fetch_dexy_state(client, capabilities, &ids).await {
+            Ok(s) => {
+                book.add_dexy_lp(DexyLpPrice {
+                    lp_token_id: ids.lp_token_id.clone(),
+                    dexy_token_id: ids.dexy_token.clone(),
+                    erg_reserves: s.lp_erg_reserves.max(0) as u64,
+                    dexy_reserves: s.lp_dexy_reserves.max(0) as u64,
+                    lp_circulating: s.lp_circulating.max(0) as u64,
+                });
+                if s.oracle_rate_nano <= 0 {
+                    continue;
+                }
+                let lp_rate = if s.lp_dexy_reserves > 0 {
+                    s.lp_erg_reserves as f64 / s.lp_dexy_reserves as f64
+                } else {
+                    0.0
+                };
+                let deviation =
+                    (lp_rate - s.oracle_rate_nano as f64).abs() / s.oracle_rate_nano as f64 * 100.0;
+                let off_peg = deviation > DEXY_LP_DEVIATION_PCT;
+                book.set_fixed(
+                    &ids.dexy_token,
+                    FixedPrice {
+                        nano_per_unit: s.oracle_rate_nano as f64,
+                        source: PriceSource::Oracle,
+                        confidence: if off_peg {
+                            PriceConfidence::Medium
+                        } else {
+                            PriceConfidence::High
+                        },
+                        note: off_peg.then(|| {
+                            format!("Dexy LP trades {:.1}% away from the oracle", deviation)
+                        }),
+                    },
+                );
+            }
+            Err(e) => errors.push(format!("{}: {}", variant.token_name(), e)),
+        }
+    }
+
+    match hodlcoin::discover_banks(client).await {
+        Ok(banks) => book.set_hodl_banks(banks),
+        Err(e) => errors.push(format!("HodlCoin: {}", e)),
+    }
+
+    match lending::fetch_all_markets(client, capabilities, None).await {
+        Ok(markets) => book.set_lending(
+            markets
+                .pools
+                .iter()
+                .filter_map(|ps| {
+                    let config = lending::constants::get_pool(&ps.pool_id)?;
+                    Some(LendingLpPrice {
+                        lend_token_id: config.lend_token_id.to_string(),
+                        currency_id: config.currency_id.map(str::to_string),
+                        total_supplied: ps.total_supplied,
+                        lp_circulating: ps.lp_tokens_in_circulation,
+                    })
+                })
+                .collect(),
+        ),
+        Err(e) => errors.push(format!("Duckpools: {}", e)),
+    }
+
+    (book, errors)
+}
+
+/// Value everything the active wallet holds.
+pub async fn get_wallet_valuation(state: &AppState) -> ServiceResult<WalletValuationResponse> {
+    let wallet = state
+        .wallet()
+        .await
+        .ok_or_else(|| "No wallet connected".to_string())?;
+
+    let client = state.require_node_client().await?;
+    let capabilities = client.require_capabilities().await?;
+    if capabilities.capability_tier == ergo_node_client::CapabilityTier::Basic {
+        return Err("Wallet valuation requires extraIndex enabled on the node".to_string());
+    }
+    let height = client.current_height().await.into_service()? as u32;
+
+    let (erg_nano, tokens) = client
+        .get_addresses_balances(&wallet.addresses)
+        .await
+        .into_service()?;
+    let network = state.config().await.network;
+    let oracle_guard = state.oracle_guard().await;
+    let (book, errors) = load_price_book(&client, &capabilities, network, &oracle_guard).await;
+
+    let mut assets = Vec::with_capacity(tokens.len() + 1);
+    let mut breakdown = ValuationBreakdown::default();
+
+    let erg = book.value(amm::ERG_TOKEN_ID, erg_nano);
+    assets.push(asset_valuation(
+        &book,
+        amm::ERG_TOKEN_ID.to_string(),
+        Some("ERG".to_string()),
+        9,
+        erg_nano,
+        erg,
+    ));
+    breakdown.erg_nano = erg_nano;
+
+    for (token_id, amount) in tokens {
+        let (name, decimals) = match client.get_token_info(&token_id).await {
+            Ok(info) => (info.name, info.decimals.unwrap_or(0) as u8),
+            Err(_) => (None, 0),
+        };
+        let valuation = book.value(&token_id, amount);
+        match valuation.source {
+            PriceSource::SpectrumLp | PriceSource::DexyLp => {
+                breakdown.amm_lp_nano += valuation.value_nano
+            }
+            PriceSource::LendingLp => breakdown.lending_lp_nano += valuation.value_nano,
+            PriceSource::Unpriced => breakdown.unpriced_count += 1,
+            _ => breakdown.tokens_nano += valuation.value_nano,
+        }
+        assets.push(asset_valuation(
+            &book, token_id, name, decimals, amount, valuation,
+        ));
+    }
+
+    breakdown.uncertain_nano = assets
+        .iter()
+        .filter(|a| a.confidence < PriceConfidence::High)
+        .map(|a| a.value_nano_erg)
+        .sum();
+    assets.sort_by(|a, b| b.value_nano_erg.cmp(&a.value_nano_erg));
+    let total: u64 = assets.iter().map(|a| a.value_nano_erg).sum();
+
+    Ok(WalletValuationResponse {
+        wallet: wallet
+            .watch_only
+            .clone()
+            .unwrap_or_else(|| "connected".to_string()),
+        addresses: wallet.addresses,
+        erg_usd: book.erg_usd,
+        total_value_nano_erg: total,
+        total_value_usd: book.usd(total),
+        breakdown,
+        assets,
+        errors,
+        block_height: height,
+    })
+}
+
+fn asset_valuation(
+    book: &PriceBook,
+    token_id: String,
+    name: Option<String>,
+    decimals: u8,
+    amount: u64,
+    valuation: Valuation,
+) -> AssetValuation {
+    AssetValuation {
+        token_id,
+        name,
+        decimals,
+        amount,
+        source: valuation.source,
+        confidence: valuation.confidence,
+        discount_pct: valuation.discount_pct(),
+        value_usd: book.usd(valuation.value_nano),
+        value_nano_erg: valuation.value_nano,
+        spot_value_nano_erg: valuation.spot_value_nano,
+        note: valuation.note,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "aa00000000000000000000000000000000000000000000000000000000000000";
+    const THIN: &str = "bb00000000000000000000000000000000000000000000000000000000000000";
+
+    fn n2t_pool(pool_id: &str, token_id: &str, erg: u64, tokens: u64) -> amm::AmmPool {
+        amm::AmmPool {
+            pool_id: pool_id.to_string(),
+            pool_type: amm::PoolType::N2T,
+            box_id: format!("box_{}", pool_id),
+            erg_reserves: Some(erg),
+            token_x: None,
+            token_y: amm::TokenAmount {
+                token_id: token_id.to_string(),
+                amount: tokens,
+                decimals: Some(0),
+                name: None,
+            },
+            lp_token_id: format!("lp_{}", pool_id),
+            lp_circulating: 1_000_000,
+            fee_num: 997,
+            fee_denom: 1000,
+        }
+    }
+
+    fn book() -> PriceBook {
+        PriceBook::new(
+            Some(2.0),
+            vec![
+                // 1 token = 1 ERG, deep
+                n2t_pool("deep", TOKEN, 1_000_000_000_000_000, 1_000_000_000_000_000),
+                // 1 token = 1 ERG, only 2 ERG of depth
+                n2t_pool("thin", THIN, 2_000_000_000, 2_000_000_000),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_erg_is_native() {
+        let v = book().value("ERG", 5_000_000_000);
+        assert_eq!(v.source, PriceSource::Native);
+        assert_eq!(v.value_nano, 5_000_000_000);
+        assert_eq!(book().usd(v.value_nano), Some(10.0));
+    }
+
+    #[test]
+    fn test_deep_route_is_high_confidence() {
+        let v = book().value(TOKEN, 1_000_000_000);
+        assert_eq!(v.source, PriceSource::AmmRoute);
+        assert_eq!(v.confidence, PriceConfidence::High);
+        assert!(v.value_nano < v.spot_value_nano);
+        assert!(v.discount_pct() < 1.0);
+    }
+
+    #[test]
+    fn test_thin_route_is_discounted() {
+        let v = book().value(THIN, 1_000_000_000);
+        assert_eq!(v.source, PriceSource::AmmRoute);
+        assert_eq!(v.confidence, PriceConfidence::Low);
+        assert!(v.discount_pct() > 10.0);
+    }
+
+    #[test]
+    fn test_fixed_price_wins_over_route() {
+        let mut book = book();
+        book.set_fixed(
+            TOKEN,
+            FixedPrice {
+                nano_per_unit: 3.0,
+                source: PriceSource::Oracle,
+                confidence: PriceConfidence::High,
+                note: None,
+            },
+        );
+        let v = book.value(TOKEN, 10);
+        assert_eq!(v.source, PriceSource::Oracle);
+        assert_eq!(v.value_nano, 30);
+    }
+
+    #[test]
+    fn test_spectrum_lp_valued_by_pool_share() {
+        // 10% of the deep pool: 1e14 nanoERG + 1e14 tokens (~1e14 nanoERG after impact)
+        let v = book().value("lp_deep", 100_000);
+        assert_eq!(v.source, PriceSource::SpectrumLp);
+        assert!(v.value_nano > 100_000_000_000_000);
+        assert!(v.spot_value_nano >= v.value_nano);
+    }
+
+    #[test]
+    fn test_dexy_lp_valued_by_pool_share() {
+        let mut book = book();
+        book.set_fixed(
+            "dexy",
+            FixedPrice {
+                nano_per_unit: 3.0,
+                source: PriceSource::Oracle,
+                confidence: PriceConfidence::High,
+                note: None,
+            },
+        );
+        book.add_dexy_lp(DexyLpPrice {
+            lp_token_id: "dexy_lp".to_string(),
+            dexy_token_id: "dexy".to_string(),
+            erg_reserves: 1_000,
+            dexy_reserves: 100,
+            lp_circulating: 100,
+        });
+        // 10% of the pool: 100 nanoERG + 10 Dexy at 3 nanoERG
+        let v = book.value("dexy_lp", 10);
+        assert_eq!(v.source, PriceSource::DexyLp);
+        assert_eq!(v.value_nano, 130);
+        assert_eq!(v.confidence, PriceConfidence::High);
+    }
+
+    #[test]
+    fn test_lending_lp_valued_by_underlying() {
+        let mut book = book();
+        book.set_lending(vec![LendingLpPrice {
+            lend_token_id: "lend_erg".to_string(),
+            currency_id: None,
+            total_supplied: 2_000,
+            lp_circulating: 1_000,
+        }]);
+        let v = book.value("lend_erg", 50);
+        assert_eq!(v.source, PriceSource::LendingLp);
+        assert_eq!(v.value_nano, 100);
+        assert_eq!(v.confidence, PriceConfidence::High);
+    }
+
+    #[test]
+    fn test_unknown_token_is_unpriced() {
+        let v = book().value("cc", 1);
+        assert_eq!(v.source, PriceSource::Unpriced);
+        assert_eq!(v.confidence, PriceConfidence::None);
+        assert_eq!(v.value_nano, 0);
+    }
+}
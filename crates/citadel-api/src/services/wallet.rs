@@ -1,14 +1,17 @@
-//! Wallet connection, balances, transaction history, and send tx building.
+//! Wallet connection, watch-only wallets, balances, transaction history, and
+//! send tx building.
 
 use crate::dto::{
-    wallet_status, ConnectionStatusResponse, RecentTxDto, RecentTxsResponse, TokenBalance,
-    TokenChangeDto, WalletBalanceResponse, WalletConnectResponse, WalletStatusResponse,
+    wallet_status, AddWatchWalletRequest, ConnectionStatusResponse, ContractInfo, RecentTxDto,
+    RecentTxsResponse, TokenBalance, TokenChangeDto, WalletBalanceResponse, WalletConnectResponse,
+    WalletListResponse, WalletStatusResponse, WatchWalletInfo,
 };
 use citadel_core::constants::{MIN_BOX_VALUE_NANO, TX_FEE_NANO};
+use citadel_core::{Counterparty, TxIntent};
 use ergopay_server::RequestStatus;
 
 use super::error::{IntoServiceError, ServiceResult};
-use crate::AppState;
+use crate::{AppState, WatchWallet};
 
 pub async fn start_wallet_connect(state: &AppState) -> ServiceResult<WalletConnectResponse> {
     let server = state.ergopay_server().await.into_service()?;
@@ -28,6 +31,7 @@ pub async fn get_wallet_status(state: &AppState) -> ServiceResult<WalletStatusRe
     Ok(WalletStatusResponse {
         connected: wallet.is_some(),
         address: wallet.as_ref().map(|w| w.address.clone()),
+        watch_only: wallet.as_ref().and_then(|w| w.watch_only.clone()),
         addresses: wallet.map(|w| w.addresses).unwrap_or_default(),
     })
 }
@@ -79,6 +83,84 @@ pub async fn disconnect_wallet(state: &AppState) -> ServiceResult<()> {
     Ok(())
 }
 
+/// Addresses derived from an xpub when the caller does not pick a count
+/// (the usual BIP-44 gap limit).
+const DEFAULT_XPUB_ADDRESS_COUNT: u32 = 20;
+const MAX_XPUB_ADDRESS_COUNT: u32 = 200;
+
+pub async fn list_wallets(state: &AppState) -> ServiceResult<WalletListResponse> {
+    let active = state.active_watch_wallet().await;
+    let watch_wallets = state
+        .watch_wallets()
+        .await
+        .into_iter()
+        .map(|w| WatchWalletInfo {
+            active: active.as_deref() == Some(w.name.as_str()),
+            name: w.name,
+            addresses: w.addresses,
+            xpub: w.xpub,
+        })
+        .collect();
+
+    Ok(WalletListResponse {
+        connected_address: state.connected_wallet().await.map(|w| w.address),
+        active_watch_wallet: active,
+        watch_wallets,
+    })
+}
+
+pub async fn add_watch_wallet(
+    state: &AppState,
+    request: AddWatchWalletRequest,
+) -> ServiceResult<WalletListResponse> {
+    let mut addresses: Vec<String> = Vec::new();
+    for a in request.addresses.iter().map(|a| a.trim()) {
+        if !a.is_empty() && !addresses.iter().any(|x| x == a) {
+            addresses.push(a.to_string());
+        }
+    }
+
+    if let Some(xpub) = &request.xpub {
+        let count = request
+            .xpub_count
+            .unwrap_or(DEFAULT_XPUB_ADDRESS_COUNT)
+            .clamp(1, MAX_XPUB_ADDRESS_COUNT);
+        for a in ergo_tx::derive_xpub_addresses(xpub, 0, count).into_service()? {
+            if !addresses.contains(&a) {
+                addresses.push(a);
+            }
+        }
+    }
+
+    state
+        .add_watch_wallet(WatchWallet {
+            name: request.name.trim().to_string(),
+            addresses,
+            xpub: request.xpub,
+        })
+        .await
+        .into_service()?;
+
+    list_wallets(state).await
+}
+
+pub async fn remove_watch_wallet(
+    state: &AppState,
+    name: &str,
+) -> ServiceResult<WalletListResponse> {
+    state.remove_watch_wallet(name).await.into_service()?;
+    list_wallets(state).await
+}
+
+/// Switch the active wallet; `None` returns to the connected wallet.
+pub async fn set_active_wallet(
+    state: &AppState,
+    name: Option<String>,
+) -> ServiceResult<WalletListResponse> {
+    state.set_active_wallet(name).await.into_service()?;
+    list_wallets(state).await
+}
+
 pub async fn get_wallet_balance(state: &AppState) -> ServiceResult<WalletBalanceResponse> {
     let wallet = state
         .wallet()
@@ -147,9 +229,25 @@ pub async fn get_wallet_balance(state: &AppState) -> ServiceResult<WalletBalance
             name,
             decimals,
             pending_amount,
+            verified: false,
+            warning: None,
         });
     }
 
+    // List and name checks only: a pool scan per balance load is too slow.
+    // Swap quotes re-screen with pools, where the liquidity heuristic matters.
+    let screen: Vec<(String, Option<String>)> = token_balances
+        .iter()
+        .map(|t| (t.token_id.clone(), t.name.clone()))
+        .collect();
+    let statuses = super::tokens::screen_tokens(state, &client, None, &screen).await;
+    for balance in &mut token_balances {
+        if let Some(status) = statuses.get(&balance.token_id) {
+            balance.verified = status.verified;
+            balance.warning = status.warning.clone();
+        }
+    }
+
     Ok(WalletBalanceResponse {
         address: wallet.address.clone(),
         addresses: wallet.addresses.clone(),
@@ -306,6 +404,8 @@ pub async fn get_recent_transactions(
                 amount: change,
                 name,
                 decimals,
+                verified: false,
+                warning: None,
             });
         }
 
@@ -319,6 +419,21 @@ pub async fn get_recent_transactions(
         });
     }
 
+    let screen: Vec<(String, Option<String>)> = token_cache
+        .iter()
+        .map(|(id, (name, _))| (id.clone(), name.clone()))
+        .collect();
+    let statuses = super::tokens::screen_tokens(state, &client, None, &screen).await;
+    for change in transactions
+        .iter_mut()
+        .flat_map(|tx| tx.token_changes.iter_mut())
+    {
+        if let Some(status) = statuses.get(&change.token_id) {
+            change.verified = status.verified;
+            change.warning = status.warning.clone();
+        }
+    }
+
     Ok(RecentTxsResponse { transactions })
 }
 
@@ -333,9 +448,16 @@ pub struct SendBuildResponse {
     pub miner_fee: i64,
     pub citadel_fee_nano: i64,
     pub input_count: usize,
+    /// Address book label or known contract name of the recipient.
+    pub recipient_label: Option<String>,
+    pub recipient_contract: Option<ContractInfo>,
+    /// Shown before signing, e.g. when the recipient is a protocol contract.
+    pub warnings: Vec<String>,
+    pub intent: TxIntent,
 }
 
-pub fn build_send_tx(
+pub async fn build_send_tx(
+    state: &AppState,
     recipient_address: &str,
     change_address: &str,
     erg_nano: &str,
@@ -359,6 +481,9 @@ pub fn build_send_tx(
         _ => return Err("token_id and token_amount must both be set or both omitted".to_string()),
     };
 
+    let recipient = super::address_book::describe_address(state, recipient_address).await?;
+    let warnings = super::address_book::send_warnings(&recipient);
+
     let recipient_tree = ergo_tx::address_to_ergo_tree(recipient_address).into_service()?;
     let change_tree = ergo_tx::address_to_ergo_tree(change_address).into_service()?;
 
@@ -399,9 +524,18 @@ pub fn build_send_tx(
 
     let unsigned_tx = serde_json::to_value(&result.unsigned_tx)
         .map_err(|e| format!("Failed to serialize tx: {}", e))?;
+    let mut intent = result.summary.intent();
+    intent.counterparties.push(Counterparty {
+        role: "recipient".to_string(),
+        name: recipient
+            .display_name()
+            .unwrap_or_else(|| recipient_address.to_string()),
+        address: Some(recipient_address.to_string()),
+    });
 
     Ok(SendBuildResponse {
         unsigned_tx,
+        intent,
         recipient_erg: result.summary.recipient_erg,
         token_id: result.summary.token_id,
         token_amount: result.summary.token_amount.map(|a| a.to_string()),
@@ -409,6 +543,9 @@ pub fn build_send_tx(
         miner_fee: result.summary.miner_fee,
         citadel_fee_nano: result.summary.citadel_fee_nano,
         input_count: result.summary.input_count,
+        recipient_label: recipient.display_name(),
+        recipient_contract: recipient.contract,
+        warnings,
     })
 }
 
@@ -418,6 +555,13 @@ pub async fn get_user_utxos(state: &AppState) -> ServiceResult<Vec<ergo_tx::Eip1
         .await
         .ok_or_else(|| "No wallet connected".to_string())?;
 
+    if let Some(name) = &wallet.watch_only {
+        return Err(format!(
+            "Watch-only wallet '{}' cannot sign transactions; switch to the connected wallet",
+            name
+        ));
+    }
+
     let client = state.require_node_client().await?;
 
     client
@@ -1,12 +1,29 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
-use citadel_core::{AppConfig, Network, NodeConfig};
+use citadel_core::{AppConfig, Network, NodeConfig, TokenOverrides, TokenRegistry};
 use ergo_node_client::NodeClient;
 use ergopay_server::ErgoPayServer;
 use thiserror::Error;
 use tokio::sync::RwLock;
 
+use crate::dto::{
+    AddressBookEntry, ArbMonitorConfig, ArbMonitorState, ArbObservation, OracleGuardConfig,
+    SwapOrder,
+};
+use crate::store::{JsonStore, StoreError};
+
+const ADDRESS_BOOK_FILE: &str = "address_book";
+const WATCH_WALLETS_FILE: &str = "watch_wallets";
+const SWAP_ORDERS_FILE: &str = "swap_orders";
+const ARB_MONITOR_FILE: &str = "arb_monitor";
+const ARB_MONITOR_HISTORY_LOG: &str = "arb_monitor_history";
+const TOKEN_REGISTRY_FILE: &str = "token_registry";
+const ORACLE_GUARD_FILE: &str = "oracle_guard";
+const HODL_DISCOVERY_FILE: &str = "hodlcoin_discovery";
+
 #[derive(Debug, Error)]
 pub enum ApiError {
     #[error("Invalid wallet address: {reason}")]
@@ -14,6 +31,21 @@ pub enum ApiError {
 
     #[error("ErgoPay server error: {0}")]
     ErgoPayServer(#[from] std::io::Error),
+
+    #[error("Wallet not found: {0}")]
+    UnknownWallet(String),
+
+    #[error("A wallet named '{0}' already exists")]
+    DuplicateWallet(String),
+
+    #[error("Invalid watch-only wallet: {reason}")]
+    InvalidWatchWallet { reason: String },
+
+    #[error("No address book entry for {0}")]
+    UnknownAddressBookEntry(String),
+
+    #[error(transparent)]
+    Store(#[from] StoreError),
 }
 
 #[derive(Clone, Debug)]
@@ -23,6 +55,9 @@ pub struct WalletState {
     /// All known addresses from the wallet (includes `address`).
     pub addresses: Vec<String>,
     pub connected_at: Instant,
+    /// Name of the watch-only wallet this state was built from (`None` for the
+    /// Nautilus / ErgoPay connected wallet).
+    pub watch_only: Option<String>,
 }
 
 impl WalletState {
@@ -44,8 +79,31 @@ impl WalletState {
             address: primary,
             addresses: addrs,
             connected_at: Instant::now(),
+            watch_only: None,
         }
     }
+
+    pub fn from_watch_wallet(wallet: &WatchWallet) -> Self {
+        let primary = wallet.addresses.first().cloned().unwrap_or_default();
+        Self {
+            watch_only: Some(wallet.name.clone()),
+            ..Self::with_addresses(primary, wallet.addresses.clone())
+        }
+    }
+
+    pub fn is_watch_only(&self) -> bool {
+        self.watch_only.is_some()
+    }
+}
+
+/// A named, address-only wallet. Balances and positions can be tracked but
+/// nothing can be signed.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct WatchWallet {
+    pub name: String,
+    pub addresses: Vec<String>,
+    /// Extended public key the addresses were derived from, if any.
+    pub xpub: Option<String>,
 }
 
 fn validate_p2pk_address(address: &str) -> Result<(), ApiError> {
@@ -89,7 +147,22 @@ struct AppStateInner {
     config: RwLock<AppConfig>,
     node_client: RwLock<Option<NodeClient>>,
     wallet: RwLock<Option<WalletState>>,
+    watch_wallets: RwLock<Vec<WatchWallet>>,
+    /// Name of the active watch-only wallet; `None` selects the connected wallet.
+    active_watch_wallet: RwLock<Option<String>>,
     ergopay_server: RwLock<Option<Arc<ErgoPayServer>>>,
+    store: RwLock<JsonStore>,
+    address_book: RwLock<Vec<AddressBookEntry>>,
+    swap_orders: RwLock<Vec<SwapOrder>>,
+    arb_monitor: RwLock<ArbMonitorState>,
+    /// Oldest first. Persisted as an append-only log next to `arb_monitor`.
+    arb_history: RwLock<Vec<ArbObservation>>,
+    /// Entries in the arb history log, including ones already trimmed from
+    /// memory. Only changed under the `arb_history` write lock.
+    arb_history_logged: AtomicUsize,
+    token_overrides: RwLock<TokenOverrides>,
+    oracle_guard: RwLock<OracleGuardConfig>,
+    hodl_discovery: RwLock<hodlcoin::DiscoveryOptions>,
 }
 
 impl AppState {
@@ -99,7 +172,18 @@ impl AppState {
                 config: RwLock::new(AppConfig::default()),
                 node_client: RwLock::new(None),
                 wallet: RwLock::new(None),
+                watch_wallets: RwLock::new(Vec::new()),
+                active_watch_wallet: RwLock::new(None),
                 ergopay_server: RwLock::new(None),
+                store: RwLock::new(JsonStore::in_memory()),
+                address_book: RwLock::new(Vec::new()),
+                swap_orders: RwLock::new(Vec::new()),
+                arb_monitor: RwLock::new(ArbMonitorState::default()),
+                arb_history: RwLock::new(Vec::new()),
+                arb_history_logged: AtomicUsize::new(0),
+                token_overrides: RwLock::new(TokenOverrides::default()),
+                oracle_guard: RwLock::new(OracleGuardConfig::default()),
+                hodl_discovery: RwLock::new(hodlcoin::DiscoveryOptions::default()),
             }),
         }
     }
@@ -110,11 +194,46 @@ impl AppState {
                 config: RwLock::new(config),
                 node_client: RwLock::new(None),
                 wallet: RwLock::new(None),
+                watch_wallets: RwLock::new(Vec::new()),
+                active_watch_wallet: RwLock::new(None),
                 ergopay_server: RwLock::new(None),
+                store: RwLock::new(JsonStore::in_memory()),
+                address_book: RwLock::new(Vec::new()),
+                swap_orders: RwLock::new(Vec::new()),
+                arb_monitor: RwLock::new(ArbMonitorState::default()),
+                arb_history: RwLock::new(Vec::new()),
+                arb_history_logged: AtomicUsize::new(0),
+                token_overrides: RwLock::new(TokenOverrides::default()),
+                oracle_guard: RwLock::new(OracleGuardConfig::default()),
+                hodl_discovery: RwLock::new(hodlcoin::DiscoveryOptions::default()),
             }),
         }
     }
 
+    /// Point persistence at `dir` and load everything stored there. Until this
+    /// is called user data lives in memory only.
+    pub async fn set_data_dir(&self, dir: impl Into<PathBuf>) -> Result<(), ApiError> {
+        let store = JsonStore::open(dir)?;
+        *self.inner.address_book.write().await = store.load(ADDRESS_BOOK_FILE);
+        *self.inner.watch_wallets.write().await = store.load(WATCH_WALLETS_FILE);
+        *self.inner.swap_orders.write().await = store.load(SWAP_ORDERS_FILE);
+        *self.inner.arb_monitor.write().await = store.load(ARB_MONITOR_FILE);
+        let arb_history: Vec<ArbObservation> = store.load_log(ARB_MONITOR_HISTORY_LOG);
+        self.inner
+            .arb_history_logged
+            .store(arb_history.len(), Ordering::Relaxed);
+        *self.inner.arb_history.write().await = arb_history;
+        *self.inner.token_overrides.write().await = store.load(TOKEN_REGISTRY_FILE);
+        *self.inner.oracle_guard.write().await = store.load(ORACLE_GUARD_FILE);
+        *self.inner.hodl_discovery.write().await = store.load(HODL_DISCOVERY_FILE);
+        *self.inner.store.write().await = store;
+        Ok(())
+    }
+
+    pub async fn store(&self) -> JsonStore {
+        self.inner.store.read().await.clone()
+    }
+
     pub async fn config(&self) -> AppConfig {
         self.inner.config.read().await.clone()
     }
@@ -173,10 +292,277 @@ impl AppState {
         self.inner.config.read().await.network
     }
 
+    /// The active wallet: the selected watch-only wallet if one is active,
+    /// otherwise the connected wallet.
     pub async fn wallet(&self) -> Option<WalletState> {
+        if let Some(name) = self.inner.active_watch_wallet.read().await.as_ref() {
+            let watch = self.inner.watch_wallets.read().await;
+            if let Some(w) = watch.iter().find(|w| &w.name == name) {
+                return Some(WalletState::from_watch_wallet(w));
+            }
+        }
+        self.connected_wallet().await
+    }
+
+    /// The Nautilus / ErgoPay connected wallet, regardless of the active selection.
+    pub async fn connected_wallet(&self) -> Option<WalletState> {
         self.inner.wallet.read().await.clone()
     }
 
+    /// Connected wallet (if any) followed by every watch-only wallet.
+    pub async fn all_wallets(&self) -> Vec<WalletState> {
+        let mut wallets: Vec<WalletState> = self.connected_wallet().await.into_iter().collect();
+        wallets.extend(
+            self.inner
+                .watch_wallets
+                .read()
+                .await
+                .iter()
+                .map(WalletState::from_watch_wallet),
+        );
+        wallets
+    }
+
+    pub async fn watch_wallets(&self) -> Vec<WatchWallet> {
+        self.inner.watch_wallets.read().await.clone()
+    }
+
+    pub async fn active_watch_wallet(&self) -> Option<String> {
+        self.inner.active_watch_wallet.read().await.clone()
+    }
+
+    pub async fn add_watch_wallet(&self, wallet: WatchWallet) -> Result<(), ApiError> {
+        if wallet.name.trim().is_empty() {
+            return Err(ApiError::InvalidWatchWallet {
+                reason: "Name must not be empty".to_string(),
+            });
+        }
+        if wallet.addresses.is_empty() {
+            return Err(ApiError::InvalidWatchWallet {
+                reason: "At least one address is required".to_string(),
+            });
+        }
+        for a in &wallet.addresses {
+            validate_p2pk_address(a)?;
+        }
+
+        let mut watch = self.inner.watch_wallets.write().await;
+        if watch.iter().any(|w| w.name == wallet.name) {
+            return Err(ApiError::DuplicateWallet(wallet.name));
+        }
+        watch.push(wallet);
+        self.inner
+            .store
+            .read()
+            .await
+            .save(WATCH_WALLETS_FILE, &*watch)?;
+        Ok(())
+    }
+
+    pub async fn remove_watch_wallet(&self, name: &str) -> Result<(), ApiError> {
+        let mut watch = self.inner.watch_wallets.write().await;
+        let before = watch.len();
+        watch.retain(|w| w.name != name);
+        if watch.len() == before {
+            return Err(ApiError::UnknownWallet(name.to_string()));
+        }
+        self.inner
+            .store
+            .read()
+            .await
+            .save(WATCH_WALLETS_FILE, &*watch)?;
+        drop(watch);
+
+        let mut active = self.inner.active_watch_wallet.write().await;
+        if active.as_deref() == Some(name) {
+            *active = None;
+        }
+        Ok(())
+    }
+
+    /// Select the active wallet by watch-only name; `None` selects the connected wallet.
+    pub async fn set_active_wallet(&self, name: Option<String>) -> Result<(), ApiError> {
+        if let Some(ref n) = name {
+            let watch = self.inner.watch_wallets.read().await;
+            if !watch.iter().any(|w| &w.name == n) {
+                return Err(ApiError::UnknownWallet(n.clone()));
+            }
+        }
+        *self.inner.active_watch_wallet.write().await = name;
+        Ok(())
+    }
+
+    pub async fn address_book(&self) -> Vec<AddressBookEntry> {
+        self.inner.address_book.read().await.clone()
+    }
+
+    /// Insert or replace the entry for `entry.address` and persist the book.
+    pub async fn upsert_address_book_entry(&self, entry: AddressBookEntry) -> Result<(), ApiError> {
+        let mut book = self.inner.address_book.write().await;
+        match book.iter_mut().find(|e| e.address == entry.address) {
+            Some(existing) => *existing = entry,
+            None => book.push(entry),
+        }
+        self.inner
+            .store
+            .read()
+            .await
+            .save(ADDRESS_BOOK_FILE, &*book)?;
+        Ok(())
+    }
+
+    pub async fn remove_address_book_entry(&self, address: &str) -> Result<(), ApiError> {
+        let mut book = self.inner.address_book.write().await;
+        let before = book.len();
+        book.retain(|e| e.address != address);
+        if book.len() == before {
+            return Err(ApiError::UnknownAddressBookEntry(address.to_string()));
+        }
+        self.inner
+            .store
+            .read()
+            .await
+            .save(ADDRESS_BOOK_FILE, &*book)?;
+        Ok(())
+    }
+
+    pub async fn swap_orders(&self) -> Vec<SwapOrder> {
+        self.inner.swap_orders.read().await.clone()
+    }
+
+    /// Apply `f` to the order list under the write lock and persist the result.
+    pub async fn update_swap_orders<R>(
+        &self,
+        f: impl FnOnce(&mut Vec<SwapOrder>) -> R,
+    ) -> Result<R, ApiError> {
+        let mut orders = self.inner.swap_orders.write().await;
+        let result = f(&mut orders);
+        self.inner
+            .store
+            .read()
+            .await
+            .save(SWAP_ORDERS_FILE, &*orders)?;
+        Ok(result)
+    }
+
+    pub async fn arb_monitor(&self) -> ArbMonitorState {
+        self.inner.arb_monitor.read().await.clone()
+    }
+
+    pub async fn arb_monitor_history(&self) -> Vec<ArbObservation> {
+        self.inner.arb_history.read().await.clone()
+    }
+
+    pub async fn set_arb_monitor_config(&self, config: ArbMonitorConfig) -> Result<(), ApiError> {
+        let mut monitor = self.inner.arb_monitor.write().await;
+        monitor.config = config;
+        self.inner
+            .store
+            .read()
+            .await
+            .save(ARB_MONITOR_FILE, &*monitor)?;
+        Ok(())
+    }
+
+    /// Record one monitored block, keeping the newest `max_len` in memory.
+    /// The observation is appended to the history log, which is compacted
+    /// only once it holds a tenth more entries than are kept.
+    pub async fn record_arb_observation(
+        &self,
+        observation: ArbObservation,
+        max_len: usize,
+    ) -> Result<(), ApiError> {
+        let mut monitor = self.inner.arb_monitor.write().await;
+        let mut history = self.inner.arb_history.write().await;
+        let store = self.inner.store.read().await;
+        store.append_log(ARB_MONITOR_HISTORY_LOG, &observation)?;
+        monitor.push_observation(&mut history, observation, max_len);
+        let logged = self
+            .inner
+            .arb_history_logged
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        if logged > max_len + max_len / 10 {
+            store.save_log(ARB_MONITOR_HISTORY_LOG, &history)?;
+            self.inner
+                .arb_history_logged
+                .store(history.len(), Ordering::Relaxed);
+        }
+        store.save(ARB_MONITOR_FILE, &*monitor)?;
+        Ok(())
+    }
+
+    pub async fn clear_arb_monitor_history(&self) -> Result<(), ApiError> {
+        let mut history = self.inner.arb_history.write().await;
+        history.clear();
+        self.inner
+            .store
+            .read()
+            .await
+            .save_log::<ArbObservation>(ARB_MONITOR_HISTORY_LOG, &[])?;
+        self.inner.arb_history_logged.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub async fn token_overrides(&self) -> TokenOverrides {
+        self.inner.token_overrides.read().await.clone()
+    }
+
+    /// Bundled token list merged with the local overrides.
+    pub async fn token_registry(&self) -> TokenRegistry {
+        TokenRegistry::with_overrides(&self.inner.token_overrides.read().await)
+    }
+
+    /// Apply `f` to the local token overrides under the write lock and persist them.
+    pub async fn update_token_overrides<R>(
+        &self,
+        f: impl FnOnce(&mut TokenOverrides) -> R,
+    ) -> Result<R, ApiError> {
+        let mut overrides = self.inner.token_overrides.write().await;
+        let result = f(&mut overrides);
+        self.inner
+            .store
+            .read()
+            .await
+            .save(TOKEN_REGISTRY_FILE, &*overrides)?;
+        Ok(result)
+    }
+
+    pub async fn oracle_guard(&self) -> OracleGuardConfig {
+        self.inner.oracle_guard.read().await.clone()
+    }
+
+    /// Replace the oracle guard settings and persist them.
+    pub async fn set_oracle_guard(&self, config: OracleGuardConfig) -> Result<(), ApiError> {
+        let mut guard = self.inner.oracle_guard.write().await;
+        *guard = config;
+        self.inner
+            .store
+            .read()
+            .await
+            .save(ORACLE_GUARD_FILE, &*guard)?;
+        Ok(())
+    }
+
+    pub async fn hodl_discovery(&self) -> hodlcoin::DiscoveryOptions {
+        self.inner.hodl_discovery.read().await.clone()
+    }
+
+    /// Replace the HodlCoin bank discovery settings and persist them.
+    pub async fn set_hodl_discovery(
+        &self,
+        options: hodlcoin::DiscoveryOptions,
+    ) -> Result<(), ApiError> {
+        let mut discovery = self.inner.hodl_discovery.write().await;
+        *discovery = options;
+        self.inner
+            .store
+            .read()
+            .await
+            .save(HODL_DISCOVERY_FILE, &*discovery)?;
+        Ok(())
+    }
+
     pub async fn set_wallet(&self, address: String) -> Result<(), ApiError> {
         self.set_wallet_addresses(address, Vec::new()).await
     }
@@ -192,6 +578,10 @@ impl AppState {
         }
         let mut wallet = self.inner.wallet.write().await;
         *wallet = Some(WalletState::with_addresses(primary, addresses));
+        drop(wallet);
+
+        // A fresh connection becomes the active wallet
+        *self.inner.active_watch_wallet.write().await = None;
         Ok(())
     }
 
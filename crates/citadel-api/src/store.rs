@@ -0,0 +1,223 @@
+//! JSON-file persistence for user data (address book, ...).
+//!
+//! Each collection is one pretty-printed JSON file in the app data directory.
+//! Writes go to a temp file and are renamed into place so a crash mid-write
+//! never leaves a truncated file. Growing histories use an append-only log
+//! instead (one JSON value per line) so recording an entry does not rewrite
+//! the whole collection. Without a data directory (tests, CLI use) the store
+//! is in-memory only: loads return defaults and saves are no-ops.
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StoreError {
+    #[error("Store I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Store serialization error: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct JsonStore {
+    dir: Option<PathBuf>,
+}
+
+impl JsonStore {
+    /// Store backed by `dir`, created if missing.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir: Some(dir) })
+    }
+
+    pub fn in_memory() -> Self {
+        Self { dir: None }
+    }
+
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    fn path(&self, name: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|d| d.join(format!("{}.json", name)))
+    }
+
+    /// Load collection `name`, falling back to `T::default()` when the file is
+    /// missing or unreadable (a corrupt file is logged, not fatal).
+    pub fn load<T: DeserializeOwned + Default>(&self, name: &str) -> T {
+        let Some(path) = self.path(name) else {
+            return T::default();
+        };
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return T::default(),
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {}", path.display(), e);
+                return T::default();
+            }
+        };
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring corrupt store file {}: {}", path.display(), e);
+            T::default()
+        })
+    }
+
+    pub fn save<T: Serialize + ?Sized>(&self, name: &str, value: &T) -> Result<(), StoreError> {
+        let Some(path) = self.path(name) else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(value)?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn log_path(&self, name: &str) -> Option<PathBuf> {
+        self.dir.as_ref().map(|d| d.join(format!("{}.jsonl", name)))
+    }
+
+    /// Load log `name` written by [`append_log`](Self::append_log). Lines that
+    /// do not parse (e.g. a write torn by a crash) are skipped.
+    pub fn load_log<T: DeserializeOwned>(&self, name: &str) -> Vec<T> {
+        let Some(path) = self.log_path(name) else {
+            return Vec::new();
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+            Err(e) => {
+                tracing::warn!("Failed to read {}: {}", path.display(), e);
+                return Vec::new();
+            }
+        };
+        let mut skipped = 0;
+        let entries = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).map_err(|_| skipped += 1).ok())
+            .collect();
+        if skipped > 0 {
+            tracing::warn!("Skipped {} corrupt lines in {}", skipped, path.display());
+        }
+        entries
+    }
+
+    /// Append one entry to log `name`. A torn last line left by a crash is
+    /// terminated first so the new entry starts on a line of its own.
+    pub fn append_log<T: Serialize>(&self, name: &str, value: &T) -> Result<(), StoreError> {
+        let Some(path) = self.log_path(name) else {
+            return Ok(());
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&path)?;
+        let mut line = String::new();
+        if file.seek(SeekFrom::End(0))? > 0 {
+            let mut last = [0u8];
+            file.seek(SeekFrom::End(-1))?;
+            file.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                line.push('\n');
+            }
+        }
+        line.push_str(&serde_json::to_string(value)?);
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    /// Replace log `name` with `values` (used to compact it).
+    pub fn save_log<T: Serialize>(&self, name: &str, values: &[T]) -> Result<(), StoreError> {
+        let Some(path) = self.log_path(name) else {
+            return Ok(());
+        };
+        let mut text = String::new();
+        for value in values {
+            text.push_str(&serde_json::to_string(value)?);
+            text.push('\n');
+        }
+        let tmp = path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, text)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("citadel-store-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = temp_dir("round-trip");
+        let store = JsonStore::open(&dir).unwrap();
+        store
+            .save("items", &vec!["a".to_string(), "b".to_string()])
+            .unwrap();
+
+        let loaded: Vec<String> = JsonStore::open(&dir).unwrap().load("items");
+        assert_eq!(loaded, vec!["a", "b"]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_and_corrupt_files_load_default() {
+        let dir = temp_dir("corrupt");
+        let store = JsonStore::open(&dir).unwrap();
+        let missing: Vec<String> = store.load("missing");
+        assert!(missing.is_empty());
+
+        std::fs::write(dir.join("broken.json"), "{not json").unwrap();
+        let broken: Vec<String> = store.load("broken");
+        assert!(broken.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_log_appends_and_compacts() {
+        let dir = temp_dir("log");
+        let store = JsonStore::open(&dir).unwrap();
+        store.append_log("log", &1u32).unwrap();
+        store.append_log("log", &2u32).unwrap();
+        assert_eq!(store.load_log::<u32>("log"), vec![1, 2]);
+
+        // A torn last line is dropped, earlier entries survive
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.join("log.jsonl"))
+            .unwrap();
+        file.write_all(b"{\"tru").unwrap();
+        assert_eq!(store.load_log::<u32>("log"), vec![1, 2]);
+
+        // The next entry does not land on the torn line
+        store.append_log("log", &4u32).unwrap();
+        assert_eq!(store.load_log::<u32>("log"), vec![1, 2, 4]);
+
+        store.save_log("log", &[3u32]).unwrap();
+        assert_eq!(store.load_log::<u32>("log"), vec![3]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_in_memory_store_discards_saves() {
+        let store = JsonStore::in_memory();
+        store.save("items", &vec![1u32]).unwrap();
+        let loaded: Vec<u32> = store.load("items");
+        assert!(loaded.is_empty());
+        store.append_log("log", &1u32).unwrap();
+        assert!(store.load_log::<u32>("log").is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+//! Protocol-agnostic transaction intent.
+//!
+//! Every transaction builder describes what it does as a [`TxIntent`]: the
+//! action, what leaves and enters the user's wallet, the fee split and who the
+//! user is dealing with. The signing page, transaction journal, notifications
+//! and the ErgoPay `message` field all render from this one structure so the
+//! wording is consistent across protocols.
+
+use serde::{Deserialize, Serialize};
+
+/// Token ID used for the native coin in intents.
+pub const ERG_ASSET_ID: &str = "ERG";
+
+/// An amount of ERG or a token, in raw units.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntentAsset {
+    /// Token ID, or [`ERG_ASSET_ID`] for nanoERG.
+    pub token_id: String,
+    pub name: Option<String>,
+    pub amount: u64,
+    /// `None` when the builder does not know the token's decimals; rendered as raw units.
+    pub decimals: Option<u8>,
+}
+
+impl IntentAsset {
+    pub fn erg(nano: u64) -> Self {
+        Self {
+            token_id: ERG_ASSET_ID.to_string(),
+            name: Some("ERG".to_string()),
+            amount: nano,
+            decimals: Some(9),
+        }
+    }
+
+    pub fn token(token_id: impl Into<String>, amount: u64) -> Self {
+        Self {
+            token_id: token_id.into(),
+            name: None,
+            amount,
+            decimals: None,
+        }
+    }
+
+    pub fn named(
+        token_id: impl Into<String>,
+        name: impl Into<String>,
+        amount: u64,
+        decimals: u8,
+    ) -> Self {
+        Self {
+            token_id: token_id.into(),
+            name: Some(name.into()),
+            amount,
+            decimals: Some(decimals),
+        }
+    }
+
+    pub fn is_erg(&self) -> bool {
+        self.token_id == ERG_ASSET_ID
+    }
+
+    /// Display name: the token name, or an abbreviated token ID.
+    pub fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None if self.token_id.len() > 8 => format!("{}…", &self.token_id[..8]),
+            None => self.token_id.clone(),
+        }
+    }
+
+    /// e.g. `"1.5 ERG"`, `"10.25 SigUSD"`.
+    pub fn display(&self) -> String {
+        format!(
+            "{} {}",
+            format_amount(self.amount, self.decimals.unwrap_or(0)),
+            self.label()
+        )
+    }
+}
+
+/// Fees split by who receives them. All in nanoERG.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IntentFees {
+    pub miner_nano: u64,
+    /// Protocol fees (bank / pool fees, off-chain executor fees, ...). Already
+    /// reflected in the intent's asset amounts; listed for information.
+    pub protocol_nano: u64,
+    /// Citadel app developer fee.
+    pub citadel_nano: u64,
+}
+
+impl IntentFees {
+    pub fn total_nano(&self) -> u64 {
+        self.miner_nano + self.protocol_nano + self.citadel_nano
+    }
+}
+
+/// A contract or party the transaction interacts with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Counterparty {
+    /// e.g. "bank", "pool", "proxy", "recipient".
+    pub role: String,
+    pub name: String,
+    pub address: Option<String>,
+}
+
+/// Price protection the transaction enforces.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlippageBounds {
+    /// Least the user accepts to receive.
+    pub min_out: Option<IntentAsset>,
+    /// Most the user may spend.
+    pub max_in: Option<IntentAsset>,
+    pub tolerance_pct: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxIntent {
+    pub protocol: String,
+    /// Short verb phrase, e.g. "Mint SigUSD".
+    pub action: String,
+    /// Assets leaving the user's wallet, excluding miner and Citadel fees.
+    pub assets_in: Vec<IntentAsset>,
+    /// Assets the user receives (or an estimate, see `slippage`).
+    pub assets_out: Vec<IntentAsset>,
+    pub fees: IntentFees,
+    pub counterparties: Vec<Counterparty>,
+    pub slippage: Option<SlippageBounds>,
+    pub notes: Vec<String>,
+}
+
+impl TxIntent {
+    pub fn new(protocol: impl Into<String>, action: impl Into<String>) -> Self {
+        Self {
+            protocol: protocol.into(),
+            action: action.into(),
+            assets_in: Vec::new(),
+            assets_out: Vec::new(),
+            fees: IntentFees::default(),
+            counterparties: Vec::new(),
+            slippage: None,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Add an asset the user gives up. Zero amounts are skipped.
+    pub fn pays(mut self, asset: IntentAsset) -> Self {
+        if asset.amount > 0 {
+            self.assets_in.push(asset);
+        }
+        self
+    }
+
+    /// Add an asset the user receives. Zero amounts are skipped.
+    pub fn receives(mut self, asset: IntentAsset) -> Self {
+        if asset.amount > 0 {
+            self.assets_out.push(asset);
+        }
+        self
+    }
+
+    pub fn miner_fee(mut self, nano: u64) -> Self {
+        self.fees.miner_nano = nano;
+        self
+    }
+
+    pub fn protocol_fee(mut self, nano: u64) -> Self {
+        self.fees.protocol_nano = nano;
+        self
+    }
+
+    pub fn citadel_fee(mut self, nano: u64) -> Self {
+        self.fees.citadel_nano = nano;
+        self
+    }
+
+    pub fn counterparty(mut self, role: impl Into<String>, name: impl Into<String>) -> Self {
+        self.counterparties.push(Counterparty {
+            role: role.into(),
+            name: name.into(),
+            address: None,
+        });
+        self
+    }
+
+    pub fn min_out(mut self, asset: IntentAsset, tolerance_pct: Option<f64>) -> Self {
+        let bounds = self.slippage.get_or_insert_with(SlippageBounds::default);
+        bounds.min_out = Some(asset);
+        bounds.tolerance_pct = tolerance_pct.or(bounds.tolerance_pct);
+        self
+    }
+
+    pub fn max_in(mut self, asset: IntentAsset) -> Self {
+        self.slippage
+            .get_or_insert_with(SlippageBounds::default)
+            .max_in = Some(asset);
+        self
+    }
+
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Fill in name and decimals for every asset with `token_id` whose
+    /// metadata the builder did not know.
+    pub fn describe_token(&mut self, token_id: &str, name: Option<&str>, decimals: Option<u8>) {
+        let slippage = self
+            .slippage
+            .iter_mut()
+            .flat_map(|s| s.min_out.iter_mut().chain(s.max_in.iter_mut()));
+        for asset in self
+            .assets_in
+            .iter_mut()
+            .chain(self.assets_out.iter_mut())
+            .chain(slippage)
+            .filter(|a| a.token_id == token_id)
+        {
+            if asset.name.is_none() {
+                asset.name = name.map(str::to_string);
+            }
+            if asset.decimals.is_none() {
+                asset.decimals = decimals;
+            }
+        }
+    }
+
+    /// One-line description for lists and notifications,
+    /// e.g. `"SigmaUSD: Mint SigUSD (12.3 ERG → 10 SigUSD)"`.
+    pub fn headline(&self) -> String {
+        let flow = match (self.assets_in.is_empty(), self.assets_out.is_empty()) {
+            (false, false) => format!(
+                " ({} → {})",
+                join_assets(&self.assets_in),
+                join_assets(&self.assets_out)
+            ),
+            (false, true) => format!(" ({})", join_assets(&self.assets_in)),
+            (true, false) => format!(" (→ {})", join_assets(&self.assets_out)),
+            (true, true) => String::new(),
+        };
+        format!("{}: {}{}", self.protocol, self.action, flow)
+    }
+
+    /// Multi-line description for signing prompts and the ErgoPay `message`.
+    pub fn message(&self) -> String {
+        let mut lines = vec![format!("{}: {}", self.protocol, self.action)];
+        if !self.assets_in.is_empty() {
+            lines.push(format!("You send: {}", join_assets(&self.assets_in)));
+        }
+        if !self.assets_out.is_empty() {
+            lines.push(format!("You receive: {}", join_assets(&self.assets_out)));
+        }
+        if let Some(slippage) = &self.slippage {
+            if let Some(min_out) = &slippage.min_out {
+                let tolerance = slippage
+                    .tolerance_pct
+                    .map(|t| format!(" ({}% slippage)", t))
+                    .unwrap_or_default();
+                lines.push(format!(
+                    "Minimum received: {}{}",
+                    min_out.display(),
+                    tolerance
+                ));
+            }
+            if let Some(max_in) = &slippage.max_in {
+                lines.push(format!("Maximum spent: {}", max_in.display()));
+            }
+        }
+
+        let mut fees = Vec::new();
+        if self.fees.miner_nano > 0 {
+            fees.push(format!(
+                "miner {}",
+                IntentAsset::erg(self.fees.miner_nano).display()
+            ));
+        }
+        if self.fees.protocol_nano > 0 {
+            fees.push(format!(
+                "protocol {}",
+                IntentAsset::erg(self.fees.protocol_nano).display()
+            ));
+        }
+        if self.fees.citadel_nano > 0 {
+            fees.push(format!(
+                "Citadel {}",
+                IntentAsset::erg(self.fees.citadel_nano).display()
+            ));
+        }
+        if !fees.is_empty() {
+            lines.push(format!("Fees: {}", fees.join(", ")));
+        }
+
+        for c in &self.counterparties {
+            let name = match &c.address {
+                Some(address) if *address != c.name => format!("{} ({})", c.name, address),
+                _ => c.name.clone(),
+            };
+            lines.push(format!("{}: {}", capitalize(&c.role), name));
+        }
+        lines.extend(self.notes.iter().cloned());
+        lines.join("\n")
+    }
+}
+
+fn join_assets(assets: &[IntentAsset]) -> String {
+    assets
+        .iter()
+        .map(IntentAsset::display)
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Format a raw amount with `decimals`, trimming trailing zeros.
+pub fn format_amount(raw: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return raw.to_string();
+    }
+    let divisor = 10u64.pow(decimals as u32);
+    let whole = raw / divisor;
+    let frac = raw % divisor;
+    if frac == 0 {
+        return whole.to_string();
+    }
+    let frac = format!("{:0width$}", frac, width = decimals as usize);
+    format!("{}.{}", whole, frac.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(1_500_000_000, 9), "1.5");
+        assert_eq!(format_amount(1_000, 2), "10");
+        assert_eq!(format_amount(1_005, 2), "10.05");
+        assert_eq!(format_amount(7, 0), "7");
+        assert_eq!(format_amount(1, 9), "0.000000001");
+    }
+
+    #[test]
+    fn test_headline_and_message() {
+        let intent = TxIntent::new("SigmaUSD", "Mint SigUSD")
+            .pays(IntentAsset::erg(12_300_000_000))
+            .receives(IntentAsset::named("abc", "SigUSD", 1_000, 2))
+            .miner_fee(1_100_000)
+            .protocol_fee(20_000_000)
+            .citadel_fee(11_000_000)
+            .counterparty("bank", "SigmaUSD bank");
+
+        assert_eq!(
+            intent.headline(),
+            "SigmaUSD: Mint SigUSD (12.3 ERG → 10 SigUSD)"
+        );
+        let message = intent.message();
+        assert!(message.contains("You send: 12.3 ERG"));
+        assert!(message.contains("Fees: miner 0.0011 ERG, protocol 0.02 ERG, Citadel 0.011 ERG"));
+        assert!(message.contains("Bank: SigmaUSD bank"));
+        assert_eq!(intent.fees.total_nano(), 32_100_000);
+    }
+
+    #[test]
+    fn test_zero_amounts_skipped() {
+        let intent = TxIntent::new("Spectrum", "Refund")
+            .pays(IntentAsset::erg(0))
+            .receives(IntentAsset::token("t", 0));
+        assert!(intent.assets_in.is_empty());
+        assert!(intent.assets_out.is_empty());
+        assert_eq!(intent.headline(), "Spectrum: Refund");
+    }
+
+    #[test]
+    fn test_describe_token_fills_unknown_metadata() {
+        let mut intent = TxIntent::new("Spectrum", "Swap")
+            .pays(IntentAsset::erg(1_000_000_000))
+            .receives(IntentAsset::token("0123456789abcdef", 1_234))
+            .min_out(IntentAsset::token("0123456789abcdef", 1_200), Some(0.5));
+        assert_eq!(intent.assets_out[0].display(), "1234 01234567…");
+
+        intent.describe_token("0123456789abcdef", Some("SPF"), Some(2));
+        assert_eq!(intent.assets_out[0].display(), "12.34 SPF");
+        let min_out = intent.slippage.as_ref().unwrap().min_out.as_ref().unwrap();
+        assert_eq!(min_out.display(), "12 SPF");
+    }
+}
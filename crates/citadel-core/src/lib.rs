@@ -4,8 +4,12 @@
 
 pub mod config;
 pub mod errors;
+pub mod intent;
+pub mod token_registry;
 pub mod types;
 
 pub use config::*;
 pub use errors::*;
+pub use intent::*;
+pub use token_registry::*;
 pub use types::*;
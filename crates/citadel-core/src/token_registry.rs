@@ -0,0 +1,349 @@
+//! Verified token registry and scam-token heuristics
+//!
+//! A list of verified token IDs ships with the app (`data/verified_tokens.json`).
+//! A local override file can add or block tokens and carry a newer copy of the
+//! list. Anything not on the list is checked against two heuristics: a name
+//! that reads like a verified token's, and a fresh token with little liquidity.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// The list bundled at build time.
+pub const BUNDLED_TOKEN_LIST: &str = include_str!("../data/verified_tokens.json");
+
+/// Native ERG, as used for token IDs throughout the app.
+const ERG_TOKEN_ID: &str = "ERG";
+
+/// Tokens minted within this many blocks (~7 days) count as fresh.
+pub const FRESH_TOKEN_BLOCKS: u64 = 5_040;
+
+/// Fresh tokens with less ERG pool liquidity than this are flagged (100 ERG).
+pub const LOW_LIQUIDITY_NANO: u64 = 100_000_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedToken {
+    pub token_id: String,
+    pub name: String,
+    pub decimals: u8,
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Icon path served by the frontend.
+    #[serde(default)]
+    pub logo: Option<String>,
+    /// SHA-256 of the icon file, hex.
+    #[serde(default)]
+    pub logo_sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenList {
+    pub version: u32,
+    pub tokens: Vec<VerifiedToken>,
+}
+
+impl TokenList {
+    pub fn bundled() -> Self {
+        Self::bundled_ref().clone()
+    }
+
+    /// The bundled list, parsed on first use.
+    fn bundled_ref() -> &'static Self {
+        static BUNDLED: OnceLock<TokenList> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            serde_json::from_str(BUNDLED_TOKEN_LIST).expect("bundled token list is valid JSON")
+        })
+    }
+}
+
+/// Local additions on top of the shipped list, persisted in the data dir.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenOverrides {
+    /// Newer copy of the list; used instead of the bundled one while its
+    /// version is higher.
+    #[serde(default)]
+    pub list: Option<TokenList>,
+    /// Tokens the user verified themselves.
+    #[serde(default)]
+    pub verified: Vec<VerifiedToken>,
+    /// Token IDs the user marked as scams. Wins over any list.
+    #[serde(default)]
+    pub blocked: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TokenWarning {
+    /// Blocked in the local override file.
+    Blocked,
+    /// Name reads like a verified token's but the ID differs.
+    NameCollision {
+        verified_token_id: String,
+        verified_name: String,
+    },
+    /// Minted recently and thinly traded.
+    FreshLowLiquidity {
+        age_blocks: u64,
+        liquidity_nano: u64,
+    },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenStatus {
+    pub verified: bool,
+    pub warning: Option<TokenWarning>,
+}
+
+/// On-chain context for the freshness heuristic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenMarket {
+    /// ERG held by pools trading the token.
+    pub liquidity_nano: u64,
+    /// Blocks since the token was minted, when known.
+    pub age_blocks: Option<u64>,
+}
+
+impl TokenMarket {
+    fn is_fresh_and_thin(&self) -> bool {
+        self.liquidity_nano < LOW_LIQUIDITY_NANO
+            && self.age_blocks.is_some_and(|age| age < FRESH_TOKEN_BLOCKS)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenRegistry {
+    list_version: u32,
+    tokens: HashMap<String, VerifiedToken>,
+    /// Normalized name -> (token ID, display name) of verified tokens.
+    names: HashMap<String, (String, String)>,
+    blocked: HashSet<String>,
+}
+
+impl Default for TokenRegistry {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+impl TokenRegistry {
+    pub fn bundled() -> Self {
+        Self::with_overrides(&TokenOverrides::default())
+    }
+
+    pub fn with_overrides(overrides: &TokenOverrides) -> Self {
+        let bundled = TokenList::bundled_ref();
+        let list = match &overrides.list {
+            Some(list) if list.version > bundled.version => list,
+            _ => bundled,
+        };
+
+        let mut names = HashMap::new();
+        names.insert(
+            normalize_token_name(ERG_TOKEN_ID),
+            (ERG_TOKEN_ID.to_string(), ERG_TOKEN_ID.to_string()),
+        );
+        let mut tokens = HashMap::new();
+        for token in list.tokens.iter().chain(&overrides.verified).cloned() {
+            names
+                .entry(normalize_token_name(&token.name))
+                .or_insert_with(|| (token.token_id.clone(), token.name.clone()));
+            tokens.insert(token.token_id.clone(), token);
+        }
+
+        Self {
+            list_version: list.version,
+            tokens,
+            names,
+            blocked: overrides.blocked.iter().cloned().collect(),
+        }
+    }
+
+    pub fn list_version(&self) -> u32 {
+        self.list_version
+    }
+
+    pub fn get(&self, token_id: &str) -> Option<&VerifiedToken> {
+        self.tokens.get(token_id)
+    }
+
+    pub fn is_verified(&self, token_id: &str) -> bool {
+        token_id == ERG_TOKEN_ID
+            || (self.tokens.contains_key(token_id) && !self.blocked.contains(token_id))
+    }
+
+    /// Verified tokens, sorted by name.
+    pub fn tokens(&self) -> Vec<&VerifiedToken> {
+        let mut tokens: Vec<&VerifiedToken> = self.tokens.values().collect();
+        tokens.sort_by(|a, b| a.name.cmp(&b.name));
+        tokens
+    }
+
+    /// Verification status of a token. `name` is the on-chain name and
+    /// `market` feeds the freshness check; both are optional.
+    pub fn assess(
+        &self,
+        token_id: &str,
+        name: Option<&str>,
+        market: Option<&TokenMarket>,
+    ) -> TokenStatus {
+        if self.blocked.contains(token_id) {
+            return TokenStatus {
+                verified: false,
+                warning: Some(TokenWarning::Blocked),
+            };
+        }
+        if self.is_verified(token_id) {
+            return TokenStatus {
+                verified: true,
+                warning: None,
+            };
+        }
+
+        if let Some((verified_id, verified_name)) = name
+            .map(normalize_token_name)
+            .and_then(|n| self.names.get(&n))
+        {
+            return TokenStatus {
+                verified: false,
+                warning: Some(TokenWarning::NameCollision {
+                    verified_token_id: verified_id.clone(),
+                    verified_name: verified_name.clone(),
+                }),
+            };
+        }
+
+        let warning =
+            market
+                .filter(|m| m.is_fresh_and_thin())
+                .map(|m| TokenWarning::FreshLowLiquidity {
+                    age_blocks: m.age_blocks.unwrap_or(0),
+                    liquidity_nano: m.liquidity_nano,
+                });
+        TokenStatus {
+            verified: false,
+            warning,
+        }
+    }
+}
+
+/// Lowercase, fold look-alike characters and drop everything that is not a
+/// letter or digit, so "$igUSD", "SIG-USD" and "SіgUSD" (Cyrillic i) all
+/// compare equal to "SigUSD".
+pub fn normalize_token_name(name: &str) -> String {
+    name.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| match c {
+            '0' | 'о' | 'ο' => 'o',
+            '1' | 'i' | 'l' | '|' | 'і' | 'ı' => 'l',
+            '$' | '5' | 'ѕ' => 's',
+            'а' | 'α' => 'a',
+            'е' | 'ε' => 'e',
+            'с' => 'c',
+            'р' | 'ρ' => 'p',
+            'у' => 'y',
+            'х' => 'x',
+            'ν' => 'v',
+            _ => c,
+        })
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGUSD: &str = "03faf2cb329f2e90d6d23b58d91bbb6c046aa143261cc21f52fbe2824bfcbf04";
+
+    #[test]
+    fn test_bundled_list_parses() {
+        let registry = TokenRegistry::bundled();
+        assert!(registry.list_version() >= 1);
+        assert!(registry.is_verified(SIGUSD));
+        assert!(registry.is_verified("ERG"));
+        for token in registry.tokens() {
+            assert_eq!(token.token_id.len(), 64, "{}", token.name);
+        }
+    }
+
+    #[test]
+    fn test_impersonator_name_flagged() {
+        let registry = TokenRegistry::bundled();
+        let fake = "ff".repeat(32);
+        for name in ["SigUSD", "$igUSD", "SIG-USD", "SіgUSD", "Erg"] {
+            let status = registry.assess(&fake, Some(name), None);
+            assert!(!status.verified);
+            assert!(
+                matches!(status.warning, Some(TokenWarning::NameCollision { .. })),
+                "{} not flagged",
+                name
+            );
+        }
+        let status = registry.assess(&fake, Some("Comet"), None);
+        assert_eq!(status, TokenStatus::default());
+    }
+
+    #[test]
+    fn test_fresh_low_liquidity_flagged() {
+        let registry = TokenRegistry::bundled();
+        let token = "ee".repeat(32);
+        let fresh = TokenMarket {
+            liquidity_nano: 5_000_000_000,
+            age_blocks: Some(100),
+        };
+        let status = registry.assess(&token, Some("Moon"), Some(&fresh));
+        assert_eq!(
+            status.warning,
+            Some(TokenWarning::FreshLowLiquidity {
+                age_blocks: 100,
+                liquidity_nano: 5_000_000_000,
+            })
+        );
+
+        let deep = TokenMarket {
+            liquidity_nano: LOW_LIQUIDITY_NANO,
+            ..fresh
+        };
+        assert!(registry
+            .assess(&token, Some("Moon"), Some(&deep))
+            .warning
+            .is_none());
+        let unknown_age = TokenMarket {
+            age_blocks: None,
+            ..fresh
+        };
+        assert!(registry
+            .assess(&token, Some("Moon"), Some(&unknown_age))
+            .warning
+            .is_none());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mine = VerifiedToken {
+            token_id: "aa".repeat(32),
+            name: "Mine".to_string(),
+            decimals: 0,
+            project: None,
+            logo: None,
+            logo_sha256: None,
+        };
+        let overrides = TokenOverrides {
+            list: Some(TokenList {
+                version: 0,
+                tokens: Vec::new(),
+            }),
+            verified: vec![mine.clone()],
+            blocked: vec![SIGUSD.to_string()],
+        };
+        let registry = TokenRegistry::with_overrides(&overrides);
+
+        // An older list does not replace the bundled one
+        assert!(registry.list_version() >= 1);
+        assert!(registry.assess(&mine.token_id, Some("Mine"), None).verified);
+        let blocked = registry.assess(SIGUSD, Some("SigUSD"), None);
+        assert!(!blocked.verified);
+        assert_eq!(blocked.warning, Some(TokenWarning::Blocked));
+    }
+}
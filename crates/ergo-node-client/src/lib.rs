@@ -1,4 +1,5 @@
 pub mod capabilities;
+pub mod mempool;
 
 use std::sync::Arc;
 
@@ -21,12 +22,15 @@ const MAX_WALLET_PAGES: u32 = 100;
 const MAX_UTXO_PAGES: u32 = 100;
 
 pub use capabilities::{CapabilityTier, NodeCapabilities};
+pub use mempool::{MempoolIndex, MempoolSpends, PendingSuccessor, SpentInput};
 
 #[derive(Debug, Clone)]
 pub struct TokenInfo {
     pub name: Option<String>,
     pub decimals: Option<u32>,
     pub emission_amount: Option<i64>,
+    /// Box the token was minted in.
+    pub box_id: Option<String>,
 }
 
 pub type Result<T> = std::result::Result<T, NodeError>;
@@ -36,6 +40,8 @@ pub struct NodeClient {
     inner: Arc<NodeInterface>,
     capabilities: Arc<RwLock<Option<NodeCapabilities>>>,
     config: NodeConfig,
+    /// Resolve singleton token lookups to their latest unconfirmed successor.
+    follow_mempool: bool,
 }
 
 impl NodeClient {
@@ -50,6 +56,7 @@ impl NodeClient {
             inner: Arc::new(node),
             capabilities: Arc::new(RwLock::new(None)),
             config,
+            follow_mempool: false,
         };
 
         client.refresh_capabilities().await;
@@ -65,6 +72,20 @@ impl NodeClient {
         &self.config
     }
 
+    /// Client whose [`Self::get_box_by_token_id`] returns the latest unconfirmed
+    /// successor of the box, so protocol txs can chain on pending bank/LP spends
+    /// instead of racing for the confirmed box.
+    pub fn following_mempool(&self) -> Self {
+        Self {
+            follow_mempool: true,
+            ..self.clone()
+        }
+    }
+
+    pub fn is_following_mempool(&self) -> bool {
+        self.follow_mempool
+    }
+
     pub async fn refresh_capabilities(&self) {
         let caps = capabilities::detect_capabilities(&self.inner).await;
         let mut lock = self.capabilities.write().await;
@@ -226,6 +247,82 @@ impl NodeClient {
         Ok(boxes)
     }
 
+    /// Unspent boxes whose ErgoTree template hashes to `template_hash`
+    /// (extraIndex). Finds every deployment of a contract whose constants
+    /// differ between deployments.
+    pub async fn unspent_boxes_by_template_hash(
+        &self,
+        template_hash: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<ergo_lib::ergotree_ir::chain::ergo_box::ErgoBox>> {
+        let items = self
+            .unspent_json_by_template_hash(template_hash, offset, limit)
+            .await?;
+        let mut boxes = Vec::with_capacity(items.len());
+        for item in items {
+            match serde_json::from_value::<ergo_lib::ergotree_ir::chain::ergo_box::ErgoBox>(item) {
+                Ok(b) => boxes.push(b),
+                Err(e) => tracing::debug!("Skipping unparseable byTemplateHash box: {}", e),
+            }
+        }
+        Ok(boxes)
+    }
+
+    /// Unspent boxes whose ErgoTree template hashes to `template_hash`
+    /// (extraIndex), as EIP-12 inputs. Finds contract boxes whose constants
+    /// differ per box, e.g. every open order of one kind.
+    pub async fn unspent_eip12_boxes_by_template_hash(
+        &self,
+        template_hash: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<ergo_tx::Eip12InputBox>> {
+        let items = self
+            .unspent_json_by_template_hash(template_hash, offset, limit)
+            .await?;
+        Ok(items.iter().filter_map(json_box_to_eip12).collect())
+    }
+
+    /// Internal: one page of unspent byTemplateHash boxes as raw JSON. An
+    /// unknown template (404) is an empty page.
+    async fn unspent_json_by_template_hash(
+        &self,
+        template_hash: &str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<serde_json::Value>> {
+        let endpoint = format!(
+            "/blockchain/box/unspent/byTemplateHash/{}?offset={}&limit={}",
+            template_hash, offset, limit
+        );
+        let response = timed_request(self.inner.send_get_req(&endpoint)).await?;
+        let status = response.status();
+        let text = response.text().await.map_err(|e| NodeError::ApiError {
+            message: format!("Failed to read byTemplateHash response: {}", e),
+        })?;
+        if status.as_u16() == 404 || text.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !status.is_success() {
+            return Err(NodeError::ApiError {
+                message: format!(
+                    "byTemplateHash failed ({}): {}",
+                    status.as_u16(),
+                    text.chars().take(200).collect::<String>()
+                ),
+            });
+        }
+        let value: serde_json::Value =
+            serde_json::from_str(&text).map_err(|e| NodeError::ApiError {
+                message: format!("Failed to parse byTemplateHash response: {}", e),
+            })?;
+        Ok(json_array_items(value)
+            .into_iter()
+            .filter(|item| item["spentTransactionId"].is_null())
+            .collect())
+    }
+
     /// Internal: paginated fetch of all unspent boxes at an address, capped by `max_pages`.
     async fn fetch_all_unspent_by_address(
         &self,
@@ -319,6 +416,7 @@ impl NodeClient {
             name: json["name"].as_str().map(|s| s.to_string()),
             decimals: json["decimals"].as_u64().map(|d| d as u32),
             emission_amount: json["emissionAmount"].as_i64(),
+            box_id: json["boxId"].as_str().map(|s| s.to_string()),
         })
     }
 
@@ -554,16 +652,100 @@ impl NodeClient {
             .get_boxes_by_token_id(capabilities, token_id, 1)
             .await?;
 
-        boxes
+        let confirmed = boxes
             .into_iter()
             .next()
             .ok_or_else(|| NodeError::BoxNotFound {
                 box_id: format!("box with token {}", token_id),
-            })
+            })?;
+
+        if !self.follow_mempool {
+            return Ok(confirmed);
+        }
+
+        let box_id = confirmed.box_id().to_string();
+        match self.pending_successor(&box_id, token_id.as_str()).await {
+            Ok(Some(successor)) => {
+                tracing::debug!(
+                    token_id = %token_id,
+                    box_id = %successor.box_id,
+                    pending = successor.pending_tx_ids.len(),
+                    "Using unconfirmed singleton successor"
+                );
+                Ok(successor.ergo_box)
+            }
+            Ok(None) => Ok(confirmed),
+            Err(e) => {
+                tracing::warn!("Mempool query failed, using confirmed box: {}", e);
+                Ok(confirmed)
+            }
+        }
+    }
+
+    /// Latest unconfirmed successor of the singleton `token_id` box `box_id`,
+    /// or `None` when no pending transaction spends it.
+    pub async fn pending_successor(
+        &self,
+        box_id: &str,
+        token_id: &str,
+    ) -> Result<Option<PendingSuccessor>> {
+        let mempool_txs = self.get_mempool_transactions().await?;
+        Ok(mempool::follow_singleton(box_id, token_id, &mempool_txs))
+    }
+
+    /// Inputs of a built (not yet submitted) transaction that were spent by
+    /// another transaction, in the mempool or on chain.
+    ///
+    /// A mempool tx spending every input is reported as `own_tx_id`, and inputs
+    /// that are all gone from the UTXO set are taken as our own confirmed tx;
+    /// neither counts as competing.
+    pub async fn check_inputs_unspent(&self, box_ids: &[String]) -> Result<MempoolSpends> {
+        let mempool_txs = self.get_mempool_transactions().await?;
+        let mut spends = mempool::mempool_spends(box_ids, &mempool_txs);
+        if spends.own_tx_id.is_some() {
+            return Ok(spends);
+        }
+
+        let mut confirmed_spent = Vec::new();
+        for box_id in box_ids {
+            if spends.competing.iter().any(|s| &s.box_id == box_id) {
+                continue;
+            }
+            let id = citadel_core::BoxId::new(box_id.clone());
+            match self.get_box_by_id(&id).await {
+                Ok(_) => {}
+                Err(NodeError::BoxNotFound { .. }) => confirmed_spent.push(SpentInput {
+                    box_id: box_id.clone(),
+                    spent_by: None,
+                }),
+                Err(e) => return Err(e),
+            }
+        }
+
+        if confirmed_spent.len() < box_ids.len() {
+            spends.competing.extend(confirmed_spent);
+        }
+        Ok(spends)
     }
 
     /// Returns (transactionId, output index) for EIP-12 input construction.
+    ///
+    /// The blockchain index only knows confirmed boxes, so unconfirmed ones
+    /// (e.g. a pending singleton successor) fall back to the mempool-aware
+    /// lookup, whose box carries its creating tx context.
     pub async fn get_box_creation_info(&self, box_id: &str) -> Result<(String, u16)> {
+        match self.indexed_box_creation_info(box_id).await {
+            Ok(info) => Ok(info),
+            Err(indexed_err) => {
+                match timed_request(self.inner.box_from_id_with_pool(box_id)).await {
+                    Ok(ergo_box) => Ok((String::from(ergo_box.transaction_id), ergo_box.index)),
+                    Err(_) => Err(indexed_err),
+                }
+            }
+        }
+    }
+
+    async fn indexed_box_creation_info(&self, box_id: &str) -> Result<(String, u16)> {
         let endpoint = format!("/blockchain/box/byId/{}", box_id);
         let response = timed_request(self.inner.send_get_req(&endpoint)).await?;
 
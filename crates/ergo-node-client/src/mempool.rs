@@ -0,0 +1,347 @@
+//! Singleton-box contention: following protocol boxes through the mempool.
+//!
+//! Bank, LP and oracle boxes are singletons identified by an NFT. Every action
+//! spends the box and recreates it with the same NFT, so concurrent users race
+//! for one input. Following unconfirmed spends from the confirmed box gives the
+//! successor a new transaction can chain on, and the same spend index tells a
+//! built transaction whether one of its inputs was taken by someone else.
+
+use std::collections::HashMap;
+
+use ergo_lib::ergotree_ir::chain::ergo_box::ErgoBox;
+use serde::Serialize;
+
+/// Latest unconfirmed successor of a singleton box.
+#[derive(Debug, Clone)]
+pub struct PendingSuccessor {
+    pub box_id: String,
+    pub tx_id: String,
+    pub index: u16,
+    /// Unconfirmed transactions between the confirmed box and this one, oldest first.
+    pub pending_tx_ids: Vec<String>,
+    pub ergo_box: ErgoBox,
+}
+
+/// An input of a built transaction that another transaction already spends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SpentInput {
+    pub box_id: String,
+    /// Spending mempool transaction; `None` when the spend is already confirmed.
+    pub spent_by: Option<String>,
+}
+
+/// Mempool spends of a built transaction's inputs, split into our own
+/// submission and competitors.
+#[derive(Debug, Clone, Default)]
+pub struct MempoolSpends {
+    /// A mempool tx spending every input: the signed version of our transaction.
+    pub own_tx_id: Option<String>,
+    pub competing: Vec<SpentInput>,
+}
+
+/// Input box ID -> spending mempool tx.
+fn spenders_index(mempool_txs: &[serde_json::Value]) -> HashMap<&str, &serde_json::Value> {
+    let mut spenders = HashMap::new();
+    for tx in mempool_txs {
+        for input in tx["inputs"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+            if let Some(box_id) = input["boxId"].as_str() {
+                spenders.insert(box_id, tx);
+            }
+        }
+    }
+    spenders
+}
+
+fn carries_token(output: &serde_json::Value, token_id: &str) -> bool {
+    output["assets"].as_array().is_some_and(|assets| {
+        assets
+            .iter()
+            .any(|a| a["tokenId"].as_str() == Some(token_id))
+    })
+}
+
+/// Follows `token_id` from `box_id` through the `spenders` index, stopping at
+/// the first hop whose token-carrying output `accept` rejects. Returns the
+/// pending tx ids, oldest first, and the latest output with its tx id and index.
+fn follow_token_chain<'a>(
+    box_id: &str,
+    token_id: &str,
+    spenders: &HashMap<&str, &'a serde_json::Value>,
+    accept: impl Fn(&serde_json::Value) -> bool,
+) -> (Vec<String>, Option<(&'a serde_json::Value, String, u16)>) {
+    let mut tx_ids = Vec::new();
+    let mut latest = None;
+    let mut current = box_id.to_string();
+
+    while let Some(tx) = spenders.get(current.as_str()) {
+        let Some(tx_id) = tx["id"].as_str() else {
+            break;
+        };
+        let outputs = tx["outputs"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+        let Some((index, output)) = outputs
+            .iter()
+            .enumerate()
+            .find(|(_, o)| carries_token(o, token_id))
+        else {
+            break;
+        };
+        if !accept(output) {
+            break;
+        }
+        let Some(next_box_id) = output["boxId"].as_str() else {
+            break;
+        };
+        // Box IDs are unique, so a repeat means malformed input
+        if tx_ids.iter().any(|id| id == tx_id) {
+            break;
+        }
+        tx_ids.push(tx_id.to_string());
+        latest = Some((output, tx_id.to_string(), index as u16));
+        current = next_box_id.to_string();
+    }
+
+    (tx_ids, latest)
+}
+
+/// Mempool transactions indexed by the boxes they spend, for following many
+/// singletons through one mempool snapshot.
+pub struct MempoolIndex<'a> {
+    spenders: HashMap<&'a str, &'a serde_json::Value>,
+}
+
+impl<'a> MempoolIndex<'a> {
+    pub fn new(mempool_txs: &'a [serde_json::Value]) -> Self {
+        Self {
+            spenders: spenders_index(mempool_txs),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spenders.is_empty()
+    }
+
+    /// Latest unconfirmed successor of the singleton `token_id` box `box_id`.
+    ///
+    /// Returns `None` when the box has no pending spend that recreates the
+    /// token, or when the tip output fails to parse.
+    pub fn follow_singleton(&self, box_id: &str, token_id: &str) -> Option<PendingSuccessor> {
+        self.follow_singleton_where(box_id, token_id, |_| true)
+    }
+
+    /// [`follow_singleton`](Self::follow_singleton) that only crosses outputs
+    /// `accept` approves, e.g. ones still guarded by the protocol contract.
+    /// The walk stops before the first rejected hop.
+    pub fn follow_singleton_where(
+        &self,
+        box_id: &str,
+        token_id: &str,
+        accept: impl Fn(&serde_json::Value) -> bool,
+    ) -> Option<PendingSuccessor> {
+        let (pending_tx_ids, latest) = follow_token_chain(box_id, token_id, &self.spenders, accept);
+        let (output, tx_id, index) = latest?;
+
+        // Mempool outputs may omit the creating tx context that ErgoBox requires
+        let mut json = output.clone();
+        json["transactionId"] = serde_json::json!(tx_id);
+        json["index"] = serde_json::json!(index);
+        let ergo_box: ErgoBox = match serde_json::from_value(json) {
+            Ok(b) => b,
+            Err(e) => {
+                tracing::warn!(token_id, error = %e, "Pending singleton box failed to parse");
+                return None;
+            }
+        };
+
+        Some(PendingSuccessor {
+            box_id: output["boxId"].as_str()?.to_string(),
+            tx_id,
+            index,
+            pending_tx_ids,
+            ergo_box,
+        })
+    }
+}
+
+/// Latest unconfirmed successor of the singleton `token_id` box `box_id`.
+/// See [`MempoolIndex::follow_singleton`].
+pub fn follow_singleton(
+    box_id: &str,
+    token_id: &str,
+    mempool_txs: &[serde_json::Value],
+) -> Option<PendingSuccessor> {
+    MempoolIndex::new(mempool_txs).follow_singleton(box_id, token_id)
+}
+
+/// Finds mempool transactions spending any of `input_box_ids`.
+///
+/// A transaction that spends every input is our own signed submission rather
+/// than a competitor: a competing action shares the protocol box but never
+/// the user's wallet inputs.
+pub fn mempool_spends(
+    input_box_ids: &[String],
+    mempool_txs: &[serde_json::Value],
+) -> MempoolSpends {
+    let spenders = spenders_index(mempool_txs);
+    let spent: Vec<(&String, &str)> = input_box_ids
+        .iter()
+        .filter_map(|id| {
+            spenders
+                .get(id.as_str())
+                .and_then(|tx| tx["id"].as_str())
+                .map(|tx_id| (id, tx_id))
+        })
+        .collect();
+
+    let own_tx_id = spent
+        .first()
+        .map(|(_, tx_id)| *tx_id)
+        .filter(|tx_id| {
+            spent.len() == input_box_ids.len() && spent.iter().all(|(_, id)| id == tx_id)
+        })
+        .map(str::to_string);
+
+    let competing = spent
+        .into_iter()
+        .filter(|(_, tx_id)| own_tx_id.as_deref() != Some(*tx_id))
+        .map(|(box_id, tx_id)| SpentInput {
+            box_id: box_id.clone(),
+            spent_by: Some(tx_id.to_string()),
+        })
+        .collect();
+
+    MempoolSpends {
+        own_tx_id,
+        competing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NFT: &str = "0cd8c9f416e5b1ca9f986a7f10a84191dfb85941619e49e53c0dc30ebf83324b";
+    const TREE: &str = "0008cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn box_id(n: u8) -> String {
+        format!("{:02x}", n).repeat(32)
+    }
+
+    fn output(id: &str, token: Option<&str>) -> serde_json::Value {
+        let assets = match token {
+            Some(t) => serde_json::json!([{ "tokenId": t, "amount": 1 }]),
+            None => serde_json::json!([]),
+        };
+        serde_json::json!({
+            "boxId": id,
+            "value": 1_000_000_000u64,
+            "ergoTree": TREE,
+            "assets": assets,
+            "creationHeight": 1_000_000,
+            "additionalRegisters": {},
+        })
+    }
+
+    fn tx(id: u8, inputs: &[String], outputs: Vec<serde_json::Value>) -> serde_json::Value {
+        let inputs: Vec<_> = inputs
+            .iter()
+            .map(|b| serde_json::json!({ "boxId": b }))
+            .collect();
+        serde_json::json!({ "id": box_id(id), "inputs": inputs, "outputs": outputs })
+    }
+
+    #[test]
+    fn test_follow_singleton_no_pending_spend() {
+        let mempool = vec![tx(0xa0, &[box_id(9)], vec![output(&box_id(10), None)])];
+        let spenders = spenders_index(&mempool);
+        let (tx_ids, latest) = follow_token_chain(&box_id(1), NFT, &spenders, |_| true);
+        assert!(tx_ids.is_empty());
+        assert!(latest.is_none());
+        assert!(follow_singleton(&box_id(1), NFT, &mempool).is_none());
+    }
+
+    #[test]
+    fn test_follow_token_chain_walks_to_tip() {
+        // Out of order on purpose: the walk follows spends, not mempool order
+        let mempool = vec![
+            tx(
+                0xa2,
+                &[box_id(2), box_id(20)],
+                vec![output(&box_id(3), Some(NFT)), output(&box_id(21), None)],
+            ),
+            tx(
+                0xa1,
+                &[box_id(1)],
+                vec![output(&box_id(11), None), output(&box_id(2), Some(NFT))],
+            ),
+        ];
+
+        let spenders = spenders_index(&mempool);
+        let (tx_ids, latest) = follow_token_chain(&box_id(1), NFT, &spenders, |_| true);
+        let (output, tx_id, index) = latest.expect("successor");
+        assert_eq!(tx_ids, vec![box_id(0xa1), box_id(0xa2)]);
+        assert_eq!(output["boxId"].as_str(), Some(box_id(3).as_str()));
+        assert_eq!(tx_id, box_id(0xa2));
+        assert_eq!(index, 0);
+    }
+
+    #[test]
+    fn test_follow_token_chain_stops_when_token_leaves() {
+        let mempool = vec![
+            tx(0xa1, &[box_id(1)], vec![output(&box_id(2), Some(NFT))]),
+            tx(0xa2, &[box_id(2)], vec![output(&box_id(3), None)]),
+        ];
+
+        let spenders = spenders_index(&mempool);
+        let (tx_ids, latest) = follow_token_chain(&box_id(1), NFT, &spenders, |_| true);
+        let (output, _, _) = latest.expect("successor");
+        assert_eq!(tx_ids.len(), 1);
+        assert_eq!(output["boxId"].as_str(), Some(box_id(2).as_str()));
+    }
+
+    #[test]
+    fn test_follow_token_chain_stops_at_rejected_hop() {
+        let mempool = vec![
+            tx(0xa1, &[box_id(1)], vec![output(&box_id(2), Some(NFT))]),
+            tx(0xa2, &[box_id(2)], vec![output(&box_id(3), Some(NFT))]),
+        ];
+
+        let spenders = spenders_index(&mempool);
+        let rejected = box_id(3);
+        let (tx_ids, latest) = follow_token_chain(&box_id(1), NFT, &spenders, |o| {
+            o["boxId"].as_str() != Some(rejected.as_str())
+        });
+        let (output, _, _) = latest.expect("successor");
+        assert_eq!(tx_ids, vec![box_id(0xa1)]);
+        assert_eq!(output["boxId"].as_str(), Some(box_id(2).as_str()));
+    }
+
+    #[test]
+    fn test_mempool_spends_own_submission() {
+        let inputs = vec![box_id(1), box_id(5)];
+        let mempool = vec![tx(0xa1, &inputs, vec![output(&box_id(2), Some(NFT))])];
+
+        let spends = mempool_spends(&inputs, &mempool);
+        assert_eq!(spends.own_tx_id, Some(box_id(0xa1)));
+        assert!(spends.competing.is_empty());
+    }
+
+    #[test]
+    fn test_mempool_spends_competing_bank_spend() {
+        let inputs = vec![box_id(1), box_id(5)];
+        let mempool = vec![tx(
+            0xb1,
+            &[box_id(1), box_id(7)],
+            vec![output(&box_id(2), Some(NFT))],
+        )];
+
+        let spends = mempool_spends(&inputs, &mempool);
+        assert_eq!(spends.own_tx_id, None);
+        assert_eq!(
+            spends.competing,
+            vec![SpentInput {
+                box_id: box_id(1),
+                spent_by: Some(box_id(0xb1)),
+            }]
+        );
+    }
+}
@@ -40,12 +40,61 @@ pub fn ergo_tree_to_address(ergo_tree_hex: &str) -> Result<String, AddressError>
     Ok(encoder.address_to_str(&address))
 }
 
+/// Length of a BIP-32 serialized extended key (without the base58 checksum).
+const XPUB_LEN: usize = 78;
+
+/// Derive mainnet P2PK addresses `start..start + count` from a BIP-32 `xpub`.
+///
+/// The xpub is expected at the EIP-3 change level (`m/44'/429'/account'/0`),
+/// so each address is a direct non-hardened child, matching the addresses
+/// Ergo wallets hand out.
+pub fn derive_xpub_addresses(
+    xpub: &str,
+    start: u32,
+    count: u32,
+) -> Result<Vec<String>, AddressError> {
+    use ergo_lib::ergotree_ir::chain::address::{Address, AddressEncoder, NetworkPrefix};
+    use ergo_lib::wallet::derivation_path::{ChildIndexNormal, DerivationPath};
+    use ergo_lib::wallet::ext_pub_key::ExtPubKey;
+
+    let bytes = bs58::decode(xpub.trim())
+        .with_check(None)
+        .into_vec()
+        .map_err(|e| AddressError::InvalidXpub(format!("Invalid base58: {}", e)))?;
+    if bytes.len() != XPUB_LEN {
+        return Err(AddressError::InvalidXpub(format!(
+            "Expected {} bytes, got {}",
+            XPUB_LEN,
+            bytes.len()
+        )));
+    }
+
+    // version(4) | depth(1) | parent fingerprint(4) | child number(4) | chain code(32) | key(33)
+    let chain_code: [u8; 32] = bytes[13..45].try_into().expect("slice length checked");
+    let public_key: [u8; 33] = bytes[45..78].try_into().expect("slice length checked");
+
+    let ext_pub_key = ExtPubKey::new(public_key, chain_code, DerivationPath::master_path())
+        .map_err(|e| AddressError::InvalidXpub(format!("Invalid public key: {}", e)))?;
+
+    let encoder = AddressEncoder::new(NetworkPrefix::Mainnet);
+    (start..start.saturating_add(count))
+        .map(|index| {
+            let child_index = ChildIndexNormal::normal(index)
+                .map_err(|e| AddressError::InvalidXpub(format!("Index {}: {}", index, e)))?;
+            let address: Address = ext_pub_key.child(child_index).into();
+            Ok(encoder.address_to_str(&address))
+        })
+        .collect()
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum AddressError {
     #[error("Invalid Ergo address: {0}")]
     InvalidAddress(String),
     #[error("Invalid ErgoTree: {0}")]
     InvalidErgoTree(String),
+    #[error("Invalid extended public key: {0}")]
+    InvalidXpub(String),
 }
 
 #[cfg(test)]
@@ -66,4 +115,35 @@ mod tests {
         let result = address_to_ergo_tree("not_an_address");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_derive_xpub_addresses_known_answer() {
+        // BIP-32 test vector 1, chain m/0H. Its non-hardened child 1 is the
+        // published m/0H/1 key 03501e45...711c, encoded here as a P2PK address.
+        let xpub = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+        let addresses = derive_xpub_addresses(xpub, 1, 1).unwrap();
+        assert_eq!(
+            addresses,
+            vec!["9h56Cp6eZYDkFBGc115Tq9wvVQ7k44bEKde9uhiJicf7M7GE9Qc"]
+        );
+        assert_eq!(
+            address_to_ergo_tree(&addresses[0]).unwrap(),
+            "0008cd03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c"
+        );
+
+        let range = derive_xpub_addresses(xpub, 0, 2).unwrap();
+        assert_eq!(range[1], addresses[0]);
+        assert_eq!(
+            range[0],
+            "9gqagZjHL3kWQPQ2SkQEiQM4qMRgtvTkRWsdxqHQLsUpHsGqiaK"
+        );
+    }
+
+    #[test]
+    fn test_invalid_xpub() {
+        assert!(derive_xpub_addresses("not_an_xpub", 0, 5).is_err());
+        // Valid base58 but not an extended key (Ergo addresses use a blake2b checksum)
+        let addr = "9hY16vzHmmfyVBwKeFGHvb2bMFsG94A1u7To1QWtUokACyFVENQ";
+        assert!(derive_xpub_addresses(addr, 0, 5).is_err());
+    }
 }
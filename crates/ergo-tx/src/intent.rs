@@ -0,0 +1,205 @@
+//! Transaction intents for wallet-level builders, plus a generic fallback that
+//! derives one from any unsigned transaction.
+
+use std::collections::{BTreeMap, HashSet};
+
+use citadel_core::constants::MINER_FEE_ERGO_TREE;
+use citadel_core::{IntentAsset, TxIntent};
+
+use crate::burn::{BurnSummary, MultiBurnSummary};
+use crate::dev_fee::DEFAULT_DEV_FEE_ERGO_TREE;
+use crate::eip12::{Eip12Asset, Eip12UnsignedTx};
+use crate::send::SendSummary;
+use crate::utxo_management::{ConsolidateSummary, RestructureSummary, SplitSummary};
+
+const P2PK_TREE_PREFIX: &str = "0008cd";
+
+/// Derive an intent from the transaction itself, for builders without a
+/// summary. The user is whoever owns the P2PK inputs; fees are recognised by
+/// the miner-fee and Citadel-fee trees. Counterparties are left to the caller.
+pub fn derive_intent(
+    tx: &Eip12UnsignedTx,
+    protocol: impl Into<String>,
+    action: impl Into<String>,
+) -> TxIntent {
+    let user_trees: HashSet<&str> = tx
+        .inputs
+        .iter()
+        .map(|i| i.ergo_tree.as_str())
+        .filter(|t| t.starts_with(P2PK_TREE_PREFIX) && *t != DEFAULT_DEV_FEE_ERGO_TREE)
+        .collect();
+
+    let mut erg_delta: i128 = 0;
+    let mut token_delta: BTreeMap<String, i128> = BTreeMap::new();
+    let mut add_assets = |assets: &[Eip12Asset], sign: i128| {
+        for a in assets {
+            let amount: i128 = a.amount.parse().unwrap_or(0);
+            *token_delta.entry(a.token_id.clone()).or_default() += sign * amount;
+        }
+    };
+    for input in tx
+        .inputs
+        .iter()
+        .filter(|i| user_trees.contains(i.ergo_tree.as_str()))
+    {
+        erg_delta += input.value.parse::<i128>().unwrap_or(0);
+        add_assets(&input.assets, 1);
+    }
+
+    let (mut miner_fee, mut citadel_fee) = (0u64, 0u64);
+    for output in &tx.outputs {
+        let value: u64 = output.value.parse().unwrap_or(0);
+        if user_trees.contains(output.ergo_tree.as_str()) {
+            erg_delta -= value as i128;
+            add_assets(&output.assets, -1);
+        } else if output.ergo_tree == MINER_FEE_ERGO_TREE {
+            miner_fee += value;
+        } else if output.ergo_tree == DEFAULT_DEV_FEE_ERGO_TREE {
+            citadel_fee += value;
+        }
+    }
+    erg_delta -= (miner_fee + citadel_fee) as i128;
+
+    let mut intent = TxIntent::new(protocol, action)
+        .miner_fee(miner_fee)
+        .citadel_fee(citadel_fee);
+    if erg_delta > 0 {
+        intent = intent.pays(IntentAsset::erg(erg_delta as u64));
+    } else {
+        intent = intent.receives(IntentAsset::erg(erg_delta.unsigned_abs() as u64));
+    }
+    for (token_id, delta) in token_delta {
+        let asset = IntentAsset::token(token_id, delta.unsigned_abs() as u64);
+        intent = if delta > 0 {
+            intent.pays(asset)
+        } else {
+            intent.receives(asset)
+        };
+    }
+    intent
+}
+
+impl SendSummary {
+    pub fn intent(&self) -> TxIntent {
+        let mut intent = TxIntent::new("Wallet", "Send")
+            .pays(IntentAsset::erg(self.recipient_erg.max(0) as u64))
+            .miner_fee(self.miner_fee.max(0) as u64)
+            .citadel_fee(self.citadel_fee_nano.max(0) as u64);
+        if let (Some(token_id), Some(amount)) = (&self.token_id, self.token_amount) {
+            intent = intent.pays(IntentAsset::token(token_id, amount));
+        }
+        intent
+    }
+}
+
+impl BurnSummary {
+    pub fn intent(&self) -> TxIntent {
+        TxIntent::new("Wallet", "Burn token")
+            .pays(IntentAsset::token(
+                &self.burned_token_id,
+                self.burned_amount,
+            ))
+            .miner_fee(self.miner_fee.max(0) as u64)
+            .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+            .note("Burned tokens are destroyed permanently")
+    }
+}
+
+impl MultiBurnSummary {
+    pub fn intent(&self) -> TxIntent {
+        let mut intent = TxIntent::new(
+            "Wallet",
+            format!("Burn {} tokens", self.burned_tokens.len()),
+        )
+        .miner_fee(self.miner_fee.max(0) as u64)
+        .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+        .note("Burned tokens are destroyed permanently");
+        for item in &self.burned_tokens {
+            intent = intent.pays(IntentAsset::token(&item.token_id, item.amount));
+        }
+        intent
+    }
+}
+
+impl ConsolidateSummary {
+    pub fn intent(&self) -> TxIntent {
+        TxIntent::new("Wallet", format!("Consolidate {} boxes", self.input_count))
+            .miner_fee(self.miner_fee.max(0) as u64)
+            .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+    }
+}
+
+impl SplitSummary {
+    pub fn intent(&self) -> TxIntent {
+        TxIntent::new(
+            "Wallet",
+            format!(
+                "Split into {} boxes of {}",
+                self.split_count, self.amount_per_box
+            ),
+        )
+        .miner_fee(self.miner_fee.max(0) as u64)
+        .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+    }
+}
+
+impl RestructureSummary {
+    pub fn intent(&self) -> TxIntent {
+        TxIntent::new(
+            "Wallet",
+            format!(
+                "Restructure {} boxes into {}",
+                self.input_count, self.output_count
+            ),
+        )
+        .miner_fee(self.miner_fee.max(0) as u64)
+        .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip12::{Eip12InputBox, Eip12Output};
+    use std::collections::HashMap;
+
+    const USER: &str = "0008cd03aaaa";
+    const POOL: &str = "1999030f0400";
+
+    fn input(tree: &str, value: i64, assets: Vec<Eip12Asset>) -> Eip12InputBox {
+        Eip12InputBox {
+            box_id: "b".repeat(64),
+            transaction_id: "t".repeat(64),
+            index: 0,
+            value: value.to_string(),
+            ergo_tree: tree.to_string(),
+            assets,
+            creation_height: 1,
+            additional_registers: HashMap::new(),
+            extension: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_derive_intent_swap_like_tx() {
+        let tx = Eip12UnsignedTx {
+            inputs: vec![
+                input(POOL, 100_000_000_000, vec![Eip12Asset::new("tok", 1_000)]),
+                input(USER, 5_000_000_000, vec![]),
+            ],
+            data_inputs: vec![],
+            outputs: vec![
+                Eip12Output::change(101_000_000_000, POOL, vec![Eip12Asset::new("tok", 990)], 1),
+                Eip12Output::change(3_987_900_000, USER, vec![Eip12Asset::new("tok", 10)], 1),
+                Eip12Output::simple(11_000_000, DEFAULT_DEV_FEE_ERGO_TREE, 1),
+                Eip12Output::fee(1_100_000, 1),
+            ],
+        };
+
+        let intent = derive_intent(&tx, "Spectrum", "Swap");
+        assert_eq!(intent.assets_in, vec![IntentAsset::erg(1_000_000_000)]);
+        assert_eq!(intent.assets_out, vec![IntentAsset::token("tok", 10)]);
+        assert_eq!(intent.fees.miner_nano, 1_100_000);
+        assert_eq!(intent.fees.citadel_nano, 11_000_000);
+    }
+}
@@ -6,6 +6,7 @@ pub mod box_selector;
 pub mod burn;
 pub mod dev_fee;
 pub mod eip12;
+pub mod intent;
 pub mod send;
 pub mod sigma;
 pub mod tx_helpers;
@@ -14,7 +15,9 @@ pub mod utxo_management;
 #[cfg(feature = "ergo-lib")]
 pub mod address;
 #[cfg(feature = "ergo-lib")]
-pub use address::{address_to_ergo_tree, AddressError};
+pub use address::{
+    address_to_ergo_tree, derive_xpub_addresses, ergo_tree_to_address, AddressError,
+};
 
 #[cfg(feature = "ergo-lib")]
 pub mod chain;
@@ -38,6 +41,7 @@ pub use dev_fee::{
     DEFAULT_DEV_FEE_ADDRESS, DEFAULT_DEV_FEE_ERGO_TREE,
 };
 pub use eip12::*;
+pub use intent::derive_intent;
 pub use send::{build_send_tx, SendBuildResult, SendError, SendSummary};
 pub use sigma::*;
 pub use tx_helpers::{
@@ -4,6 +4,7 @@
 //!
 //! This crate provides the core functionality for ErgoPay:
 //! - Transaction reduction (converting EIP-12 transactions to sigma-serialized ReducedTransaction bytes)
+//! - Differential verification of the fallback reducer against the standard one
 //! - Error types for reduction operations
 //!
 //! # Example
@@ -20,8 +21,14 @@ pub mod error;
 pub mod reduce;
 pub mod reduce_fallback;
 pub mod types;
+pub mod verify;
 
 pub use error::ReductionError;
-pub use reduce::{reduce_transaction, reduce_transaction_with_context};
+pub use reduce::{build_unsigned_transaction, reduce_transaction, reduce_transaction_with_context};
 pub use reduce_fallback::reduce_transaction_fallback;
 pub use types::{ErgoPayResponse, MessageSeverity};
+pub use verify::{
+    check_fallback_signers, compare_reductions, reduce_transaction_checked,
+    reduce_transaction_checked_with_context, FallbackSignerCheck, ReductionMismatch,
+    ReductionOutcome, ReductionPath,
+};
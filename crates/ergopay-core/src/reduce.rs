@@ -73,40 +73,75 @@ pub fn reduce_transaction_with_context(
     data_input_boxes: Vec<ErgoBox>,
     state_context: &ErgoStateContext,
 ) -> Result<Vec<u8>, ReductionError> {
-    // Get current height from state context for output creation
+    // Outputs without an explicit creation height fall back to the current height
     let current_height = state_context.pre_header.height;
+    let unsigned_tx = build_unsigned_transaction(eip12_tx, current_height)?;
 
+    // Create TransactionContext
+    let tx_context = TransactionContext::new(unsigned_tx, input_boxes, data_input_boxes)
+        .map_err(|e| ReductionError::TransactionError(e.to_string()))?;
+
+    // Reduce the transaction
+    let reduced_tx = reduce_tx(tx_context, state_context)
+        .map_err(|e| ReductionError::ReductionFailed(e.to_string()))?;
+
+    // Sigma-serialize
+    let bytes = reduced_tx
+        .sigma_serialize_bytes()
+        .map_err(|e| ReductionError::SerializationError(e.to_string()))?;
+
+    Ok(bytes)
+}
+
+/// Build a sigma-rust `UnsignedTransaction` from an EIP-12 transaction
+///
+/// Outputs keep the `creationHeight` chosen by the builder so that the resulting
+/// `bytes_to_sign` match what Nautilus (and the fallback reducer) sign. Outputs
+/// with a non-positive creation height use `default_height` instead.
+pub fn build_unsigned_transaction(
+    eip12_tx: &Eip12UnsignedTx,
+    default_height: u32,
+) -> Result<UnsignedTransaction, ReductionError> {
     // Convert outputs to ErgoBoxCandidate using the builder pattern
     let output_candidates: Vec<_> = eip12_tx
         .outputs
         .iter()
-        .map(|output| convert_output_to_candidate(output, current_height))
+        .map(|output| {
+            let height = if output.creation_height > 0 {
+                output.creation_height as u32
+            } else {
+                default_height
+            };
+            convert_output_to_candidate(output, height)
+        })
         .collect::<Result<Vec<_>, ReductionError>>()?;
 
-    // Build data inputs from data input boxes
-    let data_inputs: Option<TxIoVec<DataInput>> = if data_input_boxes.is_empty() {
+    // Build data inputs from data input box IDs
+    let data_inputs: Option<TxIoVec<DataInput>> = if eip12_tx.data_inputs.is_empty() {
         None
     } else {
-        let dis: Vec<DataInput> = data_input_boxes
+        let dis: Vec<DataInput> = eip12_tx
+            .data_inputs
             .iter()
-            .map(|b| DataInput::from(b.box_id()))
-            .collect();
+            .map(|d| parse_box_id(&d.box_id).map(DataInput::from))
+            .collect::<Result<Vec<_>, ReductionError>>()?;
         Some(
             TxIoVec::from_vec(dis)
                 .map_err(|e| ReductionError::TransactionError(format!("Data inputs: {}", e)))?,
         )
     };
 
-    // Build unsigned inputs from input boxes with context extensions
+    // Build unsigned inputs with context extensions
     // UnsignedInput requires BoxId and ContextExtension
-    let unsigned_inputs: Vec<UnsignedInput> = input_boxes
+    let unsigned_inputs: Vec<UnsignedInput> = eip12_tx
+        .inputs
         .iter()
-        .zip(eip12_tx.inputs.iter())
-        .map(|(b, eip12_input)| {
+        .map(|eip12_input| {
+            let box_id = parse_box_id(&eip12_input.box_id)?;
             let ctx_ext = build_context_extension(&eip12_input.extension);
-            UnsignedInput::new(b.box_id(), ctx_ext)
+            Ok(UnsignedInput::new(box_id, ctx_ext))
         })
-        .collect();
+        .collect::<Result<Vec<_>, ReductionError>>()?;
 
     let inputs = TxIoVec::from_vec(unsigned_inputs)
         .map_err(|e| ReductionError::TransactionError(format!("Inputs: {}", e)))?;
@@ -115,28 +150,11 @@ pub fn reduce_transaction_with_context(
     let outputs = TxIoVec::from_vec(output_candidates)
         .map_err(|e| ReductionError::TransactionError(format!("Outputs: {}", e)))?;
 
-    // Create UnsignedTransaction
-    let unsigned_tx = UnsignedTransaction::new(inputs, data_inputs, outputs)
-        .map_err(|e| ReductionError::TransactionError(e.to_string()))?;
-
-    // Create TransactionContext
-    let tx_context = TransactionContext::new(unsigned_tx, input_boxes, data_input_boxes)
-        .map_err(|e| ReductionError::TransactionError(e.to_string()))?;
-
-    // Reduce the transaction
-    let reduced_tx = reduce_tx(tx_context, state_context)
-        .map_err(|e| ReductionError::ReductionFailed(e.to_string()))?;
-
-    // Sigma-serialize
-    let bytes = reduced_tx
-        .sigma_serialize_bytes()
-        .map_err(|e| ReductionError::SerializationError(e.to_string()))?;
-
-    Ok(bytes)
+    UnsignedTransaction::new(inputs, data_inputs, outputs)
+        .map_err(|e| ReductionError::TransactionError(e.to_string()))
 }
 
-/// Parse a hex string to BoxId (used only by tests)
-#[cfg(test)]
+/// Parse a hex string to BoxId
 fn parse_box_id(
     hex_str: &str,
 ) -> Result<ergo_lib::ergotree_ir::chain::ergo_box::BoxId, ReductionError> {
@@ -220,10 +238,13 @@ fn build_context_extension(extension: &HashMap<String, String>) -> ContextExtens
         return ContextExtension::empty();
     }
 
-    // Start with an empty context extension and add values
+    // Start with an empty context extension and add values in key order, so the
+    // serialized extension is deterministic and matches the fallback reducer
     let mut ctx_ext = ContextExtension::empty();
+    let mut entries: Vec<_> = extension.iter().collect();
+    entries.sort_by_key(|(key, _)| key.parse::<u8>().unwrap_or(u8::MAX));
 
-    for (key, value) in extension {
+    for (key, value) in entries {
         // Parse key as u8
         let key_num: u8 = match key.parse() {
             Ok(n) => n,
@@ -221,14 +221,14 @@ fn write_registers(
 // =============================================================================
 
 /// P2PK ErgoTree prefix: header(0x00) + SigmaPropConstant type(0x08) + ProveDlog(0xCD)
-const P2PK_PREFIX: [u8; 3] = [0x00, 0x08, 0xCD];
+pub(crate) const P2PK_PREFIX: [u8; 3] = [0x00, 0x08, 0xCD];
 /// P2PK ErgoTree total length: 3 bytes prefix + 33 bytes compressed EC point
-const P2PK_TREE_LEN: usize = 36;
+pub(crate) const P2PK_TREE_LEN: usize = 36;
 
 /// SigmaBoolean opcode for ProveDlog
-const SIGMA_PROVE_DLOG: u8 = 0xCD;
+pub(crate) const SIGMA_PROVE_DLOG: u8 = 0xCD;
 /// SigmaBoolean opcode for TrivialProp(true)
-const SIGMA_TRIVIAL_TRUE: u8 = 0xD3;
+pub(crate) const SIGMA_TRIVIAL_TRUE: u8 = 0xD3;
 
 /// Write the SigmaBoolean for an input's ErgoTree.
 fn write_sigma_boolean_for_tree(w: &mut Vec<u8>, ergo_tree_bytes: &[u8]) {
@@ -262,7 +262,7 @@ fn collect_distinct_token_ids(tx: &Eip12UnsignedTx) -> IndexSet<String> {
 // =============================================================================
 
 /// Encode a u64 value as unsigned VLQ and append to the buffer.
-fn vlq_put_u64(buf: &mut Vec<u8>, mut value: u64) {
+pub(crate) fn vlq_put_u64(buf: &mut Vec<u8>, mut value: u64) {
     loop {
         if (value & !0x7F) == 0 {
             buf.push(value as u8);
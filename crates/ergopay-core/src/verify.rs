@@ -0,0 +1,631 @@
+//! Differential Verification of Fallback Reduction
+//!
+//! `reduce_transaction_fallback` never evaluates scripts: every non-P2PK input
+//! is written as `TrivialProp(true)` with zero cost. This module checks that
+//! shortcut against the standard reducer:
+//!
+//! - When both reducers succeed, `bytes_to_sign` must be byte-identical and every
+//!   input must reduce to the same SigmaBoolean.
+//! - When only the fallback succeeds, every P2PK input must carry its ProveDlog
+//!   signer, and script inputs that embed a public key are reported because the
+//!   wallet will not produce a proof for them.
+//!
+//! Both checks operate on the EIP-19 byte layout, so they do not depend on
+//! sigma-rust being able to parse the ErgoTrees involved.
+
+use std::fmt;
+
+use ergo_lib::chain::ergo_state_context::ErgoStateContext;
+use ergo_lib::ergotree_ir::chain::ergo_box::ErgoBox;
+use ergo_node_client::NodeClient;
+use ergo_tx::Eip12UnsignedTx;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ReductionError;
+use crate::reduce::reduce_transaction_with_context;
+use crate::reduce_fallback::{
+    reduce_transaction_fallback, P2PK_PREFIX, P2PK_TREE_LEN, SIGMA_PROVE_DLOG, SIGMA_TRIVIAL_TRUE,
+};
+
+/// SigmaBoolean opcode for TrivialProp(false)
+const SIGMA_TRIVIAL_FALSE: u8 = 0xD2;
+/// SigmaBoolean opcode for ProveDHTuple (4 group elements follow)
+const SIGMA_PROVE_DH_TUPLE: u8 = 0xCE;
+/// SigmaBoolean opcode for CAND
+const SIGMA_CAND: u8 = 0x96;
+/// SigmaBoolean opcode for COR
+const SIGMA_COR: u8 = 0x97;
+/// SigmaBoolean opcode for CTHRESHOLD
+const SIGMA_CTHRESHOLD: u8 = 0x98;
+/// Compressed EC point length
+const GROUP_ELEMENT_LEN: usize = 33;
+
+// =============================================================================
+// Types
+// =============================================================================
+
+/// Which reducer produced the bytes handed to the wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReductionPath {
+    Standard,
+    Fallback,
+}
+
+impl fmt::Display for ReductionPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReductionPath::Standard => write!(f, "standard"),
+            ReductionPath::Fallback => write!(f, "fallback"),
+        }
+    }
+}
+
+/// EIP-19 ReducedTransaction bytes split into their components
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReducedTxParts {
+    pub bytes_to_sign: Vec<u8>,
+    pub inputs: Vec<ReducedInputParts>,
+    pub tx_cost: u64,
+}
+
+/// Serialized SigmaBoolean and cost of a single reduced input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReducedInputParts {
+    pub sigma_prop: Vec<u8>,
+    pub cost: u64,
+}
+
+/// A difference between the standard and the fallback reduction of one transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReductionMismatch {
+    /// `bytes_to_sign` differ; `first_difference` is the first differing byte offset
+    BytesToSign {
+        standard_len: usize,
+        fallback_len: usize,
+        first_difference: usize,
+    },
+    /// An input reduced to a different SigmaBoolean (hex-encoded)
+    SigmaProp {
+        input_index: usize,
+        standard: String,
+        fallback: String,
+    },
+}
+
+impl fmt::Display for ReductionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReductionMismatch::BytesToSign {
+                standard_len,
+                fallback_len,
+                first_difference,
+            } => write!(
+                f,
+                "bytes_to_sign differ at offset {} (standard {} bytes, fallback {} bytes)",
+                first_difference, standard_len, fallback_len
+            ),
+            ReductionMismatch::SigmaProp {
+                input_index,
+                standard,
+                fallback,
+            } => write!(
+                f,
+                "input {} reduced to {} (standard) vs {} (fallback)",
+                input_index, standard, fallback
+            ),
+        }
+    }
+}
+
+/// Result of checking a fallback-only reduction for signers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FallbackSignerCheck {
+    /// Public keys (hex) the wallet will be asked to prove
+    pub signers: Vec<String>,
+    /// Indices of P2PK inputs that did not reduce to their ProveDlog
+    pub missing_signers: Vec<usize>,
+    /// Script inputs reduced to `TrivialProp(true)` whose ErgoTree embeds public keys
+    pub unproven_inputs: Vec<UnprovenInput>,
+}
+
+/// A script input the fallback assumed to be trivially true
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnprovenInput {
+    pub input_index: usize,
+    pub box_id: String,
+    pub embedded_pubkeys: Vec<String>,
+}
+
+impl FallbackSignerCheck {
+    /// Human-readable warnings for the signing response
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if self.signers.is_empty() {
+            warnings.push("No P2PK input found: the wallet has nothing to sign".to_string());
+        }
+        for index in &self.missing_signers {
+            warnings.push(format!(
+                "P2PK input {} was not reduced to its public key",
+                index
+            ));
+        }
+        for input in &self.unproven_inputs {
+            warnings.push(format!(
+                "Script input {} ({}) embeds public key(s) {} but was assumed trivially true; \
+                 the wallet will not sign for it",
+                input.input_index,
+                input.box_id,
+                input.embedded_pubkeys.join(", ")
+            ));
+        }
+        warnings
+    }
+}
+
+/// Reduced bytes plus how they were produced
+#[derive(Debug, Clone)]
+pub struct ReductionOutcome {
+    pub reduced_bytes: Vec<u8>,
+    pub path: ReductionPath,
+    /// Differences found by the differential check (standard path only)
+    pub mismatches: Vec<ReductionMismatch>,
+    /// User-facing warnings (fallback path only)
+    pub warnings: Vec<String>,
+}
+
+// =============================================================================
+// Public API
+// =============================================================================
+
+/// Reduce a transaction, falling back to the manual reducer when sigma-rust
+/// cannot, and verify the fallback against the standard reducer when both work.
+pub async fn reduce_transaction_checked(
+    eip12_tx: &Eip12UnsignedTx,
+    input_boxes: Vec<ErgoBox>,
+    data_input_boxes: Vec<ErgoBox>,
+    client: &NodeClient,
+) -> Result<ReductionOutcome, ReductionError> {
+    let state_context = client
+        .inner()
+        .get_state_context()
+        .await
+        .map_err(|e| ReductionError::StateContextError(e.to_string()));
+
+    match state_context {
+        Ok(ctx) => {
+            reduce_transaction_checked_with_context(eip12_tx, input_boxes, data_input_boxes, &ctx)
+        }
+        Err(e) => reduce_with_fallback(eip12_tx, e),
+    }
+}
+
+/// Same as [`reduce_transaction_checked`] with a pre-fetched state context
+pub fn reduce_transaction_checked_with_context(
+    eip12_tx: &Eip12UnsignedTx,
+    input_boxes: Vec<ErgoBox>,
+    data_input_boxes: Vec<ErgoBox>,
+    state_context: &ErgoStateContext,
+) -> Result<ReductionOutcome, ReductionError> {
+    let standard = match reduce_transaction_with_context(
+        eip12_tx,
+        input_boxes,
+        data_input_boxes,
+        state_context,
+    ) {
+        Ok(bytes) => bytes,
+        Err(e) => return reduce_with_fallback(eip12_tx, e),
+    };
+
+    let mismatches = match reduce_transaction_fallback(eip12_tx)
+        .and_then(|fallback| compare_reductions(eip12_tx, &standard, &fallback))
+    {
+        Ok(mismatches) => mismatches,
+        Err(e) => {
+            tracing::warn!("Differential reduction check could not run: {}", e);
+            Vec::new()
+        }
+    };
+    for mismatch in &mismatches {
+        tracing::warn!("Fallback reducer diverges from standard: {}", mismatch);
+    }
+
+    Ok(ReductionOutcome {
+        reduced_bytes: standard,
+        path: ReductionPath::Standard,
+        mismatches,
+        warnings: Vec::new(),
+    })
+}
+
+/// Compare standard and fallback reductions of the same transaction
+pub fn compare_reductions(
+    eip12_tx: &Eip12UnsignedTx,
+    standard: &[u8],
+    fallback: &[u8],
+) -> Result<Vec<ReductionMismatch>, ReductionError> {
+    let input_count = eip12_tx.inputs.len();
+    let standard = parse_reduced_tx(standard, input_count)?;
+    let fallback = parse_reduced_tx(fallback, input_count)?;
+
+    let mut mismatches = Vec::new();
+
+    if standard.bytes_to_sign != fallback.bytes_to_sign {
+        let first_difference = standard
+            .bytes_to_sign
+            .iter()
+            .zip(fallback.bytes_to_sign.iter())
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| {
+                standard
+                    .bytes_to_sign
+                    .len()
+                    .min(fallback.bytes_to_sign.len())
+            });
+        mismatches.push(ReductionMismatch::BytesToSign {
+            standard_len: standard.bytes_to_sign.len(),
+            fallback_len: fallback.bytes_to_sign.len(),
+            first_difference,
+        });
+    }
+
+    for (index, (s, f)) in standard
+        .inputs
+        .iter()
+        .zip(fallback.inputs.iter())
+        .enumerate()
+    {
+        if s.sigma_prop != f.sigma_prop {
+            mismatches.push(ReductionMismatch::SigmaProp {
+                input_index: index,
+                standard: hex::encode(&s.sigma_prop),
+                fallback: hex::encode(&f.sigma_prop),
+            });
+        }
+    }
+
+    Ok(mismatches)
+}
+
+/// Check that a fallback reduction asks the wallet for every signer it needs
+pub fn check_fallback_signers(
+    eip12_tx: &Eip12UnsignedTx,
+    fallback: &[u8],
+) -> Result<FallbackSignerCheck, ReductionError> {
+    let parts = parse_reduced_tx(fallback, eip12_tx.inputs.len())?;
+    let mut check = FallbackSignerCheck::default();
+
+    for (index, (input, reduced)) in eip12_tx.inputs.iter().zip(parts.inputs.iter()).enumerate() {
+        let tree = hex::decode(&input.ergo_tree).map_err(|e| {
+            ReductionError::InvalidErgoTree(format!("Invalid input ErgoTree hex: {}", e))
+        })?;
+
+        if tree.len() == P2PK_TREE_LEN && tree[..3] == P2PK_PREFIX {
+            let pubkey = &tree[3..];
+            let expected: Vec<u8> = std::iter::once(SIGMA_PROVE_DLOG)
+                .chain(pubkey.iter().copied())
+                .collect();
+            if reduced.sigma_prop == expected {
+                let pubkey_hex = hex::encode(pubkey);
+                if !check.signers.contains(&pubkey_hex) {
+                    check.signers.push(pubkey_hex);
+                }
+            } else {
+                check.missing_signers.push(index);
+            }
+        } else if reduced.sigma_prop == [SIGMA_TRIVIAL_TRUE] {
+            let embedded_pubkeys = find_embedded_pubkeys(&tree);
+            if !embedded_pubkeys.is_empty() {
+                check.unproven_inputs.push(UnprovenInput {
+                    input_index: index,
+                    box_id: input.box_id.clone(),
+                    embedded_pubkeys,
+                });
+            }
+        }
+    }
+
+    Ok(check)
+}
+
+/// Split EIP-19 ReducedTransaction bytes into `bytes_to_sign`, per-input
+/// SigmaBooleans and costs.
+pub fn parse_reduced_tx(
+    bytes: &[u8],
+    input_count: usize,
+) -> Result<ReducedTxParts, ReductionError> {
+    let mut r = ByteReader { bytes, pos: 0 };
+
+    let msg_len = r.vlq()? as usize;
+    let bytes_to_sign = r.take(msg_len)?.to_vec();
+
+    let mut inputs = Vec::with_capacity(input_count);
+    for _ in 0..input_count {
+        let start = r.pos;
+        r.skip_sigma_boolean()?;
+        let sigma_prop = bytes[start..r.pos].to_vec();
+        let cost = r.vlq()?;
+        inputs.push(ReducedInputParts { sigma_prop, cost });
+    }
+
+    let tx_cost = r.vlq()?;
+    if r.pos != bytes.len() {
+        return Err(ReductionError::SerializationError(format!(
+            "{} trailing bytes after reduced transaction",
+            bytes.len() - r.pos
+        )));
+    }
+
+    Ok(ReducedTxParts {
+        bytes_to_sign,
+        inputs,
+        tx_cost,
+    })
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Run the fallback reducer after the standard one failed with `reason`
+fn reduce_with_fallback(
+    eip12_tx: &Eip12UnsignedTx,
+    reason: ReductionError,
+) -> Result<ReductionOutcome, ReductionError> {
+    tracing::warn!("Standard reduction failed ({}), using fallback", reason);
+
+    let fallback = reduce_transaction_fallback(eip12_tx)?;
+    let check = check_fallback_signers(eip12_tx, &fallback)?;
+
+    let mut warnings = vec![format!(
+        "Standard reduction failed ({}); script inputs were not evaluated before signing",
+        reason
+    )];
+    warnings.extend(check.warnings());
+
+    Ok(ReductionOutcome {
+        reduced_bytes: fallback,
+        path: ReductionPath::Fallback,
+        mismatches: Vec::new(),
+        warnings,
+    })
+}
+
+/// Find ProveDlog SigmaProp constants (`08 cd <33-byte point>`) in an ErgoTree
+fn find_embedded_pubkeys(tree: &[u8]) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    for window in tree.windows(2 + GROUP_ELEMENT_LEN) {
+        if window[0] == 0x08 && window[1] == SIGMA_PROVE_DLOG && matches!(window[2], 0x02 | 0x03) {
+            let pubkey = hex::encode(&window[2..]);
+            if !found.contains(&pubkey) {
+                found.push(pubkey);
+            }
+        }
+    }
+    found
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ReductionError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len());
+        match end {
+            Some(end) => {
+                let slice = &self.bytes[self.pos..end];
+                self.pos = end;
+                Ok(slice)
+            }
+            None => Err(ReductionError::SerializationError(
+                "Unexpected end of reduced transaction".to_string(),
+            )),
+        }
+    }
+
+    fn byte(&mut self) -> Result<u8, ReductionError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn vlq(&mut self) -> Result<u64, ReductionError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = self.byte()?;
+            value |= ((b & 0x7F) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(ReductionError::SerializationError(
+            "VLQ value overflows u64".to_string(),
+        ))
+    }
+
+    fn skip_sigma_boolean(&mut self) -> Result<(), ReductionError> {
+        match self.byte()? {
+            SIGMA_TRIVIAL_TRUE | SIGMA_TRIVIAL_FALSE => Ok(()),
+            SIGMA_PROVE_DLOG => self.take(GROUP_ELEMENT_LEN).map(|_| ()),
+            SIGMA_PROVE_DH_TUPLE => self.take(4 * GROUP_ELEMENT_LEN).map(|_| ()),
+            SIGMA_CAND | SIGMA_COR => {
+                let count = self.vlq()?;
+                (0..count).try_for_each(|_| self.skip_sigma_boolean())
+            }
+            SIGMA_CTHRESHOLD => {
+                let _k = self.vlq()?;
+                let count = self.vlq()?;
+                (0..count).try_for_each(|_| self.skip_sigma_boolean())
+            }
+            other => Err(ReductionError::SerializationError(format!(
+                "Unknown SigmaBoolean opcode 0x{:02x}",
+                other
+            ))),
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reduce_fallback::vlq_put_u64;
+    use ergo_tx::{Eip12InputBox, Eip12Output};
+    use std::collections::HashMap;
+
+    const PUBKEY: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn p2pk_tree() -> String {
+        format!("0008cd{}", PUBKEY)
+    }
+
+    fn input(box_byte: char, ergo_tree: String) -> Eip12InputBox {
+        Eip12InputBox {
+            box_id: box_byte.to_string().repeat(64),
+            transaction_id: "f".repeat(64),
+            index: 0,
+            value: "1000000000".to_string(),
+            ergo_tree,
+            assets: vec![],
+            creation_height: 1_000_000,
+            additional_registers: HashMap::new(),
+            extension: HashMap::new(),
+        }
+    }
+
+    fn tx(inputs: Vec<Eip12InputBox>) -> Eip12UnsignedTx {
+        Eip12UnsignedTx {
+            inputs,
+            data_inputs: vec![],
+            outputs: vec![Eip12Output::simple(999_000_000, p2pk_tree(), 1_000_000)],
+        }
+    }
+
+    /// Assemble EIP-19 bytes from parts (mirrors the fallback layout)
+    fn assemble(bytes_to_sign: &[u8], props: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = Vec::new();
+        vlq_put_u64(&mut out, bytes_to_sign.len() as u64);
+        out.extend_from_slice(bytes_to_sign);
+        for prop in props {
+            out.extend_from_slice(prop);
+            vlq_put_u64(&mut out, 0);
+        }
+        vlq_put_u64(&mut out, 0);
+        out
+    }
+
+    fn prove_dlog() -> Vec<u8> {
+        let mut prop = vec![SIGMA_PROVE_DLOG];
+        prop.extend(hex::decode(PUBKEY).unwrap());
+        prop
+    }
+
+    #[test]
+    fn test_parse_fallback_output() {
+        let tx = tx(vec![
+            input('a', p2pk_tree()),
+            input('b', "100204a00b08cd".into()),
+        ]);
+        let bytes = reduce_transaction_fallback(&tx).unwrap();
+        let parts = parse_reduced_tx(&bytes, 2).unwrap();
+
+        assert_eq!(parts.inputs.len(), 2);
+        assert_eq!(parts.inputs[0].sigma_prop, prove_dlog());
+        assert_eq!(parts.inputs[1].sigma_prop, vec![SIGMA_TRIVIAL_TRUE]);
+        assert_eq!(parts.tx_cost, 0);
+    }
+
+    #[test]
+    fn test_parse_nested_sigma_boolean() {
+        // CTHRESHOLD(1, [ProveDlog, COR(TrivialTrue, TrivialFalse)])
+        let mut prop = vec![SIGMA_CTHRESHOLD, 0x01, 0x02];
+        prop.extend(prove_dlog());
+        prop.extend([SIGMA_COR, 0x02, SIGMA_TRIVIAL_TRUE, SIGMA_TRIVIAL_FALSE]);
+
+        let bytes = assemble(&[1, 2, 3], &[prop.clone()]);
+        let parts = parse_reduced_tx(&bytes, 1).unwrap();
+        assert_eq!(parts.bytes_to_sign, vec![1, 2, 3]);
+        assert_eq!(parts.inputs[0].sigma_prop, prop);
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_and_trailing() {
+        let bytes = assemble(&[1, 2, 3], &[prove_dlog()]);
+        assert!(parse_reduced_tx(&bytes[..bytes.len() - 2], 1).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(parse_reduced_tx(&trailing, 1).is_err());
+    }
+
+    #[test]
+    fn test_compare_identical_reductions() {
+        let tx = tx(vec![input('a', p2pk_tree())]);
+        let bytes = reduce_transaction_fallback(&tx).unwrap();
+        assert!(compare_reductions(&tx, &bytes, &bytes).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_compare_detects_divergence() {
+        let tx = tx(vec![input('a', p2pk_tree())]);
+        let standard = assemble(&[1, 2, 3, 4], &[prove_dlog()]);
+        let fallback = assemble(&[1, 2, 9, 4], &[vec![SIGMA_TRIVIAL_TRUE]]);
+
+        let mismatches = compare_reductions(&tx, &standard, &fallback).unwrap();
+        assert_eq!(
+            mismatches,
+            vec![
+                ReductionMismatch::BytesToSign {
+                    standard_len: 4,
+                    fallback_len: 4,
+                    first_difference: 2,
+                },
+                ReductionMismatch::SigmaProp {
+                    input_index: 0,
+                    standard: hex::encode(prove_dlog()),
+                    fallback: "d3".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_signer_check_finds_p2pk_signers() {
+        let tx = tx(vec![input('a', p2pk_tree()), input('b', p2pk_tree())]);
+        let bytes = reduce_transaction_fallback(&tx).unwrap();
+        let check = check_fallback_signers(&tx, &bytes).unwrap();
+
+        assert_eq!(check.signers, vec![PUBKEY.to_string()]);
+        assert!(check.missing_signers.is_empty());
+        assert!(check.unproven_inputs.is_empty());
+        assert!(check.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_signer_check_flags_script_with_embedded_pubkey() {
+        let script = format!("10010e08cd{}d801", PUBKEY);
+        let tx = tx(vec![input('a', script)]);
+        let bytes = reduce_transaction_fallback(&tx).unwrap();
+        let check = check_fallback_signers(&tx, &bytes).unwrap();
+
+        assert!(check.signers.is_empty());
+        assert_eq!(check.unproven_inputs.len(), 1);
+        assert_eq!(check.unproven_inputs[0].embedded_pubkeys, vec![PUBKEY]);
+        assert_eq!(check.warnings().len(), 2);
+    }
+
+    #[test]
+    fn test_signer_check_flags_missing_signer() {
+        let tx = tx(vec![input('a', p2pk_tree())]);
+        let bytes = assemble(&[0], &[vec![SIGMA_TRIVIAL_TRUE]]);
+        let check = check_fallback_signers(&tx, &bytes).unwrap();
+        assert_eq!(check.missing_signers, vec![0]);
+    }
+}
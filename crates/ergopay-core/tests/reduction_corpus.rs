@@ -0,0 +1,569 @@
+//! Property corpus for the fallback reducer.
+//!
+//! Every transaction the builders produce must reduce through the fallback path to
+//! the same `bytes_to_sign` sigma-rust computes, with a ProveDlog for every P2PK
+//! input. The corpus is generated from the `ergo-tx` builders over a sweep of
+//! inputs, from the protocol builders on synthetic state, and from the protocol
+//! golden fixtures. Script inputs keep their contract trees; only placeholder
+//! trees and IDs are normalised to valid hex.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use ergo_tx::{
+    build_burn_tx, build_consolidate_tx, build_multi_burn_tx, build_send_tx, build_split_tx,
+    with_test_dev_fee, BurnItem, DevFeeConfig, Eip12Asset, Eip12DataInputBox, Eip12InputBox,
+    Eip12UnsignedTx, SplitMode,
+};
+use ergopay_core::verify::parse_reduced_tx;
+use ergopay_core::{
+    build_unsigned_transaction, check_fallback_signers, reduce_transaction_fallback,
+};
+
+const HEIGHT: i32 = 1_200_000;
+const USER_TREE: &str = "0008cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const RECIPIENT_TREE: &str =
+    "0008cd02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+/// `sigmaProp(true)`, standing in for protocol contracts the fixtures only name
+const SCRIPT_TREE: &str = "10010101d17300";
+
+/// Deterministic xorshift so corpus failures are reproducible
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next() % (hi - lo + 1)
+    }
+}
+
+fn hex32(seed: u64) -> String {
+    format!("{:064x}", seed.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+fn token_id(n: u64) -> String {
+    hex32(0x7000 + n)
+}
+
+fn random_inputs(rng: &mut Rng, case: u64) -> Vec<Eip12InputBox> {
+    let count = rng.range(1, 4);
+    (0..count)
+        .map(|i| {
+            let token_count = rng.range(0, 3);
+            Eip12InputBox {
+                box_id: hex32(case * 100 + i),
+                transaction_id: hex32(case * 100 + i + 50),
+                index: i as u16,
+                value: rng.range(1_000_000_000, 50_000_000_000).to_string(),
+                ergo_tree: USER_TREE.to_string(),
+                assets: (0..token_count)
+                    .map(|t| Eip12Asset::new(token_id(t), rng.range(1, 1_000_000) as i64))
+                    .collect(),
+                creation_height: HEIGHT - 10,
+                additional_registers: HashMap::new(),
+                extension: HashMap::new(),
+            }
+        })
+        .collect()
+}
+
+fn builder_corpus() -> Vec<(String, Eip12UnsignedTx)> {
+    let mut rng = Rng(0x5EED_CAFE);
+    let mut corpus = Vec::new();
+
+    for case in 0..40u64 {
+        let inputs = random_inputs(&mut rng, case);
+        let first_token = inputs.iter().flat_map(|i| i.assets.first()).next().cloned();
+
+        if let Ok(r) = build_send_tx(
+            &inputs,
+            RECIPIENT_TREE,
+            USER_TREE,
+            rng.range(1_000_000, 500_000_000) as i64,
+            None,
+            HEIGHT,
+        ) {
+            corpus.push((format!("send_erg_{}", case), r.unsigned_tx));
+        }
+
+        if let Some(token) = &first_token {
+            let amount: u64 = token.amount.parse().unwrap();
+            if let Ok(r) = build_send_tx(
+                &inputs,
+                RECIPIENT_TREE,
+                USER_TREE,
+                1_000_000,
+                Some((&token.token_id, rng.range(1, amount))),
+                HEIGHT,
+            ) {
+                corpus.push((format!("send_token_{}", case), r.unsigned_tx));
+            }
+            if let Ok(r) = build_burn_tx(&inputs, &token.token_id, 1, USER_TREE, HEIGHT) {
+                corpus.push((format!("burn_{}", case), r.unsigned_tx));
+            }
+            let items = vec![BurnItem {
+                token_id: token.token_id.clone(),
+                amount: 1,
+            }];
+            if let Ok(r) = build_multi_burn_tx(&inputs, &items, USER_TREE, HEIGHT) {
+                corpus.push((format!("multi_burn_{}", case), r.unsigned_tx));
+            }
+        }
+
+        if let Ok(r) = build_consolidate_tx(&inputs, USER_TREE, HEIGHT) {
+            corpus.push((format!("consolidate_{}", case), r.unsigned_tx));
+        }
+
+        let mode = SplitMode::Erg {
+            amount_per_box: rng.range(10_000_000, 100_000_000) as i64,
+        };
+        if let Ok(r) = build_split_tx(&inputs, &mode, rng.range(1, 5) as usize, USER_TREE, HEIGHT) {
+            corpus.push((format!("split_{}", case), r.unsigned_tx));
+        }
+    }
+
+    corpus
+}
+
+fn box_at(seed: u64, value: u64, tree: &str, assets: Vec<Eip12Asset>) -> Eip12InputBox {
+    Eip12InputBox {
+        box_id: hex32(seed),
+        transaction_id: hex32(seed + 1),
+        index: 0,
+        value: value.to_string(),
+        ergo_tree: tree.to_string(),
+        assets,
+        creation_height: HEIGHT - 10,
+        additional_registers: HashMap::new(),
+        extension: HashMap::new(),
+    }
+}
+
+fn sigmausd_corpus(corpus: &mut Vec<(String, Eip12UnsignedTx)>) {
+    use sigmausd::tx_builder::{
+        build_mint_sigusd_tx, build_redeem_sigusd_tx, MintSigUsdRequest, RedeemSigUsdRequest,
+        TxContext,
+    };
+    use sigmausd::{BankBoxData, BoxId, NftIds, OracleBoxData, SigmaUsdState};
+
+    let nft_ids = NftIds::for_network(citadel_core::Network::Mainnet).unwrap();
+    let bank_input = box_at(
+        0x5100,
+        1_000_000_000_000_000,
+        SCRIPT_TREE,
+        vec![
+            Eip12Asset::new(&nft_ids.sigusd_token, 5_000_000_000),
+            Eip12Asset::new(&nft_ids.sigrsv_token, 5_000_000_000),
+            Eip12Asset::new(&nft_ids.bank_nft, 1),
+        ],
+    );
+    let oracle = box_at(0x5200, 1_000_000, SCRIPT_TREE, vec![]);
+    let ctx = TxContext {
+        nft_ids: nft_ids.clone(),
+        bank_input: bank_input.clone(),
+        bank_erg_nano: 1_000_000_000_000_000,
+        sigusd_circulating: 10_000_000,
+        sigrsv_circulating: 1_000_000_000,
+        sigusd_in_bank: 5_000_000_000,
+        sigrsv_in_bank: 5_000_000_000,
+        oracle_data_input: Eip12DataInputBox {
+            box_id: oracle.box_id,
+            transaction_id: oracle.transaction_id,
+            index: 0,
+            value: oracle.value,
+            ergo_tree: oracle.ergo_tree,
+            assets: vec![Eip12Asset::new(&nft_ids.oracle_pool_nft, 1)],
+            creation_height: oracle.creation_height,
+            additional_registers: HashMap::new(),
+        },
+        oracle_rate: 5_000_000,
+    };
+    let state = SigmaUsdState::from_boxes(
+        &BankBoxData {
+            box_id: BoxId::new(bank_input.box_id.clone()),
+            value_nano: ctx.bank_erg_nano,
+            sigusd_circulating: ctx.sigusd_circulating,
+            sigrsv_circulating: ctx.sigrsv_circulating,
+        },
+        &OracleBoxData {
+            box_id: BoxId::new(ctx.oracle_data_input.box_id.clone()),
+            nanoerg_per_usd: ctx.oracle_rate * 100,
+        },
+    );
+    let user_inputs = vec![box_at(
+        0x5300,
+        50_000_000_000,
+        USER_TREE,
+        vec![Eip12Asset::new(&nft_ids.sigusd_token, 10_000)],
+    )];
+
+    let mint = MintSigUsdRequest {
+        amount: 1_000,
+        user_address: String::new(),
+        user_ergo_tree: USER_TREE.to_string(),
+        user_inputs: user_inputs.clone(),
+        current_height: HEIGHT,
+        recipient_ergo_tree: None,
+    };
+    let r = build_mint_sigusd_tx(&mint, &ctx, &state).unwrap();
+    corpus.push(("sigmausd_mint_sigusd".to_string(), r.unsigned_tx));
+
+    let redeem = RedeemSigUsdRequest {
+        amount: 1_000,
+        user_address: String::new(),
+        user_ergo_tree: USER_TREE.to_string(),
+        user_inputs,
+        current_height: HEIGHT,
+        recipient_ergo_tree: None,
+    };
+    let r = build_redeem_sigusd_tx(&redeem, &ctx, &state).unwrap();
+    corpus.push(("sigmausd_redeem_sigusd".to_string(), r.unsigned_tx));
+}
+
+fn hodlcoin_corpus(corpus: &mut Vec<(String, Eip12UnsignedTx)>) {
+    use hodlcoin::{build_burn_tx_eip12, build_mint_tx_eip12, HodlBankState};
+
+    let singleton = token_id(0x401);
+    let hodl = token_id(0x402);
+    let bank_box = box_at(
+        0x4100,
+        100_000_000_000,
+        hodlcoin::HODLERG_BANK_ERGO_TREE,
+        vec![
+            Eip12Asset::new(&singleton, 1),
+            Eip12Asset::new(&hodl, 900_000_000_000),
+        ],
+    );
+    let state = HodlBankState {
+        bank_box_id: bank_box.box_id.clone(),
+        singleton_token_id: singleton,
+        hodl_token_id: hodl.clone(),
+        hodl_token_name: Some("hodlERG".to_string()),
+        total_token_supply: 1_000_000_000_000,
+        precision_factor: 1_000_000,
+        min_bank_value: 1_000_000,
+        dev_fee_num: 3,
+        bank_fee_num: 30,
+        reserve_token_id: None,
+        reserve_token_name: None,
+        reserve_amount: 100_000_000_000,
+        reserve_nano_erg: 100_000_000_000,
+        hodl_tokens_in_bank: 900_000_000_000,
+        circulating_supply: 100_000_000_000,
+        price_nano_per_hodl: 1.0,
+        tvl_nano_erg: 100_000_000_000,
+        total_fee_pct: 3.3,
+        bank_fee_pct: 3.0,
+        dev_fee_pct: 0.3,
+    };
+    let user_inputs = vec![box_at(
+        0x4200,
+        20_000_000_000,
+        USER_TREE,
+        vec![Eip12Asset::new(&hodl, 5_000_000_000)],
+    )];
+
+    let tx = build_mint_tx_eip12(
+        &bank_box,
+        &state,
+        1_000_000_000,
+        &user_inputs,
+        USER_TREE,
+        HEIGHT,
+    )
+    .unwrap();
+    corpus.push(("hodlcoin_mint".to_string(), tx));
+    let tx = build_burn_tx_eip12(
+        &bank_box,
+        &state,
+        1_000_000_000,
+        &user_inputs,
+        USER_TREE,
+        HEIGHT,
+    )
+    .unwrap();
+    corpus.push(("hodlcoin_burn".to_string(), tx));
+}
+
+fn sigmafi_corpus(corpus: &mut Vec<(String, Eip12UnsignedTx)>) {
+    use sigmafi::tx_builder::{CancelOrderRequest, OpenOrderRequest};
+
+    let user_inputs = vec![box_at(0x6100, 30_000_000_000, USER_TREE, vec![])];
+    let open = OpenOrderRequest {
+        borrower_ergo_tree: USER_TREE.to_string(),
+        loan_token_id: "ERG".to_string(),
+        principal: 10_000_000_000,
+        repayment: 10_500_000_000,
+        maturity_blocks: 21_600,
+        collateral_erg: 15_000_000_000,
+        collateral_tokens: vec![],
+        user_inputs: user_inputs.clone(),
+        current_height: HEIGHT,
+    };
+    corpus.push((
+        "sigmafi_open_order".to_string(),
+        sigmafi::build_open_order(&open).unwrap(),
+    ));
+
+    let cancel = CancelOrderRequest {
+        order_box: box_at(
+            0x6200,
+            15_000_000_000,
+            sigmafi::constants::ORDER_ON_CLOSE_ERG_CONTRACT,
+            vec![],
+        ),
+        borrower_ergo_tree: USER_TREE.to_string(),
+        user_inputs,
+        current_height: HEIGHT,
+    };
+    corpus.push((
+        "sigmafi_cancel_order".to_string(),
+        sigmafi::build_cancel_order(&cancel).unwrap(),
+    ));
+}
+
+fn mewlock_corpus(corpus: &mut Vec<(String, Eip12UnsignedTx)>) {
+    use mewlock::tx_builder::{LockRequest, UnlockRequest};
+
+    let user_inputs = vec![box_at(
+        0x7100,
+        20_000_000_000,
+        USER_TREE,
+        vec![Eip12Asset::new(token_id(0x701), 1_000)],
+    )];
+    let lock = LockRequest {
+        user_ergo_tree: USER_TREE.to_string(),
+        lock_erg: 5_000_000_000,
+        lock_tokens: vec![(token_id(0x701), 500)],
+        unlock_height: HEIGHT + 1_000,
+        timestamp: None,
+        lock_name: Some("corpus".to_string()),
+        lock_description: None,
+        user_inputs: user_inputs.clone(),
+        current_height: HEIGHT,
+    };
+    corpus.push((
+        "mewlock_lock".to_string(),
+        mewlock::build_lock_tx(&lock).unwrap(),
+    ));
+
+    let unlock = UnlockRequest {
+        lock_box: box_at(
+            0x7200,
+            5_000_000_000,
+            mewlock::constants::MEWLOCK_ERGO_TREE,
+            vec![Eip12Asset::new(token_id(0x701), 500)],
+        ),
+        user_ergo_tree: USER_TREE.to_string(),
+        user_inputs,
+        current_height: HEIGHT,
+    };
+    corpus.push((
+        "mewlock_unlock".to_string(),
+        mewlock::build_unlock_tx(&unlock).unwrap(),
+    ));
+}
+
+fn stake_recovery_corpus(corpus: &mut Vec<(String, Eip12UnsignedTx)>) {
+    use stake_recovery::{RecoverableStake, StakeStateSnapshot, PAIDEIA_PROXY_ERGO_TREE};
+
+    let stake_key = token_id(0x801);
+    let state = StakeStateSnapshot {
+        protocol: "Paideia".into(),
+        state_box_id: hex32(0x8100),
+        state_box_value_nano: 1_000_000,
+        total_staked_raw: 509_068_736_638,
+        checkpoint: 790,
+        num_stakers: 474,
+        last_checkpoint_ts: 1_722_513_600_000,
+        cycle_duration_ms: 86_400_000,
+        stake_token_amount: 999_999_999_526,
+    };
+    let stake = RecoverableStake {
+        protocol: "Paideia".into(),
+        reward_token_name: "PAIDEIA".into(),
+        stake_key_id: stake_key.clone(),
+        stake_box_id: hex32(0x8200),
+        stake_box_value_nano: 1_000_000,
+        reward_amount_raw: 850_495_800,
+        checkpoint: 790,
+        stake_time_ms: 0,
+        reward_amount_display: String::new(),
+    };
+    let user_inputs = vec![box_at(
+        0x8300,
+        2_000_000_000,
+        USER_TREE,
+        vec![Eip12Asset::new(&stake_key, 1)],
+    )];
+    let proxy_tx =
+        stake_recovery::build_paideia_proxy_tx(&stake, &state, &user_inputs, USER_TREE, HEIGHT)
+            .unwrap();
+
+    // Refund spends the proxy box the first transaction created
+    let proxy_out = &proxy_tx.outputs[0];
+    assert_eq!(proxy_out.ergo_tree, PAIDEIA_PROXY_ERGO_TREE);
+    let mut proxy_box = box_at(
+        0x8400,
+        proxy_out.value.parse().unwrap(),
+        &proxy_out.ergo_tree,
+        proxy_out.assets.clone(),
+    );
+    proxy_box.additional_registers = proxy_out.additional_registers.clone();
+    corpus.push(("stake_recovery_paideia_proxy".to_string(), proxy_tx));
+    corpus.push((
+        "stake_recovery_paideia_refund".to_string(),
+        stake_recovery::build_paideia_refund_tx(&proxy_box, HEIGHT).unwrap(),
+    ));
+}
+
+/// Protocol builders the golden fixtures don't cover, on synthetic state
+fn protocol_builder_corpus() -> Vec<(String, Eip12UnsignedTx)> {
+    let mut corpus = Vec::new();
+    sigmausd_corpus(&mut corpus);
+    hodlcoin_corpus(&mut corpus);
+    sigmafi_corpus(&mut corpus);
+    mewlock_corpus(&mut corpus);
+    stake_recovery_corpus(&mut corpus);
+    corpus
+}
+
+fn is_hex_len(s: &str, len: usize) -> bool {
+    s.len() == len && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Replace placeholder IDs and trees with deterministic valid values
+fn normalise_fixture(tx: &mut Eip12UnsignedTx) {
+    let mut ids: HashMap<String, String> = HashMap::new();
+    let mut fix_id = |id: &mut String| {
+        if !is_hex_len(id, 64) {
+            let next = hex32(0x1000 + ids.len() as u64);
+            *id = ids.entry(id.clone()).or_insert(next).clone();
+        }
+    };
+    // Placeholders name the tree they stand for: the user's wallet or a contract
+    let fix_tree = |tree: &mut String| {
+        if hex::decode(tree.as_str()).is_err() {
+            *tree = if tree.contains("user") {
+                USER_TREE.to_string()
+            } else {
+                SCRIPT_TREE.to_string()
+            };
+        }
+    };
+
+    for input in &mut tx.inputs {
+        fix_id(&mut input.box_id);
+        fix_tree(&mut input.ergo_tree);
+    }
+    for data_input in &mut tx.data_inputs {
+        fix_id(&mut data_input.box_id);
+    }
+    for output in &mut tx.outputs {
+        fix_tree(&mut output.ergo_tree);
+        for asset in &mut output.assets {
+            fix_id(&mut asset.token_id);
+        }
+    }
+}
+
+fn fixture_corpus() -> Vec<(String, Eip12UnsignedTx)> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    let dirs = [
+        "protocols/amm/tests/fixtures",
+        "protocols/dexy/tests/fixtures",
+        "protocols/lending/tests/fixtures",
+        "ergo-tx/tests/fixtures",
+    ];
+
+    let mut corpus = Vec::new();
+    for dir in dirs {
+        let mut paths: Vec<_> = std::fs::read_dir(root.join(dir))
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        paths.sort();
+        for path in paths {
+            let json = std::fs::read_to_string(&path).unwrap();
+            // Summary and quote fixtures are not transactions
+            let Ok(mut tx) = serde_json::from_str::<Eip12UnsignedTx>(&json) else {
+                continue;
+            };
+            normalise_fixture(&mut tx);
+            corpus.push((path.file_name().unwrap().to_string_lossy().into(), tx));
+        }
+    }
+    corpus
+}
+
+fn assert_fallback_matches_sigma_rust(name: &str, tx: &Eip12UnsignedTx) {
+    let fallback = reduce_transaction_fallback(tx)
+        .unwrap_or_else(|e| panic!("{}: fallback failed: {}", name, e));
+    let parts = parse_reduced_tx(&fallback, tx.inputs.len())
+        .unwrap_or_else(|e| panic!("{}: cannot parse fallback bytes: {}", name, e));
+
+    let expected = build_unsigned_transaction(tx, HEIGHT as u32)
+        .unwrap_or_else(|e| panic!("{}: cannot build unsigned tx: {}", name, e))
+        .bytes_to_sign()
+        .unwrap();
+    assert_eq!(
+        parts.bytes_to_sign, expected,
+        "{}: fallback bytes_to_sign differ from sigma-rust",
+        name
+    );
+
+    // Every P2PK input must be asked for its key; script inputs sign nothing
+    let mut expected_signers: Vec<String> = Vec::new();
+    for input in &tx.inputs {
+        if let Some(pubkey) = input.ergo_tree.strip_prefix("0008cd") {
+            if pubkey.len() == 66 && !expected_signers.iter().any(|s| s == pubkey) {
+                expected_signers.push(pubkey.to_string());
+            }
+        }
+    }
+    let check = check_fallback_signers(tx, &fallback).unwrap();
+    assert_eq!(check.signers, expected_signers, "{}: signers", name);
+    assert!(
+        check.missing_signers.is_empty(),
+        "{}: missing signers {:?}",
+        name,
+        check.missing_signers
+    );
+}
+
+#[test]
+fn builder_corpus_fallback_matches_sigma_rust() {
+    for cfg in [DevFeeConfig::disabled(), DevFeeConfig::enabled_default()] {
+        let corpus = with_test_dev_fee(cfg, builder_corpus);
+        assert!(corpus.len() > 40, "corpus unexpectedly small");
+        for (name, tx) in &corpus {
+            assert_fallback_matches_sigma_rust(name, tx);
+        }
+    }
+}
+
+#[test]
+fn protocol_builder_corpus_fallback_matches_sigma_rust() {
+    for cfg in [DevFeeConfig::disabled(), DevFeeConfig::enabled_default()] {
+        let corpus = with_test_dev_fee(cfg, protocol_builder_corpus);
+        assert_eq!(corpus.len(), 10);
+        for (name, tx) in &corpus {
+            assert_fallback_matches_sigma_rust(name, tx);
+        }
+    }
+}
+
+#[test]
+fn protocol_fixture_corpus_fallback_matches_sigma_rust() {
+    let corpus = fixture_corpus();
+    assert!(corpus.len() >= 10, "expected protocol golden fixtures");
+    for (name, tx) in &corpus {
+        assert_fallback_matches_sigma_rust(name, tx);
+    }
+}
@@ -218,6 +218,24 @@ pub mod swap_template_bytes {
         LazyLock::new(|| compute_template(super::swap_templates::N2T_SWAP_BUY_TEMPLATE));
 }
 
+/// Pre-computed ErgoTree template bytes for LP order matching.
+pub mod lp_template_bytes {
+    use ergo_lib::ergotree_ir::ergo_tree::ErgoTree;
+    use ergo_lib::ergotree_ir::serialization::SigmaSerializable;
+    use std::sync::LazyLock;
+
+    fn compute_template(hex_str: &str) -> Vec<u8> {
+        let bytes = hex::decode(hex_str).expect("invalid template hex");
+        let tree = ErgoTree::sigma_parse_bytes(&bytes).expect("failed to parse ErgoTree");
+        tree.template_bytes().expect("failed to get template bytes")
+    }
+
+    pub static N2T_DEPOSIT: LazyLock<Vec<u8>> =
+        LazyLock::new(|| compute_template(super::lp_templates::N2T_DEPOSIT_TEMPLATE));
+    pub static N2T_REDEEM: LazyLock<Vec<u8>> =
+        LazyLock::new(|| compute_template(super::lp_templates::N2T_REDEEM_TEMPLATE));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
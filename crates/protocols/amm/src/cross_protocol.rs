@@ -5,7 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::router::{find_best_routes, PoolGraph, Route};
+use crate::router::{find_best_routes, PoolGraph, Route, RouteHop};
 
 /// An acquisition option for a target token.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,10 +55,22 @@ pub struct SigmaUsdParams {
     pub reserve_ratio_pct: f64,
 }
 
-/// Compare ERG -> target token acquisition across DEX routes and SigmaUSD mint.
+const SPECTRUM: &str = "Spectrum DEX";
+
+/// Spectrum pools report their pool type; liquidity sources their protocol.
+fn hop_venue(hop: &RouteHop) -> &str {
+    match hop.pool_type.as_str() {
+        "N2T" | "T2T" => SPECTRUM,
+        protocol => protocol,
+    }
+}
+
+/// Compare ERG -> target token acquisition across routes and SigmaUSD mint.
 ///
-/// Returns ranked options. The `sigmausd_params` is `Some` only when the target
-/// token is SigUSD.
+/// Routes through liquidity sources in the graph (banks, Dexy LP) are labelled
+/// with their protocols. `sigmausd_params` is the stand-alone SigUSD mint
+/// option for graphs without a SigmaUSD source; pass `None` when the graph
+/// already has one.
 pub fn compare_acquisition(
     graph: &PoolGraph,
     target_token_id: &str,
@@ -79,8 +91,17 @@ pub fn compare_acquisition(
     );
 
     for (i, route) in routes.iter().enumerate() {
-        let hop_desc = if route.hops.len() == 1 {
+        let mut venues: Vec<&str> = Vec::new();
+        for hop in &route.hops {
+            let venue = hop_venue(hop);
+            if !venues.contains(&venue) {
+                venues.push(venue);
+            }
+        }
+        let hop_desc = if route.hops.len() == 1 && venues == [SPECTRUM] {
             "Direct DEX swap".to_string()
+        } else if route.hops.len() == 1 {
+            format!("Direct via {}", venues[0])
         } else {
             let intermediates: Vec<String> = route.hops[..route.hops.len() - 1]
                 .iter()
@@ -100,7 +121,7 @@ pub fn compare_acquisition(
         };
 
         options.push(AcquisitionOption {
-            protocol: "Spectrum DEX".to_string(),
+            protocol: venues.join(" + "),
             description: format!("{} (route #{})", hop_desc, i + 1),
             erg_cost_nano: input_erg_nano,
             output_amount: route.total_output,
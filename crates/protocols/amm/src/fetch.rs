@@ -305,7 +305,7 @@ fn extract_i64_constant(tree: &ErgoTree, idx: usize, name: &str) -> Result<i64,
     }
 }
 
-fn extract_coll_byte_constant(
+pub(crate) fn extract_coll_byte_constant(
     tree: &ErgoTree,
     idx: usize,
     name: &str,
@@ -434,7 +434,7 @@ pub async fn find_pending_orders(
     Ok(orders)
 }
 
-fn is_pool_ergo_tree(ergo_tree_hex: &str) -> bool {
+pub(crate) fn is_pool_ergo_tree(ergo_tree_hex: &str) -> bool {
     let tree_bytes = match hex::decode(ergo_tree_hex) {
         Ok(b) => b,
         Err(_) => return false,
@@ -0,0 +1,297 @@
+//! Common transaction intents for Spectrum AMM builders.
+//!
+//! Summaries only carry display names; the pool supplies token IDs and
+//! decimals, so each `intent` method takes the pool the builder used.
+
+use citadel_core::{IntentAsset, TxIntent};
+
+use crate::direct_swap::DirectSwapSummary;
+use crate::lp_deposit::LpDepositSummary;
+use crate::lp_order::LpOrderSummary;
+use crate::lp_redeem::LpRedeemSummary;
+use crate::pool_setup::{PoolBootstrapSummary, PoolLaunchBuild, PoolSetupParams};
+use crate::refund::{BatchRefundSummary, RefundSummary};
+use crate::router::ERG_TOKEN_ID;
+use crate::state::{AmmPool, PoolType, SwapInput};
+use crate::tx_builder::SwapTxSummary;
+
+const PROTOCOL: &str = "Spectrum";
+
+impl AmmPool {
+    /// Token ID of the X side (`"ERG"` for N2T pools).
+    pub fn x_token_id(&self) -> &str {
+        match (&self.pool_type, &self.token_x) {
+            (PoolType::T2T, Some(x)) => &x.token_id,
+            _ => ERG_TOKEN_ID,
+        }
+    }
+
+    /// `amount` of `token_id` with the pool's name/decimals for that token.
+    pub fn intent_asset(&self, token_id: &str, amount: u64) -> IntentAsset {
+        if token_id == ERG_TOKEN_ID {
+            return IntentAsset::erg(amount);
+        }
+        let side = std::iter::once(&self.token_y)
+            .chain(self.token_x.as_ref())
+            .find(|t| t.token_id == token_id);
+        match side {
+            Some(t) => IntentAsset {
+                name: t.name.clone(),
+                decimals: t.decimals,
+                ..IntentAsset::token(token_id, amount)
+            },
+            None if token_id == self.lp_token_id => IntentAsset {
+                name: Some(format!("{} LP", self.pair_name())),
+                ..IntentAsset::token(token_id, amount)
+            },
+            None => IntentAsset::token(token_id, amount),
+        }
+    }
+
+    /// The opposite side of the pair from `token_id`.
+    pub fn other_token_id(&self, token_id: &str) -> &str {
+        if token_id == self.token_y.token_id {
+            self.x_token_id()
+        } else {
+            &self.token_y.token_id
+        }
+    }
+
+    /// e.g. `"ERG/SigUSD"`.
+    pub fn pair_name(&self) -> String {
+        let x = self.intent_asset(self.x_token_id(), 0).label();
+        let y = self.intent_asset(&self.token_y.token_id, 0).label();
+        format!("{}/{}", x, y)
+    }
+}
+
+fn input_token_id(input: &SwapInput) -> &str {
+    match input {
+        SwapInput::Erg { .. } => ERG_TOKEN_ID,
+        SwapInput::Token { token_id, .. } => token_id,
+    }
+}
+
+fn swap_intent<'a>(pool: &'a AmmPool, input: &SwapInput, amount_in: u64) -> (TxIntent, &'a str) {
+    let token_in = input_token_id(input);
+    let token_out = pool.other_token_id(token_in);
+    let asset_in = pool.intent_asset(token_in, amount_in);
+    let action = format!(
+        "Swap {} for {}",
+        asset_in.label(),
+        pool.intent_asset(token_out, 0).label()
+    );
+    let intent = TxIntent::new(PROTOCOL, action)
+        .pays(asset_in)
+        .counterparty("pool", format!("{} pool", pool.pair_name()));
+    (intent, token_out)
+}
+
+impl SwapTxSummary {
+    /// Proxy swap order: executed later by an off-chain bot at `min_output` or better.
+    pub fn intent(&self, pool: &AmmPool, input: &SwapInput) -> TxIntent {
+        let (intent, token_out) = swap_intent(pool, input, self.input_amount);
+        intent
+            .receives(pool.intent_asset(token_out, self.min_output))
+            .min_out(pool.intent_asset(token_out, self.min_output), None)
+            .miner_fee(self.miner_fee)
+            .protocol_fee(self.execution_fee)
+            .citadel_fee(self.citadel_fee_nano)
+            .counterparty("proxy", "Spectrum swap order")
+            .note("Executed by Spectrum off-chain bots; refundable until filled")
+    }
+}
+
+impl DirectSwapSummary {
+    /// Direct swap against the pool box, settled in this transaction.
+    pub fn intent(&self, pool: &AmmPool, input: &SwapInput) -> TxIntent {
+        let (intent, token_out) = swap_intent(pool, input, self.input_amount);
+        intent
+            .receives(pool.intent_asset(token_out, self.output_amount))
+            .min_out(pool.intent_asset(token_out, self.min_output), None)
+            .miner_fee(self.miner_fee)
+            .citadel_fee(self.citadel_fee_nano)
+    }
+}
+
+impl LpDepositSummary {
+    pub fn intent(&self, pool: &AmmPool) -> TxIntent {
+        TxIntent::new(PROTOCOL, format!("Add liquidity to {}", pool.pair_name()))
+            .pays(pool.intent_asset(pool.x_token_id(), self.erg_deposited))
+            .pays(pool.intent_asset(&pool.token_y.token_id, self.token_deposited))
+            .receives(pool.intent_asset(&pool.lp_token_id, self.lp_reward))
+            .miner_fee(self.miner_fee)
+            .counterparty("pool", format!("{} pool", pool.pair_name()))
+    }
+}
+
+impl LpRedeemSummary {
+    pub fn intent(&self, pool: &AmmPool) -> TxIntent {
+        TxIntent::new(
+            PROTOCOL,
+            format!("Remove liquidity from {}", pool.pair_name()),
+        )
+        .pays(pool.intent_asset(&pool.lp_token_id, self.lp_redeemed))
+        .receives(pool.intent_asset(pool.x_token_id(), self.erg_received))
+        .receives(pool.intent_asset(&pool.token_y.token_id, self.token_received))
+        .miner_fee(self.miner_fee)
+        .counterparty("pool", format!("{} pool", pool.pair_name()))
+    }
+}
+
+impl LpOrderSummary {
+    /// Proxy LP order: the off-chain bot settles it against the pool.
+    pub fn intent(&self, pool: &AmmPool) -> TxIntent {
+        let x = pool.intent_asset(pool.x_token_id(), self.erg_amount);
+        let y = pool.intent_asset(&pool.token_y.token_id, self.token_amount);
+        let lp = pool.intent_asset(&pool.lp_token_id, self.lp_amount);
+        let intent = if self.operation == "Redeem" {
+            TxIntent::new(
+                PROTOCOL,
+                format!("Remove liquidity from {}", pool.pair_name()),
+            )
+            .pays(lp)
+            .receives(x)
+            .receives(y)
+        } else {
+            TxIntent::new(PROTOCOL, format!("Add liquidity to {}", pool.pair_name()))
+                .pays(x)
+                .pays(y)
+                .receives(lp)
+        };
+        intent
+            .miner_fee(self.miner_fee)
+            .protocol_fee(self.execution_fee)
+            .counterparty("proxy", format!("Spectrum {} order", self.operation))
+            .note("Executed by Spectrum off-chain bots; amounts are estimates")
+    }
+}
+
+impl RefundSummary {
+    pub fn intent(&self) -> TxIntent {
+        let mut intent = TxIntent::new(PROTOCOL, "Refund order")
+            .receives(IntentAsset::erg(self.refunded_erg))
+            .miner_fee(self.miner_fee)
+            .counterparty("proxy", format!("Order box {}", self.proxy_box_id));
+        for (token_id, amount) in &self.refunded_tokens {
+            intent = intent.receives(IntentAsset::token(token_id, *amount));
+        }
+        intent
+    }
+}
+
+impl BatchRefundSummary {
+    pub fn intent(&self) -> TxIntent {
+        let mut intent = TxIntent::new(
+            PROTOCOL,
+            format!("Refund {} orders", self.proxy_box_ids.len()),
+        )
+        .receives(IntentAsset::erg(self.refunded_erg))
+        .miner_fee(self.miner_fee);
+        for box_id in &self.proxy_box_ids {
+            intent = intent.counterparty("proxy", format!("Order box {}", box_id));
+        }
+        for (token_id, amount) in &self.refunded_tokens {
+            intent = intent.receives(IntentAsset::token(token_id, *amount));
+        }
+        intent
+    }
+}
+
+impl PoolBootstrapSummary {
+    pub fn intent(&self, params: &PoolSetupParams) -> TxIntent {
+        let x = match &params.x_token_id {
+            Some(id) => IntentAsset::token(id, self.x_amount),
+            None => IntentAsset::erg(self.x_amount),
+        };
+        TxIntent::new(PROTOCOL, format!("Create {} pool", self.pool_type))
+            .pays(x)
+            .pays(IntentAsset::token(&params.y_token_id, self.y_amount))
+            .receives(IntentAsset {
+                name: Some("LP".to_string()),
+                ..IntentAsset::token(&self.lp_token_id, self.user_lp_share)
+            })
+            .miner_fee(self.miner_fee)
+            .note(format!("Pool fee {}%", self.fee_percent))
+    }
+}
+
+impl PoolLaunchBuild {
+    /// Both txs as one action: the bootstrap's transfers plus the create fee.
+    pub fn intent(&self, params: &PoolSetupParams) -> TxIntent {
+        self.bootstrap_summary
+            .intent(params)
+            .miner_fee(self.bootstrap_summary.miner_fee * 2)
+            .note(format!("Pool NFT {}", self.create_summary.pool_nft_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::TokenAmount;
+
+    fn pool() -> AmmPool {
+        AmmPool {
+            pool_id: "p".repeat(64),
+            pool_type: PoolType::N2T,
+            box_id: "b".repeat(64),
+            erg_reserves: Some(100_000_000_000),
+            token_x: None,
+            token_y: TokenAmount {
+                token_id: "t".repeat(64),
+                amount: 1_000_000,
+                decimals: Some(2),
+                name: Some("SigUSD".to_string()),
+            },
+            lp_token_id: "l".repeat(64),
+            lp_circulating: 1_000,
+            fee_num: 997,
+            fee_denom: 1000,
+        }
+    }
+
+    #[test]
+    fn test_direct_swap_intent() {
+        let pool = pool();
+        let summary = DirectSwapSummary {
+            input_amount: 1_000_000_000,
+            input_token: "ERG".to_string(),
+            output_amount: 987,
+            min_output: 982,
+            output_token: "SigUSD".to_string(),
+            miner_fee: 1_100_000,
+            citadel_fee_nano: 0,
+            total_erg_cost: 1_001_100_000,
+        };
+        let intent = summary.intent(
+            &pool,
+            &SwapInput::Erg {
+                amount: 1_000_000_000,
+            },
+        );
+        assert_eq!(intent.action, "Swap ERG for SigUSD");
+        assert_eq!(
+            intent.headline(),
+            "Spectrum: Swap ERG for SigUSD (1 ERG → 9.87 SigUSD)"
+        );
+        assert!(intent.message().contains("Minimum received: 9.82 SigUSD"));
+        assert_eq!(intent.counterparties[0].name, "ERG/SigUSD pool");
+    }
+
+    #[test]
+    fn test_lp_redeem_intent() {
+        let pool = pool();
+        let summary = LpRedeemSummary {
+            lp_redeemed: 10,
+            erg_received: 1_000_000_000,
+            token_received: 100,
+            token_name: "SigUSD".to_string(),
+            miner_fee: 1_100_000,
+            total_erg_cost: 1_100_000,
+        };
+        let intent = summary.intent(&pool);
+        assert_eq!(intent.assets_in[0].label(), "ERG/SigUSD LP");
+        assert_eq!(intent.assets_out.len(), 2);
+    }
+}
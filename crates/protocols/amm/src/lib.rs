@@ -9,12 +9,16 @@ pub mod constants;
 pub mod cross_protocol;
 pub mod direct_swap;
 pub mod fetch;
+pub mod intent;
 pub mod lp_deposit;
 pub mod lp_order;
 pub mod lp_redeem;
+pub mod mempool;
+pub mod pool_history;
 pub mod pool_setup;
 pub mod refund;
 pub mod router;
+pub mod stale_orders;
 pub mod state;
 pub mod tx_builder;
 
@@ -28,7 +32,9 @@ pub use calculator::{
     max_erg_extractable, max_token_in_for_erg_out, quote_swap, would_breach_pool_min_erg,
     MIN_BOX_VALUE as AMM_MIN_BOX_VALUE,
 };
-pub use constants::{erg, fees, lp, pool_indices, pool_templates, swap_template_bytes};
+pub use constants::{
+    erg, fees, lp, lp_template_bytes, pool_indices, pool_templates, swap_template_bytes,
+};
 pub use cross_protocol::{
     compare_acquisition, AcquisitionComparison, AcquisitionOption, SigmaUsdParams,
 };
@@ -38,28 +44,47 @@ pub use lp_order::{
     build_lp_deposit_order_eip12, build_lp_redeem_order_eip12, LpOrderBuildResult, LpOrderSummary,
 };
 pub use lp_redeem::{build_lp_redeem_eip12, LpRedeemBuildResult, LpRedeemSummary};
+pub use pool_history::{
+    derive_pool_events, pool_analytics, trace_pool_history, PoolAnalytics, PoolEvent,
+    PoolEventKind, PoolSnapshot,
+};
 pub use pool_setup::{
-    build_pool_bootstrap_eip12, build_pool_create_eip12, PoolBootstrapResult,
-    PoolBootstrapSummary, PoolCreateResult, PoolCreateSummary, PoolSetupParams,
+    build_pool_bootstrap_eip12, build_pool_create_eip12, build_pool_launch_chain,
+    plan_pool_launch, PoolBootstrapResult, PoolBootstrapSummary, PoolCreateResult,
+    PoolCreateSummary, PoolLaunchBuild, PoolLaunchLeg, PoolLaunchPlan, PoolSetupParams,
 };
 pub use fetch::{
     discover_n2t_pools, discover_pools, discover_t2t_pools, find_mempool_swaps,
     find_pending_orders, match_swap_template, parse_n2t_pool, parse_t2t_pool,
 };
-pub use refund::{build_refund_tx_eip12, RefundBuildResult, RefundSummary};
+pub use mempool::{
+    discover_pools_projected, fetch_pool_mempool, project_pools, MempoolProjection,
+    PendingPoolState,
+};
+pub use refund::{
+    build_batch_refund_tx_eip12, build_batch_refund_txs_eip12, build_refund_tx_eip12,
+    BatchRefundBuildResult, BatchRefundSummary, RefundBuildResult, RefundSummary,
+    MAX_BATCH_REFUND_INPUTS,
+};
+pub use stale_orders::{
+    assess_order, parse_user_order, scan_user_orders, OrderKind, StaleReason, UserOrder,
+    DEFAULT_STALE_ORDER_BLOCKS,
+};
 pub use state::{
     AmmError, AmmPool, MempoolSwap, PendingSwapOrder, PoolType, SwapInput, SwapOrderType,
     SwapQuote, SwapRequest, TokenAmount,
 };
 pub use router::{
+    add_liquidity_sources, BankDirection, BankSource, CfmmSource, LiquiditySource, SourceToken,
     build_pool_graph, build_pool_graph_with_limit, calculate_all_depth_tiers,
     ensure_direct_pair_edges,
-    calculate_depth_tiers, find_best_routes, find_best_routes_by_output, find_paths,
-    make_route_quote, max_executable_swap_to_erg, max_swap_hint_if_needed, optimize_split,
+    calculate_depth_tiers, find_best_routes, find_best_routes_by_output, find_k_best_paths,
+    find_k_best_paths_by_output, find_paths, make_route_quote, max_executable_swap_to_erg,
+    max_swap_hint_if_needed, optimize_split,
     optimize_split_detailed, quote_route, quote_route_reverse, DepthTiers, MaxSwapHint, PoolEdge,
     PoolGraph, Route, RouteHop, RouteQuote, SplitAllocation, SplitAllocationDetail, SplitRoute,
-    SplitRouteDetail, DEFAULT_MAX_POOLS_PER_PAIR, DEFAULT_MIN_LIQUIDITY_NANO, ERG_TOKEN_ID,
-    MINER_FEE_PER_HOP, miner_fees_for_hops,
+    SplitRouteDetail, DEFAULT_MAX_POOLS_PER_PAIR, DEFAULT_MIN_LIQUIDITY_NANO, DEFAULT_PATH_BEAM,
+    ERG_TOKEN_ID, MINER_FEE_PER_HOP, miner_fees_for_hops,
     calculate_oracle_arb_snapshot, OracleArbSnapshot, OracleArbWindow,
     find_circular_arbs, find_cycles, CircularArb, CircularArbSnapshot,
 };
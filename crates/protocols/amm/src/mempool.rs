@@ -0,0 +1,293 @@
+//! Mempool projection: pools advanced past swaps that are still unconfirmed.
+//!
+//! Every pool transition spends the pool box and recreates it with the same
+//! NFT, so following unconfirmed pool-NFT outputs from the confirmed box gives
+//! the state the next swap will actually see.
+
+use std::collections::HashMap;
+
+use ergo_node_client::mempool::{MempoolIndex, PendingSuccessor};
+use ergo_tx::Eip12InputBox;
+use serde::Serialize;
+
+use crate::constants::pool_templates;
+use crate::fetch::{discover_pools, is_pool_ergo_tree, parse_n2t_pool, parse_t2t_pool};
+use crate::router::RouteQuote;
+use crate::state::{AmmError, AmmPool, PoolType};
+
+/// A pool whose latest box is an unconfirmed mempool output.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingPoolState {
+    pub pool_id: String,
+    /// Pool box in the confirmed UTXO set.
+    pub confirmed_box_id: String,
+    /// Latest pool box after all pending transitions.
+    pub box_id: String,
+    /// Unconfirmed transactions between the two, oldest first.
+    pub pending_tx_ids: Vec<String>,
+    /// Latest pool box, ready to spend in a chained transaction.
+    #[serde(skip)]
+    pub pool_box: Eip12InputBox,
+}
+
+/// Pools projected forward by [`project_pools`], keyed by pool NFT.
+#[derive(Debug, Clone, Default)]
+pub struct MempoolProjection {
+    pub pending: HashMap<String, PendingPoolState>,
+}
+
+impl MempoolProjection {
+    pub fn get(&self, pool_id: &str) -> Option<&PendingPoolState> {
+        self.pending.get(pool_id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Marks `quote` with the pools it prices from unconfirmed boxes.
+    pub fn flag_route(&self, quote: &mut RouteQuote) {
+        quote.unconfirmed_pools = quote
+            .route
+            .hops
+            .iter()
+            .filter(|hop| self.pending.contains_key(&hop.pool_id))
+            .map(|hop| hop.pool_id.clone())
+            .collect();
+    }
+}
+
+/// Parses the pending pool box `successor`. The walk that found it only
+/// crosses pool-contract boxes, so the tree is not checked again here.
+fn parse_projected_pool(
+    pool: &AmmPool,
+    successor: &PendingSuccessor,
+) -> Result<(AmmPool, Eip12InputBox), String> {
+    let pool_box = Eip12InputBox::from_ergo_box(
+        &successor.ergo_box,
+        successor.tx_id.clone(),
+        successor.index,
+    );
+
+    let mut projected = match pool.pool_type {
+        PoolType::N2T => parse_n2t_pool(&successor.ergo_box),
+        PoolType::T2T => parse_t2t_pool(&successor.ergo_box),
+    }
+    .map_err(|e| e.to_string())?;
+
+    projected.token_y.name = pool.token_y.name.clone();
+    projected.token_y.decimals = pool.token_y.decimals;
+    if let (Some(x), Some(orig)) = (projected.token_x.as_mut(), pool.token_x.as_ref()) {
+        x.name = orig.name.clone();
+        x.decimals = orig.decimals;
+    }
+
+    Ok((projected, pool_box))
+}
+
+/// Replaces each pool in `pools` with its latest unconfirmed state. Pools with
+/// no pending transition, or whose pending box fails to parse, are untouched.
+/// The NFT is followed only through boxes under a pool contract, so a
+/// transition that moves it elsewhere ends the projection at the box before.
+pub fn project_pools(
+    pools: &mut [AmmPool],
+    mempool_txs: &[serde_json::Value],
+) -> MempoolProjection {
+    let index = MempoolIndex::new(mempool_txs);
+    let mut projection = MempoolProjection::default();
+    if index.is_empty() {
+        return projection;
+    }
+
+    for pool in pools.iter_mut() {
+        let Some(successor) = index.follow_singleton_where(&pool.box_id, &pool.pool_id, |o| {
+            o["ergoTree"].as_str().is_some_and(is_pool_ergo_tree)
+        }) else {
+            continue;
+        };
+        match parse_projected_pool(pool, &successor) {
+            Ok((projected, pool_box)) => {
+                projection.pending.insert(
+                    pool.pool_id.clone(),
+                    PendingPoolState {
+                        pool_id: pool.pool_id.clone(),
+                        confirmed_box_id: pool.box_id.clone(),
+                        box_id: projected.box_id.clone(),
+                        pending_tx_ids: successor.pending_tx_ids,
+                        pool_box,
+                    },
+                );
+                *pool = projected;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to parse pending pool box for {}: {}",
+                    &pool.pool_id[..pool.pool_id.len().min(8)],
+                    e
+                );
+            }
+        }
+    }
+
+    tracing::info!(
+        "Projected {} pool(s) past mempool swaps",
+        projection.pending.len()
+    );
+    projection
+}
+
+/// Unconfirmed transactions touching N2T or T2T pool boxes. A failed query
+/// yields no transactions so callers fall back to confirmed state.
+pub async fn fetch_pool_mempool(node: &ergo_node_client::NodeClient) -> Vec<serde_json::Value> {
+    let mut txs: Vec<serde_json::Value> = Vec::new();
+    for tree in [
+        pool_templates::N2T_POOL_TEMPLATE,
+        pool_templates::T2T_POOL_TEMPLATE,
+    ] {
+        match node.get_unconfirmed_by_ergo_tree(tree).await {
+            Ok(batch) => {
+                for tx in batch {
+                    if !txs.iter().any(|t| t["id"] == tx["id"]) {
+                        txs.push(tx);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Pool mempool query failed: {}", e),
+        }
+    }
+    txs
+}
+
+/// [`discover_pools`] with every pool projected past its pending swaps.
+pub async fn discover_pools_projected(
+    node: &ergo_node_client::NodeClient,
+) -> Result<(Vec<AmmPool>, MempoolProjection), AmmError> {
+    let mut pools = discover_pools(node).await?;
+    let txs = fetch_pool_mempool(node).await;
+    let projection = project_pools(&mut pools, &txs);
+    Ok((pools, projection))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ergo_lib::ergo_chain_types::Digest32;
+    use ergo_lib::ergotree_ir::chain::ergo_box::box_value::BoxValue;
+    use ergo_lib::ergotree_ir::chain::ergo_box::{BoxTokens, ErgoBox, NonMandatoryRegisters};
+    use ergo_lib::ergotree_ir::chain::token::{Token, TokenAmount, TokenId};
+    use ergo_lib::ergotree_ir::chain::tx_id::TxId;
+    use ergo_lib::ergotree_ir::ergo_tree::ErgoTree;
+    use ergo_lib::ergotree_ir::serialization::SigmaSerializable;
+
+    const P2PK_TREE: &str =
+        "0008cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn tx_id(n: u8) -> String {
+        format!("{:02x}", n).repeat(32)
+    }
+
+    fn token(n: u8, amount: u64) -> Token {
+        Token {
+            token_id: TokenId::from(Digest32::from([n; 32])),
+            amount: TokenAmount::try_from(amount).unwrap(),
+        }
+    }
+
+    /// N2T pool box holding `y` of token Y, the first output of tx `tx`.
+    fn pool_box(tx: u8, tree: &str, y: u64) -> ErgoBox {
+        let tree = ErgoTree::sigma_parse_bytes(&hex::decode(tree).unwrap()).unwrap();
+        let tokens =
+            BoxTokens::from_vec(vec![token(0xaa, 1), token(0xbb, 1_000_000), token(0xcc, y)])
+                .unwrap();
+        ErgoBox::new(
+            BoxValue::new(100_000_000_000).unwrap(),
+            tree,
+            Some(tokens),
+            NonMandatoryRegisters::empty(),
+            1_000_000,
+            TxId::from(Digest32::from([tx; 32])),
+            0,
+        )
+        .unwrap()
+    }
+
+    fn box_id(b: &ErgoBox) -> String {
+        hex::encode(b.box_id().as_ref())
+    }
+
+    /// Mempool tx `id` spending `input` and a user box, creating `pool_output`.
+    fn mempool_tx(id: u8, input: &str, pool_output: &ErgoBox) -> serde_json::Value {
+        serde_json::json!({
+            "id": tx_id(id),
+            "inputs": [{ "boxId": input }, { "boxId": tx_id(id ^ 0xff) }],
+            "outputs": [serde_json::to_value(pool_output).unwrap()],
+        })
+    }
+
+    #[test]
+    fn test_project_pools_follows_chained_swaps() {
+        let tree = pool_templates::N2T_POOL_TEMPLATE;
+        let confirmed = pool_box(0x10, tree, 1_000);
+        let first = pool_box(0x11, tree, 1_100);
+        let second = pool_box(0x12, tree, 1_200);
+        // Out of order on purpose: the walk follows spends, not mempool order
+        let mempool = vec![
+            mempool_tx(0x12, &box_id(&first), &second),
+            mempool_tx(0x11, &box_id(&confirmed), &first),
+        ];
+
+        let mut pools = vec![parse_n2t_pool(&confirmed).unwrap()];
+        let projection = project_pools(&mut pools, &mempool);
+
+        let pending = projection.get(&pools[0].pool_id).expect("projected");
+        assert_eq!(pending.confirmed_box_id, box_id(&confirmed));
+        assert_eq!(pending.box_id, box_id(&second));
+        assert_eq!(pending.pending_tx_ids, vec![tx_id(0x11), tx_id(0x12)]);
+        assert_eq!(pending.pool_box.transaction_id, tx_id(0x12));
+        assert_eq!(pools[0].box_id, box_id(&second));
+        assert_eq!(pools[0].token_y.amount, 1_200);
+    }
+
+    #[test]
+    fn test_project_pools_stops_before_non_pool_hop() {
+        let tree = pool_templates::N2T_POOL_TEMPLATE;
+        let confirmed = pool_box(0x10, tree, 1_000);
+        let first = pool_box(0x11, tree, 1_100);
+        // The NFT leaves the pool contract, then comes back
+        let moved = pool_box(0x12, P2PK_TREE, 1_100);
+        let returned = pool_box(0x13, tree, 1_300);
+        let mempool = vec![
+            mempool_tx(0x11, &box_id(&confirmed), &first),
+            mempool_tx(0x12, &box_id(&first), &moved),
+            mempool_tx(0x13, &box_id(&moved), &returned),
+        ];
+
+        let mut pools = vec![parse_n2t_pool(&confirmed).unwrap()];
+        let projection = project_pools(&mut pools, &mempool);
+
+        let pending = projection.get(&pools[0].pool_id).expect("projected");
+        assert_eq!(pending.box_id, box_id(&first));
+        assert_eq!(pending.pending_tx_ids, vec![tx_id(0x11)]);
+        assert_eq!(pools[0].token_y.amount, 1_100);
+    }
+
+    #[test]
+    fn test_project_pools_leaves_pool_whose_nft_leaves_the_contract() {
+        let confirmed = pool_box(0x10, pool_templates::N2T_POOL_TEMPLATE, 1_000);
+        let moved = pool_box(0x11, P2PK_TREE, 1_000);
+        let mempool = vec![mempool_tx(0x11, &box_id(&confirmed), &moved)];
+
+        let mut pools = vec![parse_n2t_pool(&confirmed).unwrap()];
+        let projection = project_pools(&mut pools, &mempool);
+
+        assert!(projection.is_empty());
+        assert_eq!(pools[0].box_id, box_id(&confirmed));
+    }
+
+    #[test]
+    fn test_project_pools_without_mempool_is_noop() {
+        let mut pools: Vec<AmmPool> = Vec::new();
+        let projection = project_pools(&mut pools, &[]);
+        assert!(projection.is_empty());
+    }
+}
@@ -0,0 +1,436 @@
+//! Pool history: box-chain snapshots, per-swap events and fee APR.
+//!
+//! A pool box is spent and recreated by every swap, deposit and redeem, so the
+//! chain of boxes holding the pool NFT is the pool's full trade history.
+//! Amounts are in raw units of the pool's X side (nanoERG for N2T pools).
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::pool_indices::{n2t, t2t};
+use crate::state::{AmmError, AmmPool, PoolType};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const YEAR_MS: f64 = 365.0 * DAY_MS as f64;
+
+/// Pool reserves as of one box in the NFT chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolSnapshot {
+    pub box_id: String,
+    /// Transaction that created this box.
+    pub tx_id: String,
+    pub height: u64,
+    /// Block timestamp in milliseconds.
+    pub timestamp: u64,
+    pub reserves_x: u64,
+    pub reserves_y: u64,
+    pub lp_locked: u64,
+}
+
+impl PoolSnapshot {
+    /// Reserves from a node box JSON (`/blockchain/box/byId`).
+    pub fn from_box_json(
+        pool_type: PoolType,
+        box_json: &serde_json::Value,
+        timestamp: u64,
+    ) -> Option<Self> {
+        let assets = box_json["assets"].as_array()?;
+        let amount = |i: usize| assets.get(i).and_then(|a| a["amount"].as_u64());
+        let (reserves_x, reserves_y, lp_locked) = match pool_type {
+            PoolType::N2T => (
+                box_json["value"].as_u64()?,
+                amount(n2t::INDEX_Y)?,
+                amount(n2t::INDEX_LP)?,
+            ),
+            PoolType::T2T => (
+                amount(t2t::INDEX_X)?,
+                amount(t2t::INDEX_Y)?,
+                amount(t2t::INDEX_LP)?,
+            ),
+        };
+        Some(Self {
+            box_id: box_json["boxId"].as_str()?.to_string(),
+            tx_id: box_json["transactionId"].as_str()?.to_string(),
+            height: box_json["inclusionHeight"]
+                .as_u64()
+                .or_else(|| box_json["settlementHeight"].as_u64())
+                .or_else(|| box_json["creationHeight"].as_u64())
+                .unwrap_or(0),
+            timestamp,
+            reserves_x,
+            reserves_y,
+            lp_locked,
+        })
+    }
+
+    /// Pool value in X units: both sides are worth the same at the pool price.
+    pub fn tvl_x(&self) -> u64 {
+        self.reserves_x.saturating_mul(2)
+    }
+
+    pub fn price_x_per_y(&self) -> f64 {
+        if self.reserves_y == 0 {
+            return 0.0;
+        }
+        self.reserves_x as f64 / self.reserves_y as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolEventKind {
+    /// X in, Y out.
+    SwapXForY,
+    /// Y in, X out.
+    SwapYForX,
+    Deposit,
+    Redeem,
+}
+
+/// One pool transition between consecutive snapshots.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolEvent {
+    pub tx_id: String,
+    pub height: u64,
+    pub timestamp: u64,
+    pub kind: PoolEventKind,
+    /// X side of the trade (0 for deposits and redeems).
+    pub volume_x: u64,
+    /// Swap fee retained by the pool, valued in X.
+    pub fee_x: u64,
+    /// Pool price after the event.
+    pub price_x_per_y: f64,
+    pub tvl_x: u64,
+}
+
+/// Volume, fee income and LP APR over the traced history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolAnalytics {
+    pub pool_id: String,
+    pub pool_type: PoolType,
+    pub tvl_x: u64,
+    pub price_x_per_y: f64,
+    pub volume_x_24h: u64,
+    pub volume_x_7d: u64,
+    pub volume_x_30d: u64,
+    pub fees_x_24h: u64,
+    pub fees_x_7d: u64,
+    pub fees_x_30d: u64,
+    pub swaps_24h: usize,
+    /// Realized LP fee APR (percent) from fees earned over the window.
+    pub fee_apr_7d: Option<f64>,
+    pub fee_apr_30d: Option<f64>,
+    /// Oldest timestamp covered by the traced history. Windows reaching past
+    /// it are lower bounds.
+    pub history_start: u64,
+    pub events: Vec<PoolEvent>,
+}
+
+/// Classify each transition in `snapshots` (oldest first).
+pub fn derive_pool_events(
+    snapshots: &[PoolSnapshot],
+    fee_num: i32,
+    fee_denom: i32,
+) -> Vec<PoolEvent> {
+    let fee_denom = fee_denom.max(1) as u128;
+    let fee_part = fee_denom.saturating_sub(fee_num.max(0) as u128);
+
+    snapshots
+        .windows(2)
+        .filter_map(|pair| {
+            let (prev, cur) = (&pair[0], &pair[1]);
+            let dx = cur.reserves_x as i128 - prev.reserves_x as i128;
+            let dy = cur.reserves_y as i128 - prev.reserves_y as i128;
+
+            let (kind, volume_x, fee_x) = if cur.lp_locked != prev.lp_locked {
+                // LP tokens leave the pool on deposit and return on redeem
+                let kind = if cur.lp_locked < prev.lp_locked {
+                    PoolEventKind::Deposit
+                } else {
+                    PoolEventKind::Redeem
+                };
+                (kind, 0, 0)
+            } else if dx > 0 {
+                let fee = dx as u128 * fee_part / fee_denom;
+                (PoolEventKind::SwapXForY, dx as u64, fee as u64)
+            } else if dy > 0 {
+                // Fee is taken in Y; value it at the pre-swap price
+                let fee_y = dy as u128 * fee_part / fee_denom;
+                let fee = fee_y * prev.reserves_x as u128 / prev.reserves_y.max(1) as u128;
+                (
+                    PoolEventKind::SwapYForX,
+                    dx.unsigned_abs() as u64,
+                    fee as u64,
+                )
+            } else {
+                return None;
+            };
+
+            Some(PoolEvent {
+                tx_id: cur.tx_id.clone(),
+                height: cur.height,
+                timestamp: cur.timestamp,
+                kind,
+                volume_x,
+                fee_x,
+                price_x_per_y: cur.price_x_per_y(),
+                tvl_x: cur.tvl_x(),
+            })
+        })
+        .collect()
+}
+
+/// Aggregate `snapshots` (oldest first, ending at the current pool box) into
+/// windowed volume, fees and realized APR as of `now_ms`.
+pub fn pool_analytics(pool: &AmmPool, snapshots: &[PoolSnapshot], now_ms: u64) -> PoolAnalytics {
+    let events = derive_pool_events(snapshots, pool.fee_num, pool.fee_denom);
+    let latest = snapshots.last();
+
+    let window = |days: u64| {
+        let since = now_ms.saturating_sub(days * DAY_MS);
+        let in_window = events.iter().filter(move |e| e.timestamp >= since);
+        let volume: u64 = in_window.clone().map(|e| e.volume_x).sum();
+        let fees: u64 = in_window.clone().map(|e| e.fee_x).sum();
+        let swaps = in_window.filter(|e| e.volume_x > 0).count();
+        (volume, fees, swaps)
+    };
+    let (volume_x_24h, fees_x_24h, swaps_24h) = window(1);
+    let (volume_x_7d, fees_x_7d, _) = window(7);
+    let (volume_x_30d, fees_x_30d, _) = window(30);
+
+    let history_start = snapshots.first().map(|s| s.timestamp).unwrap_or(now_ms);
+
+    PoolAnalytics {
+        pool_id: pool.pool_id.clone(),
+        pool_type: pool.pool_type,
+        tvl_x: latest.map(PoolSnapshot::tvl_x).unwrap_or(0),
+        price_x_per_y: latest.map(PoolSnapshot::price_x_per_y).unwrap_or(0.0),
+        volume_x_24h,
+        volume_x_7d,
+        volume_x_30d,
+        fees_x_24h,
+        fees_x_7d,
+        fees_x_30d,
+        swaps_24h,
+        fee_apr_7d: fee_apr(snapshots, fees_x_7d, now_ms, 7),
+        fee_apr_30d: fee_apr(snapshots, fees_x_30d, now_ms, 30),
+        history_start,
+        events,
+    }
+}
+
+/// Fees over the covered part of the window against the time-weighted TVL,
+/// annualized. `None` without history inside the window.
+fn fee_apr(snapshots: &[PoolSnapshot], fees_x: u64, now_ms: u64, days: u64) -> Option<f64> {
+    let since = now_ms.saturating_sub(days * DAY_MS);
+    let start = snapshots.first()?.timestamp.max(since);
+    if now_ms <= start {
+        return None;
+    }
+
+    // Each snapshot's TVL holds until the next one (or now)
+    let mut weighted = 0.0;
+    for (i, snap) in snapshots.iter().enumerate() {
+        let from = snap.timestamp.max(start);
+        let to = snapshots
+            .get(i + 1)
+            .map(|n| n.timestamp)
+            .unwrap_or(now_ms)
+            .min(now_ms);
+        if to > from {
+            weighted += snap.tvl_x() as f64 * (to - from) as f64;
+        }
+    }
+    let span = (now_ms - start) as f64;
+    let avg_tvl = weighted / span;
+    if avg_tvl <= 0.0 {
+        return None;
+    }
+    Some(fees_x as f64 / avg_tvl * (YEAR_MS / span) * 100.0)
+}
+
+/// Walk the pool NFT's box chain backwards from `start_box_id`, newest first.
+/// Stops after `max_steps` boxes, at the first box in `known_box_ids` (already
+/// cached), or once a box is older than `min_timestamp`.
+///
+/// Also returns whether the walk ended on a known box, i.e. whether the
+/// snapshots join up with the cached ones.
+pub async fn trace_pool_history(
+    node: &ergo_node_client::NodeClient,
+    pool_type: PoolType,
+    pool_id: &str,
+    start_box_id: &str,
+    known_box_ids: &HashSet<String>,
+    max_steps: usize,
+    min_timestamp: u64,
+) -> Result<(Vec<PoolSnapshot>, bool), AmmError> {
+    let mut snapshots = Vec::new();
+    let mut current_box_id = start_box_id.to_string();
+
+    for _ in 0..max_steps {
+        if known_box_ids.contains(&current_box_id) {
+            break;
+        }
+        let current_box = node
+            .get_blockchain_box_by_id(&current_box_id)
+            .await
+            .map_err(|e| AmmError::NodeError(e.to_string()))?;
+        let Some(tx_id) = current_box["transactionId"].as_str() else {
+            break;
+        };
+        let tx = node
+            .get_transaction_by_id(tx_id)
+            .await
+            .map_err(|e| AmmError::NodeError(e.to_string()))?;
+        let timestamp = tx["timestamp"].as_u64().unwrap_or(0);
+
+        let Some(snapshot) = PoolSnapshot::from_box_json(pool_type, &current_box, timestamp) else {
+            break;
+        };
+        snapshots.push(snapshot);
+        if timestamp < min_timestamp {
+            break;
+        }
+
+        // Spectrum pool transitions spend the pool box as input 0, but check
+        // every input so bootstrap and foreign txs end the walk cleanly.
+        let mut prev_box_id = None;
+        for input in tx["inputs"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+            let Some(input_id) = input["boxId"].as_str() else {
+                continue;
+            };
+            let input_box = match input["assets"].is_array() {
+                true => input.clone(),
+                false => match node.get_blockchain_box_by_id(input_id).await {
+                    Ok(b) => b,
+                    Err(_) => continue,
+                },
+            };
+            let holds_nft = input_box["assets"]
+                .as_array()
+                .and_then(|a| a.first())
+                .is_some_and(|t| t["tokenId"].as_str() == Some(pool_id));
+            if holds_nft {
+                prev_box_id = Some(input_id.to_string());
+                break;
+            }
+        }
+        match prev_box_id {
+            Some(id) => current_box_id = id,
+            None => break,
+        }
+    }
+
+    let reached_known = known_box_ids.contains(&current_box_id);
+    Ok((snapshots, reached_known))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::TokenAmount;
+
+    fn snap(i: u64, x: u64, y: u64, lp: u64) -> PoolSnapshot {
+        PoolSnapshot {
+            box_id: format!("box{}", i),
+            tx_id: format!("tx{}", i),
+            height: 1000 + i,
+            timestamp: i * DAY_MS,
+            reserves_x: x,
+            reserves_y: y,
+            lp_locked: lp,
+        }
+    }
+
+    fn pool() -> AmmPool {
+        AmmPool {
+            pool_id: "pool".to_string(),
+            pool_type: PoolType::N2T,
+            box_id: "box3".to_string(),
+            erg_reserves: Some(1_000_000),
+            token_x: None,
+            token_y: TokenAmount {
+                token_id: "y".to_string(),
+                amount: 1_000_000,
+                decimals: None,
+                name: None,
+            },
+            lp_token_id: "lp".to_string(),
+            lp_circulating: 0,
+            fee_num: 997,
+            fee_denom: 1000,
+        }
+    }
+
+    #[test]
+    fn test_derive_events_classifies_transitions() {
+        let snaps = vec![
+            snap(0, 1_000_000, 1_000_000, 500),
+            snap(1, 1_100_000, 909_364, 500),
+            snap(2, 1_000_000, 1_000_000, 500),
+            snap(3, 2_000_000, 2_000_000, 400),
+            snap(4, 1_000_000, 1_000_000, 500),
+        ];
+        let events = derive_pool_events(&snaps, 997, 1000);
+        let kinds: Vec<_> = events.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PoolEventKind::SwapXForY,
+                PoolEventKind::SwapYForX,
+                PoolEventKind::Deposit,
+                PoolEventKind::Redeem,
+            ]
+        );
+        assert_eq!(events[0].volume_x, 100_000);
+        assert_eq!(events[0].fee_x, 300);
+        // 90_636 Y in -> 271 Y fee, valued at the pre-swap 1.21 X/Y
+        assert_eq!(events[1].volume_x, 100_000);
+        assert_eq!(events[1].fee_x, 327);
+        assert_eq!(events[2].volume_x, 0);
+    }
+
+    #[test]
+    fn test_analytics_windows_and_apr() {
+        // One swap a day for 10 days at constant TVL of 2_000_000
+        let snaps: Vec<PoolSnapshot> = (0..=10)
+            .map(|i| {
+                let x = if i % 2 == 0 { 1_000_000 } else { 1_100_000 };
+                let y = if i % 2 == 0 { 1_000_000 } else { 909_364 };
+                snap(i, x, y, 500)
+            })
+            .collect();
+        let analytics = pool_analytics(&pool(), &snaps, 10 * DAY_MS + 1);
+
+        assert_eq!(analytics.swaps_24h, 1);
+        assert_eq!(analytics.volume_x_24h, 100_000);
+        assert_eq!(analytics.volume_x_7d, 700_000);
+        assert_eq!(analytics.volume_x_30d, 1_000_000);
+        assert_eq!(analytics.tvl_x, 2_000_000);
+        assert_eq!(analytics.history_start, 0);
+        let apr = analytics.fee_apr_30d.unwrap();
+        assert!(apr > 0.0 && apr < 10.0, "apr {}", apr);
+    }
+
+    #[test]
+    fn test_snapshot_from_n2t_box_json() {
+        let json = serde_json::json!({
+            "boxId": "b1",
+            "transactionId": "t1",
+            "value": 5_000_000_000u64,
+            "inclusionHeight": 1200,
+            "assets": [
+                { "tokenId": "nft", "amount": 1 },
+                { "tokenId": "lp", "amount": 9_000 },
+                { "tokenId": "y", "amount": 42 },
+            ],
+        });
+        let snapshot = PoolSnapshot::from_box_json(PoolType::N2T, &json, 7).unwrap();
+        assert_eq!(snapshot.reserves_x, 5_000_000_000);
+        assert_eq!(snapshot.reserves_y, 42);
+        assert_eq!(snapshot.lp_locked, 9_000);
+        assert_eq!(snapshot.height, 1200);
+        assert!(PoolSnapshot::from_box_json(PoolType::T2T, &json, 7).is_none());
+    }
+}
@@ -10,8 +10,8 @@ use crate::constants::pool_templates::{N2T_POOL_TEMPLATE, T2T_POOL_TEMPLATE};
 use crate::state::{AmmError, PoolType};
 use ergo_tx::sigma::{encode_sigma_coll_byte, encode_sigma_int};
 use ergo_tx::{
-    collect_multi_change_tokens, select_multi_token_boxes, select_token_boxes, Eip12Asset,
-    Eip12InputBox, Eip12Output, Eip12UnsignedTx,
+    collect_multi_change_tokens, derive_output_boxes, select_multi_token_boxes,
+    select_token_boxes, Eip12Asset, Eip12InputBox, Eip12Output, Eip12UnsignedTx,
 };
 
 const TX_FEE: u64 = citadel_core::constants::TX_FEE_NANO as u64;
@@ -109,7 +109,8 @@ pub fn build_pool_bootstrap_eip12(
             (bootstrap_erg, sel)
         }
         PoolType::T2T => {
-            let bootstrap_erg = MIN_BOX_VALUE;
+            // Covers the pool box, the creator's LP box and the create tx fee.
+            let bootstrap_erg = MIN_BOX_VALUE * 2 + TX_FEE;
             let x_token_id = params.x_token_id.as_deref().ok_or_else(|| {
                 AmmError::TxBuildError("T2T pool requires x_token_id".to_string())
             })?;
@@ -171,7 +172,7 @@ pub fn build_pool_bootstrap_eip12(
             .checked_add(MIN_BOX_VALUE)
             .and_then(|v| v.checked_add(TX_FEE))
             .ok_or_else(|| AmmError::TxBuildError("ERG cost overflow".to_string()))?,
-        PoolType::T2T => MIN_BOX_VALUE
+        PoolType::T2T => bootstrap_box_erg
             .checked_add(MIN_BOX_VALUE)
             .and_then(|v| v.checked_add(TX_FEE))
             .ok_or_else(|| AmmError::TxBuildError("ERG cost overflow".to_string()))?,
@@ -339,6 +340,217 @@ pub fn build_pool_create_eip12(
     })
 }
 
+/// Initial amounts and projected state for a pool launch anchored at a price.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolLaunchPlan {
+    pub pool_type: String,
+    pub x_token_id: Option<String>,
+    pub y_token_id: String,
+    /// X amount for the bootstrap tx. For N2T this also covers the create
+    /// tx's miner fee and the user's LP box, on top of `pool_x_reserve`.
+    pub x_amount: u64,
+    pub y_amount: u64,
+    pub fee_num: i32,
+    /// Reserves of the pool box once created.
+    pub pool_x_reserve: u64,
+    pub pool_y_reserve: u64,
+    /// LP tokens paid to the creator.
+    pub user_lp_share: u64,
+    /// LP tokens held by the pool box.
+    pub lp_locked: u64,
+    /// LP tokens never minted; they back the pool's minimum liquidity.
+    pub lp_burned: u64,
+    /// Creator's share of the pool after launch.
+    pub pool_share_percent: f64,
+    /// Y per X in display units, as requested.
+    pub target_price: f64,
+    /// Y per X in display units implied by the created pool's reserves.
+    pub post_launch_price: f64,
+    /// Miner fees of both txs.
+    pub miner_fee: u64,
+    /// ERG locked in the pool plus both miner fees; the creator's LP box
+    /// ERG comes back to the wallet.
+    pub total_erg_cost: u64,
+}
+
+impl PoolLaunchPlan {
+    pub fn setup_params(&self) -> Result<PoolSetupParams, AmmError> {
+        let pool_type = match self.pool_type.as_str() {
+            "N2T" => PoolType::N2T,
+            "T2T" => PoolType::T2T,
+            other => {
+                return Err(AmmError::TxBuildError(format!(
+                    "Invalid pool type: {}",
+                    other
+                )))
+            }
+        };
+        Ok(PoolSetupParams {
+            pool_type,
+            x_token_id: self.x_token_id.clone(),
+            x_amount: self.x_amount,
+            y_token_id: self.y_token_id.clone(),
+            y_amount: self.y_amount,
+            fee_num: self.fee_num,
+        })
+    }
+}
+
+/// Plan a pool launch holding `pool_x_reserve` of X, with Y sized so the
+/// pool opens at `price` (Y per X, display units). X is ERG for N2T pools.
+#[allow(clippy::too_many_arguments)]
+pub fn plan_pool_launch(
+    pool_type: PoolType,
+    x_token_id: Option<String>,
+    y_token_id: String,
+    pool_x_reserve: u64,
+    price: f64,
+    x_decimals: u8,
+    y_decimals: u8,
+    fee_num: i32,
+) -> Result<PoolLaunchPlan, AmmError> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err(AmmError::TxBuildError(format!(
+            "Price must be positive, got {}",
+            price
+        )));
+    }
+    if pool_x_reserve == 0 {
+        return Err(AmmError::TxBuildError(
+            "Initial X liquidity must be greater than 0".to_string(),
+        ));
+    }
+    match (&pool_type, &x_token_id) {
+        (PoolType::N2T, Some(_)) => {
+            return Err(AmmError::TxBuildError(
+                "N2T pools take ERG as X; x_token_id must be empty".to_string(),
+            ))
+        }
+        (PoolType::T2T, None) => {
+            return Err(AmmError::TxBuildError(
+                "T2T pool requires x_token_id".to_string(),
+            ))
+        }
+        (PoolType::T2T, Some(x)) if *x == y_token_id => {
+            return Err(AmmError::TxBuildError(
+                "X and Y must be different tokens".to_string(),
+            ))
+        }
+        _ => {}
+    }
+
+    // Raw Y units per raw X unit.
+    let decimal_shift = 10f64.powi(y_decimals as i32 - x_decimals as i32);
+    let pool_y = (pool_x_reserve as f64 * price * decimal_shift).round();
+    if pool_y < 1.0 || pool_y >= u64::MAX as f64 {
+        return Err(AmmError::TxBuildError(format!(
+            "Price {} gives an unusable Y amount for {} X",
+            price, pool_x_reserve
+        )));
+    }
+    let pool_y_reserve = pool_y as u64;
+
+    let (x_amount, total_erg_cost) = match pool_type {
+        // The bootstrap box funds the create tx's fee and the user's LP box.
+        PoolType::N2T => {
+            let x_amount = pool_x_reserve
+                .checked_add(MIN_BOX_VALUE + TX_FEE)
+                .ok_or_else(|| AmmError::TxBuildError("ERG cost overflow".to_string()))?;
+            (x_amount, pool_x_reserve + TX_FEE * 2)
+        }
+        PoolType::T2T => (pool_x_reserve, MIN_BOX_VALUE + TX_FEE * 2),
+    };
+
+    let user_lp_share = calculate_initial_lp_share(x_amount, pool_y_reserve);
+    if user_lp_share == 0 {
+        return Err(AmmError::TxBuildError(
+            "Initial LP share would be 0".to_string(),
+        ));
+    }
+    let lp_burned = BURN_LP as u64;
+    let lp_locked = ((TOTAL_EMISSION - BURN_LP) as u64)
+        .checked_sub(user_lp_share)
+        .ok_or_else(|| AmmError::TxBuildError("LP share exceeds minted amount".to_string()))?;
+    let pool_share_percent = user_lp_share as f64 / (user_lp_share + lp_burned) as f64 * 100.0;
+    let post_launch_price = pool_y_reserve as f64 / pool_x_reserve as f64 / decimal_shift;
+
+    Ok(PoolLaunchPlan {
+        pool_type: format!("{:?}", pool_type),
+        x_token_id,
+        y_token_id,
+        x_amount,
+        y_amount: pool_y_reserve,
+        fee_num,
+        pool_x_reserve,
+        pool_y_reserve,
+        user_lp_share,
+        lp_locked,
+        lp_burned,
+        pool_share_percent,
+        target_price: price,
+        post_launch_price,
+        miner_fee: TX_FEE * 2,
+        total_erg_cost,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolLaunchLeg {
+    /// Deterministic txId of the unsigned tx.
+    pub tx_id: String,
+    pub unsigned_tx: Eip12UnsignedTx,
+}
+
+/// Bootstrap and create as one 0-conf chain: the create tx spends the
+/// bootstrap output before it is broadcast, so both are signed up front.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolLaunchBuild {
+    pub bootstrap: PoolLaunchLeg,
+    pub create: PoolLaunchLeg,
+    pub bootstrap_summary: PoolBootstrapSummary,
+    pub create_summary: PoolCreateSummary,
+}
+
+pub fn build_pool_launch_chain(
+    params: &PoolSetupParams,
+    user_utxos: &[Eip12InputBox],
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<PoolLaunchBuild, AmmError> {
+    let bootstrap =
+        build_pool_bootstrap_eip12(params, user_utxos, user_ergo_tree, current_height)?;
+    let (bootstrap_tx_id, outputs) = derive_output_boxes(&bootstrap.unsigned_tx)
+        .map_err(|e| AmmError::TxBuildError(format!("Chain derivation failed: {}", e)))?;
+    let bootstrap_box = outputs
+        .into_iter()
+        .next()
+        .ok_or_else(|| AmmError::TxBuildError("Bootstrap tx has no outputs".to_string()))?;
+
+    let create = build_pool_create_eip12(
+        &bootstrap_box,
+        params,
+        &bootstrap.summary.lp_token_id,
+        bootstrap.summary.user_lp_share,
+        user_ergo_tree,
+        current_height,
+    )?;
+    let (create_tx_id, _) = derive_output_boxes(&create.unsigned_tx)
+        .map_err(|e| AmmError::TxBuildError(format!("Chain derivation failed: {}", e)))?;
+
+    Ok(PoolLaunchBuild {
+        bootstrap: PoolLaunchLeg {
+            tx_id: bootstrap_tx_id,
+            unsigned_tx: bootstrap.unsigned_tx,
+        },
+        create: PoolLaunchLeg {
+            tx_id: create_tx_id,
+            unsigned_tx: create.unsigned_tx,
+        },
+        bootstrap_summary: bootstrap.summary,
+        create_summary: create.summary,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -455,7 +667,7 @@ mod tests {
         assert_eq!(bootstrap.assets[2].token_id, TOKEN_Y_ID);
 
         let bootstrap_erg: u64 = bootstrap.value.parse().unwrap();
-        assert_eq!(bootstrap_erg, MIN_BOX_VALUE);
+        assert_eq!(bootstrap_erg, MIN_BOX_VALUE * 2 + TX_FEE);
 
         let expected_fee_pct = (1.0 - 997.0 / 1000.0) * 100.0;
         assert!(
@@ -647,4 +859,91 @@ mod tests {
         let user_erg: u64 = user_out.value.parse().unwrap();
         assert_eq!(user_erg, bootstrap_erg - MIN_BOX_VALUE - TX_FEE);
     }
+
+    #[test]
+    fn test_plan_pool_launch_n2t_anchors_price() {
+        // 100 ERG at 2.5 tokens (2 decimals) per ERG
+        let plan = plan_pool_launch(
+            PoolType::N2T,
+            None,
+            TOKEN_Y_ID.to_string(),
+            100_000_000_000,
+            2.5,
+            9,
+            2,
+            997,
+        )
+        .unwrap();
+
+        assert_eq!(plan.pool_y_reserve, 25_000);
+        assert_eq!(plan.x_amount, 100_000_000_000 + MIN_BOX_VALUE + TX_FEE);
+        assert!((plan.post_launch_price - 2.5).abs() < 1e-9);
+        assert_eq!(plan.lp_burned, BURN_LP as u64);
+        assert_eq!(
+            plan.lp_locked + plan.user_lp_share,
+            (TOTAL_EMISSION - BURN_LP) as u64
+        );
+        assert!(plan.pool_share_percent > 99.0 && plan.pool_share_percent < 100.0);
+        assert_eq!(plan.total_erg_cost, 100_000_000_000 + 2 * TX_FEE);
+    }
+
+    #[test]
+    fn test_plan_pool_launch_rejects_bad_input() {
+        let plan = |x_token: Option<&str>, price: f64| {
+            plan_pool_launch(
+                if x_token.is_some() { PoolType::T2T } else { PoolType::N2T },
+                x_token.map(str::to_string),
+                TOKEN_Y_ID.to_string(),
+                1_000_000,
+                price,
+                0,
+                0,
+                997,
+            )
+        };
+        assert!(plan(None, 0.0).is_err());
+        assert!(plan(None, f64::NAN).is_err());
+        // Rounds to zero Y
+        assert!(plan(None, 1e-9).is_err());
+        assert!(plan(Some(TOKEN_Y_ID), 1.0).is_err());
+        assert!(plan(Some(TOKEN_X_ID), 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_pool_launch_chain_spends_bootstrap_box() {
+        for (pool_type, x_token) in [(PoolType::N2T, None), (PoolType::T2T, Some(TOKEN_X_ID))] {
+            let utxos = vec![make_utxo(
+                BOX_ID_1,
+                50_000_000_000,
+                vec![(TOKEN_X_ID, 1_000_000), (TOKEN_Y_ID, 5_000_000)],
+            )];
+            let plan = plan_pool_launch(
+                pool_type,
+                x_token.map(str::to_string),
+                TOKEN_Y_ID.to_string(),
+                if x_token.is_some() { 500_000 } else { 10_000_000_000 },
+                2.0,
+                if x_token.is_some() { 0 } else { 9 },
+                0,
+                997,
+            )
+            .unwrap();
+            let params = plan.setup_params().unwrap();
+
+            let build =
+                build_pool_launch_chain(&params, &utxos, USER_ERGO_TREE, 1_000_000).unwrap();
+            let create_input = &build.create.unsigned_tx.inputs[0];
+            assert_eq!(create_input.transaction_id, build.bootstrap.tx_id);
+            assert_eq!(create_input.index, 0);
+            assert_eq!(build.create_summary.pool_nft_id, create_input.box_id);
+            assert_eq!(build.create_summary.lp_token_id, BOX_ID_1);
+
+            let pool_box = &build.create.unsigned_tx.outputs[0];
+            let y = pool_box.assets.last().unwrap();
+            assert_eq!(y.amount, plan.pool_y_reserve.to_string());
+            if pool_type == PoolType::N2T {
+                assert_eq!(pool_box.value, plan.pool_x_reserve.to_string());
+            }
+        }
+    }
 }
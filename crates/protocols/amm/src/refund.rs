@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use ergo_tx::{Eip12Asset, Eip12InputBox, Eip12Output, Eip12UnsignedTx};
 use serde::{Deserialize, Serialize};
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 use crate::state::AmmError;
 
 const REFUND_TX_FEE: u64 = citadel_core::constants::TX_FEE_NANO as u64;
+/// Order boxes per batch refund; keeps the tx well under the size limit.
+pub const MAX_BATCH_REFUND_INPUTS: usize = 50;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RefundBuildResult {
@@ -27,17 +29,114 @@ pub fn build_refund_tx_eip12(
     current_height: i32,
     additional_inputs: &[Eip12InputBox],
 ) -> Result<RefundBuildResult, AmmError> {
-    let proxy_value: u64 = proxy_box
-        .value
-        .parse()
-        .map_err(|_| AmmError::RefundError("Invalid proxy box value".to_string()))?;
+    let mut inputs = vec![proxy_box.clone()];
+    inputs.extend(additional_inputs.iter().cloned());
+    let (unsigned_tx, refunded_erg, refunded_tokens) =
+        build_refund_tx(inputs, user_ergo_tree, current_height)?;
 
-    let additional_erg: u64 = additional_inputs
-        .iter()
-        .map(|u| u.value.parse::<u64>().unwrap_or(0))
-        .sum();
+    let summary = RefundSummary {
+        proxy_box_id: proxy_box.box_id.clone(),
+        refunded_erg,
+        refunded_tokens,
+        miner_fee: REFUND_TX_FEE,
+    };
 
-    let total_input_erg = proxy_value + additional_erg;
+    Ok(RefundBuildResult {
+        unsigned_tx,
+        summary,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchRefundBuildResult {
+    pub unsigned_tx: Eip12UnsignedTx,
+    pub summary: BatchRefundSummary,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRefundSummary {
+    pub proxy_box_ids: Vec<String>,
+    pub refunded_erg: u64,
+    pub refunded_tokens: Vec<(String, u64)>,
+    pub miner_fee: u64,
+}
+
+/// Refund several order boxes in one transaction, paying a single miner fee.
+/// Every box must be refundable by the signer (refund prop = user's key).
+pub fn build_batch_refund_tx_eip12(
+    proxy_boxes: &[Eip12InputBox],
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<BatchRefundBuildResult, AmmError> {
+    if proxy_boxes.len() > MAX_BATCH_REFUND_INPUTS {
+        return Err(AmmError::RefundError(format!(
+            "Too many order boxes for one refund: {} (max {})",
+            proxy_boxes.len(),
+            MAX_BATCH_REFUND_INPUTS
+        )));
+    }
+    check_refund_boxes(proxy_boxes)?;
+
+    let (unsigned_tx, refunded_erg, refunded_tokens) =
+        build_refund_tx(proxy_boxes.to_vec(), user_ergo_tree, current_height)?;
+
+    Ok(BatchRefundBuildResult {
+        unsigned_tx,
+        summary: BatchRefundSummary {
+            proxy_box_ids: proxy_boxes.iter().map(|b| b.box_id.clone()).collect(),
+            refunded_erg,
+            refunded_tokens,
+            miner_fee: REFUND_TX_FEE,
+        },
+    })
+}
+
+/// Refund any number of order boxes, at most [`MAX_BATCH_REFUND_INPUTS`] per
+/// transaction. The transactions spend disjoint boxes, so each can be signed
+/// and submitted on its own.
+pub fn build_batch_refund_txs_eip12(
+    proxy_boxes: &[Eip12InputBox],
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<Vec<BatchRefundBuildResult>, AmmError> {
+    check_refund_boxes(proxy_boxes)?;
+    proxy_boxes
+        .chunks(MAX_BATCH_REFUND_INPUTS)
+        .map(|chunk| build_batch_refund_tx_eip12(chunk, user_ergo_tree, current_height))
+        .collect()
+}
+
+fn check_refund_boxes(proxy_boxes: &[Eip12InputBox]) -> Result<(), AmmError> {
+    if proxy_boxes.is_empty() {
+        return Err(AmmError::RefundError(
+            "No order boxes to refund".to_string(),
+        ));
+    }
+    let mut seen = HashSet::new();
+    if let Some(dup) = proxy_boxes.iter().find(|b| !seen.insert(b.box_id.as_str())) {
+        return Err(AmmError::RefundError(format!(
+            "Order box {} listed twice",
+            dup.box_id
+        )));
+    }
+    Ok(())
+}
+
+/// Sweep `inputs` into one user output plus the miner fee. Returns the tx,
+/// the ERG sent to the user and the refunded tokens (sorted by ID).
+fn build_refund_tx(
+    inputs: Vec<Eip12InputBox>,
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<(Eip12UnsignedTx, u64, Vec<(String, u64)>), AmmError> {
+    let mut total_input_erg: u64 = 0;
+    for input in &inputs {
+        let value: u64 = input
+            .value
+            .parse()
+            .map_err(|_| AmmError::RefundError(format!("Invalid value in box {}", input.box_id)))?;
+        total_input_erg = total_input_erg.saturating_add(value);
+    }
 
     if total_input_erg <= REFUND_TX_FEE {
         return Err(AmmError::RefundError(format!(
@@ -49,7 +148,7 @@ pub fn build_refund_tx_eip12(
     let user_erg = total_input_erg - REFUND_TX_FEE;
 
     let mut token_totals: HashMap<String, u64> = HashMap::new();
-    for input in std::iter::once(proxy_box).chain(additional_inputs.iter()) {
+    for input in &inputs {
         for asset in &input.assets {
             let amount: u64 = asset.amount.parse().unwrap_or_else(|_| {
                 tracing::warn!(
@@ -63,46 +162,32 @@ pub fn build_refund_tx_eip12(
         }
     }
 
-    let user_assets: Vec<Eip12Asset> = token_totals
+    let mut refunded_tokens: Vec<(String, u64)> = token_totals
+        .into_iter()
+        .filter(|(_, amount)| *amount > 0)
+        .collect();
+    refunded_tokens.sort();
+
+    let user_assets: Vec<Eip12Asset> = refunded_tokens
         .iter()
-        .filter(|(_, &amount)| amount > 0)
-        .map(|(token_id, &amount)| Eip12Asset {
+        .map(|(token_id, amount)| Eip12Asset {
             token_id: token_id.clone(),
             amount: amount.to_string(),
         })
         .collect();
 
-    let refunded_tokens: Vec<(String, u64)> = token_totals
-        .iter()
-        .filter(|(_, &amount)| amount > 0)
-        .map(|(id, &amount)| (id.clone(), amount))
-        .collect();
-
     let user_output =
         Eip12Output::change(user_erg as i64, user_ergo_tree, user_assets, current_height);
 
     let fee_output = Eip12Output::fee(REFUND_TX_FEE as i64, current_height);
 
-    let mut inputs = vec![proxy_box.clone()];
-    inputs.extend(additional_inputs.iter().cloned());
-
     let unsigned_tx = Eip12UnsignedTx {
         inputs,
         data_inputs: vec![],
         outputs: vec![user_output, fee_output],
     };
 
-    let summary = RefundSummary {
-        proxy_box_id: proxy_box.box_id.clone(),
-        refunded_erg: user_erg,
-        refunded_tokens,
-        miner_fee: REFUND_TX_FEE,
-    };
-
-    Ok(RefundBuildResult {
-        unsigned_tx,
-        summary,
-    })
+    Ok((unsigned_tx, user_erg, refunded_tokens))
 }
 
 #[cfg(test)]
@@ -307,4 +392,55 @@ mod tests {
         assert_eq!(user_output.assets[0].token_id, "some_token");
         assert_eq!(user_output.assets[0].amount, "5000");
     }
+
+    #[test]
+    fn test_batch_refund_merges_boxes() {
+        let boxes = vec![test_proxy_box_erg_to_token(), test_proxy_box_token_to_erg()];
+        let result = build_batch_refund_tx_eip12(&boxes, USER_ERGO_TREE, 1_000_000).unwrap();
+
+        let tx = &result.unsigned_tx;
+        assert_eq!(tx.inputs.len(), 2);
+        assert_eq!(tx.outputs.len(), 2);
+        let user_value: u64 = tx.outputs[0].value.parse().unwrap();
+        assert_eq!(user_value, 1_012_000_000 - REFUND_TX_FEE);
+        assert_eq!(tx.outputs[0].assets.len(), 1);
+        assert_eq!(
+            result.summary.proxy_box_ids,
+            vec!["proxy_box_1", "proxy_box_2"]
+        );
+        assert_eq!(result.summary.miner_fee, REFUND_TX_FEE);
+    }
+
+    #[test]
+    fn test_batch_refund_rejects_empty_and_duplicates() {
+        assert!(build_batch_refund_tx_eip12(&[], USER_ERGO_TREE, 1_000_000).is_err());
+
+        let proxy = test_proxy_box_erg_to_token();
+        let err = build_batch_refund_tx_eip12(&[proxy.clone(), proxy], USER_ERGO_TREE, 1_000_000)
+            .unwrap_err();
+        assert!(err.to_string().contains("twice"));
+    }
+
+    #[test]
+    fn test_batch_refunds_are_chunked() {
+        let boxes: Vec<Eip12InputBox> = (0..MAX_BATCH_REFUND_INPUTS * 2 + 7)
+            .map(|i| Eip12InputBox {
+                box_id: format!("proxy_box_{}", i),
+                ..test_proxy_box_erg_to_token()
+            })
+            .collect();
+        assert!(build_batch_refund_tx_eip12(&boxes, USER_ERGO_TREE, 1_000_000).is_err());
+
+        let results = build_batch_refund_txs_eip12(&boxes, USER_ERGO_TREE, 1_000_000).unwrap();
+        let sizes: Vec<usize> = results.iter().map(|r| r.unsigned_tx.inputs.len()).collect();
+        assert_eq!(
+            sizes,
+            vec![MAX_BATCH_REFUND_INPUTS, MAX_BATCH_REFUND_INPUTS, 7]
+        );
+        assert_eq!(results[2].summary.proxy_box_ids[0], "proxy_box_100");
+
+        let mut dup = boxes.clone();
+        dup.push(boxes[0].clone());
+        assert!(build_batch_refund_txs_eip12(&dup, USER_ERGO_TREE, 1_000_000).is_err());
+    }
 }
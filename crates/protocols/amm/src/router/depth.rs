@@ -65,7 +65,7 @@ fn max_executable_input_on_path(path: &[PoolEdge]) -> Option<(u64, u64)> {
 
     // Direct token → ERG: closed-form max input, then walk down if CFMM
     // rounding would still breach the dust ceiling.
-    if path.len() == 1 && path[0].token_out == ERG_TOKEN_ID {
+    if path.len() == 1 && path[0].token_out == ERG_TOKEN_ID && path[0].is_spectrum() {
         let e = &path[0];
         let mut hi = max_token_in_for_erg_out(
             e.reserves_in,
@@ -119,11 +119,15 @@ fn max_executable_input_on_path(path: &[PoolEdge]) -> Option<(u64, u64)> {
 }
 
 /// Max input per impact tier. Constant product: `max_input = reserves_in * impact / (1 - impact)`
+/// Fixed-price sources have no curve: every tier is their per-tx capacity.
 pub fn calculate_depth_tiers(edge: &PoolEdge) -> DepthTiers {
     let tiers: Vec<(f64, u64)> = IMPACT_TIERS
         .iter()
         .map(|&impact| {
-            let max_input = (edge.reserves_in as f64 * impact / (1.0 - impact)) as u64;
+            let max_input = match &edge.source {
+                Some(source) if source.is_fixed_price() => source.capacity(),
+                _ => (edge.reserves_in as f64 * impact / (1.0 - impact)) as u64,
+            };
             (impact * 100.0, max_input)
         })
         .collect();
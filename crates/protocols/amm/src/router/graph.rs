@@ -1,7 +1,9 @@
 //! Pool graph construction.
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+use super::source::LiquiditySource;
 use super::types::{PoolEdge, PoolGraph, DEFAULT_MAX_POOLS_PER_PAIR, ERG_TOKEN_ID};
 use crate::state::{AmmPool, PoolType};
 
@@ -42,6 +44,7 @@ pub fn build_pool_graph_with_limit(
                         token_out: pool.token_y.token_id.clone(),
                         reserves_in: erg_reserves,
                         reserves_out: token_reserves,
+                        source: None,
                     });
 
                 adjacency
@@ -53,6 +56,7 @@ pub fn build_pool_graph_with_limit(
                         token_out: ERG_TOKEN_ID.to_string(),
                         reserves_in: token_reserves,
                         reserves_out: erg_reserves,
+                        source: None,
                     });
 
                 pool_count += 1;
@@ -75,6 +79,7 @@ pub fn build_pool_graph_with_limit(
                         token_out: pool.token_y.token_id.clone(),
                         reserves_in: token_x.amount,
                         reserves_out: pool.token_y.amount,
+                        source: None,
                     });
 
                 adjacency
@@ -86,6 +91,7 @@ pub fn build_pool_graph_with_limit(
                         token_out: token_x.token_id.clone(),
                         reserves_in: pool.token_y.amount,
                         reserves_out: token_x.amount,
+                        source: None,
                     });
 
                 pool_count += 1;
@@ -179,6 +185,7 @@ pub fn ensure_direct_pair_edges(
                 token_out: token_b.clone(),
                 reserves_in: reserves_a,
                 reserves_out: reserves_b,
+                source: None,
             });
         graph
             .adjacency
@@ -190,7 +197,25 @@ pub fn ensure_direct_pair_edges(
                 token_out: token_a.clone(),
                 reserves_in: reserves_b,
                 reserves_out: reserves_a,
+                source: None,
             });
         graph.pool_count += 1;
     }
 }
+
+/// Add protocol banks and foreign pools as edges. They bypass the liquidity
+/// floor and per-pair cap: there are only a handful, and each is the sole
+/// venue of its kind for its pair. Sources with no capacity are skipped.
+pub fn add_liquidity_sources(graph: &mut PoolGraph, sources: &[Arc<dyn LiquiditySource>]) {
+    for source in sources {
+        if source.capacity() == 0 || source.spot_rate() <= 0.0 {
+            continue;
+        }
+        let edge = PoolEdge::from_source(Arc::clone(source));
+        graph
+            .adjacency
+            .entry(edge.token_in.clone())
+            .or_default()
+            .push(edge);
+    }
+}
@@ -7,6 +7,7 @@ mod arb;
 mod depth;
 mod graph;
 mod search;
+mod source;
 mod split;
 mod types;
 
@@ -21,14 +22,18 @@ pub use depth::{
     calculate_all_depth_tiers, calculate_depth_tiers, max_executable_swap_to_erg,
     max_swap_hint_if_needed,
 };
-pub use graph::{build_pool_graph, build_pool_graph_with_limit, ensure_direct_pair_edges};
+pub use graph::{
+    add_liquidity_sources, build_pool_graph, build_pool_graph_with_limit, ensure_direct_pair_edges,
+};
 pub use search::{
-    find_best_routes, find_best_routes_by_output, find_paths, make_route_quote, quote_route,
-    quote_route_reverse,
+    find_best_routes, find_best_routes_by_output, find_k_best_paths, find_k_best_paths_by_output,
+    find_paths, make_route_quote, quote_route, quote_route_reverse,
 };
+pub use source::{BankDirection, BankSource, CfmmSource, LiquiditySource, SourceToken};
 pub use split::{optimize_split, optimize_split_detailed};
 pub use types::{
     miner_fees_for_hops, DepthTiers, MaxSwapHint, PoolEdge, PoolGraph, Route, RouteHop, RouteQuote,
     SplitAllocation, SplitAllocationDetail, SplitRoute, SplitRouteDetail,
-    DEFAULT_MAX_POOLS_PER_PAIR, DEFAULT_MIN_LIQUIDITY_NANO, ERG_TOKEN_ID, MINER_FEE_PER_HOP,
+    DEFAULT_MAX_POOLS_PER_PAIR, DEFAULT_MIN_LIQUIDITY_NANO, DEFAULT_PATH_BEAM, ERG_TOKEN_ID,
+    MINER_FEE_PER_HOP,
 };
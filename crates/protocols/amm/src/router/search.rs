@@ -1,14 +1,13 @@
 //! Path finding and route quoting.
 
-use std::collections::{HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::types::{
-    miner_fees_for_hops, PoolEdge, PoolGraph, Route, RouteHop, RouteQuote, ERG_TOKEN_ID,
-};
-use crate::calculator::{
-    apply_slippage, calculate_input, calculate_output, calculate_price_impact,
-    calculate_token_to_erg_output, would_breach_pool_min_erg,
+    miner_fees_for_hops, PoolEdge, PoolGraph, Route, RouteHop, RouteQuote, DEFAULT_PATH_BEAM,
+    ERG_TOKEN_ID,
 };
+use crate::calculator::{apply_slippage, calculate_price_impact};
 use crate::state::AmmPool;
 
 fn finish_route(
@@ -41,6 +40,18 @@ fn finish_route(
     }
 }
 
+/// Execution vs spot rate, in percent (includes the venue fee, as for CFMM pools).
+fn hop_price_impact(edge: &PoolEdge, input: u64, output: u64) -> f64 {
+    if edge.is_spectrum() {
+        return calculate_price_impact(edge.reserves_in, edge.reserves_out, input, output);
+    }
+    let spot = edge.spot_rate();
+    if spot <= 0.0 || input == 0 {
+        return 0.0;
+    }
+    ((spot - output as f64 / input as f64) / spot).abs() * 100.0
+}
+
 pub(crate) fn route_score(route: &Route) -> u64 {
     route.net_output
 }
@@ -109,6 +120,223 @@ pub fn find_paths(
     results
 }
 
+/// A partial path in the k-best search: the amount it carries (output so far,
+/// or input still needed when searching backwards) plus what it has used.
+#[derive(Clone)]
+struct PathLabel {
+    path: Vec<PoolEdge>,
+    amount: u64,
+    /// Sum of ln(spot rate) over the path; ranks labels when no amount is given.
+    log_rate: f64,
+    tokens: HashSet<String>,
+    pools: HashSet<String>,
+}
+
+impl PathLabel {
+    fn start(token: &str, amount: u64) -> Self {
+        Self {
+            path: Vec::new(),
+            amount,
+            log_rate: 0.0,
+            tokens: HashSet::from([token.to_string()]),
+            pools: HashSet::new(),
+        }
+    }
+
+    fn extend(&self, edge: &PoolEdge, amount: u64, next_token: &str, prepend: bool) -> Self {
+        let mut label = self.clone();
+        if prepend {
+            label.path.insert(0, edge.clone());
+        } else {
+            label.path.push(edge.clone());
+        }
+        label.amount = amount;
+        label.log_rate += edge.spot_rate().ln();
+        label.tokens.insert(next_token.to_string());
+        label.pools.insert(edge.pool.pool_id.clone());
+        label
+    }
+}
+
+/// Label ranking: most output forwards, least input backwards, highest log
+/// spot rate when pricing by spot. Ties go to fewer hops, then pool ids, so
+/// results do not depend on map iteration order.
+fn compare_labels(a: &PathLabel, b: &PathLabel, by_spot: bool, backwards: bool) -> Ordering {
+    let primary = if by_spot {
+        b.log_rate.total_cmp(&a.log_rate)
+    } else if backwards {
+        a.amount.cmp(&b.amount)
+    } else {
+        b.amount.cmp(&a.amount)
+    };
+    primary
+        .then_with(|| a.path.len().cmp(&b.path.len()))
+        .then_with(|| {
+            let ids = |l: &PathLabel| -> Vec<String> {
+                l.path.iter().map(|e| e.pool.pool_id.clone()).collect()
+            };
+            ids(a).cmp(&ids(b))
+        })
+}
+
+/// Keep the `k` best labels per token.
+fn prune_labels(
+    labels: &mut HashMap<String, Vec<PathLabel>>,
+    k: usize,
+    by_spot: bool,
+    backwards: bool,
+) {
+    for list in labels.values_mut() {
+        list.sort_by(|a, b| compare_labels(a, b, by_spot, backwards));
+        list.truncate(k);
+    }
+}
+
+/// Up to `k` best simple paths for swapping `input_amount`, best first.
+///
+/// Hop-bounded Bellman-Ford over log-price edge weights: each relaxation round
+/// extends the labels of the previous round by one hop and keeps the `k` best
+/// per token. Labels carry the actual amount through the path, so weights are
+/// the execution rates at this trade size and edges that cannot absorb it are
+/// pruned. With `input_amount == 0` labels rank by spot rate instead. Labels
+/// of different lengths only compete once complete, after miner fees.
+pub fn find_k_best_paths(
+    graph: &PoolGraph,
+    source_token: &str,
+    target_token: &str,
+    input_amount: u64,
+    max_hops: usize,
+    k: usize,
+) -> Vec<Vec<PoolEdge>> {
+    let k = k.max(1);
+    let by_spot = input_amount == 0;
+    let mut frontier: HashMap<String, Vec<PathLabel>> = HashMap::from([(
+        source_token.to_string(),
+        vec![PathLabel::start(source_token, input_amount)],
+    )]);
+    let mut complete: Vec<PathLabel> = Vec::new();
+
+    for depth in 0..max_hops {
+        let mut next: HashMap<String, Vec<PathLabel>> = HashMap::new();
+        for (token, labels) in &frontier {
+            let Some(edges) = graph.adjacency.get(token.as_str()) else {
+                continue;
+            };
+            for label in labels {
+                for edge in edges {
+                    if label.pools.contains(&edge.pool.pool_id) {
+                        continue;
+                    }
+                    let is_target = edge.token_out == target_token;
+                    if !is_target && label.tokens.contains(&edge.token_out) {
+                        continue;
+                    }
+                    let amount = if by_spot {
+                        0
+                    } else {
+                        match edge.output_for(label.amount) {
+                            0 => continue,
+                            out => out,
+                        }
+                    };
+                    if by_spot && edge.spot_rate() <= 0.0 {
+                        continue;
+                    }
+                    let extended = label.extend(edge, amount, &edge.token_out, false);
+                    if is_target {
+                        complete.push(extended);
+                    } else if depth + 1 < max_hops {
+                        next.entry(edge.token_out.clone())
+                            .or_default()
+                            .push(extended);
+                    }
+                }
+            }
+        }
+        prune_labels(&mut next, k, by_spot, false);
+        frontier = next;
+    }
+
+    if target_token == ERG_TOKEN_ID && !by_spot {
+        // Rank completed paths on net ERG, as `route_score` does
+        for label in &mut complete {
+            label.amount = label
+                .amount
+                .saturating_sub(miner_fees_for_hops(label.path.len()));
+        }
+    }
+    complete.sort_by(|a, b| compare_labels(a, b, by_spot, false));
+    complete.truncate(k);
+    complete.into_iter().map(|l| l.path).collect()
+}
+
+/// Up to `k` simple paths needing the least input for `desired_output`, best
+/// first. The same search as [`find_k_best_paths`], run backwards from the
+/// target with each label carrying the input still required.
+pub fn find_k_best_paths_by_output(
+    graph: &PoolGraph,
+    source_token: &str,
+    target_token: &str,
+    desired_output: u64,
+    max_hops: usize,
+    k: usize,
+) -> Vec<Vec<PoolEdge>> {
+    if desired_output == 0 {
+        return Vec::new();
+    }
+    let k = k.max(1);
+    let mut incoming: HashMap<&str, Vec<&PoolEdge>> = HashMap::new();
+    for edge in graph.adjacency.values().flatten() {
+        incoming
+            .entry(edge.token_out.as_str())
+            .or_default()
+            .push(edge);
+    }
+
+    let mut frontier: HashMap<String, Vec<PathLabel>> = HashMap::from([(
+        target_token.to_string(),
+        vec![PathLabel::start(target_token, desired_output)],
+    )]);
+    let mut complete: Vec<PathLabel> = Vec::new();
+
+    for depth in 0..max_hops {
+        let mut next: HashMap<String, Vec<PathLabel>> = HashMap::new();
+        for (token, labels) in &frontier {
+            let Some(edges) = incoming.get(token.as_str()) else {
+                continue;
+            };
+            for label in labels {
+                for edge in edges {
+                    if label.pools.contains(&edge.pool.pool_id) {
+                        continue;
+                    }
+                    let is_source = edge.token_in == source_token;
+                    if !is_source && label.tokens.contains(&edge.token_in) {
+                        continue;
+                    }
+                    let Some(needed) = edge.input_for(label.amount) else {
+                        continue;
+                    };
+                    let extended = label.extend(edge, needed, &edge.token_in, true);
+                    if is_source {
+                        complete.push(extended);
+                    } else if depth + 1 < max_hops {
+                        next.entry(edge.token_in.clone())
+                            .or_default()
+                            .push(extended);
+                    }
+                }
+            }
+        }
+        prune_labels(&mut next, k, false, true);
+        frontier = next;
+    }
+
+    complete.sort_by(|a, b| compare_labels(a, b, false, true));
+    complete.truncate(k);
+    complete.into_iter().map(|l| l.path).collect()
+}
+
 /// Chain `calculate_output` through each hop. Returns `None` if any hop yields zero.
 pub fn quote_route(path: &[PoolEdge], input_amount: u64) -> Option<Route> {
     if path.is_empty() || input_amount == 0 {
@@ -120,44 +348,25 @@ pub fn quote_route(path: &[PoolEdge], input_amount: u64) -> Option<Route> {
     let mut total_fees: u64 = 0;
 
     for edge in path {
-        let output = if edge.token_out == ERG_TOKEN_ID {
-            calculate_token_to_erg_output(
-                edge.reserves_in,
-                edge.reserves_out,
-                current_amount,
-                edge.pool.fee_num,
-                edge.pool.fee_denom,
-            )
-        } else {
-            calculate_output(
-                edge.reserves_in,
-                edge.reserves_out,
-                current_amount,
-                edge.pool.fee_num,
-                edge.pool.fee_denom,
-            )
-        };
+        let output = edge.output_for(current_amount);
 
         if output == 0 {
             return None;
         }
 
-        let price_impact =
-            calculate_price_impact(edge.reserves_in, edge.reserves_out, current_amount, output);
+        let price_impact = hop_price_impact(edge, current_amount, output);
 
-        let fee_amount = (current_amount as f64
-            * (1.0 - edge.pool.fee_num as f64 / edge.pool.fee_denom as f64))
-            as u64;
+        let fee_amount = edge.fee_for(current_amount);
 
         let token_in_name = resolve_token_name(&edge.pool, &edge.token_in);
         let token_out_name = resolve_token_name(&edge.pool, &edge.token_out);
         let token_in_decimals = resolve_token_decimals(&edge.pool, &edge.token_in);
         let token_out_decimals = resolve_token_decimals(&edge.pool, &edge.token_out);
-        let pool_display_name = make_pool_display_name(&edge.pool, &edge.token_in, &edge.token_out);
+        let pool_display_name = hop_display_name(edge);
 
         hops.push(RouteHop {
             pool_id: edge.pool.pool_id.clone(),
-            pool_type: format!("{:?}", edge.pool.pool_type),
+            pool_type: edge.hop_type(),
             token_in: edge.token_in.clone(),
             token_in_name,
             token_in_decimals,
@@ -173,6 +382,7 @@ pub fn quote_route(path: &[PoolEdge], input_amount: u64) -> Option<Route> {
             fee_denom: edge.pool.fee_denom,
             reserves_in: edge.reserves_in,
             reserves_out: edge.reserves_out,
+            executable: edge.source.is_none(),
         });
 
         total_fees += fee_amount;
@@ -182,10 +392,7 @@ pub fn quote_route(path: &[PoolEdge], input_amount: u64) -> Option<Route> {
     let total_output = current_amount;
 
     // Compare actual rate to product of spot prices for end-to-end impact
-    let spot_product: f64 = path
-        .iter()
-        .map(|e| e.reserves_out as f64 / e.reserves_in as f64)
-        .product();
+    let spot_product: f64 = path.iter().map(PoolEdge::spot_rate).product();
     let actual_rate = total_output as f64 / input_amount as f64;
     let total_price_impact = if spot_product > 0.0 {
         ((spot_product - actual_rate) / spot_product).abs() * 100.0
@@ -213,7 +420,15 @@ pub fn find_best_routes(
     max_hops: usize,
     max_routes: usize,
 ) -> Vec<Route> {
-    let paths = find_paths(graph, source_token, target_token, max_hops);
+    let beam = (max_routes * 2).max(DEFAULT_PATH_BEAM);
+    let paths = find_k_best_paths(
+        graph,
+        source_token,
+        target_token,
+        input_amount,
+        max_hops,
+        beam,
+    );
 
     let mut routes: Vec<Route> = paths
         .iter()
@@ -232,6 +447,7 @@ pub fn make_route_quote(route: Route, slippage_percent: f64) -> RouteQuote {
         route,
         min_output,
         slippage_percent,
+        unconfirmed_pools: Vec::new(),
     }
 }
 
@@ -246,16 +462,7 @@ pub fn quote_route_reverse(path: &[PoolEdge], desired_output: u64) -> Option<Rou
     let mut needed = desired_output;
 
     for edge in path.iter().rev() {
-        if edge.token_out == ERG_TOKEN_ID && would_breach_pool_min_erg(edge.reserves_out, needed) {
-            return None;
-        }
-        let input_needed = calculate_input(
-            edge.reserves_in,
-            edge.reserves_out,
-            needed,
-            edge.pool.fee_num,
-            edge.pool.fee_denom,
-        )?;
+        let input_needed = edge.input_for(needed)?;
         required_amounts.push((input_needed, needed));
         needed = input_needed;
     }
@@ -269,34 +476,25 @@ pub fn quote_route_reverse(path: &[PoolEdge], desired_output: u64) -> Option<Rou
     for (i, edge) in path.iter().enumerate() {
         let (input_amount, _) = required_amounts[i];
 
-        let output = calculate_output(
-            edge.reserves_in,
-            edge.reserves_out,
-            input_amount,
-            edge.pool.fee_num,
-            edge.pool.fee_denom,
-        );
+        let output = edge.output_for(input_amount);
 
         if output == 0 {
             return None;
         }
 
-        let price_impact =
-            calculate_price_impact(edge.reserves_in, edge.reserves_out, input_amount, output);
+        let price_impact = hop_price_impact(edge, input_amount, output);
 
-        let fee_amount = (input_amount as f64
-            * (1.0 - edge.pool.fee_num as f64 / edge.pool.fee_denom as f64))
-            as u64;
+        let fee_amount = edge.fee_for(input_amount);
 
         let token_in_name = resolve_token_name(&edge.pool, &edge.token_in);
         let token_out_name = resolve_token_name(&edge.pool, &edge.token_out);
         let token_in_decimals = resolve_token_decimals(&edge.pool, &edge.token_in);
         let token_out_decimals = resolve_token_decimals(&edge.pool, &edge.token_out);
-        let pool_display_name = make_pool_display_name(&edge.pool, &edge.token_in, &edge.token_out);
+        let pool_display_name = hop_display_name(edge);
 
         hops.push(RouteHop {
             pool_id: edge.pool.pool_id.clone(),
-            pool_type: format!("{:?}", edge.pool.pool_type),
+            pool_type: edge.hop_type(),
             token_in: edge.token_in.clone(),
             token_in_name,
             token_in_decimals,
@@ -312,6 +510,7 @@ pub fn quote_route_reverse(path: &[PoolEdge], desired_output: u64) -> Option<Rou
             fee_denom: edge.pool.fee_denom,
             reserves_in: edge.reserves_in,
             reserves_out: edge.reserves_out,
+            executable: edge.source.is_none(),
         });
 
         total_fees += fee_amount;
@@ -319,10 +518,7 @@ pub fn quote_route_reverse(path: &[PoolEdge], desired_output: u64) -> Option<Rou
 
     let total_output = hops.last().map(|h| h.output_amount).unwrap_or(0);
 
-    let spot_product: f64 = path
-        .iter()
-        .map(|e| e.reserves_out as f64 / e.reserves_in as f64)
-        .product();
+    let spot_product: f64 = path.iter().map(PoolEdge::spot_rate).product();
     let actual_rate = if total_input > 0 {
         total_output as f64 / total_input as f64
     } else {
@@ -352,7 +548,15 @@ pub fn find_best_routes_by_output(
     max_hops: usize,
     max_routes: usize,
 ) -> Vec<Route> {
-    let paths = find_paths(graph, source_token, target_token, max_hops);
+    let beam = (max_routes * 2).max(DEFAULT_PATH_BEAM);
+    let paths = find_k_best_paths_by_output(
+        graph,
+        source_token,
+        target_token,
+        desired_output,
+        max_hops,
+        beam,
+    );
 
     let mut routes: Vec<Route> = paths
         .iter()
@@ -394,6 +598,15 @@ pub(crate) fn resolve_token_decimals(pool: &AmmPool, token_id: &str) -> u8 {
     0
 }
 
+/// Pair name, prefixed with the protocol for non-Spectrum venues.
+fn hop_display_name(edge: &PoolEdge) -> Option<String> {
+    let pair = make_pool_display_name(&edge.pool, &edge.token_in, &edge.token_out);
+    match &edge.source {
+        Some(source) => pair.map(|p| format!("{} {}", source.protocol(), p)),
+        None => pair,
+    }
+}
+
 pub(crate) fn make_pool_display_name(
     pool: &AmmPool,
     token_in: &str,
@@ -0,0 +1,521 @@
+//! Non-Spectrum liquidity the router can traverse alongside AMM pools.
+//!
+//! Protocol banks (SigmaUSD, Dexy, HodlCoin) and foreign CFMM pools (Dexy LP)
+//! are added to the graph as directed edges priced by their own functions.
+//! This crate does not depend on the protocol crates; the caller builds
+//! sources from protocol state. Routes through a source are quotes only --
+//! each such hop is executed by its protocol's own builder.
+
+use std::fmt;
+use std::sync::Arc;
+
+use num_bigint::BigInt;
+
+use super::types::{PoolEdge, ERG_TOKEN_ID};
+use crate::calculator::{
+    calculate_input, calculate_output, calculate_token_to_erg_output, would_breach_pool_min_erg,
+};
+use crate::state::{AmmPool, PoolType, TokenAmount};
+
+/// Token metadata for one side of a source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceToken {
+    pub token_id: String,
+    pub name: Option<String>,
+    pub decimals: u8,
+}
+
+impl SourceToken {
+    pub fn erg() -> Self {
+        Self {
+            token_id: ERG_TOKEN_ID.to_string(),
+            name: Some("ERG".to_string()),
+            decimals: 9,
+        }
+    }
+
+    pub fn new(token_id: impl Into<String>, name: impl Into<String>, decimals: u8) -> Self {
+        Self {
+            token_id: token_id.into(),
+            name: Some(name.into()),
+            decimals,
+        }
+    }
+
+    fn is_erg(&self) -> bool {
+        self.token_id == ERG_TOKEN_ID
+    }
+}
+
+/// A directed edge with its own pricing function.
+pub trait LiquiditySource: fmt::Debug + Send + Sync {
+    /// Box identity shared by every direction of the same venue (bank or LP
+    /// NFT), so a path never spends the same box twice.
+    fn venue_id(&self) -> &str;
+    /// Shown as the hop's `pool_type`, e.g. `"SigmaUSD"`.
+    fn protocol(&self) -> &str;
+    fn token_in(&self) -> &SourceToken;
+    fn token_out(&self) -> &SourceToken;
+    /// Output for `amount_in`, or `None` when the venue would reject it
+    /// (limits, reserve ratio, pool drained).
+    fn output(&self, amount_in: u64) -> Option<u64>;
+    /// Smallest input that yields at least `amount_out`.
+    fn input_for(&self, amount_out: u64) -> Option<u64>;
+    /// Fee charged on `amount_in`, in input units.
+    fn fee(&self, amount_in: u64) -> u64;
+    /// Marginal rate before fees, in output units per input unit.
+    fn spot_rate(&self) -> f64;
+    /// Largest input accepted in a single transaction.
+    fn capacity(&self) -> u64;
+    /// `true` when the rate does not move with size (banks).
+    fn is_fixed_price(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BankDirection {
+    /// ERG → token.
+    Mint,
+    /// Token → ERG.
+    Redeem,
+}
+
+/// Fixed-price bank leg. The bank prices the whole transaction from its
+/// pre-transaction state, so the rate does not move with size; only the
+/// per-transaction token limit bounds it.
+#[derive(Debug, Clone)]
+pub struct BankSource {
+    pub venue_id: String,
+    pub protocol: String,
+    pub direction: BankDirection,
+    pub token_in: SourceToken,
+    pub token_out: SourceToken,
+    /// nanoERG per `price_per` raw token units, before fees.
+    pub price_nano: u64,
+    pub price_per: u64,
+    /// Fee on the ERG value: added on mint, deducted on redeem.
+    pub fee_num: u64,
+    pub fee_denom: u64,
+    /// Raw tokens the bank will mint or redeem in one transaction.
+    pub max_tokens: u64,
+}
+
+impl BankSource {
+    pub fn mint(
+        venue_id: impl Into<String>,
+        protocol: impl Into<String>,
+        token: SourceToken,
+        price_nano: u64,
+        price_per: u64,
+    ) -> Self {
+        Self {
+            venue_id: venue_id.into(),
+            protocol: protocol.into(),
+            direction: BankDirection::Mint,
+            token_in: SourceToken::erg(),
+            token_out: token,
+            price_nano,
+            price_per: price_per.max(1),
+            fee_num: 0,
+            fee_denom: 1,
+            max_tokens: u64::MAX,
+        }
+    }
+
+    pub fn redeem(
+        venue_id: impl Into<String>,
+        protocol: impl Into<String>,
+        token: SourceToken,
+        price_nano: u64,
+        price_per: u64,
+    ) -> Self {
+        Self {
+            direction: BankDirection::Redeem,
+            token_in: token.clone(),
+            token_out: SourceToken::erg(),
+            ..Self::mint(venue_id, protocol, token, price_nano, price_per)
+        }
+    }
+
+    pub fn with_fee(mut self, fee_num: u64, fee_denom: u64) -> Self {
+        self.fee_num = fee_num;
+        self.fee_denom = fee_denom.max(1);
+        self
+    }
+
+    pub fn with_max_tokens(mut self, max_tokens: u64) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    fn base_nano(&self, tokens: u64) -> u128 {
+        tokens as u128 * self.price_nano as u128 / self.price_per as u128
+    }
+
+    fn fee_nano(&self, base: u128) -> u128 {
+        base * self.fee_num as u128 / self.fee_denom as u128
+    }
+
+    /// ERG paid to mint `tokens` (mint) or received for redeeming them (redeem).
+    fn erg_for_tokens(&self, tokens: u64) -> u128 {
+        let base = self.base_nano(tokens);
+        match self.direction {
+            BankDirection::Mint => base + self.fee_nano(base),
+            BankDirection::Redeem => base - self.fee_nano(base),
+        }
+    }
+
+    /// Tokens per nanoERG including fees, as a ratio (num, denom).
+    fn tokens_per_nano(&self) -> (u128, u128) {
+        let (f_num, f_denom) = (self.fee_num as u128, self.fee_denom as u128);
+        let fee_factor = match self.direction {
+            BankDirection::Mint => f_denom + f_num,
+            BankDirection::Redeem => f_denom.saturating_sub(f_num),
+        };
+        (
+            self.price_per as u128 * f_denom,
+            self.price_nano as u128 * fee_factor,
+        )
+    }
+}
+
+fn to_u64(v: u128) -> Option<u64> {
+    u64::try_from(v).ok()
+}
+
+impl LiquiditySource for BankSource {
+    fn venue_id(&self) -> &str {
+        &self.venue_id
+    }
+
+    fn protocol(&self) -> &str {
+        &self.protocol
+    }
+
+    fn token_in(&self) -> &SourceToken {
+        &self.token_in
+    }
+
+    fn token_out(&self) -> &SourceToken {
+        &self.token_out
+    }
+
+    fn output(&self, amount_in: u64) -> Option<u64> {
+        if amount_in == 0 || self.price_nano == 0 {
+            return None;
+        }
+        match self.direction {
+            BankDirection::Mint => {
+                let (num, denom) = self.tokens_per_nano();
+                let mut tokens = to_u64(amount_in as u128 * num / denom)?;
+                // Per-unit rounding can leave the estimate one unit over budget
+                while tokens > 0 && self.erg_for_tokens(tokens) > amount_in as u128 {
+                    tokens -= 1;
+                }
+                (tokens > 0 && tokens <= self.max_tokens).then_some(tokens)
+            }
+            BankDirection::Redeem => {
+                if amount_in > self.max_tokens {
+                    return None;
+                }
+                to_u64(self.erg_for_tokens(amount_in)).filter(|erg| *erg > 0)
+            }
+        }
+    }
+
+    fn input_for(&self, amount_out: u64) -> Option<u64> {
+        if amount_out == 0 || self.price_nano == 0 {
+            return None;
+        }
+        match self.direction {
+            BankDirection::Mint => {
+                if amount_out > self.max_tokens {
+                    return None;
+                }
+                to_u64(self.erg_for_tokens(amount_out))
+            }
+            BankDirection::Redeem => {
+                let (num, denom) = self.tokens_per_nano();
+                if denom == 0 {
+                    return None;
+                }
+                let mut tokens = to_u64((amount_out as u128 * num).div_ceil(denom))?;
+                while self.erg_for_tokens(tokens) < amount_out as u128 {
+                    tokens = tokens.checked_add(1)?;
+                }
+                (tokens <= self.max_tokens).then_some(tokens)
+            }
+        }
+    }
+
+    fn fee(&self, amount_in: u64) -> u64 {
+        let (f_num, f_denom) = (self.fee_num as u128, self.fee_denom as u128);
+        let fee = match self.direction {
+            BankDirection::Mint => amount_in as u128 * f_num / (f_denom + f_num),
+            BankDirection::Redeem => amount_in as u128 * f_num / f_denom,
+        };
+        to_u64(fee).unwrap_or(u64::MAX)
+    }
+
+    fn spot_rate(&self) -> f64 {
+        if self.price_nano == 0 {
+            return 0.0;
+        }
+        let tokens_per_nano = self.price_per as f64 / self.price_nano as f64;
+        match self.direction {
+            BankDirection::Mint => tokens_per_nano,
+            BankDirection::Redeem => 1.0 / tokens_per_nano,
+        }
+    }
+
+    fn capacity(&self) -> u64 {
+        match self.direction {
+            BankDirection::Mint if self.max_tokens == u64::MAX => u64::MAX,
+            BankDirection::Mint => to_u64(self.erg_for_tokens(self.max_tokens)).unwrap_or(u64::MAX),
+            BankDirection::Redeem => self.max_tokens,
+        }
+    }
+
+    fn is_fixed_price(&self) -> bool {
+        true
+    }
+}
+
+/// Constant-product pool outside Spectrum (e.g. the Dexy LP). `fee_num` is
+/// the pass-through portion, as for `AmmPool` (997/1000 = 0.3% fee).
+#[derive(Debug, Clone)]
+pub struct CfmmSource {
+    pub venue_id: String,
+    pub protocol: String,
+    pub token_in: SourceToken,
+    pub token_out: SourceToken,
+    pub reserves_in: u64,
+    pub reserves_out: u64,
+    pub fee_num: i32,
+    pub fee_denom: i32,
+}
+
+impl CfmmSource {
+    /// Both directions of one pool.
+    pub fn pair(
+        venue_id: &str,
+        protocol: &str,
+        (token_a, reserves_a): (SourceToken, u64),
+        (token_b, reserves_b): (SourceToken, u64),
+        fee_num: i32,
+        fee_denom: i32,
+    ) -> [CfmmSource; 2] {
+        let leg = |token_in: &SourceToken, r_in, token_out: &SourceToken, r_out| CfmmSource {
+            venue_id: venue_id.to_string(),
+            protocol: protocol.to_string(),
+            token_in: token_in.clone(),
+            token_out: token_out.clone(),
+            reserves_in: r_in,
+            reserves_out: r_out,
+            fee_num,
+            fee_denom,
+        };
+        [
+            leg(&token_a, reserves_a, &token_b, reserves_b),
+            leg(&token_b, reserves_b, &token_a, reserves_a),
+        ]
+    }
+}
+
+impl LiquiditySource for CfmmSource {
+    fn venue_id(&self) -> &str {
+        &self.venue_id
+    }
+
+    fn protocol(&self) -> &str {
+        &self.protocol
+    }
+
+    fn token_in(&self) -> &SourceToken {
+        &self.token_in
+    }
+
+    fn token_out(&self) -> &SourceToken {
+        &self.token_out
+    }
+
+    fn output(&self, amount_in: u64) -> Option<u64> {
+        let out = if self.token_out.is_erg() {
+            calculate_token_to_erg_output(
+                self.reserves_in,
+                self.reserves_out,
+                amount_in,
+                self.fee_num,
+                self.fee_denom,
+            )
+        } else {
+            calculate_output(
+                self.reserves_in,
+                self.reserves_out,
+                amount_in,
+                self.fee_num,
+                self.fee_denom,
+            )
+        };
+        (out > 0).then_some(out)
+    }
+
+    fn input_for(&self, amount_out: u64) -> Option<u64> {
+        if self.token_out.is_erg() && would_breach_pool_min_erg(self.reserves_out, amount_out) {
+            return None;
+        }
+        calculate_input(
+            self.reserves_in,
+            self.reserves_out,
+            amount_out,
+            self.fee_num,
+            self.fee_denom,
+        )
+    }
+
+    fn fee(&self, amount_in: u64) -> u64 {
+        let fee = BigInt::from(amount_in) * BigInt::from(self.fee_denom - self.fee_num)
+            / BigInt::from(self.fee_denom.max(1));
+        fee.try_into().unwrap_or(0)
+    }
+
+    fn spot_rate(&self) -> f64 {
+        if self.reserves_in == 0 {
+            return 0.0;
+        }
+        self.reserves_out as f64 / self.reserves_in as f64
+    }
+
+    fn capacity(&self) -> u64 {
+        self.reserves_in
+    }
+}
+
+/// Pricing dispatch: Spectrum edges use the pool's CFMM math, source edges
+/// their own functions.
+impl PoolEdge {
+    pub fn output_for(&self, amount_in: u64) -> u64 {
+        if let Some(source) = &self.source {
+            return source.output(amount_in).unwrap_or(0);
+        }
+        self.output_with_reserves(self.reserves_in, self.reserves_out, amount_in)
+    }
+
+    /// Spectrum pool output against the given reserves instead of the
+    /// snapshot ones, for simulating several trades through the same pool.
+    pub(crate) fn output_with_reserves(
+        &self,
+        reserves_in: u64,
+        reserves_out: u64,
+        amount_in: u64,
+    ) -> u64 {
+        if self.token_out == ERG_TOKEN_ID {
+            calculate_token_to_erg_output(
+                reserves_in,
+                reserves_out,
+                amount_in,
+                self.pool.fee_num,
+                self.pool.fee_denom,
+            )
+        } else {
+            calculate_output(
+                reserves_in,
+                reserves_out,
+                amount_in,
+                self.pool.fee_num,
+                self.pool.fee_denom,
+            )
+        }
+    }
+
+    pub fn input_for(&self, amount_out: u64) -> Option<u64> {
+        if let Some(source) = &self.source {
+            return source.input_for(amount_out);
+        }
+        if self.token_out == ERG_TOKEN_ID
+            && would_breach_pool_min_erg(self.reserves_out, amount_out)
+        {
+            return None;
+        }
+        calculate_input(
+            self.reserves_in,
+            self.reserves_out,
+            amount_out,
+            self.pool.fee_num,
+            self.pool.fee_denom,
+        )
+    }
+
+    pub fn fee_for(&self, amount_in: u64) -> u64 {
+        match &self.source {
+            Some(source) => source.fee(amount_in),
+            None => {
+                (amount_in as f64 * (1.0 - self.pool.fee_num as f64 / self.pool.fee_denom as f64))
+                    as u64
+            }
+        }
+    }
+
+    pub fn spot_rate(&self) -> f64 {
+        match &self.source {
+            Some(source) => source.spot_rate(),
+            None if self.reserves_in == 0 => 0.0,
+            None => self.reserves_out as f64 / self.reserves_in as f64,
+        }
+    }
+
+    /// `"N2T"` / `"T2T"` for Spectrum pools, the protocol name otherwise.
+    pub fn hop_type(&self) -> String {
+        match &self.source {
+            Some(source) => source.protocol().to_string(),
+            None => format!("{:?}", self.pool.pool_type),
+        }
+    }
+
+    pub fn is_spectrum(&self) -> bool {
+        self.source.is_none()
+    }
+
+    /// Edge for `source`, with a descriptor pool carrying the venue ID and
+    /// token metadata so naming and pool-reuse checks work unchanged.
+    /// Virtual reserves are the source capacity at its spot rate.
+    pub fn from_source(source: Arc<dyn LiquiditySource>) -> Self {
+        let token = |t: &SourceToken| TokenAmount {
+            token_id: t.token_id.clone(),
+            amount: 0,
+            decimals: Some(t.decimals),
+            name: t.name.clone(),
+        };
+        let (token_in, token_out) = (source.token_in(), source.token_out());
+        let (pool_type, token_x, token_y) = if token_in.is_erg() {
+            (PoolType::N2T, None, token(token_out))
+        } else if token_out.is_erg() {
+            (PoolType::N2T, None, token(token_in))
+        } else {
+            (PoolType::T2T, Some(token(token_in)), token(token_out))
+        };
+        let reserves_in = source.capacity();
+        let reserves_out = (reserves_in as f64 * source.spot_rate()).min(u64::MAX as f64) as u64;
+        let pool = AmmPool {
+            pool_id: source.venue_id().to_string(),
+            pool_type,
+            box_id: source.venue_id().to_string(),
+            erg_reserves: None,
+            token_x,
+            token_y,
+            lp_token_id: String::new(),
+            lp_circulating: 0,
+            fee_num: 1,
+            fee_denom: 1,
+        };
+        PoolEdge {
+            pool,
+            token_in: token_in.token_id.clone(),
+            token_out: token_out.token_id.clone(),
+            reserves_in,
+            reserves_out,
+            source: Some(source),
+        }
+    }
+}
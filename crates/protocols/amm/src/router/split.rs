@@ -1,15 +1,32 @@
 //! Split-route optimization.
+//!
+//! Allocation follows marginal-rate equalization: input goes chunk by chunk
+//! to the route with the best marginal output, then pairwise transfers with
+//! shrinking steps level the marginal rates out. Routes are simulated jointly
+//! so pools shared between routes are not counted twice.
+
+use std::collections::HashMap;
 
 use super::arb::route_rate_at_input;
-use super::search::{find_paths, path_ends_in_erg, quote_allocation, quote_route, route_score};
+use super::search::{
+    find_k_best_paths, path_ends_in_erg, quote_allocation, quote_route, route_score,
+};
 use super::types::{
     miner_fees_for_hops, PoolEdge, PoolGraph, SplitAllocation, SplitAllocationDetail, SplitRoute,
-    SplitRouteDetail,
+    SplitRouteDetail, DEFAULT_PATH_BEAM,
 };
 
-/// Grid-search optimal split across up to 3 routes to maximize fee-aware net.
+/// Chunks handed out by the greedy allocation pass.
+const SPLIT_CHUNKS: u64 = 100;
+/// Halvings of the transfer step during refinement.
+const REFINE_LEVELS: u32 = 8;
+/// Upper bound on accepted transfers per refinement level.
+const MAX_TRANSFERS_PER_LEVEL: usize = 64;
+
+/// Optimal split across up to `max_splits` of `paths` (best first) to maximize
+/// fee-aware net output.
 pub fn optimize_split(paths: &[Vec<PoolEdge>], total_input: u64, max_splits: usize) -> SplitRoute {
-    let max_splits = max_splits.min(paths.len()).min(3);
+    let max_splits = max_splits.min(paths.len());
 
     if max_splits <= 1 || paths.is_empty() {
         let (output, fees, net) = paths
@@ -30,12 +47,29 @@ pub fn optimize_split(paths: &[Vec<PoolEdge>], total_input: u64, max_splits: usi
         };
     }
 
-    if max_splits == 2 {
-        return optimize_split_two(paths, total_input);
-    }
+    let paths = &paths[..max_splits];
+    let mut inputs = greedy_allocation(paths, total_input);
+    refine_allocation(paths, total_input, &mut inputs);
+    consolidate_allocation(paths, &mut inputs);
 
-    // For 3 routes: iterative pairwise optimization
-    optimize_split_multi(paths, total_input, max_splits)
+    let outputs = quote_joint(paths, &inputs);
+    let allocations: Vec<SplitAllocation> = inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, &input)| input > 0)
+        .map(|(k, &input)| SplitAllocation {
+            route_index: k,
+            fraction: if total_input > 0 {
+                input as f64 / total_input as f64
+            } else {
+                0.0
+            },
+            input_amount: input,
+            output_amount: outputs[k],
+        })
+        .collect();
+
+    finalize_split(paths, total_input, allocations)
 }
 
 fn finalize_split(
@@ -65,140 +99,169 @@ fn finalize_split(
     }
 }
 
-fn optimize_split_two(paths: &[Vec<PoolEdge>], total_input: u64) -> SplitRoute {
-    let mut best_score: u64 = 0;
-    let mut best_permille: u64 = 1000; // permille for path[0]
-
-    for permille in 0..=1000u64 {
-        let input_a = total_input * permille / 1000;
-        let input_b = total_input - input_a;
-
-        let (_, _, score_a) = quote_allocation(&paths[0], input_a);
-        let (_, _, score_b) = quote_allocation(&paths[1], input_b);
-        let score = score_a.saturating_add(score_b);
+/// Outputs of executing every route with its input, one after another.
+/// Spectrum pools carry their reserves over between routes; protocol banks
+/// and foreign sources are priced independently per route.
+fn quote_joint(paths: &[Vec<PoolEdge>], inputs: &[u64]) -> Vec<u64> {
+    let mut reserves: HashMap<(&str, &str), u64> = HashMap::new();
+    paths
+        .iter()
+        .zip(inputs)
+        .map(|(path, &input)| {
+            if input == 0 {
+                return 0;
+            }
+            let mut amount = input;
+            for edge in path {
+                amount = if edge.source.is_some() {
+                    edge.output_for(amount)
+                } else {
+                    let key_in = (edge.pool.pool_id.as_str(), edge.token_in.as_str());
+                    let key_out = (edge.pool.pool_id.as_str(), edge.token_out.as_str());
+                    let r_in = reserves.get(&key_in).copied().unwrap_or(edge.reserves_in);
+                    let r_out = reserves.get(&key_out).copied().unwrap_or(edge.reserves_out);
+                    let out = edge.output_with_reserves(r_in, r_out, amount);
+                    if out > 0 {
+                        reserves.insert(key_in, r_in.saturating_add(amount));
+                        reserves.insert(key_out, r_out.saturating_sub(out));
+                    }
+                    out
+                };
+                if amount == 0 {
+                    return 0;
+                }
+            }
+            amount
+        })
+        .collect()
+}
 
-        if score > best_score {
-            best_score = score;
-            best_permille = permille;
-        }
+/// Net output of an allocation: joint output minus miner fees of the routes
+/// used, when the target is ERG (as in `route_score`).
+fn joint_score(paths: &[Vec<PoolEdge>], inputs: &[u64]) -> u64 {
+    let outputs = quote_joint(paths, inputs);
+    let total_output: u64 = outputs.iter().sum();
+    if !paths.first().map(|p| path_ends_in_erg(p)).unwrap_or(false) {
+        return total_output;
     }
+    let fees: u64 = inputs
+        .iter()
+        .zip(paths)
+        .filter(|(&input, _)| input > 0)
+        .map(|(_, p)| miner_fees_for_hops(p.len()))
+        .sum();
+    total_output.saturating_sub(fees)
+}
 
-    let input_a = total_input * best_permille / 1000;
-    let input_b = total_input - input_a;
-    let (out_a, _, _) = quote_allocation(&paths[0], input_a);
-    let (out_b, _, _) = quote_allocation(&paths[1], input_b);
-
-    let mut allocations = Vec::new();
-    if input_a > 0 {
-        allocations.push(SplitAllocation {
-            route_index: 0,
-            fraction: best_permille as f64 / 1000.0,
-            input_amount: input_a,
-            output_amount: out_a,
-        });
-    }
-    if input_b > 0 {
-        allocations.push(SplitAllocation {
-            route_index: 1,
-            fraction: (1000 - best_permille) as f64 / 1000.0,
-            input_amount: input_b,
-            output_amount: out_b,
-        });
+/// Hand out the input in `SPLIT_CHUNKS` chunks, each to the route whose joint
+/// output grows the most. Miner fees are left to the later passes, otherwise
+/// no second route would ever be opened by a small chunk.
+fn greedy_allocation(paths: &[Vec<PoolEdge>], total_input: u64) -> Vec<u64> {
+    let mut inputs = vec![0u64; paths.len()];
+    let chunk = (total_input / SPLIT_CHUNKS).max(1);
+    let mut remaining = total_input;
+
+    while remaining > 0 {
+        let step = chunk.min(remaining);
+        let mut best: Option<(usize, u64)> = None;
+        for k in 0..paths.len() {
+            inputs[k] += step;
+            let output: u64 = quote_joint(paths, &inputs).iter().sum();
+            inputs[k] -= step;
+            let beats = match best {
+                Some((_, o)) => output > o,
+                None => true,
+            };
+            if beats {
+                best = Some((k, output));
+            }
+        }
+        let k = best.map(|(k, _)| k).unwrap_or(0);
+        inputs[k] += step;
+        remaining -= step;
     }
-
-    finalize_split(paths, total_input, allocations)
+    inputs
 }
 
-fn optimize_split_multi(
-    paths: &[Vec<PoolEdge>],
-    total_input: u64,
-    max_splits: usize,
-) -> SplitRoute {
-    let n = max_splits.min(paths.len());
-    let mut fractions: Vec<u64> = vec![1000 / n as u64; n];
-    let remainder = 1000 - fractions.iter().sum::<u64>();
-    fractions[0] += remainder;
-
-    for _ in 0..5 {
-        for i in 0..n {
-            let mut best_score: u64 = 0;
-            let mut best_frac: u64 = fractions[i];
-
-            let other_sum: u64 = fractions
-                .iter()
-                .enumerate()
-                .filter(|&(j, _)| j != i)
-                .map(|(_, f)| f)
-                .sum();
-
-            let max_for_i = 1000 - other_sum;
-
-            for f in (0..=max_for_i).step_by(10) {
-                let mut test_fracs = fractions.clone();
-                test_fracs[i] = f;
-                let diff = max_for_i - f;
-                if other_sum > 0 {
-                    let mut remaining_diff = diff;
-                    for j in 0..n {
-                        if j != i && remaining_diff > 0 {
-                            let add = if j == n - 1 || (j == n - 2 && i == n - 1) {
-                                remaining_diff
-                            } else {
-                                (diff as f64 * fractions[j] as f64 / other_sum as f64).round()
-                                    as u64
-                            };
-                            let add = add.min(remaining_diff);
-                            test_fracs[j] = fractions[j] + add;
-                            remaining_diff -= add;
-                        }
-                    }
+/// Move input between route pairs while it improves the net score, halving
+/// the transfer size each level down to a fraction of a chunk.
+fn refine_allocation(paths: &[Vec<PoolEdge>], total_input: u64, inputs: &mut [u64]) {
+    let mut score = joint_score(paths, inputs);
+    let mut step = (total_input / SPLIT_CHUNKS / 2).max(1);
+
+    for _ in 0..REFINE_LEVELS {
+        for _ in 0..MAX_TRANSFERS_PER_LEVEL {
+            let mut best: Option<(usize, usize, u64)> = None;
+            for from in 0..paths.len() {
+                if inputs[from] < step {
+                    continue;
                 }
-
-                let score: u64 = (0..n)
-                    .map(|k| {
-                        let inp = total_input * test_fracs[k] / 1000;
-                        quote_allocation(&paths[k], inp).2
-                    })
-                    .sum();
-
-                if score > best_score {
-                    best_score = score;
-                    best_frac = f;
+                for to in 0..paths.len() {
+                    if to == from {
+                        continue;
+                    }
+                    inputs[from] -= step;
+                    inputs[to] += step;
+                    let candidate = joint_score(paths, inputs);
+                    inputs[to] -= step;
+                    inputs[from] += step;
+                    let beats = match best {
+                        Some((_, _, s)) => candidate > s,
+                        None => candidate > score,
+                    };
+                    if beats {
+                        best = Some((from, to, candidate));
+                    }
                 }
             }
-
-            fractions[i] = best_frac;
-        }
-    }
-
-    let sum: u64 = fractions.iter().sum();
-    if sum != 1000 && sum > 0 {
-        let scale = 1000.0 / sum as f64;
-        for f in &mut fractions {
-            *f = (*f as f64 * scale).round() as u64;
+            let Some((from, to, candidate)) = best else {
+                break;
+            };
+            inputs[from] -= step;
+            inputs[to] += step;
+            score = candidate;
         }
-        let new_sum: u64 = fractions.iter().sum();
-        if new_sum != 1000 {
-            let diff = 1000i64 - new_sum as i64;
-            fractions[0] = (fractions[0] as i64 + diff) as u64;
+        if step == 1 {
+            break;
         }
+        step /= 2;
     }
+}
 
-    let allocations: Vec<SplitAllocation> = (0..n)
-        .filter(|&k| fractions[k] > 0)
-        .map(|k| {
-            let input = total_input * fractions[k] / 1000;
-            let (output, _, _) = quote_allocation(&paths[k], input);
-            SplitAllocation {
-                route_index: k,
-                fraction: fractions[k] as f64 / 1000.0,
-                input_amount: input,
-                output_amount: output,
+/// Fold whole allocations into other routes when saving the extra miner fee
+/// outweighs the price impact.
+fn consolidate_allocation(paths: &[Vec<PoolEdge>], inputs: &mut [u64]) {
+    let mut score = joint_score(paths, inputs);
+    loop {
+        let mut best: Option<(usize, usize, u64)> = None;
+        for from in 0..paths.len() {
+            if inputs[from] == 0 {
+                continue;
             }
-        })
-        .collect();
-
-    finalize_split(paths, total_input, allocations)
+            for to in 0..paths.len() {
+                if to == from {
+                    continue;
+                }
+                let mut candidate_inputs = inputs.to_vec();
+                candidate_inputs[to] += candidate_inputs[from];
+                candidate_inputs[from] = 0;
+                let candidate = joint_score(paths, &candidate_inputs);
+                let beats = match best {
+                    Some((_, _, s)) => candidate > s,
+                    None => candidate > score,
+                };
+                if beats {
+                    best = Some((from, to, candidate));
+                }
+            }
+        }
+        let Some((from, to, candidate)) = best else {
+            return;
+        };
+        inputs[to] += inputs[from];
+        inputs[from] = 0;
+        score = candidate;
+    }
 }
 
 /// Optimal split with full route details. Only returned if > 0.5% better on net.
@@ -211,7 +274,14 @@ pub fn optimize_split_detailed(
     max_splits: usize,
     min_rate_filter: Option<(f64, u8)>,
 ) -> Option<SplitRouteDetail> {
-    let all_paths = find_paths(graph, source_token, target_token, max_hops);
+    let all_paths = find_k_best_paths(
+        graph,
+        source_token,
+        target_token,
+        total_input,
+        max_hops,
+        (max_splits * 2).max(DEFAULT_PATH_BEAM),
+    );
     if all_paths.is_empty() {
         return None;
     }
@@ -255,7 +325,7 @@ pub fn optimize_split_detailed(
         return None;
     }
 
-    let max_splits = max_splits.min(quoted.len()).min(3);
+    let max_splits = max_splits.min(quoted.len());
     if max_splits < 2 {
         return None;
     }
@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use super::*;
 use crate::calculator::apply_slippage;
@@ -304,6 +305,7 @@ fn test_depth_tiers_formula() {
         token_out: "tok".to_string(),
         reserves_in: 100_000_000_000, // 100 ERG
         reserves_out: 50_000,
+        source: None,
     };
 
     let tiers = calculate_depth_tiers(&edge);
@@ -333,6 +335,7 @@ fn test_depth_tiers_increasing() {
         token_out: "tok".to_string(),
         reserves_in: 500_000_000_000,
         reserves_out: 100_000,
+        source: None,
     };
 
     let tiers = calculate_depth_tiers(&edge);
@@ -688,3 +691,233 @@ fn test_direct_pair_included_despite_low_liquidity() {
     ensure_direct_pair_edges(&mut graph, &pools, ERG_TOKEN_ID, "etosi");
     assert_eq!(find_paths(&graph, ERG_TOKEN_ID, "etosi", 3).len(), 1);
 }
+
+fn sigusd_bank() -> Vec<Arc<dyn LiquiditySource>> {
+    // 0.5 ERG per USD; SigUSD has 2 decimals; 2% fee
+    let sigusd = SourceToken::new("sigusd", "SigUSD", 2);
+    vec![
+        Arc::new(
+            BankSource::mint("bank", "SigmaUSD", sigusd.clone(), 500_000_000, 100).with_fee(2, 100),
+        ),
+        Arc::new(BankSource::redeem("bank", "SigmaUSD", sigusd, 500_000_000, 100).with_fee(2, 100)),
+    ]
+}
+
+#[test]
+fn test_bank_source_mint_and_redeem() {
+    let sigusd = SourceToken::new("sigusd", "SigUSD", 2);
+    let mint = BankSource::mint("bank", "SigmaUSD", sigusd.clone(), 500_000_000, 100)
+        .with_fee(2, 100)
+        .with_max_tokens(1_000);
+
+    // 5_100_000 nanoERG per cent including the fee
+    assert_eq!(mint.output(1_000_000_000), Some(196));
+    assert_eq!(mint.input_for(196), Some(999_600_000));
+    assert_eq!(mint.output(100_000_000_000), None, "above mint limit");
+    assert_eq!(mint.capacity(), 5_100_000_000);
+
+    let redeem = BankSource::redeem("bank", "SigmaUSD", sigusd, 500_000_000, 100).with_fee(2, 100);
+    assert_eq!(redeem.output(100), Some(490_000_000));
+    assert_eq!(redeem.input_for(490_000_000), Some(100));
+    assert_eq!(redeem.fee(100), 2);
+}
+
+#[test]
+fn test_router_uses_bank_when_pool_is_thin() {
+    // 100 ERG vs 100.00 SigUSD: far worse than the bank's 0.51 ERG per USD
+    let pools = vec![make_n2t_pool(
+        "p1",
+        100_000_000_000,
+        "sigusd",
+        "SigUSD",
+        10_000,
+        997,
+    )];
+    let mut graph = build_pool_graph(&pools, DEFAULT_MIN_LIQUIDITY_NANO);
+    add_liquidity_sources(&mut graph, &sigusd_bank());
+
+    let routes = find_best_routes(&graph, ERG_TOKEN_ID, "sigusd", 10_000_000_000, 3, 5);
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0].hops[0].pool_type, "SigmaUSD");
+    assert_eq!(routes[0].hops[0].pool_id, "bank");
+    assert_eq!(routes[0].total_output, 1_960);
+    assert!((routes[0].total_price_impact - 2.0).abs() < 0.01);
+    assert_eq!(routes[1].hops[0].pool_type, "N2T");
+
+    // Bank hops are quoted, not built as swaps
+    assert!(!routes[0].hops[0].executable);
+    assert!(!routes[0].is_executable());
+    assert!(routes[1].is_executable());
+}
+
+#[test]
+fn test_router_chains_mint_into_pool() {
+    // Dexy bank mints DexyUSD (3 decimals) at the oracle; a T2T pool trades
+    // it 1:1 for SigUSD while the direct SigUSD pool is thin.
+    let dexy_usd = SourceToken::new("dexyusd", "DexyUSD", 3);
+    let sources: Vec<Arc<dyn LiquiditySource>> = vec![Arc::new(
+        BankSource::mint("dexy_bank", "Dexy", dexy_usd, 500_000_000, 1_000).with_fee(5, 1000),
+    )];
+    let pools = vec![
+        make_n2t_pool("thin", 100_000_000_000, "sigusd", "SigUSD", 10_000, 997),
+        make_t2t_pool(
+            "t2t",
+            "dexyusd",
+            "DexyUSD",
+            100_000_000,
+            "sigusd",
+            "SigUSD",
+            10_000_000,
+            997,
+        ),
+    ];
+    let mut graph = build_pool_graph(&pools, 0);
+    add_liquidity_sources(&mut graph, &sources);
+
+    let routes = find_best_routes(&graph, ERG_TOKEN_ID, "sigusd", 10_000_000_000, 3, 5);
+    let best = &routes[0];
+    assert_eq!(best.hops.len(), 2);
+    assert_eq!(best.hops[0].pool_type, "Dexy");
+    assert_eq!(
+        best.hops[0].pool_display_name.as_deref(),
+        Some("Dexy ERG/DexyUSD")
+    );
+    assert_eq!(best.hops[1].pool_id, "t2t");
+}
+
+#[test]
+fn test_bank_not_reused_within_path() {
+    let mut graph = build_pool_graph(&[], 0);
+    add_liquidity_sources(&mut graph, &sigusd_bank());
+
+    // Mint and redeem share the bank venue, so no ERG → SigUSD → ERG cycle
+    assert!(find_cycles(&graph, 3).is_empty());
+    assert_eq!(find_paths(&graph, ERG_TOKEN_ID, "sigusd", 3).len(), 1);
+}
+
+#[test]
+fn test_k_best_matches_exhaustive_best() {
+    let pools = vec![
+        make_n2t_pool("direct", 10_000_000_000, "sigusd", "SigUSD", 5_000, 997),
+        make_n2t_pool("p1", 100_000_000_000, "gort", "GORT", 100_000, 997),
+        make_t2t_pool(
+            "t2t", "gort", "GORT", 50_000, "sigusd", "SigUSD", 25_000, 997,
+        ),
+    ];
+    let graph = build_pool_graph(&pools, 0);
+    let input = 1_000_000_000;
+
+    let best_exhaustive = find_paths(&graph, ERG_TOKEN_ID, "sigusd", 3)
+        .iter()
+        .filter_map(|p| quote_route(p, input).map(|r| r.total_output))
+        .max()
+        .unwrap();
+    let k_best = find_k_best_paths(&graph, ERG_TOKEN_ID, "sigusd", input, 3, 4);
+    assert!(!k_best.is_empty());
+    let first = quote_route(&k_best[0], input).unwrap();
+    assert_eq!(first.total_output, best_exhaustive);
+}
+
+#[test]
+fn test_k_best_limits_and_orders_paths() {
+    let pools: Vec<AmmPool> = (1..=5u64)
+        .map(|i| {
+            make_n2t_pool(
+                &format!("p{}", i),
+                i * 10_000_000_000,
+                "tok",
+                "Token",
+                i * 10_000,
+                997,
+            )
+        })
+        .collect();
+    let graph = build_pool_graph(&pools, 0);
+    let paths = find_k_best_paths(&graph, ERG_TOKEN_ID, "tok", 1_000_000_000, 3, 2);
+
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[0][0].pool.pool_id, "p5");
+    assert_eq!(paths[1][0].pool.pool_id, "p4");
+}
+
+#[test]
+fn test_k_best_by_output_needs_least_input() {
+    let pools = vec![
+        make_n2t_pool("deep", 1_000_000_000_000, "tok", "Token", 1_000_000, 997),
+        make_n2t_pool("shallow", 50_000_000_000, "tok", "Token", 50_000, 995),
+    ];
+    let graph = build_pool_graph(&pools, 0);
+    let paths = find_k_best_paths_by_output(&graph, ERG_TOKEN_ID, "tok", 1_000, 3, 4);
+
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[0][0].pool.pool_id, "deep");
+    let first = quote_route_reverse(&paths[0], 1_000).unwrap();
+    let second = quote_route_reverse(&paths[1], 1_000).unwrap();
+    assert!(first.total_input <= second.total_input);
+}
+
+#[test]
+fn test_split_beyond_three_routes() {
+    let pools: Vec<AmmPool> = (1..=5)
+        .map(|i| {
+            make_n2t_pool(
+                &format!("p{}", i),
+                100_000_000_000,
+                "tok",
+                "Token",
+                100_000,
+                997,
+            )
+        })
+        .collect();
+    let graph = build_pool_graph_with_limit(&pools, 0, 5);
+    let input = 100_000_000_000;
+    let paths = find_k_best_paths(&graph, ERG_TOKEN_ID, "tok", input, 1, 5);
+    assert_eq!(paths.len(), 5);
+
+    let split = optimize_split(&paths, input, 5);
+    assert_eq!(split.allocations.len(), 5);
+    for alloc in &split.allocations {
+        assert!(
+            (alloc.fraction - 0.2).abs() < 0.05,
+            "Expected ~20% per pool, got {:.0}%",
+            alloc.fraction * 100.0
+        );
+    }
+    let allocated: u64 = split.allocations.iter().map(|a| a.input_amount).sum();
+    assert_eq!(allocated, input);
+
+    let single = quote_route(&paths[0], input).unwrap();
+    assert!(split.net_output > single.net_output);
+}
+
+#[test]
+fn test_split_shared_pool_not_double_counted() {
+    // Both routes go ERG -> GORT through the same pool, then fork.
+    let pools = vec![
+        make_n2t_pool("shared", 100_000_000_000, "gort", "GORT", 100_000, 997),
+        make_t2t_pool(
+            "t2t_a", "gort", "GORT", 100_000, "sigusd", "SigUSD", 100_000, 997,
+        ),
+        make_t2t_pool(
+            "t2t_b", "gort", "GORT", 100_000, "sigusd", "SigUSD", 100_000, 997,
+        ),
+    ];
+    let graph = build_pool_graph(&pools, 0);
+    let input = 50_000_000_000;
+    let paths = find_k_best_paths(&graph, ERG_TOKEN_ID, "sigusd", input, 2, 2);
+    assert_eq!(paths.len(), 2);
+
+    let split = optimize_split(&paths, input, 2);
+    let independent: u64 = split
+        .allocations
+        .iter()
+        .map(|a| search::quote_allocation(&paths[a.route_index], a.input_amount).0)
+        .sum();
+    assert_eq!(split.allocations.len(), 2);
+    assert!(split.total_output < independent);
+
+    // Still beats sending everything down one fork.
+    let single = quote_route(&paths[0], input).unwrap();
+    assert!(split.total_output >= single.total_output);
+}
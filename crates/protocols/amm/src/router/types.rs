@@ -1,14 +1,18 @@
 //! Router types and constants.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
+use super::source::LiquiditySource;
 use crate::state::AmmPool;
 
 pub const ERG_TOKEN_ID: &str = "ERG";
 pub const DEFAULT_MIN_LIQUIDITY_NANO: u64 = 10_000_000_000; // 10 ERG
 pub const DEFAULT_MAX_POOLS_PER_PAIR: usize = 3;
+/// Paths kept per token and hop count by the k-best path search.
+pub const DEFAULT_PATH_BEAM: usize = 8;
 
 /// Network miner fee charged once per hop (each hop is its own tx).
 pub const MINER_FEE_PER_HOP: u64 = citadel_core::constants::TX_FEE_NANO as u64;
@@ -24,6 +28,9 @@ pub struct PoolEdge {
     pub token_out: String,
     pub reserves_in: u64,
     pub reserves_out: u64,
+    /// Set for protocol banks and foreign pools; `pool` is then a descriptor
+    /// and pricing goes through the source (see `PoolEdge::from_source`).
+    pub source: Option<Arc<dyn LiquiditySource>>,
 }
 
 #[derive(Debug, Clone)]
@@ -51,6 +58,10 @@ pub struct RouteHop {
     pub fee_denom: i32,
     pub reserves_in: u64,
     pub reserves_out: u64,
+    /// `false` for protocol banks and foreign pools: they are priced for
+    /// comparison, but only Spectrum pool hops can be built as swaps.
+    #[serde(default)]
+    pub executable: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,11 +80,22 @@ pub struct Route {
     pub effective_rate: f64,
 }
 
+impl Route {
+    /// Whether every hop can be built as a Spectrum swap.
+    pub fn is_executable(&self) -> bool {
+        self.hops.iter().all(|h| h.executable)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouteQuote {
     pub route: Route,
     pub min_output: u64,
     pub slippage_percent: f64,
+    /// Pools priced from unconfirmed boxes (see `MempoolProjection::flag_route`).
+    /// The route fails if any of their pending swaps is dropped.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unconfirmed_pools: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,427 @@
+//! Discovery of a user's open Spectrum order boxes, of any age.
+//!
+//! `find_pending_orders` only sees orders created in recent wallet history.
+//! This scans the indexer by order contract template instead, keeps boxes
+//! whose redeemer is one of the user's trees, and flags those a bot is
+//! unlikely to ever execute so they can be refunded together.
+
+use blake2::digest::{consts::U32, Digest};
+use blake2::Blake2b;
+use ergo_lib::ergotree_ir::ergo_tree::ErgoTree;
+use ergo_lib::ergotree_ir::serialization::SigmaSerializable;
+use ergo_tx::Eip12InputBox;
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::quote_swap;
+use crate::constants::{lp_template_bytes, swap_template_bytes};
+use crate::fetch::{
+    extract_coll_byte_constant, match_swap_template, parse_order_constants, redeemer_matches_user,
+};
+use crate::state::{AmmError, AmmPool, SwapInput, SwapOrderType};
+
+/// Orders older than this (~1 day) are flagged by default.
+pub const DEFAULT_STALE_ORDER_BLOCKS: u32 = 720;
+
+const TEMPLATE_PAGE_SIZE: u64 = 100;
+const MAX_TEMPLATE_PAGES: u64 = 20;
+
+/// Constant positions of the refund prop and pool NFT in LP order contracts.
+const LP_REFUND_PROP_INDEX: usize = 0;
+const DEPOSIT_POOL_NFT_INDEX: usize = 12;
+const REDEEM_POOL_NFT_INDEX: usize = 11;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderKind {
+    SwapSell,
+    SwapBuy,
+    Deposit,
+    Redeem,
+}
+
+impl OrderKind {
+    pub const ALL: [OrderKind; 4] = [
+        OrderKind::SwapSell,
+        OrderKind::SwapBuy,
+        OrderKind::Deposit,
+        OrderKind::Redeem,
+    ];
+
+    fn template_bytes(self) -> &'static [u8] {
+        match self {
+            OrderKind::SwapSell => &swap_template_bytes::N2T_SWAP_SELL,
+            OrderKind::SwapBuy => &swap_template_bytes::N2T_SWAP_BUY,
+            OrderKind::Deposit => &lp_template_bytes::N2T_DEPOSIT,
+            OrderKind::Redeem => &lp_template_bytes::N2T_REDEEM,
+        }
+    }
+
+    /// blake2b256 of the contract template, as indexed by the node.
+    pub fn template_hash(self) -> String {
+        let mut hasher = Blake2b::<U32>::new();
+        hasher.update(self.template_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    fn from_tree(tree: &ErgoTree) -> Option<Self> {
+        if let Some(swap) = match_swap_template(tree) {
+            return Some(match swap {
+                SwapOrderType::N2tSwapSell => OrderKind::SwapSell,
+                SwapOrderType::N2tSwapBuy => OrderKind::SwapBuy,
+            });
+        }
+        let tmpl = tree.template_bytes().ok()?;
+        if tmpl == *lp_template_bytes::N2T_DEPOSIT {
+            Some(OrderKind::Deposit)
+        } else if tmpl == *lp_template_bytes::N2T_REDEEM {
+            Some(OrderKind::Redeem)
+        } else {
+            None
+        }
+    }
+}
+
+/// Why an order is unlikely to be executed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StaleReason {
+    /// Open for more than the configured number of blocks.
+    Expired { age_blocks: u32 },
+    /// The pool can no longer pay the order's minimum output.
+    UnreachableMinOutput {
+        expected_output: u64,
+        min_output: u64,
+    },
+    /// The target pool was not found among live pools.
+    PoolNotFound,
+}
+
+/// An unspent order box owned by the user.
+#[derive(Debug, Clone)]
+pub struct UserOrder {
+    pub kind: OrderKind,
+    pub pool_id: String,
+    /// Locked swap input (swap orders only).
+    pub input: Option<SwapInput>,
+    /// Minimum swap output (swap orders only).
+    pub min_output: Option<u64>,
+    /// The user tree the order redeems to.
+    pub owner_ergo_tree: String,
+    pub order_box: Eip12InputBox,
+    pub stale: Vec<StaleReason>,
+}
+
+impl UserOrder {
+    pub fn is_stale(&self) -> bool {
+        !self.stale.is_empty()
+    }
+}
+
+/// Parse `order_box` as a Spectrum order redeeming to one of `user_ergo_trees`.
+pub fn parse_user_order(
+    order_box: &Eip12InputBox,
+    user_ergo_trees: &[String],
+) -> Option<UserOrder> {
+    let tree_bytes = hex::decode(&order_box.ergo_tree).ok()?;
+    let tree = ErgoTree::sigma_parse_bytes(&tree_bytes).ok()?;
+    let kind = OrderKind::from_tree(&tree)?;
+
+    let (pool_id, redeemer_bytes, input, min_output) = match kind {
+        OrderKind::SwapSell | OrderKind::SwapBuy => {
+            let swap_type = match kind {
+                OrderKind::SwapSell => SwapOrderType::N2tSwapSell,
+                _ => SwapOrderType::N2tSwapBuy,
+            };
+            let (pool_id, redeemer, base_amount, min_quote) =
+                parse_order_constants(&tree, swap_type).ok()?;
+            let input = match swap_type {
+                SwapOrderType::N2tSwapSell => SwapInput::Erg {
+                    amount: base_amount as u64,
+                },
+                SwapOrderType::N2tSwapBuy => SwapInput::Token {
+                    token_id: order_box.assets.first()?.token_id.clone(),
+                    amount: base_amount as u64,
+                },
+            };
+            (pool_id, redeemer, Some(input), Some(min_quote as u64))
+        }
+        OrderKind::Deposit | OrderKind::Redeem => {
+            let nft_index = match kind {
+                OrderKind::Deposit => DEPOSIT_POOL_NFT_INDEX,
+                _ => REDEEM_POOL_NFT_INDEX,
+            };
+            let pool_nft = extract_coll_byte_constant(&tree, nft_index, "PoolNFT").ok()?;
+            // LP orders pay out to the refund prop; as a P2PK tree that is
+            // the 0x00 header followed by the serialized SigmaProp constant.
+            let refund_prop = tree.get_constant(LP_REFUND_PROP_INDEX).ok()??;
+            let mut redeemer = vec![0u8];
+            redeemer.extend(refund_prop.sigma_serialize_bytes().ok()?);
+            (hex::encode(pool_nft), redeemer, None, None)
+        }
+    };
+
+    let owner_ergo_tree = user_ergo_trees
+        .iter()
+        .find(|t| redeemer_matches_user(&redeemer_bytes, t))?
+        .clone();
+
+    Some(UserOrder {
+        kind,
+        pool_id,
+        input,
+        min_output,
+        owner_ergo_tree,
+        order_box: order_box.clone(),
+        stale: Vec::new(),
+    })
+}
+
+/// Reasons `order` is stale at `current_height` given live `pools`.
+pub fn assess_order(
+    order: &UserOrder,
+    pools: &[AmmPool],
+    current_height: u32,
+    max_age_blocks: u32,
+) -> Vec<StaleReason> {
+    let mut reasons = Vec::new();
+
+    let created = order.order_box.creation_height.max(0) as u32;
+    let age_blocks = current_height.saturating_sub(created);
+    if age_blocks > max_age_blocks {
+        reasons.push(StaleReason::Expired { age_blocks });
+    }
+
+    let Some(pool) = pools.iter().find(|p| p.pool_id == order.pool_id) else {
+        reasons.push(StaleReason::PoolNotFound);
+        return reasons;
+    };
+    if let (Some(input), Some(min_output)) = (&order.input, order.min_output) {
+        let expected_output = quote_swap(pool, input).map_or(0, |q| q.output.amount);
+        if expected_output < min_output {
+            reasons.push(StaleReason::UnreachableMinOutput {
+                expected_output,
+                min_output,
+            });
+        }
+    }
+    reasons
+}
+
+/// All unspent swap, deposit and redeem orders redeeming to any of
+/// `user_ergo_trees`, each assessed against `pools`.
+pub async fn scan_user_orders(
+    node: &ergo_node_client::NodeClient,
+    user_ergo_trees: &[String],
+    pools: &[AmmPool],
+    current_height: u32,
+    max_age_blocks: u32,
+) -> Result<Vec<UserOrder>, AmmError> {
+    let mut orders = Vec::new();
+
+    for kind in OrderKind::ALL {
+        let hash = kind.template_hash();
+        let mut page = 0;
+        loop {
+            let boxes = node
+                .unspent_eip12_boxes_by_template_hash(
+                    &hash,
+                    page * TEMPLATE_PAGE_SIZE,
+                    TEMPLATE_PAGE_SIZE,
+                )
+                .await
+                .map_err(|e| AmmError::NodeError(format!("Failed to fetch order boxes: {}", e)))?;
+            let len = boxes.len() as u64;
+
+            for order_box in &boxes {
+                if let Some(mut order) = parse_user_order(order_box, user_ergo_trees) {
+                    order.stale = assess_order(&order, pools, current_height, max_age_blocks);
+                    orders.push(order);
+                }
+            }
+
+            page += 1;
+            if len < TEMPLATE_PAGE_SIZE {
+                break;
+            }
+            if page >= MAX_TEMPLATE_PAGES {
+                tracing::warn!(
+                    ?kind,
+                    "Order scan page cap reached; some orders may be missing"
+                );
+                break;
+            }
+        }
+    }
+
+    orders.sort_by_key(|o| o.order_box.creation_height);
+    tracing::info!(
+        "Found {} open orders ({} stale)",
+        orders.len(),
+        orders.iter().filter(|o| o.is_stale()).count()
+    );
+    Ok(orders)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ergo_tx::{with_test_dev_fee, DevFeeConfig, Eip12Asset, Eip12Output};
+
+    use super::*;
+    use crate::lp_order::build_lp_deposit_order_eip12;
+    use crate::state::{PoolType, SwapRequest, TokenAmount};
+    use crate::tx_builder::build_swap_order_eip12;
+
+    const USER_PK: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const USER_TREE: &str =
+        "0008cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const OTHER_TREE: &str =
+        "0008cd02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+    const TOKEN_ID: &str = "0000000000000000000000000000000000000000000000000000000000000002";
+
+    fn pool() -> AmmPool {
+        AmmPool {
+            pool_id: "0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+            pool_type: PoolType::N2T,
+            box_id: "pool_box".to_string(),
+            erg_reserves: Some(100_000_000_000),
+            token_x: None,
+            token_y: TokenAmount {
+                token_id: TOKEN_ID.to_string(),
+                amount: 1_000_000,
+                decimals: Some(0),
+                name: Some("TestToken".to_string()),
+            },
+            lp_token_id: "lp".to_string(),
+            lp_circulating: 1000,
+            fee_num: 997,
+            fee_denom: 1000,
+        }
+    }
+
+    fn user_utxo() -> Eip12InputBox {
+        Eip12InputBox {
+            box_id: "utxo".to_string(),
+            transaction_id: "tx".to_string(),
+            index: 0,
+            value: "10000000000".to_string(),
+            ergo_tree: USER_TREE.to_string(),
+            assets: vec![Eip12Asset {
+                token_id: TOKEN_ID.to_string(),
+                amount: "500000".to_string(),
+            }],
+            creation_height: 999_000,
+            additional_registers: HashMap::new(),
+            extension: HashMap::new(),
+        }
+    }
+
+    fn as_input(output: &Eip12Output) -> Eip12InputBox {
+        Eip12InputBox {
+            box_id: "order_box".to_string(),
+            transaction_id: "order_tx".to_string(),
+            index: 0,
+            value: output.value.clone(),
+            ergo_tree: output.ergo_tree.clone(),
+            assets: output.assets.clone(),
+            creation_height: output.creation_height,
+            additional_registers: output.additional_registers.clone(),
+            extension: HashMap::new(),
+        }
+    }
+
+    fn sell_order_box(min_output: u64) -> Eip12InputBox {
+        let pool = pool();
+        let request = SwapRequest {
+            pool_id: pool.pool_id.clone(),
+            input: SwapInput::Erg {
+                amount: 1_000_000_000,
+            },
+            min_output,
+            redeemer_address: String::new(),
+        };
+        let build = with_test_dev_fee(DevFeeConfig::disabled(), || {
+            build_swap_order_eip12(
+                &request,
+                &pool,
+                &[user_utxo()],
+                USER_TREE,
+                USER_PK,
+                1_000_000,
+                None,
+                None,
+            )
+        })
+        .unwrap();
+        as_input(&build.unsigned_tx.outputs[0])
+    }
+
+    #[test]
+    fn test_parse_swap_order_matches_owner() {
+        let order_box = sell_order_box(9_000);
+        let trees = vec![OTHER_TREE.to_string(), USER_TREE.to_string()];
+        let order = parse_user_order(&order_box, &trees).unwrap();
+        assert_eq!(order.kind, OrderKind::SwapSell);
+        assert_eq!(order.pool_id, pool().pool_id);
+        assert_eq!(order.min_output, Some(9_000));
+        assert_eq!(order.owner_ergo_tree, USER_TREE);
+
+        assert!(parse_user_order(&order_box, &[OTHER_TREE.to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_parse_deposit_order() {
+        let build = build_lp_deposit_order_eip12(
+            &pool(),
+            1_000_000_000,
+            10_000,
+            &[user_utxo()],
+            USER_TREE,
+            USER_PK,
+            1_000_000,
+            None,
+        )
+        .unwrap();
+        let order_box = as_input(&build.unsigned_tx.outputs[0]);
+        let order = parse_user_order(&order_box, &[USER_TREE.to_string()]).unwrap();
+        assert_eq!(order.kind, OrderKind::Deposit);
+        assert_eq!(order.pool_id, pool().pool_id);
+        assert!(order.input.is_none());
+    }
+
+    #[test]
+    fn test_assess_order() {
+        let trees = vec![USER_TREE.to_string()];
+        let pools = vec![pool()];
+
+        let fresh = parse_user_order(&sell_order_box(9_000), &trees).unwrap();
+        assert!(assess_order(&fresh, &pools, 1_000_010, 720).is_empty());
+        assert_eq!(
+            assess_order(&fresh, &pools, 1_001_000, 720),
+            vec![StaleReason::Expired { age_blocks: 1_000 }]
+        );
+        assert_eq!(
+            assess_order(&fresh, &[], 1_000_010, 720),
+            vec![StaleReason::PoolNotFound]
+        );
+
+        // 1 ERG into 100 ERG / 1M token reserves yields ~9871 tokens
+        let greedy = parse_user_order(&sell_order_box(50_000), &trees).unwrap();
+        assert!(matches!(
+            assess_order(&greedy, &pools, 1_000_010, 720).as_slice(),
+            [StaleReason::UnreachableMinOutput {
+                min_output: 50_000,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_template_hashes_are_distinct() {
+        let hashes: std::collections::HashSet<String> =
+            OrderKind::ALL.iter().map(|k| k.template_hash()).collect();
+        assert_eq!(hashes.len(), 4);
+        assert!(hashes.iter().all(|h| h.len() == 64));
+    }
+}
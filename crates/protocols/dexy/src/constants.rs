@@ -221,6 +221,7 @@ pub struct DexyIds {
     pub oracle_pool_nft: String,
     pub lp_nft: String,
     pub free_mint_nft: String,
+    pub arb_mint_nft: String,
     pub buyback_nft: String,
     pub lp_swap_nft: String,
     pub lp_token_id: String,
@@ -238,6 +239,7 @@ impl DexyIds {
             oracle_pool_nft: gold_mainnet::ORACLE_POOL_NFT_ID.to_string(),
             lp_nft: gold_mainnet::LP_NFT_ID.to_string(),
             free_mint_nft: gold_mainnet::FREE_MINT_NFT_ID.to_string(),
+            arb_mint_nft: gold_mainnet::ARBITRAGE_MINT_NFT_ID.to_string(),
             buyback_nft: gold_mainnet::BUYBACK_NFT_ID.to_string(),
             lp_swap_nft: gold_mainnet::LP_SWAP_NFT_ID.to_string(),
             lp_token_id: gold_mainnet::LP_TOKEN_ID.to_string(),
@@ -255,6 +257,7 @@ impl DexyIds {
             oracle_pool_nft: usd_mainnet::ORACLE_POOL_NFT_ID.to_string(),
             lp_nft: usd_mainnet::LP_NFT_ID.to_string(),
             free_mint_nft: usd_mainnet::FREE_MINT_NFT_ID.to_string(),
+            arb_mint_nft: usd_mainnet::ARBITRAGE_MINT_NFT_ID.to_string(),
             buyback_nft: usd_mainnet::BUYBACK_NFT_ID.to_string(),
             lp_swap_nft: usd_mainnet::LP_SWAP_NFT_ID.to_string(),
             lp_token_id: usd_mainnet::LP_TOKEN_ID.to_string(),
@@ -2,7 +2,7 @@ use citadel_core::{ProtocolError, TokenId};
 use ergo_lib::ergotree_ir::chain::ergo_box::ErgoBox;
 use ergo_node_client::{NodeCapabilities, NodeClient};
 use ergo_tx::ergo_box_utils::{
-    find_token_amount, get_register_int, get_register_long, map_node_error,
+    find_token_amount, get_register, get_register_int, get_register_long, map_node_error,
 };
 use ergo_tx::{Eip12DataInputBox, Eip12InputBox};
 
@@ -315,6 +315,434 @@ pub async fn fetch_tx_context(
     })
 }
 
+/// ArbMint transaction context.
+/// Inputs: [0] ArbMint, [1] Bank, [2] Buyback
+/// Data Inputs: [0] Oracle, [1] LP, [2] Tracking (101%)
+#[derive(Debug, Clone)]
+pub struct DexyArbMintTxContext {
+    pub arb_mint_input: Eip12InputBox,
+    pub arb_mint_erg_nano: i64,
+    pub arb_mint_ergo_tree: String,
+    pub arb_mint_r4_height: i32,
+    pub arb_mint_r5_available: i64,
+
+    pub bank_input: Eip12InputBox,
+    pub bank_erg_nano: i64,
+    pub dexy_in_bank: i64,
+    pub bank_ergo_tree: String,
+
+    pub buyback_input: Eip12InputBox,
+    pub buyback_erg_nano: i64,
+    pub buyback_ergo_tree: String,
+
+    pub oracle_data_input: Eip12DataInputBox,
+    /// Raw oracle R4, before the variant divisor
+    pub oracle_rate_nano: i64,
+
+    pub lp_data_input: Eip12DataInputBox,
+    pub lp_erg_reserves: i64,
+    pub lp_dexy_reserves: i64,
+
+    pub tracking_data_input: Eip12DataInputBox,
+    /// R7: height at which the LP rate went above 101% of oracle,
+    /// `i32::MAX` while it is below
+    pub tracking_height: i32,
+}
+
+pub async fn fetch_arb_mint_tx_context(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    ids: &DexyIds,
+) -> Result<DexyArbMintTxContext, ProtocolError> {
+    let arb_mint_token_id = TokenId::new(&ids.arb_mint_nft);
+    let arb_mint_box = client
+        .get_box_by_token_id(capabilities, &arb_mint_token_id)
+        .await
+        .map_err(|e| ProtocolError::BoxParseError {
+            message: format!("ArbMint box not found: {}", e),
+        })?;
+
+    let tracking_nft = find_tracking_nft(&arb_mint_box, ids)?;
+    let tracking_token_id = TokenId::new(&tracking_nft);
+    let tracking_box = client
+        .get_box_by_token_id(capabilities, &tracking_token_id)
+        .await
+        .map_err(|e| ProtocolError::BoxParseError {
+            message: format!("Tracking box not found: {}", e),
+        })?;
+
+    let bank_token_id = TokenId::new(&ids.bank_nft);
+    let bank_box = client
+        .get_box_by_token_id(capabilities, &bank_token_id)
+        .await
+        .map_err(|e| ProtocolError::BoxParseError {
+            message: format!("Bank box not found: {}", e),
+        })?;
+
+    let buyback_token_id = TokenId::new(&ids.buyback_nft);
+    let buyback_box = client
+        .get_box_by_token_id(capabilities, &buyback_token_id)
+        .await
+        .map_err(|e| ProtocolError::BoxParseError {
+            message: format!("Buyback box not found: {}", e),
+        })?;
+
+    let oracle_token_id = TokenId::new(&ids.oracle_pool_nft);
+    let oracle_box = client
+        .get_box_by_token_id(capabilities, &oracle_token_id)
+        .await
+        .map_err(|e| ProtocolError::BoxParseError {
+            message: format!("Oracle box not found: {}", e),
+        })?;
+
+    let lp_token_id = TokenId::new(&ids.lp_nft);
+    let lp_box = client
+        .get_box_by_token_id(capabilities, &lp_token_id)
+        .await
+        .map_err(|e| ProtocolError::BoxParseError {
+            message: format!("LP box not found: {}", e),
+        })?;
+
+    // Same R4/R5 layout as the FreeMint box.
+    let arb_mint_data = parse_free_mint_box_data(&arb_mint_box)?;
+    let arb_mint_ergo_tree = serialize_ergo_tree(&arb_mint_box)?;
+    let tracking_height = parse_tracking_height(&tracking_box)?;
+    let bank_data = parse_bank_box(&bank_box, ids)?;
+    let buyback_ergo_tree = serialize_ergo_tree(&buyback_box)?;
+    let oracle_data = parse_oracle_box(&oracle_box)?;
+    let lp_data = parse_lp_box(&lp_box, ids)?;
+
+    let arb_mint_tx_info = client
+        .get_box_creation_info(&arb_mint_box.box_id().to_string())
+        .await
+        .map_err(map_box_creation_info_err)?;
+    let bank_tx_info = client
+        .get_box_creation_info(&bank_box.box_id().to_string())
+        .await
+        .map_err(map_box_creation_info_err)?;
+    let buyback_tx_info = client
+        .get_box_creation_info(&buyback_box.box_id().to_string())
+        .await
+        .map_err(map_box_creation_info_err)?;
+    let oracle_tx_info = client
+        .get_box_creation_info(&oracle_box.box_id().to_string())
+        .await
+        .map_err(map_box_creation_info_err)?;
+    let lp_tx_info = client
+        .get_box_creation_info(&lp_box.box_id().to_string())
+        .await
+        .map_err(map_box_creation_info_err)?;
+    let tracking_tx_info = client
+        .get_box_creation_info(&tracking_box.box_id().to_string())
+        .await
+        .map_err(map_box_creation_info_err)?;
+
+    Ok(DexyArbMintTxContext {
+        arb_mint_input: Eip12InputBox::from_ergo_box(
+            &arb_mint_box,
+            arb_mint_tx_info.0,
+            arb_mint_tx_info.1,
+        ),
+        arb_mint_erg_nano: arb_mint_box.value.as_i64(),
+        arb_mint_ergo_tree,
+        arb_mint_r4_height: arb_mint_data.reset_height,
+        arb_mint_r5_available: arb_mint_data.available,
+        bank_input: Eip12InputBox::from_ergo_box(&bank_box, bank_tx_info.0, bank_tx_info.1),
+        bank_erg_nano: bank_data.erg_value,
+        dexy_in_bank: bank_data.dexy_tokens,
+        bank_ergo_tree: bank_data.ergo_tree,
+        buyback_input: Eip12InputBox::from_ergo_box(
+            &buyback_box,
+            buyback_tx_info.0,
+            buyback_tx_info.1,
+        ),
+        buyback_erg_nano: buyback_box.value.as_i64(),
+        buyback_ergo_tree,
+        oracle_data_input: Eip12DataInputBox::from_ergo_box(
+            &oracle_box,
+            oracle_tx_info.0,
+            oracle_tx_info.1,
+        ),
+        oracle_rate_nano: oracle_data.rate_nano,
+        lp_data_input: Eip12DataInputBox::from_ergo_box(&lp_box, lp_tx_info.0, lp_tx_info.1),
+        lp_erg_reserves: lp_data.erg_reserves,
+        lp_dexy_reserves: lp_data.dexy_reserves,
+        tracking_data_input: Eip12DataInputBox::from_ergo_box(
+            &tracking_box,
+            tracking_tx_info.0,
+            tracking_tx_info.1,
+        ),
+        tracking_height,
+    })
+}
+
+/// R7: Int = height since which the tracked condition holds
+pub fn parse_tracking_height(ergo_box: &ErgoBox) -> Result<i32, ProtocolError> {
+    use ergo_lib::ergotree_ir::chain::ergo_box::NonMandatoryRegisterId;
+
+    get_register_int(ergo_box, NonMandatoryRegisterId::R7)
+}
+
+/// The 101% tracking box NFT is compiled into the ArbMint contract instead of
+/// being published with the other IDs. It is the only 32-byte constant of that
+/// contract that is not one of the known protocol NFTs.
+pub fn find_tracking_nft(arb_mint_box: &ErgoBox, ids: &DexyIds) -> Result<String, ProtocolError> {
+    let candidates = unknown_contract_nfts(arb_mint_box, &known_nfts(ids), "ArbMint")?;
+    match candidates.as_slice() {
+        [id] => Ok(id.clone()),
+        _ => Err(ProtocolError::BoxParseError {
+            message: format!(
+                "Cannot identify tracking box NFT in ArbMint contract ({} candidates)",
+                candidates.len()
+            ),
+        }),
+    }
+}
+
+fn known_nfts(ids: &DexyIds) -> Vec<String> {
+    vec![
+        ids.dexy_token.clone(),
+        ids.bank_nft.clone(),
+        ids.oracle_pool_nft.clone(),
+        ids.lp_nft.clone(),
+        ids.free_mint_nft.clone(),
+        ids.buyback_nft.clone(),
+        ids.arb_mint_nft.clone(),
+        ids.lp_swap_nft.clone(),
+        ids.lp_token_id.clone(),
+        ids.lp_mint_nft.clone(),
+        ids.lp_redeem_nft.clone(),
+    ]
+}
+
+/// 32-byte `Coll[Byte]` constants of the box's contract that are not in `known`.
+fn unknown_contract_nfts(
+    ergo_box: &ErgoBox,
+    known: &[String],
+    contract: &str,
+) -> Result<Vec<String>, ProtocolError> {
+    use ergo_lib::ergotree_ir::mir::constant::Literal;
+    use ergo_lib::ergotree_ir::mir::value::{CollKind, NativeColl};
+
+    let tree = &ergo_box.ergo_tree;
+    let count = tree
+        .constants_len()
+        .map_err(|e| ProtocolError::BoxParseError {
+            message: format!("Failed to read {} contract constants: {}", contract, e),
+        })?;
+
+    let mut candidates: Vec<String> = Vec::new();
+    for idx in 0..count {
+        let Ok(Some(constant)) = tree.get_constant(idx) else {
+            continue;
+        };
+        if let Literal::Coll(CollKind::NativeColl(NativeColl::CollByte(bytes))) = &constant.v {
+            if bytes.len() != 32 {
+                continue;
+            }
+            let raw: Vec<u8> = bytes.iter().map(|b| *b as u8).collect();
+            let id = base16::encode_lower(&raw);
+            if !known.contains(&id) && !candidates.contains(&id) {
+                candidates.push(id);
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+/// Tracking box: R4/R5 Int ratio, R6 Boolean isBelow, R7 Int tracking height
+/// (`i32::MAX` while the condition does not hold).
+#[derive(Debug, Clone)]
+pub struct DexyTrackingBox {
+    pub input: Eip12InputBox,
+    pub data_input: Eip12DataInputBox,
+    pub num: i32,
+    pub denom: i32,
+    pub is_below: bool,
+    pub tracking_height: i32,
+}
+
+pub fn parse_tracking_box_registers(
+    ergo_box: &ErgoBox,
+) -> Result<(i32, i32, bool, i32), ProtocolError> {
+    use ergo_lib::ergotree_ir::chain::ergo_box::NonMandatoryRegisterId;
+    use ergo_lib::ergotree_ir::mir::constant::Literal;
+
+    let num = get_register_int(ergo_box, NonMandatoryRegisterId::R4)?;
+    let denom = get_register_int(ergo_box, NonMandatoryRegisterId::R5)?;
+    let is_below = match get_register(ergo_box, NonMandatoryRegisterId::R6)?.v {
+        Literal::Boolean(b) => b,
+        other => {
+            return Err(ProtocolError::BoxParseError {
+                message: format!("R6: expected Boolean, got {:?}", other),
+            })
+        }
+    };
+    let tracking_height = parse_tracking_height(ergo_box)?;
+    Ok((num, denom, is_below, tracking_height))
+}
+
+/// Keeper (maintenance) transaction context: everything needed to update the
+/// tracking boxes, run a bank intervention and extract to / release from the
+/// future.
+///
+/// Only the oracle, LP and bank NFTs are published; the intervention and
+/// extract NFTs are read from the LP contract and the tracking NFTs from the
+/// intervention, extract and ArbMint contracts.
+#[derive(Debug, Clone)]
+pub struct DexyKeeperTxContext {
+    pub oracle_data_input: Eip12DataInputBox,
+    pub oracle_rate_nano: i64,
+
+    pub lp_input: Eip12InputBox,
+    pub lp_data_input: Eip12DataInputBox,
+    pub lp_erg_reserves: i64,
+    pub lp_dexy_reserves: i64,
+
+    pub bank_input: Eip12InputBox,
+    pub bank_data_input: Eip12DataInputBox,
+    pub bank_erg_nano: i64,
+    pub dexy_in_bank: i64,
+
+    pub intervention_input: Eip12InputBox,
+
+    pub extract_input: Eip12InputBox,
+    pub dexy_in_extract: i64,
+
+    pub trackers: Vec<DexyTrackingBox>,
+}
+
+pub async fn fetch_keeper_tx_context(
+    client: &NodeClient,
+    capabilities: &NodeCapabilities,
+    ids: &DexyIds,
+) -> Result<DexyKeeperTxContext, ProtocolError> {
+    let fetch = |token_id: String, label: &'static str| async move {
+        client
+            .get_box_by_token_id(capabilities, &TokenId::new(&token_id))
+            .await
+            .map_err(|e| ProtocolError::BoxParseError {
+                message: format!("{} box not found: {}", label, e),
+            })
+    };
+    let creation_info = |ergo_box: &ErgoBox| {
+        let box_id = ergo_box.box_id().to_string();
+        async move {
+            client
+                .get_box_creation_info(&box_id)
+                .await
+                .map_err(map_box_creation_info_err)
+        }
+    };
+
+    let oracle_box = fetch(ids.oracle_pool_nft.clone(), "Oracle").await?;
+    let lp_box = fetch(ids.lp_nft.clone(), "LP").await?;
+    let bank_box = fetch(ids.bank_nft.clone(), "Bank").await?;
+    let arb_mint_box = fetch(ids.arb_mint_nft.clone(), "ArbMint").await?;
+
+    // The LP contract admits two foreign action boxes: extract holds Dexy,
+    // intervention holds only its NFT.
+    let mut known = known_nfts(ids);
+    let mut extract_box = None;
+    let mut intervention_box = None;
+    for nft in unknown_contract_nfts(&lp_box, &known, "LP")? {
+        let Ok(action_box) = fetch(nft.clone(), "LP action").await else {
+            continue;
+        };
+        if find_token_amount(&action_box, &ids.dexy_token).is_some() {
+            extract_box = Some(action_box);
+        } else {
+            intervention_box = Some(action_box);
+        }
+        known.push(nft);
+    }
+    let extract_box = extract_box.ok_or_else(|| ProtocolError::BoxParseError {
+        message: "Cannot identify extract box NFT in LP contract".to_string(),
+    })?;
+    let intervention_box = intervention_box.ok_or_else(|| ProtocolError::BoxParseError {
+        message: "Cannot identify intervention box NFT in LP contract".to_string(),
+    })?;
+
+    let mut tracking_nfts: Vec<String> = Vec::new();
+    for (action_box, label) in [
+        (&intervention_box, "Intervention"),
+        (&extract_box, "Extract"),
+        (&arb_mint_box, "ArbMint"),
+    ] {
+        for nft in unknown_contract_nfts(action_box, &known, label)? {
+            if !tracking_nfts.contains(&nft) {
+                tracking_nfts.push(nft);
+            }
+        }
+    }
+
+    let mut trackers = Vec::new();
+    for nft in tracking_nfts {
+        // Constants that are not tracking NFTs are skipped by box layout.
+        let Ok(tracking_box) = fetch(nft, "Tracking").await else {
+            continue;
+        };
+        let Ok((num, denom, is_below, tracking_height)) =
+            parse_tracking_box_registers(&tracking_box)
+        else {
+            continue;
+        };
+        let (tx_id, index) = creation_info(&tracking_box).await?;
+        trackers.push(DexyTrackingBox {
+            input: Eip12InputBox::from_ergo_box(&tracking_box, tx_id.clone(), index),
+            data_input: Eip12DataInputBox::from_ergo_box(&tracking_box, tx_id, index),
+            num,
+            denom,
+            is_below,
+            tracking_height,
+        });
+    }
+
+    let oracle_data = parse_oracle_box(&oracle_box)?;
+    let lp_data = parse_lp_box(&lp_box, ids)?;
+    let bank_data = parse_bank_box(&bank_box, ids)?;
+    let dexy_in_extract = find_token_amount(&extract_box, &ids.dexy_token).unwrap_or(0) as i64;
+
+    let oracle_tx_info = creation_info(&oracle_box).await?;
+    let lp_tx_info = creation_info(&lp_box).await?;
+    let bank_tx_info = creation_info(&bank_box).await?;
+    let intervention_tx_info = creation_info(&intervention_box).await?;
+    let extract_tx_info = creation_info(&extract_box).await?;
+
+    Ok(DexyKeeperTxContext {
+        oracle_data_input: Eip12DataInputBox::from_ergo_box(
+            &oracle_box,
+            oracle_tx_info.0,
+            oracle_tx_info.1,
+        ),
+        oracle_rate_nano: oracle_data.rate_nano,
+        lp_input: Eip12InputBox::from_ergo_box(&lp_box, lp_tx_info.0.clone(), lp_tx_info.1),
+        lp_data_input: Eip12DataInputBox::from_ergo_box(&lp_box, lp_tx_info.0, lp_tx_info.1),
+        lp_erg_reserves: lp_data.erg_reserves,
+        lp_dexy_reserves: lp_data.dexy_reserves,
+        bank_input: Eip12InputBox::from_ergo_box(&bank_box, bank_tx_info.0.clone(), bank_tx_info.1),
+        bank_data_input: Eip12DataInputBox::from_ergo_box(
+            &bank_box,
+            bank_tx_info.0,
+            bank_tx_info.1,
+        ),
+        bank_erg_nano: bank_data.erg_value,
+        dexy_in_bank: bank_data.dexy_tokens,
+        intervention_input: Eip12InputBox::from_ergo_box(
+            &intervention_box,
+            intervention_tx_info.0,
+            intervention_tx_info.1,
+        ),
+        extract_input: Eip12InputBox::from_ergo_box(
+            &extract_box,
+            extract_tx_info.0,
+            extract_tx_info.1,
+        ),
+        dexy_in_extract,
+        trackers,
+    })
+}
+
 /// LP swap transaction context.
 /// Inputs: [0] LP, [1] Swap NFT, [2+] User UTXOs
 /// Outputs: [0] LP (updated), [1] Swap NFT (preserved), [2] User output, [3+] Change/Fee
@@ -630,6 +1058,8 @@ mod tests {
             lp_nft: "cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc".to_string(),
             free_mint_nft: "dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd"
                 .to_string(),
+            arb_mint_nft: "4444444444444444444444444444444444444444444444444444444444444444"
+                .to_string(),
             buyback_nft: "eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
                 .to_string(),
             lp_swap_nft: "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
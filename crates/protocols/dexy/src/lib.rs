@@ -25,6 +25,7 @@ pub mod calculator;
 pub mod constants;
 pub mod fetch;
 pub mod rates;
+pub mod simulator;
 pub mod state;
 pub mod tx_builder;
 
@@ -32,5 +33,6 @@ pub use calculator::*;
 pub use constants::*;
 pub use fetch::*;
 pub use rates::DexyRates;
+pub use simulator::{simulate_dexy, DexyFlow, DexyHoldings, DexyScenario, DexySimulationResult};
 pub use state::*;
 pub use tx_builder::*;
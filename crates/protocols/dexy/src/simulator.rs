@@ -0,0 +1,390 @@
+//! What-if simulation: Dexy bank/LP state under ERG price shocks and
+//! hypothetical mint and LP swap flows.
+//!
+//! The LP does not follow the oracle by itself: after a shock the LP rate only
+//! moves with the simulated swaps, which is what opens or closes FreeMint and
+//! LP redemption. Per-period FreeMint/ArbMint allowances are not modelled.
+
+use citadel_core::ProtocolError;
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::{
+    calculate_lp_redeem, calculate_lp_swap_output, calculate_state, can_redeem_lp, DexyInput,
+};
+use crate::constants::{
+    BANK_FEE_NUM, BUYBACK_FEE_NUM, FEE_DENOM, LP_SWAP_FEE_DENOM, LP_SWAP_FEE_NUM,
+};
+use crate::state::DexyState;
+use crate::tx_builder::validate_mint_dexy;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DexyFlow {
+    /// Mint this many Dexy tokens from the bank at the oracle rate.
+    Mint(i64),
+    /// Sell this many Dexy tokens to the LP.
+    SellToLp(i64),
+    /// Buy Dexy from the LP with this much nanoERG.
+    BuyFromLp(i64),
+}
+
+impl DexyFlow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DexyFlow::Mint(_) => "mint",
+            DexyFlow::SellToLp(_) => "sell_to_lp",
+            DexyFlow::BuyFromLp(_) => "buy_from_lp",
+        }
+    }
+
+    fn amount(&self) -> i64 {
+        match self {
+            DexyFlow::Mint(a) | DexyFlow::SellToLp(a) | DexyFlow::BuyFromLp(a) => *a,
+        }
+    }
+}
+
+/// Dexy tokens and LP tokens held by the user (raw units).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DexyHoldings {
+    pub dexy: i64,
+    pub lp_tokens: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct DexyScenario {
+    /// ERG price change against the peg asset in percent: -50.0 halves it.
+    pub erg_price_change_pct: f64,
+    /// Applied in order after the price shock.
+    pub flows: Vec<DexyFlow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexySimulatedState {
+    /// nanoERG per token.
+    pub oracle_rate_nano: i64,
+    pub lp_rate_nano: i64,
+    pub lp_erg_reserves: i64,
+    pub lp_dexy_reserves: i64,
+    pub dexy_in_bank: i64,
+    /// Positive = oracle above LP.
+    pub rate_difference_pct: f64,
+    /// "mint" (LP below 98% of oracle or bank empty) and "lp_redeem" (LP
+    /// depeg protection).
+    pub blocked_actions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexyFlowOutcome {
+    pub action: String,
+    pub amount: i64,
+    pub applied: bool,
+    /// ERG paid by the user (negative when received).
+    pub erg_delta_nano: i64,
+    /// Dexy received by the user (negative when paid).
+    pub dexy_delta: i64,
+    pub error: Option<String>,
+    pub lp_rate_after_nano: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexyHoldingsValue {
+    /// ERG from selling the Dexy holdings into the LP (slippage included).
+    pub dexy_lp_exit_nano: i64,
+    /// Dexy holdings at the oracle rate.
+    pub dexy_oracle_value_nano: i64,
+    /// LP tokens redeemed, the Dexy share valued at the LP rate.
+    pub lp_value_nano: i64,
+    pub lp_redeemable: bool,
+    /// LP exit plus LP token value.
+    pub total_erg_nano: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DexySimulationResult {
+    pub erg_price_change_pct: f64,
+    pub before: DexySimulatedState,
+    pub shocked: DexySimulatedState,
+    pub after: DexySimulatedState,
+    pub flows: Vec<DexyFlowOutcome>,
+    pub holdings_before: DexyHoldingsValue,
+    pub holdings_after: DexyHoldingsValue,
+    /// Change of the holdings' ERG value; `None` when they were worth nothing.
+    pub holdings_change_pct: Option<f64>,
+}
+
+/// Recompute the derived fields after reserves or the oracle rate changed.
+fn recompute(mut state: DexyState) -> DexyState {
+    let calculated = calculate_state(&DexyInput {
+        oracle_rate_nano: state.oracle_rate_nano,
+        lp_erg_reserves: state.lp_erg_reserves,
+        lp_dexy_reserves: state.lp_dexy_reserves,
+        dexy_in_bank: state.dexy_in_bank,
+        total_supply: state.dexy_circulating + state.dexy_in_bank,
+    });
+    state.lp_rate_nano = calculated.lp_rate_nano;
+    state.can_mint = calculated.can_mint;
+    state.rate_difference_pct = calculated.rate_difference_pct;
+    state.can_redeem_lp = can_redeem_lp(
+        state.lp_erg_reserves,
+        state.lp_dexy_reserves,
+        state.oracle_rate_nano,
+    );
+    state
+}
+
+fn snapshot(state: &DexyState) -> DexySimulatedState {
+    let mut blocked_actions = Vec::new();
+    if !state.can_mint {
+        blocked_actions.push("mint".to_string());
+    }
+    if !state.can_redeem_lp {
+        blocked_actions.push("lp_redeem".to_string());
+    }
+    DexySimulatedState {
+        oracle_rate_nano: state.oracle_rate_nano,
+        lp_rate_nano: state.lp_rate_nano,
+        lp_erg_reserves: state.lp_erg_reserves,
+        lp_dexy_reserves: state.lp_dexy_reserves,
+        dexy_in_bank: state.dexy_in_bank,
+        rate_difference_pct: state.rate_difference_pct,
+        blocked_actions,
+    }
+}
+
+fn lp_swap(input: i64, reserves_sold: i64, reserves_bought: i64) -> i64 {
+    if input <= 0 || reserves_sold <= 0 || reserves_bought <= 0 {
+        return 0;
+    }
+    calculate_lp_swap_output(
+        input,
+        reserves_sold,
+        reserves_bought,
+        LP_SWAP_FEE_NUM,
+        LP_SWAP_FEE_DENOM,
+    )
+}
+
+fn holdings_value(state: &DexyState, holdings: &DexyHoldings) -> DexyHoldingsValue {
+    let dexy_lp_exit_nano = lp_swap(holdings.dexy, state.lp_dexy_reserves, state.lp_erg_reserves);
+    let dexy_oracle_value_nano = holdings.dexy.saturating_mul(state.oracle_rate_nano);
+    let lp_value_nano = if holdings.lp_tokens > 0 && state.lp_circulating > 0 {
+        let out = calculate_lp_redeem(
+            holdings.lp_tokens,
+            state.lp_erg_reserves,
+            state.lp_dexy_reserves,
+            state.lp_token_reserves,
+            state.variant.initial_lp(),
+        );
+        out.erg_out
+            .saturating_add(out.dexy_out.saturating_mul(state.lp_rate_nano))
+    } else {
+        0
+    };
+
+    DexyHoldingsValue {
+        dexy_lp_exit_nano,
+        dexy_oracle_value_nano,
+        lp_value_nano,
+        lp_redeemable: state.can_redeem_lp,
+        total_erg_nano: dexy_lp_exit_nano.saturating_add(lp_value_nano),
+    }
+}
+
+/// Apply one flow; returns the new state and the user's (ERG paid, Dexy received).
+fn apply_flow(state: &DexyState, flow: DexyFlow) -> Result<(DexyState, i64, i64), ProtocolError> {
+    let mut next = state.clone();
+    let (erg_delta, dexy_delta) = match flow {
+        DexyFlow::Mint(amount) => {
+            validate_mint_dexy(amount, state)?;
+            let bank_erg = amount * state.oracle_rate_nano * (FEE_DENOM + BANK_FEE_NUM) / FEE_DENOM;
+            let buyback_erg = amount * state.oracle_rate_nano * BUYBACK_FEE_NUM / FEE_DENOM;
+            next.bank_erg_nano += bank_erg;
+            next.dexy_in_bank -= amount;
+            next.dexy_circulating += amount;
+            (bank_erg + buyback_erg, amount)
+        }
+        DexyFlow::SellToLp(amount) => {
+            let erg_out = lp_swap(amount, state.lp_dexy_reserves, state.lp_erg_reserves);
+            if erg_out <= 0 {
+                return Err(ProtocolError::InvalidAmount {
+                    message: "Swap output would be zero".to_string(),
+                });
+            }
+            next.lp_dexy_reserves += amount;
+            next.lp_erg_reserves -= erg_out;
+            (-erg_out, -amount)
+        }
+        DexyFlow::BuyFromLp(erg_in) => {
+            let dexy_out = lp_swap(erg_in, state.lp_erg_reserves, state.lp_dexy_reserves);
+            if dexy_out <= 0 {
+                return Err(ProtocolError::InvalidAmount {
+                    message: "Swap output would be zero".to_string(),
+                });
+            }
+            next.lp_erg_reserves += erg_in;
+            next.lp_dexy_reserves -= dexy_out;
+            (erg_in, dexy_out)
+        }
+    };
+    Ok((recompute(next), erg_delta, dexy_delta))
+}
+
+/// Run `scenario` against `state`. Flows that fail validation are reported
+/// and skipped; later flows still run against the state without them.
+pub fn simulate_dexy(
+    state: &DexyState,
+    scenario: &DexyScenario,
+    holdings: &DexyHoldings,
+) -> Result<DexySimulationResult, ProtocolError> {
+    if !scenario.erg_price_change_pct.is_finite() || scenario.erg_price_change_pct <= -100.0 {
+        return Err(ProtocolError::InvalidAmount {
+            message: "ERG price change must be above -100%".to_string(),
+        });
+    }
+
+    // nanoERG per token moves inversely to the ERG price
+    let mut shocked = state.clone();
+    shocked.oracle_rate_nano = ((state.oracle_rate_nano as f64)
+        / (1.0 + scenario.erg_price_change_pct / 100.0))
+        .round()
+        .max(1.0) as i64;
+    let shocked = recompute(shocked);
+
+    let mut current = shocked.clone();
+    let mut flows = Vec::with_capacity(scenario.flows.len());
+    for &flow in &scenario.flows {
+        let outcome = match apply_flow(&current, flow) {
+            Ok((next, erg_delta, dexy_delta)) => {
+                current = next;
+                DexyFlowOutcome {
+                    action: flow.as_str().to_string(),
+                    amount: flow.amount(),
+                    applied: true,
+                    erg_delta_nano: erg_delta,
+                    dexy_delta,
+                    error: None,
+                    lp_rate_after_nano: current.lp_rate_nano,
+                }
+            }
+            Err(e) => DexyFlowOutcome {
+                action: flow.as_str().to_string(),
+                amount: flow.amount(),
+                applied: false,
+                erg_delta_nano: 0,
+                dexy_delta: 0,
+                error: Some(e.to_string()),
+                lp_rate_after_nano: current.lp_rate_nano,
+            },
+        };
+        flows.push(outcome);
+    }
+
+    let holdings_before = holdings_value(state, holdings);
+    let holdings_after = holdings_value(&current, holdings);
+    let holdings_change_pct = (holdings_before.total_erg_nano > 0).then(|| {
+        (holdings_after.total_erg_nano - holdings_before.total_erg_nano) as f64
+            / holdings_before.total_erg_nano as f64
+            * 100.0
+    });
+
+    Ok(DexySimulationResult {
+        erg_price_change_pct: scenario.erg_price_change_pct,
+        before: snapshot(state),
+        shocked: snapshot(&shocked),
+        after: snapshot(&current),
+        flows,
+        holdings_before,
+        holdings_after,
+        holdings_change_pct,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::DexyVariant;
+
+    /// LP at parity with the oracle: 1_000_000 nanoERG per token.
+    fn state() -> DexyState {
+        recompute(DexyState {
+            variant: DexyVariant::Gold,
+            bank_erg_nano: 1_000_000_000_000,
+            dexy_in_bank: 1_000_000,
+            bank_box_id: "bank".to_string(),
+            dexy_token_id: "token".to_string(),
+            free_mint_available: 5_000,
+            free_mint_reset_height: 1_000_000,
+            current_height: 999_500,
+            oracle_rate_nano: 1_000_000,
+            oracle_box_id: "oracle".to_string(),
+            lp_erg_reserves: 500_000_000_000,
+            lp_dexy_reserves: 500_000,
+            lp_box_id: "lp".to_string(),
+            lp_rate_nano: 0,
+            lp_token_reserves: 0,
+            lp_circulating: 0,
+            can_redeem_lp: false,
+            can_mint: false,
+            rate_difference_pct: 0.0,
+            dexy_circulating: 500_000,
+        })
+    }
+
+    fn scenario(pct: f64, flows: Vec<DexyFlow>) -> DexyScenario {
+        DexyScenario {
+            erg_price_change_pct: pct,
+            flows,
+        }
+    }
+
+    #[test]
+    fn test_erg_crash_blocks_mint_until_lp_catches_up() {
+        // ERG -50%: oracle doubles, LP still at the old rate
+        let flows = vec![DexyFlow::Mint(100), DexyFlow::BuyFromLp(300_000_000_000)];
+        let result =
+            simulate_dexy(&state(), &scenario(-50.0, flows), &DexyHoldings::default()).unwrap();
+
+        assert!(result.before.blocked_actions.is_empty());
+        assert_eq!(result.shocked.oracle_rate_nano, 2_000_000);
+        assert_eq!(
+            result.shocked.blocked_actions,
+            vec!["mint".to_string(), "lp_redeem".to_string()]
+        );
+        assert!(!result.flows[0].applied);
+        // Buying pushes the LP rate past 98% of the oracle again
+        assert!(result.flows[1].applied);
+        assert!(result.after.lp_rate_nano * 100 > result.after.oracle_rate_nano * 98);
+        assert!(!result.after.blocked_actions.contains(&"mint".to_string()));
+    }
+
+    #[test]
+    fn test_mint_moves_tokens_out_of_bank() {
+        let result = simulate_dexy(
+            &state(),
+            &scenario(0.0, vec![DexyFlow::Mint(1_000)]),
+            &DexyHoldings::default(),
+        )
+        .unwrap();
+        assert!(result.flows[0].applied);
+        // 1000 tokens at 1_000_000 plus 0.5% bank and buyback fees
+        assert_eq!(result.flows[0].erg_delta_nano, 1_005_000_000);
+        assert_eq!(result.after.dexy_in_bank, 999_000);
+    }
+
+    #[test]
+    fn test_holdings_exit_through_lp() {
+        let holdings = DexyHoldings {
+            dexy: 1_000,
+            lp_tokens: 0,
+        };
+        let result = simulate_dexy(
+            &state(),
+            &scenario(0.0, vec![DexyFlow::SellToLp(100_000)]),
+            &holdings,
+        )
+        .unwrap();
+        // Selling into the LP first lowers what the holdings fetch there
+        assert!(result.holdings_after.total_erg_nano < result.holdings_before.total_erg_nano);
+        assert!(result.holdings_change_pct.unwrap() < 0.0);
+    }
+}
@@ -0,0 +1,189 @@
+use citadel_core::{constants, TxError};
+use ergo_tx::{
+    append_change_output, append_dev_fee_output, resolved_dev_fee_config, select_inputs_for_spend,
+    Eip12Asset, Eip12Output, Eip12UnsignedTx,
+};
+
+use crate::constants::{BANK_FEE_NUM, FEE_DENOM};
+use crate::fetch::DexyArbMintTxContext;
+use crate::state::DexyState;
+
+use super::mint::{
+    build_bank_output, build_buyback_output, calculate_mint_amounts, BuildResult, MintDexyRequest,
+    TxSummary, BUYBACK_ACTION_TOPUP,
+};
+use super::validate::{arb_mint_allowance, validate_arb_mint_preflight, T_ARB, T_BUFFER};
+
+/// ArbMint: mint at the oracle rate (plus fees) while the LP trades above it.
+///
+/// Same bank and buyback deltas as FreeMint; only the action box, its
+/// period/allowance rules and the tracking data input differ.
+pub fn build_arb_mint_dexy_tx(
+    request: &MintDexyRequest,
+    ctx: &DexyArbMintTxContext,
+    state: &DexyState,
+) -> Result<BuildResult, TxError> {
+    let output_ergo_tree = request
+        .recipient_ergo_tree
+        .as_deref()
+        .unwrap_or(&request.user_ergo_tree);
+
+    validate_arb_mint_preflight(ctx, request.amount, request.current_height, request.variant)?;
+
+    let (bank_erg_added, buyback_fee) =
+        calculate_mint_amounts(request.amount, ctx.oracle_rate_nano, request.variant);
+
+    let adjusted_rate = ctx.oracle_rate_nano / request.variant.oracle_divisor();
+    let bank_fee = request.amount * adjusted_rate * BANK_FEE_NUM / FEE_DENOM;
+    let fee_cfg = resolved_dev_fee_config();
+    let citadel_fee = fee_cfg.budget();
+    let total_cost = bank_erg_added
+        + buyback_fee
+        + constants::TX_FEE_NANO
+        + citadel_fee
+        + constants::MIN_BOX_VALUE_NANO;
+
+    let selected =
+        select_inputs_for_spend(&request.user_inputs, total_cost as u64, None).map_err(|e| {
+            TxError::BuildFailed {
+                message: e.to_string(),
+            }
+        })?;
+
+    if request.amount > state.dexy_in_bank {
+        return Err(TxError::BuildFailed {
+            message: format!(
+                "Amount {} exceeds available tokens {} in bank",
+                request.amount, state.dexy_in_bank
+            ),
+        });
+    }
+
+    let (is_counter_reset, available_to_mint) =
+        arb_mint_allowance(ctx, request.current_height, request.variant);
+    let new_r4 = if is_counter_reset {
+        request.current_height + T_ARB + T_BUFFER
+    } else {
+        ctx.arb_mint_r4_height
+    };
+    let new_r5 = available_to_mint - request.amount;
+
+    let mut buyback_input = ctx.buyback_input.clone();
+    buyback_input.extension.insert(
+        "0".to_string(),
+        ergo_tx::sigma::encode_sigma_int(BUYBACK_ACTION_TOPUP),
+    );
+
+    let mut inputs = vec![
+        ctx.arb_mint_input.clone(),
+        ctx.bank_input.clone(),
+        buyback_input,
+    ];
+    inputs.extend(selected.boxes.clone());
+
+    let data_inputs = vec![
+        ctx.oracle_data_input.clone(),
+        ctx.lp_data_input.clone(),
+        ctx.tracking_data_input.clone(),
+    ];
+
+    let new_bank_erg = ctx.bank_erg_nano + bank_erg_added;
+    let new_dexy_in_bank = ctx.dexy_in_bank - request.amount;
+    let new_buyback_erg = ctx.buyback_erg_nano + buyback_fee;
+
+    let mut outputs = vec![
+        build_arb_mint_output(ctx, new_r4, new_r5, request.current_height),
+        build_bank_output(
+            &ctx.bank_input,
+            &ctx.bank_ergo_tree,
+            new_bank_erg,
+            new_dexy_in_bank,
+            request.current_height,
+        ),
+        build_buyback_output(
+            &ctx.buyback_input,
+            &ctx.buyback_ergo_tree,
+            new_buyback_erg,
+            request.current_height,
+        )?,
+        Eip12Output::change(
+            constants::MIN_BOX_VALUE_NANO,
+            output_ergo_tree,
+            vec![Eip12Asset::new(&state.dexy_token_id, request.amount)],
+            request.current_height,
+        ),
+    ];
+
+    append_change_output(
+        &mut outputs,
+        &selected,
+        total_cost as u64,
+        &[],
+        &request.user_ergo_tree,
+        request.current_height,
+        constants::MIN_BOX_VALUE_NANO as u64,
+    )
+    .map_err(|e| TxError::BuildFailed {
+        message: e.to_string(),
+    })?;
+
+    append_dev_fee_output(&mut outputs, &fee_cfg, request.current_height).map_err(|e| {
+        TxError::BuildFailed {
+            message: e.to_string(),
+        }
+    })?;
+    outputs.push(Eip12Output::fee(
+        constants::TX_FEE_NANO,
+        request.current_height,
+    ));
+
+    let unsigned_tx = Eip12UnsignedTx {
+        inputs,
+        data_inputs,
+        outputs,
+    };
+
+    let summary = TxSummary {
+        action: format!("arb_mint_dexy_{}", request.variant.as_str()),
+        erg_amount_nano: bank_erg_added + buyback_fee,
+        token_amount: request.amount,
+        token_name: request.variant.token_name().to_string(),
+        tx_fee_nano: constants::TX_FEE_NANO,
+        citadel_fee_nano: citadel_fee,
+        bank_fee_nano: bank_fee,
+        buyback_fee_nano: buyback_fee,
+    };
+
+    Ok(BuildResult {
+        unsigned_tx,
+        summary,
+    })
+}
+
+/// Successor keeps value, tokens and script; only the period registers change.
+fn build_arb_mint_output(
+    ctx: &DexyArbMintTxContext,
+    new_r4: i32,
+    new_r5: i64,
+    height: i32,
+) -> Eip12Output {
+    let registers = ergo_tx::sigma_registers!(
+        "R4" => ergo_tx::sigma::encode_sigma_int(new_r4),
+        "R5" => ergo_tx::sigma::encode_sigma_long(new_r5),
+    );
+
+    let assets: Vec<Eip12Asset> = ctx
+        .arb_mint_input
+        .assets
+        .iter()
+        .map(|a| Eip12Asset::new(&a.token_id, a.amount.parse().unwrap_or(1)))
+        .collect();
+
+    Eip12Output {
+        value: ctx.arb_mint_erg_nano.to_string(),
+        ergo_tree: ctx.arb_mint_ergo_tree.clone(),
+        assets,
+        creation_height: height,
+        additional_registers: registers,
+    }
+}
@@ -0,0 +1,567 @@
+//! Keeper transactions: the permissionless maintenance actions that keep the
+//! peg working. The keeper only pays the miner fee (and app fee); protocol
+//! boxes keep their value except for the deltas each action moves.
+//!
+//! - Tracking update: records (R7 = HEIGHT) or clears (R7 = Int.MaxValue) the
+//!   height since which `lpRate * denom` has been below/above `num * oracleRate`.
+//! - Intervention: once the 98% tracker has held for `T_INT` blocks and
+//!   `T_INTERVENTION_GAP` blocks have passed since the last one, the bank swaps
+//!   up to 1% of its ERG into the LP, leaving the LP rate at most 99.5% of the
+//!   oracle rate.
+//! - Extract to future: once the 95% tracker has held for `T_EXTRACT` blocks,
+//!   Dexy moves from the LP into the extract box, raising the LP rate to at most
+//!   98% of the oracle rate.
+//! - Release from future: once the 101% tracker has held for `T_RELEASE`
+//!   blocks, extracted Dexy flows back into the LP without taking the LP rate
+//!   below the oracle rate.
+
+use serde::{Deserialize, Serialize};
+
+use citadel_core::{constants, TxError, TxIntent};
+use ergo_tx::{
+    append_change_output, append_dev_fee_output, resolved_dev_fee_config, select_inputs_for_spend,
+    Eip12Asset, Eip12DataInputBox, Eip12InputBox, Eip12Output, Eip12UnsignedTx,
+};
+
+use crate::calculator::calculate_lp_swap_output;
+use crate::constants::{DexyIds, DexyVariant, LP_SWAP_FEE_DENOM, LP_SWAP_FEE_NUM};
+use crate::fetch::{DexyKeeperTxContext, DexyTrackingBox};
+
+use super::mint::build_bank_output;
+
+/// Blocks the 98% tracker must have held before an intervention.
+pub(crate) const T_INT: i32 = 20;
+/// Minimum blocks between two interventions (intervention box creation height).
+pub(crate) const T_INTERVENTION_GAP: i32 = 360;
+/// Blocks the 95% tracker must have held before extracting.
+pub(crate) const T_EXTRACT: i32 = 10;
+/// Blocks the 101% tracker must have held before releasing.
+pub(crate) const T_RELEASE: i32 = 2;
+/// Minimum blocks between two extract/release actions (extract box creation height).
+pub(crate) const T_EXTRACT_DELAY: i32 = 20;
+
+/// validThreshold: lpRate * 100 < 98 * oracleRate
+const INTERVENTION_THRESHOLD_PERCENT: i64 = 98;
+/// validAmount: lpRateOut * 1000 <= 995 * oracleRate
+const INTERVENTION_MAX_RATE_PER_MILLE: i64 = 995;
+/// The bank spends at most 1% of its ERG per intervention.
+const INTERVENTION_MAX_SPEND_DIVISOR: i64 = 100;
+/// Extract: lpRateOut * 100 <= 98 * oracleRate
+const EXTRACT_MAX_RATE_PERCENT: i64 = 98;
+/// Release: lpRateOut * 100 >= 100 * oracleRate
+const RELEASE_MIN_RATE_PERCENT: i64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeeperAction {
+    #[serde(rename = "tracking_95")]
+    Tracking95,
+    #[serde(rename = "tracking_98")]
+    Tracking98,
+    #[serde(rename = "tracking_101")]
+    Tracking101,
+    Intervention,
+    Extract,
+    Release,
+}
+
+impl KeeperAction {
+    pub const ALL: [KeeperAction; 6] = [
+        KeeperAction::Tracking95,
+        KeeperAction::Tracking98,
+        KeeperAction::Tracking101,
+        KeeperAction::Intervention,
+        KeeperAction::Extract,
+        KeeperAction::Release,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeeperAction::Tracking95 => "tracking_95",
+            KeeperAction::Tracking98 => "tracking_98",
+            KeeperAction::Tracking101 => "tracking_101",
+            KeeperAction::Intervention => "intervention",
+            KeeperAction::Extract => "extract",
+            KeeperAction::Release => "release",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.as_str() == s)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeeperAction::Tracking95 => "Update 95% tracker",
+            KeeperAction::Tracking98 => "Update 98% tracker",
+            KeeperAction::Tracking101 => "Update 101% tracker",
+            KeeperAction::Intervention => "Bank intervention",
+            KeeperAction::Extract => "Extract to future",
+            KeeperAction::Release => "Release from future",
+        }
+    }
+
+    /// (num, isBelow) of the tracking box the action updates or depends on.
+    fn tracker_kind(&self) -> (i32, bool) {
+        match self {
+            KeeperAction::Tracking95 | KeeperAction::Extract => (95, true),
+            KeeperAction::Tracking98 | KeeperAction::Intervention => (98, true),
+            KeeperAction::Tracking101 | KeeperAction::Release => (101, false),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeeperActionStatus {
+    pub action: KeeperAction,
+    pub executable: bool,
+    /// What the tx would do, or why it cannot run yet.
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeeperRequest {
+    pub variant: DexyVariant,
+    pub action: KeeperAction,
+    pub user_ergo_tree: String,
+    pub user_inputs: Vec<Eip12InputBox>,
+    pub current_height: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeeperTxSummary {
+    pub action: KeeperAction,
+    pub detail: String,
+    /// ERG moved from the bank into the LP (intervention only)
+    pub erg_moved_nano: i64,
+    /// Dexy moved between protocol boxes
+    pub dexy_moved: i64,
+    pub tx_fee_nano: i64,
+    pub citadel_fee_nano: i64,
+}
+
+impl KeeperTxSummary {
+    /// Common intent description (signing page, journal, ErgoPay message).
+    pub fn intent(&self, ids: &DexyIds) -> TxIntent {
+        TxIntent::new(
+            "Dexy",
+            format!("{}: {}", ids.variant.token_name(), self.action.label()),
+        )
+        .miner_fee(self.tx_fee_nano.max(0) as u64)
+        .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+        .note(self.detail.clone())
+    }
+}
+
+#[derive(Debug)]
+pub struct KeeperBuildResult {
+    pub unsigned_tx: Eip12UnsignedTx,
+    pub summary: KeeperTxSummary,
+}
+
+/// What an executable action would move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeeperPlan {
+    Tracking { new_height: i32 },
+    Intervention { erg_in: i64, dexy_out: i64 },
+    Extract { amount: i64 },
+    Release { amount: i64 },
+}
+
+impl KeeperPlan {
+    fn describe(&self) -> String {
+        match self {
+            KeeperPlan::Tracking { new_height } if *new_height == i32::MAX => {
+                "Condition no longer holds: reset tracking height".to_string()
+            }
+            KeeperPlan::Tracking { new_height } => {
+                format!("Condition holds: record tracking height {}", new_height)
+            }
+            KeeperPlan::Intervention { erg_in, dexy_out } => format!(
+                "Bank swaps {} nanoERG into the LP for {} Dexy",
+                erg_in, dexy_out
+            ),
+            KeeperPlan::Extract { amount } => {
+                format!("Move {} Dexy from the LP to the extract box", amount)
+            }
+            KeeperPlan::Release { amount } => {
+                format!("Move {} Dexy from the extract box back to the LP", amount)
+            }
+        }
+    }
+}
+
+/// Which keeper actions the current state admits, in `KeeperAction::ALL` order.
+pub fn evaluate_keeper_actions(
+    ctx: &DexyKeeperTxContext,
+    current_height: i32,
+    variant: DexyVariant,
+) -> Vec<KeeperActionStatus> {
+    KeeperAction::ALL
+        .into_iter()
+        .map(
+            |action| match plan_keeper_action(action, ctx, current_height, variant) {
+                Ok(plan) => KeeperActionStatus {
+                    action,
+                    executable: true,
+                    detail: plan.describe(),
+                },
+                Err(e) => KeeperActionStatus {
+                    action,
+                    executable: false,
+                    detail: match e {
+                        TxError::BuildFailed { message } => message,
+                        other => other.to_string(),
+                    },
+                },
+            },
+        )
+        .collect()
+}
+
+pub fn build_keeper_tx(
+    request: &KeeperRequest,
+    ctx: &DexyKeeperTxContext,
+    ids: &DexyIds,
+) -> Result<KeeperBuildResult, TxError> {
+    let height = request.current_height;
+    let plan = plan_keeper_action(request.action, ctx, height, request.variant)?;
+
+    let (inputs, data_inputs, outputs, erg_moved, dexy_moved) = match plan {
+        KeeperPlan::Tracking { new_height } => {
+            let tracker = find_tracker(ctx, request.action)?;
+            let mut successor = successor_output(&tracker.input, None, None, height);
+            successor.additional_registers.insert(
+                "R7".to_string(),
+                ergo_tx::sigma::encode_sigma_int(new_height),
+            );
+            (
+                vec![tracker.input.clone()],
+                vec![ctx.oracle_data_input.clone(), ctx.lp_data_input.clone()],
+                vec![successor],
+                0,
+                0,
+            )
+        }
+        KeeperPlan::Intervention { erg_in, dexy_out } => {
+            let tracker = find_tracker(ctx, request.action)?;
+            (
+                vec![
+                    ctx.lp_input.clone(),
+                    ctx.bank_input.clone(),
+                    ctx.intervention_input.clone(),
+                ],
+                vec![ctx.oracle_data_input.clone(), tracker.data_input.clone()],
+                vec![
+                    successor_output(
+                        &ctx.lp_input,
+                        Some(ctx.lp_erg_reserves + erg_in),
+                        Some((&ids.dexy_token, ctx.lp_dexy_reserves - dexy_out)),
+                        height,
+                    ),
+                    build_bank_output(
+                        &ctx.bank_input,
+                        &ctx.bank_input.ergo_tree,
+                        ctx.bank_erg_nano - erg_in,
+                        ctx.dexy_in_bank + dexy_out,
+                        height,
+                    ),
+                    successor_output(&ctx.intervention_input, None, None, height),
+                ],
+                erg_in,
+                dexy_out,
+            )
+        }
+        KeeperPlan::Extract { amount } | KeeperPlan::Release { amount } => {
+            let tracker = find_tracker(ctx, request.action)?;
+            // Positive: LP -> extract box
+            let delta = if request.action == KeeperAction::Extract {
+                amount
+            } else {
+                -amount
+            };
+            let mut data_inputs = vec![ctx.oracle_data_input.clone(), tracker.data_input.clone()];
+            if request.action == KeeperAction::Extract {
+                data_inputs.push(ctx.bank_data_input.clone());
+            }
+            (
+                vec![ctx.lp_input.clone(), ctx.extract_input.clone()],
+                data_inputs,
+                vec![
+                    successor_output(
+                        &ctx.lp_input,
+                        None,
+                        Some((&ids.dexy_token, ctx.lp_dexy_reserves - delta)),
+                        height,
+                    ),
+                    successor_output(
+                        &ctx.extract_input,
+                        None,
+                        Some((&ids.dexy_token, ctx.dexy_in_extract + delta)),
+                        height,
+                    ),
+                ],
+                0,
+                amount,
+            )
+        }
+    };
+
+    let (unsigned_tx, citadel_fee) = fund_keeper_tx(request, inputs, data_inputs, outputs)?;
+
+    Ok(KeeperBuildResult {
+        unsigned_tx,
+        summary: KeeperTxSummary {
+            action: request.action,
+            detail: plan.describe(),
+            erg_moved_nano: erg_moved,
+            dexy_moved,
+            tx_fee_nano: constants::TX_FEE_NANO,
+            citadel_fee_nano: citadel_fee,
+        },
+    })
+}
+
+fn plan_keeper_action(
+    action: KeeperAction,
+    ctx: &DexyKeeperTxContext,
+    height: i32,
+    variant: DexyVariant,
+) -> Result<KeeperPlan, TxError> {
+    let (lp_rate, oracle_rate) = keeper_rates(ctx, variant)?;
+    let tracker = find_tracker(ctx, action)?;
+
+    if matches!(
+        action,
+        KeeperAction::Tracking95 | KeeperAction::Tracking98 | KeeperAction::Tracking101
+    ) {
+        let x = lp_rate * tracker.denom as i64;
+        let y = tracker.num as i64 * oracle_rate;
+        let holds = if tracker.is_below { x < y } else { x > y };
+        let tracking = tracker.tracking_height != i32::MAX;
+        return match (tracking, holds) {
+            (false, true) => Ok(KeeperPlan::Tracking { new_height: height }),
+            (true, false) => Ok(KeeperPlan::Tracking {
+                new_height: i32::MAX,
+            }),
+            (false, false) => Err(build_failed("Condition does not hold; nothing to record")),
+            (true, true) => Err(build_failed(format!(
+                "Already tracking since height {}",
+                tracker.tracking_height
+            ))),
+        };
+    }
+
+    let period = match action {
+        KeeperAction::Intervention => T_INT,
+        KeeperAction::Extract => T_EXTRACT,
+        _ => T_RELEASE,
+    };
+    if tracker.tracking_height == i32::MAX {
+        return Err(build_failed(format!(
+            "{}% tracker has not been triggered",
+            tracker.num
+        )));
+    }
+    // trackingHeight < HEIGHT - T is strict
+    if tracker.tracking_height >= height - period {
+        return Err(build_failed(format!(
+            "{}% tracker triggered at {}; opens at height {}",
+            tracker.num,
+            tracker.tracking_height,
+            tracker.tracking_height + period + 1
+        )));
+    }
+
+    let (last_action, gap) = if action == KeeperAction::Intervention {
+        (ctx.intervention_input.creation_height, T_INTERVENTION_GAP)
+    } else {
+        (ctx.extract_input.creation_height, T_EXTRACT_DELAY)
+    };
+    if last_action >= height - gap {
+        return Err(build_failed(format!(
+            "Last {} at height {}; next allowed at height {}",
+            if action == KeeperAction::Intervention {
+                "intervention"
+            } else {
+                "extract/release"
+            },
+            last_action,
+            last_action + gap + 1
+        )));
+    }
+
+    let x = ctx.lp_erg_reserves;
+    let y = ctx.lp_dexy_reserves;
+    match action {
+        KeeperAction::Intervention => {
+            if lp_rate * 100 >= INTERVENTION_THRESHOLD_PERCENT * oracle_rate {
+                return Err(build_failed(format!(
+                    "LP rate {} is not below {}% of oracle rate {}",
+                    lp_rate, INTERVENTION_THRESHOLD_PERCENT, oracle_rate
+                )));
+            }
+            let dexy_out = |erg_in: i64| {
+                calculate_lp_swap_output(erg_in, x, y, LP_SWAP_FEE_NUM, LP_SWAP_FEE_DENOM)
+            };
+            let max_spend = ctx.bank_erg_nano / INTERVENTION_MAX_SPEND_DIVISOR;
+            let erg_in = max_satisfying(1, max_spend, |erg_in| {
+                let rate_out = (x + erg_in) / (y - dexy_out(erg_in));
+                rate_out * 1000 <= INTERVENTION_MAX_RATE_PER_MILLE * oracle_rate
+            })
+            .filter(|erg_in| dexy_out(*erg_in) > 0)
+            .ok_or_else(|| build_failed("Bank cannot move the LP rate within limits"))?;
+            Ok(KeeperPlan::Intervention {
+                erg_in,
+                dexy_out: dexy_out(erg_in),
+            })
+        }
+        KeeperAction::Extract => {
+            let amount = max_satisfying(1, y - 1, |amount| {
+                (x / (y - amount)) * 100 <= EXTRACT_MAX_RATE_PERCENT * oracle_rate
+            })
+            .ok_or_else(|| {
+                build_failed(format!(
+                    "LP rate {} is already at or above {}% of oracle rate {}",
+                    lp_rate, EXTRACT_MAX_RATE_PERCENT, oracle_rate
+                ))
+            })?;
+            Ok(KeeperPlan::Extract { amount })
+        }
+        _ => {
+            // The extract box keeps at least one Dexy token.
+            let amount = max_satisfying(1, ctx.dexy_in_extract - 1, |amount| {
+                (x / (y + amount)) * 100 >= RELEASE_MIN_RATE_PERCENT * oracle_rate
+            })
+            .ok_or_else(|| {
+                build_failed(format!(
+                    "Nothing to release: extract box holds {} Dexy, LP rate {} vs oracle rate {}",
+                    ctx.dexy_in_extract, lp_rate, oracle_rate
+                ))
+            })?;
+            Ok(KeeperPlan::Release { amount })
+        }
+    }
+}
+
+/// (lpRate, oracleRate) in nanoERG per token, as the contracts compute them.
+fn keeper_rates(ctx: &DexyKeeperTxContext, variant: DexyVariant) -> Result<(i64, i64), TxError> {
+    let oracle_rate = ctx.oracle_rate_nano / variant.oracle_divisor();
+    if oracle_rate <= 0 {
+        return Err(build_failed(format!(
+            "Invalid oracle rate: {} (raw: {})",
+            oracle_rate, ctx.oracle_rate_nano
+        )));
+    }
+    if ctx.lp_dexy_reserves <= 0 {
+        return Err(build_failed("LP has no Dexy reserves"));
+    }
+    Ok((ctx.lp_erg_reserves / ctx.lp_dexy_reserves, oracle_rate))
+}
+
+fn find_tracker(
+    ctx: &DexyKeeperTxContext,
+    action: KeeperAction,
+) -> Result<&DexyTrackingBox, TxError> {
+    let (num, is_below) = action.tracker_kind();
+    ctx.trackers
+        .iter()
+        .find(|t| t.num == num && t.is_below == is_below)
+        .ok_or_else(|| build_failed(format!("{}% tracking box not found", num)))
+}
+
+/// Largest `v` in `[lo, hi]` with `pred(v)`, for a predicate that holds up to
+/// some point and fails after it.
+fn max_satisfying(lo: i64, hi: i64, pred: impl Fn(i64) -> bool) -> Option<i64> {
+    if hi < lo || !pred(lo) {
+        return None;
+    }
+    let (mut ok, mut bad) = (lo, hi + 1);
+    while bad - ok > 1 {
+        let mid = ok + (bad - ok) / 2;
+        if pred(mid) {
+            ok = mid;
+        } else {
+            bad = mid;
+        }
+    }
+    Some(ok)
+}
+
+/// Successor of a protocol box: same script, tokens and registers, with an
+/// optional new value and Dexy amount.
+fn successor_output(
+    input: &Eip12InputBox,
+    value: Option<i64>,
+    dexy: Option<(&str, i64)>,
+    height: i32,
+) -> Eip12Output {
+    let assets: Vec<Eip12Asset> = input
+        .assets
+        .iter()
+        .map(|a| match dexy {
+            Some((dexy_id, amount)) if a.token_id == dexy_id => Eip12Asset::new(dexy_id, amount),
+            _ => Eip12Asset::new(&a.token_id, a.amount.parse().unwrap_or(1)),
+        })
+        .collect();
+
+    Eip12Output {
+        value: value.map_or_else(|| input.value.clone(), |v| v.to_string()),
+        ergo_tree: input.ergo_tree.clone(),
+        assets,
+        creation_height: height,
+        additional_registers: input.additional_registers.clone(),
+    }
+}
+
+/// Append the keeper's inputs, change, app fee and miner fee.
+fn fund_keeper_tx(
+    request: &KeeperRequest,
+    mut inputs: Vec<Eip12InputBox>,
+    data_inputs: Vec<Eip12DataInputBox>,
+    mut outputs: Vec<Eip12Output>,
+) -> Result<(Eip12UnsignedTx, i64), TxError> {
+    let fee_cfg = resolved_dev_fee_config();
+    let citadel_fee = fee_cfg.budget();
+    let erg_used = constants::TX_FEE_NANO + citadel_fee;
+
+    let selected = select_inputs_for_spend(
+        &request.user_inputs,
+        (erg_used + constants::MIN_BOX_VALUE_NANO) as u64,
+        None,
+    )
+    .map_err(|e| build_failed(e.to_string()))?;
+    inputs.extend(selected.boxes.clone());
+
+    append_change_output(
+        &mut outputs,
+        &selected,
+        erg_used as u64,
+        &[],
+        &request.user_ergo_tree,
+        request.current_height,
+        constants::MIN_BOX_VALUE_NANO as u64,
+    )
+    .map_err(|e| build_failed(e.to_string()))?;
+    append_dev_fee_output(&mut outputs, &fee_cfg, request.current_height)
+        .map_err(|e| build_failed(e.to_string()))?;
+    outputs.push(Eip12Output::fee(
+        constants::TX_FEE_NANO,
+        request.current_height,
+    ));
+
+    Ok((
+        Eip12UnsignedTx {
+            inputs,
+            data_inputs,
+            outputs,
+        },
+        citadel_fee,
+    ))
+}
+
+fn build_failed(message: impl Into<String>) -> TxError {
+    TxError::BuildFailed {
+        message: message.into(),
+    }
+}
@@ -1,13 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use citadel_core::{constants, TxError};
+use citadel_core::{constants, IntentAsset, TxError, TxIntent};
 use ergo_tx::{
     append_dev_fee_output, collect_change_tokens, resolved_dev_fee_config, select_inputs_for_spend,
     Eip12Asset, Eip12InputBox, Eip12Output, Eip12UnsignedTx,
 };
 
 use crate::calculator::calculate_lp_deposit;
-use crate::constants::DexyVariant;
+use crate::constants::{DexyIds, DexyVariant};
 use crate::fetch::DexyLpTxContext;
 
 // =============================================================================
@@ -38,6 +38,41 @@ pub struct LpTxSummary {
     pub citadel_fee_nano: i64,
 }
 
+impl LpTxSummary {
+    /// Common intent description (signing page, journal, ErgoPay message).
+    pub fn intent(&self, ids: &DexyIds) -> TxIntent {
+        let name = ids.variant.token_name();
+        let erg = IntentAsset::erg(self.erg_amount.max(0) as u64);
+        let dexy = IntentAsset::named(
+            &ids.dexy_token,
+            name,
+            self.dexy_amount.max(0) as u64,
+            ids.variant.decimals(),
+        );
+        let lp = IntentAsset::named(
+            &ids.lp_token_id,
+            format!("{} LP", name),
+            self.lp_tokens.max(0) as u64,
+            0,
+        );
+        let intent = if self.action.starts_with("lp_redeem") {
+            TxIntent::new("Dexy", format!("Remove {} liquidity", name))
+                .pays(lp)
+                .receives(erg)
+                .receives(dexy)
+        } else {
+            TxIntent::new("Dexy", format!("Add {} liquidity", name))
+                .pays(erg)
+                .pays(dexy)
+                .receives(lp)
+        };
+        intent
+            .miner_fee(self.miner_fee_nano.max(0) as u64)
+            .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+            .counterparty("pool", format!("{} LP", name))
+    }
+}
+
 /// Build result for LP deposit/redeem transactions
 #[derive(Debug)]
 pub struct LpBuildResult {
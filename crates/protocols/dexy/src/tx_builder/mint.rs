@@ -1,12 +1,12 @@
 use std::collections::HashMap;
 
-use citadel_core::{constants, TxError};
+use citadel_core::{constants, IntentAsset, TxError, TxIntent};
 use ergo_tx::{
     append_change_output, append_dev_fee_output, resolved_dev_fee_config, select_inputs_for_spend,
     Eip12Asset, Eip12InputBox, Eip12Output, Eip12UnsignedTx,
 };
 
-use crate::constants::{DexyVariant, BANK_FEE_NUM, BUYBACK_FEE_NUM, FEE_DENOM};
+use crate::constants::{DexyIds, DexyVariant, BANK_FEE_NUM, BUYBACK_FEE_NUM, FEE_DENOM};
 use crate::fetch::DexyTxContext;
 use crate::state::DexyState;
 
@@ -16,7 +16,7 @@ use super::validate::{validate_free_mint_preflight, T_BUFFER};
 const T_FREE: i32 = 360;
 /// Buyback action type for top-up (used during FreeMint/ArbMint)
 /// Action 0 = swap (buy GORT), Action 1 = top-up (receive ERG), Action 2 = return (give GORT)
-const BUYBACK_ACTION_TOPUP: i32 = 1;
+pub(crate) const BUYBACK_ACTION_TOPUP: i32 = 1;
 
 #[derive(Debug, Clone)]
 pub struct MintDexyRequest {
@@ -41,6 +41,32 @@ pub struct TxSummary {
     pub buyback_fee_nano: i64,
 }
 
+impl TxSummary {
+    /// Common intent description (signing page, journal, ErgoPay message).
+    pub fn intent(&self, ids: &DexyIds) -> TxIntent {
+        TxIntent::new("Dexy", format!("Mint {}", self.token_name))
+            .pays(IntentAsset::erg(self.erg_amount_nano.max(0) as u64))
+            .receives(IntentAsset::named(
+                &ids.dexy_token,
+                &self.token_name,
+                self.token_amount.max(0) as u64,
+                ids.variant.decimals(),
+            ))
+            .miner_fee(self.tx_fee_nano.max(0) as u64)
+            .protocol_fee((self.bank_fee_nano + self.buyback_fee_nano).max(0) as u64)
+            .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+            .counterparty("bank", format!("{} bank ({})", self.token_name, self.mint_path()))
+    }
+
+    fn mint_path(&self) -> &'static str {
+        if self.action.starts_with("arb_mint") {
+            "ArbMint"
+        } else {
+            "FreeMint"
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BuildResult {
     pub unsigned_tx: Eip12UnsignedTx,
@@ -173,8 +199,19 @@ pub fn build_mint_dexy_tx(
 
     let mut outputs = vec![
         build_free_mint_output(ctx, new_r4, new_r5, request.current_height),
-        build_bank_output(ctx, new_bank_erg, new_dexy_in_bank, request.current_height),
-        build_buyback_output(ctx, new_buyback_erg, request.current_height)?,
+        build_bank_output(
+            &ctx.bank_input,
+            &ctx.bank_ergo_tree,
+            new_bank_erg,
+            new_dexy_in_bank,
+            request.current_height,
+        ),
+        build_buyback_output(
+            &ctx.buyback_input,
+            &ctx.buyback_ergo_tree,
+            new_buyback_erg,
+            request.current_height,
+        )?,
         Eip12Output::change(
             constants::MIN_BOX_VALUE_NANO,
             output_ergo_tree,
@@ -254,14 +291,20 @@ pub(crate) fn build_free_mint_output(ctx: &DexyTxContext, new_r4: i32, new_r5: i
 }
 
 /// Token order must exactly match input bank box.
-pub(crate) fn build_bank_output(ctx: &DexyTxContext, new_erg: i64, new_dexy_tokens: i64, height: i32) -> Eip12Output {
-    let assets: Vec<Eip12Asset> = ctx.bank_input.assets.iter().enumerate()
+pub(crate) fn build_bank_output(
+    bank_input: &Eip12InputBox,
+    bank_ergo_tree: &str,
+    new_erg: i64,
+    new_dexy_tokens: i64,
+    height: i32,
+) -> Eip12Output {
+    let assets: Vec<Eip12Asset> = bank_input.assets.iter().enumerate()
         .map(|(i, a)| Eip12Asset::new(&a.token_id, if i == 0 { 1 } else { new_dexy_tokens }))
         .collect();
 
     Eip12Output {
         value: new_erg.to_string(),
-        ergo_tree: ctx.bank_ergo_tree.clone(),
+        ergo_tree: bank_ergo_tree.to_string(),
         assets,
         creation_height: height,
         additional_registers: HashMap::new(),
@@ -269,13 +312,18 @@ pub(crate) fn build_bank_output(ctx: &DexyTxContext, new_erg: i64, new_dexy_toke
 }
 
 /// Buyback contract (action=1) requires R4 = SELF.id.
-pub(crate) fn build_buyback_output(ctx: &DexyTxContext, new_erg: i64, height: i32) -> Result<Eip12Output, TxError> {
-    let assets: Vec<Eip12Asset> = ctx.buyback_input.assets.iter()
+pub(crate) fn build_buyback_output(
+    buyback_input: &Eip12InputBox,
+    buyback_ergo_tree: &str,
+    new_erg: i64,
+    height: i32,
+) -> Result<Eip12Output, TxError> {
+    let assets: Vec<Eip12Asset> = buyback_input.assets.iter()
         .map(|a| Eip12Asset::new(&a.token_id, a.amount.parse().unwrap_or(1)))
         .collect();
 
     let box_id_bytes: Vec<u8> =
-        base16::decode(&ctx.buyback_input.box_id).map_err(|e| TxError::BuildFailed {
+        base16::decode(&buyback_input.box_id).map_err(|e| TxError::BuildFailed {
             message: format!("Invalid buyback box_id hex: {}", e),
         })?;
     let registers = ergo_tx::sigma_registers!(
@@ -284,7 +332,7 @@ pub(crate) fn build_buyback_output(ctx: &DexyTxContext, new_erg: i64, height: i3
 
     Ok(Eip12Output {
         value: new_erg.to_string(),
-        ergo_tree: ctx.buyback_ergo_tree.clone(),
+        ergo_tree: buyback_ergo_tree.to_string(),
         assets,
         creation_height: height,
         additional_registers: registers,
@@ -1,8 +1,11 @@
-//! Dexy transaction builders: FreeMint, LP Swap, LP Deposit/Redeem.
+//! Dexy transaction builders: FreeMint, ArbMint, LP Swap, LP Deposit/Redeem and
+//! keeper actions (tracking, intervention, extract/release).
 //!
 //! Citadel app fee (0.011 ERG) is funded from user inputs and placed after
-//! protocol successors (free-mint/bank/buyback or LP/action NFT) and before miner fee.
+//! protocol successors (mint action/bank/buyback or LP/action NFT) and before miner fee.
 
+mod arb_mint;
+mod keeper;
 mod lp_deposit;
 mod lp_redeem;
 mod mint;
@@ -12,13 +15,18 @@ mod validate;
 #[cfg(test)]
 mod tests;
 
+pub use arb_mint::build_arb_mint_dexy_tx;
+pub use keeper::{
+    build_keeper_tx, evaluate_keeper_actions, KeeperAction, KeeperActionStatus,
+    KeeperBuildResult, KeeperRequest, KeeperTxSummary,
+};
 pub use lp_deposit::{build_lp_deposit_tx, LpBuildResult, LpDepositRequest, LpTxSummary};
 pub use lp_redeem::{build_lp_redeem_tx, LpRedeemRequest};
 pub use mint::{build_mint_dexy_tx, BuildResult, MintDexyRequest, TxSummary};
 pub use swap::{
     build_swap_dexy_tx, SwapBuildResult, SwapDexyRequest, SwapDirection, SwapTxSummary,
 };
-pub use validate::{validate_free_mint_preflight, validate_mint_dexy};
+pub use validate::{validate_arb_mint_preflight, validate_free_mint_preflight, validate_mint_dexy};
 
 #[cfg(test)]
 pub(crate) use lp_deposit::{build_action_nft_output, build_lp_pool_output};
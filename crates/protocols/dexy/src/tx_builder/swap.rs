@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use citadel_core::{constants, TxError};
+use citadel_core::{constants, IntentAsset, TxError, TxIntent};
 use ergo_tx::{
     append_change_output, append_dev_fee_output, collect_change_tokens, resolved_dev_fee_config,
     select_inputs_for_spend, Eip12Asset, Eip12InputBox, Eip12Output, Eip12UnsignedTx,
@@ -9,7 +9,7 @@ use ergo_tx::{
 use crate::calculator::{
     calculate_lp_swap_output, calculate_lp_swap_price_impact, validate_lp_swap,
 };
-use crate::constants::{DexyVariant, LP_SWAP_FEE_DENOM, LP_SWAP_FEE_NUM};
+use crate::constants::{DexyIds, DexyVariant, LP_SWAP_FEE_DENOM, LP_SWAP_FEE_NUM};
 use crate::fetch::DexySwapTxContext;
 use crate::state::DexyState;
 
@@ -44,6 +44,48 @@ pub struct SwapTxSummary {
     pub citadel_fee_nano: i64,
 }
 
+impl SwapTxSummary {
+    /// Common intent description (signing page, journal, ErgoPay message).
+    pub fn intent(&self, ids: &DexyIds) -> TxIntent {
+        let name = ids.variant.token_name();
+        let dexy = |amount: i64| {
+            IntentAsset::named(
+                &ids.dexy_token,
+                name,
+                amount.max(0) as u64,
+                ids.variant.decimals(),
+            )
+        };
+        let erg = |amount: i64| IntentAsset::erg(amount.max(0) as u64);
+        let (action, input, output, min_output) = if self.direction == "erg_to_dexy" {
+            (
+                format!("Swap ERG for {}", name),
+                erg(self.input_amount),
+                dexy(self.output_amount),
+                dexy(self.min_output),
+            )
+        } else {
+            (
+                format!("Swap {} for ERG", name),
+                dexy(self.input_amount),
+                erg(self.output_amount),
+                erg(self.min_output),
+            )
+        };
+        let mut intent = TxIntent::new("Dexy", action)
+            .pays(input)
+            .receives(output)
+            .min_out(min_output, None)
+            .miner_fee(self.miner_fee_nano.max(0) as u64)
+            .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+            .counterparty("pool", format!("{} LP", name));
+        if self.price_impact_pct >= 1.0 {
+            intent = intent.note(format!("Price impact {:.2}%", self.price_impact_pct));
+        }
+        intent
+    }
+}
+
 #[derive(Debug)]
 pub struct SwapBuildResult {
     pub unsigned_tx: Eip12UnsignedTx,
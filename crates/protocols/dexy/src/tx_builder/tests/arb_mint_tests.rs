@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use citadel_core::TxError;
+use ergo_tx::{Eip12Asset, Eip12DataInputBox, Eip12InputBox};
+
+use super::*;
+use crate::fetch::DexyArbMintTxContext;
+
+const HEIGHT: i32 = 100_000;
+
+fn action_box(box_id: &str, ergo_tree: &str, value: i64) -> Eip12InputBox {
+    Eip12InputBox {
+        box_id: box_id.to_string(),
+        transaction_id: "tx_id".to_string(),
+        index: 0,
+        value: value.to_string(),
+        ergo_tree: ergo_tree.to_string(),
+        assets: vec![Eip12Asset::new(box_id, 1)],
+        creation_height: HEIGHT,
+        additional_registers: HashMap::new(),
+        extension: HashMap::new(),
+    }
+}
+
+fn data_box(box_id: &str) -> Eip12DataInputBox {
+    Eip12DataInputBox {
+        box_id: box_id.to_string(),
+        transaction_id: "tx_id".to_string(),
+        index: 0,
+        value: "1000000".to_string(),
+        ergo_tree: "data_ergo_tree".to_string(),
+        assets: vec![],
+        creation_height: HEIGHT,
+        additional_registers: HashMap::new(),
+    }
+}
+
+/// Gold, oracle 220_000 nanoERG/mg (221_100 with fees), LP at 250_000.
+fn create_arb_mint_context(lp_dexy: i64, tracking_height: i32) -> DexyArbMintTxContext {
+    let buyback_box_id = "cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc";
+    DexyArbMintTxContext {
+        arb_mint_input: action_box("arb_mint_box", "arb_mint_ergo_tree", 1_000_000),
+        arb_mint_erg_nano: 1_000_000,
+        arb_mint_ergo_tree: "arb_mint_ergo_tree".to_string(),
+        arb_mint_r4_height: HEIGHT + 20,
+        arb_mint_r5_available: 50,
+        bank_input: action_box("bank_box", "bank_ergo_tree", 1_000_000_000_000),
+        bank_erg_nano: 1_000_000_000_000,
+        dexy_in_bank: 10_000,
+        bank_ergo_tree: "bank_ergo_tree".to_string(),
+        buyback_input: action_box(buyback_box_id, "buyback_ergo_tree", 5_000_000),
+        buyback_erg_nano: 5_000_000,
+        buyback_ergo_tree: "buyback_ergo_tree".to_string(),
+        oracle_data_input: data_box("oracle_box"),
+        oracle_rate_nano: 220_000_000_000,
+        lp_data_input: data_box("lp_box"),
+        lp_erg_reserves: 250_000 * lp_dexy,
+        lp_dexy_reserves: lp_dexy,
+        tracking_data_input: data_box("tracking_box"),
+        tracking_height,
+    }
+}
+
+fn arb_mint_request(amount: i64) -> MintDexyRequest {
+    MintDexyRequest {
+        variant: DexyVariant::Gold,
+        amount,
+        user_address: "user_address".to_string(),
+        user_ergo_tree: "user_ergo_tree".to_string(),
+        user_inputs: vec![create_test_input(100_000_000_000, vec![])],
+        current_height: HEIGHT,
+        recipient_ergo_tree: None,
+    }
+}
+
+fn preflight_error(ctx: &DexyArbMintTxContext, amount: i64) -> String {
+    match validate_arb_mint_preflight(ctx, amount, HEIGHT, DexyVariant::Gold) {
+        Err(TxError::BuildFailed { message }) => message,
+        other => panic!("Expected BuildFailed, got: {:?}", other),
+    }
+}
+
+#[test]
+fn test_arb_mint_preflight_ok_within_period() {
+    let ctx = create_arb_mint_context(2_000_000, HEIGHT - 1_000);
+    assert!(validate_arb_mint_preflight(&ctx, 50, HEIGHT, DexyVariant::Gold).is_ok());
+
+    // R5 caps the period allowance
+    let message = preflight_error(&ctx, 51);
+    assert!(message.contains("ArbMint limit 50"), "{}", message);
+}
+
+#[test]
+fn test_arb_mint_preflight_requires_lp_above_threshold() {
+    let mut ctx = create_arb_mint_context(2_000_000, HEIGHT - 1_000);
+    // 223_000 * 100 <= 101 * 221_100: just under the 101% threshold
+    ctx.lp_erg_reserves = 223_000 * ctx.lp_dexy_reserves;
+
+    let message = preflight_error(&ctx, 10);
+    assert!(message.contains("rate condition not met"), "{}", message);
+}
+
+#[test]
+fn test_arb_mint_preflight_requires_tracking_period() {
+    let ctx = create_arb_mint_context(2_000_000, i32::MAX);
+    let message = preflight_error(&ctx, 10);
+    assert!(message.contains("has not recorded"), "{}", message);
+
+    // trackingHeight < HEIGHT - T_arb is strict
+    let ctx = create_arb_mint_context(2_000_000, HEIGHT - 30);
+    let message = preflight_error(&ctx, 10);
+    assert!(message.contains("not open yet"), "{}", message);
+
+    let ctx = create_arb_mint_context(2_000_000, HEIGHT - 31);
+    assert!(validate_arb_mint_preflight(&ctx, 10, HEIGHT, DexyVariant::Gold).is_ok());
+}
+
+#[test]
+fn test_arb_mint_counter_reset_successor() {
+    no_citadel_fee(|| {
+        let mut ctx = create_arb_mint_context(2_000_000, HEIGHT - 1_000);
+        ctx.arb_mint_r4_height = HEIGHT - 10;
+        let state = create_test_state(10_000, true);
+
+        // (5e11 - 221_100 * 2e6) / 221_100 = 261_420
+        let result = build_arb_mint_dexy_tx(&arb_mint_request(261_421), &ctx, &state);
+        assert!(result.is_err());
+
+        let result =
+            build_arb_mint_dexy_tx(&arb_mint_request(100), &ctx, &state).expect("arb mint");
+        let tx = &result.unsigned_tx;
+        assert_eq!(tx.inputs[0].box_id, "arb_mint_box");
+        assert_eq!(tx.data_inputs.len(), 3);
+        assert_eq!(tx.data_inputs[2].box_id, "tracking_box");
+
+        let successor = &tx.outputs[0];
+        assert_eq!(successor.ergo_tree, "arb_mint_ergo_tree");
+        assert_eq!(successor.value, "1000000");
+        assert_eq!(
+            successor.additional_registers.get("R4"),
+            Some(&ergo_tx::sigma::encode_sigma_int(HEIGHT + 35))
+        );
+        assert_eq!(
+            successor.additional_registers.get("R5"),
+            Some(&ergo_tx::sigma::encode_sigma_long(261_420 - 100))
+        );
+        assert_eq!(result.summary.action, "arb_mint_dexy_gold");
+    });
+}
@@ -0,0 +1,316 @@
+use std::collections::HashMap;
+
+use citadel_core::TxError;
+use ergo_tx::{Eip12Asset, Eip12DataInputBox, Eip12InputBox};
+
+use super::*;
+use crate::constants::DexyIds;
+use crate::fetch::{DexyKeeperTxContext, DexyTrackingBox};
+
+const HEIGHT: i32 = 100_000;
+
+fn protocol_box(box_id: &str, value: i64, assets: Vec<Eip12Asset>) -> Eip12InputBox {
+    Eip12InputBox {
+        box_id: box_id.to_string(),
+        transaction_id: "tx_id".to_string(),
+        index: 0,
+        value: value.to_string(),
+        ergo_tree: format!("{}_ergo_tree", box_id),
+        assets,
+        creation_height: HEIGHT - 1_000,
+        additional_registers: HashMap::new(),
+        extension: HashMap::new(),
+    }
+}
+
+fn data_box(input: &Eip12InputBox) -> Eip12DataInputBox {
+    Eip12DataInputBox {
+        box_id: input.box_id.clone(),
+        transaction_id: input.transaction_id.clone(),
+        index: input.index,
+        value: input.value.clone(),
+        ergo_tree: input.ergo_tree.clone(),
+        assets: input.assets.clone(),
+        creation_height: input.creation_height,
+        additional_registers: input.additional_registers.clone(),
+    }
+}
+
+fn tracker(num: i32, is_below: bool, tracking_height: i32) -> DexyTrackingBox {
+    let mut input = protocol_box(
+        &format!("tracking{}", num),
+        1_000_000,
+        vec![Eip12Asset::new(&format!("tracking{}_nft", num), 1)],
+    );
+    input.additional_registers = ergo_tx::sigma_registers!(
+        "R4" => ergo_tx::sigma::encode_sigma_int(num),
+        "R5" => ergo_tx::sigma::encode_sigma_int(100),
+        "R6" => if is_below { "0101" } else { "0100" }.to_string(),
+        "R7" => ergo_tx::sigma::encode_sigma_int(tracking_height),
+    );
+    DexyTrackingBox {
+        data_input: data_box(&input),
+        input,
+        num,
+        denom: 100,
+        is_below,
+        tracking_height,
+    }
+}
+
+/// Gold, oracle 220_000 nanoERG/mg, LP holding 2_000_000 mg at `lp_rate`.
+/// No tracker is triggered.
+fn create_keeper_context(lp_rate: i64) -> DexyKeeperTxContext {
+    let ids = DexyIds::gold_mainnet();
+    let lp_dexy = 2_000_000;
+    let lp_input = protocol_box(
+        "lp",
+        lp_rate * lp_dexy,
+        vec![
+            Eip12Asset::new(&ids.lp_nft, 1),
+            Eip12Asset::new(&ids.lp_token_id, 1_000_000),
+            Eip12Asset::new(&ids.dexy_token, lp_dexy),
+        ],
+    );
+    let bank_input = protocol_box(
+        "bank",
+        1_000_000_000_000,
+        vec![
+            Eip12Asset::new(&ids.bank_nft, 1),
+            Eip12Asset::new(&ids.dexy_token, 10_000),
+        ],
+    );
+    let oracle_input = protocol_box("oracle", 1_000_000, vec![]);
+    DexyKeeperTxContext {
+        oracle_data_input: data_box(&oracle_input),
+        oracle_rate_nano: 220_000_000_000,
+        lp_data_input: data_box(&lp_input),
+        lp_erg_reserves: lp_rate * lp_dexy,
+        lp_dexy_reserves: lp_dexy,
+        lp_input,
+        bank_data_input: data_box(&bank_input),
+        bank_erg_nano: 1_000_000_000_000,
+        dexy_in_bank: 10_000,
+        bank_input,
+        intervention_input: protocol_box(
+            "intervention",
+            1_000_000,
+            vec![Eip12Asset::new("intervention_nft", 1)],
+        ),
+        extract_input: protocol_box(
+            "extract",
+            1_000_000,
+            vec![
+                Eip12Asset::new("extract_nft", 1),
+                Eip12Asset::new(&ids.dexy_token, 50_000),
+            ],
+        ),
+        dexy_in_extract: 50_000,
+        trackers: vec![
+            tracker(95, true, i32::MAX),
+            tracker(98, true, i32::MAX),
+            tracker(101, false, i32::MAX),
+        ],
+    }
+}
+
+fn keeper_request(action: KeeperAction) -> KeeperRequest {
+    KeeperRequest {
+        variant: DexyVariant::Gold,
+        action,
+        user_ergo_tree: "user_ergo_tree".to_string(),
+        user_inputs: vec![create_test_input(1_000_000_000, vec![])],
+        current_height: HEIGHT,
+    }
+}
+
+fn status(ctx: &DexyKeeperTxContext, action: KeeperAction) -> KeeperActionStatus {
+    evaluate_keeper_actions(ctx, HEIGHT, DexyVariant::Gold)
+        .into_iter()
+        .find(|s| s.action == action)
+        .expect("status for every action")
+}
+
+fn token_amount(output: &ergo_tx::Eip12Output, token_id: &str) -> Option<String> {
+    output
+        .assets
+        .iter()
+        .find(|a| a.token_id == token_id)
+        .map(|a| a.amount.clone())
+}
+
+#[test]
+fn test_keeper_action_names_round_trip() {
+    for action in KeeperAction::ALL {
+        assert_eq!(KeeperAction::parse(action.as_str()), Some(action));
+        assert_eq!(
+            serde_json::to_value(action).unwrap(),
+            serde_json::Value::String(action.as_str().to_string())
+        );
+    }
+    assert_eq!(KeeperAction::parse("payout"), None);
+}
+
+#[test]
+fn test_tracking_trigger_and_reset() {
+    no_citadel_fee(|| {
+        // 200_000 < 95% of 220_000: both below-trackers can record
+        let mut ctx = create_keeper_context(200_000);
+        assert!(status(&ctx, KeeperAction::Tracking95).executable);
+        assert!(status(&ctx, KeeperAction::Tracking98).executable);
+        let above = status(&ctx, KeeperAction::Tracking101);
+        assert!(!above.executable);
+        assert!(above.detail.contains("does not hold"), "{}", above.detail);
+
+        let ids = DexyIds::gold_mainnet();
+        let result = build_keeper_tx(&keeper_request(KeeperAction::Tracking95), &ctx, &ids)
+            .expect("tracking update");
+        let tx = &result.unsigned_tx;
+        assert_eq!(tx.inputs[0].box_id, "tracking95");
+        assert_eq!(tx.data_inputs[0].box_id, "oracle");
+        assert_eq!(tx.data_inputs[1].box_id, "lp");
+        let successor = &tx.outputs[0];
+        assert_eq!(successor.ergo_tree, "tracking95_ergo_tree");
+        assert_eq!(successor.value, "1000000");
+        assert_eq!(
+            successor.additional_registers.get("R4"),
+            Some(&ergo_tx::sigma::encode_sigma_int(95))
+        );
+        assert_eq!(
+            successor.additional_registers.get("R7"),
+            Some(&ergo_tx::sigma::encode_sigma_int(HEIGHT))
+        );
+
+        // Already recorded: nothing to do
+        ctx.trackers[0] = tracker(95, true, HEIGHT - 5);
+        let message = status(&ctx, KeeperAction::Tracking95).detail;
+        assert!(message.contains("Already tracking"), "{}", message);
+
+        // A stale 101% tracker is reset once the LP drops back
+        ctx.trackers[2] = tracker(101, false, HEIGHT - 100);
+        let result = build_keeper_tx(&keeper_request(KeeperAction::Tracking101), &ctx, &ids)
+            .expect("tracking reset");
+        assert_eq!(
+            result.unsigned_tx.outputs[0].additional_registers.get("R7"),
+            Some(&ergo_tx::sigma::encode_sigma_int(i32::MAX))
+        );
+    });
+}
+
+#[test]
+fn test_intervention_preconditions_and_amounts() {
+    no_citadel_fee(|| {
+        let mut ctx = create_keeper_context(200_000);
+        let message = status(&ctx, KeeperAction::Intervention).detail;
+        assert!(message.contains("not been triggered"), "{}", message);
+
+        // trackingHeight < HEIGHT - T_int is strict
+        ctx.trackers[1] = tracker(98, true, HEIGHT - 20);
+        let message = status(&ctx, KeeperAction::Intervention).detail;
+        assert!(message.contains("opens at height 100001"), "{}", message);
+
+        ctx.trackers[1] = tracker(98, true, HEIGHT - 21);
+        ctx.intervention_input.creation_height = HEIGHT - 360;
+        let message = status(&ctx, KeeperAction::Intervention).detail;
+        assert!(
+            message.contains("next allowed at height 100001"),
+            "{}",
+            message
+        );
+
+        ctx.intervention_input.creation_height = HEIGHT - 361;
+        assert!(status(&ctx, KeeperAction::Intervention).executable);
+
+        let ids = DexyIds::gold_mainnet();
+        let result = build_keeper_tx(&keeper_request(KeeperAction::Intervention), &ctx, &ids)
+            .expect("intervention");
+        let tx = &result.unsigned_tx;
+        assert_eq!(tx.inputs[0].box_id, "lp");
+        assert_eq!(tx.inputs[1].box_id, "bank");
+        assert_eq!(tx.inputs[2].box_id, "intervention");
+        assert_eq!(tx.data_inputs[1].box_id, "tracking98");
+
+        // Capped at 1% of the bank; the LP rate ends at 210_109 <= 99.5% of oracle
+        assert_eq!(result.summary.erg_moved_nano, 10_000_000_000);
+        assert_eq!(result.summary.dexy_moved, 48_637);
+        assert_eq!(tx.outputs[0].value, "410000000000");
+        assert_eq!(
+            token_amount(&tx.outputs[0], &ids.dexy_token).as_deref(),
+            Some("1951363")
+        );
+        assert_eq!(tx.outputs[1].value, "990000000000");
+        assert_eq!(
+            token_amount(&tx.outputs[1], &ids.dexy_token).as_deref(),
+            Some("58637")
+        );
+        assert_eq!(tx.outputs[2].ergo_tree, "intervention_ergo_tree");
+
+        // No intervention once the LP is within 98% of the oracle
+        let mut ctx = create_keeper_context(216_000);
+        ctx.trackers[1] = tracker(98, true, HEIGHT - 21);
+        let message = status(&ctx, KeeperAction::Intervention).detail;
+        assert!(message.contains("is not below 98%"), "{}", message);
+    });
+}
+
+#[test]
+fn test_extract_and_release_amounts() {
+    no_citadel_fee(|| {
+        let ids = DexyIds::gold_mainnet();
+
+        let mut ctx = create_keeper_context(200_000);
+        ctx.trackers[0] = tracker(95, true, HEIGHT - 11);
+        assert!(!status(&ctx, KeeperAction::Release).executable);
+
+        // Largest extraction keeping the LP rate <= 98% of oracle (215_600)
+        let result =
+            build_keeper_tx(&keeper_request(KeeperAction::Extract), &ctx, &ids).expect("extract");
+        let tx = &result.unsigned_tx;
+        assert_eq!(result.summary.dexy_moved, 144_721);
+        assert_eq!(tx.data_inputs.len(), 3);
+        assert_eq!(tx.data_inputs[2].box_id, "bank");
+        assert_eq!(tx.outputs[0].value, "400000000000");
+        assert_eq!(
+            token_amount(&tx.outputs[0], &ids.dexy_token).as_deref(),
+            Some("1855279")
+        );
+        assert_eq!(
+            token_amount(&tx.outputs[1], &ids.dexy_token).as_deref(),
+            Some("194721")
+        );
+
+        ctx.extract_input.creation_height = HEIGHT - 20;
+        let message = status(&ctx, KeeperAction::Extract).detail;
+        assert!(message.contains("extract/release"), "{}", message);
+
+        // Release is capped by the extract box, which keeps one token
+        let mut ctx = create_keeper_context(230_000);
+        ctx.trackers[2] = tracker(101, false, HEIGHT - 3);
+        let result =
+            build_keeper_tx(&keeper_request(KeeperAction::Release), &ctx, &ids).expect("release");
+        let tx = &result.unsigned_tx;
+        assert_eq!(result.summary.dexy_moved, 49_999);
+        assert_eq!(tx.data_inputs.len(), 2);
+        assert_eq!(tx.data_inputs[1].box_id, "tracking101");
+        assert_eq!(
+            token_amount(&tx.outputs[0], &ids.dexy_token).as_deref(),
+            Some("2049999")
+        );
+        assert_eq!(
+            token_amount(&tx.outputs[1], &ids.dexy_token).as_deref(),
+            Some("1")
+        );
+    });
+}
+
+#[test]
+fn test_keeper_build_rejects_unavailable_action() {
+    let ctx = create_keeper_context(220_000);
+    let ids = DexyIds::gold_mainnet();
+    match build_keeper_tx(&keeper_request(KeeperAction::Extract), &ctx, &ids) {
+        Err(TxError::BuildFailed { message }) => {
+            assert!(message.contains("not been triggered"), "{}", message)
+        }
+        other => panic!("Expected BuildFailed, got: {:?}", other.map(|r| r.summary)),
+    }
+}
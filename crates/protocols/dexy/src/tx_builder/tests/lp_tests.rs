@@ -473,6 +473,11 @@ fn test_lp_redeem_builds_correctly() {
         assert!(build.summary.erg_amount > 0);
         assert!(build.summary.dexy_amount > 0);
         assert_eq!(build.summary.lp_tokens, 1_000_000);
+
+        let intent = build.summary.intent(&crate::constants::DexyIds::gold_mainnet());
+        assert_eq!(intent.action, "Remove DexyGold liquidity");
+        assert_eq!(intent.assets_in[0].amount, 1_000_000);
+        assert_eq!(intent.assets_out.len(), 2);
             });
 }
 
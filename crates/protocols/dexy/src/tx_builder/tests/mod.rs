@@ -7,6 +7,8 @@ use super::*;
 use crate::constants::DexyVariant;
 use crate::state::DexyState;
 
+mod arb_mint_tests;
+mod keeper_tests;
 mod lp_tests;
 mod mint_tests;
 mod swap_tests;
@@ -1,7 +1,7 @@
 use citadel_core::{ProtocolError, TxError};
 
-use crate::constants::DexyVariant;
-use crate::fetch::DexyTxContext;
+use crate::constants::{DexyVariant, BANK_FEE_NUM, BUYBACK_FEE_NUM, FEE_DENOM};
+use crate::fetch::{DexyArbMintTxContext, DexyTxContext};
 use crate::state::DexyState;
 
 /// Max delay buffer for tx confirmation.
@@ -9,6 +9,12 @@ use crate::state::DexyState;
 /// The contract validates: successorR4 >= HEIGHT + T_free && successorR4 <= HEIGHT + T_free + T_buffer
 pub(crate) const T_BUFFER: i32 = 5;
 
+/// ArbMint period length, and how long the LP rate must have stayed above the
+/// threshold (tracking box R7) before ArbMint opens. Matches arbmint.es T_arb.
+pub(crate) const T_ARB: i32 = 30;
+/// validThreshold: lpRate * 100 > 101 * oracleRateWithFee
+const ARB_THRESHOLD_PERCENT: i64 = 101;
+
 pub fn validate_mint_dexy(amount: i64, state: &DexyState) -> Result<(), ProtocolError> {
     if amount <= 0 {
         return Err(ProtocolError::InvalidAmount {
@@ -110,3 +116,118 @@ pub fn validate_free_mint_preflight(
 
     Ok(())
 }
+
+/// Oracle rate per token including bank and buyback fees, as arbmint.es
+/// computes it: oracleRate * (feeNum + feeDenom) / feeDenom.
+pub(crate) fn arb_mint_rate_with_fee(oracle_rate_nano: i64, variant: DexyVariant) -> i64 {
+    let oracle_rate = oracle_rate_nano / variant.oracle_divisor();
+    oracle_rate * (BANK_FEE_NUM + BUYBACK_FEE_NUM + FEE_DENOM) / FEE_DENOM
+}
+
+/// Whether the ArbMint counter resets in this tx, and how much can be minted.
+///
+/// On reset the allowance is what brings the LP rate back down to the oracle
+/// rate with fees: (lpX - rate * lpY) / rate.
+pub(crate) fn arb_mint_allowance(
+    ctx: &DexyArbMintTxContext,
+    current_height: i32,
+    variant: DexyVariant,
+) -> (bool, i64) {
+    // Conservative counter reset, same as FreeMint
+    let is_counter_reset = current_height > ctx.arb_mint_r4_height - T_BUFFER;
+    if !is_counter_reset {
+        return (false, ctx.arb_mint_r5_available);
+    }
+    let rate = arb_mint_rate_with_fee(ctx.oracle_rate_nano, variant);
+    let max_allowed = if rate > 0 {
+        ((ctx.lp_erg_reserves - rate * ctx.lp_dexy_reserves) / rate).max(0)
+    } else {
+        0
+    };
+    (true, max_allowed)
+}
+
+/// Pre-flight validation matching arbmint.es contract conditions.
+pub fn validate_arb_mint_preflight(
+    ctx: &DexyArbMintTxContext,
+    amount: i64,
+    current_height: i32,
+    variant: DexyVariant,
+) -> Result<(), TxError> {
+    if amount <= 0 {
+        return Err(TxError::BuildFailed {
+            message: "Amount must be positive".to_string(),
+        });
+    }
+
+    let rate_with_fee = arb_mint_rate_with_fee(ctx.oracle_rate_nano, variant);
+    if rate_with_fee <= 0 {
+        return Err(TxError::BuildFailed {
+            message: format!(
+                "Invalid oracle rate: {} (raw: {} / divisor: {}). Oracle may be stale or unavailable.",
+                rate_with_fee, ctx.oracle_rate_nano, variant.oracle_divisor()
+            ),
+        });
+    }
+
+    if ctx.lp_dexy_reserves <= 0 {
+        return Err(TxError::BuildFailed {
+            message: "LP has no Dexy reserves".to_string(),
+        });
+    }
+    let lp_rate = ctx.lp_erg_reserves / ctx.lp_dexy_reserves;
+
+    // validThreshold: lpRate * 100 > 101 * oracleRateWithFee
+    if lp_rate * 100 <= ARB_THRESHOLD_PERCENT * rate_with_fee {
+        let lp_pct_of_oracle = (lp_rate as f64 / rate_with_fee as f64) * 100.0;
+        return Err(TxError::BuildFailed {
+            message: format!(
+                "ArbMint rate condition not met: LP rate ({} nanoERG/token, {:.2}% of oracle with fees) must be > {}% of {} nanoERG/token.",
+                lp_rate, lp_pct_of_oracle, ARB_THRESHOLD_PERCENT, rate_with_fee
+            ),
+        });
+    }
+
+    // validTracking: trackingHeight < HEIGHT - T_arb. HEIGHT only grows
+    // until inclusion, so checking against the current height is safe.
+    if ctx.tracking_height >= current_height - T_ARB {
+        let message = if ctx.tracking_height == i32::MAX {
+            "ArbMint not open: tracking box has not recorded the LP rate above 101% of oracle"
+                .to_string()
+        } else {
+            format!(
+                "ArbMint not open yet: LP rate must stay above threshold for {} blocks (tracking since height {}, now {})",
+                T_ARB, ctx.tracking_height, current_height
+            )
+        };
+        return Err(TxError::BuildFailed { message });
+    }
+
+    let (is_counter_reset, available_to_mint) = arb_mint_allowance(ctx, current_height, variant);
+    if amount > available_to_mint {
+        return Err(TxError::BuildFailed {
+            message: format!(
+                "Amount {} exceeds ArbMint limit {} for this period. \
+                {}",
+                amount,
+                available_to_mint,
+                if is_counter_reset {
+                    "Larger mints would push the LP rate below the oracle rate."
+                } else {
+                    "Wait for ArbMint counter to reset."
+                }
+            ),
+        });
+    }
+
+    if amount > ctx.dexy_in_bank {
+        return Err(TxError::BuildFailed {
+            message: format!(
+                "Amount {} exceeds bank reserves {}",
+                amount, ctx.dexy_in_bank
+            ),
+        });
+    }
+
+    Ok(())
+}
@@ -5,11 +5,11 @@ use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use dexy::constants::DexyVariant;
-use dexy::fetch::{DexyLpTxContext, DexySwapTxContext, DexyTxContext};
+use dexy::fetch::{DexyArbMintTxContext, DexyLpTxContext, DexySwapTxContext, DexyTxContext};
 use dexy::state::DexyState;
 use dexy::tx_builder::{
-    build_lp_deposit_tx, build_lp_redeem_tx, build_mint_dexy_tx, build_swap_dexy_tx,
-    LpDepositRequest, LpRedeemRequest, MintDexyRequest, SwapDexyRequest, SwapDirection,
+    build_arb_mint_dexy_tx, build_lp_deposit_tx, build_lp_redeem_tx, build_mint_dexy_tx,
+    build_swap_dexy_tx, LpDepositRequest, LpRedeemRequest, MintDexyRequest, SwapDexyRequest, SwapDirection,
 };
 use ergo_lib::ergotree_ir::chain::ergo_box::{
     box_value::BoxValue, ErgoBox, NonMandatoryRegisters,
@@ -32,6 +32,8 @@ const SWAP_NFT_ID: &str = "ff7b7eff3c818f9dc573ca03a723a7f6ed1615bf27980ebd4a6c9
 const BANK_NFT_ID: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
 const FREE_MINT_NFT_ID: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
 const BUYBACK_NFT_ID: &str = "dddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddddd";
+const ARB_MINT_NFT_ID: &str = "abababababababababababababababababababababababababababababababab";
+const TRACKING_NFT_ID: &str = "cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd";
 const INITIAL_LP: i64 = 100_000_000_000;
 const HEIGHT: i32 = 100_000;
 
@@ -190,6 +192,70 @@ fn mint_context() -> DexyTxContext {
     }
 }
 
+/// LP at 250_000 nanoERG/mg vs oracle-with-fees 221_100 (113%); tracking
+/// since height 99_000, well past T_arb.
+fn arb_mint_context(arb_mint_r4: i32, arb_mint_r5: i64) -> DexyArbMintTxContext {
+    let mint = mint_context();
+    let arb_mint_input = Eip12InputBox {
+        box_id: "9999999999999999999999999999999999999999999999999999999999999999".to_string(),
+        transaction_id: "0000000000000000000000000000000000000000000000000000000000000000"
+            .to_string(),
+        index: 0,
+        value: "1000000".to_string(),
+        ergo_tree: "arb_mint_ergo_tree".to_string(),
+        assets: vec![Eip12Asset::new(ARB_MINT_NFT_ID, 1)],
+        creation_height: HEIGHT,
+        additional_registers: HashMap::new(),
+        extension: HashMap::new(),
+    };
+    let mut lp_data_input = mint.lp_data_input;
+    lp_data_input.assets.push(Eip12Asset::new(DEXY_TOKEN_ID, 2_000_000));
+    let tracking_data_input = Eip12DataInputBox {
+        box_id: "7777777777777777777777777777777777777777777777777777777777777777".to_string(),
+        transaction_id: "8888888888888888888888888888888888888888888888888888888888888888"
+            .to_string(),
+        index: 0,
+        value: "1000000".to_string(),
+        ergo_tree: "tracking_ergo_tree".to_string(),
+        assets: vec![Eip12Asset::new(TRACKING_NFT_ID, 1)],
+        creation_height: HEIGHT,
+        additional_registers: HashMap::from([("R7".to_string(), "04f08a0c".to_string())]),
+    };
+    DexyArbMintTxContext {
+        arb_mint_input,
+        arb_mint_erg_nano: 1_000_000,
+        arb_mint_ergo_tree: "arb_mint_ergo_tree".to_string(),
+        arb_mint_r4_height: arb_mint_r4,
+        arb_mint_r5_available: arb_mint_r5,
+        bank_input: mint.bank_input,
+        bank_erg_nano: mint.bank_erg_nano,
+        dexy_in_bank: mint.dexy_in_bank,
+        bank_ergo_tree: mint.bank_ergo_tree,
+        buyback_input: mint.buyback_input,
+        buyback_erg_nano: mint.buyback_erg_nano,
+        buyback_ergo_tree: mint.buyback_ergo_tree,
+        oracle_data_input: mint.oracle_data_input,
+        oracle_rate_nano: mint.oracle_rate_nano,
+        lp_data_input,
+        lp_erg_reserves: 500_000_000_000,
+        lp_dexy_reserves: 2_000_000,
+        tracking_data_input,
+        tracking_height: 99_000,
+    }
+}
+
+fn arb_mint_request() -> MintDexyRequest {
+    MintDexyRequest {
+        variant: DexyVariant::Gold,
+        amount: 10,
+        user_address: "user_address".to_string(),
+        user_ergo_tree: "user_ergo_tree".to_string(),
+        user_inputs: vec![user_input(100_000_000_000, vec![])],
+        current_height: HEIGHT,
+        recipient_ergo_tree: None,
+    }
+}
+
 fn swap_context(lp_erg: i64, lp_dexy: i64) -> DexySwapTxContext {
     let dummy = dummy_ergo_box();
     DexySwapTxContext {
@@ -491,3 +557,32 @@ fn golden_build_lp_redeem_tx() {
         assert_eip12_field_eq(&result.unsigned_tx, &expected);
     });
 }
+
+#[test]
+fn golden_build_arb_mint_dexy_tx_counter_reset() {
+    no_citadel_fee(|| {
+        // R4 already passed: allowance = (5e11 - 221_100 * 2e6) / 221_100 = 261_420
+        let ctx = arb_mint_context(99_990, 0);
+        let result =
+            build_arb_mint_dexy_tx(&arb_mint_request(), &ctx, &mint_state()).expect("arb mint");
+        let expected = load_or_generate(
+            "build_arb_mint_dexy_tx_counter_reset.json",
+            &result.unsigned_tx,
+        );
+        assert_eip12_field_eq(&result.unsigned_tx, &expected);
+    });
+}
+
+#[test]
+fn golden_build_arb_mint_dexy_tx_within_period() {
+    no_citadel_fee(|| {
+        let ctx = arb_mint_context(100_100, 1_000);
+        let result =
+            build_arb_mint_dexy_tx(&arb_mint_request(), &ctx, &mint_state()).expect("arb mint");
+        let expected = load_or_generate(
+            "build_arb_mint_dexy_tx_within_period.json",
+            &result.unsigned_tx,
+        );
+        assert_eip12_field_eq(&result.unsigned_tx, &expected);
+    });
+}
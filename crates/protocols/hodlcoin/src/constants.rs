@@ -26,6 +26,8 @@ pub mod bank_tokens {
     pub const SINGLETON: usize = 0;
     /// hodlToken at index 1
     pub const HODL_TOKEN: usize = 1;
+    /// Reserve token at index 2 (token-backed banks only; ERG banks hold 2 tokens)
+    pub const RESERVE_TOKEN: usize = 2;
 }
 
 /// Bank register layout
@@ -34,7 +36,8 @@ pub mod bank_registers {
     pub const R4_TOTAL_SUPPLY: u8 = 4;
     /// R5 = Precision factor (Long)
     pub const R5_PRECISION: u8 = 5;
-    /// R6 = Minimum bank nanoERG value (Long)
+    /// R6 = Minimum bank reserve (Long): nanoERG, or reserve token units for
+    /// token-backed banks
     pub const R6_MIN_BANK_VALUE: u8 = 6;
     /// R7 = Dev fee numerator (Long)
     pub const R7_DEV_FEE: u8 = 7;
@@ -51,3 +54,11 @@ pub const DEV_FEE_CONTRACT_HASH: &str =
 /// This is the compiled `phoenix_v1_hodlcoin_fee.es` contract (with $minerFee = 1100000).
 /// blake2b256 of these bytes == DEV_FEE_CONTRACT_HASH.
 pub const DEV_FEE_CONTRACT_BYTES: &str = "101705000400040a053205c80108cd0329bd895314c80845841b988371bed38942748983eec1da61358b5fa848f8d1a3040208cd036cfe5ecd80b5ccc6b130aed8f526705b48f770e87f7c9bd6fb393fcdadb7ace4040408cd03fe709b7fb79ad097c234e42d2218ba6873239e5cb177b91e1524712ddc26e883040608cd03e8196967038a183915bd79c249385904a9264cf81183099d80254e6c0166d3a6040808cd02d3f408925bfaec210be688bd0893de168130370386be4bb48d2f5f08c51a098e0580ade204051e05c801051405c8010580897a0e20e540cceffd3b8dd0f401193576cc413467039695969427df94454193dddfb375040c0402d80fd601b0ada5d9010163c172017300d90101599a8c7201018c720102d602b2a5730100d603b2a5730200d604c17203d6059972017204d6069d9c730372057304d6077305d608b2a5730600d6097307d60ab2a5730800d60b7309d60cb2a5730a00d60d730bd60eb2a5730c00d60f730dea02d196830401927201730e96830501ed93c17202720693c27202d07207ed93c17208720693c27208d07209ed93c1720a720693c2720ad0720bed93c1720c9d9c730f7205731093c2720cd0720ded93c1720e9d9c73117205731293c2720ed0720f96830201927204731393cbc27203731493b1a5731598731683050872077209720b720d720f";
+
+/// Phoenix bank contracts discovery always scans, next to any
+/// `DiscoveryOptions::custom_bank_trees`. Add published deployments here.
+pub const KNOWN_BANK_ERGO_TREES: &[&str] = &[HODLERG_BANK_ERGO_TREE];
+
+/// Dev fee contracts a burn may pay into, next to any
+/// `DiscoveryOptions::custom_fee_trees`.
+pub const KNOWN_FEE_ERGO_TREES: &[&str] = &[DEV_FEE_CONTRACT_BYTES];
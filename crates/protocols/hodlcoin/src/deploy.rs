@@ -0,0 +1,482 @@
+//! Two-tx bank deployment: TX0 mints the hodlToken supply into a bootstrap
+//! box, TX1 spends it to mint the singleton NFT into the new bank box
+//! (R4–R8 = supply, precision, min bank value, dev fee, bank fee) and pays
+//! the creator the initial circulating hodlTokens.
+//!
+//! The bank contract divides by the circulating supply, so a bank cannot
+//! open empty: the creator buys `initial_circulating` hodlTokens with
+//! `initial_reserve`, which sets the opening price.
+
+use serde::{Deserialize, Serialize};
+
+use crate::calculator;
+use crate::constants::{self, FEE_DENOM, MIN_BOX_VALUE, MIN_CHANGE_VALUE, MIN_MINER_FEE};
+use crate::state::HodlError;
+use ergo_tx::sigma::{encode_sigma_coll_byte, encode_sigma_long};
+use ergo_tx::{
+    append_change_output, derive_output_boxes, select_erg_boxes, Eip12Asset, Eip12InputBox,
+    Eip12Output, Eip12UnsignedTx,
+};
+
+/// User-chosen parameters of a new hodlERG bank.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankDeployParams {
+    /// EIP-4 metadata of the hodlToken
+    pub hodl_token_name: String,
+    #[serde(default)]
+    pub hodl_token_description: String,
+    pub hodl_token_decimals: u8,
+    /// R4: hodlTokens ever minted (raw units)
+    pub total_token_supply: i64,
+    /// R5: price scaling factor
+    pub precision_factor: i64,
+    /// R6: nanoERG the bank can never be burned below
+    pub min_bank_value: i64,
+    /// R7: dev fee numerator (out of FEE_DENOM)
+    pub dev_fee_num: i64,
+    /// R8: bank fee numerator (out of FEE_DENOM)
+    pub bank_fee_num: i64,
+    /// nanoERG seeded into the bank
+    pub initial_reserve: i64,
+    /// hodlTokens paid to the creator for `initial_reserve`
+    pub initial_circulating: i64,
+}
+
+/// Validated deployment with the bank's opening state and total cost.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankDeployPlan {
+    pub params: BankDeployParams,
+    pub bank_ergo_tree: String,
+    pub hodl_tokens_in_bank: i64,
+    /// nanoERG per hodlToken at launch
+    pub opening_price_nano_per_hodl: f64,
+    pub total_fee_pct: f64,
+    pub bank_fee_pct: f64,
+    pub dev_fee_pct: f64,
+    /// Smallest burn (hodlTokens) whose dev fee fills a valid box at the
+    /// opening price; smaller burns cannot be built.
+    pub min_burn_hodl: i64,
+    /// Miner fees of both txs
+    pub miner_fee: i64,
+    /// Reserve, creator's hodlToken box and both miner fees
+    pub total_erg_cost: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankDeployLeg {
+    /// Deterministic txId of the unsigned tx
+    pub tx_id: String,
+    pub unsigned_tx: Eip12UnsignedTx,
+}
+
+/// Bootstrap and bank creation as one 0-conf chain: the create tx spends
+/// the bootstrap output before it is broadcast, so both are signed up front.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankDeployBuild {
+    pub bootstrap: BankDeployLeg,
+    pub create: BankDeployLeg,
+    pub singleton_token_id: String,
+    pub hodl_token_id: String,
+    pub plan: BankDeployPlan,
+}
+
+fn ceil_div(num: i128, den: i128) -> i128 {
+    (num + den - 1) / den
+}
+
+/// Validate `params` against the bank contract's rules and work out the
+/// opening state.
+pub fn plan_bank_deploy(params: &BankDeployParams) -> Result<BankDeployPlan, HodlError> {
+    let invalid = |msg: String| Err(HodlError::TxBuildError(msg));
+
+    if params.hodl_token_name.trim().is_empty() {
+        return invalid("hodlToken name is required".to_string());
+    }
+    if params.total_token_supply <= 0 || params.precision_factor <= 0 {
+        return invalid("Total supply and precision factor must be positive".to_string());
+    }
+    // The bank keeps at least one hodlToken: its layout needs the token at index 1
+    if params.initial_circulating <= 0 || params.initial_circulating >= params.total_token_supply {
+        return invalid(format!(
+            "Initial circulating supply must be between 1 and {}",
+            params.total_token_supply - 1
+        ));
+    }
+    // Burns may take the bank down to R6; it must still be a valid box there
+    if params.min_bank_value < MIN_BOX_VALUE as i64 {
+        return invalid(format!(
+            "Minimum bank value must be at least {} nanoERG",
+            MIN_BOX_VALUE
+        ));
+    }
+    if params.initial_reserve < params.min_bank_value {
+        return invalid("Initial reserve is below the minimum bank value".to_string());
+    }
+    if params.bank_fee_num < 0 {
+        return invalid("Bank fee cannot be negative".to_string());
+    }
+    // Every burn pays the dev fee contract at OUTPUTS(2); a zero fee box is invalid
+    if params.dev_fee_num <= 0 {
+        return invalid("Dev fee must be positive".to_string());
+    }
+    if params.bank_fee_num + params.dev_fee_num >= FEE_DENOM {
+        return invalid(format!(
+            "Bank and dev fees must add up to less than {}",
+            FEE_DENOM
+        ));
+    }
+
+    let bank_ergo_tree = constants::HODLERG_BANK_ERGO_TREE;
+
+    let price = calculator::hodl_price(
+        params.initial_reserve,
+        params.initial_circulating,
+        params.precision_factor,
+    );
+    if price <= 0 {
+        return invalid(
+            "Opening price rounds to 0; raise the reserve or the precision factor".to_string(),
+        );
+    }
+
+    // burn_amount floors twice: before_fees = hodl * price / precision, then
+    // dev_fee = before_fees * dev_fee_num / FEE_DENOM
+    let min_burn_hodl = {
+        let before_fees = ceil_div(
+            MIN_BOX_VALUE as i128 * FEE_DENOM as i128,
+            params.dev_fee_num as i128,
+        );
+        ceil_div(before_fees * params.precision_factor as i128, price as i128).min(i64::MAX as i128)
+            as i64
+    };
+
+    let total_erg_cost = params.initial_reserve + MIN_BOX_VALUE as i64 + 2 * MIN_MINER_FEE as i64;
+
+    Ok(BankDeployPlan {
+        params: params.clone(),
+        bank_ergo_tree: bank_ergo_tree.to_string(),
+        hodl_tokens_in_bank: params.total_token_supply - params.initial_circulating,
+        opening_price_nano_per_hodl: price as f64 / params.precision_factor as f64,
+        total_fee_pct: (params.bank_fee_num + params.dev_fee_num) as f64 / FEE_DENOM as f64 * 100.0,
+        bank_fee_pct: params.bank_fee_num as f64 / FEE_DENOM as f64 * 100.0,
+        dev_fee_pct: params.dev_fee_num as f64 / FEE_DENOM as f64 * 100.0,
+        min_burn_hodl,
+        miner_fee: 2 * MIN_MINER_FEE as i64,
+        total_erg_cost,
+    })
+}
+
+/// TX0. hodlToken ID = first input's box_id per Ergo minting rule; the
+/// bootstrap box (outputs[0]) carries the ERG for the bank, the creator's
+/// box and TX1's fee.
+pub fn build_deploy_bootstrap_eip12(
+    plan: &BankDeployPlan,
+    user_utxos: &[Eip12InputBox],
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<Eip12UnsignedTx, HodlError> {
+    let params = &plan.params;
+    let bootstrap_erg = params.initial_reserve as u64 + MIN_BOX_VALUE + MIN_MINER_FEE;
+    let user_erg_needed = bootstrap_erg + MIN_MINER_FEE;
+
+    let selected = select_erg_boxes(user_utxos, user_erg_needed)
+        .map_err(|e| HodlError::InsufficientFunds(e.to_string()))?;
+    let hodl_token_id = selected.boxes[0].box_id.clone();
+
+    // EIP-4 token metadata
+    let bootstrap_registers = ergo_tx::sigma_registers!(
+        "R4" => encode_sigma_coll_byte(params.hodl_token_name.trim().as_bytes()),
+        "R5" => encode_sigma_coll_byte(params.hodl_token_description.as_bytes()),
+        "R6" => encode_sigma_coll_byte(params.hodl_token_decimals.to_string().as_bytes()),
+    );
+
+    let bootstrap_output = Eip12Output {
+        value: bootstrap_erg.to_string(),
+        ergo_tree: user_ergo_tree.to_string(),
+        assets: vec![Eip12Asset::new(hodl_token_id, params.total_token_supply)],
+        creation_height: current_height,
+        additional_registers: bootstrap_registers,
+    };
+
+    let fee_output = Eip12Output::fee(MIN_MINER_FEE as i64, current_height);
+
+    let mut outputs = vec![bootstrap_output, fee_output];
+
+    append_change_output(
+        &mut outputs,
+        &selected,
+        user_erg_needed,
+        &[],
+        user_ergo_tree,
+        current_height,
+        MIN_CHANGE_VALUE,
+    )
+    .map_err(|e| HodlError::TxBuildError(e.to_string()))?;
+
+    Ok(Eip12UnsignedTx {
+        inputs: selected.boxes,
+        data_inputs: vec![],
+        outputs,
+    })
+}
+
+/// TX1. Singleton ID = bootstrap box_id per Ergo minting rule. The bank box
+/// is outputs[0], like every later bank box.
+pub fn build_deploy_bank_eip12(
+    plan: &BankDeployPlan,
+    bootstrap_box: &Eip12InputBox,
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<Eip12UnsignedTx, HodlError> {
+    let params = &plan.params;
+    let singleton_token_id = bootstrap_box.box_id.clone();
+
+    let hodl = bootstrap_box
+        .assets
+        .first()
+        .ok_or_else(|| HodlError::TxBuildError("Bootstrap box has no hodlTokens".to_string()))?;
+    if hodl.amount != params.total_token_supply.to_string() {
+        return Err(HodlError::TxBuildError(format!(
+            "Bootstrap box holds {} hodlTokens, expected {}",
+            hodl.amount, params.total_token_supply
+        )));
+    }
+
+    let bootstrap_erg: u64 = bootstrap_box
+        .value
+        .parse()
+        .map_err(|_| HodlError::TxBuildError("Invalid bootstrap box ERG value".to_string()))?;
+    if bootstrap_erg != params.initial_reserve as u64 + MIN_BOX_VALUE + MIN_MINER_FEE {
+        return Err(HodlError::TxBuildError(
+            "Bootstrap box ERG does not match the deployment".to_string(),
+        ));
+    }
+
+    let bank_registers = ergo_tx::sigma_registers!(
+        "R4" => encode_sigma_long(params.total_token_supply),
+        "R5" => encode_sigma_long(params.precision_factor),
+        "R6" => encode_sigma_long(params.min_bank_value),
+        "R7" => encode_sigma_long(params.dev_fee_num),
+        "R8" => encode_sigma_long(params.bank_fee_num),
+    );
+
+    let bank_output = Eip12Output {
+        value: params.initial_reserve.to_string(),
+        ergo_tree: plan.bank_ergo_tree.clone(),
+        assets: vec![
+            Eip12Asset::new(singleton_token_id, 1),
+            Eip12Asset::new(&hodl.token_id, plan.hodl_tokens_in_bank),
+        ],
+        creation_height: current_height,
+        additional_registers: bank_registers,
+    };
+
+    let user_output = Eip12Output::change(
+        MIN_BOX_VALUE as i64,
+        user_ergo_tree,
+        vec![Eip12Asset::new(&hodl.token_id, params.initial_circulating)],
+        current_height,
+    );
+
+    let fee_output = Eip12Output::fee(MIN_MINER_FEE as i64, current_height);
+
+    Ok(Eip12UnsignedTx {
+        inputs: vec![bootstrap_box.clone()],
+        data_inputs: vec![],
+        outputs: vec![bank_output, user_output, fee_output],
+    })
+}
+
+pub fn build_bank_deploy_chain(
+    params: &BankDeployParams,
+    user_utxos: &[Eip12InputBox],
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<BankDeployBuild, HodlError> {
+    let plan = plan_bank_deploy(params)?;
+
+    let bootstrap =
+        build_deploy_bootstrap_eip12(&plan, user_utxos, user_ergo_tree, current_height)?;
+    let (bootstrap_tx_id, outputs) = derive_output_boxes(&bootstrap)
+        .map_err(|e| HodlError::TxBuildError(format!("Chain derivation failed: {}", e)))?;
+    let bootstrap_box = outputs
+        .into_iter()
+        .next()
+        .ok_or_else(|| HodlError::TxBuildError("Bootstrap tx has no outputs".to_string()))?;
+
+    let create = build_deploy_bank_eip12(&plan, &bootstrap_box, user_ergo_tree, current_height)?;
+    let (create_tx_id, _) = derive_output_boxes(&create)
+        .map_err(|e| HodlError::TxBuildError(format!("Chain derivation failed: {}", e)))?;
+
+    Ok(BankDeployBuild {
+        singleton_token_id: bootstrap_box.box_id.clone(),
+        hodl_token_id: bootstrap.inputs[0].box_id.clone(),
+        bootstrap: BankDeployLeg {
+            tx_id: bootstrap_tx_id,
+            unsigned_tx: bootstrap,
+        },
+        create: BankDeployLeg {
+            tx_id: create_tx_id,
+            unsigned_tx: create,
+        },
+        plan,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::fetch::blake2b256;
+    use ergo_tx::sigma::decode_sigma_long;
+
+    const USER_ERGO_TREE: &str =
+        "0008cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const BOX_ID: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    fn make_utxo(erg: u64) -> Eip12InputBox {
+        Eip12InputBox {
+            box_id: BOX_ID.to_string(),
+            transaction_id: "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+                .to_string(),
+            index: 0,
+            value: erg.to_string(),
+            ergo_tree: USER_ERGO_TREE.to_string(),
+            assets: vec![],
+            creation_height: 999_000,
+            additional_registers: HashMap::new(),
+            extension: HashMap::new(),
+        }
+    }
+
+    fn params() -> BankDeployParams {
+        BankDeployParams {
+            hodl_token_name: "hodlTEST".to_string(),
+            hodl_token_description: "Test bank".to_string(),
+            hodl_token_decimals: 9,
+            total_token_supply: 97_739_924_000_000_000,
+            precision_factor: 1_000_000,
+            min_bank_value: 1_000_000,
+            dev_fee_num: 3,
+            bank_fee_num: 30,
+            initial_reserve: 1_000_000_000,
+            initial_circulating: 1_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_plan_opening_state() {
+        let plan = plan_bank_deploy(&params()).unwrap();
+        assert!((plan.opening_price_nano_per_hodl - 1.0).abs() < 1e-9);
+        assert_eq!(plan.hodl_tokens_in_bank, 97_739_923_000_000_000);
+        assert!((plan.total_fee_pct - 3.3).abs() < 1e-9);
+        // 1_000_000 nanoERG dev fee at 0.3% needs a burn worth ~333.3M nanoERG
+        assert_eq!(plan.min_burn_hodl, 333_333_334);
+        let p = params();
+        let dev_fee = |hodl| {
+            calculator::burn_amount(
+                p.initial_reserve,
+                p.initial_circulating,
+                p.precision_factor,
+                hodl,
+                p.bank_fee_num,
+                p.dev_fee_num,
+            )
+            .dev_fee
+        };
+        assert!(dev_fee(plan.min_burn_hodl) >= MIN_BOX_VALUE as i64);
+        assert!(dev_fee(plan.min_burn_hodl - 1) < MIN_BOX_VALUE as i64);
+        assert_eq!(
+            plan.total_erg_cost,
+            1_000_000_000 + MIN_BOX_VALUE as i64 + 2 * MIN_MINER_FEE as i64
+        );
+    }
+
+    #[test]
+    fn test_plan_rejects_invalid_params() {
+        let cases: [fn(&mut BankDeployParams); 7] = [
+            |p| p.dev_fee_num = 0,
+            |p| p.bank_fee_num = FEE_DENOM - p.dev_fee_num,
+            |p| p.initial_reserve = p.min_bank_value - 1,
+            |p| p.min_bank_value = MIN_BOX_VALUE as i64 - 1,
+            |p| p.initial_circulating = p.total_token_supply,
+            |p| p.initial_circulating = 0,
+            |p| p.hodl_token_name = " ".to_string(),
+        ];
+        for mutate in cases {
+            let mut p = params();
+            mutate(&mut p);
+            assert!(plan_bank_deploy(&p).is_err(), "{:?}", p);
+        }
+    }
+
+    /// Burns on a deployed bank pay the dev fee into `DEV_FEE_CONTRACT_BYTES`,
+    /// so the deployed contract must pin exactly that script.
+    #[test]
+    fn test_deployed_bank_pins_dev_fee_contract() {
+        let fee_bytes = hex::decode(constants::DEV_FEE_CONTRACT_BYTES).unwrap();
+        assert_eq!(
+            hex::encode(blake2b256(&fee_bytes)),
+            constants::DEV_FEE_CONTRACT_HASH
+        );
+        assert!(crate::fetch::embeds_hash_of(
+            constants::HODLERG_BANK_ERGO_TREE,
+            constants::DEV_FEE_CONTRACT_BYTES
+        ));
+        assert!(!crate::fetch::embeds_hash_of(
+            USER_ERGO_TREE,
+            constants::DEV_FEE_CONTRACT_BYTES
+        ));
+    }
+
+    #[test]
+    fn test_deploy_chain() {
+        let build = build_bank_deploy_chain(
+            &params(),
+            &[make_utxo(5_000_000_000)],
+            USER_ERGO_TREE,
+            1_000,
+        )
+        .unwrap();
+
+        // hodlToken minted from the first input, full supply into the bootstrap box
+        assert_eq!(build.hodl_token_id, BOX_ID);
+        let bootstrap_out = &build.bootstrap.unsigned_tx.outputs[0];
+        assert_eq!(bootstrap_out.assets[0].token_id, BOX_ID);
+        assert_eq!(bootstrap_out.assets[0].amount, "97739924000000000");
+
+        let create = &build.create.unsigned_tx;
+        assert_eq!(create.inputs[0].box_id, build.singleton_token_id);
+        assert_eq!(create.inputs[0].transaction_id, build.bootstrap.tx_id);
+
+        let bank = &create.outputs[0];
+        assert_eq!(bank.ergo_tree, constants::HODLERG_BANK_ERGO_TREE);
+        assert_eq!(bank.value, "1000000000");
+        assert_eq!(bank.assets[0].token_id, build.singleton_token_id);
+        assert_eq!(bank.assets[0].amount, "1");
+        assert_eq!(bank.assets[1].token_id, BOX_ID);
+        assert_eq!(bank.assets[1].amount, "97739923000000000");
+        let reg = |r: &str| decode_sigma_long(&bank.additional_registers[r]).unwrap();
+        assert_eq!(reg("R4"), 97_739_924_000_000_000);
+        assert_eq!(reg("R5"), 1_000_000);
+        assert_eq!(reg("R6"), 1_000_000);
+        assert_eq!(reg("R7"), 3);
+        assert_eq!(reg("R8"), 30);
+
+        let creator = &create.outputs[1];
+        assert_eq!(creator.assets[0].amount, "1000000000");
+
+        // TX1 spends exactly the bootstrap box
+        let out_total: u64 = create
+            .outputs
+            .iter()
+            .map(|o| o.value.parse::<u64>().unwrap())
+            .sum();
+        assert_eq!(out_total, create.inputs[0].value.parse::<u64>().unwrap());
+    }
+}
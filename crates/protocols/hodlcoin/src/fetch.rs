@@ -1,9 +1,44 @@
+use blake2::digest::{consts::U32, Digest};
+use blake2::Blake2b;
 use ergo_lib::ergotree_ir::chain::ergo_box::{ErgoBox, NonMandatoryRegisterId};
+use ergo_lib::ergotree_ir::ergo_tree::ErgoTree;
+use ergo_lib::ergotree_ir::serialization::SigmaSerializable;
 use ergo_tx::ergo_box_utils::read_register_long;
 
 use crate::calculator;
 use crate::constants::{self, FEE_DENOM};
-use crate::state::{HodlBankState, HodlError};
+use crate::state::{DiscoveryOptions, HodlBankState, HodlError};
+
+const TEMPLATE_PAGE_SIZE: u64 = 100;
+const MAX_TEMPLATE_PAGES: u64 = 10;
+
+pub(crate) fn blake2b256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Blake2b::<U32>::new();
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+/// Whether `ergo_tree_hex` embeds `blake2b256(contract_hex)` as whole bytes,
+/// the way a contract pins the script of an output it pays into.
+pub(crate) fn embeds_hash_of(ergo_tree_hex: &str, contract_hex: &str) -> bool {
+    let (Ok(tree), Ok(contract)) = (hex::decode(ergo_tree_hex), hex::decode(contract_hex)) else {
+        return false;
+    };
+    let hash = blake2b256(&contract);
+    tree.windows(hash.len()).any(|window| window == hash)
+}
+
+/// blake2b256 of an ErgoTree's template (the tree with its constants taken
+/// out), as indexed by the node. Deployments of one contract with different
+/// constants share it. `None` if `ergo_tree_hex` is not a valid ErgoTree.
+pub fn template_hash(ergo_tree_hex: &str) -> Option<String> {
+    let bytes = hex::decode(ergo_tree_hex).ok()?;
+    let template = ErgoTree::sigma_parse_bytes(&bytes)
+        .ok()?
+        .template_bytes()
+        .ok()?;
+    Some(hex::encode(blake2b256(&template)))
+}
 
 pub fn parse_bank_box(ergo_box: &ErgoBox) -> Result<HodlBankState, HodlError> {
     let tokens = ergo_box
@@ -38,7 +73,11 @@ pub fn parse_bank_box(ergo_box: &ErgoBox) -> Result<HodlBankState, HodlError> {
     let hodl_token_id = hex::encode(hodl_token.token_id.as_ref());
     let hodl_tokens_in_bank = u64::from(hodl_token.amount) as i64;
 
+    // Token-backed banks carry their reserve as a third token
+    let reserve_token = tokens.get(constants::bank_tokens::RESERVE_TOKEN);
+    let reserve_token_id = reserve_token.map(|t| hex::encode(t.token_id.as_ref()));
     let reserve_nano_erg = u64::from(ergo_box.value) as i64;
+    let reserve_amount = reserve_token.map_or(reserve_nano_erg, |t| u64::from(t.amount) as i64);
     let bank_box_id = hex::encode(ergo_box.box_id().as_ref());
 
     let total_token_supply = read_register_long(ergo_box, NonMandatoryRegisterId::R4)
@@ -59,7 +98,7 @@ pub fn parse_bank_box(ergo_box: &ErgoBox) -> Result<HodlBankState, HodlError> {
     let circulating_supply = total_token_supply - hodl_tokens_in_bank;
 
     let price_nano_per_hodl = if circulating_supply > 0 {
-        let price = calculator::hodl_price(reserve_nano_erg, circulating_supply, precision_factor);
+        let price = calculator::hodl_price(reserve_amount, circulating_supply, precision_factor);
         price as f64 / precision_factor as f64
     } else {
         0.0
@@ -74,6 +113,9 @@ pub fn parse_bank_box(ergo_box: &ErgoBox) -> Result<HodlBankState, HodlError> {
         singleton_token_id,
         hodl_token_id,
         hodl_token_name: None, // resolved later
+        reserve_token_id,
+        reserve_token_name: None,
+        reserve_amount,
         total_token_supply,
         precision_factor,
         min_bank_value,
@@ -90,24 +132,77 @@ pub fn parse_bank_box(ergo_box: &ErgoBox) -> Result<HodlBankState, HodlError> {
     })
 }
 
+/// Banks of the known Phoenix contracts above the display threshold.
 pub async fn discover_banks(
     node: &ergo_node_client::NodeClient,
 ) -> Result<Vec<HodlBankState>, HodlError> {
-    let boxes = node
-        .unspent_boxes_by_ergo_tree(constants::HODLERG_BANK_ERGO_TREE, 0, 100)
-        .await
-        .map_err(|e| HodlError::NodeError(e.to_string()))?;
-
-    let mut banks = Vec::new();
-    for ergo_box in &boxes {
-        match parse_bank_box(ergo_box) {
-            Ok(bank) => banks.push(bank),
-            Err(e) => {
-                tracing::warn!("Failed to parse HodlCoin bank: {}", e);
+    discover_banks_with(node, &DiscoveryOptions::default()).await
+}
+
+/// Banks of every known Phoenix contract template plus those in `options`,
+/// ERG- or token-backed, largest TVL first.
+pub async fn discover_banks_with(
+    node: &ergo_node_client::NodeClient,
+    options: &DiscoveryOptions,
+) -> Result<Vec<HodlBankState>, HodlError> {
+    let mut hashes: Vec<String> = Vec::new();
+    let trees = constants::KNOWN_BANK_ERGO_TREES
+        .iter()
+        .copied()
+        .chain(options.custom_bank_trees.iter().map(String::as_str));
+    for tree in trees {
+        match template_hash(tree) {
+            Some(hash) if !hashes.contains(&hash) => hashes.push(hash),
+            Some(_) => {}
+            None => tracing::warn!(
+                "Skipping unparseable HodlCoin bank tree {}",
+                &tree[..tree.len().min(16)]
+            ),
+        }
+    }
+
+    let mut banks: Vec<HodlBankState> = Vec::new();
+    for hash in &hashes {
+        let mut page = 0;
+        loop {
+            let boxes = node
+                .unspent_boxes_by_template_hash(hash, page * TEMPLATE_PAGE_SIZE, TEMPLATE_PAGE_SIZE)
+                .await
+                .map_err(|e| HodlError::NodeError(e.to_string()))?;
+            let len = boxes.len() as u64;
+
+            for ergo_box in &boxes {
+                match parse_bank_box(ergo_box) {
+                    Ok(bank) => {
+                        // Pages can overlap when a bank moves between requests
+                        if !banks
+                            .iter()
+                            .any(|b| b.singleton_token_id == bank.singleton_token_id)
+                        {
+                            banks.push(bank);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to parse HodlCoin bank: {}", e);
+                    }
+                }
+            }
+
+            page += 1;
+            if len < TEMPLATE_PAGE_SIZE {
+                break;
+            }
+            if page >= MAX_TEMPLATE_PAGES {
+                tracing::warn!("HodlCoin bank scan page cap reached; some banks may be missing");
+                break;
             }
         }
     }
 
+    if !options.include_low_tvl {
+        banks.retain(|b| !b.is_low_tvl());
+    }
+
     for bank in &mut banks {
         match node.get_token_info(&bank.hodl_token_id).await {
             Ok(info) => {
@@ -121,10 +216,18 @@ pub async fn discover_banks(
                 );
             }
         }
+        if let Some(reserve_token_id) = &bank.reserve_token_id {
+            match node.get_token_info(reserve_token_id).await {
+                Ok(info) => bank.reserve_token_name = info.name,
+                Err(e) => tracing::warn!(
+                    "Failed to resolve reserve token name for {}: {}",
+                    &reserve_token_id[..8],
+                    e
+                ),
+            }
+        }
     }
 
-    banks.retain(|b| b.tvl_nano_erg >= constants::MIN_DISPLAY_TVL);
-
     banks.sort_by(|a, b| b.tvl_nano_erg.cmp(&a.tvl_nano_erg));
 
     tracing::info!("Discovered {} HodlCoin banks", banks.len());
@@ -359,4 +462,58 @@ mod tests {
         assert_eq!(state.circulating_supply, 0);
         assert_eq!(state.price_nano_per_hodl, 0.0);
     }
+
+    #[test]
+    fn parse_token_backed_bank_box() {
+        let token = |id: &str, amount: u64| Token {
+            token_id: make_token_id(id),
+            amount: TokenAmount::try_from(amount).unwrap(),
+        };
+        let tokens = BoxTokens::from_vec(vec![
+            token(&"a".repeat(64), 1),
+            token(&"b".repeat(64), 900_000_000),
+            token(&"c".repeat(64), 50_000_000),
+        ])
+        .unwrap();
+        let regs = vec![
+            long_constant(1_000_000_000),
+            long_constant(1_000_000),
+            long_constant(1_000),
+            long_constant(30),
+            long_constant(20),
+        ];
+        let ergo_box = ErgoBox::new(
+            BoxValue::new(1_000_000).unwrap(),
+            test_ergo_tree(),
+            Some(tokens),
+            NonMandatoryRegisters::try_from(regs).unwrap(),
+            100_000,
+            TxId::zero(),
+            0,
+        )
+        .unwrap();
+
+        let mut state = parse_bank_box(&ergo_box).unwrap();
+        assert!(state.is_token_backed());
+        assert_eq!(
+            state.reserve_token_id.as_deref(),
+            Some("c".repeat(64).as_str())
+        );
+        assert_eq!(state.reserve_amount, 50_000_000);
+        assert_eq!(state.reserve_nano_erg, 1_000_000);
+        // 50M reserve units over 100M circulating hodlTokens
+        assert!((state.price_nano_per_hodl - 0.5).abs() < 1e-9);
+        // Judged by its token reserve, not the box's dust ERG
+        assert!(!state.is_low_tvl());
+
+        state.reserve_amount = state.min_bank_value;
+        assert!(state.is_low_tvl());
+    }
+
+    #[test]
+    fn template_hash_of_builtin_bank() {
+        let hash = template_hash(constants::HODLERG_BANK_ERGO_TREE).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert!(template_hash("zz").is_none());
+    }
 }
@@ -0,0 +1,279 @@
+//! Bank price history: reserve, circulating supply and hodlToken price over
+//! time.
+//!
+//! Every mint and burn spends the bank box and recreates it, so walking the
+//! singleton NFT's box chain recovers the bank state at every transition.
+//! The price only moves on burns (the bank fee stays in the reserve), which
+//! makes the series the holder's yield curve.
+
+use std::collections::HashSet;
+
+use ergo_node_client::NodeClient;
+use ergo_tx::decode_sigma_long;
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::hodl_price;
+use crate::constants::bank_tokens;
+use crate::state::HodlError;
+
+/// Bank state as of one box in the singleton NFT chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankPricePoint {
+    pub box_id: String,
+    /// Transaction that created this box.
+    pub tx_id: String,
+    pub height: u64,
+    /// Block timestamp in milliseconds.
+    pub timestamp: u64,
+    /// nanoERG, or raw reserve token units for token-backed banks.
+    pub reserve_amount: i64,
+    pub circulating_supply: i64,
+    /// Reserve units per hodlToken.
+    pub price_nano_per_hodl: f64,
+}
+
+impl BankPricePoint {
+    /// Bank state from a node box JSON (`/blockchain/box/byId`).
+    pub fn from_box_json(box_json: &serde_json::Value, timestamp: u64) -> Option<Self> {
+        let assets = box_json["assets"].as_array()?;
+        let hodl_in_bank = assets.get(bank_tokens::HODL_TOKEN)?["amount"].as_i64()?;
+        let reserve_amount = match assets.get(bank_tokens::RESERVE_TOKEN) {
+            Some(reserve) => reserve["amount"].as_i64()?,
+            None => box_json["value"].as_i64()?,
+        };
+
+        let registers = &box_json["additionalRegisters"];
+        let total_supply = decode_sigma_long(register_hex(&registers["R4"])?).ok()?;
+        let precision = decode_sigma_long(register_hex(&registers["R5"])?).ok()?;
+        let circulating_supply = total_supply - hodl_in_bank;
+        let price_nano_per_hodl = if circulating_supply > 0 && precision > 0 {
+            hodl_price(reserve_amount, circulating_supply, precision) as f64 / precision as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            box_id: box_json["boxId"].as_str()?.to_string(),
+            tx_id: box_json["transactionId"].as_str()?.to_string(),
+            height: box_json["inclusionHeight"]
+                .as_u64()
+                .or_else(|| box_json["settlementHeight"].as_u64())
+                .or_else(|| box_json["creationHeight"].as_u64())
+                .unwrap_or(0),
+            timestamp,
+            reserve_amount,
+            circulating_supply,
+            price_nano_per_hodl,
+        })
+    }
+}
+
+/// Price, reserve and supply series of one bank over the traced history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BankPriceHistory {
+    pub singleton_token_id: String,
+    /// Blocks per sample; 0 keeps every bank transition.
+    pub sample_blocks: u64,
+    /// Oldest height covered by the traced history.
+    pub history_start_height: u64,
+    /// Bank transitions traced before sampling.
+    pub transitions: usize,
+    /// Oldest first.
+    pub points: Vec<BankPricePoint>,
+}
+
+/// Registers come either as plain serialized hex or as
+/// `{ "serializedValue": ... }` depending on the node endpoint.
+fn register_hex(value: &serde_json::Value) -> Option<&str> {
+    value.as_str().or_else(|| value["serializedValue"].as_str())
+}
+
+/// Whether the box carries the bank singleton at its expected index.
+fn holds_singleton(box_json: &serde_json::Value, singleton_token_id: &str) -> bool {
+    box_json["assets"][bank_tokens::SINGLETON]["tokenId"].as_str() == Some(singleton_token_id)
+}
+
+/// Reduce `points` (oldest first) to the state at the end of each
+/// `sample_blocks`-block window, i.e. the last transition in every window.
+/// Windows without a transition are skipped: the previous point still holds.
+pub fn sample_history(points: &[BankPricePoint], sample_blocks: u64) -> Vec<BankPricePoint> {
+    if sample_blocks == 0 {
+        return points.to_vec();
+    }
+    let mut sampled: Vec<BankPricePoint> = Vec::new();
+    for point in points {
+        let window = point.height / sample_blocks;
+        match sampled.last_mut() {
+            Some(last) if last.height / sample_blocks == window => *last = point.clone(),
+            _ => sampled.push(point.clone()),
+        }
+    }
+    sampled
+}
+
+/// Sample `points` (oldest first) into chartable series.
+pub fn price_history(
+    singleton_token_id: &str,
+    points: &[BankPricePoint],
+    sample_blocks: u64,
+) -> BankPriceHistory {
+    BankPriceHistory {
+        singleton_token_id: singleton_token_id.to_string(),
+        sample_blocks,
+        history_start_height: points.first().map(|p| p.height).unwrap_or(0),
+        transitions: points.len(),
+        points: sample_history(points, sample_blocks),
+    }
+}
+
+/// Walk the singleton NFT's box chain backwards from `start_box_id`, newest
+/// first. Stops after `max_steps` boxes, at the first box in `known_box_ids`
+/// (already cached), once a box is below `min_height`, at a box that cannot
+/// be read, or at the bank creation (no input holds the singleton).
+///
+/// Also returns whether the walk ended on a known box, i.e. whether the
+/// points join up with the cached ones.
+pub async fn trace_bank_history(
+    node: &NodeClient,
+    singleton_token_id: &str,
+    start_box_id: &str,
+    known_box_ids: &HashSet<String>,
+    max_steps: usize,
+    min_height: u64,
+) -> Result<(Vec<BankPricePoint>, bool), HodlError> {
+    let node_err = |e: citadel_core::NodeError| HodlError::NodeError(e.to_string());
+    let mut points = Vec::new();
+    let mut current_box_id = start_box_id.to_string();
+
+    for _ in 0..max_steps {
+        if known_box_ids.contains(&current_box_id) {
+            break;
+        }
+        let current_box = node
+            .get_blockchain_box_by_id(&current_box_id)
+            .await
+            .map_err(node_err)?;
+        let Some(tx_id) = current_box["transactionId"].as_str() else {
+            break;
+        };
+        let tx = node.get_transaction_by_id(tx_id).await.map_err(node_err)?;
+        let timestamp = tx["timestamp"].as_u64().unwrap_or(0);
+
+        let Some(point) = BankPricePoint::from_box_json(&current_box, timestamp) else {
+            break;
+        };
+        let height = point.height;
+        points.push(point);
+        if height < min_height {
+            break;
+        }
+
+        let mut prev_box_id = None;
+        for input in tx["inputs"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+            let Some(input_id) = input["boxId"].as_str() else {
+                continue;
+            };
+            let input_box = match input["assets"].is_array() {
+                true => input.clone(),
+                false => match node.get_blockchain_box_by_id(input_id).await {
+                    Ok(b) => b,
+                    Err(_) => continue,
+                },
+            };
+            if holds_singleton(&input_box, singleton_token_id) {
+                prev_box_id = Some(input_id.to_string());
+                break;
+            }
+        }
+        match prev_box_id {
+            Some(id) => current_box_id = id,
+            None => break,
+        }
+    }
+
+    let reached_known = known_box_ids.contains(&current_box_id);
+    Ok((points, reached_known))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ergo_tx::encode_sigma_long;
+
+    fn point(height: u64, circulating: i64) -> BankPricePoint {
+        BankPricePoint {
+            box_id: format!("box{}", height),
+            tx_id: format!("tx{}", height),
+            height,
+            timestamp: height * 120_000,
+            reserve_amount: 1_000_000_000_000,
+            circulating_supply: circulating,
+            price_nano_per_hodl: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_point_from_box_json() {
+        let box_json = serde_json::json!({
+            "boxId": "bank1",
+            "transactionId": "tx1",
+            "value": 200_000_000_000i64,
+            "inclusionHeight": 1_300_000,
+            "assets": [
+                { "tokenId": "singleton", "amount": 1 },
+                { "tokenId": "hodl", "amount": 900_000_000_000i64 },
+            ],
+            "additionalRegisters": {
+                "R4": encode_sigma_long(1_000_000_000_000),
+                "R5": { "serializedValue": encode_sigma_long(1_000_000) },
+            },
+        });
+
+        // 200 ERG backing 100e9 circulating: 2 nanoERG per raw hodlToken
+        let point = BankPricePoint::from_box_json(&box_json, 7).unwrap();
+        assert_eq!(point.height, 1_300_000);
+        assert_eq!(point.reserve_amount, 200_000_000_000);
+        assert_eq!(point.circulating_supply, 100_000_000_000);
+        assert!((point.price_nano_per_hodl - 2.0).abs() < 1e-9);
+        assert!(holds_singleton(&box_json, "singleton"));
+    }
+
+    #[test]
+    fn test_point_from_token_backed_box() {
+        let box_json = serde_json::json!({
+            "boxId": "bank1",
+            "transactionId": "tx1",
+            "value": 1_000_000i64,
+            "assets": [
+                { "tokenId": "singleton", "amount": 1 },
+                { "tokenId": "hodl", "amount": 600 },
+                { "tokenId": "reserve", "amount": 1_200 },
+            ],
+            "additionalRegisters": {
+                "R4": encode_sigma_long(1_000),
+                "R5": encode_sigma_long(1_000),
+            },
+        });
+
+        let point = BankPricePoint::from_box_json(&box_json, 0).unwrap();
+        assert_eq!(point.reserve_amount, 1_200);
+        assert_eq!(point.circulating_supply, 400);
+        assert!((point.price_nano_per_hodl - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sample_keeps_last_per_window() {
+        let points = vec![point(100, 1), point(150, 2), point(199, 3), point(420, 4)];
+
+        let sampled = sample_history(&points, 100);
+        let heights: Vec<u64> = sampled.iter().map(|p| p.height).collect();
+        assert_eq!(heights, vec![199, 420]);
+        assert_eq!(sampled[0].circulating_supply, 3);
+
+        let history = price_history("singleton", &points, 100);
+        assert_eq!(history.transitions, 4);
+        assert_eq!(history.history_start_height, 100);
+    }
+}
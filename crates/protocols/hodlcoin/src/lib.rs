@@ -6,13 +6,25 @@
 
 pub mod calculator;
 pub mod constants;
+pub mod deploy;
 pub mod fetch;
+pub mod history;
+pub mod position;
 pub mod state;
 pub mod tx_builder;
 
 // Re-exports
 pub use calculator::{burn_amount, hodl_price, mint_amount, BurnResult};
 pub use constants::HODLERG_BANK_ERGO_TREE;
-pub use fetch::{discover_banks, parse_bank_box};
-pub use state::{HodlBankState, HodlBurnPreview, HodlError, HodlMintPreview};
-pub use tx_builder::{build_burn_tx_eip12, build_mint_tx_eip12};
+pub use deploy::{
+    build_bank_deploy_chain, plan_bank_deploy, BankDeployBuild, BankDeployLeg, BankDeployParams,
+    BankDeployPlan,
+};
+pub use fetch::{discover_banks, discover_banks_with, parse_bank_box, template_hash};
+pub use history::{price_history, trace_bank_history, BankPriceHistory, BankPricePoint};
+pub use position::{holder_position, trade_from_tx, HodlPosition, HodlTrade, HodlTradeKind};
+pub use state::{DiscoveryOptions, HodlBankState, HodlBurnPreview, HodlError, HodlMintPreview};
+pub use tx_builder::{
+    build_burn_tx_eip12, build_mint_tx_eip12, build_token_burn_tx_eip12, build_token_mint_tx_eip12,
+    resolve_dev_fee_tree,
+};
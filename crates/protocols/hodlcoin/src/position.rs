@@ -0,0 +1,372 @@
+//! Holder positions: average mint price, burn value and gains.
+//!
+//! Trades come from the holder's own history: a mint or burn spends and
+//! recreates the bank box, so the bank box delta is the trade. Burn proceeds
+//! are recomputed from the spent bank box with the contract's fee math, as
+//! the holder's outputs also carry change.
+//!
+//! Gains use the average cost method: a burn releases the average mint cost
+//! of the tokens it returns.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::burn_amount;
+use crate::constants::bank_tokens;
+use crate::state::HodlBankState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HodlTradeKind {
+    Mint,
+    Burn,
+}
+
+/// A holder transaction against one bank.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HodlTrade {
+    pub tx_id: String,
+    pub height: u64,
+    pub timestamp: u64,
+    pub kind: HodlTradeKind,
+    /// hodlTokens minted or burned by the holder.
+    pub hodl_amount: i64,
+    /// Reserve deposited (mint) or received after fees (burn).
+    pub reserve_amount: i64,
+}
+
+/// One holder's position in one bank.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HodlPosition {
+    pub singleton_token_id: String,
+    pub hodl_token_id: String,
+    pub hodl_token_name: Option<String>,
+    /// Unit of the reserve amounts; `None` for ERG.
+    pub reserve_token_id: Option<String>,
+    /// hodlTokens held now.
+    pub held: i64,
+    /// Totals over the tracked trades.
+    pub minted: i64,
+    pub mint_cost: i64,
+    pub burned: i64,
+    pub burn_proceeds: i64,
+    /// Reserve units per hodlToken paid on average over all tracked mints.
+    pub avg_mint_price: Option<f64>,
+    pub current_price: f64,
+    /// Reserve received for burning the whole holding now, after fees.
+    pub burn_value: i64,
+    /// Mint cost still attributed to the tracked part of the holding.
+    pub cost_basis: i64,
+    /// Burn proceeds minus the average cost of the tokens burned.
+    pub realized_gain: i64,
+    /// Burn value of the tracked holding minus its cost basis.
+    pub unrealized_gain: Option<i64>,
+    pub unrealized_gain_pct: Option<f64>,
+    /// hodlTokens held but not matched to a mint (transfers, history beyond
+    /// the scanned range). Gains cover the tracked part only.
+    pub untracked: i64,
+    /// Oldest first.
+    pub trades: Vec<HodlTrade>,
+}
+
+/// (reserve, hodlTokens in bank) of a box holding the bank singleton.
+fn bank_box_amounts(box_json: &serde_json::Value, bank: &HodlBankState) -> Option<(i64, i64)> {
+    let assets = box_json["assets"].as_array()?;
+    if assets.get(bank_tokens::SINGLETON)?["tokenId"].as_str()? != bank.singleton_token_id {
+        return None;
+    }
+    let hodl_in_bank = assets.get(bank_tokens::HODL_TOKEN)?["amount"].as_i64()?;
+    let reserve = match &bank.reserve_token_id {
+        Some(reserve_token_id) => {
+            let reserve = assets.get(bank_tokens::RESERVE_TOKEN)?;
+            if reserve["tokenId"].as_str()? != reserve_token_id {
+                return None;
+            }
+            reserve["amount"].as_i64()?
+        }
+        None => box_json["value"].as_i64()?,
+    };
+    Some((reserve, hodl_in_bank))
+}
+
+/// `v * part / whole` without intermediate overflow.
+fn scale(v: i64, part: i64, whole: i64) -> i64 {
+    (v as i128 * part as i128 / whole as i128) as i64
+}
+
+/// Net hodlTokens `tx` moved into `addresses`.
+fn net_hodl_received(
+    tx: &serde_json::Value,
+    hodl_token_id: &str,
+    addresses: &HashSet<&str>,
+) -> i64 {
+    let sum = |boxes: &serde_json::Value| -> i64 {
+        boxes
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+            .iter()
+            .filter(|b| addresses.contains(b["address"].as_str().unwrap_or("")))
+            .flat_map(|b| b["assets"].as_array().cloned().unwrap_or_default())
+            .filter(|a| a["tokenId"].as_str() == Some(hodl_token_id))
+            .map(|a| a["amount"].as_i64().unwrap_or(0))
+            .sum()
+    };
+    sum(&tx["outputs"]) - sum(&tx["inputs"])
+}
+
+/// The mint or burn `tx` made against `bank` for `addresses`, if it is one.
+/// Bank parameters (R4–R8) are fixed for the bank's lifetime, so the current
+/// state supplies them. When the holder got only part of the bank delta, the
+/// reserve side is scaled to their share.
+pub fn trade_from_tx(
+    tx: &serde_json::Value,
+    bank: &HodlBankState,
+    addresses: &HashSet<&str>,
+) -> Option<HodlTrade> {
+    let find_bank_box = |boxes: &serde_json::Value| {
+        boxes
+            .as_array()?
+            .iter()
+            .find_map(|b| bank_box_amounts(b, bank))
+    };
+    let (reserve_before, hodl_before) = find_bank_box(&tx["inputs"])?;
+    let (reserve_after, hodl_after) = find_bank_box(&tx["outputs"])?;
+    let received = net_hodl_received(tx, &bank.hodl_token_id, addresses);
+
+    let (kind, hodl_amount, reserve_amount) = if hodl_after < hodl_before && received > 0 {
+        let bank_minted = hodl_before - hodl_after;
+        let hodl = received.min(bank_minted);
+        let deposit = reserve_after - reserve_before;
+        (HodlTradeKind::Mint, hodl, scale(deposit, hodl, bank_minted))
+    } else if hodl_after > hodl_before && received < 0 {
+        let bank_burned = hodl_after - hodl_before;
+        let hodl = (-received).min(bank_burned);
+        let proceeds = burn_amount(
+            reserve_before,
+            bank.total_token_supply - hodl_before,
+            bank.precision_factor,
+            bank_burned,
+            bank.bank_fee_num,
+            bank.dev_fee_num,
+        )
+        .erg_to_user;
+        (
+            HodlTradeKind::Burn,
+            hodl,
+            scale(proceeds, hodl, bank_burned),
+        )
+    } else {
+        return None;
+    };
+
+    Some(HodlTrade {
+        tx_id: tx["id"].as_str().unwrap_or_default().to_string(),
+        height: tx["inclusionHeight"].as_u64().unwrap_or(0),
+        timestamp: tx["timestamp"].as_u64().unwrap_or(0),
+        kind,
+        hodl_amount,
+        reserve_amount,
+    })
+}
+
+/// Position in `bank` for a holder with `held` hodlTokens and `trades`
+/// (oldest first).
+pub fn holder_position(bank: &HodlBankState, held: i64, trades: Vec<HodlTrade>) -> HodlPosition {
+    let (mut minted, mut mint_cost, mut burned, mut burn_proceeds) = (0, 0, 0, 0);
+    let (mut tracked, mut cost_basis, mut realized_gain) = (0i64, 0i64, 0i64);
+
+    for trade in &trades {
+        match trade.kind {
+            HodlTradeKind::Mint => {
+                minted += trade.hodl_amount;
+                mint_cost += trade.reserve_amount;
+                tracked += trade.hodl_amount;
+                cost_basis += trade.reserve_amount;
+            }
+            HodlTradeKind::Burn => {
+                burned += trade.hodl_amount;
+                burn_proceeds += trade.reserve_amount;
+                // Tokens burned beyond the tracked mints have no known cost
+                let matched = trade.hodl_amount.min(tracked);
+                if matched > 0 {
+                    let released = scale(cost_basis, matched, tracked);
+                    let proceeds = scale(trade.reserve_amount, matched, trade.hodl_amount);
+                    realized_gain += proceeds - released;
+                    cost_basis -= released;
+                    tracked -= matched;
+                }
+            }
+        }
+    }
+    // Tokens sent away take their share of the cost basis with them
+    if tracked > held {
+        cost_basis = scale(cost_basis, held.max(0), tracked);
+        tracked = held.max(0);
+    }
+
+    let burn_value = burn_amount(
+        bank.reserve_amount,
+        bank.circulating_supply,
+        bank.precision_factor,
+        held,
+        bank.bank_fee_num,
+        bank.dev_fee_num,
+    )
+    .erg_to_user;
+    let unrealized_gain = (tracked > 0).then(|| scale(burn_value, tracked, held) - cost_basis);
+
+    HodlPosition {
+        singleton_token_id: bank.singleton_token_id.clone(),
+        hodl_token_id: bank.hodl_token_id.clone(),
+        hodl_token_name: bank.hodl_token_name.clone(),
+        reserve_token_id: bank.reserve_token_id.clone(),
+        held,
+        minted,
+        mint_cost,
+        burned,
+        burn_proceeds,
+        avg_mint_price: (minted > 0).then(|| mint_cost as f64 / minted as f64),
+        current_price: bank.price_nano_per_hodl,
+        burn_value,
+        cost_basis,
+        realized_gain,
+        unrealized_gain,
+        unrealized_gain_pct: unrealized_gain
+            .filter(|_| cost_basis > 0)
+            .map(|gain| gain as f64 / cost_basis as f64 * 100.0),
+        untracked: held.max(0) - tracked,
+        trades,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SINGLETON: &str = "singleton";
+    const HODL: &str = "hodl";
+    const USER: &str = "9user";
+    const SUPPLY: i64 = 1_000_000;
+
+    fn bank(reserve: i64, circulating: i64) -> HodlBankState {
+        HodlBankState {
+            bank_box_id: "bank".to_string(),
+            singleton_token_id: SINGLETON.to_string(),
+            hodl_token_id: HODL.to_string(),
+            hodl_token_name: None,
+            total_token_supply: SUPPLY,
+            precision_factor: 1_000_000,
+            min_bank_value: 1_000_000,
+            dev_fee_num: 10,
+            bank_fee_num: 20,
+            reserve_token_id: None,
+            reserve_token_name: None,
+            reserve_amount: reserve,
+            reserve_nano_erg: reserve,
+            hodl_tokens_in_bank: SUPPLY - circulating,
+            circulating_supply: circulating,
+            price_nano_per_hodl: reserve as f64 / circulating as f64,
+            tvl_nano_erg: reserve,
+            total_fee_pct: 3.0,
+            bank_fee_pct: 2.0,
+            dev_fee_pct: 1.0,
+        }
+    }
+
+    fn bank_box(reserve: i64, circulating: i64) -> serde_json::Value {
+        serde_json::json!({
+            "address": "bank",
+            "value": reserve,
+            "assets": [
+                { "tokenId": SINGLETON, "amount": 1 },
+                { "tokenId": HODL, "amount": SUPPLY - circulating },
+            ],
+        })
+    }
+
+    fn user_box(hodl: i64) -> serde_json::Value {
+        serde_json::json!({
+            "address": USER,
+            "value": 1_000_000,
+            "assets": [{ "tokenId": HODL, "amount": hodl }],
+        })
+    }
+
+    fn trade(kind: HodlTradeKind, hodl: i64, reserve: i64) -> HodlTrade {
+        HodlTrade {
+            tx_id: "tx".to_string(),
+            height: 1,
+            timestamp: 0,
+            kind,
+            hodl_amount: hodl,
+            reserve_amount: reserve,
+        }
+    }
+
+    #[test]
+    fn test_trades_from_bank_delta() {
+        let addresses: HashSet<&str> = [USER].into_iter().collect();
+        let bank = bank(2_000_000, 1_000);
+
+        let mint = serde_json::json!({
+            "id": "mint",
+            "inclusionHeight": 100,
+            "inputs": [bank_box(1_000_000, 500), { "address": USER, "value": 2_000_000 }],
+            "outputs": [bank_box(2_000_000, 1_000), user_box(500)],
+        });
+        let t = trade_from_tx(&mint, &bank, &addresses).unwrap();
+        assert_eq!(t.kind, HodlTradeKind::Mint);
+        assert_eq!(
+            (t.hodl_amount, t.reserve_amount, t.height),
+            (500, 1_000_000, 100)
+        );
+
+        // 100 of 1000 at 2000 per token: 200_000 before 3% fees
+        let burn = serde_json::json!({
+            "id": "burn",
+            "inputs": [bank_box(2_000_000, 1_000), user_box(100)],
+            "outputs": [bank_box(1_806_000, 900), { "address": USER, "value": 194_000 }],
+        });
+        let t = trade_from_tx(&burn, &bank, &addresses).unwrap();
+        assert_eq!(t.kind, HodlTradeKind::Burn);
+        assert_eq!((t.hodl_amount, t.reserve_amount), (100, 194_000));
+
+        let others: HashSet<&str> = ["9other"].into_iter().collect();
+        assert!(trade_from_tx(&mint, &bank, &others).is_none());
+    }
+
+    #[test]
+    fn test_average_cost_gains() {
+        // Price doubled since the mints; 3% burn fees
+        let bank = bank(4_000_000, 1_000);
+        let trades = vec![
+            trade(HodlTradeKind::Mint, 100, 100_000),
+            trade(HodlTradeKind::Mint, 100, 300_000),
+            trade(HodlTradeKind::Burn, 50, 150_000),
+        ];
+        let position = holder_position(&bank, 200, trades);
+
+        assert_eq!(position.avg_mint_price, Some(2_000.0));
+        // Burn released 50 * 2000 of cost against 150_000 proceeds
+        assert_eq!(position.realized_gain, 50_000);
+        assert_eq!(position.cost_basis, 300_000);
+        // 200 held, 150 from tracked mints
+        assert_eq!(position.untracked, 50);
+        assert_eq!(position.burn_value, 776_000);
+        assert_eq!(position.unrealized_gain, Some(582_000 - 300_000));
+        assert!((position.unrealized_gain_pct.unwrap() - 94.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transfer_out_drops_cost_share() {
+        let bank = bank(1_000_000, 1_000);
+        let position = holder_position(&bank, 50, vec![trade(HodlTradeKind::Mint, 100, 100_000)]);
+        assert_eq!(position.cost_basis, 50_000);
+        assert_eq!(position.untracked, 0);
+        assert_eq!(position.realized_gain, 0);
+    }
+}
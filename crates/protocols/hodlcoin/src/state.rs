@@ -22,10 +22,21 @@ pub struct HodlBankState {
     pub dev_fee_num: i64,
     pub bank_fee_num: i64,
 
+    // Reserve asset: ERG, or the token at index 2 for token-backed banks
+    #[serde(default)]
+    pub reserve_token_id: Option<String>,
+    #[serde(default)]
+    pub reserve_token_name: Option<String>,
+    /// Reserve backing the hodlTokens: nanoERG, or raw reserve token units.
+    #[serde(default)]
+    pub reserve_amount: i64,
+
     // Derived state
+    /// ERG held by the bank box (only the box minimum for token-backed banks).
     pub reserve_nano_erg: i64,
     pub hodl_tokens_in_bank: i64,
     pub circulating_supply: i64,
+    /// Reserve units per hodlToken (nanoERG for ERG-backed banks).
     pub price_nano_per_hodl: f64,
     pub tvl_nano_erg: i64,
 
@@ -35,11 +46,43 @@ pub struct HodlBankState {
     pub dev_fee_pct: f64,
 }
 
+impl HodlBankState {
+    pub fn is_token_backed(&self) -> bool {
+        self.reserve_token_id.is_some()
+    }
+
+    /// Test or abandoned deployment: an ERG bank under `MIN_DISPLAY_TVL`, or
+    /// a token bank holding no more than its R6 floor (nothing minted
+    /// against it). Token reserves have no ERG price here.
+    pub fn is_low_tvl(&self) -> bool {
+        if self.is_token_backed() {
+            self.reserve_amount <= self.min_bank_value || self.circulating_supply <= 0
+        } else {
+            self.tvl_nano_erg < crate::constants::MIN_DISPLAY_TVL
+        }
+    }
+}
+
+/// Which bank contracts discovery scans, and which banks it returns.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiscoveryOptions {
+    /// Also return test/abandoned banks (see [`HodlBankState::is_low_tvl`]).
+    pub include_low_tvl: bool,
+    /// Extra bank contract ErgoTrees (hex) whose templates are scanned next
+    /// to [`crate::constants::KNOWN_BANK_ERGO_TREES`].
+    pub custom_bank_trees: Vec<String>,
+    /// Extra dev fee contract ErgoTrees (hex). A burn pays the dev fee to
+    /// the one whose hash the bank contract embeds.
+    pub custom_fee_trees: Vec<String>,
+}
+
 /// Preview for minting hodlTokens
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct HodlMintPreview {
-    /// nanoERG user deposits into the bank
+    /// Reserve the user deposits into the bank (nanoERG, or reserve token
+    /// units for token-backed banks)
     pub erg_deposited: i64,
     /// hodlTokens the user will receive
     pub hodl_tokens_received: i64,
@@ -47,8 +90,12 @@ pub struct HodlMintPreview {
     pub price_per_token: f64,
     /// Miner fee
     pub miner_fee: i64,
-    /// Total ERG cost (deposit + miner fee + min box value)
+    /// Total ERG cost (deposit + miner fee + min box value; no deposit for
+    /// token-backed banks)
     pub total_erg_cost: i64,
+    /// Unit of `erg_deposited`; `None` for ERG.
+    #[serde(default)]
+    pub reserve_token_id: Option<String>,
 }
 
 /// Preview for burning hodlTokens
@@ -57,7 +104,7 @@ pub struct HodlMintPreview {
 pub struct HodlBurnPreview {
     /// hodlTokens the user will burn
     pub hodl_tokens_spent: i64,
-    /// ERG received after all fees
+    /// Reserve received after all fees (nanoERG, or reserve token units)
     pub erg_received: i64,
     /// Bank fee in nanoERG
     pub bank_fee_nano: i64,
@@ -69,6 +116,9 @@ pub struct HodlBurnPreview {
     pub price_per_token: f64,
     /// Miner fee
     pub miner_fee: i64,
+    /// Unit of the reserve amounts; `None` for ERG.
+    #[serde(default)]
+    pub reserve_token_id: Option<String>,
 }
 
 /// HodlCoin protocol errors
@@ -18,6 +18,12 @@ pub fn build_mint_tx_eip12(
     user_ergo_tree: &str,
     current_height: i32,
 ) -> Result<Eip12UnsignedTx, HodlError> {
+    if bank_state.is_token_backed() {
+        return Err(HodlError::TxBuildError(
+            "Bank is token-backed; use build_token_mint_tx_eip12".to_string(),
+        ));
+    }
+
     if erg_to_deposit <= 0 {
         return Err(HodlError::TxBuildError(
             "Deposit amount must be positive".to_string(),
@@ -130,6 +136,12 @@ pub fn build_burn_tx_eip12(
     user_ergo_tree: &str,
     current_height: i32,
 ) -> Result<Eip12UnsignedTx, HodlError> {
+    if bank_state.is_token_backed() {
+        return Err(HodlError::TxBuildError(
+            "Bank is token-backed; use build_token_burn_tx_eip12".to_string(),
+        ));
+    }
+
     if hodl_to_burn <= 0 {
         return Err(HodlError::TxBuildError(
             "Burn amount must be positive".to_string(),
@@ -265,3 +277,487 @@ fn extract_dev_fee_tree(bank_box: &Eip12InputBox) -> Result<String, HodlError> {
     );
     Ok(constants::DEV_FEE_CONTRACT_BYTES.to_string())
 }
+
+/// Bank singleton and hodlToken plus the reserve token of a token-backed bank.
+fn token_bank_assets(
+    bank_box: &Eip12InputBox,
+    bank_state: &HodlBankState,
+) -> Result<(Eip12Asset, u64, Eip12Asset, u64), HodlError> {
+    if bank_box.assets.len() < 3 {
+        return Err(HodlError::TxBuildError(format!(
+            "Bank box has {} tokens, expected 3 for a token-backed bank",
+            bank_box.assets.len()
+        )));
+    }
+
+    let bank_singleton = bank_box.assets[constants::bank_tokens::SINGLETON].clone();
+    let bank_hodl = &bank_box.assets[constants::bank_tokens::HODL_TOKEN];
+    let bank_reserve = &bank_box.assets[constants::bank_tokens::RESERVE_TOKEN];
+
+    if bank_state.reserve_token_id.as_deref() != Some(bank_reserve.token_id.as_str()) {
+        return Err(HodlError::TxBuildError(
+            "Bank box reserve token does not match bank state".to_string(),
+        ));
+    }
+
+    let hodl_in_bank: u64 = bank_hodl
+        .amount
+        .parse()
+        .map_err(|_| HodlError::TxBuildError("Invalid hodl token amount".to_string()))?;
+    let reserve_in_bank: u64 = bank_reserve
+        .amount
+        .parse()
+        .map_err(|_| HodlError::TxBuildError("Invalid reserve token amount".to_string()))?;
+
+    Ok((
+        bank_singleton,
+        hodl_in_bank,
+        bank_reserve.clone(),
+        reserve_in_bank,
+    ))
+}
+
+/// Mint against a token-backed bank: the user deposits `reserve_to_deposit`
+/// reserve token units; the bank's ERG value is untouched.
+/// Bank box must be inputs[0]; new bank box must be outputs[0].
+pub fn build_token_mint_tx_eip12(
+    bank_box: &Eip12InputBox,
+    bank_state: &HodlBankState,
+    reserve_to_deposit: i64,
+    user_utxos: &[Eip12InputBox],
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<Eip12UnsignedTx, HodlError> {
+    if reserve_to_deposit <= 0 {
+        return Err(HodlError::TxBuildError(
+            "Deposit amount must be positive".to_string(),
+        ));
+    }
+
+    let (bank_singleton, hodl_in_bank, bank_reserve, reserve_in_bank) =
+        token_bank_assets(bank_box, bank_state)?;
+
+    let tokens_received = calculator::mint_amount(
+        bank_state.reserve_amount,
+        bank_state.circulating_supply,
+        bank_state.precision_factor,
+        reserve_to_deposit,
+    );
+
+    if tokens_received <= 0 {
+        return Err(HodlError::TxBuildError(
+            "Deposit too small to receive any tokens".to_string(),
+        ));
+    }
+
+    if tokens_received > bank_state.hodl_tokens_in_bank {
+        return Err(HodlError::TxBuildError(format!(
+            "Bank only has {} tokens, but {} needed",
+            bank_state.hodl_tokens_in_bank, tokens_received
+        )));
+    }
+
+    let new_bank_output = Eip12Output {
+        value: bank_box.value.clone(),
+        ergo_tree: bank_box.ergo_tree.clone(),
+        assets: vec![
+            bank_singleton,
+            Eip12Asset::new(
+                &bank_state.hodl_token_id,
+                (hodl_in_bank - tokens_received as u64) as i64,
+            ),
+            Eip12Asset::new(
+                &bank_reserve.token_id,
+                (reserve_in_bank + reserve_to_deposit as u64) as i64,
+            ),
+        ],
+        creation_height: current_height,
+        additional_registers: bank_box.additional_registers.clone(),
+    };
+
+    let user_output = Eip12Output::change(
+        MIN_BOX_VALUE as i64,
+        user_ergo_tree,
+        vec![Eip12Asset::new(&bank_state.hodl_token_id, tokens_received)],
+        current_height,
+    );
+
+    let fee_output = Eip12Output::fee(MIN_MINER_FEE as i64, current_height);
+
+    let user_erg_needed = MIN_BOX_VALUE + MIN_MINER_FEE;
+
+    let selected = select_token_boxes(
+        user_utxos,
+        &bank_reserve.token_id,
+        reserve_to_deposit as u64,
+        user_erg_needed,
+    )
+    .map_err(|e| HodlError::InsufficientFunds(e.to_string()))?;
+
+    let mut outputs = vec![new_bank_output, user_output, fee_output];
+
+    append_change_output(
+        &mut outputs,
+        &selected,
+        user_erg_needed,
+        &[(bank_reserve.token_id.as_str(), reserve_to_deposit as u64)],
+        user_ergo_tree,
+        current_height,
+        MIN_CHANGE_VALUE,
+    )
+    .map_err(|e| HodlError::TxBuildError(e.to_string()))?;
+
+    let mut inputs = vec![bank_box.clone()];
+    inputs.extend(selected.boxes);
+
+    Ok(Eip12UnsignedTx {
+        inputs,
+        data_inputs: vec![],
+        outputs,
+    })
+}
+
+/// Burn against a token-backed bank. Reserve tokens leave the bank for the
+/// user and the dev fee box; both boxes carry `MIN_BOX_VALUE` ERG paid by
+/// the user. `dev_fee_ergo_tree` must be the contract the bank embeds the
+/// hash of (see [`resolve_dev_fee_tree`]).
+/// Bank box must be inputs[0]; new bank box must be outputs[0].
+pub fn build_token_burn_tx_eip12(
+    bank_box: &Eip12InputBox,
+    bank_state: &HodlBankState,
+    hodl_to_burn: i64,
+    dev_fee_ergo_tree: &str,
+    user_utxos: &[Eip12InputBox],
+    user_ergo_tree: &str,
+    current_height: i32,
+) -> Result<Eip12UnsignedTx, HodlError> {
+    if hodl_to_burn <= 0 {
+        return Err(HodlError::TxBuildError(
+            "Burn amount must be positive".to_string(),
+        ));
+    }
+
+    let (bank_singleton, hodl_in_bank, bank_reserve, reserve_in_bank) =
+        token_bank_assets(bank_box, bank_state)?;
+
+    let burn_result = calculator::burn_amount(
+        bank_state.reserve_amount,
+        bank_state.circulating_supply,
+        bank_state.precision_factor,
+        hodl_to_burn,
+        bank_state.bank_fee_num,
+        bank_state.dev_fee_num,
+    );
+
+    if burn_result.erg_to_user <= 0 {
+        return Err(HodlError::TxBuildError(
+            "Burn amount too small to receive any reserve tokens".to_string(),
+        ));
+    }
+
+    // bank_fee stays in the bank, as for ERG banks
+    let reserve_leaving_bank = (burn_result.erg_to_user + burn_result.dev_fee) as u64;
+    let new_reserve_in_bank = reserve_in_bank
+        .checked_sub(reserve_leaving_bank)
+        .ok_or_else(|| HodlError::TxBuildError("Bank reserve underflow".to_string()))?;
+
+    if (new_reserve_in_bank as i64) < bank_state.min_bank_value {
+        return Err(HodlError::BelowMinBankValue);
+    }
+
+    let new_bank_output = Eip12Output {
+        value: bank_box.value.clone(),
+        ergo_tree: bank_box.ergo_tree.clone(),
+        assets: vec![
+            bank_singleton,
+            Eip12Asset::new(
+                &bank_state.hodl_token_id,
+                (hodl_in_bank + hodl_to_burn as u64) as i64,
+            ),
+            Eip12Asset::new(&bank_reserve.token_id, new_reserve_in_bank as i64),
+        ],
+        creation_height: current_height,
+        additional_registers: bank_box.additional_registers.clone(),
+    };
+
+    let user_output = Eip12Output::change(
+        MIN_BOX_VALUE as i64,
+        user_ergo_tree,
+        vec![Eip12Asset::new(
+            &bank_reserve.token_id,
+            burn_result.erg_to_user,
+        )],
+        current_height,
+    );
+
+    let dev_fee_assets = if burn_result.dev_fee > 0 {
+        vec![Eip12Asset::new(&bank_reserve.token_id, burn_result.dev_fee)]
+    } else {
+        vec![]
+    };
+    let dev_fee_output = Eip12Output::change(
+        MIN_BOX_VALUE as i64,
+        dev_fee_ergo_tree,
+        dev_fee_assets,
+        current_height,
+    );
+
+    let fee_output = Eip12Output::fee(MIN_MINER_FEE as i64, current_height);
+
+    let mut outputs = vec![new_bank_output, user_output, dev_fee_output, fee_output];
+
+    let user_erg_needed = 2 * MIN_BOX_VALUE + MIN_MINER_FEE;
+
+    let selected = select_token_boxes(
+        user_utxos,
+        &bank_state.hodl_token_id,
+        hodl_to_burn as u64,
+        user_erg_needed,
+    )
+    .map_err(|e| HodlError::InsufficientFunds(e.to_string()))?;
+
+    append_change_output(
+        &mut outputs,
+        &selected,
+        user_erg_needed,
+        &[(bank_state.hodl_token_id.as_str(), hodl_to_burn as u64)],
+        user_ergo_tree,
+        current_height,
+        MIN_CHANGE_VALUE,
+    )
+    .map_err(|e| HodlError::TxBuildError(e.to_string()))?;
+
+    let mut inputs = vec![bank_box.clone()];
+    inputs.extend(selected.boxes);
+
+    Ok(Eip12UnsignedTx {
+        inputs,
+        data_inputs: vec![],
+        outputs,
+    })
+}
+
+/// The dev fee contract a bank pays into: a known one or one of
+/// `custom_fee_trees`, whichever's `blake2b256(propBytes)` the bank ErgoTree
+/// embeds. `None` if the bank references none of them.
+pub fn resolve_dev_fee_tree(bank_ergo_tree: &str, custom_fee_trees: &[String]) -> Option<String> {
+    constants::KNOWN_FEE_ERGO_TREES
+        .iter()
+        .copied()
+        .chain(custom_fee_trees.iter().map(String::as_str))
+        .find(|tree| crate::fetch::embeds_hash_of(bank_ergo_tree, tree))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    const USER_ERGO_TREE: &str =
+        "0008cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const BANK_ERGO_TREE: &str = "1004aabbccdd";
+    const HEIGHT: i32 = 1_000_000;
+
+    fn id(byte: &str) -> String {
+        byte.repeat(32)
+    }
+
+    fn input(box_id: &str, erg: u64, tree: &str, assets: Vec<Eip12Asset>) -> Eip12InputBox {
+        Eip12InputBox {
+            box_id: box_id.repeat(32),
+            transaction_id: id("ee"),
+            index: 0,
+            value: erg.to_string(),
+            ergo_tree: tree.to_string(),
+            assets,
+            creation_height: 999_000,
+            additional_registers: HashMap::new(),
+            extension: HashMap::new(),
+        }
+    }
+
+    /// Token bank holding 1e12 reserve units against 1e8 circulating hodl.
+    fn token_bank() -> (Eip12InputBox, HodlBankState) {
+        let bank_box = input(
+            "aa",
+            MIN_BOX_VALUE,
+            BANK_ERGO_TREE,
+            vec![
+                Eip12Asset::new(&id("11"), 1),
+                Eip12Asset::new(&id("22"), 900_000_000),
+                Eip12Asset::new(&id("33"), 1_000_000_000_000),
+            ],
+        );
+        let state = HodlBankState {
+            bank_box_id: bank_box.box_id.clone(),
+            singleton_token_id: id("11"),
+            hodl_token_id: id("22"),
+            hodl_token_name: Some("hodlTKN".to_string()),
+            total_token_supply: 1_000_000_000,
+            precision_factor: 1_000_000,
+            min_bank_value: 1_000_000,
+            dev_fee_num: 3,
+            bank_fee_num: 30,
+            reserve_token_id: Some(id("33")),
+            reserve_token_name: Some("TKN".to_string()),
+            reserve_amount: 1_000_000_000_000,
+            reserve_nano_erg: MIN_BOX_VALUE as i64,
+            hodl_tokens_in_bank: 900_000_000,
+            circulating_supply: 100_000_000,
+            price_nano_per_hodl: 10_000.0,
+            tvl_nano_erg: 0,
+            total_fee_pct: 3.3,
+            bank_fee_pct: 3.0,
+            dev_fee_pct: 0.3,
+        };
+        (bank_box, state)
+    }
+
+    fn user_utxos() -> Vec<Eip12InputBox> {
+        vec![input(
+            "bb",
+            1_000_000_000,
+            USER_ERGO_TREE,
+            vec![
+                Eip12Asset::new(&id("33"), 500_000_000_000),
+                Eip12Asset::new(&id("22"), 50_000_000),
+            ],
+        )]
+    }
+
+    fn amount(output: &Eip12Output, index: usize) -> (String, u64) {
+        let asset = &output.assets[index];
+        (asset.token_id.clone(), asset.amount.parse().unwrap())
+    }
+
+    #[test]
+    fn test_token_mint_moves_reserve_into_bank() {
+        let (bank_box, state) = token_bank();
+        let deposit = 100_000_000_000;
+        let tx = build_token_mint_tx_eip12(
+            &bank_box,
+            &state,
+            deposit,
+            &user_utxos(),
+            USER_ERGO_TREE,
+            HEIGHT,
+        )
+        .unwrap();
+        let minted = calculator::mint_amount(
+            state.reserve_amount,
+            state.circulating_supply,
+            state.precision_factor,
+            deposit,
+        );
+        assert!(minted > 0);
+
+        assert_eq!(tx.inputs[0].box_id, bank_box.box_id);
+        let bank = &tx.outputs[0];
+        assert_eq!(bank.ergo_tree, BANK_ERGO_TREE);
+        assert_eq!(bank.value, bank_box.value);
+        assert_eq!(bank.assets.len(), 3);
+        assert_eq!(amount(bank, 0), (id("11"), 1));
+        assert_eq!(amount(bank, 1), (id("22"), 900_000_000 - minted as u64));
+        assert_eq!(
+            amount(bank, constants::bank_tokens::RESERVE_TOKEN),
+            (id("33"), 1_000_000_000_000 + deposit as u64)
+        );
+        assert_eq!(amount(&tx.outputs[1], 0), (id("22"), minted as u64));
+    }
+
+    #[test]
+    fn test_token_burn_pays_user_and_dev_fee_in_reserve() {
+        let (bank_box, state) = token_bank();
+        let burn = 10_000_000;
+        let tx = build_token_burn_tx_eip12(
+            &bank_box,
+            &state,
+            burn,
+            constants::DEV_FEE_CONTRACT_BYTES,
+            &user_utxos(),
+            USER_ERGO_TREE,
+            HEIGHT,
+        )
+        .unwrap();
+        let expected = calculator::burn_amount(
+            state.reserve_amount,
+            state.circulating_supply,
+            state.precision_factor,
+            burn,
+            state.bank_fee_num,
+            state.dev_fee_num,
+        );
+        assert!(expected.dev_fee > 0 && expected.bank_fee > 0);
+
+        let bank = &tx.outputs[0];
+        assert_eq!(amount(bank, 1), (id("22"), 900_000_000 + burn as u64));
+        // The bank fee stays in the reserve
+        let leaving = (expected.erg_to_user + expected.dev_fee) as u64;
+        assert_eq!(
+            amount(bank, constants::bank_tokens::RESERVE_TOKEN),
+            (id("33"), 1_000_000_000_000 - leaving)
+        );
+        assert_eq!(
+            amount(&tx.outputs[1], 0),
+            (id("33"), expected.erg_to_user as u64)
+        );
+        let dev = &tx.outputs[2];
+        assert_eq!(dev.ergo_tree, constants::DEV_FEE_CONTRACT_BYTES);
+        assert_eq!(dev.value, MIN_BOX_VALUE.to_string());
+        assert_eq!(amount(dev, 0), (id("33"), expected.dev_fee as u64));
+    }
+
+    #[test]
+    fn test_token_builders_reject_reserve_mismatch() {
+        let (bank_box, mut state) = token_bank();
+        state.reserve_token_id = Some(id("44"));
+        let mint = build_token_mint_tx_eip12(
+            &bank_box,
+            &state,
+            100_000_000_000,
+            &user_utxos(),
+            USER_ERGO_TREE,
+            HEIGHT,
+        );
+        assert!(mint.unwrap_err().to_string().contains("reserve token"));
+        let burn = build_token_burn_tx_eip12(
+            &bank_box,
+            &state,
+            10_000_000,
+            constants::DEV_FEE_CONTRACT_BYTES,
+            &user_utxos(),
+            USER_ERGO_TREE,
+            HEIGHT,
+        );
+        assert!(burn.unwrap_err().to_string().contains("reserve token"));
+    }
+
+    #[test]
+    fn test_resolve_dev_fee_tree_needs_byte_aligned_hash() {
+        let hash = constants::DEV_FEE_CONTRACT_HASH;
+        assert_eq!(
+            resolve_dev_fee_tree(&format!("10{}00", hash), &[]).as_deref(),
+            Some(constants::DEV_FEE_CONTRACT_BYTES)
+        );
+        // The same hex at an odd nibble offset is not an embedded hash
+        assert_eq!(resolve_dev_fee_tree(&format!("100{}0", hash), &[]), None);
+    }
+
+    #[test]
+    fn test_token_burn_respects_min_bank_value() {
+        let (bank_box, mut state) = token_bank();
+        state.min_bank_value = state.reserve_amount;
+        let result = build_token_burn_tx_eip12(
+            &bank_box,
+            &state,
+            10_000_000,
+            constants::DEV_FEE_CONTRACT_BYTES,
+            &user_utxos(),
+            USER_ERGO_TREE,
+            HEIGHT,
+        );
+        assert!(matches!(result, Err(HodlError::BelowMinBankValue)));
+    }
+}
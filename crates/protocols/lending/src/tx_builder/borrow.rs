@@ -1,4 +1,5 @@
 use crate::constants::PoolConfig;
+use citadel_core::IntentAsset;
 use ergo_tx::{
     append_change_output,
     sigma::{encode_sigma_coll_byte, encode_sigma_long},
@@ -6,8 +7,8 @@ use ergo_tx::{
 };
 
 use super::common::{
-    finalize_proxy_tx, miner_fee_output, resolve_user_ergo_tree, select_erg_inputs,
-    select_token_inputs, to_ergo_tx_selected, user_utxo_to_eip12,
+    currency_asset, finalize_proxy_tx, miner_fee_output, proxy_intent, resolve_user_ergo_tree,
+    select_erg_inputs, select_token_inputs, to_ergo_tx_selected, user_utxo_to_eip12,
 };
 use super::{
     BorrowRequest, BuildError, BuildResponse, TxSummary, MIN_BOX_VALUE_NANO,
@@ -136,6 +137,20 @@ pub fn build_borrow_tx(
     let divisor = 10f64.powi(config.decimals as i32);
     let borrow_display = (req.borrow_amount as f64) / divisor;
 
+    let collateral = if req.collateral_token == "native" {
+        IntentAsset::erg(req.collateral_amount)
+    } else {
+        IntentAsset::token(&req.collateral_token, req.collateral_amount)
+    };
+    let intent = proxy_intent(
+        config,
+        format!("Borrow {}", config.symbol),
+        PROXY_EXECUTION_FEE_NANO,
+        refund_height,
+    )
+    .pays(collateral)
+    .receives(currency_asset(config, req.borrow_amount));
+
     Ok(BuildResponse {
         unsigned_tx: unsigned_tx_json,
         fee_nano: TX_FEE_NANO,
@@ -152,5 +167,6 @@ pub fn build_borrow_tx(
             total_to_send_raw: req.collateral_amount,
             total_to_send_display: format!("{} collateral", req.collateral_amount),
         },
+        intent,
     })
 }
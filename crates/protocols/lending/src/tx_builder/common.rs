@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
+use citadel_core::{IntentAsset, TxIntent};
 use ergo_tx::{Eip12Asset, Eip12InputBox, Eip12Output, Eip12UnsignedTx};
 
 use super::{BuildError, UserUtxo, TX_FEE_NANO};
+use crate::constants::PoolConfig;
 
 #[derive(Debug, Clone)]
 pub struct SelectedInputs {
@@ -148,6 +150,40 @@ pub(crate) fn miner_fee_output(current_height: i32) -> Eip12Output {
     Eip12Output::fee(TX_FEE_NANO, current_height)
 }
 
+/// Pool currency as an intent asset (ERG for the ERG pool).
+pub(crate) fn currency_asset(config: &PoolConfig, amount: u64) -> IntentAsset {
+    match config.currency_id {
+        Some(id) if !config.is_erg_pool => {
+            IntentAsset::named(id, config.symbol, amount, config.decimals)
+        }
+        _ => IntentAsset::erg(amount),
+    }
+}
+
+pub(crate) fn lend_token_asset(config: &PoolConfig, amount: u64) -> IntentAsset {
+    IntentAsset {
+        name: Some(format!("{} lend token", config.symbol)),
+        ..IntentAsset::token(config.lend_token_id, amount)
+    }
+}
+
+/// Shared proxy-order intent: miner fee, bot fee, proxy counterparty and refund note.
+pub(crate) fn proxy_intent(
+    config: &PoolConfig,
+    action: String,
+    execution_fee: i64,
+    refund_height: i32,
+) -> TxIntent {
+    TxIntent::new("Duckpools", action)
+        .miner_fee(TX_FEE_NANO as u64)
+        .protocol_fee(execution_fee as u64)
+        .counterparty("proxy", format!("{} proxy", config.name))
+        .note(format!(
+            "Executed by the Duckpools bot; refundable after block {}",
+            refund_height
+        ))
+}
+
 pub(crate) fn finalize_proxy_tx(
     eip12_inputs: Vec<Eip12InputBox>,
     outputs: Vec<Eip12Output>,
@@ -6,8 +6,9 @@ use ergo_tx::{
 };
 
 use super::common::{
-    finalize_proxy_tx, miner_fee_output, resolve_user_ergo_tree, select_erg_inputs,
-    select_token_inputs, to_ergo_tx_selected, user_utxo_to_eip12,
+    currency_asset, finalize_proxy_tx, lend_token_asset, miner_fee_output, proxy_intent,
+    resolve_user_ergo_tree, select_erg_inputs, select_token_inputs, to_ergo_tx_selected,
+    user_utxo_to_eip12,
 };
 use super::{
     BuildError, BuildResponse, LendRequest, TxSummary, BOT_PROCESSING_OVERHEAD, MIN_BOX_VALUE_NANO,
@@ -151,6 +152,24 @@ pub fn build_lend_tx(
     let fee_display = (service_fee as f64) / divisor;
     let total_display = (total_to_send as f64) / divisor;
 
+    let mut intent = proxy_intent(
+        config,
+        format!("Lend {}", config.symbol),
+        BOT_PROCESSING_OVERHEAD,
+        refund_height,
+    )
+    .pays(currency_asset(config, total_to_send))
+    .note(format!(
+        "Includes service fee of {}",
+        currency_asset(config, service_fee).display()
+    ));
+    if let Some(min_lp) = req.min_lp_tokens.filter(|m| *m > 0) {
+        intent = intent.min_out(
+            lend_token_asset(config, min_lp),
+            Some(slippage_bps as f64 / 100.0),
+        );
+    }
+
     Ok(BuildResponse {
         unsigned_tx: unsigned_tx_json,
         fee_nano: TX_FEE_NANO,
@@ -167,5 +186,6 @@ pub fn build_lend_tx(
             total_to_send_raw: total_to_send,
             total_to_send_display: format!("{:.6} {}", total_display, config.symbol),
         },
+        intent,
     })
 }
@@ -2,6 +2,8 @@
 
 use std::collections::HashMap;
 
+use citadel_core::TxIntent;
+
 mod borrow;
 mod common;
 mod lend;
@@ -120,6 +122,7 @@ pub struct BuildResponse {
     pub unsigned_tx: String,
     pub fee_nano: i64,
     pub summary: TxSummary,
+    pub intent: TxIntent,
 }
 
 #[derive(Debug, Clone)]
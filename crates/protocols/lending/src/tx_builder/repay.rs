@@ -6,8 +6,8 @@ use ergo_tx::{
 };
 
 use super::common::{
-    finalize_proxy_tx, miner_fee_output, resolve_user_ergo_tree, select_erg_inputs,
-    select_token_inputs, to_ergo_tx_selected, user_utxo_to_eip12,
+    currency_asset, finalize_proxy_tx, miner_fee_output, proxy_intent, resolve_user_ergo_tree,
+    select_erg_inputs, select_token_inputs, to_ergo_tx_selected, user_utxo_to_eip12,
 };
 use super::{
     BuildError, BuildResponse, RepayRequest, TxSummary, BOT_PROCESSING_OVERHEAD,
@@ -146,6 +146,19 @@ pub fn build_repay_tx(
     let divisor = 10f64.powi(config.decimals as i32);
     let amount_display = (req.repay_amount as f64) / divisor;
 
+    let intent = proxy_intent(
+        config,
+        format!("Repay {}", config.symbol),
+        BOT_PROCESSING_OVERHEAD,
+        refund_height,
+    )
+    .pays(currency_asset(config, req.repay_amount))
+    .note(if req.repay_amount >= req.total_owed {
+        "Collateral is returned once the loan is fully repaid"
+    } else {
+        "Partial repayment; collateral stays locked"
+    });
+
     Ok(BuildResponse {
         unsigned_tx: unsigned_tx_json,
         fee_nano: TX_FEE_NANO,
@@ -162,5 +175,6 @@ pub fn build_repay_tx(
             total_to_send_raw: 0,
             total_to_send_display: String::new(),
         },
+        intent,
     })
 }
@@ -316,6 +316,7 @@ fn test_build_response_struct() {
             total_to_send_raw: 0,
             total_to_send_display: String::new(),
         },
+        intent: TxIntent::new("Duckpools", "Withdraw ERG"),
     };
 
     assert_eq!(response.fee_nano, TX_FEE_NANO);
@@ -40,6 +40,15 @@ fn test_build_lend_tx_erg_pool() {
     assert_eq!(response.summary.service_fee_raw, 62_500_000);
     assert_eq!(response.summary.total_to_send_raw, amount + 62_500_000); // no slippage
 
+    assert_eq!(response.intent.action, "Lend ERG");
+    assert_eq!(response.intent.assets_in[0].amount, amount + 62_500_000);
+    assert_eq!(
+        response.intent.fees.protocol_nano,
+        BOT_PROCESSING_OVERHEAD as u64
+    );
+    let min_out = response.intent.slippage.as_ref().unwrap().min_out.as_ref();
+    assert_eq!(min_out.unwrap().amount, 100);
+
     let tx: serde_json::Value = serde_json::from_str(&response.unsigned_tx).unwrap();
     assert!(tx["inputs"].is_array());
     assert!(tx["outputs"].is_array());
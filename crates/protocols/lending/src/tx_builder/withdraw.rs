@@ -6,8 +6,8 @@ use ergo_tx::{
 };
 
 use super::common::{
-    finalize_proxy_tx, miner_fee_output, resolve_user_ergo_tree, select_token_inputs,
-    to_ergo_tx_selected, user_utxo_to_eip12,
+    currency_asset, finalize_proxy_tx, lend_token_asset, miner_fee_output, proxy_intent,
+    resolve_user_ergo_tree, select_token_inputs, to_ergo_tx_selected, user_utxo_to_eip12,
 };
 use super::{
     BuildError, BuildResponse, TxSummary, WithdrawRequest, MIN_BOX_VALUE_NANO,
@@ -92,6 +92,17 @@ pub fn build_withdraw_tx(
     let (unsigned_tx_json, proxy_address) =
         finalize_proxy_tx(eip12_inputs, outputs, &proxy_ergo_tree)?;
 
+    let mut intent = proxy_intent(
+        config,
+        format!("Withdraw {}", config.symbol),
+        PROXY_EXECUTION_FEE_NANO,
+        refund_height,
+    )
+    .pays(lend_token_asset(config, req.lp_amount));
+    if let Some(min_output) = req.min_output.filter(|m| *m > 0) {
+        intent = intent.min_out(currency_asset(config, min_output), None);
+    }
+
     Ok(BuildResponse {
         unsigned_tx: unsigned_tx_json,
         fee_nano: TX_FEE_NANO,
@@ -108,5 +119,6 @@ pub fn build_withdraw_tx(
             total_to_send_raw: 0,
             total_to_send_display: String::new(),
         },
+        intent,
     })
 }
@@ -0,0 +1,266 @@
+//! Reserve-ratio-aware advice for the four bank actions: the exact amount
+//! each one can take right now, what it costs per unit once the 2% fee is
+//! in, and how far prices must move before reversing it breaks even.
+//!
+//! Limits come from the calculator's post-action ratio checks, so an amount
+//! at or below `max_amount` passes the same validators the tx builders use.
+
+use citadel_core::ProtocolError;
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::{
+    calculate_state, cost_to_mint_sigrsv, cost_to_mint_sigusd, erg_from_redeem_sigrsv,
+    erg_from_redeem_sigusd, ProtocolInput,
+};
+use crate::constants::params;
+use crate::state::SigmaUsdState;
+use crate::tx_builder::{
+    validate_mint_sigrsv, validate_mint_sigusd, validate_redeem_sigrsv, validate_redeem_sigusd,
+    SigmaUsdAction,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionAdvice {
+    pub action: String,
+    pub allowed: bool,
+    /// Largest amount (raw units) the contract accepts right now.
+    pub max_amount: i64,
+    /// What caps `max_amount`: "reserve_ratio" or "circulating_supply".
+    pub limited_by: String,
+    /// nanoERG per raw unit the bank charges (mints) or pays (redeems),
+    /// fee included.
+    pub effective_price_nano: f64,
+    /// ERG paid (mints) or received (redeems) for `max_amount`.
+    pub erg_at_max_nano: i64,
+    pub reserve_ratio_at_max_pct: f64,
+    /// Change of the token's bank price (in ERG) at which undoing this
+    /// action through the bank breaks even after both fees. For SigUSD the
+    /// bank price is the oracle rate, so +4.08% means ERG/USD down ~3.92%.
+    pub round_trip_break_even_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankAdvice {
+    pub reserve_ratio_pct: f64,
+    pub liabilities_nano: i64,
+    pub equity_nano: i64,
+    /// ERG/USD move that takes the ratio down to 400% (SigUSD minting and
+    /// SigRSV redemption close); `None` without SigUSD in circulation.
+    pub erg_move_to_min_ratio_pct: Option<f64>,
+    /// ERG/USD move that takes the ratio up to 800% (SigRSV minting closes).
+    pub erg_move_to_max_ratio_pct: Option<f64>,
+    pub actions: Vec<ActionAdvice>,
+}
+
+const ACTIONS: [SigmaUsdAction; 4] = [
+    SigmaUsdAction::MintSigUsd,
+    SigmaUsdAction::RedeemSigUsd,
+    SigmaUsdAction::MintSigRsv,
+    SigmaUsdAction::RedeemSigRsv,
+];
+
+fn is_mint(action: SigmaUsdAction) -> bool {
+    matches!(
+        action,
+        SigmaUsdAction::MintSigUsd | SigmaUsdAction::MintSigRsv
+    )
+}
+
+/// ERG moved by `amount` of `action` against `state`: paid in for mints,
+/// paid out for redeems (always positive). Fails like the tx builder would.
+pub fn bank_quote(
+    state: &SigmaUsdState,
+    action: SigmaUsdAction,
+    amount: i64,
+) -> Result<i64, ProtocolError> {
+    let erg = match action {
+        SigmaUsdAction::MintSigUsd => {
+            validate_mint_sigusd(amount, state)?;
+            cost_to_mint_sigusd(amount, state.sigusd_price_nano).net_amount
+        }
+        SigmaUsdAction::RedeemSigUsd => {
+            validate_redeem_sigusd(amount, state)?;
+            erg_from_redeem_sigusd(amount, state.sigusd_price_nano).net_amount
+        }
+        SigmaUsdAction::MintSigRsv => {
+            validate_mint_sigrsv(amount, state)?;
+            cost_to_mint_sigrsv(amount, state.sigrsv_price_nano).net_amount
+        }
+        SigmaUsdAction::RedeemSigRsv => {
+            validate_redeem_sigrsv(amount, state)?;
+            erg_from_redeem_sigrsv(amount, state.sigrsv_price_nano).net_amount
+        }
+    };
+    Ok(erg)
+}
+
+/// Reserve ratio once `amount` of `action` has gone through the bank.
+fn ratio_after(state: &SigmaUsdState, action: SigmaUsdAction, amount: i64, erg: i64) -> f64 {
+    let (erg_delta, sigusd_delta) = match action {
+        SigmaUsdAction::MintSigUsd => (erg, amount),
+        SigmaUsdAction::RedeemSigUsd => (-erg, -amount),
+        SigmaUsdAction::MintSigRsv => (erg, 0),
+        SigmaUsdAction::RedeemSigRsv => (-erg, 0),
+    };
+    calculate_state(&ProtocolInput {
+        bank_erg_nano: state.bank_erg_nano + erg_delta,
+        sigusd_circulating: state.sigusd_circulating + sigusd_delta,
+        sigrsv_circulating: state.sigrsv_circulating,
+        nanoerg_per_usd: state.oracle_erg_per_usd_nano,
+    })
+    .reserve_ratio_pct
+}
+
+/// Bank price per raw unit before the fee.
+fn unit_price_nano(state: &SigmaUsdState, action: SigmaUsdAction) -> f64 {
+    match action {
+        SigmaUsdAction::MintSigUsd | SigmaUsdAction::RedeemSigUsd => {
+            state.sigusd_price_nano as f64 / 100.0
+        }
+        SigmaUsdAction::MintSigRsv | SigmaUsdAction::RedeemSigRsv => state.sigrsv_price_nano as f64,
+    }
+}
+
+fn advise_action(state: &SigmaUsdState, action: SigmaUsdAction) -> ActionAdvice {
+    let (max_amount, supply_bound) = match action {
+        SigmaUsdAction::MintSigUsd => (state.max_sigusd_mintable, false),
+        SigmaUsdAction::RedeemSigUsd => (state.sigusd_circulating, true),
+        SigmaUsdAction::MintSigRsv => (state.max_sigrsv_mintable, false),
+        SigmaUsdAction::RedeemSigRsv => (
+            state.max_sigrsv_redeemable,
+            state.max_sigrsv_redeemable >= state.sigrsv_circulating,
+        ),
+    };
+    let erg_at_max_nano = bank_quote(state, action, max_amount).unwrap_or(0);
+    let allowed = max_amount > 0 && erg_at_max_nano > 0;
+
+    let fee = params::FEE_BPS as f64 / 10_000.0;
+    let (effective_price_nano, round_trip_break_even_pct) = if is_mint(action) {
+        (
+            unit_price_nano(state, action) * (1.0 + fee),
+            ((1.0 + fee) / (1.0 - fee) - 1.0) * 100.0,
+        )
+    } else {
+        (
+            unit_price_nano(state, action) * (1.0 - fee),
+            ((1.0 - fee) / (1.0 + fee) - 1.0) * 100.0,
+        )
+    };
+
+    ActionAdvice {
+        action: action.as_str().to_string(),
+        allowed,
+        max_amount: if allowed { max_amount } else { 0 },
+        limited_by: if supply_bound {
+            "circulating_supply"
+        } else {
+            "reserve_ratio"
+        }
+        .to_string(),
+        effective_price_nano,
+        erg_at_max_nano,
+        reserve_ratio_at_max_pct: if allowed {
+            ratio_after(state, action, max_amount, erg_at_max_nano)
+        } else {
+            state.reserve_ratio_pct
+        },
+        round_trip_break_even_pct,
+    }
+}
+
+/// The ratio scales with the ERG price (liabilities are fixed in USD), so
+/// reaching `target` takes an ERG/USD move of `target / ratio - 1`.
+fn erg_move_to_ratio_pct(state: &SigmaUsdState, target: i32) -> Option<f64> {
+    (state.liabilities_nano > 0 && state.reserve_ratio_pct > 0.0)
+        .then(|| (target as f64 / state.reserve_ratio_pct - 1.0) * 100.0)
+}
+
+/// Limits, fee-inclusive prices and break-even moves for all four actions.
+pub fn advise(state: &SigmaUsdState) -> BankAdvice {
+    BankAdvice {
+        reserve_ratio_pct: state.reserve_ratio_pct,
+        liabilities_nano: state.liabilities_nano,
+        equity_nano: state.equity_nano,
+        erg_move_to_min_ratio_pct: erg_move_to_ratio_pct(state, params::MIN_RESERVE_RATIO_PCT),
+        erg_move_to_max_ratio_pct: erg_move_to_ratio_pct(state, params::MAX_RESERVE_RATIO_PCT),
+        actions: ACTIONS
+            .iter()
+            .map(|&action| advise_action(state, action))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{BankBoxData, OracleBoxData};
+    use citadel_core::BoxId;
+
+    /// `bank_erg` ERG backing `sigusd` SigUSD at 1 ERG/USD.
+    fn state(bank_erg: i64, sigusd: i64) -> SigmaUsdState {
+        SigmaUsdState::from_boxes(
+            &BankBoxData {
+                box_id: BoxId::new("bank"),
+                value_nano: bank_erg * 1_000_000_000,
+                sigusd_circulating: sigusd * 100,
+                sigrsv_circulating: 1_000_000,
+            },
+            &OracleBoxData {
+                box_id: BoxId::new("oracle"),
+                nanoerg_per_usd: 1_000_000_000,
+            },
+        )
+    }
+
+    fn action<'a>(advice: &'a BankAdvice, name: &str) -> &'a ActionAdvice {
+        advice.actions.iter().find(|a| a.action == name).unwrap()
+    }
+
+    #[test]
+    fn test_max_amounts_pass_validation() {
+        let s = state(1000, 100);
+        let advice = advise(&s);
+
+        for a in advice.actions.iter().filter(|a| a.allowed) {
+            let parsed: SigmaUsdAction = a.action.parse().unwrap();
+            assert!(bank_quote(&s, parsed, a.max_amount).is_ok(), "{}", a.action);
+        }
+        // 1000% leaves no room to mint SigRSV
+        assert!(!action(&advice, "mint_sigrsv").allowed);
+
+        let mint = action(&advice, "mint_sigusd");
+        assert!(mint.reserve_ratio_at_max_pct >= 400.0);
+        assert!(mint.reserve_ratio_at_max_pct < 400.01);
+        assert!(bank_quote(&s, SigmaUsdAction::MintSigUsd, mint.max_amount + 1).is_err());
+
+        let redeem = action(&advice, "redeem_sigrsv");
+        assert_eq!(redeem.limited_by, "reserve_ratio");
+        assert!(bank_quote(&s, SigmaUsdAction::RedeemSigRsv, redeem.max_amount + 1).is_err());
+    }
+
+    #[test]
+    fn test_break_even_and_prices_include_fee() {
+        let advice = advise(&state(1000, 100));
+
+        let mint = action(&advice, "mint_sigusd");
+        assert!((mint.effective_price_nano - 10_200_000.0).abs() < 1e-6);
+        assert!((mint.round_trip_break_even_pct - 4.0816).abs() < 1e-3);
+
+        let redeem = action(&advice, "redeem_sigusd");
+        assert!((redeem.effective_price_nano - 9_800_000.0).abs() < 1e-6);
+        assert!((redeem.round_trip_break_even_pct + 3.9216).abs() < 1e-3);
+        assert_eq!(redeem.limited_by, "circulating_supply");
+    }
+
+    #[test]
+    fn test_erg_moves_to_ratio_bounds() {
+        let advice = advise(&state(500, 100));
+        // 500%: -20% reaches 400%, +60% reaches 800%
+        assert!((advice.erg_move_to_min_ratio_pct.unwrap() + 20.0).abs() < 1e-9);
+        assert!((advice.erg_move_to_max_ratio_pct.unwrap() - 60.0).abs() < 1e-9);
+        assert!(action(&advice, "mint_sigrsv").allowed);
+
+        let empty = advise(&state(500, 0));
+        assert!(empty.erg_move_to_min_ratio_pct.is_none());
+    }
+}
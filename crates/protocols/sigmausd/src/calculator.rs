@@ -21,6 +21,8 @@ pub struct ProtocolState {
     pub can_mint_sigusd: bool,
     pub can_mint_sigrsv: bool,
     pub can_redeem_sigrsv: bool,
+    /// Exact limits: the largest amounts whose post-action reserve ratio the
+    /// contract still accepts.
     pub max_sigusd_mintable: i64,
     pub max_sigrsv_mintable: i64,
     pub max_sigrsv_redeemable: i64,
@@ -51,9 +53,11 @@ pub fn calculate_state(input: &ProtocolInput) -> ProtocolState {
     let can_mint_sigrsv = reserve_ratio_pct < params::MAX_RESERVE_RATIO_PCT as f64;
     let can_redeem_sigrsv = reserve_ratio_pct > params::MIN_RESERVE_RATIO_PCT as f64;
 
-    let max_sigusd_mintable = calculate_max_sigusd_mintable(input, liabilities_nano);
-    let max_sigrsv_mintable = calculate_max_sigrsv_mintable(input, liabilities_nano);
-    let max_sigrsv_redeemable = calculate_max_sigrsv_redeemable(input, reserve_ratio_pct);
+    let max_sigusd_mintable = calculate_max_sigusd_mintable(input);
+    let max_sigrsv_mintable =
+        calculate_max_sigrsv_mintable(input, liabilities_nano, sigrsv_price_nano);
+    let max_sigrsv_redeemable =
+        calculate_max_sigrsv_redeemable(input, liabilities_nano, sigrsv_price_nano);
 
     ProtocolState {
         reserve_ratio_pct,
@@ -70,51 +74,88 @@ pub fn calculate_state(input: &ProtocolInput) -> ProtocolState {
     }
 }
 
-/// At 400% ratio: max_liabilities = reserves / 4
-fn calculate_max_sigusd_mintable(input: &ProtocolInput, current_liabilities: i128) -> i64 {
-    let max_liabilities = (input.bank_erg_nano as i128) / 4;
-
-    if max_liabilities <= current_liabilities {
-        return 0;
-    }
-
-    let headroom_nano = max_liabilities - current_liabilities;
-    let max_sigusd = (headroom_nano * 100) / (input.nanoerg_per_usd as i128);
-
-    max_sigusd.min(i64::MAX as i128) as i64
+/// Reserve ratio the contract sees after an action: integer percent, or
+/// `None` without liabilities (no ratio bound applies).
+fn post_action_ratio(reserves: i128, liabilities: i128) -> Option<i128> {
+    (liabilities > 0).then(|| reserves * 100 / liabilities)
 }
 
-/// At 800% ratio: target_reserves = 8 * liabilities
-fn calculate_max_sigrsv_mintable(input: &ProtocolInput, current_liabilities: i128) -> i64 {
-    let target_reserves = current_liabilities * 8;
-    let current_reserves = input.bank_erg_nano as i128;
-
-    if target_reserves <= current_reserves {
+/// Largest amount in `1..=hi` accepted by `ok`, or 0. `ok` must be monotone
+/// (accepts everything up to some amount and nothing above it).
+fn max_accepted(hi: i64, ok: impl Fn(i64) -> bool) -> i64 {
+    if hi <= 0 || !ok(1) {
         return 0;
     }
+    let (mut lo, mut hi) = (1i64, hi);
+    while lo < hi {
+        let mid = lo + (hi - lo + 1) / 2;
+        if ok(mid) {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    lo
+}
 
-    let headroom_nano = target_reserves - current_reserves;
-    let current_equity = current_reserves - current_liabilities;
-
-    if current_equity <= 0 || input.sigrsv_circulating == 0 {
+/// Largest mint that keeps the post-mint ratio at 400% or above. The ERG
+/// paid in (2% fee included) counts towards the reserves.
+fn calculate_max_sigusd_mintable(input: &ProtocolInput) -> i64 {
+    if input.nanoerg_per_usd <= 0 {
         return 0;
     }
+    let reserves = input.bank_erg_nano as i128;
+    let rate = input.nanoerg_per_usd as i128;
+    let hi = (reserves * 100 / rate).min(i64::MAX as i128) as i64;
+
+    max_accepted(hi, |amount| {
+        let paid = cost_to_mint_sigusd(amount, input.nanoerg_per_usd).net_amount as i128;
+        let liabilities = (input.sigusd_circulating as i128 + amount as i128) * rate / 100;
+        post_action_ratio(reserves + paid, liabilities)
+            .is_some_and(|ratio| ratio >= params::MIN_RESERVE_RATIO_PCT as i128)
+    })
+}
 
-    let sigrsv_price = current_equity / (input.sigrsv_circulating as i128);
-    if sigrsv_price <= 0 {
+/// Largest mint that keeps the post-mint ratio at 800% or below.
+fn calculate_max_sigrsv_mintable(
+    input: &ProtocolInput,
+    liabilities: i128,
+    sigrsv_price: i64,
+) -> i64 {
+    let reserves = input.bank_erg_nano as i128;
+    // The ratio is floored, so reserves up to (but excluding) 801% still pass
+    let ceiling = liabilities * (params::MAX_RESERVE_RATIO_PCT as i128 + 1) / 100;
+    if sigrsv_price <= 0 || ceiling <= reserves {
         return 0;
     }
+    let hi = ((ceiling - reserves) / sigrsv_price as i128).min(i64::MAX as i128) as i64;
 
-    let max_sigrsv = headroom_nano / sigrsv_price;
-    max_sigrsv.min(i64::MAX as i128) as i64
+    max_accepted(hi, |amount| {
+        let paid = cost_to_mint_sigrsv(amount, sigrsv_price).net_amount as i128;
+        post_action_ratio(reserves + paid, liabilities)
+            .is_some_and(|ratio| ratio <= params::MAX_RESERVE_RATIO_PCT as i128)
+    })
 }
 
-fn calculate_max_sigrsv_redeemable(input: &ProtocolInput, current_ratio: f64) -> i64 {
-    if current_ratio <= params::MIN_RESERVE_RATIO_PCT as f64 {
+/// Largest redemption that keeps the post-redeem ratio at 400% or above.
+fn calculate_max_sigrsv_redeemable(
+    input: &ProtocolInput,
+    liabilities: i128,
+    sigrsv_price: i64,
+) -> i64 {
+    let reserves = input.bank_erg_nano as i128;
+    if liabilities <= 0 {
+        return input.sigrsv_circulating;
+    }
+    if sigrsv_price <= 0 {
         return 0;
     }
 
-    input.sigrsv_circulating
+    max_accepted(input.sigrsv_circulating, |amount| {
+        let paid_out = erg_from_redeem_sigrsv(amount, sigrsv_price).net_amount as i128;
+        post_action_ratio(reserves - paid_out, liabilities)
+            .is_some_and(|ratio| ratio >= params::MIN_RESERVE_RATIO_PCT as i128)
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -262,4 +303,62 @@ mod tests {
         assert!(state.reserve_ratio_pct > 1000.0);
         assert_eq!(state.sigrsv_price_nano, 0);
     }
+
+    /// 1000 ERG backing 100 SigUSD at 1 ERG/USD: 1000% reserve ratio.
+    fn roomy_input() -> ProtocolInput {
+        ProtocolInput {
+            bank_erg_nano: 1_000_000_000_000,
+            sigusd_circulating: 10_000,
+            sigrsv_circulating: 1_000_000,
+            nanoerg_per_usd: 1_000_000_000,
+        }
+    }
+
+    fn ratio_after(input: &ProtocolInput, erg_delta: i64, sigusd_delta: i64) -> f64 {
+        calculate_state(&ProtocolInput {
+            bank_erg_nano: input.bank_erg_nano + erg_delta,
+            sigusd_circulating: input.sigusd_circulating + sigusd_delta,
+            ..input.clone()
+        })
+        .reserve_ratio_pct
+    }
+
+    #[test]
+    fn test_max_sigusd_mintable_is_exact() {
+        let input = roomy_input();
+        let max = calculate_state(&input).max_sigusd_mintable;
+        // (1000 - 400) / 2.98 ERG of base cost, i.e. ~201.34 SigUSD
+        assert_eq!(max, 20_134);
+
+        let at_max = cost_to_mint_sigusd(max, input.nanoerg_per_usd).net_amount;
+        assert!(ratio_after(&input, at_max, max) >= 400.0);
+        let over = cost_to_mint_sigusd(max + 1, input.nanoerg_per_usd).net_amount;
+        assert!(ratio_after(&input, over, max + 1) < 400.0);
+    }
+
+    #[test]
+    fn test_max_sigrsv_redeemable_stops_at_min_ratio() {
+        let input = roomy_input();
+        let state = calculate_state(&input);
+        let max = state.max_sigrsv_redeemable;
+        assert!(max > 0 && max < input.sigrsv_circulating);
+
+        let at_max = erg_from_redeem_sigrsv(max, state.sigrsv_price_nano).net_amount;
+        assert!(ratio_after(&input, -at_max, 0) >= 400.0);
+        let over = erg_from_redeem_sigrsv(max + 1, state.sigrsv_price_nano).net_amount;
+        assert!(ratio_after(&input, -over, 0) < 400.0);
+    }
+
+    #[test]
+    fn test_max_sigrsv_mintable_counts_fee() {
+        let mut input = roomy_input();
+        input.sigusd_circulating = 20_000; // 500%
+        let state = calculate_state(&input);
+        let max = state.max_sigrsv_mintable;
+
+        let at_max = cost_to_mint_sigrsv(max, state.sigrsv_price_nano).net_amount;
+        assert!(ratio_after(&input, at_max, 0) < 801.0);
+        let over = cost_to_mint_sigrsv(max + 1, state.sigrsv_price_nano).net_amount;
+        assert!(ratio_after(&input, over, 0) >= 801.0);
+    }
 }
@@ -0,0 +1,290 @@
+//! Bank history: reserve ratio, supplies and SigRSV price over time.
+//!
+//! Every mint and redeem spends the bank box and recreates it, reading the
+//! oracle pool box as data input 0. Walking the bank NFT's box chain and
+//! pairing each box with the oracle datapoint its transaction used recovers
+//! the protocol state at every bank transition.
+
+use std::collections::{HashMap, HashSet};
+
+use citadel_core::ProtocolError;
+use ergo_node_client::NodeClient;
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::{calculate_state, ProtocolInput};
+use crate::constants::NftIds;
+use crate::state::{parse_bank_registers, parse_oracle_register};
+
+/// Bank and oracle state as of one box in the bank NFT chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BankSnapshot {
+    pub box_id: String,
+    /// Transaction that created this box.
+    pub tx_id: String,
+    pub height: u64,
+    /// Block timestamp in milliseconds.
+    pub timestamp: u64,
+    pub bank_erg_nano: i64,
+    pub sigusd_circulating: i64,
+    pub sigrsv_circulating: i64,
+    /// Oracle datapoint the transition was validated against.
+    pub nanoerg_per_usd: i64,
+    pub reserve_ratio_pct: f64,
+    pub sigrsv_price_nano: i64,
+}
+
+impl BankSnapshot {
+    /// Bank state from a node box JSON (`/blockchain/box/byId`) and the oracle
+    /// rate its creating transaction read.
+    pub fn from_box_json(
+        box_json: &serde_json::Value,
+        nanoerg_per_usd: i64,
+        timestamp: u64,
+    ) -> Option<Self> {
+        let registers = &box_json["additionalRegisters"];
+        let (sigusd_circulating, sigrsv_circulating) = parse_bank_registers(
+            register_hex(&registers["R4"])?,
+            register_hex(&registers["R5"])?,
+        )
+        .ok()?;
+        let bank_erg_nano = box_json["value"].as_i64()?;
+        let state = calculate_state(&ProtocolInput {
+            bank_erg_nano,
+            sigusd_circulating,
+            sigrsv_circulating,
+            nanoerg_per_usd,
+        });
+
+        Some(Self {
+            box_id: box_json["boxId"].as_str()?.to_string(),
+            tx_id: box_json["transactionId"].as_str()?.to_string(),
+            height: box_json["inclusionHeight"]
+                .as_u64()
+                .or_else(|| box_json["settlementHeight"].as_u64())
+                .or_else(|| box_json["creationHeight"].as_u64())
+                .unwrap_or(0),
+            timestamp,
+            bank_erg_nano,
+            sigusd_circulating,
+            sigrsv_circulating,
+            nanoerg_per_usd,
+            reserve_ratio_pct: state.reserve_ratio_pct,
+            sigrsv_price_nano: state.sigrsv_price_nano,
+        })
+    }
+}
+
+/// Reserve ratio, supply and SigRSV price series over the traced history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BankHistory {
+    /// Blocks per sample; 0 keeps every bank transition.
+    pub sample_blocks: u64,
+    /// Oldest height covered by the traced history.
+    pub history_start_height: u64,
+    /// Bank transitions traced before sampling.
+    pub transitions: usize,
+    /// Oldest first.
+    pub points: Vec<BankSnapshot>,
+}
+
+/// Registers come either as plain serialized hex or as
+/// `{ "serializedValue": ... }` depending on the node endpoint.
+fn register_hex(value: &serde_json::Value) -> Option<&str> {
+    value.as_str().or_else(|| value["serializedValue"].as_str())
+}
+
+fn holds_token(box_json: &serde_json::Value, token_id: &str) -> bool {
+    box_json["assets"]
+        .as_array()
+        .is_some_and(|a| a.iter().any(|t| t["tokenId"].as_str() == Some(token_id)))
+}
+
+/// Reduce `snapshots` (oldest first) to the state at the end of each
+/// `sample_blocks`-block window, i.e. the last transition in every window.
+/// Windows without a transition are skipped: the previous point still holds.
+pub fn sample_history(snapshots: &[BankSnapshot], sample_blocks: u64) -> Vec<BankSnapshot> {
+    if sample_blocks == 0 {
+        return snapshots.to_vec();
+    }
+    let mut points: Vec<BankSnapshot> = Vec::new();
+    for snap in snapshots {
+        let window = snap.height / sample_blocks;
+        match points.last_mut() {
+            Some(last) if last.height / sample_blocks == window => *last = snap.clone(),
+            _ => points.push(snap.clone()),
+        }
+    }
+    points
+}
+
+/// Sample `snapshots` (oldest first) into chartable series.
+pub fn bank_history(snapshots: &[BankSnapshot], sample_blocks: u64) -> BankHistory {
+    BankHistory {
+        sample_blocks,
+        history_start_height: snapshots.first().map(|s| s.height).unwrap_or(0),
+        transitions: snapshots.len(),
+        points: sample_history(snapshots, sample_blocks),
+    }
+}
+
+/// Walk the bank NFT's box chain backwards from `start_box_id`, newest first.
+/// Stops after `max_steps` boxes, at the first box in `known_box_ids` (already
+/// cached), once a box is below `min_height`, or at a transition without an
+/// oracle data input (the bank bootstrap).
+///
+/// Also returns whether the walk ended on a known box, i.e. whether the
+/// snapshots join up with the cached ones.
+pub async fn trace_bank_history(
+    node: &NodeClient,
+    nft_ids: &NftIds,
+    start_box_id: &str,
+    known_box_ids: &HashSet<String>,
+    max_steps: usize,
+    min_height: u64,
+) -> Result<(Vec<BankSnapshot>, bool), ProtocolError> {
+    let node_err = |e: citadel_core::NodeError| ProtocolError::StateUnavailable {
+        reason: format!("Bank history: {}", e),
+    };
+    let mut snapshots = Vec::new();
+    // Oracle boxes are refreshed once per epoch, so consecutive bank txs
+    // usually share one
+    let mut oracle_rates: HashMap<String, Option<i64>> = HashMap::new();
+    let mut current_box_id = start_box_id.to_string();
+
+    for _ in 0..max_steps {
+        if known_box_ids.contains(&current_box_id) {
+            break;
+        }
+        let current_box = node
+            .get_blockchain_box_by_id(&current_box_id)
+            .await
+            .map_err(node_err)?;
+        let Some(tx_id) = current_box["transactionId"].as_str() else {
+            break;
+        };
+        let tx = node.get_transaction_by_id(tx_id).await.map_err(node_err)?;
+        let timestamp = tx["timestamp"].as_u64().unwrap_or(0);
+
+        let Some(oracle_box_id) = tx["dataInputs"][0]["boxId"].as_str() else {
+            break;
+        };
+        let rate = match oracle_rates.get(oracle_box_id) {
+            Some(rate) => *rate,
+            None => {
+                let rate = match node.get_blockchain_box_by_id(oracle_box_id).await {
+                    Ok(b) if holds_token(&b, &nft_ids.oracle_pool_nft) => {
+                        register_hex(&b["additionalRegisters"]["R4"])
+                            .and_then(|hex| parse_oracle_register(hex).ok())
+                    }
+                    _ => None,
+                };
+                oracle_rates.insert(oracle_box_id.to_string(), rate);
+                rate
+            }
+        };
+        let Some(snapshot) =
+            rate.and_then(|r| BankSnapshot::from_box_json(&current_box, r, timestamp))
+        else {
+            break;
+        };
+        let height = snapshot.height;
+        snapshots.push(snapshot);
+        if height < min_height {
+            break;
+        }
+
+        let mut prev_box_id = None;
+        for input in tx["inputs"].as_array().map(Vec::as_slice).unwrap_or(&[]) {
+            let Some(input_id) = input["boxId"].as_str() else {
+                continue;
+            };
+            let input_box = match input["assets"].is_array() {
+                true => input.clone(),
+                false => match node.get_blockchain_box_by_id(input_id).await {
+                    Ok(b) => b,
+                    Err(_) => continue,
+                },
+            };
+            if holds_token(&input_box, &nft_ids.bank_nft) {
+                prev_box_id = Some(input_id.to_string());
+                break;
+            }
+        }
+        match prev_box_id {
+            Some(id) => current_box_id = id,
+            None => break,
+        }
+    }
+
+    let reached_known = known_box_ids.contains(&current_box_id);
+    Ok((snapshots, reached_known))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ergo_tx::encode_sigma_long;
+
+    fn snap(height: u64, sigusd: i64) -> BankSnapshot {
+        BankSnapshot {
+            box_id: format!("box{}", height),
+            tx_id: format!("tx{}", height),
+            height,
+            timestamp: height * 120_000,
+            bank_erg_nano: 1_000_000_000_000,
+            sigusd_circulating: sigusd,
+            sigrsv_circulating: 1_000_000,
+            nanoerg_per_usd: 1_000_000_000,
+            reserve_ratio_pct: 0.0,
+            sigrsv_price_nano: 0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_from_box_json() {
+        let box_json = serde_json::json!({
+            "boxId": "bank1",
+            "transactionId": "tx1",
+            "value": 1_000_000_000_000i64,
+            "inclusionHeight": 1_200_000,
+            "additionalRegisters": {
+                "R4": encode_sigma_long(10_000),
+                "R5": { "serializedValue": encode_sigma_long(1_000_000) },
+            },
+        });
+
+        // 100 SigUSD at 1 ERG/USD: 100 ERG liabilities against 1000 ERG
+        let snap = BankSnapshot::from_box_json(&box_json, 1_000_000_000, 7).unwrap();
+        assert_eq!(snap.height, 1_200_000);
+        assert_eq!(snap.sigusd_circulating, 10_000);
+        assert_eq!(snap.sigrsv_circulating, 1_000_000);
+        assert!((snap.reserve_ratio_pct - 1000.0).abs() < 1e-9);
+        assert_eq!(snap.sigrsv_price_nano, 900_000);
+    }
+
+    #[test]
+    fn test_snapshot_missing_registers() {
+        let box_json = serde_json::json!({
+            "boxId": "bank1",
+            "transactionId": "tx1",
+            "value": 1_000_000_000i64,
+            "additionalRegisters": {},
+        });
+        assert!(BankSnapshot::from_box_json(&box_json, 1_000_000_000, 0).is_none());
+    }
+
+    #[test]
+    fn test_sample_keeps_last_per_window() {
+        let snaps = vec![snap(100, 1), snap(150, 2), snap(199, 3), snap(420, 4)];
+
+        let sampled = sample_history(&snaps, 100);
+        let heights: Vec<u64> = sampled.iter().map(|s| s.height).collect();
+        assert_eq!(heights, vec![199, 420]);
+        assert_eq!(sampled[0].sigusd_circulating, 3);
+
+        assert_eq!(sample_history(&snaps, 0).len(), 4);
+        let history = bank_history(&snaps, 100);
+        assert_eq!(history.transitions, 4);
+        assert_eq!(history.history_start_height, 100);
+    }
+}
@@ -24,14 +24,20 @@
 //! println!("Reserve ratio: {:.2}%", state.reserve_ratio_pct);
 //! ```
 
+pub mod advisor;
 pub mod calculator;
 pub mod constants;
 pub mod fetch;
+pub mod history;
+pub mod simulator;
 pub mod state;
 pub mod tx_builder;
 
+pub use advisor::{advise, bank_quote, ActionAdvice, BankAdvice};
 pub use calculator::*;
 pub use citadel_core::BoxId;
 pub use constants::*;
 pub use fetch::{fetch_oracle_price, fetch_sigmausd_state, OraclePrice};
+pub use history::{bank_history, sample_history, trace_bank_history, BankHistory, BankSnapshot};
+pub use simulator::{simulate, Holdings, Scenario, SimulationResult};
 pub use state::*;
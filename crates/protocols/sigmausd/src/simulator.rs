@@ -0,0 +1,359 @@
+//! What-if simulation: bank state under ERG price shocks and hypothetical
+//! mint/redeem flows.
+//!
+//! Everything runs through the same calculator and validators the tx builders
+//! use, so a flow the simulation rejects would also be rejected on-chain (with
+//! the same `RatioOutOfBounds` error).
+
+use citadel_core::{BoxId, ProtocolError};
+use serde::{Deserialize, Serialize};
+
+use crate::calculator::{
+    cost_to_mint_sigrsv, cost_to_mint_sigusd, erg_from_redeem_sigrsv, erg_from_redeem_sigusd,
+};
+use crate::state::{BankBoxData, OracleBoxData, SigmaUsdState};
+use crate::tx_builder::{
+    validate_mint_sigrsv, validate_mint_sigusd, validate_redeem_sigrsv, validate_redeem_sigusd,
+    SigmaUsdAction,
+};
+
+/// SigUSD and SigRSV held by the user (raw units).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Holdings {
+    pub sigusd: i64,
+    pub sigrsv: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Scenario {
+    /// ERG price change against USD in percent: -50.0 halves the ERG price.
+    pub erg_price_change_pct: f64,
+    /// Applied in order after the price shock.
+    pub flows: Vec<(SigmaUsdAction, i64)>,
+}
+
+/// Bank state at one point of the simulation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedState {
+    pub nanoerg_per_usd: i64,
+    pub bank_erg_nano: i64,
+    pub sigusd_circulating: i64,
+    pub sigrsv_circulating: i64,
+    pub reserve_ratio_pct: f64,
+    pub sigrsv_price_nano: i64,
+    /// Actions the reserve ratio rules out ("mint_sigusd", "mint_sigrsv",
+    /// "redeem_sigrsv"). SigUSD redemption is never blocked.
+    pub blocked_actions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowOutcome {
+    pub action: String,
+    pub amount: i64,
+    pub applied: bool,
+    /// ERG into the bank (negative for redemptions), fees included.
+    pub erg_delta_nano: i64,
+    /// Validation error when the flow is not applied.
+    pub error: Option<String>,
+    pub reserve_ratio_after_pct: f64,
+}
+
+/// Redemption value of [`Holdings`] against one bank state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingsValue {
+    pub sigusd_erg_nano: i64,
+    pub sigrsv_erg_nano: i64,
+    pub total_erg_nano: i64,
+    pub total_usd: f64,
+    /// False when the reserve ratio blocks SigRSV redemption: the SigRSV
+    /// value is then only on paper.
+    pub sigrsv_redeemable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub erg_price_change_pct: f64,
+    pub before: SimulatedState,
+    /// After the price shock, before any flow.
+    pub shocked: SimulatedState,
+    pub after: SimulatedState,
+    pub flows: Vec<FlowOutcome>,
+    pub holdings_before: HoldingsValue,
+    pub holdings_after: HoldingsValue,
+    /// Change of the holdings' USD value; `None` when they were worth nothing.
+    pub holdings_change_pct: Option<f64>,
+}
+
+/// `state` with new bank/oracle figures and all derived fields recomputed.
+fn rebuild(
+    state: &SigmaUsdState,
+    bank_erg_nano: i64,
+    sigusd_circulating: i64,
+    sigrsv_circulating: i64,
+    nanoerg_per_usd: i64,
+) -> SigmaUsdState {
+    SigmaUsdState::from_boxes(
+        &BankBoxData {
+            box_id: BoxId::new(&state.bank_box_id),
+            value_nano: bank_erg_nano,
+            sigusd_circulating,
+            sigrsv_circulating,
+        },
+        &OracleBoxData {
+            box_id: BoxId::new(&state.oracle_box_id),
+            nanoerg_per_usd,
+        },
+    )
+}
+
+fn snapshot(state: &SigmaUsdState) -> SimulatedState {
+    let blocked_actions = [
+        (!state.can_mint_sigusd, SigmaUsdAction::MintSigUsd),
+        (!state.can_mint_sigrsv, SigmaUsdAction::MintSigRsv),
+        (!state.can_redeem_sigrsv, SigmaUsdAction::RedeemSigRsv),
+    ]
+    .into_iter()
+    .filter(|(blocked, _)| *blocked)
+    .map(|(_, action)| action.as_str().to_string())
+    .collect();
+
+    SimulatedState {
+        nanoerg_per_usd: state.oracle_erg_per_usd_nano,
+        bank_erg_nano: state.bank_erg_nano,
+        sigusd_circulating: state.sigusd_circulating,
+        sigrsv_circulating: state.sigrsv_circulating,
+        reserve_ratio_pct: state.reserve_ratio_pct,
+        sigrsv_price_nano: state.sigrsv_price_nano,
+        blocked_actions,
+    }
+}
+
+/// SigUSD redemption price: the oracle rate, or the pro-rata share of the
+/// reserves once the bank is under-collateralized.
+fn sigusd_redeem_rate(state: &SigmaUsdState) -> i64 {
+    if state.sigusd_circulating <= 0 {
+        return state.oracle_erg_per_usd_nano;
+    }
+    let pro_rata = state.bank_erg_nano as i128 * 100 / state.sigusd_circulating as i128;
+    (state.oracle_erg_per_usd_nano as i128).min(pro_rata) as i64
+}
+
+fn holdings_value(state: &SigmaUsdState, holdings: &Holdings) -> HoldingsValue {
+    let sigusd_erg_nano = if holdings.sigusd > 0 {
+        erg_from_redeem_sigusd(holdings.sigusd, sigusd_redeem_rate(state)).net_amount
+    } else {
+        0
+    };
+    let sigrsv_erg_nano = if holdings.sigrsv > 0 {
+        erg_from_redeem_sigrsv(holdings.sigrsv, state.sigrsv_price_nano).net_amount
+    } else {
+        0
+    };
+    let total_erg_nano = sigusd_erg_nano.saturating_add(sigrsv_erg_nano);
+    let total_usd = if state.oracle_erg_per_usd_nano > 0 {
+        total_erg_nano as f64 / state.oracle_erg_per_usd_nano as f64
+    } else {
+        0.0
+    };
+
+    HoldingsValue {
+        sigusd_erg_nano,
+        sigrsv_erg_nano,
+        total_erg_nano,
+        total_usd,
+        sigrsv_redeemable: state.can_redeem_sigrsv,
+    }
+}
+
+/// Apply one flow; returns the new state and the ERG moved into the bank.
+fn apply_flow(
+    state: &SigmaUsdState,
+    action: SigmaUsdAction,
+    amount: i64,
+) -> Result<(SigmaUsdState, i64), ProtocolError> {
+    let (erg_delta, sigusd_delta, sigrsv_delta) = match action {
+        SigmaUsdAction::MintSigUsd => {
+            validate_mint_sigusd(amount, state)?;
+            let cost = cost_to_mint_sigusd(amount, state.oracle_erg_per_usd_nano);
+            (cost.net_amount, amount, 0)
+        }
+        SigmaUsdAction::RedeemSigUsd => {
+            validate_redeem_sigusd(amount, state)?;
+            let out = erg_from_redeem_sigusd(amount, sigusd_redeem_rate(state));
+            (-out.net_amount, -amount, 0)
+        }
+        SigmaUsdAction::MintSigRsv => {
+            validate_mint_sigrsv(amount, state)?;
+            let cost = cost_to_mint_sigrsv(amount, state.sigrsv_price_nano);
+            (cost.net_amount, 0, amount)
+        }
+        SigmaUsdAction::RedeemSigRsv => {
+            validate_redeem_sigrsv(amount, state)?;
+            let out = erg_from_redeem_sigrsv(amount, state.sigrsv_price_nano);
+            (-out.net_amount, 0, -amount)
+        }
+    };
+
+    let next = rebuild(
+        state,
+        state.bank_erg_nano + erg_delta,
+        state.sigusd_circulating + sigusd_delta,
+        state.sigrsv_circulating + sigrsv_delta,
+        state.oracle_erg_per_usd_nano,
+    );
+    Ok((next, erg_delta))
+}
+
+/// Run `scenario` against `state`. Flows that fail validation are reported
+/// and skipped; later flows still run against the state without them.
+pub fn simulate(
+    state: &SigmaUsdState,
+    scenario: &Scenario,
+    holdings: &Holdings,
+) -> Result<SimulationResult, ProtocolError> {
+    if !scenario.erg_price_change_pct.is_finite() || scenario.erg_price_change_pct <= -100.0 {
+        return Err(ProtocolError::InvalidAmount {
+            message: "ERG price change must be above -100%".to_string(),
+        });
+    }
+
+    // nanoERG per USD moves inversely to the ERG price
+    let shocked_rate = (state.oracle_erg_per_usd_nano as f64
+        / (1.0 + scenario.erg_price_change_pct / 100.0))
+        .round()
+        .min(i64::MAX as f64) as i64;
+    let shocked = rebuild(
+        state,
+        state.bank_erg_nano,
+        state.sigusd_circulating,
+        state.sigrsv_circulating,
+        shocked_rate.max(1),
+    );
+
+    let mut current = shocked.clone();
+    let mut flows = Vec::with_capacity(scenario.flows.len());
+    for &(action, amount) in &scenario.flows {
+        let outcome = match apply_flow(&current, action, amount) {
+            Ok((next, erg_delta)) => {
+                current = next;
+                FlowOutcome {
+                    action: action.as_str().to_string(),
+                    amount,
+                    applied: true,
+                    erg_delta_nano: erg_delta,
+                    error: None,
+                    reserve_ratio_after_pct: current.reserve_ratio_pct,
+                }
+            }
+            Err(e) => FlowOutcome {
+                action: action.as_str().to_string(),
+                amount,
+                applied: false,
+                erg_delta_nano: 0,
+                error: Some(e.to_string()),
+                reserve_ratio_after_pct: current.reserve_ratio_pct,
+            },
+        };
+        flows.push(outcome);
+    }
+
+    let holdings_before = holdings_value(state, holdings);
+    let holdings_after = holdings_value(&current, holdings);
+    let holdings_change_pct = (holdings_before.total_usd > 0.0).then(|| {
+        (holdings_after.total_usd - holdings_before.total_usd) / holdings_before.total_usd * 100.0
+    });
+
+    Ok(SimulationResult {
+        erg_price_change_pct: scenario.erg_price_change_pct,
+        before: snapshot(state),
+        shocked: snapshot(&shocked),
+        after: snapshot(&current),
+        flows,
+        holdings_before,
+        holdings_after,
+        holdings_change_pct,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1000 ERG backing 100 SigUSD at 1 ERG/USD: 1000% reserve ratio.
+    fn state() -> SigmaUsdState {
+        let base = SigmaUsdState::from_boxes(
+            &BankBoxData {
+                box_id: BoxId::new("bank"),
+                value_nano: 1_000_000_000_000,
+                sigusd_circulating: 10_000,
+                sigrsv_circulating: 1_000_000,
+            },
+            &OracleBoxData {
+                box_id: BoxId::new("oracle"),
+                nanoerg_per_usd: 1_000_000_000,
+            },
+        );
+        assert!((base.reserve_ratio_pct - 1000.0).abs() < 1e-9);
+        base
+    }
+
+    fn scenario(pct: f64, flows: Vec<(SigmaUsdAction, i64)>) -> Scenario {
+        Scenario {
+            erg_price_change_pct: pct,
+            flows,
+        }
+    }
+
+    #[test]
+    fn test_price_crash_blocks_sigusd_mint() {
+        // ERG -70%: liabilities triple to 333 ERG, ratio ~300%
+        let result = simulate(&state(), &scenario(-70.0, vec![]), &Holdings::default()).unwrap();
+        assert!(result
+            .before
+            .blocked_actions
+            .contains(&"mint_sigrsv".to_string()));
+        assert!((result.shocked.reserve_ratio_pct - 300.0).abs() < 0.01);
+        assert_eq!(
+            result.shocked.blocked_actions,
+            vec!["mint_sigusd".to_string(), "redeem_sigrsv".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_blocked_flow_reports_ratio_error() {
+        let flows = vec![
+            (SigmaUsdAction::RedeemSigRsv, 1_000),
+            (SigmaUsdAction::RedeemSigUsd, 1_000),
+        ];
+        let result = simulate(&state(), &scenario(-70.0, flows), &Holdings::default()).unwrap();
+
+        assert!(!result.flows[0].applied);
+        assert!(result.flows[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("redeem SigRSV"));
+        // SigUSD redemption is always allowed and lowers liabilities
+        assert!(result.flows[1].applied);
+        assert!(result.flows[1].erg_delta_nano < 0);
+        assert_eq!(result.after.sigusd_circulating, 9_000);
+    }
+
+    #[test]
+    fn test_sigrsv_holdings_absorb_crash() {
+        let holdings = Holdings {
+            sigusd: 0,
+            sigrsv: 100_000,
+        };
+        let result = simulate(&state(), &scenario(-50.0, vec![]), &holdings).unwrap();
+
+        // Equity falls from 900 to 800 ERG while ERG halves in USD
+        let change = result.holdings_change_pct.unwrap();
+        assert!((change - (800.0 * 0.5 / 900.0 - 1.0) * 100.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_invalid_shock() {
+        assert!(simulate(&state(), &scenario(-100.0, vec![]), &Holdings::default()).is_err());
+    }
+}
@@ -9,7 +9,7 @@
 use std::fmt;
 use std::str::FromStr;
 
-use citadel_core::{constants, ProtocolError, TxError};
+use citadel_core::{constants, IntentAsset, ProtocolError, TxError, TxIntent};
 use ergo_tx::{
     append_change_output, append_dev_fee_output, collect_change_tokens, encode_sigma_long,
     resolved_dev_fee_config, select_inputs_for_spend, Eip12Asset, Eip12DataInputBox, Eip12InputBox,
@@ -137,6 +137,38 @@ pub struct TxSummary {
     pub citadel_fee_nano: i64,
 }
 
+impl TxSummary {
+    /// Common intent description (signing page, journal, ErgoPay message).
+    pub fn intent(&self, nft_ids: &NftIds) -> TxIntent {
+        let (token_id, decimals) = if self.token_name == "SigRSV" {
+            (nft_ids.sigrsv_token.as_str(), 0)
+        } else {
+            (nft_ids.sigusd_token.as_str(), 2)
+        };
+        let erg = IntentAsset::erg(self.erg_amount_nano.max(0) as u64);
+        let token = IntentAsset::named(
+            token_id,
+            &self.token_name,
+            self.token_amount.max(0) as u64,
+            decimals,
+        );
+        let intent = if self.action.starts_with("mint") {
+            TxIntent::new("SigmaUSD", format!("Mint {}", self.token_name))
+                .pays(erg)
+                .receives(token)
+        } else {
+            TxIntent::new("SigmaUSD", format!("Redeem {}", self.token_name))
+                .pays(token)
+                .receives(erg)
+        };
+        intent
+            .miner_fee(self.tx_fee_nano.max(0) as u64)
+            .protocol_fee(self.protocol_fee_nano.max(0) as u64)
+            .citadel_fee(self.citadel_fee_nano.max(0) as u64)
+            .counterparty("bank", "SigmaUSD bank")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BuildResult {
     pub unsigned_tx: Eip12UnsignedTx,
@@ -238,6 +270,15 @@ pub fn validate_redeem_sigrsv(amount: i64, state: &SigmaUsdState) -> Result<(),
         });
     }
 
+    if amount > state.max_sigrsv_redeemable {
+        return Err(ProtocolError::InvalidAmount {
+            message: format!(
+                "Amount {} exceeds max redeemable {}",
+                amount, state.max_sigrsv_redeemable
+            ),
+        });
+    }
+
     Ok(())
 }
 
@@ -794,4 +835,26 @@ mod tests {
         assert_eq!(SigmaUsdAction::MintSigUsd.as_str(), "mint_sigusd");
         assert_eq!(SigmaUsdAction::RedeemSigRsv.as_str(), "redeem_sigrsv");
     }
+
+    #[test]
+    fn test_summary_intent() {
+        let nft_ids = NftIds::for_network(citadel_core::Network::Mainnet).unwrap();
+        let summary = TxSummary {
+            action: "redeem_sigusd".to_string(),
+            erg_amount_nano: 5_000_000_000,
+            token_amount: 1_050,
+            token_name: "SigUSD".to_string(),
+            protocol_fee_nano: 10_000_000,
+            tx_fee_nano: constants::TX_FEE_NANO,
+            citadel_fee_nano: 0,
+        };
+        let intent = summary.intent(&nft_ids);
+        assert_eq!(intent.action, "Redeem SigUSD");
+        assert_eq!(intent.assets_in[0].token_id, nft_ids.sigusd_token);
+        assert_eq!(
+            intent.headline(),
+            "SigmaUSD: Redeem SigUSD (10.5 SigUSD → 5 ERG)"
+        );
+        assert_eq!(intent.fees.protocol_nano, 10_000_000);
+    }
 }
@@ -5,6 +5,7 @@ import { WalletConnect } from './components/WalletConnect'
 import { NotificationBell } from './components/NotificationBell'
 import { ToastStack } from './components/Toast'
 import { useNotifications } from './hooks/useNotifications'
+import { useArbMonitor } from './hooks/useArbMonitor'
 import { Sidebar } from './components/Sidebar'
 import { Dashboard } from './components/Dashboard'
 import { SigmaUsdTab } from './components/SigmaUsdTab'
@@ -108,6 +109,7 @@ function App() {
   const [discoveredNodes, setDiscoveredNodes] = useState<NodeProbeResult[]>([])
   const [discovering, setDiscovering] = useState(false)
   const { notifications, unreadCount, pendingCount, markAllRead } = useNotifications()
+  const { alerts: arbAlerts, clearAlerts: clearArbAlerts } = useArbMonitor()
 
   const clearPendingRoute = useCallback(() => setExplorerPendingRoute(null), [])
 
@@ -555,6 +557,8 @@ function App() {
             <ArbScannerTab
               walletAddress={walletAddress}
               onBalanceRefresh={fetchWalletBalance}
+              monitorAlerts={arbAlerts}
+              onClearMonitorAlerts={clearArbAlerts}
             />
           )}
 
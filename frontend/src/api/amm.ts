@@ -9,6 +9,8 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { TokenWarning } from './tokens'
+import type { TxIntent } from './types'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -24,6 +26,9 @@ export interface TokenAmount {
   amount: number
   decimals?: number
   name?: string
+  /** On the verified token list */
+  verified?: boolean
+  warning?: TokenWarning | null
 }
 
 /**
@@ -79,6 +84,8 @@ export interface SwapQuote {
   effective_rate: number
   /** Suggested minimum output with default slippage */
   min_output_suggested: number
+  /** Unconfirmed swaps the quoted pool state builds on (mempool mode only) */
+  pending_tx_ids?: string[]
 }
 
 // =============================================================================
@@ -107,13 +114,15 @@ export async function getAmmQuote(
   poolId: string,
   inputType: 'erg' | 'token',
   amount: number,
-  tokenId?: string
+  tokenId?: string,
+  includeMempool?: boolean
 ): Promise<SwapQuote> {
   return await invoke<SwapQuote>('get_amm_quote', {
     poolId,
     inputType,
     amount,
     tokenId,
+    includeMempool,
   })
 }
 
@@ -159,6 +168,7 @@ export interface SwapPreviewResponse {
 export interface SwapBuildResponse {
   unsigned_tx: object
   summary: SwapTxSummary
+  intent: TxIntent
 }
 
 export interface SwapTxSummary {
@@ -237,11 +247,16 @@ export interface DirectSwapPreviewResponse {
   miner_fee_nano: number
   citadel_fee_nano: number
   total_erg_cost_nano: number
+  /** Unconfirmed swaps the quoted pool state builds on (mempool mode only) */
+  pending_tx_ids?: string[]
 }
 
 export interface DirectSwapBuildResponse {
   unsigned_tx: object
   summary: DirectSwapSummary
+  intent: TxIntent
+  /** Unconfirmed swaps the spent pool box depends on */
+  pending_tx_ids?: string[]
 }
 
 export interface DirectSwapSummary {
@@ -265,6 +280,7 @@ export async function previewDirectSwap(
   amount: number,
   tokenId?: string,
   slippage?: number,
+  includeMempool?: boolean,
 ): Promise<DirectSwapPreviewResponse> {
   return await invoke<DirectSwapPreviewResponse>('preview_direct_swap', {
     poolId,
@@ -272,6 +288,7 @@ export async function previewDirectSwap(
     amount,
     tokenId,
     slippage,
+    includeMempool,
   })
 }
 
@@ -287,6 +304,8 @@ export async function buildDirectSwapTx(
   recipientAddress?: string | null,
   // Optional custom miner fee in nanoERG. Omit (or pass null) for default.
   minerFeeNano?: number | null,
+  // Spend the latest unconfirmed pool box instead of the confirmed one.
+  includeMempool?: boolean,
 ): Promise<DirectSwapBuildResponse> {
   return await invoke<DirectSwapBuildResponse>('build_direct_swap_tx', {
     poolId,
@@ -299,6 +318,7 @@ export async function buildDirectSwapTx(
     currentHeight,
     recipientAddress: recipientAddress || null,
     minerFeeNano: minerFeeNano ?? null,
+    includeMempool,
   })
 }
 
@@ -331,6 +351,7 @@ export interface AmmLpRedeemPreviewResponse {
 export interface AmmLpBuildResponse {
   unsignedTx: object
   summary: object
+  intent: TxIntent
 }
 
 // =============================================================================
@@ -509,3 +530,138 @@ export async function buildPoolCreateTx(
     currentHeight,
   })
 }
+
+// =============================================================================
+// Guided Pool Launch
+// =============================================================================
+
+/** Where the launch price comes from. */
+export type PriceAnchor =
+  | { kind: 'price'; price: number }
+  | { kind: 'pool'; pool_id: string }
+  /** ERG/USD oracle rate with Y as a 1 USD token (N2T only) */
+  | { kind: 'oracle_usd' }
+
+export interface PoolLaunchRequest {
+  poolType: 'N2T' | 'T2T'
+  /** Omit for N2T (X is ERG) */
+  xTokenId?: string | null
+  yTokenId: string
+  /** X held by the pool box (nanoERG for N2T) */
+  xAmount: number
+  anchor: PriceAnchor
+  feePercent: number
+  allowDuplicate?: boolean
+}
+
+export interface PoolLaunchPlan {
+  pool_type: string
+  x_token_id: string | null
+  y_token_id: string
+  x_amount: number
+  y_amount: number
+  fee_num: number
+  pool_x_reserve: number
+  pool_y_reserve: number
+  user_lp_share: number
+  lp_locked: number
+  lp_burned: number
+  pool_share_percent: number
+  target_price: number
+  post_launch_price: number
+  miner_fee: number
+  total_erg_cost: number
+}
+
+export interface ExistingPool {
+  poolId: string
+  poolType: string
+  feeNum: number
+  feeDenom: number
+  /** Y per X in display units, oriented like the requested pair */
+  price: number
+}
+
+export interface PoolLaunchPreviewResponse {
+  plan: PoolLaunchPlan
+  xDecimals: number
+  yDecimals: number
+  existingPools: ExistingPool[]
+}
+
+export interface PoolLaunchLeg {
+  txId: string
+  unsignedTx: object
+}
+
+export interface PoolLaunchBuildResponse {
+  /** Bootstrap then create; sign with startArbLegSign, submit with submitArbChain */
+  legs: PoolLaunchLeg[]
+  plan: PoolLaunchPlan
+  poolNftId: string
+  lpTokenId: string
+}
+
+export async function previewPoolLaunch(
+  request: PoolLaunchRequest,
+): Promise<PoolLaunchPreviewResponse> {
+  return await invoke<PoolLaunchPreviewResponse>('preview_pool_launch', { request })
+}
+
+export async function buildPoolLaunchTx(
+  request: PoolLaunchRequest,
+  userUtxos: object[],
+  currentHeight: number,
+): Promise<PoolLaunchBuildResponse> {
+  return await invoke<PoolLaunchBuildResponse>('build_pool_launch_tx', {
+    request,
+    userUtxos,
+    currentHeight,
+  })
+}
+
+// =============================================================================
+// Pool Analytics
+// =============================================================================
+
+export type PoolEventKind = 'swap_x_for_y' | 'swap_y_for_x' | 'deposit' | 'redeem'
+
+export interface PoolEvent {
+  tx_id: string
+  height: number
+  timestamp: number
+  kind: PoolEventKind
+  volume_x: number
+  fee_x: number
+  price_x_per_y: number
+  tvl_x: number
+}
+
+/**
+ * Volume, fees and LP APR from the pool's box history. Amounts are raw
+ * units of the X side (nanoERG for N2T pools).
+ */
+export interface PoolAnalytics {
+  pool_id: string
+  pool_type: 'N2T' | 'T2T'
+  tvl_x: number
+  price_x_per_y: number
+  volume_x_24h: number
+  volume_x_7d: number
+  volume_x_30d: number
+  fees_x_24h: number
+  fees_x_7d: number
+  fees_x_30d: number
+  swaps_24h: number
+  fee_apr_7d: number | null
+  fee_apr_30d: number | null
+  history_start: number
+  events: PoolEvent[]
+}
+
+export async function getPoolAnalytics(
+  poolId: string,
+  maxSteps?: number,
+): Promise<PoolAnalytics> {
+  return await invoke<PoolAnalytics>('get_pool_analytics', { poolId, maxSteps })
+}
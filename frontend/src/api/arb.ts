@@ -173,3 +173,67 @@ export async function submitArbChain(
     requestIds,
   })
 }
+
+// =============================================================================
+// Background arb monitor (per-block checks, `arb-opportunity` events)
+// =============================================================================
+
+export interface ArbMonitorConfig {
+  enabled: boolean
+  min_net_profit_nano: number
+  max_hops: number
+  build_chains: boolean
+}
+
+export type ArbKind = 'circular' | 'oracle'
+
+export interface ArbOpportunityRecord {
+  kind: ArbKind
+  path_label: string
+  pool_ids: string[]
+  input_nano: number
+  net_profit_nano: number
+}
+
+/** Payload of the `arb-opportunity` event. */
+export interface ArbAlert extends ArbOpportunityRecord {
+  height: number
+  chain: ArbChainBuild | null
+  chain_error: string | null
+}
+
+export interface ArbObservation {
+  height: number
+  timestamp: number
+  opportunities: ArbOpportunityRecord[]
+}
+
+export interface ArbMonitorStats {
+  blocks_scanned: number
+  blocks_with_opportunity: number
+  circular_opportunities: number
+  oracle_opportunities: number
+  best_net_profit_nano: number
+  total_best_net_profit_nano: number
+}
+
+export interface ArbMonitorResponse {
+  config: ArbMonitorConfig
+  last_checked_height: number
+  stats: ArbMonitorStats
+  history: ArbObservation[]
+}
+
+export async function getArbMonitor(): Promise<ArbMonitorResponse> {
+  return await invoke<ArbMonitorResponse>('get_arb_monitor')
+}
+
+export async function setArbMonitorConfig(
+  config: ArbMonitorConfig,
+): Promise<ArbMonitorConfig> {
+  return await invoke<ArbMonitorConfig>('set_arb_monitor_config', { config })
+}
+
+export async function clearArbMonitorHistory(): Promise<void> {
+  return await invoke<void>('clear_arb_monitor_history')
+}
@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { TxIntent } from './types'
 
 
 // =============================================================================
@@ -19,6 +20,7 @@ export interface BurnBuildResponse {
   minerFee: number
   citadelFeeNano: number
   changeErg: number
+  intent: TxIntent
 }
 
 // Multi-burn types
@@ -41,6 +43,7 @@ export interface MultiBurnBuildResponse {
   minerFee: number
   citadelFeeNano: number
   changeErg: number
+  intent: TxIntent
 }
 
 
@@ -0,0 +1,82 @@
+/**
+ * Singleton Contention API
+ *
+ * SigmaUSD, Dexy and HodlCoin txs all spend one singleton bank/LP box, so
+ * concurrent users race for it. These wrappers report pending spends of those
+ * boxes and check whether a built tx was invalidated by a competing spend.
+ *
+ * Commands:
+ * - get_contention_status: Pending (unconfirmed) successors of a protocol's singletons
+ * - check_tx_inputs: Whether a built tx still has all its inputs unspent
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+export type ContentionProtocol = 'sigmausd' | 'dexy' | 'hodlcoin'
+
+export interface SingletonStatus {
+  /** "bank" or "lp" */
+  label: string
+  token_id: string
+  confirmed_box_id: string
+  /** Latest unconfirmed successor, or the confirmed box when nothing is pending */
+  latest_box_id: string
+  /** Unconfirmed spends between the two, oldest first */
+  pending_tx_ids: string[]
+}
+
+export interface ContentionStatusResponse {
+  protocol: string
+  singletons: SingletonStatus[]
+  /** Non-zero means a build on confirmed state will be rejected */
+  pending_count: number
+}
+
+export interface SpentInput {
+  box_id: string
+  /** Competing mempool tx, or null once the spend is confirmed */
+  spent_by: string | null
+}
+
+export interface TxInputCheckResponse {
+  /** Another tx spent one of the inputs; the tx must be rebuilt */
+  invalidated: boolean
+  /** Our own signed tx, if it is already in the mempool */
+  submitted_tx_id: string | null
+  spent_inputs: SpentInput[]
+}
+
+// =============================================================================
+// API Functions
+// =============================================================================
+
+/**
+ * Pending spends of a protocol's singleton boxes.
+ *
+ * @param protocol - "sigmausd", "dexy" or "hodlcoin"
+ * @param key - Dexy variant ("gold" / "usd") or HodlCoin bank singleton token ID
+ */
+export async function getContentionStatus(
+  protocol: ContentionProtocol,
+  key?: string,
+): Promise<ContentionStatusResponse> {
+  return await invoke<ContentionStatusResponse>('get_contention_status', {
+    protocol,
+    key: key ?? null,
+  })
+}
+
+/**
+ * Check a built (unsubmitted) tx for inputs spent by a competing tx.
+ *
+ * @param unsignedTx - EIP-12 unsigned transaction returned by a build command
+ */
+export async function checkTxInputs(
+  unsignedTx: object,
+): Promise<TxInputCheckResponse> {
+  return await invoke<TxInputCheckResponse>('check_tx_inputs', { unsignedTx })
+}
@@ -0,0 +1,106 @@
+/**
+ * Dexy Keeper API
+ *
+ * TypeScript types and invoke wrappers for the Dexy maintenance (keeper)
+ * transactions: tracking updates, bank intervention, extract to / release
+ * from the future.
+ *
+ * Commands:
+ * - get_dexy_keeper_status: Which keeper actions are executable right now
+ * - build_dexy_keeper_tx: Build an unsigned keeper transaction
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+import type { DexyVariant } from './dexySwap'
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+export type KeeperAction =
+  | 'tracking_95'
+  | 'tracking_98'
+  | 'tracking_101'
+  | 'intervention'
+  | 'extract'
+  | 'release'
+
+export interface KeeperActionStatus {
+  action: KeeperAction
+  executable: boolean
+  /** What the tx would do, or why it cannot run yet */
+  detail: string
+}
+
+/**
+ * Response from get_dexy_keeper_status command.
+ */
+export interface DexyKeeperStatusResponse {
+  variant: string
+  current_height: number
+  /** LP rate in nanoERG per token */
+  lp_rate_nano: number
+  /** Oracle rate in nanoERG per token */
+  oracle_rate_nano: number
+  actions: KeeperActionStatus[]
+}
+
+export interface KeeperTxSummary {
+  action: KeeperAction
+  detail: string
+  /** ERG moved from the bank into the LP (intervention only) */
+  erg_moved_nano: number
+  /** Dexy moved between protocol boxes */
+  dexy_moved: number
+  tx_fee_nano: number
+  citadel_fee_nano: number
+}
+
+/**
+ * Response from build_dexy_keeper_tx command.
+ */
+export interface DexyKeeperBuildResponse {
+  unsigned_tx: object
+  summary: KeeperTxSummary
+}
+
+// =============================================================================
+// API Functions
+// =============================================================================
+
+/**
+ * Evaluate every keeper action against the current chain state.
+ *
+ * @param variant - "gold" or "usd"
+ */
+export async function getDexyKeeperStatus(
+  variant: DexyVariant,
+): Promise<DexyKeeperStatusResponse> {
+  return await invoke<DexyKeeperStatusResponse>('get_dexy_keeper_status', {
+    variant,
+  })
+}
+
+/**
+ * Build an unsigned keeper transaction. The wallet only pays the miner fee
+ * (and app fee).
+ *
+ * @param variant - "gold" or "usd"
+ * @param action - Keeper action to run
+ * @param userUtxos - User's unspent transaction outputs
+ * @param currentHeight - Current blockchain height
+ */
+export async function buildDexyKeeperTx(
+  variant: DexyVariant,
+  action: KeeperAction,
+  userUtxos: object[],
+  currentHeight: number,
+): Promise<DexyKeeperBuildResponse> {
+  return await invoke<DexyKeeperBuildResponse>('build_dexy_keeper_tx', {
+    variant,
+    action,
+    userUtxos,
+    currentHeight,
+  })
+}
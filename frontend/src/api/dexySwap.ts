@@ -11,6 +11,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { TxIntent } from './types'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -76,6 +77,7 @@ export interface DexySwapTxSummary {
 export interface DexySwapBuildResponse {
   unsigned_tx: object
   summary: DexySwapTxSummary
+  intent: TxIntent
 }
 
 
@@ -174,6 +176,7 @@ export interface LpBuildResponse {
     lp_tokens: number
     miner_fee_nano: number
   }
+  intent: TxIntent
 }
 
 // =============================================================================
@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { IntentBuildResponse, TxIntent } from './types'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -22,6 +23,11 @@ export interface HodlBankState {
   minBankValue: number
   devFeeNum: number
   bankFeeNum: number
+  /** Reserve token of a token-backed bank; null for ERG-backed banks */
+  reserveTokenId: string | null
+  reserveTokenName: string | null
+  /** nanoERG, or raw reserve token units for token-backed banks */
+  reserveAmount: number
   reserveNanoErg: number
   hodlTokensInBank: number
   circulatingSupply: number
@@ -38,6 +44,8 @@ export interface HodlMintPreview {
   pricePerToken: number
   minerFee: number
   totalErgCost: number
+  /** Unit of ergDeposited; null for ERG */
+  reserveTokenId: string | null
 }
 
 export interface HodlBurnPreview {
@@ -48,6 +56,64 @@ export interface HodlBurnPreview {
   ergBeforeFees: number
   pricePerToken: number
   minerFee: number
+  /** Unit of the reserve amounts; null for ERG */
+  reserveTokenId: string | null
+}
+
+export interface HodlDiscoveryOptions {
+  /** Also list test/abandoned banks */
+  includeLowTvl: boolean
+  /** Extra bank contract ErgoTrees (hex), e.g. token-backed deployments */
+  customBankTrees: string[]
+  /** Extra dev fee contract ErgoTrees (hex) for token-backed burns */
+  customFeeTrees: string[]
+}
+
+export interface HodlBankDeployParams {
+  hodlTokenName: string
+  hodlTokenDescription?: string
+  hodlTokenDecimals: number
+  /** R4: hodlTokens ever minted (raw units) */
+  totalTokenSupply: number
+  /** R5: price scaling factor */
+  precisionFactor: number
+  /** R6: nanoERG the bank can never be burned below */
+  minBankValue: number
+  /** R7/R8: fee numerators out of 1000 */
+  devFeeNum: number
+  bankFeeNum: number
+  /** nanoERG seeded into the bank */
+  initialReserve: number
+  /** hodlTokens paid to the creator for initialReserve (sets the opening price) */
+  initialCirculating: number
+}
+
+export interface HodlBankDeployPlan {
+  params: HodlBankDeployParams
+  bankErgoTree: string
+  hodlTokensInBank: number
+  openingPriceNanoPerHodl: number
+  totalFeePct: number
+  bankFeePct: number
+  devFeePct: number
+  /** Smallest burn whose dev fee fills a valid box at the opening price */
+  minBurnHodl: number
+  minerFee: number
+  totalErgCost: number
+}
+
+export interface HodlBankDeployLeg {
+  txId: string
+  unsignedTx: object
+}
+
+export interface HodlBankDeployResponse {
+  /** Bootstrap then create; sign with startArbLegSign, submit with submitArbChain */
+  legs: HodlBankDeployLeg[]
+  plan: HodlBankDeployPlan
+  singletonTokenId: string
+  hodlTokenId: string
+  intent: TxIntent
 }
 
 // =============================================================================
@@ -58,13 +124,24 @@ export async function getHodlCoinBanks(): Promise<HodlBankState[]> {
   return await invoke<HodlBankState[]>('get_hodlcoin_banks')
 }
 
+export async function getHodlCoinDiscoveryConfig(): Promise<HodlDiscoveryOptions> {
+  return await invoke<HodlDiscoveryOptions>('get_hodlcoin_discovery_config')
+}
+
+export async function setHodlCoinDiscoveryConfig(
+  options: HodlDiscoveryOptions,
+): Promise<HodlDiscoveryOptions> {
+  return await invoke<HodlDiscoveryOptions>('set_hodlcoin_discovery_config', { options })
+}
+
+/** `depositAmount` is nanoERG, or raw reserve token units for token-backed banks */
 export async function previewHodlCoinMint(
   singletonTokenId: string,
-  ergAmount: number,
+  depositAmount: number,
 ): Promise<HodlMintPreview> {
   return await invoke<HodlMintPreview>('preview_hodlcoin_mint', {
     singletonTokenId,
-    ergAmount,
+    depositAmount,
   })
 }
 
@@ -80,15 +157,17 @@ export async function previewHodlCoinBurn(
 
 export async function buildHodlCoinMintTx(
   singletonTokenId: string,
-  ergAmount: number,
+  depositAmount: number,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('build_hodlcoin_mint_tx', {
+  buildOnPending = false,
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('build_hodlcoin_mint_tx', {
     singletonTokenId,
-    ergAmount,
+    depositAmount,
     userUtxos,
     currentHeight,
+    buildOnPending,
   })
 }
 
@@ -97,12 +176,32 @@ export async function buildHodlCoinBurnTx(
   hodlAmount: number,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('build_hodlcoin_burn_tx', {
+  buildOnPending = false,
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('build_hodlcoin_burn_tx', {
     singletonTokenId,
     hodlAmount,
     userUtxos,
     currentHeight,
+    buildOnPending,
+  })
+}
+
+export async function previewHodlCoinDeploy(
+  params: HodlBankDeployParams,
+): Promise<HodlBankDeployPlan> {
+  return await invoke<HodlBankDeployPlan>('preview_hodlcoin_deploy', { params })
+}
+
+export async function buildHodlCoinDeployTx(
+  params: HodlBankDeployParams,
+  userUtxos: object[],
+  currentHeight: number,
+): Promise<HodlBankDeployResponse> {
+  return await invoke<HodlBankDeployResponse>('build_hodlcoin_deploy_tx', {
+    params,
+    userUtxos,
+    currentHeight,
   })
 }
 
@@ -0,0 +1,114 @@
+/**
+ * HodlCoin History API
+ *
+ * Bank price history reconstructed from the singleton NFT's box chain, cached
+ * locally, and the active wallet's positions built from its own mint and
+ * burn transactions.
+ *
+ * Commands:
+ * - get_hodlcoin_history: Bank price series, optionally sampled every N blocks
+ * - get_hodlcoin_positions: Average mint price, burn value and gains per bank
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+export interface BankPricePoint {
+  boxId: string
+  txId: string
+  height: number
+  /** Block timestamp in milliseconds */
+  timestamp: number
+  /** nanoERG, or raw reserve token units for token-backed banks */
+  reserveAmount: number
+  circulatingSupply: number
+  priceNanoPerHodl: number
+}
+
+export interface BankPriceHistory {
+  singletonTokenId: string
+  /** Blocks per sample; 0 keeps every bank transition */
+  sampleBlocks: number
+  historyStartHeight: number
+  /** Bank transitions traced before sampling */
+  transitions: number
+  /** Oldest first */
+  points: BankPricePoint[]
+}
+
+export type HodlTradeKind = 'mint' | 'burn'
+
+export interface HodlTrade {
+  txId: string
+  height: number
+  timestamp: number
+  kind: HodlTradeKind
+  hodlAmount: number
+  /** Reserve deposited (mint) or received after fees (burn) */
+  reserveAmount: number
+}
+
+export interface HodlPosition {
+  singletonTokenId: string
+  hodlTokenId: string
+  hodlTokenName: string | null
+  /** Unit of the reserve amounts; null for ERG */
+  reserveTokenId: string | null
+  held: number
+  minted: number
+  mintCost: number
+  burned: number
+  burnProceeds: number
+  avgMintPrice: number | null
+  currentPrice: number
+  /** Reserve received for burning the whole holding now, after fees */
+  burnValue: number
+  costBasis: number
+  realizedGain: number
+  unrealizedGain: number | null
+  unrealizedGainPct: number | null
+  /** hodlTokens not matched to a mint; gains cover the tracked part only */
+  untracked: number
+  /** Oldest first */
+  trades: HodlTrade[]
+}
+
+export interface HodlPositionsResponse {
+  positions: HodlPosition[]
+  errors: string[]
+  blockHeight: number
+}
+
+// =============================================================================
+// API Functions
+// =============================================================================
+
+/**
+ * Get a HodlCoin bank's price history.
+ *
+ * @param options.maxSteps - Bank boxes to walk this call (default 300)
+ * @param options.sampleBlocks - One point per this many blocks (0 = every transition)
+ * @param options.sinceHeight - Backfill the cache down to this height
+ */
+export async function getHodlCoinHistory(
+  singletonTokenId: string,
+  options: {
+    maxSteps?: number
+    sampleBlocks?: number
+    sinceHeight?: number
+  } = {},
+): Promise<BankPriceHistory> {
+  return await invoke<BankPriceHistory>('get_hodlcoin_history', {
+    singletonTokenId,
+    maxSteps: options.maxSteps ?? null,
+    sampleBlocks: options.sampleBlocks ?? null,
+    sinceHeight: options.sinceHeight ?? null,
+  })
+}
+
+export async function getHodlCoinPositions(): Promise<HodlPositionsResponse> {
+  return await invoke<HodlPositionsResponse>('get_hodlcoin_positions')
+}
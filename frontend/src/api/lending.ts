@@ -14,6 +14,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { TxIntent } from './types'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -197,6 +198,7 @@ export interface LendingBuildResponse {
   /** Unsigned transaction in EIP-12 JSON format */
   unsigned_tx: unknown
   summary: LendingTxSummary
+  intent: TxIntent
 }
 
 // =============================================================================
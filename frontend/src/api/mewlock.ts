@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { IntentBuildResponse } from './types'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -70,8 +71,8 @@ export async function buildLockTx(
   lockDescription: string | null,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('mewlock_build_lock', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('mewlock_build_lock', {
     userErgoTree,
     lockErg,
     lockTokensJson,
@@ -89,8 +90,8 @@ export async function buildUnlockTx(
   userErgoTree: string,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('mewlock_build_unlock', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('mewlock_build_unlock', {
     boxId,
     userErgoTree,
     userUtxos,
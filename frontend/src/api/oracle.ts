@@ -0,0 +1,86 @@
+/**
+ * Oracle Guard API
+ *
+ * SigmaUSD and Dexy builds price against an oracle pool datapoint. Before
+ * building, the backend checks the pool box is fresh and that its rate agrees
+ * with on-chain references (other oracle pools, Dexy LPs, the largest
+ * ERG/SigUSD Spectrum pool). Builds are blocked or carry warnings according to
+ * the persisted guard settings.
+ *
+ * Commands:
+ * - get_oracle_guard_config / set_oracle_guard_config: Staleness and deviation thresholds
+ * - check_oracle: Freshness and reference deviation of one oracle feed
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+export type OracleFeed = 'erg_usd' | 'dexy_gold' | 'dexy_usd'
+
+export type OracleVerdict = 'ok' | 'warn' | 'block'
+
+export interface OracleGuardConfig {
+  /** Pool box older than this many blocks is stale */
+  max_age_blocks: number
+  /** Median reference deviation (%) that triggers a warning */
+  warn_deviation_pct: number
+  /** Median reference deviation (%) that blocks builds */
+  block_deviation_pct: number
+  /** Block builds on a stale oracle instead of only warning */
+  block_when_stale: boolean
+}
+
+export interface OracleReference {
+  source: string
+  /** Same unit as the oracle rate (nanoERG per USD or per kg) */
+  rate_nano: number
+  /** Positive when the reference prices the asset above the oracle */
+  deviation_pct: number
+}
+
+export interface OracleCheck {
+  feed: OracleFeed
+  oracle_box_id: string
+  rate_nano: number
+  epoch: number | null
+  box_height: number
+  current_height: number
+  age_blocks: number
+  stale: boolean
+  references: OracleReference[]
+  median_deviation_pct: number | null
+  verdict: OracleVerdict
+  issues: string[]
+}
+
+// =============================================================================
+// API Functions
+// =============================================================================
+
+/**
+ * Get the persisted oracle guard thresholds.
+ */
+export async function getOracleGuardConfig(): Promise<OracleGuardConfig> {
+  return await invoke<OracleGuardConfig>('get_oracle_guard_config')
+}
+
+/**
+ * Update the oracle guard thresholds; returns the saved settings.
+ */
+export async function setOracleGuardConfig(
+  config: OracleGuardConfig,
+): Promise<OracleGuardConfig> {
+  return await invoke<OracleGuardConfig>('set_oracle_guard_config', { config })
+}
+
+/**
+ * Check freshness and reference deviation of an oracle feed.
+ *
+ * @param feed - "erg_usd" (SigmaUSD), "dexy_gold" or "dexy_usd"
+ */
+export async function checkOracle(feed: OracleFeed): Promise<OracleCheck> {
+  return await invoke<OracleCheck>('check_oracle', { feed })
+}
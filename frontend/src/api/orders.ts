@@ -1,12 +1,13 @@
 /**
  * Swap Order Discovery API
  *
- * Discovers pending swap orders on-chain via template matching
- * and direct swap transactions in the mempool.
+ * Discovers pending swap orders on-chain via template matching,
+ * stale orders of any age, and direct swap transactions in the mempool.
  */
 
 import { invoke } from '@tauri-apps/api/core'
 import type { SwapBuildResponse } from './amm'
+import type { TxIntent } from './types'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -29,6 +30,53 @@ export interface PendingOrder {
   method: 'proxy' | 'direct'
 }
 
+export type OrderKind = 'swap_sell' | 'swap_buy' | 'deposit' | 'redeem'
+
+export type StaleReason =
+  | { kind: 'expired'; age_blocks: number }
+  | { kind: 'unreachable_min_output'; expected_output: number; min_output: number }
+  | { kind: 'pool_not_found' }
+
+export interface OpenOrder {
+  boxId: string
+  txId: string
+  kind: OrderKind
+  poolId: string
+  /** Swap orders only */
+  input: { type: 'Erg'; amount: number } | { type: 'Token'; token_id: string; amount: number } | null
+  minOutput: number | null
+  valueNanoErg: number
+  tokens: [string, number][]  // [tokenId, amount]
+  createdHeight: number
+  ageBlocks: number
+  ownerErgoTree: string
+  /** Empty when the order can still execute */
+  stale: StaleReason[]
+}
+
+export interface OpenOrdersResponse {
+  orders: OpenOrder[]
+  staleCount: number
+  currentHeight: number
+  maxAgeBlocks: number
+}
+
+export interface BulkRefundTx {
+  unsigned_tx: unknown
+  summary: {
+    proxy_box_ids: string[]
+    refunded_erg: number
+    refunded_tokens: [string, number][]
+    miner_fee: number
+  }
+  intent: TxIntent
+}
+
+/** Independent refund transactions, each spending at most 50 order boxes. */
+export interface BulkRefundBuildResponse {
+  txs: BulkRefundTx[]
+}
+
 export interface MempoolSwap {
   txId: string
   poolId: string
@@ -69,6 +117,28 @@ export async function buildSwapRefundTx(
   })
 }
 
+/**
+ * Find every open swap/deposit/redeem order of the connected wallet, of any
+ * age, flagging stale ones (older than maxAgeBlocks or min output unreachable).
+ */
+export async function scanOpenOrders(maxAgeBlocks?: number): Promise<OpenOrdersResponse> {
+  return await invoke<OpenOrdersResponse>('scan_open_orders', { maxAgeBlocks })
+}
+
+/**
+ * Build refund transactions for several order boxes, batched into as few
+ * transactions as the per-transaction input limit allows.
+ */
+export async function buildBulkRefundTx(
+  boxIds: string[],
+  userErgoTree: string,
+): Promise<BulkRefundBuildResponse> {
+  return await invoke<BulkRefundBuildResponse>('build_bulk_refund_tx', {
+    boxIds,
+    userErgoTree,
+  })
+}
+
 // =============================================================================
 // Helper Functions
 // =============================================================================
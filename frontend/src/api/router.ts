@@ -6,6 +6,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { TokenStatus } from './tokens'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -31,6 +32,11 @@ export interface RouteHop {
   fee_denom: number
   reserves_in: number
   reserves_out: number
+  /**
+   * False for protocol banks (SigmaUSD, Dexy LP) and foreign pools: quoted
+   * for comparison only, since only Spectrum pool hops can be built as swaps.
+   */
+  executable: boolean
 }
 
 export interface Route {
@@ -50,10 +56,17 @@ export interface Route {
   effective_rate: number
 }
 
+/** Whether every hop of the route can be built as a Spectrum swap */
+export function isRouteExecutable(route: Route): boolean {
+  return route.hops.every(h => h.executable)
+}
+
 export interface RouteQuote {
   route: Route
   min_output: number
   slippage_percent: number
+  /** Pools priced from unconfirmed mempool boxes (mempool mode only) */
+  unconfirmed_pools?: string[]
 }
 
 export interface SplitAllocation {
@@ -131,6 +144,8 @@ export interface RoutesResponse {
   split: SplitRouteDetail | null
   /** Present when requested size cannot execute but a smaller max can (ERG-out). */
   max_swap: MaxSwapHint | null
+  /** Verification status of every token on the returned routes, by token ID */
+  tokens: Record<string, TokenStatus>
 }
 
 // =============================================================================
@@ -174,6 +189,7 @@ export async function findSwapRoutes(
   maxRoutes?: number,
   slippage?: number,
   minRate?: number,
+  includeMempool?: boolean,
 ): Promise<RoutesResponse> {
   return await invoke<RoutesResponse>('find_swap_routes', {
     sourceToken,
@@ -183,6 +199,7 @@ export async function findSwapRoutes(
     maxRoutes,
     slippage,
     minRate,
+    includeMempool,
   })
 }
 
@@ -228,6 +245,7 @@ export async function findSwapRoutesByOutput(
   maxHops?: number,
   maxRoutes?: number,
   slippage?: number,
+  includeMempool?: boolean,
 ): Promise<RoutesResponse> {
   return await invoke<RoutesResponse>('find_swap_routes_by_output', {
     sourceToken,
@@ -236,6 +254,7 @@ export async function findSwapRoutesByOutput(
     maxHops,
     maxRoutes,
     slippage,
+    includeMempool,
   })
 }
 
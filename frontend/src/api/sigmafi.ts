@@ -5,6 +5,7 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import type { IntentBuildResponse } from './types'
 
 export type { SignResponse, TxStatusResponse } from './types'
 
@@ -96,8 +97,8 @@ export async function buildOpenOrder(
   collateralTokensJson: string,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('sigmafi_build_open_order', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('sigmafi_build_open_order', {
     borrowerErgoTree,
     loanTokenId,
     principal,
@@ -115,8 +116,8 @@ export async function buildCancelOrder(
   borrowerErgoTree: string,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('sigmafi_build_cancel_order', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('sigmafi_build_cancel_order', {
     boxId,
     borrowerErgoTree,
     userUtxos,
@@ -131,8 +132,8 @@ export async function buildCloseOrder(
   loanTokenId: string,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('sigmafi_build_close_order', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('sigmafi_build_close_order', {
     boxId,
     lenderErgoTree,
     uiFeeErgoTree,
@@ -148,8 +149,8 @@ export async function buildRepay(
   borrowerErgoTree: string,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('sigmafi_build_repay', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('sigmafi_build_repay', {
     boxId,
     loanTokenId,
     borrowerErgoTree,
@@ -163,8 +164,8 @@ export async function buildLiquidate(
   lenderErgoTree: string,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('sigmafi_build_liquidate', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('sigmafi_build_liquidate', {
     boxId,
     lenderErgoTree,
     userUtxos,
@@ -0,0 +1,113 @@
+/**
+ * SigmaUSD Advisor API
+ *
+ * Exact reserve-ratio limits, fee-inclusive bank prices and round-trip
+ * break-even moves for the four SigmaUSD actions, compared against the
+ * deepest Spectrum ERG pools. For a given trade, also the cheapest split
+ * between the bank and the pool.
+ *
+ * Commands:
+ * - get_sigmausd_advice: Bank limits, bank vs DEX prices, optional route
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+export type SigmaUsdAdvisorAction =
+  | 'mint_sigusd'
+  | 'redeem_sigusd'
+  | 'mint_sigrsv'
+  | 'redeem_sigrsv'
+
+export interface ActionAdvice {
+  action: SigmaUsdAdvisorAction
+  allowed: boolean
+  /** Largest amount (raw units) the contract accepts right now */
+  max_amount: number
+  limited_by: 'reserve_ratio' | 'circulating_supply'
+  /** nanoERG per raw unit, 2% fee included */
+  effective_price_nano: number
+  /** ERG paid (mints) or received (redeems) for max_amount */
+  erg_at_max_nano: number
+  reserve_ratio_at_max_pct: number
+  /** Bank price change at which undoing the action breaks even */
+  round_trip_break_even_pct: number
+}
+
+export interface BankAdvice {
+  reserve_ratio_pct: number
+  liabilities_nano: number
+  equity_nano: number
+  /** ERG/USD move that takes the ratio to 400% (null without SigUSD supply) */
+  erg_move_to_min_ratio_pct: number | null
+  /** ERG/USD move that takes the ratio to 800% */
+  erg_move_to_max_ratio_pct: number | null
+  actions: ActionAdvice[]
+}
+
+export interface DexPoolQuote {
+  pool_id: string
+  erg_reserves: number
+  token_reserves: number
+  fee_pct: number
+  /** nanoERG per raw unit for a marginal buy, pool fee included */
+  buy_price_nano: number
+  /** nanoERG per raw unit for a marginal sell, pool fee included */
+  sell_price_nano: number
+}
+
+export interface VenueComparison {
+  action: SigmaUsdAdvisorAction
+  bank_price_nano: number | null
+  dex_price_nano: number | null
+  better_venue: 'bank' | 'dex' | 'none'
+}
+
+export interface RouteQuote {
+  bank_amount: number
+  dex_amount: number
+  /** ERG paid (mints) or received (redeems), all fees included */
+  erg_nano: number
+  effective_price_nano: number
+}
+
+export interface RouteAdvice {
+  action: SigmaUsdAdvisorAction
+  amount: number
+  bank_only: RouteQuote | null
+  dex_only: RouteQuote | null
+  best: RouteQuote | null
+  recommendation: 'bank' | 'dex' | 'split' | 'unavailable'
+  /** ERG saved or gained over the better single-venue route */
+  improvement_nano: number
+}
+
+export interface SigmaUsdAdviceResponse {
+  bank: BankAdvice
+  sigusd_pool: DexPoolQuote | null
+  sigrsv_pool: DexPoolQuote | null
+  comparisons: VenueComparison[]
+  route: RouteAdvice | null
+}
+
+// =============================================================================
+// API Functions
+// =============================================================================
+
+/**
+ * Get SigmaUSD action advice. Pass a trade to also get the best bank/DEX route.
+ *
+ * @param trade.amount - Raw token units (SigUSD has 2 decimals)
+ */
+export async function getSigmaUsdAdvice(trade?: {
+  action: SigmaUsdAdvisorAction
+  amount: number
+}): Promise<SigmaUsdAdviceResponse> {
+  return await invoke<SigmaUsdAdviceResponse>('get_sigmausd_advice', {
+    action: trade?.action ?? null,
+    amount: trade?.amount ?? null,
+  })
+}
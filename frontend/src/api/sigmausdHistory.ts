@@ -0,0 +1,65 @@
+/**
+ * SigmaUSD History API
+ *
+ * Bank and oracle state reconstructed at each bank transition from the bank
+ * NFT's box chain, cached locally. Used for reserve ratio, supply and SigRSV
+ * price charts and for backtesting.
+ *
+ * Commands:
+ * - get_sigmausd_history: Bank state series, optionally sampled every N blocks
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+export interface BankSnapshot {
+  box_id: string
+  tx_id: string
+  height: number
+  /** Block timestamp in milliseconds */
+  timestamp: number
+  bank_erg_nano: number
+  /** Raw SigUSD (2 decimals) */
+  sigusd_circulating: number
+  sigrsv_circulating: number
+  /** Oracle datapoint the transition was validated against */
+  nanoerg_per_usd: number
+  reserve_ratio_pct: number
+  sigrsv_price_nano: number
+}
+
+export interface BankHistory {
+  /** Blocks per sample; 0 keeps every bank transition */
+  sample_blocks: number
+  history_start_height: number
+  /** Bank transitions traced before sampling */
+  transitions: number
+  /** Oldest first */
+  points: BankSnapshot[]
+}
+
+// =============================================================================
+// API Functions
+// =============================================================================
+
+/**
+ * Get SigmaUSD bank history.
+ *
+ * @param options.maxSteps - Bank boxes to walk this call (default 300)
+ * @param options.sampleBlocks - One point per this many blocks (0 = every transition)
+ * @param options.sinceHeight - Backfill the cache down to this height
+ */
+export async function getSigmaUsdHistory(options: {
+  maxSteps?: number
+  sampleBlocks?: number
+  sinceHeight?: number
+} = {}): Promise<BankHistory> {
+  return await invoke<BankHistory>('get_sigmausd_history', {
+    maxSteps: options.maxSteps ?? null,
+    sampleBlocks: options.sampleBlocks ?? null,
+    sinceHeight: options.sinceHeight ?? null,
+  })
+}
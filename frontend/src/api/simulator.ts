@@ -0,0 +1,146 @@
+/**
+ * What-if Simulator API
+ *
+ * Runs the SigmaUSD and Dexy calculators against live protocol state under
+ * hypothetical ERG price shocks and mint/redeem/swap flows. Used to
+ * stress-test SigRSV and Dexy holdings before large market moves.
+ *
+ * Commands:
+ * - simulate_sigmausd: Reserve ratio, SigRSV price, blocked actions, holdings value
+ * - simulate_dexy: Oracle vs LP rate, mint/LP redeem availability, holdings value
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+// =============================================================================
+// Type Definitions
+// =============================================================================
+
+export interface SimFlow {
+  /**
+   * SigmaUSD: 'mint_sigusd' | 'redeem_sigusd' | 'mint_sigrsv' | 'redeem_sigrsv'
+   * Dexy: 'mint' | 'sell_to_lp' | 'buy_from_lp'
+   */
+  action: string
+  /** Raw token units (nanoERG for 'buy_from_lp') */
+  amount: number
+}
+
+export interface SigmaUsdSimRequest {
+  /** ERG price changes in percent, one scenario each (-50 halves the price) */
+  price_shocks_pct?: number[]
+  flows?: SimFlow[]
+  sigusd_held?: number
+  sigrsv_held?: number
+}
+
+export interface DexySimRequest {
+  variant: 'gold' | 'usd'
+  price_shocks_pct?: number[]
+  flows?: SimFlow[]
+  dexy_held?: number
+  lp_tokens_held?: number
+}
+
+export interface FlowOutcome {
+  action: string
+  amount: number
+  applied: boolean
+  /** ERG into the bank (negative for redemptions) */
+  erg_delta_nano: number
+  error: string | null
+  reserve_ratio_after_pct: number
+}
+
+export interface SimulatedState {
+  nanoerg_per_usd: number
+  bank_erg_nano: number
+  sigusd_circulating: number
+  sigrsv_circulating: number
+  reserve_ratio_pct: number
+  sigrsv_price_nano: number
+  blocked_actions: string[]
+}
+
+export interface HoldingsValue {
+  sigusd_erg_nano: number
+  sigrsv_erg_nano: number
+  total_erg_nano: number
+  total_usd: number
+  sigrsv_redeemable: boolean
+}
+
+export interface SigmaUsdSimulationResult {
+  erg_price_change_pct: number
+  before: SimulatedState
+  /** After the price shock, before any flow */
+  shocked: SimulatedState
+  after: SimulatedState
+  flows: FlowOutcome[]
+  holdings_before: HoldingsValue
+  holdings_after: HoldingsValue
+  holdings_change_pct: number | null
+}
+
+export interface DexySimulatedState {
+  oracle_rate_nano: number
+  lp_rate_nano: number
+  lp_erg_reserves: number
+  lp_dexy_reserves: number
+  dexy_in_bank: number
+  rate_difference_pct: number
+  blocked_actions: string[]
+}
+
+export interface DexyFlowOutcome {
+  action: string
+  amount: number
+  applied: boolean
+  /** ERG paid by the user (negative when received) */
+  erg_delta_nano: number
+  /** Dexy received by the user (negative when paid) */
+  dexy_delta: number
+  error: string | null
+  lp_rate_after_nano: number
+}
+
+export interface DexyHoldingsValue {
+  dexy_lp_exit_nano: number
+  dexy_oracle_value_nano: number
+  lp_value_nano: number
+  lp_redeemable: boolean
+  total_erg_nano: number
+}
+
+export interface DexySimulationResult {
+  erg_price_change_pct: number
+  before: DexySimulatedState
+  shocked: DexySimulatedState
+  after: DexySimulatedState
+  flows: DexyFlowOutcome[]
+  holdings_before: DexyHoldingsValue
+  holdings_after: DexyHoldingsValue
+  holdings_change_pct: number | null
+}
+
+// =============================================================================
+// API Functions
+// =============================================================================
+
+/**
+ * Simulate SigmaUSD under each price shock followed by the given flows.
+ */
+export async function simulateSigmaUsd(
+  request: SigmaUsdSimRequest,
+): Promise<SigmaUsdSimulationResult[]> {
+  return await invoke<SigmaUsdSimulationResult[]>('simulate_sigmausd', { request })
+}
+
+/**
+ * Simulate a Dexy variant under each price shock followed by the given flows.
+ */
+export async function simulateDexy(
+  request: DexySimRequest,
+): Promise<DexySimulationResult[]> {
+  return await invoke<DexySimulationResult[]>('simulate_dexy', { request })
+}
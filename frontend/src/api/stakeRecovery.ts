@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core'
+import type { IntentBuildResponse } from './types'
 
 export interface StakeStateSnapshot {
   protocol: string
@@ -51,8 +52,8 @@ export async function buildRecoveryTx(
   stakeKeyId: string,
   userUtxos: object[],
   currentHeight: number,
-): Promise<object> {
-  return await invoke<object>('build_recovery_tx', {
+): Promise<IntentBuildResponse> {
+  return await invoke<IntentBuildResponse>('build_recovery_tx', {
     stakeKeyId,
     userUtxos,
     currentHeight,
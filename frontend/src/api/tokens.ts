@@ -0,0 +1,78 @@
+/**
+ * Token Registry API
+ *
+ * TypeScript types and invoke wrappers for the verified token list and the
+ * scam-token warnings attached to wallet, pool and route tokens.
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+
+export type TokenWarning =
+  | { kind: 'blocked' }
+  | { kind: 'name_collision'; verified_token_id: string; verified_name: string }
+  | { kind: 'fresh_low_liquidity'; age_blocks: number; liquidity_nano: number }
+
+export interface TokenStatus {
+  verified: boolean
+  warning: TokenWarning | null
+}
+
+export interface VerifiedToken {
+  token_id: string
+  name: string
+  decimals: number
+  project?: string | null
+  /** Icon path served by the frontend */
+  logo?: string | null
+  /** SHA-256 of the icon file, hex */
+  logo_sha256?: string | null
+}
+
+export interface TokenList {
+  version: number
+  tokens: VerifiedToken[]
+}
+
+export interface TokenRegistryResponse {
+  list_version: number
+  tokens: VerifiedToken[]
+  /** IDs verified locally rather than by the list */
+  local_verified: string[]
+  blocked: string[]
+}
+
+/** Short user-facing description of a warning. */
+export function describeTokenWarning(warning: TokenWarning): string {
+  switch (warning.kind) {
+    case 'blocked':
+      return 'Blocked token'
+    case 'name_collision':
+      return `Impersonates ${warning.verified_name}`
+    case 'fresh_low_liquidity':
+      return `New token with low liquidity (${warning.age_blocks} blocks old)`
+  }
+}
+
+export async function getTokenRegistry(): Promise<TokenRegistryResponse> {
+  return await invoke<TokenRegistryResponse>('get_token_registry')
+}
+
+/** Replace the bundled list with a newer version. */
+export async function importTokenList(list: TokenList): Promise<TokenRegistryResponse> {
+  return await invoke<TokenRegistryResponse>('import_token_list', { list })
+}
+
+export async function addVerifiedToken(token: VerifiedToken): Promise<TokenRegistryResponse> {
+  return await invoke<TokenRegistryResponse>('add_verified_token', { token })
+}
+
+export async function removeVerifiedToken(tokenId: string): Promise<TokenRegistryResponse> {
+  return await invoke<TokenRegistryResponse>('remove_verified_token', { tokenId })
+}
+
+export async function setTokenBlocked(
+  tokenId: string,
+  blocked: boolean,
+): Promise<TokenRegistryResponse> {
+  return await invoke<TokenRegistryResponse>('set_token_blocked', { tokenId, blocked })
+}
@@ -20,13 +20,69 @@ export interface TxStatusResponse {
   error: string | null
 }
 
-/** Start ErgoPay signing flow for any unsigned transaction */
+/** An amount of ERG (token_id "ERG", nanoERG) or a token, in raw units */
+export interface IntentAsset {
+  token_id: string
+  name: string | null
+  amount: number
+  /** null when the builder did not know the decimals; shown as raw units */
+  decimals: number | null
+}
+
+/** Fees in nanoERG, split by recipient */
+export interface IntentFees {
+  miner_nano: number
+  /** Already reflected in the asset amounts; listed for information */
+  protocol_nano: number
+  citadel_nano: number
+}
+
+export interface Counterparty {
+  role: string
+  name: string
+  address: string | null
+}
+
+export interface SlippageBounds {
+  min_out: IntentAsset | null
+  max_in: IntentAsset | null
+  tolerance_pct: number | null
+}
+
+/** What a built transaction does, as returned next to the unsigned tx */
+export interface TxIntent {
+  protocol: string
+  action: string
+  /** Leaving the user's wallet, excluding miner and Citadel fees */
+  assets_in: IntentAsset[]
+  assets_out: IntentAsset[]
+  fees: IntentFees
+  counterparties: Counterparty[]
+  slippage: SlippageBounds | null
+  notes: string[]
+}
+
+/** Response from builders that describe their tx */
+export interface IntentBuildResponse {
+  unsignedTx: object
+  intent: TxIntent
+}
+
+/**
+ * Start ErgoPay signing flow for any unsigned transaction. With an intent,
+ * the wallet prompt is rendered from it instead of `message`.
+ */
 export async function startSign(
   unsignedTx: object,
   message?: string,
+  intent?: TxIntent,
 ): Promise<SignResponse> {
   return await invoke<SignResponse>('start_mint_sign', {
-    request: { unsigned_tx: unsignedTx, message: message ?? 'Sign transaction' },
+    request: {
+      unsigned_tx: unsignedTx,
+      message: message ?? intent?.action ?? 'Sign transaction',
+      intent: intent ?? null,
+    },
   })
 }
 
@@ -3,8 +3,9 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
-import type { SignResponse, TxStatusResponse } from './types'
+import type { SignResponse, TxStatusResponse, TxIntent } from './types'
 import { startSign, getTxStatus } from './types'
+import type { TokenWarning } from './tokens'
 
 export interface TokenBalance {
   token_id: string
@@ -13,6 +14,8 @@ export interface TokenBalance {
   name: string | null
   decimals: number
   pending_amount?: number
+  verified?: boolean
+  warning?: TokenWarning | null
 }
 
 export interface WalletBalance {
@@ -32,6 +35,8 @@ export interface TokenChange {
   amount: number
   name: string | null
   decimals: number
+  verified?: boolean
+  warning?: TokenWarning | null
 }
 
 export interface RecentTx {
@@ -53,6 +58,7 @@ export interface SendBuildResponse {
   /** Citadel app fee in nanoERG (0 when disabled) */
   citadelFeeNano: number
   inputCount: number
+  intent: TxIntent
 }
 
 export async function getWalletBalance(): Promise<WalletBalance> {
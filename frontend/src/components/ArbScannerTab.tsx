@@ -1,6 +1,11 @@
 import { useState, useEffect, useCallback } from 'react'
-import { scanCircularArbs } from '../api/arb'
-import type { CircularArbSnapshot, CircularArb } from '../api/arb'
+import {
+  scanCircularArbs,
+  getArbMonitor,
+  setArbMonitorConfig,
+  clearArbMonitorHistory,
+} from '../api/arb'
+import type { CircularArbSnapshot, CircularArb, ArbAlert, ArbMonitorResponse } from '../api/arb'
 import type { RouteHop } from '../api/router'
 import { ArbExecuteModal } from './ArbExecuteModal'
 import './ArbScannerTab.css'
@@ -8,6 +13,9 @@ import './ArbScannerTab.css'
 interface ArbScannerTabProps {
   walletAddress: string | null
   onBalanceRefresh?: () => void
+  /** `arb-opportunity` events received since startup, newest first. */
+  monitorAlerts: ArbAlert[]
+  onClearMonitorAlerts: () => void
 }
 
 function formatErg(nano: number): string {
@@ -41,7 +49,12 @@ function impactClass(impact: number): string {
   return 'impact-high'
 }
 
-export function ArbScannerTab({ walletAddress, onBalanceRefresh }: ArbScannerTabProps) {
+export function ArbScannerTab({
+  walletAddress,
+  onBalanceRefresh,
+  monitorAlerts,
+  onClearMonitorAlerts,
+}: ArbScannerTabProps) {
   const [snapshot, setSnapshot] = useState<CircularArbSnapshot | null>(null)
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
@@ -89,6 +102,8 @@ export function ArbScannerTab({ walletAddress, onBalanceRefresh }: ArbScannerTab
       </header>
 
       <div className="arb-scanner-body">
+        <ArbMonitorPanel alerts={monitorAlerts} onClearAlerts={onClearMonitorAlerts} />
+
         {error && <div className="message error">{error}</div>}
 
         {loading && !snapshot && (
@@ -154,6 +169,127 @@ export function ArbScannerTab({ walletAddress, onBalanceRefresh }: ArbScannerTab
   )
 }
 
+function ArbMonitorPanel({ alerts, onClearAlerts }: {
+  alerts: ArbAlert[]
+  onClearAlerts: () => void
+}) {
+  const [monitor, setMonitor] = useState<ArbMonitorResponse | null>(null)
+  const [saving, setSaving] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const load = useCallback(async () => {
+    try {
+      setMonitor(await getArbMonitor())
+    } catch (e) {
+      setError(String(e))
+    }
+  }, [])
+
+  // Stats move with every alert; reload them when one arrives
+  useEffect(() => {
+    load()
+  }, [load, alerts.length])
+
+  const toggle = async (field: 'enabled' | 'build_chains') => {
+    if (!monitor) return
+    setSaving(true)
+    setError(null)
+    try {
+      const config = await setArbMonitorConfig({
+        ...monitor.config,
+        [field]: !monitor.config[field],
+      })
+      setMonitor({ ...monitor, config })
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  const clearHistory = async () => {
+    setError(null)
+    try {
+      await clearArbMonitorHistory()
+      onClearAlerts()
+      await load()
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  if (!monitor) return error ? <div className="message error">{error}</div> : null
+  const { config, stats } = monitor
+
+  return (
+    <section className="arb-monitor">
+      <div className="arb-monitor-header">
+        <label className="arb-monitor-toggle">
+          <input
+            type="checkbox"
+            checked={config.enabled}
+            disabled={saving}
+            onChange={() => toggle('enabled')}
+          />
+          <span>Monitor every block</span>
+        </label>
+        <label className="arb-monitor-toggle">
+          <input
+            type="checkbox"
+            checked={config.build_chains}
+            disabled={saving || !config.enabled}
+            onChange={() => toggle('build_chains')}
+          />
+          <span>Pre-build best chain</span>
+        </label>
+        <span className="arb-monitor-meta">
+          Min profit {formatErg(config.min_net_profit_nano)} ERG &middot; up to {config.max_hops} hops
+          {monitor.last_checked_height > 0 && <> &middot; last block {monitor.last_checked_height}</>}
+        </span>
+        {stats.blocks_scanned > 0 && (
+          <button className="arb-scanner-refresh" onClick={clearHistory}>
+            Clear history
+          </button>
+        )}
+      </div>
+
+      {error && <div className="message error">{error}</div>}
+
+      {stats.blocks_scanned > 0 && (
+        <div className="arb-monitor-meta">
+          {stats.blocks_with_opportunity} of {stats.blocks_scanned} blocks had an opportunity
+          ({stats.circular_opportunities} circular, {stats.oracle_opportunities} oracle)
+          &middot; best {formatErgSigned(stats.best_net_profit_nano)} ERG
+        </div>
+      )}
+
+      {alerts.length > 0 && (
+        <ul className="arb-monitor-alerts">
+          {alerts.map((alert, idx) => (
+            <li key={`${alert.height}-${idx}`} className="arb-monitor-alert">
+              <span className="arb-monitor-alert-height">#{alert.height}</span>
+              <span className="arb-card-path">{alert.path_label}</span>
+              <span className="arb-card-value profit">
+                {formatErgSigned(alert.net_profit_nano)} ERG
+              </span>
+              {alert.chain && (
+                <span className="arb-monitor-meta">
+                  {alert.chain.legs.length} legs pre-built
+                </span>
+              )}
+              {alert.chain_error && (
+                <span className="arb-monitor-meta" title={alert.chain_error}>
+                  Chain not built
+                </span>
+              )}
+            </li>
+          ))}
+        </ul>
+      )}
+    </section>
+  )
+}
+
 function ArbCard({ arb, canExecute, onExecute }: {
   arb: CircularArb
   canExecute: boolean
@@ -28,7 +28,8 @@ import { formatErg } from '../utils/format'
 import type { WalletBalance } from './MarketCard'
 import { TxSuccess } from './TxSuccess'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
-import type { TxStatusResponse } from '../api/types'
+import { startSign, type TxStatusResponse } from '../api/types'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Modal, Button, Spinner, FormField, Select } from './ui'
 import './LendModal.css' // Reuse LendModal styles
 
@@ -281,18 +282,15 @@ export function BorrowModal({
     setError(null)
 
     try {
-      const signResult = await invoke<{
-        request_id: string
-        ergopay_url: string
-        nautilus_url: string
-      }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResponse.unsigned_tx,
-          message: `Borrow ${calculated.borrowAmount.toFixed(pool.decimals)} ${pool.symbol} from ${pool.name}`,
-        },
-      })
-
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const signResult = await startSign(buildResponse.unsigned_tx as object, undefined, buildResponse.intent)
+
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResponse.unsigned_tx as object,
+        buildResponse.intent,
+      )
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -300,7 +298,7 @@ export function BorrowModal({
     } finally {
       setLoading(false)
     }
-  }, [buildResponse, calculated.borrowAmount, pool, flow])
+  }, [buildResponse, flow])
 
   if (!isOpen) return null
 
@@ -557,6 +555,7 @@ export function BorrowModal({
 
           {step === 'signing' && (
             <div className="lend-signing-step">
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               {flow.signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
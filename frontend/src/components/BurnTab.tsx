@@ -2,11 +2,12 @@ import { useState, useEffect, useMemo, useCallback } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { QRCodeSVG } from 'qrcode.react'
 import { buildMultiBurnTx } from '../api/burn'
-import { startSign, getTxStatus } from '../api/types'
+import { startSign, getTxStatus, type TxIntent } from '../api/types'
 import type { BurnItemInput, BurnedTokenEntry } from '../api/burn'
 import { getCachedTokenInfo } from '../api/tokenCache'
 import { formatTokenAmount } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { EmptyState } from './ui'
 import './BurnTab.css'
 
@@ -76,6 +77,7 @@ export function BurnTab({ isConnected, walletAddress, walletBalance, explorerUrl
     burnedTokens: BurnedTokenEntry[]
     minerFee: number
     citadelFeeNano: number
+    intent: TxIntent
   } | null>(null)
 
   const tokens = walletBalance?.tokens ?? []
@@ -289,11 +291,10 @@ export function BurnTab({ isConnected, walletAddress, walletBalance, explorerUrl
         burnedTokens: result.burnedTokens,
         minerFee: result.minerFee,
         citadelFeeNano: result.citadelFeeNano,
+        intent: result.intent,
       })
 
-      const count = burnItems.length
-      const message = `Burn ${count} token${count !== 1 ? 's' : ''}`
-      const signResult = await startSign(result.unsignedTx, message)
+      const signResult = await startSign(result.unsignedTx, undefined, result.intent)
 
       setRequestId(signResult.request_id)
       setQrUrl(signResult.ergopay_url)
@@ -644,29 +645,7 @@ export function BurnTab({ isConnected, walletAddress, walletBalance, explorerUrl
           <div className="card">
             <div className="card-content">
               <div className="mint-signing-step">
-                {multiBurnSummary && (
-                  <div className="burn-confirm-summary" style={{ marginBottom: 'var(--space-md)' }}>
-                    <div className="burn-confirm-row burn-amount-row">
-                      <span>Burning</span>
-                      <span>{multiBurnSummary.burnedTokens.length} token{multiBurnSummary.burnedTokens.length !== 1 ? 's' : ''}</span>
-                    </div>
-                    <div className="burn-confirm-row">
-                      <span>Miner Fee</span>
-                      <span>{(multiBurnSummary.minerFee / 1e9).toFixed(4)} ERG</span>
-                    </div>
-                    {multiBurnSummary.citadelFeeNano > 0 && (
-                      <>
-                        <div className="burn-confirm-row">
-                          <span>Citadel fee</span>
-                          <span>{(multiBurnSummary.citadelFeeNano / 1e9).toFixed(4)} ERG</span>
-                        </div>
-                        <p className="burn-muted">
-                          Includes {(multiBurnSummary.citadelFeeNano / 1e9).toFixed(3)} ERG Citadel fee
-                        </p>
-                      </>
-                    )}
-                  </div>
-                )}
+                {multiBurnSummary && <TxIntentSummary intent={multiBurnSummary.intent} />}
                 <p>Choose your signing method</p>
                 <div className="wallet-options">
                   <button className="wallet-option" onClick={handleNautilusSign}>
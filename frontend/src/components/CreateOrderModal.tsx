@@ -9,6 +9,7 @@ import {
 import { formatAmount, formatErg } from '../utils/format'
 import { startSign, getTxStatus } from '../api/types'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import type { TxStatusResponse } from '../api/types'
 import { TX_FEE_NANO, MIN_BOX_VALUE_NANO } from '../constants'
@@ -157,7 +158,7 @@ export function CreateOrderModal({
       const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
       const userErgoTree = (utxos[0] as { ergoTree: string }).ergoTree
 
-      const unsignedTx = await buildOpenOrder(
+      const built = await buildOpenOrder(
         userErgoTree,
         selectedToken.token_id,
         String(calculated.principalRaw),
@@ -169,11 +170,14 @@ export function CreateOrderModal({
         nodeStatus.chain_height,
       )
 
-      const signResult = await startSign(
-        unsignedTx,
-        `Create loan request: ${calculated.principal} ${selectedToken.name}`,
+      const signResult = await startSign(built.unsignedTx, undefined, built.intent)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        built.unsignedTx,
+        built.intent,
       )
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -319,6 +323,7 @@ export function CreateOrderModal({
 
           {step === 'signing' && (
             <div className="co-signing-step">
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               {flow.signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
@@ -8,7 +8,9 @@ import { TxSuccess } from './TxSuccess'
 import { DEV_FEE_NANO, TX_FEE_NANO } from '../constants'
 import { AdvancedOptions, useRecipientAddress } from './AdvancedOptions'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
-import type { TxStatusResponse } from '../api/types'
+import type { SigningRequest } from '../hooks/useTransactionFlow'
+import { startSign, type TxIntent, type TxStatusResponse } from '../api/types'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Modal, Button, Spinner } from './ui'
 
 interface DexyState {
@@ -36,7 +38,7 @@ interface DexyRates {
   paths: MintPaths
 }
 
-type PathType = 'free_mint' | 'lp_swap'
+type PathType = 'free_mint' | 'arb_mint' | 'lp_swap'
 
 interface PreviewResponse {
   erg_cost_nano: string
@@ -97,6 +99,7 @@ export function DexyMintModal({
   const [preview, setPreview] = useState<PreviewResponse | null>(null)
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
+  const [oracleWarnings, setOracleWarnings] = useState<string[]>([])
 
   const flow = useTransactionFlow({
     pollStatus: pollMintStatus,
@@ -104,6 +107,7 @@ export function DexyMintModal({
     onSuccess: () => setStep('success'),
     onError: (err) => { setError(err); setStep('error') },
     watchParams: { protocol: 'Dexy', operation: 'mint', description: `Dexy ${variant} mint` },
+    rebuild: () => buildSigningRequest(true),
   })
 
   // Path selection state
@@ -209,7 +213,9 @@ export function DexyMintModal({
       invoke<DexyRates>('get_dexy_rates', { variant })
         .then((fetchedRates) => {
           setRates(fetchedRates)
-          setSelectedPath('free_mint')
+          // Fall back to ArbMint while FreeMint is closed (LP well above oracle)
+          const { free_mint, arb_mint } = fetchedRates.paths
+          setSelectedPath(!free_mint.available && arb_mint.available ? 'arb_mint' : 'free_mint')
         })
         .catch((e) => {
           setRatesError(String(e))
@@ -247,6 +253,36 @@ export function DexyMintModal({
     }
   }
 
+  // Build + start signing; also the auto-rebuild when a competing tx spends the bank/LP
+  const buildSigningRequest = async (buildOnPending: boolean): Promise<SigningRequest> => {
+    const utxos = await invoke<unknown[]>('get_user_utxos')
+    const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
+
+    const buildResult = await invoke<{ unsigned_tx: object; intent: TxIntent; oracle_warnings?: string[] }>('build_mint_dexy', {
+      request: {
+        variant,
+        amount: calculated.tokenAmountRaw,
+        user_address: walletAddress,
+        user_utxos: utxos,
+        current_height: nodeStatus.chain_height,
+        recipient_address: recipientOrNull,
+        mint_path: selectedPath === 'arb_mint' ? 'arb_mint' : 'free_mint',
+        build_on_pending: buildOnPending,
+      }
+    })
+    setOracleWarnings(buildResult.oracle_warnings ?? [])
+
+    const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
+
+    return {
+      unsignedTx: buildResult.unsigned_tx,
+      requestId: signResult.request_id,
+      qrUrl: signResult.ergopay_url,
+      nautilusUrl: signResult.nautilus_url,
+      intent: buildResult.intent,
+    }
+  }
+
   const handleSign = async () => {
     if (!preview || !calculated.tokenAmountRaw) return
 
@@ -254,32 +290,8 @@ export function DexyMintModal({
     setError(null)
 
     try {
-      const utxos = await invoke<unknown[]>('get_user_utxos')
-      const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
-
-      const buildResult = await invoke<{ unsigned_tx: unknown }>('build_mint_dexy', {
-        request: {
-          variant,
-          amount: calculated.tokenAmountRaw,
-          user_address: walletAddress,
-          user_utxos: utxos,
-          current_height: nodeStatus.chain_height,
-          recipient_address: recipientOrNull,
-        }
-      })
-
-      const signResult = await invoke<{
-        request_id: string
-        ergopay_url: string
-        nautilus_url: string
-      }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResult.unsigned_tx,
-          message: `Mint ${calculated.tokenAmount.toFixed(config.decimals)} ${config.name}`,
-        }
-      })
-
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const req = await buildSigningRequest(false)
+      flow.startSigning(req.requestId, req.qrUrl, req.nautilusUrl, req.unsignedTx, req.intent)
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -572,6 +584,15 @@ export function DexyMintModal({
 
           {step === 'signing' && (
             <div className="mint-signing-step">
+              {flow.rebuildCount > 0 && (
+                <div className="warning-box">
+                  The bank box was spent by another transaction. Rebuilt on the latest bank state; please sign again.
+                </div>
+              )}
+              {oracleWarnings.map((w) => (
+                <div key={w} className="warning-box">Oracle: {w}</div>
+              ))}
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               {flow.signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
@@ -12,6 +12,7 @@ import { formatErg } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
 import { AdvancedOptions, useRecipientAddress } from './AdvancedOptions'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Modal, Button } from './ui'
 import { DEV_FEE_NANO } from '../constants'
 import './DexySwapModal.css'
@@ -283,17 +284,15 @@ export function DexySwapModal({
         recipientOrNull,
       )
 
-      const inputLabel = direction === 'erg_to_dexy'
-        ? `${inputValue} ERG`
-        : `${inputValue} ${config.name}`
-      const outputLabel = direction === 'erg_to_dexy'
-        ? config.name
-        : 'ERG'
-      const message = `Swap ${inputLabel} for ${outputLabel}`
+      const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
 
-      const signResult = await startSign(buildResult.unsigned_tx, message)
-
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResult.unsigned_tx,
+        buildResult.intent,
+      )
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -556,6 +555,7 @@ export function DexySwapModal({
 
           {step === 'signing' && (
             <div className="mint-signing-step">
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               {flow.signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
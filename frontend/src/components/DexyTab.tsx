@@ -14,7 +14,8 @@ import {
 import { formatErg } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
-import type { TxStatusResponse } from '../api/types'
+import { startSign, type TxStatusResponse } from '../api/types'
+import { TxIntentSummary } from './TxIntentSummary'
 import { useExplorerNav } from '../contexts/ExplorerNavContext'
 import { Tabs, EmptyState } from './ui'
 import './DexyTab.css'
@@ -475,18 +476,15 @@ export function DexyTab({
         nodeStatus.chain_height,
       )
 
-      const signResult = await invoke<{
-        request_id: string
-        ergopay_url: string
-        nautilus_url: string
-      }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResult.unsigned_tx,
-          message: `Add liquidity: ${depositErg} ERG + ${depositDexy} ${selectedVariant === 'usd' ? 'USE' : 'DexyGold'}`,
-        },
-      })
+      const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
 
-      lpFlow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      lpFlow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResult.unsigned_tx,
+        buildResult.intent,
+      )
       setLpTxStep('signing')
     } catch (e) {
       setLpTxError(String(e))
@@ -516,18 +514,15 @@ export function DexyTab({
         nodeStatus.chain_height,
       )
 
-      const signResult = await invoke<{
-        request_id: string
-        ergopay_url: string
-        nautilus_url: string
-      }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResult.unsigned_tx,
-          message: `Remove liquidity: ${redeemErg} ERG + ${redeemDexy} ${selectedVariant === 'usd' ? 'USE' : 'DexyGold'}`,
-        },
-      })
+      const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
 
-      lpFlow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      lpFlow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResult.unsigned_tx,
+        buildResult.intent,
+      )
       setLpTxStep('signing')
     } catch (e) {
       setLpTxError(String(e))
@@ -989,6 +984,7 @@ export function DexyTab({
               <div className="dexy-lp-section">
                 <h3>Sign Transaction</h3>
                 <div className="mint-signing-step">
+                  {lpFlow.intent && <TxIntentSummary intent={lpFlow.intent} />}
                   {lpFlow.signMethod === 'choose' && (
                     <div className="sign-method-choice">
                       <p>Choose signing method:</p>
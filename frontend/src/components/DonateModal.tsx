@@ -6,6 +6,7 @@ import { DEFAULT_DEV_FEE_ADDRESS, DEV_FEE_NANO, MIN_BOX_VALUE_NANO, WALLET_TX_FE
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import { formatErg, truncateAddress } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Button, FormField, Input, Modal, Spinner } from './ui'
 import './DonateModal.css'
 
@@ -133,14 +134,13 @@ export function DonateModal({
         currentHeight: nodeStatus.chain_height,
       })
 
-      const tipErg = formatErg(Number(amountNano))
       const noteTrim = note.trim().slice(0, 80)
-      const msg = noteTrim
-        ? `Citadel tip ${tipErg} ERG — ${noteTrim}`
-        : `Citadel tip ${tipErg} ERG`
+      const intent = noteTrim
+        ? { ...result.intent, notes: [...result.intent.notes, noteTrim] }
+        : result.intent
 
-      const signResult = await startSign(result.unsignedTx, msg)
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const signResult = await startSign(result.unsignedTx, undefined, intent)
+      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url, result.unsignedTx, intent)
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -282,6 +282,7 @@ export function DonateModal({
         {step === 'signing' && flow.signMethod === 'choose' && (
           <div className="donate-signing">
             <p className="donate-lead">Choose how to sign your {formatErg(tipNano)} ERG tip</p>
+            {flow.intent && <TxIntentSummary intent={flow.intent} />}
             <div className="donate-wallet-options">
               <button type="button" className="donate-wallet-option" onClick={flow.handleNautilusSign}>
                 <span className="donate-wallet-option-name">Nautilus</span>
@@ -0,0 +1,334 @@
+import { useState, useCallback, useEffect, useRef, type ChangeEvent } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import {
+  previewHodlCoinDeploy,
+  buildHodlCoinDeployTx,
+  type HodlBankDeployParams,
+  type HodlBankDeployPlan,
+  type HodlBankDeployResponse,
+} from '../api/hodlcoin'
+import { startArbLegSign, submitArbChain, type ArbChainSubmitResponse } from '../api/arb'
+import { getTxStatus } from '../api/types'
+import { formatErg } from '../utils/format'
+import { TxIntentSummary } from './TxIntentSummary'
+import { Modal, Button, FormField, Input, Spinner } from './ui'
+
+interface HodlCoinDeployModalProps {
+  isOpen: boolean
+  onClose: () => void
+  onDone: () => void
+}
+
+type Step = 'input' | 'review' | 'building' | 'signing' | 'submitting' | 'done' | 'error'
+
+/** Form values in display units (tokens, ERG, fee per mille). */
+interface DeployForm {
+  name: string
+  description: string
+  decimals: string
+  totalSupply: string
+  initialReserveErg: string
+  initialCirculating: string
+  minBankValueErg: string
+  bankFee: string
+  devFee: string
+  precisionFactor: string
+}
+
+const DEFAULT_FORM: DeployForm = {
+  name: '',
+  description: '',
+  decimals: '9',
+  totalSupply: '1000000',
+  initialReserveErg: '1',
+  initialCirculating: '1',
+  minBankValueErg: '0.001',
+  bankFee: '30',
+  devFee: '3',
+  precisionFactor: '1000000',
+}
+
+function toParams(form: DeployForm): HodlBankDeployParams {
+  const decimals = parseInt(form.decimals || '0', 10)
+  const tokenUnits = Math.pow(10, decimals)
+  return {
+    hodlTokenName: form.name.trim(),
+    hodlTokenDescription: form.description.trim() || undefined,
+    hodlTokenDecimals: decimals,
+    totalTokenSupply: Math.floor(parseFloat(form.totalSupply || '0') * tokenUnits),
+    precisionFactor: parseInt(form.precisionFactor || '0', 10),
+    minBankValue: Math.floor(parseFloat(form.minBankValueErg || '0') * 1e9),
+    devFeeNum: parseInt(form.devFee || '0', 10),
+    bankFeeNum: parseInt(form.bankFee || '0', 10),
+    initialReserve: Math.floor(parseFloat(form.initialReserveErg || '0') * 1e9),
+    initialCirculating: Math.floor(parseFloat(form.initialCirculating || '0') * tokenUnits),
+  }
+}
+
+/**
+ * Deploys a new hodlERG bank as two 0-conf chained transactions (token
+ * bootstrap, then bank creation): preview -> build both legs -> sign each in
+ * Nautilus (nothing broadcast) -> submit in order.
+ */
+export function HodlCoinDeployModal({ isOpen, onClose, onDone }: HodlCoinDeployModalProps) {
+  const [step, setStep] = useState<Step>('input')
+  const [form, setForm] = useState<DeployForm>(DEFAULT_FORM)
+  const [plan, setPlan] = useState<HodlBankDeployPlan | null>(null)
+  const [build, setBuild] = useState<HodlBankDeployResponse | null>(null)
+  const [signingLeg, setSigningLeg] = useState(0)
+  const [submitResult, setSubmitResult] = useState<ArbChainSubmitResponse | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [loading, setLoading] = useState(false)
+  const pollRef = useRef<ReturnType<typeof setInterval> | null>(null)
+
+  const stopPolling = useCallback(() => {
+    if (pollRef.current) {
+      clearInterval(pollRef.current)
+      pollRef.current = null
+    }
+  }, [])
+
+  useEffect(() => () => stopPolling(), [stopPolling])
+
+  const update = (field: keyof DeployForm) => (e: ChangeEvent<HTMLInputElement>) =>
+    setForm(prev => ({ ...prev, [field]: e.target.value }))
+
+  const handlePreview = async () => {
+    setLoading(true)
+    setError(null)
+    try {
+      setPlan(await previewHodlCoinDeploy(toParams(form)))
+      setStep('review')
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setLoading(false)
+    }
+  }
+
+  const doSubmit = useCallback(async (ids: string[]) => {
+    setStep('submitting')
+    try {
+      const result = await submitArbChain(ids)
+      setSubmitResult(result)
+      setStep('done')
+      if (result.failedLeg === null) onDone()
+    } catch (e) {
+      setError(String(e))
+      setStep('error')
+    }
+  }, [onDone])
+
+  const signLeg = useCallback(async (
+    built: HodlBankDeployResponse,
+    legIndex: number,
+    priorRequestIds: string[],
+  ) => {
+    setSigningLeg(legIndex)
+    setStep('signing')
+    try {
+      const leg = built.legs[legIndex]
+      const label = legIndex === 0 ? 'mint bank tokens' : 'create bank'
+      const message = `HodlCoin deploy ${legIndex + 1}/${built.legs.length}: ${label} (NOT broadcast until all legs signed)`
+      const sign = await startArbLegSign(leg.unsignedTx, message)
+      const ids = [...priorRequestIds, sign.requestId]
+      await invoke('open_nautilus', { nautilusUrl: sign.nautilusUrl })
+
+      stopPolling()
+      pollRef.current = setInterval(async () => {
+        try {
+          const status = await getTxStatus(sign.requestId)
+          if (status.status === 'signed') {
+            stopPolling()
+            if (legIndex + 1 < built.legs.length) {
+              await signLeg(built, legIndex + 1, ids)
+            } else {
+              await doSubmit(ids)
+            }
+          } else if (status.status === 'expired' || status.status === 'failed') {
+            stopPolling()
+            setError(status.error || 'Signing request failed')
+            setStep('error')
+          }
+        } catch {
+          // transient poll error -- keep polling
+        }
+      }, 1500)
+    } catch (e) {
+      setError(String(e))
+      setStep('error')
+    }
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [stopPolling, doSubmit])
+
+  const handleBuild = async () => {
+    setStep('building')
+    setError(null)
+    try {
+      const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
+      const utxos = await invoke<object[]>('get_user_utxos')
+      if (!utxos?.length) throw new Error('No UTXOs available')
+      const built = await buildHodlCoinDeployTx(toParams(form), utxos, nodeStatus.chain_height)
+      setBuild(built)
+      await signLeg(built, 0, [])
+    } catch (e) {
+      setError(String(e))
+      setStep('error')
+    }
+  }
+
+  const handleClose = () => {
+    stopPolling()
+    onClose()
+  }
+
+  if (!isOpen) return null
+
+  const tokenUnits = Math.pow(10, plan?.params.hodlTokenDecimals ?? 0)
+
+  return (
+    <Modal open={isOpen} onClose={handleClose} title="Deploy HodlCoin Bank" size="md">
+      {step === 'input' && (
+        <>
+          <FormField label="hodlToken name">
+            <Input value={form.name} onChange={update('name')} placeholder="hodlERG" />
+          </FormField>
+          <FormField label="Description">
+            <Input value={form.description} onChange={update('description')} />
+          </FormField>
+          <FormField label="Decimals">
+            <Input inputMode="numeric" value={form.decimals} onChange={update('decimals')} />
+          </FormField>
+          <FormField label="Total supply" hint="hodlTokens ever minted (R4)">
+            <Input inputMode="decimal" value={form.totalSupply} onChange={update('totalSupply')} />
+          </FormField>
+          <FormField label="Initial reserve (ERG)" hint="Seeded into the bank by you">
+            <Input inputMode="decimal" value={form.initialReserveErg} onChange={update('initialReserveErg')} />
+          </FormField>
+          <FormField label="Initial circulating" hint="hodlTokens you receive for the initial reserve">
+            <Input inputMode="decimal" value={form.initialCirculating} onChange={update('initialCirculating')} />
+          </FormField>
+          <FormField label="Minimum bank value (ERG)" hint="Burns can never take the reserve below this (R6)">
+            <Input inputMode="decimal" value={form.minBankValueErg} onChange={update('minBankValueErg')} />
+          </FormField>
+          <FormField label="Bank fee (per mille)" hint="Stays in the reserve on every burn (R8)">
+            <Input inputMode="numeric" value={form.bankFee} onChange={update('bankFee')} />
+          </FormField>
+          <FormField label="Dev fee (per mille)" hint="Paid to the Phoenix dev fee contract on every burn (R7)">
+            <Input inputMode="numeric" value={form.devFee} onChange={update('devFee')} />
+          </FormField>
+          <FormField label="Precision factor" hint="Price scaling (R5)">
+            <Input inputMode="numeric" value={form.precisionFactor} onChange={update('precisionFactor')} />
+          </FormField>
+          {error && <div className="message error">{error}</div>}
+          <div className="modal-actions">
+            <Button variant="secondary" onClick={handleClose}>Cancel</Button>
+            <Button variant="primary" onClick={handlePreview} disabled={loading || !form.name.trim()}>
+              {loading ? 'Checking...' : 'Preview'}
+            </Button>
+          </div>
+        </>
+      )}
+
+      {step === 'review' && plan && (
+        <>
+          <div className="preview-section">
+            <div className="preview-row highlight">
+              <span>Opening price</span>
+              <span>{formatErg(plan.openingPriceNanoPerHodl * tokenUnits)} ERG per {plan.params.hodlTokenName}</span>
+            </div>
+            <div className="preview-row">
+              <span>Tokens left in bank</span>
+              <span>{(plan.hodlTokensInBank / tokenUnits).toLocaleString()}</span>
+            </div>
+            <div className="preview-row">
+              <span>Burn fee</span>
+              <span>{plan.totalFeePct.toFixed(1)}% (bank {plan.bankFeePct.toFixed(1)}% + dev {plan.devFeePct.toFixed(1)}%)</span>
+            </div>
+            <div className="preview-row">
+              <span>Smallest burn</span>
+              <span>{(plan.minBurnHodl / tokenUnits).toLocaleString()} {plan.params.hodlTokenName}</span>
+            </div>
+            <div className="preview-row">
+              <span>Miner fees</span>
+              <span>{formatErg(plan.minerFee)} ERG</span>
+            </div>
+            <div className="preview-row highlight">
+              <span>Total cost</span>
+              <span>{formatErg(plan.totalErgCost)} ERG</span>
+            </div>
+          </div>
+          <div className="warning-box">
+            You will sign 2 transactions in Nautilus: one mints the bank
+            tokens, the next creates the bank. Nothing is broadcast until both
+            are signed. Bank parameters cannot be changed after deployment.
+          </div>
+          <div className="modal-actions">
+            <Button variant="secondary" onClick={() => setStep('input')}>Back</Button>
+            <Button variant="primary" onClick={handleBuild}>Build &amp; sign</Button>
+          </div>
+        </>
+      )}
+
+      {step === 'building' && (
+        <div className="swap-preview-loading">
+          <Spinner size={20} />
+          <p>Building bootstrap and bank transactions...</p>
+        </div>
+      )}
+
+      {step === 'signing' && build && (
+        <div className="mint-signing-step">
+          <TxIntentSummary intent={build.intent} />
+          <Spinner size={20} />
+          <p>Sign transaction {signingLeg + 1} of {build.legs.length} in Nautilus...</p>
+          <div className="modal-actions">
+            <Button variant="secondary" onClick={handleClose}>Abort (nothing broadcast)</Button>
+          </div>
+        </div>
+      )}
+
+      {step === 'submitting' && (
+        <div className="swap-preview-loading">
+          <Spinner size={20} />
+          <p>Both transactions signed. Broadcasting in order...</p>
+        </div>
+      )}
+
+      {step === 'done' && submitResult && build && (
+        <>
+          {submitResult.failedLeg === null ? (
+            <div className="preview-section">
+              <div className="preview-row highlight">
+                <span>Bank deployed</span>
+                <span className="mono" title={build.singletonTokenId}>{build.singletonTokenId.slice(0, 16)}...</span>
+              </div>
+              <div className="preview-row">
+                <span>hodlToken</span>
+                <span className="mono" title={build.hodlTokenId}>{build.hodlTokenId.slice(0, 16)}...</span>
+              </div>
+            </div>
+          ) : (
+            <div className="message error">
+              {submitResult.error} — {submitResult.txIds.length} of {build.legs.length} transactions
+              landed.
+            </div>
+          )}
+          <div className="modal-actions">
+            <Button variant="primary" onClick={handleClose}>Close</Button>
+          </div>
+        </>
+      )}
+
+      {step === 'error' && (
+        <>
+          <div className="message error">{error}</div>
+          <div className="modal-actions">
+            <Button variant="secondary" onClick={handleClose}>Close</Button>
+            <Button variant="primary" onClick={() => { setError(null); setStep('input') }}>Edit parameters</Button>
+          </div>
+        </>
+      )}
+    </Modal>
+  )
+}
@@ -0,0 +1,148 @@
+import { useState, useEffect } from 'react'
+import {
+  getHodlCoinHistory,
+  getHodlCoinPositions,
+  type BankPriceHistory,
+  type HodlPositionsResponse,
+} from '../api/hodlcoinHistory'
+import { formatErg } from '../utils/format'
+import type { HodlBankState } from '../api/hodlcoin'
+
+/** ~1 day of blocks per history sample */
+const HISTORY_SAMPLE_BLOCKS = 720
+
+const CHART_WIDTH = 240
+const CHART_HEIGHT = 56
+
+/** Reserve amount: ERG, or raw reserve token units for token-backed banks. */
+function formatReserve(raw: number, reserveTokenId: string | null): string {
+  return reserveTokenId ? raw.toLocaleString() : `${formatErg(raw)} ERG`
+}
+
+/** Price curve of one bank, one point per sample window. */
+export function HodlBankHistory({ bank }: { bank: HodlBankState }) {
+  const [history, setHistory] = useState<BankPriceHistory | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    let cancelled = false
+    getHodlCoinHistory(bank.singletonTokenId, { sampleBlocks: HISTORY_SAMPLE_BLOCKS })
+      .then(h => { if (!cancelled) setHistory(h) })
+      .catch(e => { if (!cancelled) setError(String(e)) })
+    return () => { cancelled = true }
+  }, [bank.singletonTokenId])
+
+  if (error) return <div className="hodl-history-note">{error}</div>
+  if (!history) return <div className="hodl-history-note">Tracing bank history…</div>
+  if (history.points.length < 2) {
+    return <div className="hodl-history-note">Not enough bank transitions to chart yet</div>
+  }
+
+  const prices = history.points.map(p => p.priceNanoPerHodl)
+  const min = Math.min(...prices)
+  const max = Math.max(...prices)
+  const span = max - min || 1
+  const last = history.points.length - 1
+  const line = history.points
+    .map((p, i) => {
+      const x = (i / last) * CHART_WIDTH
+      const y = CHART_HEIGHT - ((p.priceNanoPerHodl - min) / span) * CHART_HEIGHT
+      return `${x.toFixed(1)},${y.toFixed(1)}`
+    })
+    .join(' ')
+  const first = prices[0]
+  const changePct = first > 0 ? ((prices[last] - first) / first) * 100 : 0
+
+  return (
+    <div className="hodl-history">
+      <svg
+        className="hodl-history-chart"
+        viewBox={`0 0 ${CHART_WIDTH} ${CHART_HEIGHT}`}
+        preserveAspectRatio="none"
+      >
+        <polyline points={line} fill="none" stroke="currentColor" strokeWidth="1.5" />
+      </svg>
+      <div className="hodl-history-meta mono">
+        <span>Since block {history.historyStartHeight.toLocaleString()}</span>
+        <span className={changePct >= 0 ? 'positive' : 'negative'}>
+          {changePct >= 0 ? '+' : ''}{changePct.toFixed(2)}%
+        </span>
+        <span>{history.transitions} txs</span>
+      </div>
+    </div>
+  )
+}
+
+/** The active wallet's position in every bank it holds or has traded. */
+export function HodlPositionsPanel({
+  refreshKey,
+  tokenDecimals,
+}: {
+  refreshKey: number
+  /** Decimals of a token in the wallet (0 if unknown) */
+  tokenDecimals: (tokenId: string) => number
+}) {
+  const [data, setData] = useState<HodlPositionsResponse | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    let cancelled = false
+    getHodlCoinPositions()
+      .then(d => { if (!cancelled) { setData(d); setError(null) } })
+      .catch(e => { if (!cancelled) setError(String(e)) })
+    return () => { cancelled = true }
+  }, [refreshKey])
+
+  if (error) return <div className="message error">{error}</div>
+  if (!data || data.positions.length === 0) return null
+
+  return (
+    <section className="hodl-positions">
+      <h2 className="hodl-positions-title">Your positions</h2>
+      <div className="hodl-positions-list">
+        {data.positions.map(p => {
+          const name = p.hodlTokenName || `hodl...${p.hodlTokenId.slice(-6)}`
+          const hodlUnits = Math.pow(10, tokenDecimals(p.hodlTokenId))
+          return (
+            <div key={p.singletonTokenId} className="hodl-position-row">
+              <span className="hodl-position-name">{name}</span>
+              <span>
+                <span className="hodl-stat-label">Held</span>
+                <span className="mono">{(p.held / hodlUnits).toLocaleString()}</span>
+              </span>
+              <span>
+                <span className="hodl-stat-label">Avg mint price</span>
+                <span className="mono">
+                  {p.avgMintPrice !== null ? formatReserve(p.avgMintPrice * hodlUnits, p.reserveTokenId) : '—'}
+                </span>
+              </span>
+              <span>
+                <span className="hodl-stat-label">Burn value</span>
+                <span className="mono">{formatReserve(p.burnValue, p.reserveTokenId)}</span>
+              </span>
+              <span>
+                <span className="hodl-stat-label">Unrealized</span>
+                <span className={`mono ${(p.unrealizedGain ?? 0) >= 0 ? 'positive' : 'negative'}`}>
+                  {p.unrealizedGain !== null ? formatReserve(p.unrealizedGain, p.reserveTokenId) : '—'}
+                  {p.unrealizedGainPct !== null && ` (${p.unrealizedGainPct.toFixed(2)}%)`}
+                </span>
+              </span>
+              <span>
+                <span className="hodl-stat-label">Realized</span>
+                <span className="mono">{formatReserve(p.realizedGain, p.reserveTokenId)}</span>
+              </span>
+              {p.untracked > 0 && (
+                <span className="hodl-position-note" title="Tokens not matched to a mint from this wallet">
+                  {(p.untracked / hodlUnits).toLocaleString()} untracked
+                </span>
+              )}
+            </div>
+          )
+        })}
+      </div>
+      {data.errors.length > 0 && (
+        <div className="hodl-history-note">{data.errors.join(' · ')}</div>
+      )}
+    </section>
+  )
+}
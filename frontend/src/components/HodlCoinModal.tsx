@@ -11,9 +11,11 @@ import {
   buildHodlCoinBurnTx,
 } from '../api/hodlcoin'
 import { formatErg } from '../utils/format'
-import { startSign, getTxStatus } from '../api/types'
+import { startSign, getTxStatus, type IntentBuildResponse } from '../api/types'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
+import type { SigningRequest } from '../hooks/useTransactionFlow'
 import { Modal, Button, Tabs, FormField, Spinner } from './ui'
 import './HodlCoinModal.css'
 
@@ -73,6 +75,7 @@ export function HodlCoinModal({
     onSuccess: () => { setStep('success'); onSuccess() },
     onError: (err) => { setError(err); setStep('error') },
     watchParams: { protocol: 'HodlCoin', operation: tab, description: `HodlCoin ${tab}` },
+    rebuild: () => buildSigningRequest(true),
   })
 
   const bankName = bank.hodlTokenName || `hodl...${bank.hodlTokenId.slice(-6)}`
@@ -83,8 +86,21 @@ export function HodlCoinModal({
   const hodlDivisor = Math.pow(10, hodlDecimals)
   const hodlBalanceDisplay = hodlBalanceRaw / hodlDivisor
 
-  // Parse input amounts (ERG for mint in nanoERG, display tokens for burn -> raw)
-  const mintNanoErg = Math.floor(parseFloat(mintAmountStr || '0') * 1e9)
+  // Token-backed banks take deposits and pay redemptions in their reserve token
+  const reserveToken = bank.reserveTokenId
+    ? walletBalance.tokens.find(t => t.token_id === bank.reserveTokenId)
+    : undefined
+  const reserveUnit = bank.reserveTokenId
+    ? bank.reserveTokenName || reserveToken?.name || `${bank.reserveTokenId.slice(0, 6)}...`
+    : 'ERG'
+  const reserveDivisor = bank.reserveTokenId ? Math.pow(10, reserveToken?.decimals ?? 0) : 1e9
+  const reserveBalanceRaw = bank.reserveTokenId ? reserveToken?.amount ?? 0 : ergBalance
+  const formatReserve = (raw: number) => bank.reserveTokenId
+    ? (raw / reserveDivisor).toLocaleString(undefined, { maximumFractionDigits: 6 })
+    : formatErg(raw)
+
+  // Parse input amounts (reserve units for mint, display tokens for burn -> raw)
+  const depositRaw = Math.floor(parseFloat(mintAmountStr || '0') * reserveDivisor)
   const burnAmount = Math.floor(parseFloat(burnAmountStr || '0') * hodlDivisor)
 
   const handlePreview = useCallback(async () => {
@@ -92,11 +108,11 @@ export function HodlCoinModal({
     setLoading(true)
     try {
       if (tab === 'mint') {
-        if (mintNanoErg <= 0) {
+        if (depositRaw <= 0) {
           setError('Enter an amount to deposit')
           return
         }
-        const preview = await previewHodlCoinMint(bank.singletonTokenId, mintNanoErg)
+        const preview = await previewHodlCoinMint(bank.singletonTokenId, depositRaw)
         setMintPreview(preview)
       } else {
         if (burnAmount <= 0) {
@@ -112,7 +128,45 @@ export function HodlCoinModal({
     } finally {
       setLoading(false)
     }
-  }, [tab, mintNanoErg, burnAmount, bank.singletonTokenId])
+  }, [tab, depositRaw, burnAmount, bank.singletonTokenId])
+
+  // Build + start signing; also the auto-rebuild when a competing tx spends the bank box
+  const buildSigningRequest = async (buildOnPending: boolean): Promise<SigningRequest> => {
+    const utxos = await invoke<Array<{ ergo_tree?: string; ergoTree?: string }>>('get_user_utxos')
+    if (!utxos?.length) throw new Error('No UTXOs available')
+
+    const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
+
+    let built: IntentBuildResponse
+
+    if (tab === 'mint') {
+      built = await buildHodlCoinMintTx(
+        bank.singletonTokenId,
+        depositRaw,
+        utxos as object[],
+        nodeStatus.chain_height,
+        buildOnPending,
+      )
+    } else {
+      built = await buildHodlCoinBurnTx(
+        bank.singletonTokenId,
+        burnAmount,
+        utxos as object[],
+        nodeStatus.chain_height,
+        buildOnPending,
+      )
+    }
+
+    const signResult = await startSign(built.unsignedTx, undefined, built.intent)
+
+    return {
+      unsignedTx: built.unsignedTx,
+      requestId: signResult.request_id,
+      qrUrl: signResult.ergopay_url,
+      nautilusUrl: signResult.nautilus_url,
+      intent: built.intent,
+    }
+  }
 
   const handleBuild = async () => {
     setLoading(true)
@@ -120,35 +174,8 @@ export function HodlCoinModal({
     setStep('building')
 
     try {
-      const utxos = await invoke<Array<{ ergo_tree?: string; ergoTree?: string }>>('get_user_utxos')
-      if (!utxos?.length) throw new Error('No UTXOs available')
-
-      const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
-
-      let unsignedTx: object
-      let message: string
-
-      if (tab === 'mint') {
-        unsignedTx = await buildHodlCoinMintTx(
-          bank.singletonTokenId,
-          mintNanoErg,
-          utxos as object[],
-          nodeStatus.chain_height,
-        )
-        message = `Mint ${bankName}: deposit ${formatErg(mintNanoErg)} ERG`
-      } else {
-        unsignedTx = await buildHodlCoinBurnTx(
-          bank.singletonTokenId,
-          burnAmount,
-          utxos as object[],
-          nodeStatus.chain_height,
-        )
-        message = `Burn ${burnAmount} ${bankName}`
-      }
-
-      const signResult = await startSign(unsignedTx, message)
-
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const req = await buildSigningRequest(false)
+      flow.startSigning(req.requestId, req.qrUrl, req.nautilusUrl, req.unsignedTx, req.intent)
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -187,7 +214,10 @@ export function HodlCoinModal({
           {/* Input Step */}
           {step === 'input' && tab === 'mint' && (
             <>
-              <FormField label="Deposit ERG" hint={`Balance: ${formatErg(ergBalance)} ERG`}>
+              <FormField
+                label={`Deposit ${reserveUnit}`}
+                hint={`Balance: ${formatReserve(reserveBalanceRaw)} ${reserveUnit}`}
+              >
                 <div className="hodl-input-wrapper">
                   <input
                     type="text"
@@ -199,6 +229,10 @@ export function HodlCoinModal({
                   <button
                     className="hodl-max-btn"
                     onClick={() => {
+                      if (bank.reserveTokenId) {
+                        setMintAmountStr((reserveBalanceRaw / reserveDivisor).toString())
+                        return
+                      }
                       const max = Math.max(0, ergBalance - 5_000_000) / 1e9
                       setMintAmountStr(max.toFixed(4))
                     }}
@@ -209,12 +243,12 @@ export function HodlCoinModal({
               </FormField>
               <div className="hodl-info-row">
                 <span>Price</span>
-                <span>{formatErg(bank.priceNanoPerHodl * 1e9)} ERG per token</span>
+                <span>{formatReserve(bank.priceNanoPerHodl * 1e9)} {reserveUnit} per token</span>
               </div>
               <div className="hodl-info-row">
                 <span>Est. tokens</span>
-                <span>{mintNanoErg > 0
-                  ? Math.floor(mintNanoErg / (bank.priceNanoPerHodl * 1e9) || 0).toLocaleString()
+                <span>{depositRaw > 0
+                  ? Math.floor(depositRaw / (bank.priceNanoPerHodl * 1e9) || 0).toLocaleString()
                   : '0'
                 }</span>
               </div>
@@ -245,18 +279,18 @@ export function HodlCoinModal({
               </FormField>
               <div className="hodl-info-row">
                 <span>Price</span>
-                <span>{formatErg(bank.priceNanoPerHodl * 1e9)} ERG per token</span>
+                <span>{formatReserve(bank.priceNanoPerHodl * 1e9)} {reserveUnit} per token</span>
               </div>
               <div className="hodl-info-row">
                 <span>Total fee</span>
                 <span>{bank.totalFeePct.toFixed(1)}% (bank {bank.bankFeePct.toFixed(1)}% + dev {bank.devFeePct.toFixed(1)}%)</span>
               </div>
               <div className="hodl-info-row">
-                <span>Est. ERG received</span>
+                <span>Est. {reserveUnit} received</span>
                 <span>{burnAmount > 0
-                  ? formatErg(Math.floor(burnAmount * bank.priceNanoPerHodl * 1e9 * (1 - bank.totalFeePct / 100)))
+                  ? formatReserve(Math.floor(burnAmount * bank.priceNanoPerHodl * (1 - bank.totalFeePct / 100)))
                   : '0'
-                } ERG</span>
+                } {reserveUnit}</span>
               </div>
             </>
           )}
@@ -268,7 +302,7 @@ export function HodlCoinModal({
               variant="primary"
               className="hodl-submit-btn"
               onClick={handlePreview}
-              disabled={loading || (tab === 'mint' ? mintNanoErg <= 0 : burnAmount <= 0)}
+              disabled={loading || (tab === 'mint' ? depositRaw <= 0 : burnAmount <= 0)}
             >
               {loading ? 'Loading...' : 'Preview'}
             </Button>
@@ -281,7 +315,7 @@ export function HodlCoinModal({
                 <h3>Mint Preview</h3>
                 <div className="hodl-info-row">
                   <span>Deposit</span>
-                  <span>{formatErg(mintPreview.ergDeposited)} ERG</span>
+                  <span>{formatReserve(mintPreview.ergDeposited)} {reserveUnit}</span>
                 </div>
                 <div className="hodl-info-row highlight">
                   <span>You Receive</span>
@@ -289,7 +323,7 @@ export function HodlCoinModal({
                 </div>
                 <div className="hodl-info-row">
                   <span>Price</span>
-                  <span>{formatErg(mintPreview.pricePerToken * 1e9)} ERG</span>
+                  <span>{formatReserve(mintPreview.pricePerToken * 1e9)} {reserveUnit}</span>
                 </div>
                 <div className="hodl-info-row">
                   <span>Miner Fee</span>
@@ -319,19 +353,19 @@ export function HodlCoinModal({
                 </div>
                 <div className="hodl-info-row">
                   <span>Gross Value</span>
-                  <span>{formatErg(burnPreview.ergBeforeFees)} ERG</span>
+                  <span>{formatReserve(burnPreview.ergBeforeFees)} {reserveUnit}</span>
                 </div>
                 <div className="hodl-info-row fee">
                   <span>Bank Fee ({bank.bankFeePct.toFixed(1)}%)</span>
-                  <span>-{formatErg(burnPreview.bankFeeNano)} ERG</span>
+                  <span>-{formatReserve(burnPreview.bankFeeNano)} {reserveUnit}</span>
                 </div>
                 <div className="hodl-info-row fee">
                   <span>Dev Fee ({bank.devFeePct.toFixed(1)}%)</span>
-                  <span>-{formatErg(burnPreview.devFeeNano)} ERG</span>
+                  <span>-{formatReserve(burnPreview.devFeeNano)} {reserveUnit}</span>
                 </div>
                 <div className="hodl-info-row highlight">
                   <span>You Receive</span>
-                  <span>{formatErg(burnPreview.ergReceived)} ERG</span>
+                  <span>{formatReserve(burnPreview.ergReceived)} {reserveUnit}</span>
                 </div>
                 <div className="hodl-info-row">
                   <span>Miner Fee</span>
@@ -356,6 +390,14 @@ export function HodlCoinModal({
           )}
 
           {/* Signing Step - Choose */}
+          {step === 'signing' && flow.rebuildCount > 0 && (
+            <div className="warning-box">
+              The bank box was spent by another transaction. Rebuilt on the latest bank state; please sign again.
+            </div>
+          )}
+
+          {step === 'signing' && flow.intent && <TxIntentSummary intent={flow.intent} />}
+
           {step === 'signing' && flow.signMethod === 'choose' && (
             <div className="mint-signing-step">
               <p>Choose your signing method</p>
@@ -1,7 +1,15 @@
 import { useState, useEffect, useCallback } from 'react'
-import { getHodlCoinBanks, type HodlBankState } from '../api/hodlcoin'
+import {
+  getHodlCoinBanks,
+  getHodlCoinDiscoveryConfig,
+  setHodlCoinDiscoveryConfig,
+  type HodlBankState,
+  type HodlDiscoveryOptions,
+} from '../api/hodlcoin'
 import { formatErg } from '../utils/format'
 import { HodlCoinModal } from './HodlCoinModal'
+import { HodlCoinDeployModal } from './HodlCoinDeployModal'
+import { HodlBankHistory, HodlPositionsPanel } from './HodlCoinHistory'
 import { EmptyState } from './ui'
 import './HodlCoinTab.css'
 
@@ -18,6 +26,63 @@ function BankAvatar({ name }: { name: string }) {
   return <div className="hodl-bank-avatar">H</div>
 }
 
+/** Extra bank and dev fee contracts to scan, one ErgoTree hex per line. */
+function HodlContractsPanel({
+  options,
+  onSave,
+}: {
+  options: HodlDiscoveryOptions
+  onSave: (options: HodlDiscoveryOptions) => Promise<void>
+}) {
+  const [bankTrees, setBankTrees] = useState(options.customBankTrees.join('\n'))
+  const [feeTrees, setFeeTrees] = useState(options.customFeeTrees.join('\n'))
+  const [saving, setSaving] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const lines = (text: string) => text.split('\n').map(l => l.trim()).filter(Boolean)
+
+  const save = async () => {
+    setSaving(true)
+    setError(null)
+    try {
+      await onSave({ ...options, customBankTrees: lines(bankTrees), customFeeTrees: lines(feeTrees) })
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  return (
+    <section className="hodl-contracts">
+      <label className="hodl-contracts-field">
+        <span>Extra bank contracts (ErgoTree hex, one per line)</span>
+        <textarea
+          className="mono"
+          rows={3}
+          value={bankTrees}
+          onChange={e => setBankTrees(e.target.value)}
+          spellCheck={false}
+        />
+      </label>
+      <label className="hodl-contracts-field">
+        <span>Extra dev fee contracts (for token-backed burns)</span>
+        <textarea
+          className="mono"
+          rows={2}
+          value={feeTrees}
+          onChange={e => setFeeTrees(e.target.value)}
+          spellCheck={false}
+        />
+      </label>
+      {error && <div className="message error">{error}</div>}
+      <button className="action-btn primary hodl-primary" onClick={save} disabled={saving}>
+        {saving ? 'Saving…' : 'Save & rescan'}
+      </button>
+    </section>
+  )
+}
+
 interface WalletBalance {
   address: string
   erg_nano: number
@@ -50,6 +115,12 @@ export function HodlCoinTab({
   const [error, setError] = useState<string | null>(null)
   const [selectedBank, setSelectedBank] = useState<HodlBankState | null>(null)
   const [modalOpen, setModalOpen] = useState(false)
+  const [discovery, setDiscovery] = useState<HodlDiscoveryOptions | null>(null)
+  const [showContracts, setShowContracts] = useState(false)
+  const [historyBank, setHistoryBank] = useState<string | null>(null)
+  const [deployOpen, setDeployOpen] = useState(false)
+  // Bumped after a mint, burn or deploy so positions reload
+  const [positionsKey, setPositionsKey] = useState(0)
 
   const fetchBanks = useCallback(async () => {
     if (!isConnected || capabilityTier === 'Basic') return
@@ -69,6 +140,34 @@ export function HodlCoinTab({
     fetchBanks()
   }, [fetchBanks])
 
+  const refresh = useCallback(() => {
+    fetchBanks()
+    setPositionsKey(k => k + 1)
+  }, [fetchBanks])
+
+  const tokenDecimals = (tokenId: string) =>
+    walletBalance?.tokens.find(t => t.token_id === tokenId)?.decimals ?? 0
+
+  useEffect(() => {
+    if (!isConnected) return
+    getHodlCoinDiscoveryConfig().then(setDiscovery).catch(() => {})
+  }, [isConnected])
+
+  const saveDiscovery = async (options: HodlDiscoveryOptions) => {
+    setDiscovery(await setHodlCoinDiscoveryConfig(options))
+    setShowContracts(false)
+    await fetchBanks()
+  }
+
+  const toggleLowTvl = async () => {
+    if (!discovery) return
+    try {
+      await saveDiscovery({ ...discovery, includeLowTvl: !discovery.includeLowTvl })
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
   const openModal = (bank: HodlBankState) => {
     setSelectedBank(bank)
     setModalOpen(true)
@@ -130,11 +229,41 @@ export function HodlCoinTab({
             <p className="hodl-subtitle">Deposit ERG to mint hodlTokens · price can only go up</p>
           </div>
         </div>
-        {banks.length > 0 && (
-          <span className="hodl-meta-chip mono">{banks.length} bank{banks.length !== 1 ? 's' : ''}</span>
-        )}
+        <div className="hodl-header-right">
+          {discovery && (
+            <label className="hodl-toggle">
+              <input
+                type="checkbox"
+                checked={discovery.includeLowTvl}
+                disabled={loading}
+                onChange={toggleLowTvl}
+              />
+              <span>Show low-TVL banks</span>
+            </label>
+          )}
+          {discovery && (
+            <button className="hodl-meta-chip hodl-chip-btn" onClick={() => setShowContracts(v => !v)}>
+              Contracts{discovery.customBankTrees.length > 0 ? ` +${discovery.customBankTrees.length}` : ''}
+            </button>
+          )}
+          <button
+            className="hodl-meta-chip hodl-chip-btn"
+            disabled={!walletAddress}
+            onClick={() => setDeployOpen(true)}
+            title={!walletAddress ? 'Connect wallet first' : 'Deploy a new hodlERG bank'}
+          >
+            Deploy bank
+          </button>
+          {banks.length > 0 && (
+            <span className="hodl-meta-chip mono">{banks.length} bank{banks.length !== 1 ? 's' : ''}</span>
+          )}
+        </div>
       </header>
 
+      {showContracts && discovery && <HodlContractsPanel options={discovery} onSave={saveDiscovery} />}
+
+      {walletAddress && <HodlPositionsPanel refreshKey={positionsKey} tokenDecimals={tokenDecimals} />}
+
       <div className="hodl-body">
         {loading && banks.length === 0 && (
           <div className="hodl-empty-wrap">
@@ -202,6 +331,8 @@ export function HodlCoinTab({
                       </div>
                     </div>
 
+                    {historyBank === bank.singletonTokenId && <HodlBankHistory bank={bank} />}
+
                     {walletAddress && userBalance.raw > 0 && (
                       <div className="hodl-balance-box">
                         <span className="hodl-balance-label">Your Balance</span>
@@ -221,6 +352,12 @@ export function HodlCoinTab({
                         </svg>
                         Mint / Redeem
                       </button>
+                      <button
+                        className="hodl-meta-chip hodl-chip-btn"
+                        onClick={() => setHistoryBank(id => id === bank.singletonTokenId ? null : bank.singletonTokenId)}
+                      >
+                        {historyBank === bank.singletonTokenId ? 'Hide history' : 'History'}
+                      </button>
                     </div>
                   </div>
                 </div>
@@ -238,7 +375,15 @@ export function HodlCoinTab({
           walletAddress={walletAddress}
           walletBalance={walletBalance}
           explorerUrl={explorerUrl}
-          onSuccess={fetchBanks}
+          onSuccess={refresh}
+        />
+      )}
+
+      {deployOpen && (
+        <HodlCoinDeployModal
+          isOpen={deployOpen}
+          onClose={() => setDeployOpen(false)}
+          onDone={refresh}
         />
       )}
     </div>
@@ -21,7 +21,8 @@ import { formatErg } from '../utils/format'
 import type { WalletBalance } from './MarketCard'
 import { TxSuccess } from './TxSuccess'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
-import type { TxStatusResponse } from '../api/types'
+import { startSign, type TxStatusResponse } from '../api/types'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Modal, Button, Spinner } from './ui'
 import './LendModal.css'
 
@@ -217,18 +218,15 @@ export function LendModal({
     setError(null)
 
     try {
-      const signResult = await invoke<{
-        request_id: string
-        ergopay_url: string
-        nautilus_url: string
-      }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResponse.unsigned_tx,
-          message: `Lend ${calculated.amount.toFixed(pool.decimals)} ${pool.symbol} to ${pool.name}`,
-        },
-      })
-
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const signResult = await startSign(buildResponse.unsigned_tx as object, undefined, buildResponse.intent)
+
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResponse.unsigned_tx as object,
+        buildResponse.intent,
+      )
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -236,7 +234,7 @@ export function LendModal({
     } finally {
       setLoading(false)
     }
-  }, [buildResponse, calculated.amount, pool, flow])
+  }, [buildResponse, flow])
 
   // Handle max button click -- reserve space for service fee + slippage
   const handleMaxClick = useCallback(() => {
@@ -469,6 +467,7 @@ export function LendModal({
 
           {step === 'signing' && (
             <div className="lend-signing-step">
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               {flow.signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
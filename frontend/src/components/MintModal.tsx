@@ -4,7 +4,8 @@ import { QRCodeSVG } from 'qrcode.react'
 import { formatErg } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
-import type { TxStatusResponse } from '../api/types'
+import { startSign, type TxIntent, type TxStatusResponse } from '../api/types'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Modal, Button, FormField, Input } from './ui'
 
 interface MintPreviewResponse {
@@ -95,7 +96,7 @@ export function MintModal({ isOpen, onClose, walletAddress, ergBalance, explorer
       const utxos = await invoke<object[]>('get_user_utxos')
       const amountRaw = Math.round(parseFloat(amount) * 100)
 
-      const buildResult = await invoke<{ unsigned_tx: object; summary: object }>('build_mint_sigusd', {
+      const buildResult = await invoke<{ unsigned_tx: object; summary: object; intent: TxIntent }>('build_mint_sigusd', {
         request: {
           amount: amountRaw,
           user_address: walletAddress,
@@ -104,14 +105,15 @@ export function MintModal({ isOpen, onClose, walletAddress, ergBalance, explorer
         }
       })
 
-      const signResult = await invoke<{ request_id: string; ergopay_url: string; nautilus_url: string }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResult.unsigned_tx,
-          message: `Mint ${amount} SigUSD`
-        }
-      })
+      const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
 
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResult.unsigned_tx,
+        buildResult.intent,
+      )
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -193,6 +195,8 @@ export function MintModal({ isOpen, onClose, walletAddress, ergBalance, explorer
             </div>
           )}
 
+          {step === 'signing' && flow.intent && <TxIntentSummary intent={flow.intent} />}
+
           {step === 'signing' && flow.signMethod === 'choose' && (
             <div className="mint-signing-step">
               <p>Choose your signing method</p>
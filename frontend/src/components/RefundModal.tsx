@@ -17,6 +17,8 @@ import {
 import { TX_FEE_NANO } from '../constants'
 import { formatErg } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
+import { startSign } from '../api/types'
 import { Modal, Button, Spinner } from './ui'
 import './LendModal.css' // Reuse LendModal styles
 
@@ -215,16 +217,7 @@ export function RefundModal({
     setError(null)
 
     try {
-      const signResult = await invoke<{
-        request_id: string
-        ergopay_url: string
-        nautilus_url: string
-      }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResponse.unsigned_tx,
-          message: 'Refund stuck proxy box transaction',
-        },
-      })
+      const signResult = await startSign(buildResponse.unsigned_tx as object, undefined, buildResponse.intent)
 
       setRequestId(signResult.request_id)
       setQrUrl(signResult.ergopay_url)
@@ -386,6 +379,7 @@ export function RefundModal({
 
           {step === 'signing' && (
             <div className="lend-signing-step">
+              {buildResponse && <TxIntentSummary intent={buildResponse.intent} />}
               {signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
@@ -22,6 +22,8 @@ import { TX_FEE_NANO } from '../constants'
 import { formatErg } from '../utils/format'
 import type { WalletBalance } from './MarketCard'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
+import { startSign } from '../api/types'
 import { Modal, Button, Spinner } from './ui'
 import './LendModal.css' // Reuse LendModal styles
 
@@ -266,16 +268,7 @@ export function RepayModal({
     setError(null)
 
     try {
-      const signResult = await invoke<{
-        request_id: string
-        ergopay_url: string
-        nautilus_url: string
-      }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResponse.unsigned_tx,
-          message: `Repay ${calculated.amount.toFixed(pool.decimals)} ${pool.symbol} to ${pool.name}`,
-        },
-      })
+      const signResult = await startSign(buildResponse.unsigned_tx as object, undefined, buildResponse.intent)
 
       setRequestId(signResult.request_id)
       setQrUrl(signResult.ergopay_url)
@@ -287,7 +280,7 @@ export function RepayModal({
     } finally {
       setLoading(false)
     }
-  }, [buildResponse, calculated.amount, pool])
+  }, [buildResponse])
 
   // Handle Nautilus signing
   const handleNautilusSign = useCallback(async () => {
@@ -562,6 +555,7 @@ export function RepayModal({
 
           {step === 'signing' && (
             <div className="lend-signing-step">
+              {buildResponse && <TxIntentSummary intent={buildResponse.intent} />}
               {signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
@@ -3,7 +3,7 @@ import { invoke } from '@tauri-apps/api/core'
 import { QRCodeSVG } from 'qrcode.react'
 import {
   findSwapRoutes, findSwapRoutesByOutput, compareSigusdOptions,
-  getSigusdArbSnapshot,
+  getSigusdArbSnapshot, isRouteExecutable,
   type RouteQuote, type DepthTiers as DepthTiersType,
   type AcquisitionComparison, type SplitRouteDetail, type RouteHop,
   type OracleArbSnapshot,
@@ -15,6 +15,7 @@ import { startSign, getTxStatus } from '../api/types'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import { formatTokenAmount } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { SplitExecuteModal } from './SplitExecuteModal'
 import { Button, Modal, Spinner } from './ui'
 import './RouterTab.css'
@@ -64,6 +65,10 @@ function impactClass(impact: number): string {
 
 const HOP_IMPACT_TIERS = [0.5, 1, 2, 5]
 
+function splitExecutable(split: SplitRouteDetail): boolean {
+  return split.allocations.every(a => isRouteExecutable(a.route)) && splitPoolsDisjoint(split)
+}
+
 function splitPoolsDisjoint(split: SplitRouteDetail): boolean {
   const seen = new Set<string>()
   for (const a of split.allocations) {
@@ -215,7 +220,7 @@ export function RouterTab({
   }
 
   const handleExecuteRoute = async (rq: RouteQuote) => {
-    if (!walletAddress || rq.route.hops.length !== 1) return
+    if (!walletAddress || rq.route.hops.length !== 1 || !isRouteExecutable(rq.route)) return
     const hop = rq.route.hops[0]
 
     setExecRoute(rq)
@@ -241,12 +246,14 @@ export function RouterTab({
         nodeStatus.chain_height,
       )
 
-      const inLabel = hop.token_in_name || 'ERG'
-      const outLabel = hop.token_out_name || 'SigUSD'
-      const message = `Router: ${formatTokenAmount(hop.input_amount, hop.token_in_decimals)} ${inLabel} → ${formatTokenAmount(hop.output_amount, hop.token_out_decimals)} ${outLabel}`
-
-      const signResult = await startSign(buildResult.unsigned_tx, message)
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResult.unsigned_tx,
+        buildResult.intent,
+      )
       setExecStep('signing')
     } catch (e) {
       setExecError(String(e))
@@ -375,7 +382,7 @@ export function RouterTab({
               <SplitSuggestion
                 split={split}
                 oracleRate={oracleRate}
-                canExecute={!!walletAddress && splitPoolsDisjoint(split)}
+                canExecute={!!walletAddress && splitExecutable(split)}
                 onExecute={() => setShowSplitModal(true)}
               />
             )}
@@ -393,7 +400,7 @@ export function RouterTab({
                 bestInput={routes[0].route.total_input}
                 mode={mode}
                 oracleRate={oracleRate}
-                canExecute={!!walletAddress && rq.route.hops.length === 1}
+                canExecute={!!walletAddress && rq.route.hops.length === 1 && isRouteExecutable(rq.route)}
                 onExecute={() => handleExecuteRoute(rq)}
               />
             ))}
@@ -505,6 +512,7 @@ export function RouterTab({
               {execRoute.route.hops[0].token_out_name || 'SigUSD'} via pool{' '}
               {execRoute.route.hops[0].pool_id.slice(0, 8)}
             </p>
+            {flow.intent && <TxIntentSummary intent={flow.intent} />}
 
             {flow.nautilusUrl && (
               <Button
@@ -763,7 +771,9 @@ function RouteCard({ rq, isBest, bestOutput, bestInput, mode, oracleRate, canExe
           Execute Swap
         </Button>
       )}
-      {route.hops.length > 1 && (
+      {!isRouteExecutable(route) ? (
+        <span className="router-multihop-notice">Protocol hop (quote only)</span>
+      ) : route.hops.length > 1 && (
         <span className="router-multihop-notice">Multi-hop (view only)</span>
       )}
     </div>
@@ -12,8 +12,9 @@ import {
 import { formatAmount, formatPercent, blocksToTime, truncateAddress } from '../utils/format'
 import { startSign, getTxStatus } from '../api/types'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
-import type { TxStatusResponse } from '../api/types'
+import type { IntentBuildResponse, TxStatusResponse } from '../api/types'
 import { Modal, Button, Badge } from './ui'
 import './SigmaFiConfirmModal.css'
 
@@ -88,24 +89,22 @@ export function SigmaFiConfirmModal({
       const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
       const userErgoTree = (utxos[0] as { ergoTree: string }).ergoTree
 
-      let unsignedTx: object
-      let message: string
+      let built: IntentBuildResponse
 
       switch (mode) {
         case 'cancel': {
           if (!order) throw new Error('No order provided')
-          unsignedTx = await buildCancelOrder(
+          built = await buildCancelOrder(
             order.boxId,
             userErgoTree,
             utxos,
             nodeStatus.chain_height,
           )
-          message = `Cancel SigmaFi order`
           break
         }
         case 'lend': {
           if (!order) throw new Error('No order provided')
-          unsignedTx = await buildCloseOrder(
+          built = await buildCloseOrder(
             order.boxId,
             userErgoTree,
             UI_FEE_ERGO_TREE,
@@ -113,36 +112,39 @@ export function SigmaFiConfirmModal({
             utxos,
             nodeStatus.chain_height,
           )
-          message = `Lend ${formatAmount(order.principal, order.loanTokenDecimals)} ${order.loanTokenName}`
           break
         }
         case 'repay': {
           if (!bond) throw new Error('No bond provided')
-          unsignedTx = await buildRepay(
+          built = await buildRepay(
             bond.boxId,
             bond.loanTokenId,
             userErgoTree,
             utxos,
             nodeStatus.chain_height,
           )
-          message = `Repay ${formatAmount(bond.repayment, bond.loanTokenDecimals)} ${bond.loanTokenName}`
           break
         }
         case 'liquidate': {
           if (!bond) throw new Error('No bond provided')
-          unsignedTx = await buildLiquidate(
+          built = await buildLiquidate(
             bond.boxId,
             userErgoTree,
             utxos,
             nodeStatus.chain_height,
           )
-          message = `Liquidate bond collateral`
           break
         }
       }
 
-      const signResult = await startSign(unsignedTx, message)
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const signResult = await startSign(built.unsignedTx, undefined, built.intent)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        built.unsignedTx,
+        built.intent,
+      )
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -283,6 +285,7 @@ export function SigmaFiConfirmModal({
 
           {step === 'signing' && (
             <div className="sf-signing-step">
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               {flow.signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
@@ -10,6 +10,7 @@ import { formatErg } from '../utils/format'
 import type { RouteQuote } from '../api/router'
 import { formatTokenAmount } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import { useModalStep } from '../hooks/useModalStep'
 import { Button, Modal, Spinner } from './ui'
@@ -92,10 +93,15 @@ export function SmartSwapModal({
         nodeStatus.chain_height,
       )
 
-      const message = `Direct swap ${inputLabel} → ${outputLabel}`
-      const signResult = await startSign(buildResult.unsigned_tx, message)
+      const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
 
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        buildResult.unsigned_tx,
+        buildResult.intent,
+      )
       setStep('signing')
     } catch (e) {
       const errMsg = String(e)
@@ -225,6 +231,7 @@ export function SmartSwapModal({
           {/* Signing Step - Choose Method */}
           {step === 'signing' && flow.signMethod === 'choose' && (
             <div className="mint-signing-step">
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               <p>Choose your signing method</p>
               <div className="wallet-options">
                 <button className="wallet-option" onClick={flow.handleNautilusSign}>
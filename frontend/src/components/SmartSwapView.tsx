@@ -2,6 +2,7 @@ import { useState, useEffect, useRef, useMemo, useCallback } from 'react'
 import { type AmmPool } from '../api/amm'
 import {
   findSwapRoutes,
+  isRouteExecutable,
   type RoutesResponse,
   type RouteQuote,
   type SplitRouteDetail,
@@ -351,6 +352,7 @@ export function SmartSwapView({
     useSplit &&
     split !== null &&
     split.allocations.length >= 2 &&
+    split.allocations.every(a => isRouteExecutable(a.route)) &&
     (() => {
       const seen = new Set<string>()
       for (const a of split.allocations) {
@@ -365,19 +367,21 @@ export function SmartSwapView({
   const canExecute =
     walletAddress !== null &&
     !insufficientBalance &&
-    (useSplit ? splitExecutable : selectedRoute !== null)
+    (useSplit ? splitExecutable : selectedRoute !== null && isRouteExecutable(selectedRoute.route))
 
   const isMultiHop = (selectedRoute?.route.hops.length ?? 0) > 1
 
   const executionBlockReason: string | null = (() => {
     if (useSplit) {
       if (!split) return 'No split available'
+      if (!split.allocations.every(a => isRouteExecutable(a.route))) return 'Split uses a protocol hop (quote only)'
       if (!splitExecutable) return 'Split shares a pool (unsafe)'
       if (!walletAddress) return 'Connect wallet'
       if (insufficientBalance) return 'Insufficient balance'
       return null
     }
     if (!selectedRoute) return null
+    if (!isRouteExecutable(selectedRoute.route)) return 'Route uses a protocol hop (quote only)'
     if (!walletAddress) return 'Connect wallet'
     if (insufficientBalance) return 'Insufficient balance'
     return null
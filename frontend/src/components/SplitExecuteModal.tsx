@@ -5,7 +5,7 @@ import {
   type SplitChainBuild, type ArbChainSubmitResponse,
 } from '../api/arb'
 import { getTxStatus } from '../api/types'
-import type { SplitRouteDetail } from '../api/router'
+import { isRouteExecutable, type SplitRouteDetail } from '../api/router'
 import { formatTokenAmount } from '../utils/format'
 
 interface SplitExecuteModalProps {
@@ -52,6 +52,9 @@ export function SplitExecuteModal({ isOpen, onClose, split, onSuccess }: SplitEx
     setStep('building')
     setError(null)
     try {
+      if (!split.allocations.every(a => isRouteExecutable(a.route))) {
+        throw new Error('Split includes a protocol hop that can only be quoted, not swapped')
+      }
       const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
       const utxos = await invoke<object[]>('get_user_utxos')
       const allocations = split.allocations.map(a => {
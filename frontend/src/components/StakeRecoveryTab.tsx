@@ -16,6 +16,7 @@ import { startSign, getTxStatus } from '../api/types'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import { PageHeader, Card, CardHeader, CardBody, CardFooter, EmptyState, Button, Modal, Input } from './ui'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import './StakeRecoveryTab.css'
 
 interface WalletBalance {
@@ -227,18 +228,21 @@ export function StakeRecoveryTab({
 
       const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
 
-      const unsignedTx = await buildRecoveryTx(
+      const built = await buildRecoveryTx(
         stake.stakeKeyId,
         utxos as object[],
         nodeStatus.chain_height,
       )
 
-      const signResult = await startSign(
-        unsignedTx,
-        `Recover ${stake.rewardAmountDisplay} ${stake.rewardTokenName} from ${stake.protocol} stake key ${stake.stakeKeyId.slice(0, 8)}...`,
-      )
+      const signResult = await startSign(built.unsignedTx, undefined, built.intent)
 
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        built.unsignedTx,
+        built.intent,
+      )
       setRedeemStep('signing')
     } catch (e) {
       setRedeemError(String(e))
@@ -564,6 +568,8 @@ export function StakeRecoveryTab({
                 </div>
               )}
 
+              {redeemStep === 'signing' && flow.intent && <TxIntentSummary intent={flow.intent} />}
+
               {redeemStep === 'signing' && flow.signMethod === 'choose' && (
                 <>
                   <p>Choose your signing method</p>
@@ -5,7 +5,7 @@ import {
   type SwapChainBuild, type ArbChainSubmitResponse,
 } from '../api/arb'
 import { getTxStatus } from '../api/types'
-import type { RouteQuote } from '../api/router'
+import { isRouteExecutable, type RouteQuote } from '../api/router'
 import { formatTokenAmount } from '../utils/format'
 import { Button } from './ui'
 
@@ -52,6 +52,9 @@ export function SwapChainModal({ isOpen, onClose, routeQuote, sourceAmount, onSu
     setStep('building')
     setError(null)
     try {
+      if (!isRouteExecutable(routeQuote.route)) {
+        throw new Error('Route includes a protocol hop that can only be quoted, not swapped')
+      }
       const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
       const utxos = await invoke<object[]>('get_user_utxos')
       const result = await buildSwapChain(
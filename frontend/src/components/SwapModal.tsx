@@ -8,8 +8,10 @@ import {
   type AmmPool, type SwapQuote, type SwapPreviewResponse, type DirectSwapPreviewResponse,
 } from '../api/amm'
 import { startSign, getTxStatus } from '../api/types'
+import type { TxIntent } from '../api/types'
 import { formatTokenAmount, formatErg } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { AdvancedOptions, useRecipientAddress } from './AdvancedOptions'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import { Modal, Button, Spinner, FormField, Input } from './ui'
@@ -151,6 +153,7 @@ export function SwapModal({
       const rawAmount = Math.round(parseFloat(inputAmount) * Math.pow(10, decimals))
 
       let unsignedTx: object
+      let intent: TxIntent
 
       if (swapMode === 'direct') {
         // Parse custom miner fee (ERG → nanoERG). Empty input = default fee.
@@ -174,6 +177,7 @@ export function SwapModal({
           feeNano,
         )
         unsignedTx = buildResult.unsigned_tx
+        intent = buildResult.intent
       } else {
         const executionFeeNano = Math.round(2_000_000 * nitro)
         const buildResult = await buildSwapTx(
@@ -189,16 +193,12 @@ export function SwapModal({
           recipientOrNull,
         )
         unsignedTx = buildResult.unsigned_tx
+        intent = buildResult.intent
       }
 
-      const inputLabel = getInputLabel(pool, inputSide)
-      const outputLabel = getInputLabel(pool, inputSide === 'x' ? 'y' : 'x')
-      const modeLabel = swapMode === 'direct' ? 'Direct swap' : 'Swap'
-      const message = `${modeLabel} ${inputAmount} ${inputLabel} for ${outputLabel} on ${getPoolDisplayName(pool)}`
+      const signResult = await startSign(unsignedTx, undefined, intent)
 
-      const signResult = await startSign(unsignedTx, message)
-
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url, unsignedTx, intent)
       setStep('signing')
     } catch (e) {
       const errMsg = String(e)
@@ -358,6 +358,7 @@ export function SwapModal({
           {/* Signing Step - Choose Method */}
           {step === 'signing' && flow.signMethod === 'choose' && (
             <div className="mint-signing-step">
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               <p>Choose your signing method</p>
               <div className="wallet-options">
                 <button className="wallet-option" onClick={flow.handleNautilusSign}>
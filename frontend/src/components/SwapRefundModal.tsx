@@ -6,9 +6,11 @@ import {
   formatOrderInput, type PendingOrder,
 } from '../api/orders'
 import { startSign, getTxStatus } from '../api/types'
+import type { TxIntent } from '../api/types'
 import { formatErg } from '../utils/format'
 import { useExplorerNav } from '../contexts/ExplorerNavContext'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Modal, Button, Spinner } from './ui'
 
 interface SwapRefundModalProps {
@@ -41,7 +43,7 @@ export function SwapRefundModal({
   const [requestId, setRequestId] = useState<string | null>(null)
   const [txId, setTxId] = useState<string | null>(null)
   const [signMethod, setSignMethod] = useState<SignMethod>('choose')
-  const [refundSummary, setRefundSummary] = useState<{ refundedErg: number; minerFee: number } | null>(null)
+  const [refundSummary, setRefundSummary] = useState<{ refundedErg: number; minerFee: number; intent: TxIntent } | null>(null)
 
   useEffect(() => {
     if (isOpen) {
@@ -106,10 +108,10 @@ export function SwapRefundModal({
       setRefundSummary({
         refundedErg: buildResult.summary.input_amount,
         minerFee: buildResult.summary.miner_fee,
+        intent: buildResult.intent,
       })
 
-      const message = `Refund swap order ${order.txId.slice(0, 8)}...`
-      const signResult = await startSign(buildResult.unsigned_tx, message)
+      const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
 
       setRequestId(signResult.request_id)
       setQrUrl(signResult.ergopay_url)
@@ -198,6 +200,7 @@ export function SwapRefundModal({
 
           {step === 'signing' && signMethod === 'choose' && (
             <div className="mint-signing-step">
+              {refundSummary && <TxIntentSummary intent={refundSummary.intent} />}
               {refundSummary && (
                 <div className="preview-section" style={{ marginBottom: 'var(--space-md)' }}>
                   <div className="preview-row highlight">
@@ -15,6 +15,7 @@ import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import { SwapModal } from './SwapModal'
 import { OrderHistory } from './OrderHistory'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { TokenIcon, PoolPairIcons } from './tokenIcons'
 import { SmartSwapView } from './SmartSwapView'
 import { Tabs, EmptyState } from './ui'
@@ -508,13 +509,14 @@ export function SwapTab({ isConnected, walletAddress, walletBalance, explorerUrl
         )
       }
 
-      const xName = isT2T ? (lpPool.token_x?.name || 'Token X') : 'ERG'
-      const yName = lpPool.token_y.name || 'Token'
-      const signResult = await startSign(
+      const signResult = await startSign(buildResult.unsignedTx, undefined, buildResult.intent)
+      lpFlow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
         buildResult.unsignedTx,
-        `Add liquidity: ${depositErgInput} ${xName} + ${depositTokenInput} ${yName}`
+        buildResult.intent,
       )
-      lpFlow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
       setLpTxStep('signing')
     } catch (e) {
       setLpTxError(String(e))
@@ -551,11 +553,14 @@ export function SwapTab({ isConnected, walletAddress, walletBalance, explorerUrl
         )
       }
 
-      const signResult = await startSign(
+      const signResult = await startSign(buildResult.unsignedTx, undefined, buildResult.intent)
+      lpFlow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
         buildResult.unsignedTx,
-        `Remove liquidity: ${redeemLpInput} LP tokens`
+        buildResult.intent,
       )
-      lpFlow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
       setLpTxStep('signing')
     } catch (e) {
       setLpTxError(String(e))
@@ -639,7 +644,7 @@ export function SwapTab({ isConnected, walletAddress, walletBalance, explorerUrl
       )
 
       // Sign TX0
-      const sign0 = await startSign(tx0.unsignedTx, 'Create pool: mint LP tokens')
+      const sign0 = await startSign(tx0.unsignedTx, undefined, tx0.intent)
       let status0 = await getTxStatus(sign0.request_id)
       while (status0.status === 'pending') {
         await new Promise(r => setTimeout(r, 1500))
@@ -681,7 +686,7 @@ export function SwapTab({ isConnected, walletAddress, walletBalance, explorerUrl
       )
 
       // Sign TX1
-      const sign1 = await startSign(tx1.unsignedTx, 'Create pool: deploy pool')
+      const sign1 = await startSign(tx1.unsignedTx, undefined, tx1.intent)
       let status1 = await getTxStatus(sign1.request_id)
       while (status1.status === 'pending') {
         await new Promise(r => setTimeout(r, 1500))
@@ -1380,6 +1385,7 @@ export function SwapTab({ isConnected, walletAddress, walletBalance, explorerUrl
               {lpTxStep === 'signing' && (
                 <div className="swap-input-section">
                   <div style={{ textAlign: 'center', padding: 'var(--space-md)' }}>
+                    {lpFlow.intent && <TxIntentSummary intent={lpFlow.intent} />}
                     {lpFlow.signMethod === 'choose' && (
                       <div>
                         <p style={{ color: 'var(--slate-400)', marginBottom: 'var(--space-sm)' }}>Choose signing method:</p>
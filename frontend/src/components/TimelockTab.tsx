@@ -15,6 +15,7 @@ import {
 import { formatErg, blocksToTime, truncateAddress } from '../utils/format'
 import { startSign, getTxStatus } from '../api/types'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
 import { EmptyState } from './ui'
 import './TimelockTab.css'
@@ -387,7 +388,7 @@ function CreateLockModal({
       const userUtxos = await invoke<object[]>('get_user_utxos')
       const ergoTree = await invoke<string>('validate_ergo_address', { address: walletAddress })
 
-      const tx = await buildLockTx(
+      const built = await buildLockTx(
         ergoTree,
         ergNano.toString(),
         '[]',
@@ -399,9 +400,15 @@ function CreateLockModal({
         currentHeight,
       )
 
-      const signResult = await startSign(tx, 'Lock assets in MewLock timelock')
+      const signResult = await startSign(built.unsignedTx, undefined, built.intent)
       setStep('signing')
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        built.unsignedTx,
+        built.intent,
+      )
     } catch (e) {
       setError(String(e))
       setStep('error')
@@ -527,6 +534,8 @@ function CreateLockModal({
             </div>
           )}
 
+          {step === 'signing' && flow.intent && <TxIntentSummary intent={flow.intent} />}
+
           {step === 'signing' && flow.signMethod === 'choose' && (
             <div className="tl-sign-choice">
               <p>Choose signing method</p>
@@ -620,16 +629,22 @@ function UnlockModal({
       const userUtxos = await invoke<object[]>('get_user_utxos')
       const ergoTree = await invoke<string>('validate_ergo_address', { address: walletAddress })
 
-      const tx = await buildUnlockTx(
+      const built = await buildUnlockTx(
         lock.boxId,
         ergoTree,
         userUtxos,
         currentHeight,
       )
 
-      const signResult = await startSign(tx, 'Unlock MewLock timelock')
+      const signResult = await startSign(built.unsignedTx, undefined, built.intent)
       setStep('signing')
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      flow.startSigning(
+        signResult.request_id,
+        signResult.ergopay_url,
+        signResult.nautilus_url,
+        built.unsignedTx,
+        built.intent,
+      )
     } catch (e) {
       setError(String(e))
       setStep('error')
@@ -706,6 +721,8 @@ function UnlockModal({
             </div>
           )}
 
+          {step === 'signing' && flow.intent && <TxIntentSummary intent={flow.intent} />}
+
           {step === 'signing' && flow.signMethod === 'choose' && (
             <div className="tl-sign-choice">
               <p>Choose signing method</p>
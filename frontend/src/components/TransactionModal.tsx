@@ -5,8 +5,10 @@ import { formatErg } from '../utils/format'
 import { TxSuccess } from './TxSuccess'
 import { AdvancedOptions, useRecipientAddress } from './AdvancedOptions'
 import { useTransactionFlow } from '../hooks/useTransactionFlow'
+import type { SigningRequest } from '../hooks/useTransactionFlow'
 import { DEV_FEE_NANO, TX_FEE_NANO } from '../constants'
-import type { TxStatusResponse } from '../api/types'
+import { startSign, type TxIntent, type TxStatusResponse } from '../api/types'
+import { TxIntentSummary } from './TxIntentSummary'
 import { Modal, Button, Spinner } from './ui'
 import '../components/DexyMintModal.css'
 
@@ -94,6 +96,7 @@ export function TransactionModal({
   const [lastEdited, setLastEdited] = useState<'erg' | 'token'>('token')
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
+  const [oracleWarnings, setOracleWarnings] = useState<string[]>([])
 
   const flow = useTransactionFlow({
     pollStatus: pollMintStatus,
@@ -101,6 +104,7 @@ export function TransactionModal({
     onSuccess: () => setStep('success'),
     onError: (err) => { setError(err); setStep('error') },
     watchParams: { protocol: 'SigmaUSD', operation: config.isRedeem ? 'redeem' : 'mint', description: `${config.title}` },
+    rebuild: () => buildSigningRequest(true),
   })
 
   // Token price in nanoERG per 1 display unit
@@ -316,6 +320,35 @@ export function TransactionModal({
     }
   }
 
+  // Build + start signing; also the auto-rebuild when a competing tx spends the bank box
+  const buildSigningRequest = async (buildOnPending: boolean): Promise<SigningRequest> => {
+    const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
+    const utxos = await invoke<object[]>('get_user_utxos')
+
+    const buildResult = await invoke<{ unsigned_tx: object; summary: object; intent: TxIntent; oracle_warnings?: string[] }>('build_sigmausd_tx', {
+      request: {
+        action,
+        amount: calculated.tokenAmountRaw,
+        user_address: walletAddress,
+        user_utxos: utxos,
+        current_height: nodeStatus.chain_height,
+        recipient_address: recipientOrNull,
+        build_on_pending: buildOnPending,
+      }
+    })
+    setOracleWarnings(buildResult.oracle_warnings ?? [])
+
+    const signResult = await startSign(buildResult.unsigned_tx, undefined, buildResult.intent)
+
+    return {
+      unsignedTx: buildResult.unsigned_tx,
+      requestId: signResult.request_id,
+      qrUrl: signResult.ergopay_url,
+      nautilusUrl: signResult.nautilus_url,
+      intent: buildResult.intent,
+    }
+  }
+
   const handleSign = async () => {
     if (!calculated.isValid || !calculated.tokenAmountRaw) return
 
@@ -323,28 +356,8 @@ export function TransactionModal({
     setError(null)
 
     try {
-      const nodeStatus = await invoke<{ chain_height: number }>('get_node_status')
-      const utxos = await invoke<object[]>('get_user_utxos')
-
-      const buildResult = await invoke<{ unsigned_tx: object; summary: object }>('build_sigmausd_tx', {
-        request: {
-          action,
-          amount: calculated.tokenAmountRaw,
-          user_address: walletAddress,
-          user_utxos: utxos,
-          current_height: nodeStatus.chain_height,
-          recipient_address: recipientOrNull,
-        }
-      })
-
-      const signResult = await invoke<{ request_id: string; ergopay_url: string; nautilus_url: string }>('start_mint_sign', {
-        request: {
-          unsigned_tx: buildResult.unsigned_tx,
-          message: `${config.title}: ${tokenInput} ${config.tokenName}`
-        }
-      })
-
-      flow.startSigning(signResult.request_id, signResult.ergopay_url, signResult.nautilus_url)
+      const req = await buildSigningRequest(false)
+      flow.startSigning(req.requestId, req.qrUrl, req.nautilusUrl, req.unsignedTx, req.intent)
       setStep('signing')
     } catch (e) {
       setError(String(e))
@@ -485,6 +498,15 @@ export function TransactionModal({
 
           {step === 'signing' && (
             <div className="mint-signing-step">
+              {flow.rebuildCount > 0 && (
+                <div className="warning-box">
+                  The bank box was spent by another transaction. Rebuilt on the latest bank state; please sign again.
+                </div>
+              )}
+              {oracleWarnings.map((w) => (
+                <div key={w} className="warning-box">Oracle: {w}</div>
+              ))}
+              {flow.intent && <TxIntentSummary intent={flow.intent} />}
               {flow.signMethod === 'choose' && (
                 <div className="sign-method-choice">
                   <p>Choose signing method:</p>
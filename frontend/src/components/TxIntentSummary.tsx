@@ -0,0 +1,91 @@
+import type { IntentAsset, TxIntent } from '../api/types'
+import { formatErg, formatTokenAmount, truncateAddress } from '../utils/format'
+
+const ERG_ASSET_ID = 'ERG'
+
+function assetLabel(asset: IntentAsset): string {
+  if (asset.token_id === ERG_ASSET_ID) return `${formatErg(asset.amount, 2, 9)} ERG`
+  const amount = asset.decimals != null
+    ? formatTokenAmount(asset.amount, asset.decimals)
+    : asset.amount.toLocaleString()
+  return `${amount} ${asset.name ?? truncateAddress(asset.token_id, 6)}`
+}
+
+function joinAssets(assets: IntentAsset[]): string {
+  return assets.map(assetLabel).join(' + ')
+}
+
+function capitalize(s: string): string {
+  return s.charAt(0).toUpperCase() + s.slice(1)
+}
+
+/**
+ * What the transaction being signed does, as described by its builder:
+ * flows in and out of the wallet, fees, counterparties and notes.
+ */
+export function TxIntentSummary({ intent }: { intent: TxIntent }) {
+  const { fees, slippage } = intent
+
+  return (
+    <div className="preview-section tx-intent-summary">
+      <div className="preview-row highlight">
+        <span>{intent.protocol}</span>
+        <span>{intent.action}</span>
+      </div>
+      {intent.assets_in.length > 0 && (
+        <div className="preview-row">
+          <span>You send</span>
+          <span>{joinAssets(intent.assets_in)}</span>
+        </div>
+      )}
+      {intent.assets_out.length > 0 && (
+        <div className="preview-row">
+          <span>You receive</span>
+          <span>{joinAssets(intent.assets_out)}</span>
+        </div>
+      )}
+      {slippage?.min_out && (
+        <div className="preview-row">
+          <span>Minimum received</span>
+          <span>
+            {assetLabel(slippage.min_out)}
+            {slippage.tolerance_pct != null && ` (${slippage.tolerance_pct}% slippage)`}
+          </span>
+        </div>
+      )}
+      {slippage?.max_in && (
+        <div className="preview-row">
+          <span>Maximum spent</span>
+          <span>{assetLabel(slippage.max_in)}</span>
+        </div>
+      )}
+      {fees.miner_nano > 0 && (
+        <div className="preview-row">
+          <span>Miner fee</span>
+          <span>{formatErg(fees.miner_nano, 2, 9)} ERG</span>
+        </div>
+      )}
+      {fees.protocol_nano > 0 && (
+        <div className="preview-row">
+          <span>Protocol fee</span>
+          <span>{formatErg(fees.protocol_nano, 2, 9)} ERG</span>
+        </div>
+      )}
+      {fees.citadel_nano > 0 && (
+        <div className="preview-row">
+          <span>Citadel fee</span>
+          <span>{formatErg(fees.citadel_nano, 2, 9)} ERG</span>
+        </div>
+      )}
+      {intent.counterparties.map((c, i) => (
+        <div className="preview-row" key={`${c.role}-${i}`}>
+          <span>{capitalize(c.role)}</span>
+          <span title={c.address ?? undefined}>{c.name}</span>
+        </div>
+      ))}
+      {intent.notes.map((note, i) => (
+        <p className="tx-intent-note" key={i}>{note}</p>
+      ))}
+    </div>
+  )
+}
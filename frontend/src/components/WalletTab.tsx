@@ -21,6 +21,7 @@ import { getBox, type TokenInfo } from '../api/explorer'
 import { MIN_BOX_VALUE_NANO, TX_FEE_NANO } from '../constants'
 import { Tabs, EmptyState } from './ui'
 import { TxSuccess } from './TxSuccess'
+import { TxIntentSummary } from './TxIntentSummary'
 import { UtxoManagementTab } from './UtxoManagementTab'
 import { BurnTab } from './BurnTab'
 import './WalletTab.css'
@@ -411,11 +412,7 @@ export function WalletTab({
 
       setSendSummary(result)
 
-      const msg =
-        assetKind === 'token' && selectedToken
-          ? `Send ${amount} ${selectedToken.name || 'token'}`
-          : `Send ${amount} ERG`
-      const signResult = await startSign(result.unsignedTx, msg)
+      const signResult = await startSign(result.unsignedTx, undefined, result.intent)
 
       setRequestId(signResult.request_id)
       setQrUrl(signResult.ergopay_url)
@@ -928,6 +925,7 @@ export function WalletTab({
               {signMethod === 'choose' && (
                 <>
                   <h2 className="wallet-section-title">Sign transaction</h2>
+                  {sendSummary && <TxIntentSummary intent={sendSummary.intent} />}
                   {sendSummary && (
                     <p className="wallet-muted">
                       {sendSummary.inputCount} input{sendSummary.inputCount !== 1 ? 's' : ''} · fee{' '}
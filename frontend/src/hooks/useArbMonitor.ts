@@ -0,0 +1,43 @@
+import { useState, useEffect, useCallback, useRef } from 'react'
+import { listen } from '@tauri-apps/api/event'
+import { sendNotification } from '@tauri-apps/plugin-notification'
+import type { ArbAlert } from '../api/arb'
+
+/** Alerts kept for the Arb Scanner (newest first). */
+const MAX_ALERTS = 50
+
+/**
+ * Collects `arb-opportunity` events from the background arb monitor. Mounted
+ * at the app root so alerts arrive (and notify) whichever tab is open.
+ */
+export function useArbMonitor() {
+  const [alerts, setAlerts] = useState<ArbAlert[]>([])
+  // Alerts arrive best first, so only the first of each block notifies
+  const notifiedHeight = useRef(0)
+
+  useEffect(() => {
+    const unlisten = listen<ArbAlert>('arb-opportunity', (event) => {
+      const alert = event.payload
+      setAlerts((prev) => [alert, ...prev].slice(0, MAX_ALERTS))
+      if (alert.height === notifiedHeight.current) return
+      notifiedHeight.current = alert.height
+
+      try {
+        sendNotification({
+          title: `Arb at block ${alert.height}`,
+          body: `${alert.path_label}: +${(alert.net_profit_nano / 1e9).toFixed(4)} ERG`,
+        })
+      } catch {
+        // Non-critical — OS notifications may not be available
+      }
+    })
+
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [])
+
+  const clearAlerts = useCallback(() => setAlerts([]), [])
+
+  return { alerts, clearAlerts }
+}
@@ -1,7 +1,8 @@
 import { useState, useEffect, useCallback, useRef } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import type { TxStatusResponse } from '../api/types'
+import type { TxIntent, TxStatusResponse } from '../api/types'
 import { watchTx } from '../api/notifications'
+import { checkTxInputs } from '../api/contention'
 
 export type SignMethod = 'choose' | 'nautilus' | 'mobile'
 
@@ -11,21 +12,46 @@ export interface WatchParams {
   description: string
 }
 
+/** A freshly built tx with its signing request. */
+export interface SigningRequest {
+  unsignedTx: object
+  requestId: string
+  qrUrl: string
+  nautilusUrl: string
+  /** Shown on the signing step when the builder describes its tx */
+  intent?: TxIntent
+}
+
 interface UseTransactionFlowOptions {
   pollStatus: (requestId: string) => Promise<TxStatusResponse>
   isOpen: boolean
   onSuccess?: (txId: string) => void
   onError?: (error: string) => void
   watchParams?: WatchParams
+  /**
+   * Rebuild with unchanged user parameters and request signing again. When
+   * set, a tx passed to startSigning is checked for inputs spent by a competing
+   * tx (another user's bank/LP spend) and replaced automatically.
+   */
+  rebuild?: () => Promise<SigningRequest>
 }
 
-export function useTransactionFlow({ pollStatus, isOpen, onSuccess, onError, watchParams }: UseTransactionFlowOptions) {
+/** Input-spent checks are cheaper than status polls but hit the mempool. */
+const CONTENTION_CHECK_MS = 6000
+/** Give up after this many automatic rebuilds in one signing session. */
+const MAX_AUTO_REBUILDS = 3
+
+export function useTransactionFlow({ pollStatus, isOpen, onSuccess, onError, watchParams, rebuild }: UseTransactionFlowOptions) {
   const [requestId, setRequestId] = useState<string | null>(null)
   const [qrUrl, setQrUrl] = useState<string | null>(null)
   const [nautilusUrl, setNautilusUrl] = useState<string | null>(null)
   const [signMethod, setSignMethod] = useState<SignMethod>('choose')
   const [txId, setTxId] = useState<string | null>(null)
   const [isSigning, setIsSigning] = useState(false)
+  const [unsignedTx, setUnsignedTx] = useState<object | null>(null)
+  const [intent, setIntent] = useState<TxIntent | null>(null)
+  const [rebuildCount, setRebuildCount] = useState(0)
+  const [isRebuilding, setIsRebuilding] = useState(false)
 
   const reset = useCallback(() => {
     setRequestId(null)
@@ -34,6 +60,10 @@ export function useTransactionFlow({ pollStatus, isOpen, onSuccess, onError, wat
     setSignMethod('choose')
     setTxId(null)
     setIsSigning(false)
+    setUnsignedTx(null)
+    setIntent(null)
+    setRebuildCount(0)
+    setIsRebuilding(false)
   }, [])
 
   // Reset when modal opens
@@ -49,10 +79,14 @@ export function useTransactionFlow({ pollStatus, isOpen, onSuccess, onError, wat
   const onSuccessRef = useRef(onSuccess)
   const onErrorRef = useRef(onError)
   const watchParamsRef = useRef(watchParams)
+  const rebuildRef = useRef(rebuild)
+  const rebuildCountRef = useRef(0)
   useEffect(() => { pollStatusRef.current = pollStatus }, [pollStatus])
   useEffect(() => { onSuccessRef.current = onSuccess }, [onSuccess])
   useEffect(() => { onErrorRef.current = onError }, [onError])
   useEffect(() => { watchParamsRef.current = watchParams }, [watchParams])
+  useEffect(() => { rebuildRef.current = rebuild }, [rebuild])
+  useEffect(() => { rebuildCountRef.current = rebuildCount }, [rebuildCount])
 
   // Poll for tx status while signing
   useEffect(() => {
@@ -88,10 +122,61 @@ export function useTransactionFlow({ pollStatus, isOpen, onSuccess, onError, wat
     }
   }, [isSigning, requestId])
 
-  const startSigning = useCallback((rid: string, qr: string, naut: string) => {
+  // While waiting for a signature, watch the built tx's inputs. A competing
+  // spend of the bank/LP box makes it unacceptable, so rebuild on the pending
+  // successor and swap in the new signing request.
+  useEffect(() => {
+    if (!isSigning || !unsignedTx || !rebuildRef.current) return
+
+    let active = true
+    let checking = false
+    const interval = setInterval(async () => {
+      if (checking) return
+      checking = true
+      try {
+        const check = await checkTxInputs(unsignedTx)
+        if (!active || !check.invalidated || check.submitted_tx_id) return
+        const rebuildTx = rebuildRef.current
+        if (!rebuildTx) return
+
+        if (rebuildCountRef.current >= MAX_AUTO_REBUILDS) {
+          setIsSigning(false)
+          onErrorRef.current?.('Protocol box keeps being spent by other transactions. Please try again.')
+          return
+        }
+
+        setIsRebuilding(true)
+        const next = await rebuildTx()
+        if (!active) return
+        setRequestId(next.requestId)
+        setQrUrl(next.qrUrl)
+        setNautilusUrl(next.nautilusUrl)
+        setUnsignedTx(next.unsignedTx)
+        setIntent(next.intent ?? null)
+        setRebuildCount((c) => c + 1)
+        // The old request is dead; the user signs the rebuilt one
+        setSignMethod('choose')
+      } catch (e) {
+        console.error('Contention check error:', e)
+      } finally {
+        checking = false
+        if (active) setIsRebuilding(false)
+      }
+    }, CONTENTION_CHECK_MS)
+
+    return () => {
+      active = false
+      clearInterval(interval)
+    }
+  }, [isSigning, unsignedTx])
+
+  const startSigning = useCallback((rid: string, qr: string, naut: string, tx?: object, txIntent?: TxIntent) => {
     setRequestId(rid)
     setQrUrl(qr)
     setNautilusUrl(naut)
+    setUnsignedTx(tx ?? null)
+    setIntent(txIntent ?? null)
+    setRebuildCount(0)
     setIsSigning(true)
     setSignMethod('choose')
   }, [])
@@ -121,6 +206,9 @@ export function useTransactionFlow({ pollStatus, isOpen, onSuccess, onError, wat
     signMethod,
     txId,
     isSigning,
+    intent,
+    rebuildCount,
+    isRebuilding,
     startSigning,
     handleNautilusSign,
     handleMobileSign,